@@ -9,9 +9,15 @@
 //!
 //! These are pure functions that operate on `Vec<Message>`. The caller
 //! is responsible for obtaining any LLM-generated summaries before
-//! calling `summarize_messages`.
+//! calling `summarize_messages`. [`compact_session`] does both steps: it
+//! asks the provider for a summary and folds it into the session.
 
-use crate::session::{Message, Role};
+use crate::error::{Result, ZeptoError};
+use crate::providers::{ChatOptions, LLMProvider};
+use crate::session::{Message, Role, Session};
+
+/// Prefix of the synthetic system message that holds a conversation summary.
+pub const SUMMARY_PREFIX: &str = "[Conversation Summary]";
 
 /// Truncate messages to keep only the N most recent.
 ///
@@ -119,8 +125,8 @@ pub fn summarize_messages(
 ) -> Vec<Message> {
     if messages.is_empty() {
         return vec![Message::system(&format!(
-            "[Conversation Summary]\n{}",
-            summary_text
+            "{}\n{}",
+            SUMMARY_PREFIX, summary_text
         ))];
     }
 
@@ -134,7 +140,7 @@ pub fn summarize_messages(
         .map(|m| m.role == Role::System)
         .unwrap_or(false);
 
-    let summary_msg = Message::system(&format!("[Conversation Summary]\n{}", summary_text));
+    let summary_msg = Message::system(&format!("{}\n{}", SUMMARY_PREFIX, summary_text));
 
     if has_system_prefix {
         let total = messages.len();
@@ -384,6 +390,91 @@ pub fn build_summary_prompt(messages: &[Message]) -> String {
     )
 }
 
+/// Whether `msg` is a summary produced by [`summarize_messages`].
+pub fn is_summary_message(msg: &Message) -> bool {
+    msg.role == Role::System && msg.content.starts_with(SUMMARY_PREFIX)
+}
+
+/// Find the range of messages a summarizing compaction would replace.
+///
+/// The range excludes a leading system prompt, an existing summary message
+/// and the `keep_recent` newest messages. The kept tail never starts with a
+/// tool result, so a tool call is never separated from its result. Returns
+/// `None` when there is nothing older than the kept tail to summarize, which
+/// is also what stops a freshly compacted session from being compacted again.
+fn summarizable_range(messages: &[Message], keep_recent: usize) -> Option<(usize, usize)> {
+    let mut start = 0;
+    if messages
+        .first()
+        .is_some_and(|m| m.role == Role::System && !is_summary_message(m))
+    {
+        start += 1;
+    }
+    if messages.get(start).is_some_and(is_summary_message) {
+        start += 1;
+    }
+
+    let mut end = messages.len().saturating_sub(keep_recent).max(start);
+    while end > start && messages.get(end).is_some_and(Message::is_tool_result) {
+        end -= 1;
+    }
+    (end > start).then_some((start, end))
+}
+
+/// Summarize the older part of `session` with `provider`, keeping the
+/// `keep_recent` newest messages verbatim.
+///
+/// Older messages (and any previous summary) are replaced by a single
+/// [`SUMMARY_PREFIX`] system message, and the summary text is stored in
+/// `session.summary`. Returns `Ok(false)` without calling the provider when
+/// there is nothing to summarize. On error the session is left unchanged.
+pub async fn compact_session(
+    session: &mut Session,
+    provider: &dyn LLMProvider,
+    model: Option<&str>,
+    keep_recent: usize,
+) -> Result<bool> {
+    let Some((start, end)) = summarizable_range(&session.messages, keep_recent) else {
+        return Ok(false);
+    };
+
+    let mut prompt = build_summary_prompt(&session.messages[start..end]);
+    if let Some(previous) = session.messages[..start]
+        .iter()
+        .find(|m| is_summary_message(m))
+    {
+        let previous = previous.content[SUMMARY_PREFIX.len()..].trim();
+        prompt = format!(
+            "Earlier parts of the conversation were summarized as:\n{}\n\n\
+             Fold that summary into the new one.\n\n{}",
+            previous, prompt
+        );
+    }
+
+    let options = ChatOptions::new()
+        .with_max_tokens(1024)
+        .with_temperature(0.0);
+    let response = provider
+        .chat(vec![Message::user(&prompt)], vec![], model, options)
+        .await?;
+    let summary = response.content.trim();
+    if summary.is_empty() {
+        return Err(ZeptoError::Provider(
+            "Provider returned an empty conversation summary".into(),
+        ));
+    }
+
+    let mut compacted = Vec::with_capacity(2 + session.messages.len() - end);
+    if start > 0 && !is_summary_message(&session.messages[0]) {
+        compacted.push(session.messages[0].clone());
+    }
+    compacted.push(Message::system(&format!("{}\n{}", SUMMARY_PREFIX, summary)));
+    compacted.extend(session.messages.drain(end..));
+    session.messages = compacted;
+    session.set_summary(summary);
+    Ok(true)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -770,4 +861,193 @@ mod tests {
             estimated
         );
     }
+
+    // ── compact_session ────────────────────────────────────────────────
+
+    struct SummaryProvider {
+        reply: &'static str,
+        prompts: std::sync::Mutex<Vec<String>>,
+    }
+
+    impl SummaryProvider {
+        fn new(reply: &'static str) -> Self {
+            Self {
+                reply,
+                prompts: std::sync::Mutex::new(Vec::new()),
+            }
+        }
+
+        fn calls(&self) -> Vec<String> {
+            self.prompts.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl LLMProvider for SummaryProvider {
+        async fn chat(
+            &self,
+            messages: Vec<Message>,
+            _tools: Vec<crate::providers::ToolDefinition>,
+            _model: Option<&str>,
+            _options: ChatOptions,
+        ) -> Result<crate::providers::LLMResponse> {
+            self.prompts
+                .lock()
+                .unwrap()
+                .push(messages[0].content.clone());
+            Ok(crate::providers::LLMResponse::text(self.reply))
+        }
+
+        fn default_model(&self) -> &str {
+            "fake"
+        }
+
+        fn name(&self) -> &str {
+            "fake"
+        }
+    }
+
+    fn session_with(messages: Vec<Message>) -> Session {
+        let mut session = Session::new("test");
+        session.messages = messages;
+        session
+    }
+
+    fn numbered(n: usize) -> Vec<Message> {
+        (0..n)
+            .map(|i| {
+                if i % 2 == 0 {
+                    Message::user(&format!("question {}", i))
+                } else {
+                    Message::assistant(&format!("answer {}", i))
+                }
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_compact_session_keeps_last_n_verbatim() {
+        let provider = SummaryProvider::new("Talked about numbers.");
+        let mut session = session_with(numbered(6));
+
+        assert!(compact_session(&mut session, &provider, None, 2)
+            .await
+            .unwrap());
+
+        assert_eq!(session.messages.len(), 3);
+        assert!(is_summary_message(&session.messages[0]));
+        assert!(session.messages[0]
+            .content
+            .ends_with("Talked about numbers."));
+        assert_eq!(session.messages[1].content, "question 4");
+        assert_eq!(session.messages[2].content, "answer 5");
+        assert_eq!(session.summary.as_deref(), Some("Talked about numbers."));
+
+        let prompt = &provider.calls()[0];
+        assert!(prompt.contains("question 0"));
+        assert!(prompt.contains("answer 3"));
+        assert!(!prompt.contains("question 4"));
+    }
+
+    #[tokio::test]
+    async fn test_compact_session_nothing_to_summarize() {
+        let provider = SummaryProvider::new("unused");
+
+        let mut empty = session_with(Vec::new());
+        assert!(!compact_session(&mut empty, &provider, None, 4)
+            .await
+            .unwrap());
+        assert!(empty.messages.is_empty());
+
+        // Exactly keep_recent messages: everything is recent.
+        let mut short = session_with(numbered(4));
+        assert!(!compact_session(&mut short, &provider, None, 4)
+            .await
+            .unwrap());
+        assert_eq!(short.messages.len(), 4);
+        assert!(short.summary.is_none());
+        assert!(provider.calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_compact_session_is_not_rerun_on_compacted_session() {
+        let provider = SummaryProvider::new("First summary.");
+        let mut session = session_with(numbered(5));
+        assert!(compact_session(&mut session, &provider, None, 2)
+            .await
+            .unwrap());
+
+        // Summary + kept tail: nothing older left to summarize.
+        assert!(!compact_session(&mut session, &provider, None, 2)
+            .await
+            .unwrap());
+        assert_eq!(provider.calls().len(), 1);
+
+        // Once the session grows, the previous summary is folded in.
+        session.add_message(Message::user("new question"));
+        session.add_message(Message::assistant("new answer"));
+        assert!(compact_session(&mut session, &provider, None, 2)
+            .await
+            .unwrap());
+        assert_eq!(session.messages.len(), 3);
+        assert_eq!(
+            session
+                .messages
+                .iter()
+                .filter(|m| is_summary_message(m))
+                .count(),
+            1
+        );
+        assert!(provider.calls()[1].contains("First summary."));
+    }
+
+    #[tokio::test]
+    async fn test_compact_session_does_not_split_tool_results() {
+        let provider = SummaryProvider::new("Ran a tool.");
+        let mut session = session_with(vec![
+            Message::user("list files"),
+            Message::assistant_with_tools(
+                "",
+                vec![crate::session::ToolCall::new("call_1", "list_dir", "{}")],
+            ),
+            Message::tool_result("call_1", "a.txt"),
+            Message::assistant("There is a.txt"),
+        ]);
+
+        // keep_recent = 2 would start the tail at the tool result; the
+        // assistant tool call is kept with it.
+        assert!(compact_session(&mut session, &provider, None, 2)
+            .await
+            .unwrap());
+        assert_eq!(session.messages.len(), 4);
+        assert!(session.messages[1].has_tool_calls());
+        assert!(session.messages[2].is_tool_result());
+    }
+
+    #[tokio::test]
+    async fn test_compact_session_preserves_system_prompt() {
+        let provider = SummaryProvider::new("Summary.");
+        let mut messages = vec![Message::system("You are helpful.")];
+        messages.extend(numbered(4));
+        let mut session = session_with(messages);
+
+        assert!(compact_session(&mut session, &provider, None, 1)
+            .await
+            .unwrap());
+        assert_eq!(session.messages.len(), 3);
+        assert_eq!(session.messages[0].content, "You are helpful.");
+        assert!(is_summary_message(&session.messages[1]));
+        assert_eq!(session.messages[2].content, "answer 3");
+    }
+
+    #[tokio::test]
+    async fn test_compact_session_empty_summary_leaves_session() {
+        let provider = SummaryProvider::new("   ");
+        let mut session = session_with(numbered(6));
+        assert!(compact_session(&mut session, &provider, None, 2)
+            .await
+            .is_err());
+        assert_eq!(session.messages.len(), 6);
+        assert!(session.summary.is_none());
+    }
 }
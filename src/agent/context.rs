@@ -4,9 +4,15 @@
 //! and message history for LLM conversations. It also provides `RuntimeContext`
 //! for injecting environment-awareness into the agent's system prompt.
 
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
 use chrono::Local;
 
-use crate::session::Message;
+use crate::config::templates::{builtin_prompt_vars, render_prompt};
+use crate::session::{Message, Role};
+use crate::skills::SkillRegistry;
 
 /// Format a timestamp envelope for a user message.
 ///
@@ -229,6 +235,13 @@ pub struct ContextBuilder {
     runtime_context: Option<RuntimeContext>,
     /// Optional memory context to append to system prompt
     memory_context: Option<String>,
+    /// Variables for `{{key}}` placeholders in the system prompt; `None`
+    /// leaves the prompt unrendered
+    prompt_vars: Option<HashMap<String, String>>,
+    /// Workspace exposed as the built-in `{{workspace}}` variable
+    prompt_workspace: Option<PathBuf>,
+    /// Keyword-triggered skills added when the user message matches
+    skill_registry: Option<Arc<SkillRegistry>>,
 }
 
 impl ContextBuilder {
@@ -249,6 +262,9 @@ impl ContextBuilder {
             skills_prompt: None,
             runtime_context: None,
             memory_context: None,
+            prompt_vars: None,
+            prompt_workspace: None,
+            skill_registry: None,
         }
     }
 
@@ -371,6 +387,45 @@ impl ContextBuilder {
         self
     }
 
+    /// Render `{{key}}` placeholders in the system prompt.
+    ///
+    /// The built-in variables (`date`, and `workspace` when given) are
+    /// computed each time the system message is built, so `{{date}}` stays
+    /// current in long-running sessions. Entries in `vars` override them.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use zeptoclaw::agent::ContextBuilder;
+    ///
+    /// let vars = HashMap::from([("user_name".to_string(), "Alice".to_string())]);
+    /// let builder = ContextBuilder::new()
+    ///     .with_system_prompt("You are helping {{user_name}}.")
+    ///     .with_prompt_vars(None, vars);
+    /// let system = builder.build_system_message();
+    /// assert!(system.content.contains("helping Alice."));
+    /// ```
+    pub fn with_prompt_vars(
+        mut self,
+        workspace: Option<&Path>,
+        vars: HashMap<String, String>,
+    ) -> Self {
+        self.prompt_workspace = workspace.map(Path::to_path_buf);
+        self.prompt_vars = Some(vars);
+        self
+    }
+
+    /// Add instructions of keyword-triggered skills to the system prompt.
+    ///
+    /// [`build_messages`](Self::build_messages) matches the new user input
+    /// (or, when empty, the latest user message in the history) against the
+    /// registry and appends the instructions of matching skills under a
+    /// "Relevant Skills" section.
+    pub fn with_skill_registry(mut self, registry: Arc<SkillRegistry>) -> Self {
+        self.skill_registry = Some(registry);
+        self
+    }
+
     /// Build the system message with all configured content.
     ///
     /// # Returns
@@ -391,7 +446,14 @@ impl ContextBuilder {
             content.push_str(soul);
             content.push_str("\n\n");
         }
-        content.push_str(&self.system_prompt);
+        match self.prompt_vars {
+            Some(ref vars) => {
+                let mut all = builtin_prompt_vars(self.prompt_workspace.as_deref());
+                all.extend(vars.iter().map(|(k, v)| (k.clone(), v.clone())));
+                content.push_str(&render_prompt(&self.system_prompt, &all));
+            }
+            None => content.push_str(&self.system_prompt),
+        }
         if let Some(ref skills) = self.skills_prompt {
             content.push_str("\n\n## Available Skills\n\n");
             content.push_str(skills);
@@ -437,7 +499,24 @@ impl ContextBuilder {
     /// assert_eq!(messages.len(), 4); // system + 2 history + new user
     /// ```
     pub fn build_messages(&self, history: &[Message], user_input: &str) -> Vec<Message> {
-        let mut messages = vec![self.build_system_message()];
+        let mut system = self.build_system_message();
+        if let Some(ref registry) = self.skill_registry {
+            let query = if user_input.is_empty() {
+                history
+                    .iter()
+                    .rev()
+                    .find(|m| m.role == Role::User)
+                    .map_or("", |m| m.content.as_str())
+            } else {
+                user_input
+            };
+            let matched = registry.build_matched_context(query);
+            if !matched.is_empty() {
+                system.content.push_str("\n\n## Relevant Skills\n\n");
+                system.content.push_str(&matched);
+            }
+        }
+        let mut messages = vec![system];
         messages.extend(history.iter().cloned());
         if !user_input.is_empty() {
             // Prepend timestamp envelope to user message so the LLM knows
@@ -497,6 +576,28 @@ mod tests {
         assert!(builder.system_prompt().contains("ZeptoClaw"));
     }
 
+    #[test]
+    fn test_context_builder_prompt_vars() {
+        let vars = HashMap::from([("user_name".to_string(), "Ana".to_string())]);
+        let builder = ContextBuilder::new()
+            .with_system_prompt("{{user_name}} on {{date}} in {{workspace}}, {{unknown}}")
+            .with_prompt_vars(Some(Path::new("/ws")), vars);
+        let system = builder.build_system_message();
+        let today = Local::now().format("%Y-%m-%d").to_string();
+        assert_eq!(
+            system.content,
+            format!("Ana on {} in /ws, {{{{unknown}}}}", today)
+        );
+        // The raw prompt is kept for inspection.
+        assert!(builder.system_prompt().starts_with("{{user_name}}"));
+    }
+
+    #[test]
+    fn test_context_builder_without_prompt_vars_keeps_placeholders() {
+        let builder = ContextBuilder::new().with_system_prompt("Today is {{date}}");
+        assert_eq!(builder.build_system_message().content, "Today is {{date}}");
+    }
+
     #[test]
     fn test_context_builder_custom_system_prompt() {
         let builder = ContextBuilder::new().with_system_prompt("Custom prompt here");
@@ -513,6 +614,37 @@ mod tests {
         assert!(system.content.contains("/test"));
     }
 
+    #[test]
+    fn test_context_builder_injects_matching_skills() {
+        use crate::skills::{Skill, SkillMetadata};
+
+        let skill = Skill {
+            name: "weather".to_string(),
+            description: "Weather helper".to_string(),
+            path: "/skills/weather/skill.json".to_string(),
+            source: "workspace".to_string(),
+            metadata: SkillMetadata {
+                triggers: vec!["forecast".to_string()],
+                ..Default::default()
+            },
+            content: "Use wttr.in for forecasts.".to_string(),
+        };
+        let builder =
+            ContextBuilder::new().with_skill_registry(Arc::new(SkillRegistry::new(vec![skill])));
+
+        let messages = builder.build_messages(&[], "What's the forecast?");
+        assert!(messages[0].content.contains("## Relevant Skills"));
+        assert!(messages[0].content.contains("Use wttr.in"));
+
+        // Later iterations pass no input; the last user message still matches.
+        let history = vec![Message::user("forecast please"), Message::assistant("ok")];
+        let messages = builder.build_messages(&history, "");
+        assert!(messages[0].content.contains("Use wttr.in"));
+
+        let messages = builder.build_messages(&[], "Hello");
+        assert!(!messages[0].content.contains("Relevant Skills"));
+    }
+
     #[test]
     fn test_build_system_message() {
         let builder = ContextBuilder::new();
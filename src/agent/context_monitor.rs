@@ -93,8 +93,18 @@ impl ContextMonitor {
     /// # Returns
     /// `true` if estimated tokens exceed `threshold * context_limit`.
     pub fn needs_compaction(&self, messages: &[Message]) -> bool {
-        let estimated = Self::estimate_tokens(messages);
-        estimated as f64 > self.threshold * self.context_limit as f64
+        self.exceeds_threshold(Self::estimate_tokens(messages))
+    }
+
+    /// Check whether an already-estimated token count exceeds the
+    /// compaction threshold (e.g. from [`crate::session::Session::estimated_tokens_with`]).
+    pub fn exceeds_threshold(&self, estimated_tokens: usize) -> bool {
+        estimated_tokens as f64 > self.threshold * self.context_limit as f64
+    }
+
+    /// Maximum token capacity this monitor was configured with.
+    pub fn context_limit(&self) -> usize {
+        self.context_limit
     }
 
     /// Suggest a compaction strategy based on current context fullness.
@@ -212,6 +222,14 @@ mod tests {
         assert!(monitor.needs_compaction(&messages));
     }
 
+    #[test]
+    fn test_exceeds_threshold_boundary() {
+        let monitor = ContextMonitor::new(100, 0.80);
+        assert!(!monitor.exceeds_threshold(80));
+        assert!(monitor.exceeds_threshold(81));
+        assert_eq!(monitor.context_limit(), 100);
+    }
+
     // --- suggest_strategy tests ---
 
     #[test]
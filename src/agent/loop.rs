@@ -7,23 +7,31 @@ use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
-use tokio::sync::{watch, Mutex, RwLock};
-use tracing::{debug, error, info, info_span, Instrument};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{watch, Mutex, OwnedMutexGuard, OwnedSemaphorePermit, RwLock, Semaphore};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info, info_span, warn, Instrument};
 
 use crate::agent::context_monitor::ContextMonitor;
+use crate::agent::scheduler::{ToolScheduler, ToolTicket};
+use crate::agent::tool_cache::ToolResultCache;
 use crate::bus::{InboundMessage, MessageBus, OutboundMessage};
 use crate::config::Config;
 use crate::error::{Result, ZeptoError};
+use crate::gateway::ChatRateLimiter;
 use crate::health::UsageMetrics;
-use crate::providers::{ChatOptions, LLMProvider};
+use crate::providers::{ChatOptions, LLMProvider, LLMToolCall, ToolDefinition, Usage};
 use crate::safety::SafetyLayer;
 use crate::session::{Message, Role, SessionManager, ToolCall};
-use crate::tools::approval::ApprovalGate;
-use crate::tools::{Tool, ToolContext, ToolRegistry};
+use crate::tools::approval::{ApprovalGate, ApprovalResponse, AsyncApprovalBroker};
+use crate::tools::{Tool, ToolContext, ToolDescription, ToolRegistry};
+use crate::utils::cost::CostTracker;
 use crate::utils::metrics::MetricsCollector;
+use crate::utils::redact::RedactedValue;
 
 use super::budget::TokenBudget;
 use super::context::ContextBuilder;
+use super::plan::{self, PlanApprovalHandler, ToolPlan};
 
 /// System prompt sent during the memory flush turn, instructing the LLM to
 /// persist important facts and deduplicate existing long-term memory entries.
@@ -64,6 +72,107 @@ pub enum ToolFeedbackPhase {
     },
 }
 
+/// Marker appended to partial output when a stream drops and cannot be resumed.
+pub const STREAM_INTERRUPTED_MARKER: &str = "\n\n[stream interrupted]";
+
+/// Follow-up instruction used to finish a response that was cut off mid-stream.
+const STREAM_RESUME_PROMPT: &str = "Your previous response was cut off. Continue exactly \
+where it stopped, without repeating any of it.";
+
+/// Maximum number of characters of a tool result kept in a [`ToolCallRecord`].
+const PROVENANCE_RESULT_MAX_CHARS: usize = 500;
+
+/// Provenance record for one tool call made during an agent turn.
+///
+/// Lets callers see which tool results drove a final answer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolCallRecord {
+    /// Provider-assigned tool call ID.
+    pub id: String,
+    /// Name of the tool that was called.
+    pub name: String,
+    /// JSON arguments the tool was called with: as sent by the LLM, or as
+    /// rewritten by a `before_tool` hook.
+    pub arguments: String,
+    /// Tool result as fed back to the LLM, truncated for readability.
+    pub result: String,
+    /// Wall-clock execution time in milliseconds (0 if the tool never ran).
+    pub latency_ms: u64,
+    /// Whether the tool executed successfully.
+    pub success: bool,
+}
+
+impl ToolCallRecord {
+    fn new(
+        id: &str,
+        name: &str,
+        arguments: &str,
+        result: &str,
+        latency_ms: u64,
+        success: bool,
+    ) -> Self {
+        let result = if result.chars().count() > PROVENANCE_RESULT_MAX_CHARS {
+            let truncated: String = result.chars().take(PROVENANCE_RESULT_MAX_CHARS).collect();
+            format!("{}...", truncated)
+        } else {
+            result.to_string()
+        };
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            arguments: arguments.to_string(),
+            result,
+            latency_ms,
+            success,
+        }
+    }
+}
+
+/// Finish a streamed response that ended before `Done` with a non-streaming call.
+///
+/// The partial text is replayed as an assistant message so the model
+/// continues from it. Returns only the newly generated continuation.
+async fn resume_interrupted_stream(
+    provider: &dyn LLMProvider,
+    mut messages: Vec<Message>,
+    partial: &str,
+    model: Option<&str>,
+    options: ChatOptions,
+) -> Result<crate::providers::LLMResponse> {
+    if !partial.is_empty() {
+        messages.push(Message::assistant(partial));
+    }
+    messages.push(Message::user(STREAM_RESUME_PROMPT));
+    provider.chat(messages, Vec::new(), model, options).await
+}
+
+/// Add one call's token usage to the cost tracker, when cost tracking is enabled.
+///
+/// The call is priced as the model the provider reported in `usage`, which
+/// differs from the requested `default_model` when a fallback or rotation
+/// provider served it.
+fn record_cost(tracker: Option<&CostTracker>, provider: &str, default_model: &str, usage: &Usage) {
+    if let Some(tracker) = tracker {
+        let model = usage.model.as_deref().unwrap_or(default_model);
+        let cost = tracker.record(
+            provider,
+            model,
+            usage.prompt_tokens,
+            usage.completion_tokens,
+        );
+        debug!(provider, model, cost = ?cost, "Recorded LLM call cost");
+    }
+}
+
+/// Outcome of a single agent turn: the final response plus tool-call provenance.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TurnResult {
+    /// Final assistant response text.
+    pub content: String,
+    /// Tool calls made during the turn, in the order they were requested.
+    pub tool_calls: Vec<ToolCallRecord>,
+}
+
 /// The main agent loop that processes messages and coordinates with LLM providers.
 ///
 /// The `AgentLoop` is responsible for:
@@ -85,7 +194,7 @@ pub enum ToolFeedbackPhase {
 /// let config = Config::default();
 /// let session_manager = SessionManager::new_memory();
 /// let bus = Arc::new(MessageBus::new());
-/// let agent = AgentLoop::new(config, session_manager, bus);
+/// let agent = Arc::new(AgentLoop::new(config, session_manager, bus));
 ///
 /// // Configure provider and tools
 /// agent.set_provider(Box::new(my_provider)).await;
@@ -113,6 +222,8 @@ pub struct AgentLoop {
     usage_metrics: Arc<RwLock<Option<Arc<UsageMetrics>>>>,
     /// Per-agent metrics collector for tool and token tracking.
     metrics_collector: Arc<MetricsCollector>,
+    /// Estimated LLM spend, present when `cost.enabled` is set.
+    cost_tracker: Option<Arc<CostTracker>>,
     /// Shutdown signal sender
     shutdown_tx: watch::Sender<bool>,
     /// Per-session locks to serialize concurrent messages for the same session
@@ -133,6 +244,18 @@ pub struct AgentLoop {
     context_monitor: Option<ContextMonitor>,
     /// Optional channel for tool execution feedback (tool name + duration).
     tool_feedback_tx: Arc<RwLock<Option<tokio::sync::mpsc::UnboundedSender<ToolFeedback>>>>,
+    /// Cancellation token handed (as child tokens) to running tools; cancelled by `stop()`.
+    cancel_token: std::sync::Mutex<CancellationToken>,
+    /// Reviewer for tool plans when `plan_first` is enabled.
+    plan_approval: Arc<RwLock<Option<Arc<dyn PlanApprovalHandler>>>>,
+    /// Collects tool approvals over the bus; without it gated tools are refused.
+    approval_broker: Arc<RwLock<Option<Arc<AsyncApprovalBroker>>>>,
+    /// Per-chat inbound rate limiter (gateway mode), if enabled.
+    rate_limiter: Option<ChatRateLimiter>,
+    /// Hook engine shared by all turns (holds per-rule cooldown state).
+    hook_engine: Arc<crate::hooks::HookEngine>,
+    /// Results of cacheable tools, when `tool_cache_size` is set.
+    tool_cache: Option<Arc<ToolResultCache>>,
 }
 
 impl AgentLoop {
@@ -160,7 +283,12 @@ impl AgentLoop {
     pub fn new(config: Config, session_manager: SessionManager, bus: Arc<MessageBus>) -> Self {
         let (shutdown_tx, _) = watch::channel(false);
         let token_budget = Arc::new(TokenBudget::new(config.agents.defaults.token_budget));
-        let approval_gate = Arc::new(ApprovalGate::new(config.approval.clone()));
+        let approval_gate = Arc::new(Self::build_approval_gate(&config));
+        let rate_limiter = ChatRateLimiter::from_config(&config.gateway.rate_limit);
+        let tool_cache = Self::build_tool_cache(&config);
+        let hook_engine = Arc::new(
+            crate::hooks::HookEngine::new(config.hooks.clone()).with_bus(Arc::clone(&bus)),
+        );
         let safety_layer = if config.safety.enabled {
             Some(Arc::new(SafetyLayer::new(config.safety.clone())))
         } else {
@@ -174,6 +302,11 @@ impl AgentLoop {
         } else {
             None
         };
+        let cost_tracker = config.cost.enabled.then(|| {
+            Arc::new(CostTracker::new_with_pricing(
+                config.cost.custom_pricing.clone(),
+            ))
+        });
         Self {
             config,
             session_manager: Arc::new(session_manager),
@@ -184,6 +317,7 @@ impl AgentLoop {
             context_builder: ContextBuilder::new(),
             usage_metrics: Arc::new(RwLock::new(None)),
             metrics_collector: Arc::new(MetricsCollector::new()),
+            cost_tracker,
             shutdown_tx,
             session_locks: Arc::new(Mutex::new(HashMap::new())),
             pending_messages: Arc::new(Mutex::new(HashMap::new())),
@@ -194,6 +328,12 @@ impl AgentLoop {
             safety_layer,
             context_monitor,
             tool_feedback_tx: Arc::new(RwLock::new(None)),
+            cancel_token: std::sync::Mutex::new(CancellationToken::new()),
+            plan_approval: Arc::new(RwLock::new(None)),
+            approval_broker: Arc::new(RwLock::new(None)),
+            rate_limiter,
+            hook_engine,
+            tool_cache,
         }
     }
 
@@ -212,7 +352,12 @@ impl AgentLoop {
     ) -> Self {
         let (shutdown_tx, _) = watch::channel(false);
         let token_budget = Arc::new(TokenBudget::new(config.agents.defaults.token_budget));
-        let approval_gate = Arc::new(ApprovalGate::new(config.approval.clone()));
+        let approval_gate = Arc::new(Self::build_approval_gate(&config));
+        let rate_limiter = ChatRateLimiter::from_config(&config.gateway.rate_limit);
+        let tool_cache = Self::build_tool_cache(&config);
+        let hook_engine = Arc::new(
+            crate::hooks::HookEngine::new(config.hooks.clone()).with_bus(Arc::clone(&bus)),
+        );
         let safety_layer = if config.safety.enabled {
             Some(Arc::new(SafetyLayer::new(config.safety.clone())))
         } else {
@@ -226,6 +371,11 @@ impl AgentLoop {
         } else {
             None
         };
+        let cost_tracker = config.cost.enabled.then(|| {
+            Arc::new(CostTracker::new_with_pricing(
+                config.cost.custom_pricing.clone(),
+            ))
+        });
         Self {
             config,
             session_manager: Arc::new(session_manager),
@@ -236,6 +386,7 @@ impl AgentLoop {
             context_builder,
             usage_metrics: Arc::new(RwLock::new(None)),
             metrics_collector: Arc::new(MetricsCollector::new()),
+            cost_tracker,
             shutdown_tx,
             session_locks: Arc::new(Mutex::new(HashMap::new())),
             pending_messages: Arc::new(Mutex::new(HashMap::new())),
@@ -246,6 +397,12 @@ impl AgentLoop {
             safety_layer,
             context_monitor,
             tool_feedback_tx: Arc::new(RwLock::new(None)),
+            cancel_token: std::sync::Mutex::new(CancellationToken::new()),
+            plan_approval: Arc::new(RwLock::new(None)),
+            approval_broker: Arc::new(RwLock::new(None)),
+            rate_limiter,
+            hook_engine,
+            tool_cache,
         }
     }
 
@@ -285,6 +442,22 @@ impl AgentLoop {
         Arc::clone(&self.metrics_collector)
     }
 
+    /// Get the cost tracker, if cost tracking is enabled in the config.
+    pub fn cost_tracker(&self) -> Option<Arc<CostTracker>> {
+        self.cost_tracker.clone()
+    }
+
+    /// Record a call's usage against the model that served it, falling back
+    /// to the configured model.
+    fn record_cost(&self, provider: &dyn LLMProvider, usage: &Usage) {
+        record_cost(
+            self.cost_tracker.as_deref(),
+            provider.name(),
+            &self.config.agents.defaults.model,
+            usage,
+        );
+    }
+
     /// Register a tool with the agent.
     ///
     /// # Arguments
@@ -301,6 +474,12 @@ impl AgentLoop {
         tools.register(tool);
     }
 
+    /// Remove a registered tool. Returns true if the tool was registered.
+    pub async fn unregister_tool(&self, name: &str) -> bool {
+        let mut tools = self.tools.write().await;
+        tools.unregister(name).is_some()
+    }
+
     /// Get the number of registered tools.
     pub async fn tool_count(&self) -> usize {
         let tools = self.tools.read().await;
@@ -313,6 +492,34 @@ impl AgentLoop {
         tools.has(name)
     }
 
+    /// Build the chat options for a turn from the agent defaults.
+    fn chat_options(&self) -> ChatOptions {
+        let defaults = &self.config.agents.defaults;
+        let options = ChatOptions::new()
+            .with_max_tokens(defaults.max_tokens)
+            .with_temperature(defaults.temperature)
+            .with_cache_system_prompt(defaults.prompt_caching)
+            .with_tool_call_mode(defaults.tool_call_mode);
+        match &defaults.stop_sequences {
+            Some(stop) => options.with_stop(stop.clone()),
+            None => options,
+        }
+    }
+
+    /// Get definitions for all registered tools.
+    ///
+    /// When `compact` is true, tools' compact descriptions are used.
+    pub async fn tool_definitions(&self, compact: bool) -> Vec<ToolDefinition> {
+        let tools = self.tools.read().await;
+        tools.definitions_with_options(compact)
+    }
+
+    /// Describe all registered tools, sorted by name.
+    pub async fn describe_tools(&self) -> Vec<ToolDescription> {
+        let tools = self.tools.read().await;
+        tools.describe_all()
+    }
+
     /// Process a single inbound message.
     ///
     /// This method:
@@ -335,10 +542,55 @@ impl AgentLoop {
     /// - The LLM call fails
     /// - Session management fails
     pub async fn process_message(&self, msg: &InboundMessage) -> Result<String> {
+        self.process_message_traced(msg)
+            .await
+            .map(|turn| turn.content)
+    }
+
+    /// Process a message and return the final response together with a record
+    /// of every tool call made during the turn (arguments, truncated result,
+    /// and latency).
+    ///
+    /// Behaves exactly like [`process_message`](Self::process_message); use this
+    /// when the caller wants to surface tool-call provenance, e.g. in JSON output.
+    pub async fn process_message_traced(&self, msg: &InboundMessage) -> Result<TurnResult> {
+        self.process_message_guarded(msg, None).await
+    }
+
+    /// Run a turn, reusing `session_guard` if the caller already holds the
+    /// session lock (the inbound loop reserves it before spawning).
+    ///
+    /// Fires the `on_turn_start` / `on_turn_end` hooks around the turn.
+    async fn process_message_guarded(
+        &self,
+        msg: &InboundMessage,
+        session_guard: Option<&OwnedMutexGuard<()>>,
+    ) -> Result<TurnResult> {
+        let started = std::time::Instant::now();
+        self.hook_engine.on_turn_start(&msg.channel, &msg.chat_id);
+        let result = self.run_turn(msg, session_guard).await;
+        let tool_count = result.as_ref().map_or(0, |turn| turn.tool_calls.len());
+        self.hook_engine
+            .on_turn_end(&msg.channel, &msg.chat_id, started.elapsed(), tool_count);
+        result
+    }
+
+    async fn run_turn(
+        &self,
+        msg: &InboundMessage,
+        session_guard: Option<&OwnedMutexGuard<()>>,
+    ) -> Result<TurnResult> {
         // Acquire a per-session lock to serialize concurrent messages for the
         // same session key. Different sessions can still proceed concurrently.
-        let session_lock = self.session_lock_for(&msg.session_key).await;
-        let _session_guard = session_lock.lock().await;
+        let _session_guard = match session_guard {
+            Some(_) => None,
+            None => Some(
+                self.session_lock_for(&msg.session_key)
+                    .await
+                    .lock_owned()
+                    .await,
+            ),
+        };
 
         // Clone the provider Arc early and release the RwLock immediately.
         // This avoids holding the provider read lock across multi-second LLM
@@ -362,16 +614,26 @@ impl AgentLoop {
 
         // Apply three-tier context overflow recovery if needed
         if let Some(ref monitor) = self.context_monitor {
-            if monitor.needs_compaction(&session.messages) {
+            let chars_per_token = self.config.compaction.chars_per_token_for(provider.name());
+            let estimated = session.estimated_tokens_with(chars_per_token);
+            debug!(
+                session = %session.key,
+                estimated_tokens = estimated,
+                context_limit = monitor.context_limit(),
+                "Context usage"
+            );
+            if monitor.exceeds_threshold(estimated) {
                 // Flush important memories before compaction discards context
                 self.memory_flush(&session.messages).await;
+                self.summarize_session(&mut session, provider.as_ref())
+                    .await;
 
                 let context_limit = self.config.compaction.context_limit;
                 let (recovered, tier) = crate::agent::compaction::try_recover_context(
                     session.messages,
                     context_limit,
-                    8,    // keep_recent for tier 1
-                    5120, // 5KB tool result budget for tier 2
+                    self.config.compaction.keep_recent, // keep_recent for tier 1
+                    5120,                               // 5KB tool result budget for tier 2
                 );
                 if tier > 0 {
                     debug!(
@@ -395,9 +657,7 @@ impl AgentLoop {
         };
 
         // Build chat options
-        let options = ChatOptions::new()
-            .with_max_tokens(self.config.agents.defaults.max_tokens)
-            .with_temperature(self.config.agents.defaults.temperature);
+        let options = self.chat_options();
 
         let model = Some(self.config.agents.defaults.model.as_str());
 
@@ -409,6 +669,20 @@ impl AgentLoop {
             )));
         }
 
+        // Plan-first: have the model enumerate its tool calls and get approval
+        if let Some(rejection) = self
+            .review_plan(&provider, &messages, &tool_definitions, msg, &options)
+            .await?
+        {
+            session.add_message(Message::user(&msg.content));
+            session.add_message(Message::assistant(&rejection));
+            self.session_manager.save(&session).await?;
+            return Ok(TurnResult {
+                content: rejection,
+                tool_calls: Vec::new(),
+            });
+        }
+
         // Call LLM -- provider lock is NOT held during this await
         let mut response = provider
             .chat(messages, tool_definitions, model, options.clone())
@@ -421,6 +695,7 @@ impl AgentLoop {
                 .record_tokens(usage.prompt_tokens as u64, usage.completion_tokens as u64);
             self.token_budget
                 .record(usage.prompt_tokens as u64, usage.completion_tokens as u64);
+            self.record_cost(provider.as_ref(), usage);
         }
 
         // Add user message to session
@@ -429,6 +704,7 @@ impl AgentLoop {
         // Tool loop
         let max_iterations = self.config.agents.defaults.max_tool_iterations;
         let mut iteration = 0;
+        let mut tool_records = Vec::new();
 
         while response.has_tool_calls() && iteration < max_iterations {
             iteration += 1;
@@ -452,156 +728,18 @@ impl AgentLoop {
             );
             session.add_message(assistant_msg);
 
-            // Execute tool calls in parallel
-            let workspace = self.config.workspace_path();
-            let workspace_str = workspace.to_string_lossy();
-            let tool_ctx = ToolContext::new()
-                .with_channel(&msg.channel, &msg.chat_id)
-                .with_workspace(&workspace_str);
-
-            let approval_gate = Arc::clone(&self.approval_gate);
-            let safety_layer = self.safety_layer.clone();
-            let hook_engine = Arc::new(
-                crate::hooks::HookEngine::new(self.config.hooks.clone())
-                    .with_bus(Arc::clone(&self.bus)),
-            );
-
-            // Compute dynamic tool result budget based on remaining context space
-            let current_tokens = ContextMonitor::estimate_tokens(&session.messages);
-            let context_limit = self.config.compaction.context_limit;
-            let result_budget = crate::utils::sanitize::compute_tool_result_budget(
-                context_limit,
-                current_tokens,
-                response.tool_calls.len(),
-            );
-
-            let tool_feedback_tx = self.tool_feedback_tx.clone();
-            let is_dry_run = self.dry_run.load(Ordering::SeqCst);
-            let tool_futures: Vec<_> = response
-                .tool_calls
-                .iter()
-                .map(|tool_call| {
-                    let tools = Arc::clone(&self.tools);
-                    let ctx = tool_ctx.clone();
-                    let name = tool_call.name.clone();
-                    let id = tool_call.id.clone();
-                    let raw_args = tool_call.arguments.clone();
-                    let usage_metrics = usage_metrics.clone();
-                    let metrics_collector = Arc::clone(&metrics_collector);
-                    let gate = Arc::clone(&approval_gate);
-                    let hooks = Arc::clone(&hook_engine);
-                    let safety = safety_layer.clone();
-                    let budget = result_budget;
-                    let tool_feedback_tx = tool_feedback_tx.clone();
-                    let dry_run = is_dry_run;
-
-                    async move {
-                        let args: serde_json::Value = match serde_json::from_str(&raw_args) {
-                            Ok(v) => v,
-                            Err(e) => {
-                                tracing::warn!(tool = %name, error = %e, "Invalid JSON in tool arguments");
-                                serde_json::json!({"_parse_error": format!("Invalid arguments JSON: {}", e)})
-                            }
-                        };
-
-                        // Check hooks before executing
-                        let channel_name = ctx.channel.as_deref().unwrap_or("cli");
-                        let chat_id = ctx.chat_id.as_deref().unwrap_or(channel_name);
-                        if let crate::hooks::HookResult::Block(msg) =
-                            hooks.before_tool(&name, &args, channel_name, chat_id)
-                        {
-                            return (id, format!("Tool '{}' blocked by hook: {}", name, msg));
-                        }
-
-                        // Check approval gate before executing
-                        if gate.requires_approval(&name) {
-                            let prompt = gate.format_approval_request(&name, &args);
-                            info!(tool = %name, "Tool requires approval, blocking execution");
-                            return (id, format!("Tool '{}' requires user approval and was not executed. {}", name, prompt));
-                        }
-
-                        // Dry-run mode: describe what would happen without executing
-                        if dry_run {
-                            return (id, Self::dry_run_result(&name, &args, &raw_args, budget));
-                        }
-
-                        // Send tool starting feedback
-                        if let Some(tx) = tool_feedback_tx.read().await.as_ref() {
-                            let _ = tx.send(ToolFeedback {
-                                tool_name: name.clone(),
-                                phase: ToolFeedbackPhase::Starting,
-                            });
-                        }
-                        let tool_start = std::time::Instant::now();
-                        let (result, success) = {
-                            let tools_guard = tools.read().await;
-                            match tools_guard.execute_with_context(&name, args, &ctx).await {
-                                Ok(r) => {
-                                    let elapsed = tool_start.elapsed();
-                                    let latency_ms = elapsed.as_millis() as u64;
-                                    debug!(tool = %name, latency_ms = latency_ms, "Tool executed successfully");
-                                    hooks.after_tool(&name, &r, elapsed, channel_name, chat_id);
-                                    if let Some(tx) = tool_feedback_tx.read().await.as_ref() {
-                                        let _ = tx.send(ToolFeedback {
-                                            tool_name: name.clone(),
-                                            phase: ToolFeedbackPhase::Done { elapsed_ms: latency_ms },
-                                        });
-                                    }
-                                    (r, true)
-                                }
-                                Err(e) => {
-                                    let elapsed = tool_start.elapsed();
-                                    let latency_ms = elapsed.as_millis() as u64;
-                                    error!(tool = %name, latency_ms = latency_ms, error = %e, "Tool execution failed");
-                                    hooks.on_error(&name, &e.to_string(), channel_name, chat_id);
-                                    if let Some(metrics) = usage_metrics.as_ref() {
-                                        metrics.record_error();
-                                    }
-                                    if let Some(tx) = tool_feedback_tx.read().await.as_ref() {
-                                        let _ = tx.send(ToolFeedback {
-                                            tool_name: name.clone(),
-                                            phase: ToolFeedbackPhase::Failed {
-                                                elapsed_ms: latency_ms,
-                                                error: e.to_string(),
-                                            },
-                                        });
-                                    }
-                                    (format!("Error: {}", e), false)
-                                }
-                            }
-                        };
-                        metrics_collector.record_tool_call(&name, tool_start.elapsed(), success);
-
-                        // Sanitize the result with dynamic budget
-                        let sanitized = crate::utils::sanitize::sanitize_tool_result(
-                            &result,
-                            budget,
-                        );
-
-                        // Apply safety layer if enabled
-                        let sanitized = if let Some(ref safety) = safety {
-                            let safety_result = safety.check_tool_output(&sanitized);
-                            if safety_result.blocked {
-                                format!(
-                                    "[Safety blocked]: {}",
-                                    safety_result.block_reason.unwrap_or_default()
-                                )
-                            } else {
-                                safety_result.content
-                            }
-                        } else {
-                            sanitized
-                        };
-
-                        (id, sanitized)
-                    }
-                })
-                .collect();
-
-            let results = futures::future::join_all(tool_futures).await;
-
-            for (id, result) in results {
-                session.add_message(Message::tool_result(&id, &result));
+            // Execute tool calls; parallel-safe ones run concurrently
+            let results = self
+                .execute_tool_calls(
+                    msg,
+                    &response.tool_calls,
+                    &session.messages,
+                    usage_metrics.as_deref(),
+                )
+                .await;
+            for (result, record) in results {
+                session.add_message(Message::tool_result(&record.id, &result));
+                tool_records.push(record);
             }
 
             // Get fresh tool definitions for the next LLM call
@@ -636,6 +774,7 @@ impl AgentLoop {
                     .record_tokens(usage.prompt_tokens as u64, usage.completion_tokens as u64);
                 self.token_budget
                     .record(usage.prompt_tokens as u64, usage.completion_tokens as u64);
+                self.record_cost(provider.as_ref(), usage);
             }
         }
 
@@ -650,7 +789,10 @@ impl AgentLoop {
         session.add_message(Message::assistant(&response.content));
         self.session_manager.save(&session).await?;
 
-        Ok(response.content)
+        Ok(TurnResult {
+            content: response.content,
+            tool_calls: tool_records,
+        })
     }
 
     /// Process a message with streaming output for the final LLM response.
@@ -663,6 +805,56 @@ impl AgentLoop {
         &self,
         msg: &InboundMessage,
     ) -> Result<tokio::sync::mpsc::Receiver<crate::providers::StreamEvent>> {
+        self.process_message_streaming_traced(msg)
+            .await
+            .map(|(_, rx)| rx)
+    }
+
+    /// Like [`process_message_streaming`](Self::process_message_streaming),
+    /// also returning a record of every tool call made before the final
+    /// response started streaming.
+    ///
+    /// Fires `on_turn_start` now and `on_turn_end` once the stream ends.
+    pub async fn process_message_streaming_traced(
+        &self,
+        msg: &InboundMessage,
+    ) -> Result<(
+        Vec<ToolCallRecord>,
+        tokio::sync::mpsc::Receiver<crate::providers::StreamEvent>,
+    )> {
+        let started = std::time::Instant::now();
+        self.hook_engine.on_turn_start(&msg.channel, &msg.chat_id);
+        let (tool_calls, mut turn_rx) = match self.run_streaming_turn(msg).await {
+            Ok(turn) => turn,
+            Err(e) => {
+                self.hook_engine
+                    .on_turn_end(&msg.channel, &msg.chat_id, started.elapsed(), 0);
+                return Err(e);
+            }
+        };
+
+        let (tx, rx) = tokio::sync::mpsc::channel(32);
+        let hooks = Arc::clone(&self.hook_engine);
+        let (channel, chat_id) = (msg.channel.clone(), msg.chat_id.clone());
+        let tool_count = tool_calls.len();
+        tokio::spawn(async move {
+            while let Some(event) = turn_rx.recv().await {
+                if tx.send(event).await.is_err() {
+                    break;
+                }
+            }
+            hooks.on_turn_end(&channel, &chat_id, started.elapsed(), tool_count);
+        });
+        Ok((tool_calls, rx))
+    }
+
+    async fn run_streaming_turn(
+        &self,
+        msg: &InboundMessage,
+    ) -> Result<(
+        Vec<ToolCallRecord>,
+        tokio::sync::mpsc::Receiver<crate::providers::StreamEvent>,
+    )> {
         use crate::providers::StreamEvent;
 
         // Acquire per-session lock
@@ -677,22 +869,36 @@ impl AgentLoop {
                     .ok_or_else(|| ZeptoError::Provider("No provider configured".into()))?,
             )
         };
+        let usage_metrics = {
+            let metrics = self.usage_metrics.read().await;
+            metrics.clone()
+        };
         let metrics_collector = Arc::clone(&self.metrics_collector);
 
         let mut session = self.session_manager.get_or_create(&msg.session_key).await?;
 
         // Apply three-tier context overflow recovery if needed (streaming)
         if let Some(ref monitor) = self.context_monitor {
-            if monitor.needs_compaction(&session.messages) {
+            let chars_per_token = self.config.compaction.chars_per_token_for(provider.name());
+            let estimated = session.estimated_tokens_with(chars_per_token);
+            debug!(
+                session = %session.key,
+                estimated_tokens = estimated,
+                context_limit = monitor.context_limit(),
+                "Context usage"
+            );
+            if monitor.exceeds_threshold(estimated) {
                 // Flush important memories before compaction discards context
                 self.memory_flush(&session.messages).await;
+                self.summarize_session(&mut session, provider.as_ref())
+                    .await;
 
                 let context_limit = self.config.compaction.context_limit;
                 let (recovered, tier) = crate::agent::compaction::try_recover_context(
                     session.messages,
                     context_limit,
-                    8,    // keep_recent for tier 1
-                    5120, // 5KB tool result budget for tier 2
+                    self.config.compaction.keep_recent, // keep_recent for tier 1
+                    5120,                               // 5KB tool result budget for tier 2
                 );
                 if tier > 0 {
                     debug!(
@@ -713,9 +919,7 @@ impl AgentLoop {
             tools.definitions_with_options(self.config.agents.defaults.compact_tools)
         };
 
-        let options = ChatOptions::new()
-            .with_max_tokens(self.config.agents.defaults.max_tokens)
-            .with_temperature(self.config.agents.defaults.temperature);
+        let options = self.chat_options();
         let model = Some(self.config.agents.defaults.model.as_str());
 
         // Check token budget before first LLM call
@@ -726,6 +930,25 @@ impl AgentLoop {
             )));
         }
 
+        // Plan-first: have the model enumerate its tool calls and get approval
+        if let Some(rejection) = self
+            .review_plan(&provider, &messages, &tool_definitions, msg, &options)
+            .await?
+        {
+            session.add_message(Message::user(&msg.content));
+            session.add_message(Message::assistant(&rejection));
+            self.session_manager.save(&session).await?;
+
+            let (tx, rx) = tokio::sync::mpsc::channel(1);
+            let _ = tx
+                .send(StreamEvent::Done {
+                    content: rejection,
+                    usage: None,
+                })
+                .await;
+            return Ok((Vec::new(), rx));
+        }
+
         // First call: non-streaming to see if there are tool calls
         let mut response = provider
             .chat(messages, tool_definitions, model, options.clone())
@@ -733,6 +956,7 @@ impl AgentLoop {
         if let Some(usage) = response.usage.as_ref() {
             self.token_budget
                 .record(usage.prompt_tokens as u64, usage.completion_tokens as u64);
+            self.record_cost(provider.as_ref(), usage);
         }
 
         session.add_message(Message::user(&msg.content));
@@ -740,6 +964,7 @@ impl AgentLoop {
         // Tool loop (non-streaming)
         let max_iterations = self.config.agents.defaults.max_tool_iterations;
         let mut iteration = 0;
+        let mut tool_records = Vec::new();
 
         while response.has_tool_calls() && iteration < max_iterations {
             iteration += 1;
@@ -758,126 +983,17 @@ impl AgentLoop {
             );
             session.add_message(assistant_msg);
 
-            let workspace = self.config.workspace_path();
-            let workspace_str = workspace.to_string_lossy();
-            let tool_ctx = ToolContext::new()
-                .with_channel(&msg.channel, &msg.chat_id)
-                .with_workspace(&workspace_str);
-
-            let approval_gate = Arc::clone(&self.approval_gate);
-            let safety_layer_stream = self.safety_layer.clone();
-
-            // Compute dynamic tool result budget based on remaining context space
-            let current_tokens_stream = ContextMonitor::estimate_tokens(&session.messages);
-            let context_limit_stream = self.config.compaction.context_limit;
-            let result_budget_stream = crate::utils::sanitize::compute_tool_result_budget(
-                context_limit_stream,
-                current_tokens_stream,
-                response.tool_calls.len(),
-            );
-
-            let tool_feedback_tx = self.tool_feedback_tx.clone();
-            let is_dry_run_stream = self.dry_run.load(Ordering::SeqCst);
-            let tool_futures: Vec<_> = response
-                .tool_calls
-                .iter()
-                .map(|tool_call| {
-                    let tools = Arc::clone(&self.tools);
-                    let ctx = tool_ctx.clone();
-                    let name = tool_call.name.clone();
-                    let id = tool_call.id.clone();
-                    let raw_args = tool_call.arguments.clone();
-                    let metrics_collector = Arc::clone(&metrics_collector);
-                    let gate = Arc::clone(&approval_gate);
-                    let safety = safety_layer_stream.clone();
-                    let budget = result_budget_stream;
-                    let tool_feedback_tx = tool_feedback_tx.clone();
-                    let dry_run = is_dry_run_stream;
-
-                    async move {
-                        let args: serde_json::Value = serde_json::from_str(&raw_args)
-                            .unwrap_or_else(|_| serde_json::json!({}));
-
-                        // Check approval gate before executing
-                        if gate.requires_approval(&name) {
-                            let prompt = gate.format_approval_request(&name, &args);
-                            info!(tool = %name, "Tool requires approval, blocking execution");
-                            return (
-                                id,
-                                format!(
-                                    "Tool '{}' requires user approval and was not executed. {}",
-                                    name, prompt
-                                ),
-                            );
-                        }
-
-                        // Dry-run mode: describe what would happen without executing
-                        if dry_run {
-                            return (id, Self::dry_run_result(&name, &args, &raw_args, budget));
-                        }
-
-                        // Send tool starting feedback
-                        if let Some(tx) = tool_feedback_tx.read().await.as_ref() {
-                            let _ = tx.send(ToolFeedback {
-                                tool_name: name.clone(),
-                                phase: ToolFeedbackPhase::Starting,
-                            });
-                        }
-                        let tool_start = std::time::Instant::now();
-                        let (result, success) = {
-                            let tools_guard = tools.read().await;
-                            match tools_guard.execute_with_context(&name, args, &ctx).await {
-                                Ok(r) => (r, true),
-                                Err(e) => (format!("Error: {}", e), false),
-                            }
-                        };
-                        metrics_collector.record_tool_call(&name, tool_start.elapsed(), success);
-                        // Send tool done/failed feedback
-                        if let Some(tx) = tool_feedback_tx.read().await.as_ref() {
-                            let latency_ms = tool_start.elapsed().as_millis() as u64;
-                            if success {
-                                let _ = tx.send(ToolFeedback {
-                                    tool_name: name.clone(),
-                                    phase: ToolFeedbackPhase::Done {
-                                        elapsed_ms: latency_ms,
-                                    },
-                                });
-                            } else {
-                                let _ = tx.send(ToolFeedback {
-                                    tool_name: name.clone(),
-                                    phase: ToolFeedbackPhase::Failed {
-                                        elapsed_ms: latency_ms,
-                                        error: result.clone(),
-                                    },
-                                });
-                            }
-                        }
-                        let sanitized =
-                            crate::utils::sanitize::sanitize_tool_result(&result, budget);
-
-                        // Apply safety layer if enabled
-                        let sanitized = if let Some(ref safety) = safety {
-                            let safety_result = safety.check_tool_output(&sanitized);
-                            if safety_result.blocked {
-                                format!(
-                                    "[Safety blocked]: {}",
-                                    safety_result.block_reason.unwrap_or_default()
-                                )
-                            } else {
-                                safety_result.content
-                            }
-                        } else {
-                            sanitized
-                        };
-
-                        (id, sanitized)
-                    }
-                })
-                .collect();
-
-            let results = futures::future::join_all(tool_futures).await;
-            for (id, result) in results {
-                session.add_message(Message::tool_result(&id, &result));
+            let results = self
+                .execute_tool_calls(
+                    msg,
+                    &response.tool_calls,
+                    &session.messages,
+                    usage_metrics.as_deref(),
+                )
+                .await;
+            for (result, record) in results {
+                session.add_message(Message::tool_result(&record.id, &result));
+                tool_records.push(record);
             }
 
             let tool_definitions = {
@@ -906,6 +1022,7 @@ impl AgentLoop {
                     .record_tokens(usage.prompt_tokens as u64, usage.completion_tokens as u64);
                 self.token_budget
                     .record(usage.prompt_tokens as u64, usage.completion_tokens as u64);
+                self.record_cost(provider.as_ref(), usage);
             }
         }
 
@@ -924,6 +1041,11 @@ impl AgentLoop {
                 tools.definitions_with_options(self.config.agents.defaults.compact_tools)
             };
 
+            // Kept so an interrupted stream can be resumed with a follow-up call
+            let resume_messages = messages.clone();
+            let resume_model = model.map(str::to_string);
+            let resume_options = options.clone();
+
             let stream_rx = provider
                 .chat_stream(messages, tool_definitions, model, options)
                 .await?;
@@ -933,19 +1055,38 @@ impl AgentLoop {
             let session_manager = Arc::clone(&self.session_manager);
             let session_clone = session.clone();
             let metrics_collector = Arc::clone(&metrics_collector);
+            let cost_tracker = self.cost_tracker.clone();
+            let provider = Arc::clone(&provider);
 
             tokio::spawn(async move {
                 let mut session = session_clone;
                 let mut stream_rx = stream_rx;
+                let mut partial = String::new();
 
                 while let Some(event) = stream_rx.recv().await {
                     match &event {
+                        StreamEvent::Delta(chunk) => {
+                            partial.push_str(chunk);
+                            if out_tx.send(event).await.is_err() {
+                                return;
+                            }
+                        }
+                        StreamEvent::Error(e) if !partial.is_empty() => {
+                            warn!(error = %e, "Stream failed after partial output, resuming");
+                            break;
+                        }
                         StreamEvent::Done { content, usage } => {
                             if let Some(usage) = usage.as_ref() {
                                 metrics_collector.record_tokens(
                                     usage.prompt_tokens as u64,
                                     usage.completion_tokens as u64,
                                 );
+                                record_cost(
+                                    cost_tracker.as_deref(),
+                                    provider.name(),
+                                    resume_model.as_deref().unwrap_or_default(),
+                                    usage,
+                                );
                             }
                             session.add_message(Message::assistant(content));
                             let _ = session_manager.save(&session).await;
@@ -957,16 +1098,57 @@ impl AgentLoop {
                             let _ = out_tx.send(event).await;
                             return;
                         }
-                        _ => {
-                            if out_tx.send(event).await.is_err() {
-                                return;
-                            }
+                        StreamEvent::Error(_) => {
+                            let _ = out_tx.send(event).await;
+                            return;
                         }
                     }
                 }
+
+                // The stream ended before `Done`: finish the answer from the
+                // partial text, or mark it as interrupted.
+                warn!(
+                    partial_len = partial.len(),
+                    "Stream ended prematurely, attempting to resume"
+                );
+                let (continuation, usage) = match resume_interrupted_stream(
+                    provider.as_ref(),
+                    resume_messages,
+                    &partial,
+                    resume_model.as_deref(),
+                    resume_options,
+                )
+                .await
+                {
+                    Ok(response) if !response.content.is_empty() => {
+                        (response.content, response.usage)
+                    }
+                    Ok(_) => (STREAM_INTERRUPTED_MARKER.to_string(), None),
+                    Err(e) => {
+                        warn!(error = %e, "Failed to resume interrupted stream");
+                        (STREAM_INTERRUPTED_MARKER.to_string(), None)
+                    }
+                };
+                if let Some(usage) = usage.as_ref() {
+                    metrics_collector
+                        .record_tokens(usage.prompt_tokens as u64, usage.completion_tokens as u64);
+                    record_cost(
+                        cost_tracker.as_deref(),
+                        provider.name(),
+                        resume_model.as_deref().unwrap_or_default(),
+                        usage,
+                    );
+                }
+
+                let content = format!("{}{}", partial, continuation);
+                session.add_message(Message::assistant(&content));
+                let _ = session_manager.save(&session).await;
+                if out_tx.send(StreamEvent::Delta(continuation)).await.is_ok() {
+                    let _ = out_tx.send(StreamEvent::Done { content, usage }).await;
+                }
             });
 
-            Ok(out_rx)
+            Ok((tool_records, out_rx))
         } else {
             // Still has tool calls after max iterations — return non-streaming result
             session.add_message(Message::assistant(&response.content));
@@ -979,55 +1161,456 @@ impl AgentLoop {
                     usage: response.usage,
                 })
                 .await;
-            Ok(rx)
+            Ok((tool_records, rx))
         }
     }
 
-    /// Run a silent LLM turn to flush important memories before context compaction.
+    /// Run one batch of tool calls requested by the model.
     ///
-    /// This method sends the current conversation plus a flush prompt to the LLM,
-    /// giving it the `longterm_memory` tool so it can persist any important facts,
-    /// decisions, or user preferences before the context is compacted. The call is
-    /// wrapped in a timeout and all failures are logged as warnings — the method
-    /// never panics or returns an error.
-    async fn memory_flush(&self, messages: &[crate::session::Message]) {
-        use tokio::time::{timeout, Duration};
+    /// Parallel-safe calls run concurrently. Returns each call's result as
+    /// fed back to the model, with its provenance record, in request order.
+    async fn execute_tool_calls(
+        &self,
+        msg: &InboundMessage,
+        tool_calls: &[LLMToolCall],
+        session_messages: &[Message],
+        usage_metrics: Option<&UsageMetrics>,
+    ) -> Vec<(String, ToolCallRecord)> {
+        let workspace = self.config.workspace_path();
+        let workspace_str = workspace.to_string_lossy();
+        let ctx = ToolContext::new()
+            .with_channel(&msg.channel, &msg.chat_id)
+            .with_workspace(&workspace_str)
+            .with_dry_run(self.is_dry_run())
+            .with_cancellation(self.tool_cancellation());
+        let broker = self.approval_broker.read().await.clone();
+
+        // Compute dynamic tool result budget based on remaining context space
+        let current_tokens = ContextMonitor::estimate_tokens(session_messages);
+        let budget = crate::utils::sanitize::compute_tool_result_budget(
+            self.config.compaction.context_limit,
+            current_tokens,
+            tool_calls.len(),
+        );
 
-        // Get the provider, bail silently if none configured
-        let provider = {
-            let guard = self.provider.read().await;
-            match guard.as_ref() {
-                Some(p) => Arc::clone(p),
-                None => {
-                    tracing::warn!("memory_flush: no provider configured, skipping");
-                    return;
-                }
+        let scheduler = ToolScheduler::new(self.config.agents.defaults.max_parallel_tools);
+        // Reserve scheduler tickets in model order before any call awaits
+        let calls = tool_calls.iter().map(|call| {
+            self.execute_tool_call(
+                call,
+                &ctx,
+                scheduler.reserve(),
+                broker.as_deref(),
+                budget,
+                usage_metrics,
+            )
+        });
+        futures::future::join_all(calls).await
+    }
+
+    /// Run a single tool call: `before_tool` hooks, the approval gate,
+    /// dry-run, the scheduler `ticket`, the result cache, then execution with
+    /// `on_error` retries. The result is sanitized to `budget` and passed
+    /// through the safety layer.
+    async fn execute_tool_call(
+        &self,
+        call: &LLMToolCall,
+        ctx: &ToolContext,
+        ticket: ToolTicket,
+        broker: Option<&AsyncApprovalBroker>,
+        budget: usize,
+        usage_metrics: Option<&UsageMetrics>,
+    ) -> (String, ToolCallRecord) {
+        let (id, name) = (call.id.as_str(), call.name.as_str());
+        let raw_args = call.arguments.as_str();
+        let mut args: serde_json::Value = match serde_json::from_str(raw_args) {
+            Ok(v) => v,
+            Err(e) => {
+                warn!(tool = %name, error = %e, "Invalid JSON in tool arguments");
+                serde_json::json!({"_parse_error": format!("Invalid arguments JSON: {}", e)})
             }
         };
+        // Arguments the tool actually runs with, as recorded in provenance
+        let mut effective_args = raw_args.to_string();
+        debug!(tool = %name, args = %RedactedValue::new(&args), "Executing tool");
+
+        // Check hooks before executing
+        let channel_name = ctx.channel.as_deref().unwrap_or("cli");
+        let chat_id = ctx.chat_id.as_deref().unwrap_or(channel_name);
+        match self
+            .hook_engine
+            .before_tool(name, &args, channel_name, chat_id)
+        {
+            crate::hooks::HookResult::Continue => {}
+            crate::hooks::HookResult::Rewrite(rewritten) => {
+                debug!(tool = %name, args = %RedactedValue::new(&rewritten), "Tool arguments rewritten by hook");
+                effective_args = rewritten.to_string();
+                args = rewritten;
+            }
+            crate::hooks::HookResult::Block(msg) => {
+                let result = format!("Tool '{}' blocked by hook: {}", name, msg);
+                let record = ToolCallRecord::new(id, name, &effective_args, &result, 0, false);
+                return (result, record);
+            }
+        }
 
-        // Get longterm_memory tool definitions, bail if the tool is not registered
-        let tool_defs = {
-            let tools = self.tools.read().await;
-            let defs = tools.definitions_for_tools(&["longterm_memory"]);
-            if defs.is_empty() {
-                tracing::debug!("memory_flush: longterm_memory tool not registered, skipping");
-                return;
+        // Check approval gate before executing
+        if self
+            .approval_gate
+            .requires_approval_for_call(name, &args, chat_id)
+        {
+            if let Some(result) =
+                Self::await_tool_approval(&self.approval_gate, broker, name, &args, ctx).await
+            {
+                let record = ToolCallRecord::new(id, name, &effective_args, &result, 0, false);
+                return (result, record);
             }
-            defs
+        }
+
+        // Dry-run mode: tools that can neither plan nor are
+        // read-only are described instead of executed
+        if ctx.dry_run && !self.tools.read().await.runs_in_dry_run(name) {
+            let result = Self::dry_run_result(name, &args, &effective_args, budget);
+            let record = ToolCallRecord::new(id, name, &effective_args, &result, 0, true);
+            return (result, record);
+        }
+
+        // Wait for conflicting calls; held across retries
+        let (parallel_safe, cache_ttl) = {
+            let tools = self.tools.read().await;
+            (tools.is_parallel_safe(name), tools.cache_ttl(name))
         };
+        let _permit = ticket.acquire(parallel_safe).await;
 
-        // Build flush messages: conversation history + flush prompt
-        let mut flush_messages: Vec<crate::session::Message> =
-            vec![Message::system("You are a memory management assistant.")];
-        flush_messages.extend(messages.iter().cloned());
-        flush_messages.push(Message::user(MEMORY_FLUSH_PROMPT));
+        let tool_start = std::time::Instant::now();
+        let cached = Self::cached_tool_result(self.tool_cache.as_deref(), cache_ttl, name, &args);
+        let (result, success) = if let Some(cached) = cached {
+            (cached, true)
+        } else {
+            // Send tool starting feedback
+            self.send_tool_feedback(name, ToolFeedbackPhase::Starting)
+                .await;
+            // Retry counter is scoped to this tool call.
+            let mut retries = 0u32;
+            let (result, success) = loop {
+                let outcome = {
+                    let tools = self.tools.read().await;
+                    tools.execute_with_context(name, args.clone(), ctx).await
+                };
+                let elapsed = tool_start.elapsed();
+                let latency_ms = elapsed.as_millis() as u64;
+                match outcome {
+                    Ok(r) => {
+                        debug!(tool = %name, latency_ms = latency_ms, "Tool executed successfully");
+                        self.hook_engine
+                            .after_tool(name, &r, elapsed, channel_name, chat_id);
+                        self.send_tool_feedback(
+                            name,
+                            ToolFeedbackPhase::Done {
+                                elapsed_ms: latency_ms,
+                            },
+                        )
+                        .await;
+                        break (r, true);
+                    }
+                    Err(e) => {
+                        error!(tool = %name, latency_ms = latency_ms, error = %e, "Tool execution failed");
+                        if let crate::hooks::ErrorHookOutcome::Retry {
+                            max_retries,
+                            backoff,
+                        } =
+                            self.hook_engine
+                                .on_error(name, &e.to_string(), channel_name, chat_id)
+                        {
+                            if retries < max_retries && !ctx.is_cancelled() {
+                                retries += 1;
+                                info!(tool = %name, attempt = retries, max_retries = max_retries, "Retrying tool after hook request");
+                                tokio::time::sleep(backoff).await;
+                                continue;
+                            }
+                        }
+                        if let Some(metrics) = usage_metrics {
+                            metrics.record_error();
+                        }
+                        self.send_tool_feedback(
+                            name,
+                            ToolFeedbackPhase::Failed {
+                                elapsed_ms: latency_ms,
+                                error: e.to_string(),
+                            },
+                        )
+                        .await;
+                        break (format!("Error: {}", e), false);
+                    }
+                }
+            };
+            self.metrics_collector
+                .record_tool_call(name, tool_start.elapsed(), success);
+            Self::update_tool_cache(
+                self.tool_cache.as_deref(),
+                cache_ttl,
+                parallel_safe,
+                name,
+                &args,
+                &result,
+                success,
+            );
+            (result, success)
+        };
+        let tool_elapsed = tool_start.elapsed();
+
+        // Sanitize the result with dynamic budget
+        let sanitized = crate::utils::sanitize::sanitize_tool_result(&result, budget);
+
+        // Apply safety layer if enabled
+        let sanitized = if let Some(ref safety) = self.safety_layer {
+            let safety_result = safety.check_tool_output(&sanitized);
+            if safety_result.blocked {
+                format!(
+                    "[Safety blocked]: {}",
+                    safety_result.block_reason.unwrap_or_default()
+                )
+            } else {
+                safety_result.content
+            }
+        } else {
+            sanitized
+        };
 
-        let options = ChatOptions::new()
-            .with_max_tokens(1024)
-            .with_temperature(0.0);
-        let model = Some(self.config.agents.defaults.model.as_str());
+        let record = ToolCallRecord::new(
+            id,
+            name,
+            &effective_args,
+            &sanitized,
+            tool_elapsed.as_millis() as u64,
+            success,
+        );
+        (sanitized, record)
+    }
 
-        info!("memory_flush: running pre-compaction memory flush");
+    /// Report tool progress to the CLI feedback listener, if one is set.
+    async fn send_tool_feedback(&self, tool_name: &str, phase: ToolFeedbackPhase) {
+        if let Some(tx) = self.tool_feedback_tx.read().await.as_ref() {
+            let _ = tx.send(ToolFeedback {
+                tool_name: tool_name.to_string(),
+                phase,
+            });
+        }
+    }
+
+    /// Build the approval gate, attaching the audit log if one is configured.
+    ///
+    /// An unusable audit log path is logged and approvals run unaudited.
+    fn build_approval_gate(config: &Config) -> ApprovalGate {
+        let gate = ApprovalGate::new(config.approval.clone());
+        let Some(path) = config.approval.audit_log.as_deref() else {
+            return gate;
+        };
+        match gate.with_audit_log(path, &config.workspace_path()) {
+            Ok(gate) => gate,
+            Err(e) => {
+                warn!(path = %path, error = %e, "Approval audit log disabled");
+                ApprovalGate::new(config.approval.clone())
+            }
+        }
+    }
+
+    /// Set the broker used to ask for tool approvals in the originating chat.
+    ///
+    /// Only useful while [`start`](Self::start) is consuming the bus, since
+    /// that is where replies are routed to the broker. Without a broker, tool
+    /// calls that need approval are refused.
+    pub async fn set_approval_broker(&self, broker: Arc<AsyncApprovalBroker>) {
+        *self.approval_broker.write().await = Some(broker);
+    }
+
+    /// Ask for approval of a gated tool call.
+    ///
+    /// Returns the tool result to report when the call must not run, or
+    /// `None` once it is approved. A timed-out request counts as approved
+    /// only when `approval.auto_approve_timeout_secs` has elapsed.
+    async fn await_tool_approval(
+        gate: &ApprovalGate,
+        broker: Option<&AsyncApprovalBroker>,
+        name: &str,
+        args: &serde_json::Value,
+        ctx: &ToolContext,
+    ) -> Option<String> {
+        let prompt = gate.format_approval_request(name, args);
+        let (Some(broker), Some(channel), Some(chat_id)) =
+            (broker, ctx.channel.as_deref(), ctx.chat_id.as_deref())
+        else {
+            info!(tool = %name, "Tool requires approval, blocking execution");
+            let decision = ApprovalResponse::Denied("no approval channel available".to_string());
+            gate.record_decision(name, args, ctx.chat_id.as_deref(), &decision, false);
+            return Some(format!(
+                "Tool '{}' requires user approval and was not executed. {}",
+                name, prompt
+            ));
+        };
+
+        let request = gate.create_request(name, args);
+        info!(tool = %name, channel, chat_id, "Waiting for tool approval");
+        let decision = broker.request_approval(channel, chat_id, &prompt).await;
+        let auto_approved = decision == ApprovalResponse::TimedOut && request.is_auto_approved();
+        gate.record_decision(name, args, Some(chat_id), &decision, auto_approved);
+        match decision {
+            ApprovalResponse::Approved => None,
+            ApprovalResponse::TimedOut if auto_approved => {
+                info!(tool = %name, "No approval reply, auto-approving");
+                None
+            }
+            ApprovalResponse::TimedOut => Some(format!(
+                "Tool '{}' was not executed: approval request timed out.",
+                name
+            )),
+            ApprovalResponse::Denied(reason) => Some(format!(
+                "Tool '{}' was not executed: denied by user ({}).",
+                name, reason
+            )),
+        }
+    }
+
+    /// Set the handler that reviews tool plans when `agents.defaults.plan_first`
+    /// is enabled. Without a handler, every plan is denied.
+    ///
+    /// The gateway installs its [`AsyncApprovalBroker`], so plans are approved
+    /// by replying in the originating chat.
+    pub async fn set_plan_approval_handler(&self, handler: Arc<dyn PlanApprovalHandler>) {
+        *self.plan_approval.write().await = Some(handler);
+    }
+
+    /// Run the plan-first pre-pass if enabled.
+    ///
+    /// Asks the model (without tool definitions, so nothing can execute) to
+    /// enumerate the tool calls it intends to make, then passes that plan to
+    /// the approval handler. Returns `Some(message)` when the plan was not
+    /// approved and the turn must end without executing any tools.
+    async fn review_plan(
+        &self,
+        provider: &Arc<dyn LLMProvider>,
+        messages: &[Message],
+        tool_definitions: &[ToolDefinition],
+        msg: &InboundMessage,
+        options: &ChatOptions,
+    ) -> Result<Option<String>> {
+        if !self.config.agents.defaults.plan_first || tool_definitions.is_empty() {
+            return Ok(None);
+        }
+
+        let model = Some(self.config.agents.defaults.model.as_str());
+        let response = provider
+            .chat(
+                plan::planning_messages(messages, tool_definitions),
+                Vec::new(),
+                model,
+                options.clone(),
+            )
+            .await?;
+        if let Some(usage) = response.usage.as_ref() {
+            self.metrics_collector
+                .record_tokens(usage.prompt_tokens as u64, usage.completion_tokens as u64);
+            self.token_budget
+                .record(usage.prompt_tokens as u64, usage.completion_tokens as u64);
+            self.record_cost(provider.as_ref(), usage);
+        }
+
+        let plan = ToolPlan {
+            request: msg.content.clone(),
+            plan: response.content,
+            channel: msg.channel.clone(),
+            chat_id: msg.chat_id.clone(),
+        };
+        let handler = self.plan_approval.read().await.clone();
+        let decision = match handler {
+            Some(handler) => handler.review_plan(&plan).await,
+            None => {
+                warn!("plan_first is enabled but no plan approval handler is set; denying plan");
+                ApprovalResponse::Denied("no plan approval handler configured".to_string())
+            }
+        };
+
+        if decision == ApprovalResponse::Approved {
+            debug!("Tool plan approved");
+            Ok(None)
+        } else {
+            info!(decision = ?decision, "Tool plan not approved, skipping execution");
+            Ok(Some(plan::rejection_message(&plan, &decision)))
+        }
+    }
+
+    /// Replace older session messages with an LLM summary when
+    /// `compaction.summarize` is enabled.
+    ///
+    /// Failures are logged and leave the session as-is; truncation-based
+    /// recovery still runs afterwards.
+    async fn summarize_session(
+        &self,
+        session: &mut crate::session::Session,
+        provider: &dyn LLMProvider,
+    ) {
+        if !self.config.compaction.summarize {
+            return;
+        }
+        let model = Some(self.config.agents.defaults.model.as_str());
+        let keep_recent = self.config.compaction.keep_recent;
+        match crate::agent::compaction::compact_session(session, provider, model, keep_recent).await
+        {
+            Ok(true) => info!(
+                session = %session.key,
+                messages = session.messages.len(),
+                "Summarized older session messages"
+            ),
+            Ok(false) => {}
+            Err(e) => warn!(session = %session.key, error = %e, "Session summarization failed"),
+        }
+    }
+
+    /// Run a silent LLM turn to flush important memories before context compaction.
+    ///
+    /// This method sends the current conversation plus a flush prompt to the LLM,
+    /// giving it the `longterm_memory` tool so it can persist any important facts,
+    /// decisions, or user preferences before the context is compacted. The call is
+    /// wrapped in a timeout and all failures are logged as warnings — the method
+    /// never panics or returns an error.
+    async fn memory_flush(&self, messages: &[crate::session::Message]) {
+        use tokio::time::{timeout, Duration};
+
+        // Get the provider, bail silently if none configured
+        let provider = {
+            let guard = self.provider.read().await;
+            match guard.as_ref() {
+                Some(p) => Arc::clone(p),
+                None => {
+                    tracing::warn!("memory_flush: no provider configured, skipping");
+                    return;
+                }
+            }
+        };
+
+        // Get longterm_memory tool definitions, bail if the tool is not registered
+        let tool_defs = {
+            let tools = self.tools.read().await;
+            let defs = tools.definitions_for_tools(&["longterm_memory"]);
+            if defs.is_empty() {
+                tracing::debug!("memory_flush: longterm_memory tool not registered, skipping");
+                return;
+            }
+            defs
+        };
+
+        // Build flush messages: conversation history + flush prompt
+        let mut flush_messages: Vec<crate::session::Message> =
+            vec![Message::system("You are a memory management assistant.")];
+        flush_messages.extend(messages.iter().cloned());
+        flush_messages.push(Message::user(MEMORY_FLUSH_PROMPT));
+
+        let options = ChatOptions::new()
+            .with_max_tokens(1024)
+            .with_temperature(0.0);
+        let model = Some(self.config.agents.defaults.model.as_str());
+
+        info!("memory_flush: running pre-compaction memory flush");
 
         let flush_result = timeout(
             Duration::from_secs(MEMORY_FLUSH_TIMEOUT_SECS),
@@ -1054,7 +1637,9 @@ impl AgentLoop {
         if response.has_tool_calls() {
             let workspace = self.config.workspace_path();
             let workspace_str = workspace.to_string_lossy();
-            let tool_ctx = ToolContext::new().with_workspace(&workspace_str);
+            let tool_ctx = ToolContext::new()
+                .with_workspace(&workspace_str)
+                .with_cancellation(self.tool_cancellation());
 
             for tc in &response.tool_calls {
                 let args: serde_json::Value = match serde_json::from_str(&tc.arguments) {
@@ -1135,16 +1720,33 @@ impl AgentLoop {
             .unwrap_or((0, 0))
     }
 
-    async fn drain_pending_messages(&self, msg: &InboundMessage) {
-        let pending = {
-            let mut map = self.pending_messages.lock().await;
-            map.remove(&msg.session_key).unwrap_or_default()
-        };
-
-        if pending.is_empty() {
-            return;
+    /// Take the messages queued for `session_key` while its turn ran.
+    ///
+    /// When there are none, `session_guard` is released before the pending
+    /// map is unlocked, so [`reserve_session`](Self::reserve_session) either
+    /// sees the session busy and queues behind this task, or sees it free.
+    async fn take_pending(
+        &self,
+        session_key: &str,
+        session_guard: OwnedMutexGuard<()>,
+    ) -> Option<(Vec<InboundMessage>, OwnedMutexGuard<()>)> {
+        let mut pending = self.pending_messages.lock().await;
+        match pending.remove(session_key) {
+            Some(queued) if !queued.is_empty() => Some((queued, session_guard)),
+            _ => {
+                drop(session_guard);
+                None
+            }
         }
+    }
 
+    /// Turn the messages queued behind `msg` into the turns to run next,
+    /// according to `message_queue_mode`.
+    fn pending_turns(
+        &self,
+        msg: &InboundMessage,
+        pending: Vec<InboundMessage>,
+    ) -> Vec<InboundMessage> {
         match self.config.agents.defaults.message_queue_mode {
             crate::config::MessageQueueMode::Collect => {
                 let combined: Vec<String> = pending
@@ -1156,23 +1758,14 @@ impl AgentLoop {
                     "[Queued messages while I was busy]\n\n{}",
                     combined.join("\n")
                 );
-                let synthetic = InboundMessage::new(
+                vec![InboundMessage::new(
                     &msg.channel,
                     &msg.sender_id,
                     &msg.chat_id,
                     &combined_content,
-                );
-                if let Err(e) = self.bus.publish_inbound(synthetic).await {
-                    error!("Failed to re-queue collected messages: {}", e);
-                }
-            }
-            crate::config::MessageQueueMode::Followup => {
-                for pending_msg in pending {
-                    if let Err(e) = self.bus.publish_inbound(pending_msg).await {
-                        error!("Failed to re-queue followup message: {}", e);
-                    }
-                }
+                )]
             }
+            crate::config::MessageQueueMode::Followup => pending,
         }
     }
 
@@ -1180,6 +1773,7 @@ impl AgentLoop {
         &self,
         msg: &InboundMessage,
         usage_metrics: Option<Arc<UsageMetrics>>,
+        session_guard: &OwnedMutexGuard<()>,
     ) {
         info!("Processing message");
         let start = std::time::Instant::now();
@@ -1191,8 +1785,12 @@ impl AgentLoop {
 
         let timeout_duration =
             std::time::Duration::from_secs(self.config.agents.defaults.agent_timeout_secs);
-        let process_result =
-            tokio::time::timeout(timeout_duration, self.process_message(msg)).await;
+        let process_result = tokio::time::timeout(timeout_duration, async {
+            self.process_message_guarded(msg, Some(session_guard))
+                .await
+                .map(|turn| turn.content)
+        })
+        .await;
 
         let agent_completed = match process_result {
             Ok(Ok(response)) => {
@@ -1253,40 +1851,46 @@ impl AgentLoop {
         let slo = crate::utils::slo::SessionSLO::evaluate(&self.metrics_collector, agent_completed);
         slo.emit();
         debug!(slo_summary = %slo.summary(), "Session SLO summary");
-
-        self.drain_pending_messages(msg).await;
     }
 
     /// Try to queue a message if the session is busy, or return false if lock is free.
     /// Returns `true` if the message was queued (caller should not wait for response).
     pub async fn try_queue_or_process(&self, msg: &InboundMessage) -> bool {
-        let session_lock = self.session_lock_for(&msg.session_key).await;
+        // Lock acquired and immediately dropped — caller should process normally.
+        // The real lock is acquired in process_message.
+        self.reserve_session(msg).await.is_none()
+    }
 
-        // Try to acquire the lock without blocking
-        let is_busy = session_lock.try_lock().is_err();
-
-        if is_busy {
-            // Session is busy, queue the message
-            let mut pending = self.pending_messages.lock().await;
-            pending
-                .entry(msg.session_key.clone())
-                .or_default()
-                .push(msg.clone());
-            debug!(session = %msg.session_key, "Message queued (session busy)");
-            true
-        } else {
-            // Lock acquired and immediately dropped — caller should process normally
-            // The real lock is acquired in process_message
-            false
+    /// Take the session lock for `msg`, or queue `msg` behind the session's
+    /// active turn, which runs it once the current turn completes.
+    ///
+    /// The pending map is held while trying the lock so that the active turn
+    /// cannot release the session in between and strand the message.
+    async fn reserve_session(&self, msg: &InboundMessage) -> Option<OwnedMutexGuard<()>> {
+        let session_lock = self.session_lock_for(&msg.session_key).await;
+        let mut pending = self.pending_messages.lock().await;
+        match session_lock.try_lock_owned() {
+            Ok(guard) => Some(guard),
+            Err(_) => {
+                pending
+                    .entry(msg.session_key.clone())
+                    .or_default()
+                    .push(msg.clone());
+                debug!(session = %msg.session_key, "Message queued (session busy)");
+                None
+            }
         }
     }
 
     /// Start the agent loop (consuming from message bus).
     ///
     /// This method runs in a loop, consuming messages from the inbound
-    /// channel and publishing responses to the outbound channel.
+    /// channel and publishing responses to the outbound channel. Up to
+    /// `gateway.max_concurrent_agents` messages are processed concurrently,
+    /// while messages within one session are handled in order.
     ///
-    /// The loop continues until `stop()` is called.
+    /// The loop continues until `stop()` is called, then waits for
+    /// in-progress messages to finish.
     ///
     /// # Errors
     /// Returns an error if the loop is already running.
@@ -1302,17 +1906,35 @@ impl AgentLoop {
     /// // Later, stop the loop
     /// agent.stop();
     /// ```
-    pub async fn start(&self) -> Result<()> {
+    pub async fn start(self: &Arc<Self>) -> Result<()> {
         if self.running.swap(true, Ordering::SeqCst) {
             return Err(ZeptoError::Config("Agent loop already running".into()));
         }
-        info!("Starting agent loop");
+        let max_concurrent = self.config.gateway.max_concurrent_agents.max(1);
+        info!(
+            max_concurrent_agents = max_concurrent,
+            "Starting agent loop"
+        );
+
+        // A previous stop() cancelled the tool token; hand out a fresh one.
+        if let Ok(mut token) = self.cancel_token.lock() {
+            if token.is_cancelled() {
+                *token = CancellationToken::new();
+            }
+        }
 
         // Subscribe fresh and consume any stale stop signal from a previous run.
         let mut shutdown_rx = self.shutdown_tx.subscribe();
         let _ = *shutdown_rx.borrow_and_update();
 
+        let permits = Arc::new(Semaphore::new(max_concurrent));
+        let mut in_flight = tokio::task::JoinSet::new();
+        // A turn slot is taken before the next message is read, so at most
+        // `max_concurrent` turns are spawned and the bus applies backpressure.
+        let mut slot: Option<OwnedSemaphorePermit> = None;
+
         loop {
+            let broker = self.approval_broker.read().await.clone();
             tokio::select! {
                 // Check for shutdown signal
                 _ = shutdown_rx.changed() => {
@@ -1321,43 +1943,40 @@ impl AgentLoop {
                         break;
                     }
                 }
+                // Reap finished turns
+                Some(joined) = in_flight.join_next(), if !in_flight.is_empty() => {
+                    if let Err(e) = joined {
+                        error!("Agent task failed: {}", e);
+                    }
+                }
+                // Wait for a free turn slot
+                permit = Arc::clone(&permits).acquire_owned(), if slot.is_none() => {
+                    slot = permit.ok();
+                }
+                // While every slot is busy, still pick approval replies out
+                // of the queue: the turns holding the slots may be waiting
+                // on them.
+                Some(reply) = self.bus.consume_inbound_matching(|msg| {
+                    broker.as_ref().is_some_and(|broker| broker.is_reply(msg))
+                }), if slot.is_none() && broker.is_some() => {
+                    if !self.is_approval_reply(&reply).await {
+                        // The request closed (e.g. timed out) in between.
+                        debug!(chat_id = %reply.chat_id, "Dropping late approval reply");
+                    }
+                }
                 // Wait for inbound messages
-                msg = self.bus.consume_inbound() => {
+                msg = self.bus.consume_inbound(), if slot.is_some() => {
                     if let Some(msg) = msg {
-                        let tenant_id = msg
-                            .metadata
-                            .get("tenant_id")
-                            .filter(|v| !v.is_empty())
-                            .map(String::as_str)
-                            .unwrap_or(&msg.chat_id);
-                        let request_id = uuid::Uuid::new_v4();
-                        let request_span = info_span!(
-                            "request",
-                            request_id = %request_id,
-                            tenant_id = %tenant_id,
-                            chat_id = %msg.chat_id,
-                            session_id = %msg.session_key,
-                            channel = %msg.channel,
-                            sender = %msg.sender_id,
-                        );
-                        let msg_ref = &msg;
-                        async {
-                            // Fast-path: if this session is already processing a
-                            // message, queue instead of blocking the select loop.
-                            // The queued message is drained and re-published to
-                            // the bus after the active request completes.
-                            if self.try_queue_or_process(msg_ref).await {
-                                return;
-                            }
-
-                            let usage_metrics = {
-                                let metrics = self.usage_metrics.read().await;
-                                metrics.clone()
-                            };
-                            self.process_inbound_message(msg_ref, usage_metrics).await;
+                        if self.is_approval_reply(&msg).await {
+                            continue;
                         }
-                        .instrument(request_span)
-                        .await;
+                        let Some(permit) = slot.take() else {
+                            continue;
+                        };
+                        let request_span = Self::request_span(&msg);
+                        self.dispatch_inbound(msg, permit, &mut in_flight)
+                            .instrument(request_span)
+                            .await;
                     } else {
                         // Channel closed, exit loop
                         info!("Inbound channel closed");
@@ -1372,28 +1991,160 @@ impl AgentLoop {
             }
         }
 
+        // Let in-progress turns finish before reporting the loop as stopped.
+        while let Some(joined) = in_flight.join_next().await {
+            if let Err(e) = joined {
+                error!("Agent task failed: {}", e);
+            }
+        }
+
         self.running.store(false, Ordering::SeqCst);
         info!("Agent loop stopped");
         Ok(())
     }
 
+    /// Whether `msg` answers a pending tool approval (and was consumed by
+    /// the approval broker instead of starting a turn).
+    async fn is_approval_reply(&self, msg: &InboundMessage) -> bool {
+        match self.approval_broker.read().await.as_ref() {
+            Some(broker) => broker.try_resolve(msg),
+            None => false,
+        }
+    }
+
+    /// Hand an inbound message to a spawned task holding `permit`, one of
+    /// the `gateway.max_concurrent_agents` turn slots.
+    ///
+    /// The session lock is reserved here, before spawning, so a later message
+    /// for the same session is queued behind this one instead of racing it.
+    /// The task keeps the lock and the permit until it has also run every
+    /// message queued for the session, in arrival order. Different sessions
+    /// run concurrently.
+    async fn dispatch_inbound(
+        self: &Arc<Self>,
+        msg: InboundMessage,
+        permit: OwnedSemaphorePermit,
+        in_flight: &mut tokio::task::JoinSet<()>,
+    ) {
+        if !self.within_rate_limit(&msg).await {
+            return;
+        }
+
+        let Some(mut session_guard) = self.reserve_session(&msg).await else {
+            // Session is busy; its active turn runs this message when done.
+            return;
+        };
+
+        let usage_metrics = {
+            let metrics = self.usage_metrics.read().await;
+            metrics.clone()
+        };
+        let agent = Arc::clone(self);
+        in_flight.spawn(
+            async move {
+                let _permit = permit;
+                agent
+                    .process_inbound_message(&msg, usage_metrics.clone(), &session_guard)
+                    .await;
+                let mut last = msg;
+                loop {
+                    let Some((pending, guard)) =
+                        agent.take_pending(&last.session_key, session_guard).await
+                    else {
+                        break;
+                    };
+                    session_guard = guard;
+                    for next in agent.pending_turns(&last, pending) {
+                        agent
+                            .process_inbound_message(&next, usage_metrics.clone(), &session_guard)
+                            .instrument(Self::request_span(&next))
+                            .await;
+                        last = next;
+                    }
+                }
+            }
+            .in_current_span(),
+        );
+    }
+
+    /// Tracing span for one inbound request.
+    fn request_span(msg: &InboundMessage) -> tracing::Span {
+        let tenant_id = msg
+            .metadata
+            .get("tenant_id")
+            .filter(|v| !v.is_empty())
+            .map(String::as_str)
+            .unwrap_or(&msg.chat_id);
+        let request_id = uuid::Uuid::new_v4();
+        info_span!(
+            "request",
+            request_id = %request_id,
+            tenant_id = %tenant_id,
+            chat_id = %msg.chat_id,
+            session_id = %msg.session_key,
+            channel = %msg.channel,
+            sender = %msg.sender_id,
+        )
+    }
+
+    /// Check the per-chat rate limit, replying with a throttle notice when
+    /// the chat is over its limit. Returns false if the message must be skipped.
+    async fn within_rate_limit(&self, msg: &InboundMessage) -> bool {
+        let Some(limiter) = self.rate_limiter.as_ref() else {
+            return true;
+        };
+        if limiter.check(msg) {
+            return true;
+        }
+        info!(
+            channel = %msg.channel,
+            chat_id = %msg.chat_id,
+            "Chat rate limit exceeded, sending throttle notice"
+        );
+        if let Err(e) = self
+            .bus
+            .publish_outbound(limiter.throttled_reply(msg))
+            .await
+        {
+            error!("Failed to publish throttle notice: {}", e);
+        }
+        false
+    }
+
     /// Stop the agent loop.
     ///
     /// This signals the loop to stop immediately (after completing any
-    /// in-progress message processing). The `start()` method will return
-    /// after the loop stops.
+    /// in-progress message processing). In-flight tools that poll
+    /// [`ToolContext::is_cancelled`] are asked to bail out early. The
+    /// `start()` method will return after the loop stops.
     pub fn stop(&self) {
         info!("Stopping agent loop");
         self.running.store(false, Ordering::SeqCst);
+        if let Ok(token) = self.cancel_token.lock() {
+            token.cancel();
+        }
         // Send shutdown signal to wake up the select! loop
         let _ = self.shutdown_tx.send(true);
     }
 
+    /// Child cancellation token for the tools of the current turn.
+    fn tool_cancellation(&self) -> CancellationToken {
+        self.cancel_token
+            .lock()
+            .map(|token| token.child_token())
+            .unwrap_or_default()
+    }
+
     /// Get a reference to the session manager.
     pub fn session_manager(&self) -> &Arc<SessionManager> {
         &self.session_manager
     }
 
+    /// Get a reference to the hook engine (for its match counters).
+    pub fn hook_engine(&self) -> &Arc<crate::hooks::HookEngine> {
+        &self.hook_engine
+    }
+
     /// Get a reference to the message bus.
     pub fn bus(&self) -> &Arc<MessageBus> {
         &self.bus
@@ -1422,9 +2173,10 @@ impl AgentLoop {
 
     /// Enable or disable dry-run mode.
     ///
-    /// When enabled, tool calls are intercepted and a description of
-    /// what *would* happen is returned instead of actually executing
-    /// the tool.
+    /// When enabled, mutating tools that support it (shell, write_file,
+    /// edit_file, plugin commands) report what they *would* do, read-only
+    /// tools run normally, and every other tool call is intercepted and
+    /// described instead of executed.
     pub fn set_dry_run(&self, enabled: bool) {
         self.dry_run.store(enabled, Ordering::SeqCst);
     }
@@ -1434,6 +2186,53 @@ impl AgentLoop {
         self.dry_run.load(Ordering::SeqCst)
     }
 
+    /// Build the tool result cache when `tool_cache_size` is non-zero.
+    fn build_tool_cache(config: &Config) -> Option<Arc<ToolResultCache>> {
+        let size = config.agents.defaults.tool_cache_size;
+        (size > 0).then(|| Arc::new(ToolResultCache::new(size)))
+    }
+
+    /// Look up a cached result for a call to a cacheable tool.
+    fn cached_tool_result(
+        cache: Option<&ToolResultCache>,
+        ttl: Option<std::time::Duration>,
+        name: &str,
+        args: &serde_json::Value,
+    ) -> Option<String> {
+        let cache = cache.filter(|_| ttl.is_some())?;
+        let cached = cache.get(name, args);
+        info_span!("tool_cache", tool = %name, hit = cached.is_some()).in_scope(|| {
+            if cached.is_some() {
+                info!("Serving tool result from cache");
+            } else {
+                debug!("Tool result not cached");
+            }
+        });
+        cached
+    }
+
+    /// Record the outcome of an executed tool call in the cache.
+    ///
+    /// Successful results of cacheable tools are stored. Any call to a tool
+    /// that is not parallel-safe may have changed what cached reads would
+    /// return, so it empties the cache.
+    fn update_tool_cache(
+        cache: Option<&ToolResultCache>,
+        ttl: Option<std::time::Duration>,
+        parallel_safe: bool,
+        name: &str,
+        args: &serde_json::Value,
+        result: &str,
+        success: bool,
+    ) {
+        let Some(cache) = cache else { return };
+        if !parallel_safe {
+            cache.clear();
+        } else if let Some(ttl) = ttl.filter(|_| success) {
+            cache.insert(name, args, result.to_string(), ttl);
+        }
+    }
+
     /// Format a dry-run result describing what a tool call would do.
     fn dry_run_result(
         name: &str,
@@ -1505,6 +2304,256 @@ mod tests {
         assert!(agent.has_tool("echo").await);
     }
 
+    #[test]
+    fn test_chat_options_carry_template_stop_sequences() {
+        use crate::config::templates::TemplateRegistry;
+
+        let mut template = TemplateRegistry::new().get("assistant").unwrap().clone();
+        template.max_tokens = Some(256);
+        template.stop_sequences = Some(vec!["\n\n".to_string(), "END".to_string()]);
+
+        let mut config = Config::default();
+        template.apply_to(&mut config.agents.defaults);
+        let agent = AgentLoop::new(
+            config,
+            SessionManager::new_memory(),
+            Arc::new(MessageBus::new()),
+        );
+
+        let options = agent.chat_options();
+        assert_eq!(options.max_tokens, Some(256));
+        assert_eq!(
+            options.stop,
+            Some(vec!["\n\n".to_string(), "END".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_chat_options_without_template_stop_sequences() {
+        use crate::config::templates::TemplateRegistry;
+
+        let template = TemplateRegistry::new().get("coder").unwrap().clone();
+        let mut config = Config::default();
+        template.apply_to(&mut config.agents.defaults);
+        let agent = AgentLoop::new(
+            config,
+            SessionManager::new_memory(),
+            Arc::new(MessageBus::new()),
+        );
+
+        assert!(agent.chat_options().stop.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_tool_approval_via_broker() {
+        use crate::tools::approval::{ApprovalConfig, ApprovalPolicyConfig};
+
+        let config = ApprovalConfig {
+            enabled: true,
+            policy: ApprovalPolicyConfig::AlwaysRequire,
+            ..Default::default()
+        };
+        let gate = ApprovalGate::new(config.clone());
+        let args = serde_json::json!({"command": "ls"});
+        let ctx = ToolContext::new().with_channel("telegram", "chat1");
+
+        // Without a broker the call is refused with the approval prompt.
+        let refused = AgentLoop::await_tool_approval(&gate, None, "shell", &args, &ctx)
+            .await
+            .unwrap();
+        assert!(refused.contains("requires user approval"));
+
+        let bus = Arc::new(MessageBus::new());
+        let agent = AgentLoop::new(Config::default(), SessionManager::new_memory(), bus.clone());
+        let broker = Arc::new(AsyncApprovalBroker::new(bus.clone(), &config));
+        agent.set_approval_broker(broker.clone()).await;
+
+        for (reply, expected) in [
+            ("yes", None),
+            ("no not today", Some("denied by user (not today)")),
+        ] {
+            let task_broker = broker.clone();
+            let (gate, args, ctx) = (ApprovalGate::new(config.clone()), args.clone(), ctx.clone());
+            let handle = tokio::spawn(async move {
+                AgentLoop::await_tool_approval(&gate, Some(&task_broker), "shell", &args, &ctx)
+                    .await
+            });
+            let prompt = bus.consume_outbound().await.unwrap();
+            assert!(prompt.content.contains("Tool: shell"));
+            assert!(
+                agent
+                    .is_approval_reply(&InboundMessage::new("telegram", "u1", "chat1", reply))
+                    .await
+            );
+            let result = handle.await.unwrap();
+            match expected {
+                None => assert!(result.is_none()),
+                Some(text) => assert!(result.unwrap().contains(text)),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_streaming_tool_approval_via_broker() {
+        use crate::providers::{LLMResponse, LLMToolCall, StreamEvent};
+        use crate::tools::approval::ApprovalPolicyConfig;
+
+        let workspace = tempfile::tempdir().unwrap();
+        let mut config = Config::default();
+        config.agents.defaults.workspace = workspace.path().display().to_string();
+        config.approval.enabled = true;
+        config.approval.policy = ApprovalPolicyConfig::AlwaysRequire;
+        config.approval.audit_log = Some("approvals.jsonl".to_string());
+
+        let bus = Arc::new(MessageBus::new());
+        let agent = Arc::new(AgentLoop::new(
+            config.clone(),
+            SessionManager::new_memory(),
+            bus.clone(),
+        ));
+        agent.register_tool(Box::new(SleepTool)).await;
+        agent
+            .set_provider(Box::new(ScriptedProvider::new(vec![
+                LLMResponse::with_tools(
+                    "",
+                    vec![LLMToolCall::new("call_1", "sleep", r#"{"ms": 1}"#)],
+                ),
+                // Ends the tool loop; the final answer is then streamed.
+                LLMResponse::text("rested"),
+                LLMResponse::text("rested"),
+            ])))
+            .await;
+        agent
+            .set_approval_broker(Arc::new(AsyncApprovalBroker::new(
+                bus.clone(),
+                &config.approval,
+            )))
+            .await;
+
+        let task_agent = Arc::clone(&agent);
+        let handle = tokio::spawn(async move {
+            let msg = InboundMessage::new("telegram", "user1", "chat1", "take a nap");
+            let mut rx = task_agent.process_message_streaming(&msg).await.unwrap();
+            while let Some(event) = rx.recv().await {
+                if let StreamEvent::Done { content, .. } = event {
+                    return content;
+                }
+            }
+            panic!("stream closed without Done");
+        });
+
+        let prompt = bus.consume_outbound().await.unwrap();
+        assert_eq!(prompt.chat_id, "chat1");
+        assert!(prompt.content.contains("Tool: sleep"));
+        assert!(
+            agent
+                .is_approval_reply(&InboundMessage::new("telegram", "user1", "chat1", "yes"))
+                .await
+        );
+        assert_eq!(handle.await.unwrap(), "rested");
+
+        let session = agent
+            .session_manager()
+            .get("telegram:chat1")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(session.messages.iter().any(|m| m.content == "slept 1ms"));
+        let audit = std::fs::read_to_string(workspace.path().join("approvals.jsonl")).unwrap();
+        assert_eq!(audit.lines().count(), 1);
+        assert!(audit.contains("\"sleep\""));
+    }
+
+    /// Mutating tool that records the order in which calls run.
+    struct RecordingTool {
+        name: &'static str,
+        log: Arc<std::sync::Mutex<Vec<&'static str>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl Tool for RecordingTool {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn description(&self) -> &str {
+            "Record that this tool ran"
+        }
+
+        fn parameters(&self) -> serde_json::Value {
+            serde_json::json!({"type": "object", "properties": {}})
+        }
+
+        async fn execute(&self, _args: serde_json::Value, _ctx: &ToolContext) -> Result<String> {
+            self.log.lock().unwrap().push(self.name);
+            Ok(format!("{} done", self.name))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_mutating_calls_keep_order_across_approval_wait() {
+        use crate::providers::{LLMResponse, LLMToolCall};
+        use crate::tools::approval::ApprovalPolicyConfig;
+
+        let mut config = Config::default();
+        config.approval.enabled = true;
+        config.approval.policy = ApprovalPolicyConfig::RequireForTools;
+        config.approval.require_for = vec!["first".to_string()];
+
+        let bus = Arc::new(MessageBus::new());
+        let agent = Arc::new(AgentLoop::new(
+            config.clone(),
+            SessionManager::new_memory(),
+            bus.clone(),
+        ));
+        let log = Arc::new(std::sync::Mutex::new(Vec::new()));
+        for name in ["first", "second"] {
+            agent
+                .register_tool(Box::new(RecordingTool {
+                    name,
+                    log: Arc::clone(&log),
+                }))
+                .await;
+        }
+        agent
+            .set_provider(Box::new(ScriptedProvider::new(vec![
+                LLMResponse::with_tools(
+                    "",
+                    vec![
+                        LLMToolCall::new("call_1", "first", "{}"),
+                        LLMToolCall::new("call_2", "second", "{}"),
+                    ],
+                ),
+                LLMResponse::text("both done"),
+            ])))
+            .await;
+        agent
+            .set_approval_broker(Arc::new(AsyncApprovalBroker::new(
+                bus.clone(),
+                &config.approval,
+            )))
+            .await;
+
+        let task_agent = Arc::clone(&agent);
+        let handle = tokio::spawn(async move {
+            let msg = InboundMessage::new("telegram", "user1", "chat1", "run both");
+            task_agent.process_message(&msg).await.unwrap()
+        });
+
+        // The second call needs no approval, but must not overtake the first.
+        let prompt = bus.consume_outbound().await.unwrap();
+        assert!(prompt.content.contains("Tool: first"));
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        assert!(log.lock().unwrap().is_empty());
+        assert!(
+            agent
+                .is_approval_reply(&InboundMessage::new("telegram", "user1", "chat1", "yes"))
+                .await
+        );
+        assert_eq!(handle.await.unwrap(), "both done");
+        assert_eq!(*log.lock().unwrap(), vec!["first", "second"]);
+    }
+
     #[tokio::test]
     async fn test_agent_loop_accessors() {
         let config = Config::default();
@@ -1534,9 +2583,1009 @@ mod tests {
         assert!(err.to_string().contains("No provider configured"));
     }
 
-    #[tokio::test]
-    async fn test_session_lock_for_reuses_same_session_lock() {
-        let config = Config::default();
+    /// Provider that replays a fixed script of responses, one per `chat` call.
+    struct ScriptedProvider {
+        responses: Arc<std::sync::Mutex<std::collections::VecDeque<crate::providers::LLMResponse>>>,
+    }
+
+    impl ScriptedProvider {
+        fn new(responses: Vec<crate::providers::LLMResponse>) -> Self {
+            Self {
+                responses: Arc::new(std::sync::Mutex::new(responses.into())),
+            }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl LLMProvider for ScriptedProvider {
+        async fn chat(
+            &self,
+            _messages: Vec<Message>,
+            _tools: Vec<ToolDefinition>,
+            _model: Option<&str>,
+            _options: ChatOptions,
+        ) -> Result<crate::providers::LLMResponse> {
+            Ok(self
+                .responses
+                .lock()
+                .unwrap()
+                .pop_front()
+                .unwrap_or_else(|| crate::providers::LLMResponse::text("done")))
+        }
+
+        fn default_model(&self) -> &str {
+            "scripted"
+        }
+
+        fn name(&self) -> &str {
+            "scripted"
+        }
+    }
+
+    /// Tool that sleeps for `ms` milliseconds before answering.
+    struct SleepTool;
+
+    #[async_trait::async_trait]
+    impl Tool for SleepTool {
+        fn name(&self) -> &str {
+            "sleep"
+        }
+
+        fn description(&self) -> &str {
+            "Sleep for a number of milliseconds"
+        }
+
+        fn parameters(&self) -> serde_json::Value {
+            serde_json::json!({"type": "object", "properties": {"ms": {"type": "integer"}}})
+        }
+
+        async fn execute(&self, args: serde_json::Value, _ctx: &ToolContext) -> Result<String> {
+            let ms = args["ms"].as_u64().unwrap_or(0);
+            tokio::time::sleep(std::time::Duration::from_millis(ms)).await;
+            Ok(format!("slept {}ms", ms))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_compaction_summarizes_long_session() {
+        use crate::providers::LLMResponse;
+        use crate::session::Session;
+
+        let mut config = Config::default();
+        config.compaction.enabled = true;
+        config.compaction.summarize = true;
+        config.compaction.context_limit = 100;
+        config.compaction.threshold = 0.5;
+        config.compaction.keep_recent = 2;
+        let agent = AgentLoop::new(
+            config,
+            SessionManager::new_memory(),
+            Arc::new(MessageBus::new()),
+        );
+
+        let mut session = Session::new("test:chat1");
+        for i in 0..10 {
+            session.add_message(Message::user(&format!("question number {} about rust", i)));
+            session.add_message(Message::assistant(&format!("answer number {}", i)));
+        }
+        agent.session_manager().save(&session).await.unwrap();
+        agent
+            .set_provider(Box::new(ScriptedProvider::new(vec![
+                LLMResponse::text("Ten questions about rust."),
+                LLMResponse::text("final answer"),
+            ])))
+            .await;
+
+        let msg = InboundMessage::new("test", "user1", "chat1", "one more");
+        let reply = agent.process_message(&msg).await.unwrap();
+        assert_eq!(reply, "final answer");
+
+        let saved = agent
+            .session_manager()
+            .get("test:chat1")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(saved.summary.as_deref(), Some("Ten questions about rust."));
+        assert!(crate::agent::compaction::is_summary_message(
+            &saved.messages[0]
+        ));
+        assert_eq!(saved.messages[1].content, "question number 9 about rust");
+        assert_eq!(saved.messages[2].content, "answer number 9");
+    }
+
+    #[tokio::test]
+    async fn test_process_message_traced_records_tool_calls() {
+        use crate::providers::{LLMResponse, LLMToolCall};
+
+        let agent = AgentLoop::new(
+            Config::default(),
+            SessionManager::new_memory(),
+            Arc::new(MessageBus::new()),
+        );
+        agent.register_tool(Box::new(SleepTool)).await;
+        agent
+            .set_provider(Box::new(ScriptedProvider::new(vec![
+                LLMResponse::with_tools(
+                    "",
+                    vec![
+                        LLMToolCall::new("call_1", "sleep", r#"{"ms": 20}"#),
+                        LLMToolCall::new("call_2", "sleep", r#"{"ms": 40}"#),
+                    ],
+                ),
+                LLMResponse::text("all rested"),
+            ])))
+            .await;
+
+        let msg = InboundMessage::new("test", "user1", "chat1", "take a nap");
+        let turn = agent.process_message_traced(&msg).await.unwrap();
+
+        assert_eq!(turn.content, "all rested");
+        assert_eq!(turn.tool_calls.len(), 2);
+
+        let first = &turn.tool_calls[0];
+        assert_eq!(first.id, "call_1");
+        assert_eq!(first.name, "sleep");
+        assert_eq!(first.arguments, r#"{"ms": 20}"#);
+        assert_eq!(first.result, "slept 20ms");
+        assert!(first.success);
+        assert!(first.latency_ms >= 20);
+
+        let second = &turn.tool_calls[1];
+        assert_eq!(second.id, "call_2");
+        assert_eq!(second.result, "slept 40ms");
+        assert!(second.latency_ms >= 40);
+    }
+
+    #[tokio::test]
+    async fn test_cost_tracker_records_each_chat() {
+        use crate::providers::{LLMResponse, LLMToolCall, Usage};
+
+        let disabled = AgentLoop::new(
+            Config::default(),
+            SessionManager::new_memory(),
+            Arc::new(MessageBus::new()),
+        );
+        assert!(disabled.cost_tracker().is_none());
+
+        let mut config = Config::default();
+        config.cost.enabled = true;
+        config.agents.defaults.model = "gpt-5.1".to_string();
+        let agent = AgentLoop::new(
+            config,
+            SessionManager::new_memory(),
+            Arc::new(MessageBus::new()),
+        );
+        agent.register_tool(Box::new(SleepTool)).await;
+        agent
+            .set_provider(Box::new(ScriptedProvider::new(vec![
+                LLMResponse::with_tools(
+                    "",
+                    vec![LLMToolCall::new("call_1", "sleep", r#"{"ms": 1}"#)],
+                )
+                .with_usage(Usage::new(1000, 500)),
+                // Served by another model, e.g. after a fallback
+                LLMResponse::text("rested")
+                    .with_usage(Usage::new(1000, 500).with_model(Some("claude-3-haiku-20240307"))),
+            ])))
+            .await;
+
+        let msg = InboundMessage::new("test", "user1", "chat1", "take a nap");
+        agent.process_message(&msg).await.unwrap();
+
+        let tracker = agent.cost_tracker().unwrap();
+        assert_eq!(tracker.call_count(), 2);
+        assert_eq!(tracker.total_tokens().total(), 3000);
+        // gpt-5.1: 1000/1M * 2.5 + 500/1M * 10 = 0.0075
+        // claude-3-haiku: 1000/1M * 0.25 + 500/1M * 1.25 = 0.000875
+        assert!((tracker.total_cost() - 0.008375).abs() < 1e-10);
+        assert!(tracker.cost_by_provider().contains_key("scripted"));
+        let by_model = tracker.tokens_by_model();
+        assert_eq!(by_model["gpt-5.1"].total(), 1500);
+        assert_eq!(by_model["claude-3-haiku-20240307"].total(), 1500);
+    }
+
+    /// Cacheable read-only tool that counts its executions.
+    struct CountingLookupTool(Arc<std::sync::atomic::AtomicU32>);
+
+    #[async_trait::async_trait]
+    impl Tool for CountingLookupTool {
+        fn name(&self) -> &str {
+            "lookup"
+        }
+
+        fn description(&self) -> &str {
+            "Counts executions"
+        }
+
+        fn parameters(&self) -> serde_json::Value {
+            serde_json::json!({"type": "object", "properties": {}})
+        }
+
+        fn is_parallel_safe(&self) -> bool {
+            true
+        }
+
+        fn cache_ttl(&self) -> Option<std::time::Duration> {
+            Some(std::time::Duration::from_secs(60))
+        }
+
+        async fn execute(&self, _args: serde_json::Value, _ctx: &ToolContext) -> Result<String> {
+            let n = self.0.fetch_add(1, Ordering::SeqCst) + 1;
+            Ok(format!("lookup {}", n))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tool_cache_reuses_results_until_mutation() {
+        use crate::providers::{LLMResponse, LLMToolCall};
+
+        let calls = Arc::new(std::sync::atomic::AtomicU32::new(0));
+        let mut config = Config::default();
+        config.agents.defaults.tool_cache_size = 16;
+        let agent = AgentLoop::new(
+            config,
+            SessionManager::new_memory(),
+            Arc::new(MessageBus::new()),
+        );
+        agent
+            .register_tool(Box::new(CountingLookupTool(Arc::clone(&calls))))
+            .await;
+        agent.register_tool(Box::new(SleepTool)).await;
+        agent
+            .set_provider(Box::new(ScriptedProvider::new(vec![
+                LLMResponse::with_tools(
+                    "",
+                    vec![LLMToolCall::new("call_1", "lookup", r#"{"a": 1, "b": 2}"#)],
+                ),
+                LLMResponse::with_tools(
+                    "",
+                    vec![LLMToolCall::new("call_2", "lookup", r#"{"b": 2, "a": 1}"#)],
+                ),
+                // A call to a mutating tool invalidates cached reads.
+                LLMResponse::with_tools(
+                    "",
+                    vec![LLMToolCall::new("call_3", "sleep", r#"{"ms": 1}"#)],
+                ),
+                LLMResponse::with_tools(
+                    "",
+                    vec![LLMToolCall::new("call_4", "lookup", r#"{"a": 1, "b": 2}"#)],
+                ),
+                LLMResponse::text("done"),
+            ])))
+            .await;
+
+        let msg = InboundMessage::new("test", "user1", "chat1", "look it up");
+        let turn = agent.process_message_traced(&msg).await.unwrap();
+
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert_eq!(turn.tool_calls[0].result, "lookup 1");
+        assert_eq!(turn.tool_calls[1].result, "lookup 1");
+        assert_eq!(turn.tool_calls[3].result, "lookup 2");
+    }
+
+    #[tokio::test]
+    async fn test_tool_cache_disabled_by_default() {
+        use crate::providers::{LLMResponse, LLMToolCall};
+
+        let calls = Arc::new(std::sync::atomic::AtomicU32::new(0));
+        let agent = AgentLoop::new(
+            Config::default(),
+            SessionManager::new_memory(),
+            Arc::new(MessageBus::new()),
+        );
+        agent
+            .register_tool(Box::new(CountingLookupTool(Arc::clone(&calls))))
+            .await;
+        agent
+            .set_provider(Box::new(ScriptedProvider::new(vec![
+                LLMResponse::with_tools("", vec![LLMToolCall::new("call_1", "lookup", "{}")]),
+                LLMResponse::with_tools("", vec![LLMToolCall::new("call_2", "lookup", "{}")]),
+                LLMResponse::text("done"),
+            ])))
+            .await;
+
+        let msg = InboundMessage::new("test", "user1", "chat1", "look it up");
+        agent.process_message(&msg).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    /// Tool that fails on odd-numbered calls and succeeds on even ones.
+    struct FlakyTool {
+        calls: Arc<std::sync::atomic::AtomicU32>,
+    }
+
+    #[async_trait::async_trait]
+    impl Tool for FlakyTool {
+        fn name(&self) -> &str {
+            "flaky"
+        }
+
+        fn description(&self) -> &str {
+            "Fails every other call"
+        }
+
+        fn parameters(&self) -> serde_json::Value {
+            serde_json::json!({"type": "object", "properties": {}})
+        }
+
+        async fn execute(&self, _args: serde_json::Value, _ctx: &ToolContext) -> Result<String> {
+            let n = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+            if n % 2 == 1 {
+                Err(ZeptoError::Tool(format!("transient failure {}", n)))
+            } else {
+                Ok(format!("ok on call {}", n))
+            }
+        }
+    }
+
+    fn retry_hooks_config(max_retries: Option<u32>) -> Config {
+        Config {
+            hooks: crate::hooks::HooksConfig {
+                enabled: true,
+                on_error: vec![crate::hooks::HookRule {
+                    action: crate::hooks::HookAction::Retry,
+                    tools: vec!["flaky".to_string()],
+                    max_retries,
+                    backoff_ms: Some(1),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retry_hook_reruns_failed_tool_per_call() {
+        use crate::providers::{LLMResponse, LLMToolCall};
+
+        let calls = Arc::new(std::sync::atomic::AtomicU32::new(0));
+        let agent = AgentLoop::new(
+            retry_hooks_config(None),
+            SessionManager::new_memory(),
+            Arc::new(MessageBus::new()),
+        );
+        agent
+            .register_tool(Box::new(FlakyTool {
+                calls: Arc::clone(&calls),
+            }))
+            .await;
+        // Two iterations, each failing once; the default single retry must
+        // be available to both calls.
+        agent
+            .set_provider(Box::new(ScriptedProvider::new(vec![
+                LLMResponse::with_tools("", vec![LLMToolCall::new("call_1", "flaky", "{}")]),
+                LLMResponse::with_tools("", vec![LLMToolCall::new("call_2", "flaky", "{}")]),
+                LLMResponse::text("done"),
+            ])))
+            .await;
+
+        let msg = InboundMessage::new("test", "user1", "chat1", "go");
+        let turn = agent.process_message_traced(&msg).await.unwrap();
+
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+        assert_eq!(turn.tool_calls.len(), 2);
+        assert!(turn.tool_calls.iter().all(|r| r.success));
+        assert_eq!(turn.tool_calls[0].result, "ok on call 2");
+        assert_eq!(turn.tool_calls[1].result, "ok on call 4");
+    }
+
+    #[tokio::test]
+    async fn test_retry_hook_gives_up_after_max_retries() {
+        use crate::providers::{LLMResponse, LLMToolCall};
+
+        /// Tool that always fails.
+        struct BrokenTool(Arc<std::sync::atomic::AtomicU32>);
+
+        #[async_trait::async_trait]
+        impl Tool for BrokenTool {
+            fn name(&self) -> &str {
+                "flaky"
+            }
+
+            fn description(&self) -> &str {
+                "Always fails"
+            }
+
+            fn parameters(&self) -> serde_json::Value {
+                serde_json::json!({"type": "object", "properties": {}})
+            }
+
+            async fn execute(
+                &self,
+                _args: serde_json::Value,
+                _ctx: &ToolContext,
+            ) -> Result<String> {
+                self.0.fetch_add(1, Ordering::SeqCst);
+                Err(ZeptoError::Tool("still broken".into()))
+            }
+        }
+
+        let calls = Arc::new(std::sync::atomic::AtomicU32::new(0));
+        let agent = AgentLoop::new(
+            retry_hooks_config(Some(2)),
+            SessionManager::new_memory(),
+            Arc::new(MessageBus::new()),
+        );
+        agent
+            .register_tool(Box::new(BrokenTool(Arc::clone(&calls))))
+            .await;
+        agent
+            .set_provider(Box::new(ScriptedProvider::new(vec![
+                LLMResponse::with_tools("", vec![LLMToolCall::new("call_1", "flaky", "{}")]),
+                LLMResponse::text("gave up"),
+            ])))
+            .await;
+
+        let msg = InboundMessage::new("test", "user1", "chat1", "go");
+        let turn = agent.process_message_traced(&msg).await.unwrap();
+
+        // One initial attempt plus two retries
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        assert!(!turn.tool_calls[0].success);
+        assert!(turn.tool_calls[0].result.contains("still broken"));
+    }
+
+    #[tokio::test]
+    async fn test_turn_hooks_fire_once_per_turn() {
+        use crate::providers::{LLMResponse, LLMToolCall};
+
+        let config = Config {
+            hooks: crate::hooks::HooksConfig {
+                enabled: true,
+                on_turn_start: vec![crate::hooks::HookRule {
+                    action: crate::hooks::HookAction::Notify,
+                    message: Some("started".to_string()),
+                    ..Default::default()
+                }],
+                on_turn_end: vec![crate::hooks::HookRule {
+                    action: crate::hooks::HookAction::Notify,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let bus = Arc::new(MessageBus::new());
+        let agent = AgentLoop::new(config, SessionManager::new_memory(), Arc::clone(&bus));
+        agent.register_tool(Box::new(SleepTool)).await;
+        agent
+            .set_provider(Box::new(ScriptedProvider::new(vec![
+                LLMResponse::with_tools("", vec![LLMToolCall::new("c1", "sleep", r#"{"ms": 1}"#)]),
+                LLMResponse::with_tools("", vec![LLMToolCall::new("c2", "sleep", r#"{"ms": 1}"#)]),
+                LLMResponse::text("done"),
+            ])))
+            .await;
+
+        let msg = InboundMessage::new("test", "user1", "chat1", "go");
+        agent.process_message_traced(&msg).await.unwrap();
+
+        let start = bus.consume_outbound().await.unwrap();
+        assert_eq!(start.content, "started");
+        let end = bus.consume_outbound().await.unwrap();
+        assert!(end.content.contains("2 tool call(s)"), "{}", end.content);
+        assert!(tokio::time::timeout(
+            tokio::time::Duration::from_millis(50),
+            bus.consume_outbound()
+        )
+        .await
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_rewrite_hook_replaces_tool_arguments() {
+        use crate::providers::{LLMResponse, LLMToolCall};
+
+        let mut set = serde_json::Map::new();
+        set.insert("message".to_string(), serde_json::json!("rewritten"));
+        let config = Config {
+            hooks: crate::hooks::HooksConfig {
+                enabled: true,
+                before_tool: vec![crate::hooks::HookRule {
+                    action: crate::hooks::HookAction::Rewrite,
+                    tools: vec!["echo".to_string()],
+                    set,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let agent = AgentLoop::new(
+            config,
+            SessionManager::new_memory(),
+            Arc::new(MessageBus::new()),
+        );
+        agent.register_tool(Box::new(crate::tools::EchoTool)).await;
+        agent
+            .set_provider(Box::new(ScriptedProvider::new(vec![
+                LLMResponse::with_tools(
+                    "",
+                    vec![LLMToolCall::new("c1", "echo", r#"{"message": "original"}"#)],
+                ),
+                LLMResponse::text("done"),
+            ])))
+            .await;
+
+        let msg = InboundMessage::new("test", "user1", "chat1", "go");
+        let turn = agent.process_message_traced(&msg).await.unwrap();
+        assert!(turn.tool_calls[0].success);
+        assert_eq!(turn.tool_calls[0].result, "rewritten");
+    }
+
+    /// Run a streamed turn to the end and return the final response.
+    async fn stream_turn(agent: &AgentLoop, msg: &InboundMessage) -> String {
+        let mut rx = agent.process_message_streaming(msg).await.unwrap();
+        while let Some(event) = rx.recv().await {
+            if let crate::providers::StreamEvent::Done { content, .. } = event {
+                return content;
+            }
+        }
+        panic!("stream closed without Done");
+    }
+
+    /// Tool results stored in a session, in order.
+    async fn session_tool_results(agent: &AgentLoop, key: &str) -> Vec<String> {
+        let session = agent.session_manager().get(key).await.unwrap().unwrap();
+        session
+            .messages
+            .iter()
+            .filter(|m| m.role == Role::Tool)
+            .map(|m| m.content.clone())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_streaming_applies_before_tool_hooks() {
+        use crate::providers::{LLMResponse, LLMToolCall};
+
+        let mut set = serde_json::Map::new();
+        set.insert("message".to_string(), serde_json::json!("rewritten"));
+        let config = Config {
+            hooks: crate::hooks::HooksConfig {
+                enabled: true,
+                before_tool: vec![
+                    crate::hooks::HookRule {
+                        action: crate::hooks::HookAction::Rewrite,
+                        tools: vec!["echo".to_string()],
+                        set,
+                        ..Default::default()
+                    },
+                    crate::hooks::HookRule {
+                        action: crate::hooks::HookAction::Block,
+                        tools: vec!["sleep".to_string()],
+                        message: Some("no naps".to_string()),
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let agent = AgentLoop::new(
+            config,
+            SessionManager::new_memory(),
+            Arc::new(MessageBus::new()),
+        );
+        agent.register_tool(Box::new(crate::tools::EchoTool)).await;
+        agent.register_tool(Box::new(SleepTool)).await;
+        agent
+            .set_provider(Box::new(ScriptedProvider::new(vec![
+                LLMResponse::with_tools(
+                    "",
+                    vec![
+                        LLMToolCall::new("c1", "echo", r#"{"message": "original"}"#),
+                        LLMToolCall::new("c2", "sleep", r#"{"ms": 1}"#),
+                    ],
+                ),
+                LLMResponse::text("done"),
+            ])))
+            .await;
+
+        let msg = InboundMessage::new("test", "user1", "chat1", "go");
+        stream_turn(&agent, &msg).await;
+
+        let results = session_tool_results(&agent, "test:chat1").await;
+        assert_eq!(results[0], "rewritten");
+        assert!(results[1].contains("blocked by hook"), "{}", results[1]);
+    }
+
+    #[tokio::test]
+    async fn test_streaming_retry_hook_reruns_failed_tool() {
+        use crate::providers::{LLMResponse, LLMToolCall};
+
+        let calls = Arc::new(std::sync::atomic::AtomicU32::new(0));
+        let agent = AgentLoop::new(
+            retry_hooks_config(None),
+            SessionManager::new_memory(),
+            Arc::new(MessageBus::new()),
+        );
+        agent
+            .register_tool(Box::new(FlakyTool {
+                calls: Arc::clone(&calls),
+            }))
+            .await;
+        agent
+            .set_provider(Box::new(ScriptedProvider::new(vec![
+                LLMResponse::with_tools("", vec![LLMToolCall::new("call_1", "flaky", "{}")]),
+                LLMResponse::text("done"),
+            ])))
+            .await;
+
+        let msg = InboundMessage::new("test", "user1", "chat1", "go");
+        stream_turn(&agent, &msg).await;
+
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        let results = session_tool_results(&agent, "test:chat1").await;
+        assert_eq!(results, vec!["ok on call 2"]);
+    }
+
+    #[tokio::test]
+    async fn test_streaming_turn_hooks_fire_once_per_turn() {
+        use crate::providers::{LLMResponse, LLMToolCall};
+
+        let config = Config {
+            hooks: crate::hooks::HooksConfig {
+                enabled: true,
+                on_turn_start: vec![crate::hooks::HookRule {
+                    action: crate::hooks::HookAction::Notify,
+                    message: Some("started".to_string()),
+                    ..Default::default()
+                }],
+                on_turn_end: vec![crate::hooks::HookRule {
+                    action: crate::hooks::HookAction::Notify,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let bus = Arc::new(MessageBus::new());
+        let agent = AgentLoop::new(config, SessionManager::new_memory(), Arc::clone(&bus));
+        agent.register_tool(Box::new(SleepTool)).await;
+        agent
+            .set_provider(Box::new(ScriptedProvider::new(vec![
+                LLMResponse::with_tools("", vec![LLMToolCall::new("c1", "sleep", r#"{"ms": 1}"#)]),
+                LLMResponse::text("done"),
+            ])))
+            .await;
+
+        let msg = InboundMessage::new("test", "user1", "chat1", "go");
+        stream_turn(&agent, &msg).await;
+
+        let start = bus.consume_outbound().await.unwrap();
+        assert_eq!(start.content, "started");
+        let end = tokio::time::timeout(tokio::time::Duration::from_secs(1), bus.consume_outbound())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(end.content.contains("1 tool call(s)"), "{}", end.content);
+        assert!(tokio::time::timeout(
+            tokio::time::Duration::from_millis(50),
+            bus.consume_outbound()
+        )
+        .await
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_tool_call_records_keep_rewritten_arguments() {
+        use crate::providers::{LLMResponse, LLMToolCall};
+
+        let mut set = serde_json::Map::new();
+        set.insert("message".to_string(), serde_json::json!("rewritten"));
+        let config = Config {
+            hooks: crate::hooks::HooksConfig {
+                enabled: true,
+                before_tool: vec![crate::hooks::HookRule {
+                    action: crate::hooks::HookAction::Rewrite,
+                    tools: vec!["echo".to_string()],
+                    set,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let agent = AgentLoop::new(
+            config,
+            SessionManager::new_memory(),
+            Arc::new(MessageBus::new()),
+        );
+        agent.register_tool(Box::new(crate::tools::EchoTool)).await;
+        let script = || {
+            ScriptedProvider::new(vec![
+                LLMResponse::with_tools(
+                    "",
+                    vec![LLMToolCall::new("c1", "echo", r#"{"message": "original"}"#)],
+                ),
+                LLMResponse::text("done"),
+            ])
+        };
+
+        agent.set_provider(Box::new(script())).await;
+        let msg = InboundMessage::new("test", "user1", "chat1", "go");
+        let turn = agent.process_message_traced(&msg).await.unwrap();
+
+        agent.set_provider(Box::new(script())).await;
+        let msg = InboundMessage::new("test", "user1", "chat2", "go");
+        let (streamed, mut rx) = agent.process_message_streaming_traced(&msg).await.unwrap();
+        while rx.recv().await.is_some() {}
+
+        for records in [&turn.tool_calls, &streamed] {
+            assert_eq!(records.len(), 1);
+            assert_eq!(records[0].id, "c1");
+            assert_eq!(records[0].arguments, r#"{"message":"rewritten"}"#);
+            assert_eq!(records[0].result, "rewritten");
+            assert!(records[0].success);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_process_message_traced_without_tools_has_no_records() {
+        let agent = AgentLoop::new(
+            Config::default(),
+            SessionManager::new_memory(),
+            Arc::new(MessageBus::new()),
+        );
+        agent
+            .set_provider(Box::new(ScriptedProvider::new(vec![
+                crate::providers::LLMResponse::text("hi"),
+            ])))
+            .await;
+
+        let msg = InboundMessage::new("test", "user1", "chat1", "hello");
+        let turn = agent.process_message_traced(&msg).await.unwrap();
+        assert_eq!(turn.content, "hi");
+        assert!(turn.tool_calls.is_empty());
+    }
+
+    /// Plan reviewer that returns a fixed decision and records what it saw.
+    struct FixedPlanApproval {
+        decision: ApprovalResponse,
+        seen: std::sync::Mutex<Vec<ToolPlan>>,
+    }
+
+    #[async_trait::async_trait]
+    impl PlanApprovalHandler for FixedPlanApproval {
+        async fn review_plan(&self, plan: &ToolPlan) -> ApprovalResponse {
+            self.seen.lock().unwrap().push(plan.clone());
+            self.decision.clone()
+        }
+    }
+
+    fn plan_first_agent() -> AgentLoop {
+        let config = Config {
+            agents: crate::config::AgentConfig {
+                defaults: crate::config::AgentDefaults {
+                    plan_first: true,
+                    ..Default::default()
+                },
+            },
+            ..Default::default()
+        };
+        AgentLoop::new(
+            config,
+            SessionManager::new_memory(),
+            Arc::new(MessageBus::new()),
+        )
+    }
+
+    fn plan_then_execute_script() -> ScriptedProvider {
+        use crate::providers::{LLMResponse, LLMToolCall};
+        ScriptedProvider::new(vec![
+            LLMResponse::text("sleep: wait briefly"),
+            LLMResponse::with_tools(
+                "",
+                vec![LLMToolCall::new("call_1", "sleep", r#"{"ms": 1}"#)],
+            ),
+            LLMResponse::text("finished"),
+        ])
+    }
+
+    #[tokio::test]
+    async fn test_plan_first_denied_skips_execution() {
+        let agent = plan_first_agent();
+        agent.register_tool(Box::new(SleepTool)).await;
+        let provider = plan_then_execute_script();
+        let remaining = Arc::clone(&provider.responses);
+        agent.set_provider(Box::new(provider)).await;
+        let reviewer = Arc::new(FixedPlanApproval {
+            decision: ApprovalResponse::Denied("not today".to_string()),
+            seen: std::sync::Mutex::new(Vec::new()),
+        });
+        agent.set_plan_approval_handler(reviewer.clone()).await;
+
+        let msg = InboundMessage::new("test", "user1", "chat1", "take a nap");
+        let turn = agent.process_message_traced(&msg).await.unwrap();
+
+        assert!(turn.tool_calls.is_empty());
+        assert!(turn.content.contains("not today"));
+        assert!(turn.content.contains("sleep: wait briefly"));
+
+        let seen = reviewer.seen.lock().unwrap();
+        assert_eq!(seen.len(), 1);
+        assert_eq!(seen[0].request, "take a nap");
+        assert_eq!(seen[0].plan, "sleep: wait briefly");
+        // Only the planning call was made; the execution responses are unused.
+        assert_eq!(remaining.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_plan_first_approved_executes_tools() {
+        let agent = plan_first_agent();
+        agent.register_tool(Box::new(SleepTool)).await;
+        agent
+            .set_provider(Box::new(plan_then_execute_script()))
+            .await;
+        agent
+            .set_plan_approval_handler(Arc::new(FixedPlanApproval {
+                decision: ApprovalResponse::Approved,
+                seen: std::sync::Mutex::new(Vec::new()),
+            }))
+            .await;
+
+        let msg = InboundMessage::new("test", "user1", "chat1", "take a nap");
+        let turn = agent.process_message_traced(&msg).await.unwrap();
+
+        assert_eq!(turn.content, "finished");
+        assert_eq!(turn.tool_calls.len(), 1);
+        assert_eq!(turn.tool_calls[0].result, "slept 1ms");
+    }
+
+    #[tokio::test]
+    async fn test_plan_first_without_handler_denies() {
+        let agent = plan_first_agent();
+        agent.register_tool(Box::new(SleepTool)).await;
+        agent
+            .set_provider(Box::new(plan_then_execute_script()))
+            .await;
+
+        let msg = InboundMessage::new("test", "user1", "chat1", "take a nap");
+        let turn = agent.process_message_traced(&msg).await.unwrap();
+
+        assert!(turn.tool_calls.is_empty());
+        assert!(turn.content.contains("no plan approval handler"));
+    }
+
+    /// Provider whose stream emits one delta and then drops without `Done`.
+    /// Non-streaming calls replay `chat_responses` (a `None` entry fails).
+    struct InterruptedStreamProvider {
+        chat_responses: std::sync::Mutex<std::collections::VecDeque<Option<&'static str>>>,
+        stream_error: bool,
+    }
+
+    #[async_trait::async_trait]
+    impl LLMProvider for InterruptedStreamProvider {
+        async fn chat(
+            &self,
+            _messages: Vec<Message>,
+            _tools: Vec<ToolDefinition>,
+            _model: Option<&str>,
+            _options: ChatOptions,
+        ) -> Result<crate::providers::LLMResponse> {
+            match self.chat_responses.lock().unwrap().pop_front().flatten() {
+                Some(text) => Ok(crate::providers::LLMResponse::text(text)),
+                None => Err(ZeptoError::Provider("connection reset".into())),
+            }
+        }
+
+        async fn chat_stream(
+            &self,
+            _messages: Vec<Message>,
+            _tools: Vec<ToolDefinition>,
+            _model: Option<&str>,
+            _options: ChatOptions,
+        ) -> Result<tokio::sync::mpsc::Receiver<crate::providers::StreamEvent>> {
+            use crate::providers::StreamEvent;
+            let (tx, rx) = tokio::sync::mpsc::channel(4);
+            tx.send(StreamEvent::Delta("Hello ".to_string()))
+                .await
+                .unwrap();
+            if self.stream_error {
+                tx.send(StreamEvent::Error(ZeptoError::Provider(
+                    "Stream read error".into(),
+                )))
+                .await
+                .unwrap();
+            }
+            Ok(rx)
+        }
+
+        fn default_model(&self) -> &str {
+            "interrupted"
+        }
+
+        fn name(&self) -> &str {
+            "interrupted"
+        }
+    }
+
+    async fn collect_stream(agent: &AgentLoop) -> (String, String) {
+        use crate::providers::StreamEvent;
+        let msg = InboundMessage::new("test", "user1", "chat1", "greet me");
+        let mut rx = agent.process_message_streaming(&msg).await.unwrap();
+        let mut deltas = String::new();
+        while let Some(event) = rx.recv().await {
+            match event {
+                StreamEvent::Delta(text) => deltas.push_str(&text),
+                StreamEvent::Done { content, .. } => return (deltas, content),
+                other => panic!("unexpected event: {:?}", other),
+            }
+        }
+        panic!("stream closed without Done");
+    }
+
+    async fn streaming_agent(provider: InterruptedStreamProvider) -> AgentLoop {
+        let agent = AgentLoop::new(
+            Config::default(),
+            SessionManager::new_memory(),
+            Arc::new(MessageBus::new()),
+        );
+        agent.set_provider(Box::new(provider)).await;
+        agent
+    }
+
+    #[tokio::test]
+    async fn test_stream_premature_end_is_resumed() {
+        let agent = streaming_agent(InterruptedStreamProvider {
+            chat_responses: std::sync::Mutex::new(vec![Some("first"), Some("world")].into()),
+            stream_error: false,
+        })
+        .await;
+
+        let (deltas, content) = collect_stream(&agent).await;
+        assert_eq!(content, "Hello world");
+        assert_eq!(deltas, "Hello world");
+    }
+
+    #[tokio::test]
+    async fn test_stream_premature_end_returns_partial_with_marker() {
+        let agent = streaming_agent(InterruptedStreamProvider {
+            chat_responses: std::sync::Mutex::new(vec![Some("first"), None].into()),
+            stream_error: false,
+        })
+        .await;
+
+        let (_, content) = collect_stream(&agent).await;
+        assert_eq!(content, format!("Hello {}", STREAM_INTERRUPTED_MARKER));
+
+        let session = agent
+            .session_manager()
+            .get("test:chat1")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(session.messages.last().unwrap().content, content);
+    }
+
+    #[tokio::test]
+    async fn test_stream_error_after_partial_is_resumed() {
+        let agent = streaming_agent(InterruptedStreamProvider {
+            chat_responses: std::sync::Mutex::new(vec![Some("first"), Some("again")].into()),
+            stream_error: true,
+        })
+        .await;
+
+        let (_, content) = collect_stream(&agent).await;
+        assert_eq!(content, "Hello again");
+    }
+
+    #[test]
+    fn test_tool_call_record_truncates_long_results() {
+        let long = "x".repeat(PROVENANCE_RESULT_MAX_CHARS + 100);
+        let record = ToolCallRecord::new("id", "tool", "{}", &long, 5, true);
+        assert_eq!(
+            record.result.chars().count(),
+            PROVENANCE_RESULT_MAX_CHARS + 3
+        );
+        assert!(record.result.ends_with("..."));
+    }
+
+    #[tokio::test]
+    async fn test_session_lock_for_reuses_same_session_lock() {
+        let config = Config::default();
         let session_manager = SessionManager::new_memory();
         let bus = Arc::new(MessageBus::new());
         let agent = AgentLoop::new(config, session_manager, bus);
@@ -1703,6 +3752,355 @@ mod tests {
         assert!(!agent.is_running());
     }
 
+    #[tokio::test]
+    async fn test_stop_cancels_tool_token_and_start_resets_it() {
+        let agent = Arc::new(AgentLoop::new(
+            Config::default(),
+            SessionManager::new_memory(),
+            Arc::new(MessageBus::new()),
+        ));
+
+        let before = agent.tool_cancellation();
+        assert!(!before.is_cancelled());
+        agent.stop();
+        assert!(before.is_cancelled());
+        assert!(agent.tool_cancellation().is_cancelled());
+
+        let agent_clone = Arc::clone(&agent);
+        let run = tokio::spawn(async move { agent_clone.start().await });
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+        assert!(!agent.tool_cancellation().is_cancelled());
+        agent.stop();
+        let _ = tokio::time::timeout(tokio::time::Duration::from_millis(200), run).await;
+    }
+
+    /// Provider that records how many chat calls overlap and echoes the
+    /// latest user message after a short delay.
+    #[derive(Default)]
+    struct ConcurrencyProbeProvider {
+        active: std::sync::atomic::AtomicUsize,
+        peak: Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    #[async_trait::async_trait]
+    impl LLMProvider for ConcurrencyProbeProvider {
+        async fn chat(
+            &self,
+            messages: Vec<Message>,
+            _tools: Vec<ToolDefinition>,
+            _model: Option<&str>,
+            _options: ChatOptions,
+        ) -> Result<crate::providers::LLMResponse> {
+            let now = self.active.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(now, Ordering::SeqCst);
+            tokio::time::sleep(tokio::time::Duration::from_millis(150)).await;
+            self.active.fetch_sub(1, Ordering::SeqCst);
+
+            let last_user = messages
+                .iter()
+                .rev()
+                .find(|m| m.role == Role::User)
+                .map(|m| m.content.clone())
+                .unwrap_or_default();
+            Ok(crate::providers::LLMResponse::text(&format!(
+                "echo: {}",
+                last_user
+            )))
+        }
+
+        fn default_model(&self) -> &str {
+            "probe"
+        }
+
+        fn name(&self) -> &str {
+            "probe"
+        }
+    }
+
+    /// Start a loop with `max_concurrent_agents = 2`, publish `messages` as
+    /// `(chat_id, text)` pairs and collect `expected` replies.
+    async fn run_concurrent_gateway(
+        messages: &[(&str, &str)],
+        expected: usize,
+    ) -> (Vec<OutboundMessage>, usize) {
+        let config = Config {
+            gateway: crate::config::GatewayConfig {
+                max_concurrent_agents: 2,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let bus = Arc::new(MessageBus::new());
+        let agent = Arc::new(AgentLoop::new(
+            config,
+            SessionManager::new_memory(),
+            Arc::clone(&bus),
+        ));
+        let peak = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        agent
+            .set_provider(Box::new(ConcurrencyProbeProvider {
+                peak: Arc::clone(&peak),
+                ..Default::default()
+            }))
+            .await;
+
+        let agent_clone = Arc::clone(&agent);
+        let run = tokio::spawn(async move { agent_clone.start().await });
+
+        for (chat, text) in messages {
+            bus.publish_inbound(InboundMessage::new("test", "user1", chat, text))
+                .await
+                .unwrap();
+        }
+
+        let mut replies = Vec::new();
+        for _ in 0..expected {
+            let reply =
+                tokio::time::timeout(tokio::time::Duration::from_secs(3), bus.consume_outbound())
+                    .await
+                    .unwrap()
+                    .unwrap();
+            replies.push(reply);
+        }
+
+        agent.stop();
+        let _ = tokio::time::timeout(tokio::time::Duration::from_secs(1), run).await;
+        (replies, peak.load(Ordering::SeqCst))
+    }
+
+    #[tokio::test]
+    async fn test_start_processes_different_sessions_concurrently() {
+        let (replies, peak) =
+            run_concurrent_gateway(&[("chat1", "one"), ("chat2", "two")], 2).await;
+
+        assert_eq!(peak, 2, "different sessions should overlap");
+        let mut chats: Vec<_> = replies.iter().map(|r| r.chat_id.as_str()).collect();
+        chats.sort();
+        assert_eq!(chats, vec!["chat1", "chat2"]);
+    }
+
+    #[tokio::test]
+    async fn test_start_serializes_messages_within_a_session() {
+        let (replies, peak) =
+            run_concurrent_gateway(&[("chat1", "one"), ("chat1", "two")], 2).await;
+
+        assert_eq!(peak, 1, "messages for one session must not overlap");
+        assert!(replies[0].content.contains("one"), "{}", replies[0].content);
+        assert!(replies[1].content.contains("two"), "{}", replies[1].content);
+    }
+
+    #[tokio::test]
+    async fn test_start_leaves_messages_queued_while_slots_are_busy() {
+        let mut config = Config::default();
+        config.gateway.max_concurrent_agents = 2;
+        let bus = Arc::new(MessageBus::new());
+        let agent = Arc::new(AgentLoop::new(
+            config,
+            SessionManager::new_memory(),
+            Arc::clone(&bus),
+        ));
+        agent
+            .set_provider(Box::new(ConcurrencyProbeProvider::default()))
+            .await;
+
+        let agent_clone = Arc::clone(&agent);
+        let run = tokio::spawn(async move { agent_clone.start().await });
+
+        for chat in ["chat1", "chat2", "chat3", "chat4"] {
+            bus.publish_inbound(InboundMessage::new("test", "user1", chat, "hi"))
+                .await
+                .unwrap();
+        }
+        // Two turns run; the rest wait on the bus rather than in spawned tasks.
+        tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+        assert_eq!(bus.inbound_len(), 2);
+
+        for _ in 0..4 {
+            tokio::time::timeout(tokio::time::Duration::from_secs(3), bus.consume_outbound())
+                .await
+                .unwrap()
+                .unwrap();
+        }
+        assert_eq!(bus.inbound_len(), 0);
+
+        agent.stop();
+        let _ = tokio::time::timeout(tokio::time::Duration::from_secs(1), run).await;
+    }
+
+    #[tokio::test]
+    async fn test_queued_session_messages_run_in_arrival_order() {
+        let mut config = Config::default();
+        config.gateway.max_concurrent_agents = 2;
+        config.agents.defaults.message_queue_mode = crate::config::MessageQueueMode::Followup;
+        let bus = Arc::new(MessageBus::new());
+        let agent = Arc::new(AgentLoop::new(
+            config,
+            SessionManager::new_memory(),
+            Arc::clone(&bus),
+        ));
+        agent
+            .set_provider(Box::new(ConcurrencyProbeProvider::default()))
+            .await;
+
+        let agent_clone = Arc::clone(&agent);
+        let run = tokio::spawn(async move { agent_clone.start().await });
+
+        // Both slots are taken, then three more messages for chat1 arrive.
+        for (chat, text) in [
+            ("chat1", "first"),
+            ("chat2", "other"),
+            ("chat1", "a"),
+            ("chat1", "b"),
+            ("chat1", "c"),
+        ] {
+            bus.publish_inbound(InboundMessage::new("test", "user1", chat, text))
+                .await
+                .unwrap();
+        }
+
+        let mut chat1 = Vec::new();
+        for _ in 0..5 {
+            let reply =
+                tokio::time::timeout(tokio::time::Duration::from_secs(3), bus.consume_outbound())
+                    .await
+                    .unwrap()
+                    .unwrap();
+            if reply.chat_id == "chat1" {
+                chat1.push(reply.content);
+            }
+        }
+        assert_eq!(chat1, vec!["echo: first", "echo: a", "echo: b", "echo: c"]);
+        assert_eq!(bus.inbound_len(), 0);
+
+        agent.stop();
+        let _ = tokio::time::timeout(tokio::time::Duration::from_secs(1), run).await;
+    }
+
+    #[tokio::test]
+    async fn test_approval_reply_reaches_turn_holding_the_only_permit() {
+        use crate::providers::{LLMResponse, LLMToolCall};
+        use crate::tools::approval::ApprovalPolicyConfig;
+
+        let mut config = Config::default();
+        config.gateway.max_concurrent_agents = 1;
+        config.approval.enabled = true;
+        config.approval.policy = ApprovalPolicyConfig::AlwaysRequire;
+        config.approval.auto_approve_timeout_secs = 0;
+        let bus = Arc::new(MessageBus::new());
+        let agent = Arc::new(AgentLoop::new(
+            config.clone(),
+            SessionManager::new_memory(),
+            Arc::clone(&bus),
+        ));
+        agent.register_tool(Box::new(SleepTool)).await;
+        agent
+            .set_provider(Box::new(ScriptedProvider::new(vec![
+                LLMResponse::with_tools(
+                    "",
+                    vec![LLMToolCall::new("call_1", "sleep", r#"{"ms": 1}"#)],
+                ),
+                LLMResponse::text("approved and rested"),
+            ])))
+            .await;
+        agent
+            .set_approval_broker(Arc::new(AsyncApprovalBroker::new(
+                bus.clone(),
+                &config.approval,
+            )))
+            .await;
+
+        let agent_clone = Arc::clone(&agent);
+        let run = tokio::spawn(async move { agent_clone.start().await });
+
+        bus.publish_inbound(InboundMessage::new("test", "user1", "chat1", "nap"))
+            .await
+            .unwrap();
+        let prompt =
+            tokio::time::timeout(tokio::time::Duration::from_secs(3), bus.consume_outbound())
+                .await
+                .unwrap()
+                .unwrap();
+        assert!(prompt.content.contains("Tool: sleep"), "{}", prompt.content);
+
+        // Another chat queues for the only permit; the approval reply behind
+        // it must still be read.
+        bus.publish_inbound(InboundMessage::new("test", "user2", "chat2", "hello"))
+            .await
+            .unwrap();
+        bus.publish_inbound(InboundMessage::new("test", "user1", "chat1", "yes"))
+            .await
+            .unwrap();
+
+        let reply = tokio::time::timeout(tokio::time::Duration::from_secs(3), async {
+            loop {
+                let msg = bus.consume_outbound().await.unwrap();
+                if msg.chat_id == "chat1" {
+                    return msg;
+                }
+            }
+        })
+        .await
+        .expect("approval reply was never consumed");
+        assert_eq!(reply.content, "approved and rested");
+
+        agent.stop();
+        let _ = tokio::time::timeout(tokio::time::Duration::from_secs(1), run).await;
+    }
+
+    #[tokio::test]
+    async fn test_start_throttles_chat_over_rate_limit() {
+        let config = Config {
+            gateway: crate::config::GatewayConfig {
+                rate_limit: crate::gateway::ChatRateLimitConfig {
+                    enabled: true,
+                    messages_per_minute: 1,
+                    burst: 1,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let bus = Arc::new(MessageBus::new());
+        let agent = Arc::new(AgentLoop::new(
+            config,
+            SessionManager::new_memory(),
+            Arc::clone(&bus),
+        ));
+        agent
+            .set_provider(Box::new(ScriptedProvider::new(vec![
+                crate::providers::LLMResponse::text("first answer"),
+            ])))
+            .await;
+
+        let agent_clone = Arc::clone(&agent);
+        let run = tokio::spawn(async move { agent_clone.start().await });
+
+        for text in ["one", "two"] {
+            bus.publish_inbound(InboundMessage::new("test", "user1", "chat1", text))
+                .await
+                .unwrap();
+        }
+
+        // The throttle notice is sent from the inbound loop while the first
+        // turn is still running in its own task, so arrival order may vary.
+        let mut replies = Vec::new();
+        for _ in 0..2 {
+            let reply =
+                tokio::time::timeout(tokio::time::Duration::from_secs(2), bus.consume_outbound())
+                    .await
+                    .unwrap()
+                    .unwrap();
+            replies.push(reply);
+        }
+        assert!(replies.iter().all(|r| r.chat_id == "chat1"));
+        assert!(replies.iter().any(|r| r.content == "first answer"));
+        assert!(replies.iter().any(|r| r.content.contains("slow down")));
+
+        agent.stop();
+        let _ = tokio::time::timeout(tokio::time::Duration::from_millis(200), run).await;
+    }
+
     #[test]
     fn test_context_builder_standalone() {
         let builder = ContextBuilder::new();
@@ -1781,8 +4179,8 @@ mod tests {
 
     #[test]
     fn test_memory_flush_timeout_is_reasonable() {
-        assert!(MEMORY_FLUSH_TIMEOUT_SECS > 0);
-        assert!(MEMORY_FLUSH_TIMEOUT_SECS <= 30);
+        const { assert!(MEMORY_FLUSH_TIMEOUT_SECS > 0) };
+        const { assert!(MEMORY_FLUSH_TIMEOUT_SECS <= 30) };
     }
 
     #[tokio::test]
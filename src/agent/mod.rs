@@ -40,7 +40,7 @@
 //!     let config = Config::default();
 //!     let session_manager = SessionManager::new_memory();
 //!     let bus = Arc::new(MessageBus::new());
-//!     let agent = AgentLoop::new(config, session_manager, bus);
+//!     let agent = Arc::new(AgentLoop::new(config, session_manager, bus));
 //!
 //!     // Configure provider
 //!     let provider = ClaudeProvider::new("your-api-key");
@@ -59,9 +59,17 @@ pub mod compaction;
 mod context;
 pub mod context_monitor;
 mod r#loop;
+pub mod plan;
+pub mod scheduler;
+pub mod tool_cache;
 
 pub use budget::TokenBudget;
 pub use context::{format_message_envelope, ContextBuilder, RuntimeContext};
 pub use context_monitor::{CompactionStrategy, ContextMonitor};
+pub use plan::{PlanApprovalHandler, ToolPlan};
 pub use r#loop::AgentLoop;
-pub use r#loop::{ToolFeedback, ToolFeedbackPhase};
+pub use r#loop::{
+    ToolCallRecord, ToolFeedback, ToolFeedbackPhase, TurnResult, STREAM_INTERRUPTED_MARKER,
+};
+pub use scheduler::{ToolScheduler, ToolTicket};
+pub use tool_cache::ToolResultCache;
@@ -0,0 +1,182 @@
+//! Plan-first execution ("explain plan" pre-pass).
+//!
+//! When `agents.defaults.plan_first` is enabled, the agent loop asks the model
+//! to enumerate the tool calls it intends to make before anything runs. The
+//! resulting plan is handed to a [`PlanApprovalHandler`]; the turn only
+//! proceeds to tool execution if the handler approves it.
+
+use async_trait::async_trait;
+
+use crate::providers::ToolDefinition;
+use crate::session::Message;
+use crate::tools::approval::{ApprovalResponse, AsyncApprovalBroker};
+
+/// Instruction appended to the conversation for the planning pass.
+const PLAN_PROMPT: &str = "Before taking any action, list the tool calls you intend to make \
+to complete the request above, one per line as `tool_name: purpose`. Do not call any tools \
+and do not answer the request yet. If no tools are needed, reply with `No tool calls.`";
+
+/// A proposed plan of tool calls produced by the planning pass.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolPlan {
+    /// The user request the plan was produced for.
+    pub request: String,
+    /// The model's enumerated plan, as plain text.
+    pub plan: String,
+    /// Channel the request came from.
+    pub channel: String,
+    /// Chat the request came from.
+    pub chat_id: String,
+}
+
+/// Decides whether a proposed [`ToolPlan`] may be executed.
+///
+/// Implementations typically show the plan to a human (CLI prompt, chat
+/// message) and wait for a yes/no answer.
+#[async_trait]
+pub trait PlanApprovalHandler: Send + Sync {
+    /// Review the plan and return the approval decision.
+    async fn review_plan(&self, plan: &ToolPlan) -> ApprovalResponse;
+}
+
+/// Characters of the user request quoted in a chat plan prompt.
+const PROMPT_REQUEST_CHARS: usize = 200;
+
+/// Plans are posted to the chat the request came from and approved by a
+/// "yes"/"no" reply, like tool approvals.
+#[async_trait]
+impl PlanApprovalHandler for AsyncApprovalBroker {
+    async fn review_plan(&self, plan: &ToolPlan) -> ApprovalResponse {
+        let mut request: String = plan.request.chars().take(PROMPT_REQUEST_CHARS).collect();
+        if request.len() < plan.request.len() {
+            request.push_str("...");
+        }
+        let prompt = format!(
+            "Proposed plan for \"{}\":\n{}\n\nReply \"yes\" to run it or \"no\" to cancel.",
+            request, plan.plan
+        );
+        self.request_approval(&plan.channel, &plan.chat_id, &prompt)
+            .await
+    }
+}
+
+/// Build the message list for the planning pass.
+///
+/// The available tool names are listed in the prompt because the planning
+/// call is made without tool definitions, so the model cannot execute anything.
+pub(crate) fn planning_messages(
+    messages: &[Message],
+    tool_definitions: &[ToolDefinition],
+) -> Vec<Message> {
+    let tool_names = tool_definitions
+        .iter()
+        .map(|d| d.name.as_str())
+        .collect::<Vec<_>>();
+    let tools = if tool_names.is_empty() {
+        "(none)".to_string()
+    } else {
+        tool_names.join(", ")
+    };
+
+    let mut planning = messages.to_vec();
+    planning.push(Message::user(&format!(
+        "{}\n\nAvailable tools: {}",
+        PLAN_PROMPT, tools
+    )));
+    planning
+}
+
+/// Text returned to the user when a plan is not approved.
+pub(crate) fn rejection_message(plan: &ToolPlan, response: &ApprovalResponse) -> String {
+    let reason = match response {
+        ApprovalResponse::Approved => return String::new(),
+        ApprovalResponse::Denied(reason) if !reason.is_empty() => reason.as_str(),
+        ApprovalResponse::Denied(_) => "denied",
+        ApprovalResponse::TimedOut => "approval timed out",
+    };
+    format!(
+        "Plan not approved ({}). No tools were executed.\n\nProposed plan:\n{}",
+        reason, plan.plan
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::Role;
+
+    fn plan() -> ToolPlan {
+        ToolPlan {
+            request: "deploy".to_string(),
+            plan: "shell: run deploy script".to_string(),
+            channel: "telegram".to_string(),
+            chat_id: "chat1".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_planning_messages_lists_tools() {
+        let messages = vec![Message::user("deploy the app")];
+        let defs = vec![
+            ToolDefinition::new("shell", "Run commands", serde_json::json!({})),
+            ToolDefinition::new("read_file", "Read a file", serde_json::json!({})),
+        ];
+
+        let planning = planning_messages(&messages, &defs);
+        assert_eq!(planning.len(), 2);
+        let last = planning.last().unwrap();
+        assert_eq!(last.role, Role::User);
+        assert!(last.content.contains("Do not call any tools"));
+        assert!(last.content.contains("Available tools: shell, read_file"));
+    }
+
+    #[test]
+    fn test_planning_messages_without_tools() {
+        let planning = planning_messages(&[], &[]);
+        assert!(planning[0].content.contains("Available tools: (none)"));
+    }
+
+    #[test]
+    fn test_rejection_message_includes_reason_and_plan() {
+        let msg = rejection_message(&plan(), &ApprovalResponse::Denied("too risky".into()));
+        assert!(msg.contains("too risky"));
+        assert!(msg.contains("shell: run deploy script"));
+
+        let msg = rejection_message(&plan(), &ApprovalResponse::TimedOut);
+        assert!(msg.contains("approval timed out"));
+
+        assert!(rejection_message(&plan(), &ApprovalResponse::Approved).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_broker_reviews_plan_in_originating_chat() {
+        use std::sync::Arc;
+
+        use crate::bus::{InboundMessage, MessageBus};
+        use crate::tools::approval::ApprovalConfig;
+
+        let bus = Arc::new(MessageBus::new());
+        let broker = Arc::new(AsyncApprovalBroker::new(
+            bus.clone(),
+            &ApprovalConfig::default(),
+        ));
+
+        for (reply, expected) in [
+            ("yes", ApprovalResponse::Approved),
+            ("no", ApprovalResponse::Denied("denied by user".into())),
+        ] {
+            let task_broker = broker.clone();
+            let handle = tokio::spawn(async move { task_broker.review_plan(&plan()).await });
+            let prompt = bus.consume_outbound().await.unwrap();
+            assert_eq!(
+                (prompt.channel.as_str(), prompt.chat_id.as_str()),
+                ("telegram", "chat1")
+            );
+            assert!(prompt.content.contains("Proposed plan for \"deploy\""));
+            assert!(prompt.content.contains("shell: run deploy script"));
+
+            assert!(broker.try_resolve(&InboundMessage::new("telegram", "u1", "chat1", reply)));
+            assert_eq!(handle.await.unwrap(), expected);
+        }
+    }
+}
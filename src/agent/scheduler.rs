@@ -0,0 +1,249 @@
+//! Concurrency control for the tool calls of a single turn.
+//!
+//! Tools that report [`Tool::is_parallel_safe`](crate::tools::Tool::is_parallel_safe)
+//! run concurrently, up to a fixed number at a time. Every other tool runs
+//! alone: it waits for the calls before it to finish and blocks the calls
+//! after it until it is done. Each call reserves a [`ToolTicket`] up front,
+//! before any hook or approval wait, and permits are granted in ticket order,
+//! so the relative order of mutating calls matches the order the model
+//! emitted them.
+
+use std::collections::BTreeSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::{
+    watch, OwnedRwLockReadGuard, OwnedRwLockWriteGuard, OwnedSemaphorePermit, RwLock, Semaphore,
+};
+
+/// Hands out execution permits for one batch of tool calls.
+///
+/// Cloning is cheap and every clone shares the same limits.
+#[derive(Clone)]
+pub struct ToolScheduler {
+    /// Bounds how many tools run at once.
+    slots: Arc<Semaphore>,
+    /// Shared by parallel-safe tools, exclusive for everything else.
+    exclusive: Arc<RwLock<()>>,
+    /// Ticket bookkeeping that keeps permits in reservation order.
+    queue: Arc<TicketQueue>,
+}
+
+/// Tracks which reserved tickets have taken their turn.
+struct TicketQueue {
+    /// Next ticket number to hand out.
+    issued: AtomicUsize,
+    /// Tickets that are done but whose predecessors are not.
+    done: Mutex<BTreeSet<usize>>,
+    /// The ticket whose turn it is to take the locks.
+    turn: watch::Sender<usize>,
+}
+
+impl TicketQueue {
+    /// Mark `ticket` as done and advance past every finished ticket.
+    fn finish(&self, ticket: usize) {
+        let mut done = self.done.lock().unwrap_or_else(|e| e.into_inner());
+        done.insert(ticket);
+        self.turn.send_modify(|turn| {
+            while done.remove(turn) {
+                *turn += 1;
+            }
+        });
+    }
+}
+
+/// A call's place in line, reserved before it waits on anything else.
+///
+/// Dropping a ticket without acquiring (for example when the call is blocked
+/// or denied) gives up its place so later calls are not held back.
+pub struct ToolTicket {
+    scheduler: ToolScheduler,
+    number: usize,
+    finished: bool,
+}
+
+/// Held while a tool executes; dropping it lets the next call start.
+pub struct ToolPermit {
+    _slot: OwnedSemaphorePermit,
+    _shared: Option<OwnedRwLockReadGuard<()>>,
+    _exclusive: Option<OwnedRwLockWriteGuard<()>>,
+}
+
+impl ToolScheduler {
+    /// Create a scheduler running at most `max_parallel` tools at once
+    /// (minimum 1, which runs every call sequentially).
+    pub fn new(max_parallel: usize) -> Self {
+        Self {
+            slots: Arc::new(Semaphore::new(max_parallel.max(1))),
+            exclusive: Arc::new(RwLock::new(())),
+            queue: Arc::new(TicketQueue {
+                issued: AtomicUsize::new(0),
+                done: Mutex::new(BTreeSet::new()),
+                turn: watch::channel(0).0,
+            }),
+        }
+    }
+
+    /// Reserve the next place in line. Call this in request order, before
+    /// awaiting anything that may take longer for one call than another.
+    pub fn reserve(&self) -> ToolTicket {
+        ToolTicket {
+            scheduler: self.clone(),
+            number: self.queue.issued.fetch_add(1, Ordering::SeqCst),
+            finished: false,
+        }
+    }
+
+    /// Reserve a place and wait until a tool may run.
+    pub async fn acquire(&self, parallel_safe: bool) -> ToolPermit {
+        self.reserve().acquire(parallel_safe).await
+    }
+}
+
+impl ToolTicket {
+    /// Wait for every earlier ticket to take its permit or give up its place,
+    /// then wait until the tool may run.
+    pub async fn acquire(mut self, parallel_safe: bool) -> ToolPermit {
+        let scheduler = self.scheduler.clone();
+        let mut turn = scheduler.queue.turn.subscribe();
+        turn.wait_for(|turn| *turn == self.number)
+            .await
+            .expect("tool scheduler keeps its turn sender alive");
+
+        // Take the ordering lock before a slot: whoever holds the exclusive
+        // lock is then guaranteed a free slot.
+        let lock = Arc::clone(&scheduler.exclusive);
+        let (shared, exclusive) = if parallel_safe {
+            (Some(lock.read_owned().await), None)
+        } else {
+            (None, Some(lock.write_owned().await))
+        };
+        let slot = Arc::clone(&scheduler.slots)
+            .acquire_owned()
+            .await
+            .expect("tool scheduler semaphore is never closed");
+        self.finish();
+        ToolPermit {
+            _slot: slot,
+            _shared: shared,
+            _exclusive: exclusive,
+        }
+    }
+
+    fn finish(&mut self) {
+        if !self.finished {
+            self.finished = true;
+            self.scheduler.queue.finish(self.number);
+        }
+    }
+}
+
+impl Drop for ToolTicket {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    /// Run `calls` through the scheduler and return the peak concurrency.
+    async fn peak_concurrency(scheduler: ToolScheduler, calls: &[bool]) -> usize {
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let futures = calls.iter().map(|&parallel_safe| {
+            let scheduler = scheduler.clone();
+            let running = Arc::clone(&running);
+            let peak = Arc::clone(&peak);
+            async move {
+                let _permit = scheduler.acquire(parallel_safe).await;
+                let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                running.fetch_sub(1, Ordering::SeqCst);
+            }
+        });
+        futures::future::join_all(futures).await;
+        peak.load(Ordering::SeqCst)
+    }
+
+    #[tokio::test]
+    async fn test_read_only_tools_run_concurrently() {
+        assert_eq!(
+            peak_concurrency(ToolScheduler::new(4), &[true, true]).await,
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mutating_tools_serialize() {
+        let start = std::time::Instant::now();
+        assert_eq!(
+            peak_concurrency(ToolScheduler::new(4), &[false, false]).await,
+            1
+        );
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
+
+    #[tokio::test]
+    async fn test_mutating_tool_excludes_readers() {
+        assert_eq!(
+            peak_concurrency(ToolScheduler::new(4), &[true, false, true]).await,
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_parallelism_is_bounded() {
+        let calls = [true; 6];
+        assert_eq!(peak_concurrency(ToolScheduler::new(2), &calls).await, 2);
+        assert_eq!(peak_concurrency(ToolScheduler::new(0), &calls).await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_exclusive_calls_keep_request_order() {
+        let scheduler = ToolScheduler::new(4);
+        let order = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let futures = (0..4).map(|i| {
+            let scheduler = scheduler.clone();
+            let order = Arc::clone(&order);
+            async move {
+                let _permit = scheduler.acquire(false).await;
+                order.lock().unwrap().push(i);
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        });
+        futures::future::join_all(futures).await;
+        assert_eq!(*order.lock().unwrap(), vec![0, 1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_ticket_order_survives_slow_start() {
+        let scheduler = ToolScheduler::new(4);
+        let order = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let futures = [30u64, 0].into_iter().enumerate().map(|(i, delay)| {
+            let ticket = scheduler.reserve();
+            let order = Arc::clone(&order);
+            async move {
+                tokio::time::sleep(Duration::from_millis(delay)).await;
+                let _permit = ticket.acquire(false).await;
+                order.lock().unwrap().push(i);
+            }
+        });
+        futures::future::join_all(futures).await;
+        assert_eq!(*order.lock().unwrap(), vec![0, 1]);
+    }
+
+    #[tokio::test]
+    async fn test_dropped_ticket_releases_its_place() {
+        let scheduler = ToolScheduler::new(4);
+        let first = scheduler.reserve();
+        let second = scheduler.reserve();
+        drop(first);
+        tokio::time::timeout(Duration::from_secs(1), second.acquire(false))
+            .await
+            .expect("dropped ticket held back the next one");
+    }
+}
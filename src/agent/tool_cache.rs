@@ -0,0 +1,221 @@
+//! Cache of tool results, keyed by tool name and arguments.
+//!
+//! Only tools that opt in through [`Tool::cache_ttl`](crate::tools::Tool::cache_ttl)
+//! are cached, each entry living for the TTL the tool reports. Arguments are
+//! canonicalized (object keys sorted at every level) so `{"a":1,"b":2}` and
+//! `{"b":2,"a":1}` share an entry. When full, the least recently used entry
+//! is evicted.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+
+struct CacheEntry {
+    result: String,
+    expires_at: Instant,
+    /// Logical clock value of the last read or write, for LRU eviction.
+    last_used: u64,
+}
+
+#[derive(Default)]
+struct CacheState {
+    entries: HashMap<String, CacheEntry>,
+    clock: u64,
+}
+
+/// LRU cache of tool results with a per-entry TTL.
+pub struct ToolResultCache {
+    capacity: usize,
+    state: Mutex<CacheState>,
+}
+
+impl ToolResultCache {
+    /// Create a cache holding at most `capacity` results (minimum 1).
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            state: Mutex::new(CacheState::default()),
+        }
+    }
+
+    /// Cached result for `tool` called with `args`, if present and fresh.
+    pub fn get(&self, tool: &str, args: &Value) -> Option<String> {
+        let key = cache_key(tool, args);
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        match state.entries.get(&key) {
+            Some(entry) if entry.expires_at <= now => {
+                state.entries.remove(&key);
+                None
+            }
+            Some(_) => {
+                state.clock += 1;
+                let tick = state.clock;
+                let entry = state.entries.get_mut(&key)?;
+                entry.last_used = tick;
+                Some(entry.result.clone())
+            }
+            None => None,
+        }
+    }
+
+    /// Store the result of `tool` called with `args` for `ttl`.
+    pub fn insert(&self, tool: &str, args: &Value, result: String, ttl: Duration) {
+        let key = cache_key(tool, args);
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        if !state.entries.contains_key(&key) && state.entries.len() >= self.capacity {
+            state.entries.retain(|_, entry| entry.expires_at > now);
+            if state.entries.len() >= self.capacity {
+                let oldest = state
+                    .entries
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.last_used)
+                    .map(|(key, _)| key.clone());
+                if let Some(oldest) = oldest {
+                    state.entries.remove(&oldest);
+                }
+            }
+        }
+        state.clock += 1;
+        let last_used = state.clock;
+        state.entries.insert(
+            key,
+            CacheEntry {
+                result,
+                expires_at: now + ttl,
+                last_used,
+            },
+        );
+    }
+
+    /// Drop every cached result.
+    pub fn clear(&self) {
+        self.state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entries
+            .clear();
+    }
+
+    /// Number of entries currently held, including expired ones not yet evicted.
+    pub fn len(&self) -> usize {
+        self.state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entries
+            .len()
+    }
+
+    /// Whether the cache holds no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Tool name plus canonical JSON of the arguments.
+fn cache_key(tool: &str, args: &Value) -> String {
+    let mut key = String::with_capacity(tool.len() + 64);
+    key.push_str(tool);
+    key.push('\0');
+    write_canonical(args, &mut key);
+    key
+}
+
+/// Serialize `value` with object keys sorted, independent of map ordering.
+fn write_canonical(value: &Value, out: &mut String) {
+    match value {
+        Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            out.push('{');
+            for (i, key) in keys.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::String(key.clone()).to_string());
+                out.push(':');
+                write_canonical(&map[key], out);
+            }
+            out.push('}');
+        }
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        other => out.push_str(&other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const TTL: Duration = Duration::from_secs(60);
+
+    #[test]
+    fn test_hit_and_miss() {
+        let cache = ToolResultCache::new(8);
+        let args = json!({"url": "https://example.com"});
+        assert!(cache.get("web_fetch", &args).is_none());
+
+        cache.insert("web_fetch", &args, "page".to_string(), TTL);
+        assert_eq!(cache.get("web_fetch", &args).as_deref(), Some("page"));
+        assert!(cache
+            .get("web_fetch", &json!({"url": "https://example.org"}))
+            .is_none());
+        assert!(cache.get("web_search", &args).is_none());
+    }
+
+    #[test]
+    fn test_ttl_expiry() {
+        let cache = ToolResultCache::new(8);
+        let args = json!({"query": "rust"});
+        cache.insert(
+            "web_search",
+            &args,
+            "results".to_string(),
+            Duration::from_millis(20),
+        );
+        assert!(cache.get("web_search", &args).is_some());
+        std::thread::sleep(Duration::from_millis(40));
+        assert!(cache.get("web_search", &args).is_none());
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_argument_order_shares_entry() {
+        let cache = ToolResultCache::new(8);
+        let first: Value =
+            serde_json::from_str(r#"{"query":"rust","opts":{"limit":5,"lang":"en"}}"#).unwrap();
+        let second: Value =
+            serde_json::from_str(r#"{"opts":{"lang":"en","limit":5},"query":"rust"}"#).unwrap();
+        cache.insert("memory_search", &first, "hit".to_string(), TTL);
+        assert_eq!(cache.get("memory_search", &second).as_deref(), Some("hit"));
+        assert_eq!(cache_key("t", &first), cache_key("t", &second));
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let cache = ToolResultCache::new(2);
+        cache.insert("t", &json!({"n": 1}), "one".to_string(), TTL);
+        cache.insert("t", &json!({"n": 2}), "two".to_string(), TTL);
+        // Touch the first entry so the second becomes the eviction candidate.
+        assert!(cache.get("t", &json!({"n": 1})).is_some());
+        cache.insert("t", &json!({"n": 3}), "three".to_string(), TTL);
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get("t", &json!({"n": 1})).is_some());
+        assert!(cache.get("t", &json!({"n": 2})).is_none());
+        assert!(cache.get("t", &json!({"n": 3})).is_some());
+    }
+}
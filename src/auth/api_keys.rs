@@ -0,0 +1,422 @@
+//! Storage for provider API keys.
+//!
+//! `zeptoclaw auth login --api-key` keeps keys out of plaintext `config.json`.
+//! Keys go to the OS keyring ([`KeyringApiKeys`]: macOS Keychain, Windows
+//! Credential Manager, Secret Service on Linux). Where no keyring backend is
+//! reachable, such as a headless server, they fall back to
+//! `~/.zeptoclaw/auth/api_keys.json.enc` ([`ApiKeyStore`]), encrypted with the
+//! same XChaCha20-Poly1305 master key as the OAuth
+//! [`TokenStore`](super::store::TokenStore).
+//!
+//! When the config is loaded, stored keys take precedence over keys in
+//! `config.json`, keyring keys over the encrypted file;
+//! `ZEPTOCLAW_PROVIDERS_*_API_KEY` environment variables still win over all.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use crate::error::{Result, ZeptoError};
+use crate::security::encryption::SecretEncryption;
+
+/// File name of the API key store inside the `auth` directory.
+const API_KEYS_FILE: &str = "api_keys.json.enc";
+
+/// File name of the keyring index inside the `auth` directory.
+const KEYRING_INDEX_FILE: &str = "api_keys.keyring.json";
+
+/// Service name of ZeptoClaw entries in the OS keyring.
+pub const KEYRING_SERVICE: &str = "zeptoclaw";
+
+/// Internal structure for the API keys file (before encryption).
+#[derive(serde::Serialize, serde::Deserialize, Default)]
+struct ApiKeysFile {
+    keys: BTreeMap<String, String>,
+}
+
+/// Encrypted provider API key store.
+pub struct ApiKeyStore {
+    path: PathBuf,
+    encryption: SecretEncryption,
+}
+
+impl ApiKeyStore {
+    /// Create a store at `~/.zeptoclaw/auth/api_keys.json.enc`.
+    pub fn new(encryption: SecretEncryption) -> Self {
+        Self::with_path(Self::default_path(), encryption)
+    }
+
+    /// Create a store at a custom path (for testing).
+    pub fn with_path(path: PathBuf, encryption: SecretEncryption) -> Self {
+        Self { path, encryption }
+    }
+
+    /// Default store location.
+    pub fn default_path() -> PathBuf {
+        Self::path_for_config(&crate::config::Config::path())
+    }
+
+    /// Store location for the config file at `config_path`.
+    ///
+    /// The store lives in the `auth` directory next to the config file, so
+    /// a config loaded from a custom directory never picks up keys from
+    /// `~/.zeptoclaw`.
+    pub fn path_for_config(config_path: &Path) -> PathBuf {
+        auth_dir(config_path).join(API_KEYS_FILE)
+    }
+
+    /// Path of the encrypted store file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns `true` if the store file exists.
+    pub fn exists(&self) -> bool {
+        self.path.exists()
+    }
+
+    /// Stored API key for `provider`.
+    pub fn get(&self, provider: &str) -> Result<Option<String>> {
+        Ok(self.load_file()?.keys.remove(provider))
+    }
+
+    /// Store (or replace) the API key for `provider`.
+    pub fn set(&self, provider: &str, api_key: &str) -> Result<()> {
+        let api_key = api_key.trim();
+        if api_key.is_empty() {
+            return Err(ZeptoError::Config("API key cannot be empty".into()));
+        }
+        let mut file = self.load_file()?;
+        file.keys.insert(provider.to_string(), api_key.to_string());
+        self.save_file(&file)
+    }
+
+    /// Delete the stored key for `provider`.
+    pub fn delete(&self, provider: &str) -> Result<bool> {
+        let mut file = self.load_file()?;
+        let removed = file.keys.remove(provider).is_some();
+        if removed {
+            self.save_file(&file)?;
+        }
+        Ok(removed)
+    }
+
+    /// Provider names with a stored key, sorted.
+    pub fn providers(&self) -> Result<Vec<String>> {
+        Ok(self.load_file()?.keys.into_keys().collect())
+    }
+
+    /// All stored keys by provider name.
+    pub fn all(&self) -> Result<BTreeMap<String, String>> {
+        Ok(self.load_file()?.keys)
+    }
+
+    // ========================================================================
+    // Internal helpers
+    // ========================================================================
+
+    fn load_file(&self) -> Result<ApiKeysFile> {
+        if !self.path.exists() {
+            return Ok(ApiKeysFile::default());
+        }
+
+        let encrypted = std::fs::read_to_string(&self.path).map_err(|e| {
+            ZeptoError::Config(format!(
+                "Failed to read API key store at {:?}: {}",
+                self.path, e
+            ))
+        })?;
+
+        if encrypted.trim().is_empty() {
+            return Ok(ApiKeysFile::default());
+        }
+
+        let json = self.encryption.decrypt(encrypted.trim())?;
+        serde_json::from_str(&json)
+            .map_err(|e| ZeptoError::Config(format!("Failed to parse API key store: {}", e)))
+    }
+
+    fn save_file(&self, file: &ApiKeysFile) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                ZeptoError::Config(format!(
+                    "Failed to create auth directory {:?}: {}",
+                    parent, e
+                ))
+            })?;
+        }
+
+        let json = serde_json::to_string(file)
+            .map_err(|e| ZeptoError::Config(format!("Failed to serialize API keys: {}", e)))?;
+        let encrypted = self.encryption.encrypt(&json)?;
+
+        std::fs::write(&self.path, &encrypted).map_err(|e| {
+            ZeptoError::Config(format!(
+                "Failed to write API key store at {:?}: {}",
+                self.path, e
+            ))
+        })?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let _ = std::fs::set_permissions(&self.path, std::fs::Permissions::from_mode(0o600));
+        }
+
+        Ok(())
+    }
+}
+
+/// Internal structure for the keyring index file (provider names only).
+#[derive(serde::Serialize, serde::Deserialize, Default)]
+struct KeyringIndex {
+    providers: BTreeSet<String>,
+}
+
+/// Provider API keys in the OS keyring.
+///
+/// A keyring cannot list its entries, so the providers with a key in it are
+/// recorded, by name only, in `auth/api_keys.keyring.json` next to the config
+/// file. Only indexed providers are looked up, so loading a config that was
+/// never used with `auth login` never touches the keyring.
+pub struct KeyringApiKeys {
+    index_path: PathBuf,
+}
+
+impl KeyringApiKeys {
+    /// Keyring keys for the default config file.
+    pub fn new() -> Self {
+        Self::for_config(&crate::config::Config::path())
+    }
+
+    /// Keyring keys indexed next to the config file at `config_path`.
+    pub fn for_config(config_path: &Path) -> Self {
+        Self {
+            index_path: auth_dir(config_path).join(KEYRING_INDEX_FILE),
+        }
+    }
+
+    /// Path of the keyring index file.
+    pub fn index_path(&self) -> &Path {
+        &self.index_path
+    }
+
+    /// Returns `true` if any key was stored in the keyring for this config.
+    pub fn exists(&self) -> bool {
+        self.index_path.exists()
+    }
+
+    /// Stored API key for `provider`.
+    pub fn get(&self, provider: &str) -> Result<Option<String>> {
+        if !self.load_index()?.providers.contains(provider) {
+            return Ok(None);
+        }
+        match keyring_entry(provider)?.get_password() {
+            Ok(api_key) => Ok(Some(api_key)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(keyring_error(e)),
+        }
+    }
+
+    /// Store (or replace) the API key for `provider`.
+    ///
+    /// Fails without touching the index when no keyring backend is reachable.
+    pub fn set(&self, provider: &str, api_key: &str) -> Result<()> {
+        let api_key = api_key.trim();
+        if api_key.is_empty() {
+            return Err(ZeptoError::Config("API key cannot be empty".into()));
+        }
+        keyring_entry(provider)?
+            .set_password(api_key)
+            .map_err(keyring_error)?;
+        let mut index = self.load_index()?;
+        if index.providers.insert(provider.to_string()) {
+            self.save_index(&index)?;
+        }
+        Ok(())
+    }
+
+    /// Delete the stored key for `provider`.
+    pub fn delete(&self, provider: &str) -> Result<bool> {
+        let mut index = self.load_index()?;
+        if !index.providers.remove(provider) {
+            return Ok(false);
+        }
+        match keyring_entry(provider)?.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => {}
+            Err(e) => return Err(keyring_error(e)),
+        }
+        self.save_index(&index)?;
+        Ok(true)
+    }
+
+    /// Provider names with a key in the keyring, sorted.
+    ///
+    /// Read from the index alone; the keyring itself is not queried.
+    pub fn providers(&self) -> Result<Vec<String>> {
+        Ok(self.load_index()?.providers.into_iter().collect())
+    }
+
+    /// All stored keys by provider name. Indexed providers whose keyring
+    /// entry has gone missing are skipped.
+    pub fn all(&self) -> Result<BTreeMap<String, String>> {
+        let mut keys = BTreeMap::new();
+        for provider in self.load_index()?.providers {
+            match keyring_entry(&provider)?.get_password() {
+                Ok(api_key) => {
+                    keys.insert(provider, api_key);
+                }
+                Err(keyring::Error::NoEntry) => {}
+                Err(e) => return Err(keyring_error(e)),
+            }
+        }
+        Ok(keys)
+    }
+
+    fn load_index(&self) -> Result<KeyringIndex> {
+        if !self.index_path.exists() {
+            return Ok(KeyringIndex::default());
+        }
+        let json = std::fs::read_to_string(&self.index_path).map_err(|e| {
+            ZeptoError::Config(format!(
+                "Failed to read keyring index at {:?}: {}",
+                self.index_path, e
+            ))
+        })?;
+        serde_json::from_str(&json)
+            .map_err(|e| ZeptoError::Config(format!("Failed to parse keyring index: {}", e)))
+    }
+
+    fn save_index(&self, index: &KeyringIndex) -> Result<()> {
+        if let Some(parent) = self.index_path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                ZeptoError::Config(format!(
+                    "Failed to create auth directory {:?}: {}",
+                    parent, e
+                ))
+            })?;
+        }
+        let json = serde_json::to_string_pretty(index)
+            .map_err(|e| ZeptoError::Config(format!("Failed to serialize keyring index: {}", e)))?;
+        std::fs::write(&self.index_path, json).map_err(|e| {
+            ZeptoError::Config(format!(
+                "Failed to write keyring index at {:?}: {}",
+                self.index_path, e
+            ))
+        })
+    }
+}
+
+impl Default for KeyringApiKeys {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The `auth` directory next to the config file at `config_path`.
+fn auth_dir(config_path: &Path) -> PathBuf {
+    config_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join("auth")
+}
+
+fn keyring_entry(provider: &str) -> Result<keyring::Entry> {
+    keyring::Entry::new(KEYRING_SERVICE, provider).map_err(keyring_error)
+}
+
+fn keyring_error(e: keyring::Error) -> ZeptoError {
+    ZeptoError::Config(format!("OS keyring: {}", e))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn test_store(tmp: &TempDir) -> ApiKeyStore {
+        ApiKeyStore::with_path(
+            tmp.path().join(API_KEYS_FILE),
+            SecretEncryption::from_raw_key(&[0x24u8; 32]),
+        )
+    }
+
+    #[test]
+    fn test_set_get_delete() {
+        let tmp = TempDir::new().unwrap();
+        let store = test_store(&tmp);
+        assert!(!store.exists());
+        assert!(store.get("openai").unwrap().is_none());
+
+        store.set("openai", " sk-openai \n").unwrap();
+        store.set("anthropic", "sk-ant").unwrap();
+        assert_eq!(store.get("openai").unwrap().as_deref(), Some("sk-openai"));
+        assert_eq!(store.providers().unwrap(), vec!["anthropic", "openai"]);
+
+        assert!(store.delete("openai").unwrap());
+        assert!(!store.delete("openai").unwrap());
+        assert_eq!(store.providers().unwrap(), vec!["anthropic"]);
+    }
+
+    #[test]
+    fn test_file_is_encrypted() {
+        let tmp = TempDir::new().unwrap();
+        let store = test_store(&tmp);
+        store.set("groq", "gsk-secret-value").unwrap();
+
+        let raw = std::fs::read_to_string(store.path()).unwrap();
+        assert!(!raw.contains("gsk-secret-value"));
+        assert!(!raw.contains("groq"));
+
+        let wrong_key = ApiKeyStore::with_path(
+            store.path().to_path_buf(),
+            SecretEncryption::from_raw_key(&[0x99u8; 32]),
+        );
+        assert!(wrong_key.get("groq").is_err());
+    }
+
+    #[test]
+    fn test_rejects_empty_key_and_locates_next_to_config() {
+        let tmp = TempDir::new().unwrap();
+        assert!(test_store(&tmp).set("openai", "   ").is_err());
+
+        let path = ApiKeyStore::path_for_config(Path::new("/etc/zeptoclaw/config.json"));
+        assert_eq!(path, PathBuf::from("/etc/zeptoclaw/auth/api_keys.json.enc"));
+    }
+
+    #[test]
+    fn test_keyring_index_without_logins_skips_keyring() {
+        let tmp = TempDir::new().unwrap();
+        let keys = KeyringApiKeys::for_config(&tmp.path().join("config.json"));
+        assert!(!keys.exists());
+        assert_eq!(
+            keys.index_path(),
+            tmp.path().join("auth").join(KEYRING_INDEX_FILE)
+        );
+        // With nothing indexed these never reach the OS keyring
+        assert!(keys.providers().unwrap().is_empty());
+        assert!(keys.all().unwrap().is_empty());
+        assert!(keys.get("openai").unwrap().is_none());
+        assert!(!keys.delete("openai").unwrap());
+        assert!(keys.set("openai", "  ").is_err());
+        assert!(!keys.exists());
+    }
+
+    #[test]
+    fn test_keyring_index_lists_providers_without_secrets() {
+        let tmp = TempDir::new().unwrap();
+        let keys = KeyringApiKeys::for_config(&tmp.path().join("config.json"));
+        let index = KeyringIndex {
+            providers: BTreeSet::from(["openai".to_string(), "groq".to_string()]),
+        };
+        keys.save_index(&index).unwrap();
+
+        assert_eq!(keys.providers().unwrap(), vec!["groq", "openai"]);
+        let raw = std::fs::read_to_string(keys.index_path()).unwrap();
+        assert!(raw.contains("openai"));
+        assert!(!raw.contains("sk-"));
+    }
+}
@@ -7,6 +7,7 @@
 //! Terms of Service. This module includes graceful fallback to API keys when
 //! OAuth tokens are rejected.
 
+pub mod api_keys;
 pub mod oauth;
 pub mod refresh;
 pub mod store;
@@ -50,8 +51,9 @@ impl AuthMethod {
 /// A resolved credential ready for use in API calls.
 ///
 /// This is the output of the credential resolution process that considers
-/// OAuth tokens, API keys, and the configured auth method.
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// OAuth tokens, API keys, and the configured auth method. `Debug` output
+/// masks the secret.
+#[derive(Clone, PartialEq, Eq)]
 pub enum ResolvedCredential {
     /// Traditional API key.
     ApiKey(String),
@@ -62,6 +64,20 @@ pub enum ResolvedCredential {
     },
 }
 
+impl std::fmt::Debug for ResolvedCredential {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use crate::utils::redact::REDACTED;
+        match self {
+            Self::ApiKey(_) => f.debug_tuple("ApiKey").field(&REDACTED).finish(),
+            Self::BearerToken { expires_at, .. } => f
+                .debug_struct("BearerToken")
+                .field("access_token", &REDACTED)
+                .field("expires_at", expires_at)
+                .finish(),
+        }
+    }
+}
+
 impl ResolvedCredential {
     /// Returns the credential value (API key or access token).
     pub fn value(&self) -> &str {
@@ -6,6 +6,7 @@
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 
+use crate::agent::ToolCallRecord;
 use crate::error::{Result, ZeptoError};
 
 /// Configuration for batch processing.
@@ -54,6 +55,9 @@ pub struct BatchResult {
     pub error: Option<String>,
     /// Time taken to process this prompt, in milliseconds.
     pub duration_ms: u64,
+    /// Tool calls made while answering this prompt.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tool_calls: Vec<ToolCallRecord>,
 }
 
 /// Load prompts from a file.
@@ -215,6 +219,7 @@ mod tests {
             response: Some("Hi there".to_string()),
             error: None,
             duration_ms: 150,
+            tool_calls: Vec::new(),
         };
 
         assert_eq!(result.index, 0);
@@ -346,6 +351,7 @@ mod tests {
                 response: Some("A systems programming language.".to_string()),
                 error: None,
                 duration_ms: 100,
+                tool_calls: Vec::new(),
             },
             BatchResult {
                 index: 1,
@@ -353,6 +359,7 @@ mod tests {
                 response: Some("The Rust package manager.".to_string()),
                 error: None,
                 duration_ms: 80,
+                tool_calls: Vec::new(),
             },
         ];
 
@@ -374,6 +381,7 @@ mod tests {
             response: Some("Hi".to_string()),
             error: None,
             duration_ms: 50,
+            tool_calls: Vec::new(),
         }];
 
         let output = format_results(&results, &BatchOutputFormat::Jsonl);
@@ -385,6 +393,37 @@ mod tests {
         assert!(parsed.error.is_none());
     }
 
+    #[test]
+    fn test_format_results_jsonl_includes_tool_calls() {
+        let results = vec![BatchResult {
+            index: 0,
+            prompt: "Read it".to_string(),
+            response: Some("Done".to_string()),
+            error: None,
+            duration_ms: 50,
+            tool_calls: vec![ToolCallRecord {
+                id: "call_1".to_string(),
+                name: "read_file".to_string(),
+                arguments: r#"{"path":"a.txt"}"#.to_string(),
+                result: "contents".to_string(),
+                latency_ms: 3,
+                success: true,
+            }],
+        }];
+
+        let output = format_results(&results, &BatchOutputFormat::Jsonl);
+        let parsed: BatchResult = serde_json::from_str(&output).expect("Should be valid JSON");
+        assert_eq!(parsed.tool_calls, results[0].tool_calls);
+
+        // Results without tool calls omit the field entirely.
+        let plain = vec![BatchResult {
+            tool_calls: Vec::new(),
+            ..results[0].clone()
+        }];
+        let output = format_results(&plain, &BatchOutputFormat::Jsonl);
+        assert!(!output.contains("tool_calls"));
+    }
+
     #[test]
     fn test_format_results_with_errors() {
         let results = vec![BatchResult {
@@ -393,6 +432,7 @@ mod tests {
             response: None,
             error: Some("Provider timeout".to_string()),
             duration_ms: 30000,
+            tool_calls: Vec::new(),
         }];
 
         // Text format
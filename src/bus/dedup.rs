@@ -0,0 +1,99 @@
+//! Suppression of repeated outbound messages.
+//!
+//! [`OutboundDedup`] remembers the `(channel, chat_id, content)` of every
+//! outbound message for a fixed window. A message whose tuple was seen
+//! within the window is reported as a duplicate. Entries are kept in
+//! arrival order, so expired ones are dropped from the front on each check
+//! and the set never grows past the messages published in one window.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use super::message::OutboundMessage;
+
+/// `(channel, chat_id, content)` of an outbound message.
+type MessageKey = Arc<(String, String, String)>;
+
+/// Time-indexed set of recently published outbound messages.
+#[derive(Debug)]
+pub(crate) struct OutboundDedup {
+    window: Duration,
+    /// Message keys in publish order, with the time they were seen.
+    order: VecDeque<(Instant, MessageKey)>,
+    /// Latest publish time per message key.
+    seen: HashMap<MessageKey, Instant>,
+}
+
+impl OutboundDedup {
+    pub(crate) fn new(window: Duration) -> Self {
+        Self {
+            window,
+            order: VecDeque::new(),
+            seen: HashMap::new(),
+        }
+    }
+
+    pub(crate) fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// Record `msg` at `now`; returns `true` if the same message was
+    /// already recorded within the window.
+    pub(crate) fn check(&mut self, msg: &OutboundMessage, now: Instant) -> bool {
+        self.evict(now);
+        let key = Arc::new((
+            msg.channel.clone(),
+            msg.chat_id.clone(),
+            msg.content.clone(),
+        ));
+        if self.seen.contains_key(&key) {
+            return true;
+        }
+        self.seen.insert(Arc::clone(&key), now);
+        self.order.push_back((now, key));
+        false
+    }
+
+    fn evict(&mut self, now: Instant) {
+        while let Some(&(at, _)) = self.order.front() {
+            if now.duration_since(at) < self.window {
+                break;
+            }
+            if let Some((_, key)) = self.order.pop_front() {
+                if self.seen.get(&key) == Some(&at) {
+                    self.seen.remove(&key);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duplicates_expire_after_window() {
+        let mut dedup = OutboundDedup::new(Duration::from_secs(10));
+        let start = Instant::now();
+        let msg = OutboundMessage::new("telegram", "chat1", "Sorry, something went wrong");
+
+        assert!(!dedup.check(&msg, start));
+        assert!(dedup.check(&msg, start + Duration::from_secs(9)));
+        assert!(!dedup.check(&msg, start + Duration::from_secs(10)));
+        assert_eq!(dedup.order.len(), 1);
+    }
+
+    #[test]
+    fn test_key_covers_channel_chat_and_content() {
+        let mut dedup = OutboundDedup::new(Duration::from_secs(10));
+        let now = Instant::now();
+
+        assert!(!dedup.check(&OutboundMessage::new("telegram", "chat1", "hi"), now));
+        assert!(!dedup.check(&OutboundMessage::new("slack", "chat1", "hi"), now));
+        assert!(!dedup.check(&OutboundMessage::new("telegram", "chat2", "hi"), now));
+        assert!(!dedup.check(&OutboundMessage::new("telegram", "chat1", "hello"), now));
+        assert!(dedup.check(&OutboundMessage::new("telegram", "chat1", "hi"), now));
+    }
+}
@@ -5,6 +5,7 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::time::Duration;
 
 /// Represents an incoming message from a channel (e.g., Telegram, Discord, etc.)
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,6 +24,9 @@ pub struct InboundMessage {
     pub session_key: String,
     /// Additional metadata key-value pairs
     pub metadata: HashMap<String, String>,
+    /// Delivery priority on the bus
+    #[serde(default, skip_serializing_if = "MessagePriority::is_normal")]
+    pub priority: MessagePriority,
 }
 
 /// Represents an outgoing message to be sent via a channel
@@ -36,6 +40,52 @@ pub struct OutboundMessage {
     pub content: String,
     /// Optional message ID to reply to
     pub reply_to: Option<String>,
+    /// Optional Unix timestamp (milliseconds) after which the message is
+    /// stale and should be dropped instead of delivered
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<i64>,
+    /// Delivery priority on the bus
+    #[serde(default, skip_serializing_if = "MessagePriority::is_normal")]
+    pub priority: MessagePriority,
+}
+
+/// Delivery priority of a bus message.
+///
+/// The bus hands out higher-priority messages first; messages of equal
+/// priority keep their arrival order.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum MessagePriority {
+    /// Background traffic that can wait (e.g. heartbeats, digests)
+    Low,
+    /// Regular chat traffic
+    #[default]
+    Normal,
+    /// System and approval messages that should jump the queue
+    High,
+}
+
+impl MessagePriority {
+    /// Returns true for the default `Normal` priority.
+    pub fn is_normal(&self) -> bool {
+        *self == Self::Normal
+    }
+}
+
+/// An outbound message that could not be delivered, kept for a supervisor
+/// to retry or alert on.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadLetter {
+    /// The undelivered message
+    pub message: OutboundMessage,
+    /// Why the last delivery attempt failed
+    pub reason: String,
+    /// Number of failed delivery attempts so far
+    pub attempts: u32,
+    /// Unix timestamp (milliseconds) of the last failure
+    pub failed_at: i64,
 }
 
 /// Represents a media attachment (image, audio, video, or document)
@@ -91,6 +141,7 @@ impl InboundMessage {
             media: None,
             session_key: format!("{}:{}", channel, chat_id),
             metadata: HashMap::new(),
+            priority: MessagePriority::Normal,
         }
     }
 
@@ -126,6 +177,21 @@ impl InboundMessage {
         self
     }
 
+    /// Sets the bus delivery priority (builder pattern).
+    ///
+    /// # Example
+    /// ```
+    /// use zeptoclaw::bus::message::{InboundMessage, MessagePriority};
+    ///
+    /// let msg = InboundMessage::new("telegram", "user123", "chat456", "yes")
+    ///     .with_priority(MessagePriority::High);
+    /// assert_eq!(msg.priority, MessagePriority::High);
+    /// ```
+    pub fn with_priority(mut self, priority: MessagePriority) -> Self {
+        self.priority = priority;
+        self
+    }
+
     /// Checks if this message has any media attached.
     pub fn has_media(&self) -> bool {
         self.media.is_some()
@@ -153,6 +219,8 @@ impl OutboundMessage {
             chat_id: chat_id.to_string(),
             content: content.to_string(),
             reply_to: None,
+            expires_at: None,
+            priority: MessagePriority::Normal,
         }
     }
 
@@ -171,6 +239,45 @@ impl OutboundMessage {
         self
     }
 
+    /// Sets the bus delivery priority (builder pattern).
+    ///
+    /// # Example
+    /// ```
+    /// use zeptoclaw::bus::message::{MessagePriority, OutboundMessage};
+    ///
+    /// let msg = OutboundMessage::new("telegram", "chat456", "Approve `shell`?")
+    ///     .with_priority(MessagePriority::High);
+    /// assert_eq!(msg.priority, MessagePriority::High);
+    /// ```
+    pub fn with_priority(mut self, priority: MessagePriority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Sets the message to expire `ttl` from now (builder pattern).
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    /// use zeptoclaw::bus::message::OutboundMessage;
+    ///
+    /// let msg = OutboundMessage::new("telegram", "chat456", "Hello")
+    ///     .with_ttl(Duration::from_secs(60));
+    /// assert!(msg.expires_at.is_some());
+    /// assert!(!msg.is_expired());
+    /// ```
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        let ttl_ms = i64::try_from(ttl.as_millis()).unwrap_or(i64::MAX);
+        self.expires_at = Some(chrono::Utc::now().timestamp_millis().saturating_add(ttl_ms));
+        self
+    }
+
+    /// Returns true if the message has an expiry time that has already passed.
+    pub fn is_expired(&self) -> bool {
+        self.expires_at
+            .is_some_and(|at| chrono::Utc::now().timestamp_millis() >= at)
+    }
+
     /// Creates an outbound message as a response to an inbound message.
     ///
     /// # Example
@@ -187,6 +294,39 @@ impl OutboundMessage {
     }
 }
 
+impl DeadLetter {
+    /// Records the first failed delivery of `message`.
+    ///
+    /// # Example
+    /// ```
+    /// use zeptoclaw::bus::message::{DeadLetter, OutboundMessage};
+    ///
+    /// let msg = OutboundMessage::new("telegram", "chat456", "Hello");
+    /// let letter = DeadLetter::new(msg, "channel not running");
+    /// assert_eq!(letter.attempts, 1);
+    ///
+    /// let letter = letter.failed_again("timeout");
+    /// assert_eq!(letter.attempts, 2);
+    /// assert_eq!(letter.reason, "timeout");
+    /// ```
+    pub fn new(message: OutboundMessage, reason: &str) -> Self {
+        Self {
+            message,
+            reason: reason.to_string(),
+            attempts: 1,
+            failed_at: chrono::Utc::now().timestamp_millis(),
+        }
+    }
+
+    /// Records another failed attempt after a retry (builder pattern).
+    pub fn failed_again(mut self, reason: &str) -> Self {
+        self.reason = reason.to_string();
+        self.attempts = self.attempts.saturating_add(1);
+        self.failed_at = chrono::Utc::now().timestamp_millis();
+        self
+    }
+}
+
 impl MediaAttachment {
     /// Creates a new media attachment of the specified type.
     pub fn new(media_type: MediaType) -> Self {
@@ -349,4 +489,40 @@ mod tests {
         assert_eq!(deserialized.channel, "discord");
         assert_eq!(deserialized.reply_to, Some("msg_123".to_string()));
     }
+
+    #[test]
+    fn test_message_priority_serialization() {
+        let normal = OutboundMessage::new("telegram", "chat1", "Hi");
+        assert_eq!(normal.priority, MessagePriority::Normal);
+        assert!(!serde_json::to_string(&normal).unwrap().contains("priority"));
+
+        let high = normal.with_priority(MessagePriority::High);
+        let json = serde_json::to_string(&high).unwrap();
+        assert!(json.contains(r#""priority":"high""#));
+        let parsed: OutboundMessage = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.priority, MessagePriority::High);
+
+        assert!(MessagePriority::High > MessagePriority::Normal);
+        assert!(MessagePriority::Normal > MessagePriority::Low);
+    }
+
+    #[test]
+    fn test_outbound_message_without_ttl_never_expires() {
+        let msg = OutboundMessage::new("telegram", "chat1", "Hi");
+        assert!(msg.expires_at.is_none());
+        assert!(!msg.is_expired());
+
+        let json = serde_json::to_string(&msg).unwrap();
+        assert!(!json.contains("expires_at"));
+    }
+
+    #[test]
+    fn test_outbound_message_expiry() {
+        let fresh =
+            OutboundMessage::new("telegram", "chat1", "Hi").with_ttl(Duration::from_secs(60));
+        assert!(!fresh.is_expired());
+
+        let stale = OutboundMessage::new("telegram", "chat1", "Hi").with_ttl(Duration::ZERO);
+        assert!(stale.is_expired());
+    }
 }
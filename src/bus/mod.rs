@@ -39,36 +39,91 @@
 //!     }
 //! }
 //! ```
+//!
+//! # Priority and backpressure
+//!
+//! Both directions are bounded priority queues. Messages marked
+//! [`MessagePriority::High`] (approval prompts, system notices) are consumed
+//! before earlier `Normal` and `Low` ones; equal priorities stay FIFO. When a
+//! queue is full, `publish_*` waits for space and `try_publish_*` returns
+//! [`ZeptoError::BusFull`](crate::error::ZeptoError::BusFull).
+//!
+//! # Dead letters
+//!
+//! Outbound messages a channel failed to deliver are parked in a third
+//! queue as [`DeadLetter`]s (message, failure reason, attempt count) so a
+//! supervisor can retry or alert via [`MessageBus::consume_dead_letter`].
+//!
+//! # De-duplication
+//!
+//! A bus built with [`MessageBus::with_outbound_dedup_window`] drops an
+//! outbound message when one with the same channel, chat ID and content was
+//! published within the window, so a retry loop and an error hook cannot
+//! send the same notice several times. It is off by default.
 
+mod dedup;
 pub mod message;
+pub mod queue;
 
-pub use message::{InboundMessage, MediaAttachment, MediaType, OutboundMessage};
+pub use message::{
+    DeadLetter, InboundMessage, MediaAttachment, MediaType, MessagePriority, OutboundMessage,
+};
+pub use queue::{MessageQueue, QueueSender};
 
-use crate::error::{Result, ZeptoError};
-use std::sync::Arc;
-use tokio::sync::mpsc;
-use tokio::sync::Mutex;
+use crate::error::Result;
+use dedup::OutboundDedup;
+use queue::Prioritized;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+use tracing::debug;
 
 /// Default buffer size for message channels
-const DEFAULT_BUFFER_SIZE: usize = 100;
+pub const DEFAULT_BUFFER_SIZE: usize = 100;
+
+impl Prioritized for InboundMessage {
+    fn priority(&self) -> MessagePriority {
+        self.priority
+    }
+}
+
+impl Prioritized for OutboundMessage {
+    fn priority(&self) -> MessagePriority {
+        self.priority
+    }
+}
+
+impl Prioritized for DeadLetter {
+    fn priority(&self) -> MessagePriority {
+        self.message.priority
+    }
+}
 
 /// The central message bus for routing messages between channels and agents.
 ///
-/// The `MessageBus` maintains two separate channels:
+/// The `MessageBus` maintains two separate queues:
 /// - **Inbound**: Messages from channels (e.g., Telegram) to agents
 /// - **Outbound**: Messages from agents back to channels
 ///
-/// Both channels use async MPSC (multi-producer, single-consumer) queues
-/// backed by Tokio, allowing for high-throughput message passing.
+/// Each is a bounded [`MessageQueue`] ordered by priority, then arrival.
+#[derive(Clone)]
 pub struct MessageBus {
-    /// Sender for inbound messages
-    inbound_tx: mpsc::Sender<InboundMessage>,
-    /// Receiver for inbound messages (wrapped in Arc<Mutex> for shared access)
-    inbound_rx: Arc<Mutex<mpsc::Receiver<InboundMessage>>>,
+    /// Queue of inbound messages
+    inbound: MessageQueue<InboundMessage>,
+    /// Sender for inbound messages; keeps the queue open while the bus lives
+    inbound_tx: QueueSender<InboundMessage>,
+    /// Queue of outbound messages
+    outbound: MessageQueue<OutboundMessage>,
     /// Sender for outbound messages
-    outbound_tx: mpsc::Sender<OutboundMessage>,
-    /// Receiver for outbound messages (wrapped in Arc<Mutex> for shared access)
-    outbound_rx: Arc<Mutex<mpsc::Receiver<OutboundMessage>>>,
+    outbound_tx: QueueSender<OutboundMessage>,
+    /// Queue of outbound messages that failed delivery
+    dead_letters: MessageQueue<DeadLetter>,
+    /// Sender for dead letters
+    dead_letter_tx: QueueSender<DeadLetter>,
+    /// Per-channel time-to-live applied to outbound messages at publish time
+    outbound_ttls: Arc<RwLock<HashMap<String, Duration>>>,
+    /// Recently published outbound messages, when de-duplication is enabled
+    outbound_dedup: Option<Arc<Mutex<OutboundDedup>>>,
 }
 
 impl MessageBus {
@@ -89,7 +144,8 @@ impl MessageBus {
     /// Creates a new `MessageBus` with a custom buffer size.
     ///
     /// # Arguments
-    /// * `buffer_size` - The maximum number of messages that can be buffered
+    /// * `buffer_size` - The maximum number of messages each queue can buffer
+    ///   (minimum 1)
     ///
     /// # Example
     /// ```
@@ -98,28 +154,137 @@ impl MessageBus {
     /// let bus = MessageBus::with_buffer_size(500);
     /// ```
     pub fn with_buffer_size(buffer_size: usize) -> Self {
-        let (inbound_tx, inbound_rx) = mpsc::channel(buffer_size);
-        let (outbound_tx, outbound_rx) = mpsc::channel(buffer_size);
-
+        let inbound = MessageQueue::new("inbound", buffer_size);
+        let outbound = MessageQueue::new("outbound", buffer_size);
+        let dead_letters = MessageQueue::new("dead-letter", buffer_size);
         Self {
-            inbound_tx,
-            inbound_rx: Arc::new(Mutex::new(inbound_rx)),
-            outbound_tx,
-            outbound_rx: Arc::new(Mutex::new(outbound_rx)),
+            inbound_tx: inbound.sender(),
+            inbound,
+            outbound_tx: outbound.sender(),
+            outbound,
+            dead_letter_tx: dead_letters.sender(),
+            dead_letters,
+            outbound_ttls: Arc::new(RwLock::new(HashMap::new())),
+            outbound_dedup: None,
         }
     }
 
+    /// Drops outbound messages identical to one published within `window`.
+    ///
+    /// Messages are identical when their channel, chat ID and content all
+    /// match. A zero window leaves de-duplication off.
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    /// use zeptoclaw::bus::MessageBus;
+    ///
+    /// let bus = MessageBus::new().with_outbound_dedup_window(Duration::from_secs(30));
+    /// assert_eq!(bus.outbound_dedup_window(), Some(Duration::from_secs(30)));
+    /// ```
+    pub fn with_outbound_dedup_window(mut self, window: Duration) -> Self {
+        self.outbound_dedup =
+            (!window.is_zero()).then(|| Arc::new(Mutex::new(OutboundDedup::new(window))));
+        self
+    }
+
+    /// Returns the outbound de-duplication window, if enabled.
+    pub fn outbound_dedup_window(&self) -> Option<Duration> {
+        let dedup = self.outbound_dedup.as_ref()?;
+        dedup.lock().ok().map(|dedup| dedup.window())
+    }
+
+    /// Returns the per-queue capacity.
+    pub fn capacity(&self) -> usize {
+        self.inbound.capacity()
+    }
+
+    /// Returns the number of inbound messages waiting to be consumed.
+    pub fn inbound_len(&self) -> usize {
+        self.inbound.len()
+    }
+
+    /// Returns the number of outbound messages waiting to be consumed.
+    pub fn outbound_len(&self) -> usize {
+        self.outbound.len()
+    }
+
+    /// Returns the number of dead letters waiting to be consumed.
+    pub fn dead_letter_len(&self) -> usize {
+        self.dead_letters.len()
+    }
+
+    /// Sets the time-to-live for outbound messages on a channel.
+    ///
+    /// Messages published to that channel without an explicit expiry are
+    /// stamped with `now + ttl`, and the delivery path drops them if they
+    /// are still queued once that time has passed.
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    /// use zeptoclaw::bus::MessageBus;
+    ///
+    /// let bus = MessageBus::new();
+    /// bus.set_outbound_ttl("telegram", Duration::from_secs(300));
+    /// assert_eq!(bus.outbound_ttl("telegram"), Some(Duration::from_secs(300)));
+    /// ```
+    pub fn set_outbound_ttl(&self, channel: &str, ttl: Duration) {
+        if let Ok(mut ttls) = self.outbound_ttls.write() {
+            ttls.insert(channel.to_string(), ttl);
+        }
+    }
+
+    /// Returns the outbound time-to-live configured for a channel, if any.
+    pub fn outbound_ttl(&self, channel: &str) -> Option<Duration> {
+        self.outbound_ttls
+            .read()
+            .ok()
+            .and_then(|ttls| ttls.get(channel).copied())
+    }
+
+    /// Stamps an expiry on the message from its channel's TTL, unless it
+    /// already carries one.
+    fn apply_outbound_ttl(&self, msg: OutboundMessage) -> OutboundMessage {
+        if msg.expires_at.is_some() {
+            return msg;
+        }
+        match self.outbound_ttl(&msg.channel) {
+            Some(ttl) => msg.with_ttl(ttl),
+            None => msg,
+        }
+    }
+
+    /// Whether `msg` repeats an outbound message still inside the
+    /// de-duplication window.
+    fn is_duplicate_outbound(&self, msg: &OutboundMessage) -> bool {
+        let Some(dedup) = &self.outbound_dedup else {
+            return false;
+        };
+        let duplicate = dedup
+            .lock()
+            .map(|mut dedup| dedup.check(msg, Instant::now()))
+            .unwrap_or(false);
+        if duplicate {
+            debug!(
+                channel = %msg.channel,
+                chat_id = %msg.chat_id,
+                "Dropping duplicate outbound message"
+            );
+        }
+        duplicate
+    }
+
     /// Publishes an inbound message to the bus.
     ///
     /// This is typically called by channel adapters (e.g., Telegram, Discord)
     /// when they receive a message from a user.
     ///
+    /// Waits for space if the inbound queue is full.
+    ///
     /// # Arguments
     /// * `msg` - The inbound message to publish
     ///
-    /// # Errors
-    /// Returns `ZeptoError::BusClosed` if the receiver has been dropped.
-    ///
     /// # Example
     /// ```
     /// use zeptoclaw::bus::{MessageBus, InboundMessage};
@@ -132,19 +297,18 @@ impl MessageBus {
     /// }
     /// ```
     pub async fn publish_inbound(&self, msg: InboundMessage) -> Result<()> {
-        self.inbound_tx
-            .send(msg)
-            .await
-            .map_err(|_| ZeptoError::BusClosed)
+        self.inbound_tx.send(msg).await
     }
 
     /// Consumes the next inbound message from the bus.
     ///
     /// This is typically called by agents waiting for new messages to process.
+    /// Waits until a message is available and returns the highest-priority,
+    /// oldest one.
     ///
     /// # Returns
     /// - `Some(InboundMessage)` if a message is available
-    /// - `None` if the channel is closed (all senders dropped)
+    /// - `None` if the queue is empty and closed (all senders dropped)
     ///
     /// # Example
     /// ```
@@ -165,20 +329,31 @@ impl MessageBus {
     /// }
     /// ```
     pub async fn consume_inbound(&self) -> Option<InboundMessage> {
-        self.inbound_rx.lock().await.recv().await
+        self.inbound.recv().await
+    }
+
+    /// Consumes the next inbound message matching `pred`, leaving other
+    /// messages queued.
+    ///
+    /// Lets the agent loop pick approval replies out of the queue while it
+    /// is not taking new turns.
+    pub async fn consume_inbound_matching(
+        &self,
+        pred: impl Fn(&InboundMessage) -> bool,
+    ) -> Option<InboundMessage> {
+        self.inbound.recv_matching(pred).await
     }
 
     /// Publishes an outbound message to the bus.
     ///
     /// This is typically called by agents when they have a response
-    /// to send back to a user via a channel.
+    /// to send back to a user via a channel. Waits for space if the
+    /// outbound queue is full. Returns `Ok(())` without queueing when the
+    /// message is dropped as a duplicate.
     ///
     /// # Arguments
     /// * `msg` - The outbound message to publish
     ///
-    /// # Errors
-    /// Returns `ZeptoError::BusClosed` if the receiver has been dropped.
-    ///
     /// # Example
     /// ```
     /// use zeptoclaw::bus::{MessageBus, OutboundMessage};
@@ -191,28 +366,64 @@ impl MessageBus {
     /// }
     /// ```
     pub async fn publish_outbound(&self, msg: OutboundMessage) -> Result<()> {
-        self.outbound_tx
-            .send(msg)
-            .await
-            .map_err(|_| ZeptoError::BusClosed)
+        if self.is_duplicate_outbound(&msg) {
+            return Ok(());
+        }
+        let msg = self.apply_outbound_ttl(msg);
+        self.outbound_tx.send(msg).await
     }
 
     /// Consumes the next outbound message from the bus.
     ///
     /// This is typically called by channel adapters waiting for
-    /// responses to send to users.
-    ///
-    /// # Returns
-    /// - `Some(OutboundMessage)` if a message is available
-    /// - `None` if the channel is closed (all senders dropped)
+    /// responses to send to users. Ordering and availability are the same
+    /// as for [`consume_inbound`](Self::consume_inbound).
     pub async fn consume_outbound(&self) -> Option<OutboundMessage> {
-        self.outbound_rx.lock().await.recv().await
+        self.outbound.recv().await
+    }
+
+    /// Parks an outbound message that could not be delivered.
+    ///
+    /// Called by the outbound dispatcher when a channel's `send` fails or the
+    /// target channel is unknown. Never waits: when the dead-letter queue is
+    /// full the letter is dropped and `Err(ZeptoError::BusFull)` is returned.
+    ///
+    /// # Example
+    /// ```
+    /// use zeptoclaw::bus::{MessageBus, OutboundMessage};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let bus = MessageBus::new();
+    ///     let msg = OutboundMessage::new("telegram", "chat456", "Hello");
+    ///     bus.publish_dead_letter(msg, "channel not running").unwrap();
+    ///
+    ///     let letter = bus.consume_dead_letter().await.unwrap();
+    ///     assert_eq!(letter.reason, "channel not running");
+    ///     assert_eq!(letter.attempts, 1);
+    /// }
+    /// ```
+    pub fn publish_dead_letter(&self, msg: OutboundMessage, reason: &str) -> Result<()> {
+        self.dead_letter_tx.try_send(DeadLetter::new(msg, reason))
     }
 
-    /// Returns a clone of the inbound message sender.
+    /// Parks a dead letter again, e.g. after a supervisor's retry failed.
+    ///
+    /// Use [`DeadLetter::failed_again`] first to bump the attempt count.
+    pub fn requeue_dead_letter(&self, letter: DeadLetter) -> Result<()> {
+        self.dead_letter_tx.try_send(letter)
+    }
+
+    /// Consumes the next dead letter, waiting until one is available.
+    pub async fn consume_dead_letter(&self) -> Option<DeadLetter> {
+        self.dead_letters.recv().await
+    }
+
+    /// Returns a sender for inbound messages.
     ///
     /// This is useful for giving multiple channels their own sender
-    /// to publish messages to the bus.
+    /// to publish messages to the bus. Sends go through
+    /// [`publish_inbound`](Self::publish_inbound).
     ///
     /// # Example
     /// ```
@@ -231,44 +442,42 @@ impl MessageBus {
     ///     handle.await.unwrap();
     /// }
     /// ```
-    pub fn inbound_sender(&self) -> mpsc::Sender<InboundMessage> {
-        self.inbound_tx.clone()
+    pub fn inbound_sender(&self) -> InboundSender {
+        InboundSender { bus: self.clone() }
     }
 
-    /// Returns a clone of the outbound message sender.
+    /// Returns a sender for outbound messages.
     ///
     /// This is useful for giving multiple agents their own sender
-    /// to publish responses to the bus.
-    pub fn outbound_sender(&self) -> mpsc::Sender<OutboundMessage> {
-        self.outbound_tx.clone()
+    /// to publish responses to the bus. Sends go through
+    /// [`publish_outbound`](Self::publish_outbound), so per-channel TTLs
+    /// and de-duplication still apply.
+    pub fn outbound_sender(&self) -> OutboundSender {
+        OutboundSender { bus: self.clone() }
     }
 
     /// Tries to publish an inbound message without blocking.
     ///
     /// This is useful in non-async contexts or when you want to
-    /// avoid blocking if the buffer is full.
+    /// signal backpressure instead of waiting for space.
     ///
     /// # Returns
     /// - `Ok(())` if the message was successfully queued
-    /// - `Err(ZeptoError::BusClosed)` if the channel is closed
-    /// - `Err(ZeptoError::Channel)` if the buffer is full
+    /// - `Err(ZeptoError::BusFull)` if the queue is at capacity
     pub fn try_publish_inbound(&self, msg: InboundMessage) -> Result<()> {
-        self.inbound_tx.try_send(msg).map_err(|e| match e {
-            mpsc::error::TrySendError::Full(_) => {
-                ZeptoError::Channel("inbound buffer full".to_string())
-            }
-            mpsc::error::TrySendError::Closed(_) => ZeptoError::BusClosed,
-        })
+        self.inbound_tx.try_send(msg)
     }
 
     /// Tries to publish an outbound message without blocking.
+    ///
+    /// Returns `Err(ZeptoError::BusFull)` if the queue is at capacity.
+    /// Duplicates are dropped as in [`publish_outbound`](Self::publish_outbound).
     pub fn try_publish_outbound(&self, msg: OutboundMessage) -> Result<()> {
-        self.outbound_tx.try_send(msg).map_err(|e| match e {
-            mpsc::error::TrySendError::Full(_) => {
-                ZeptoError::Channel("outbound buffer full".to_string())
-            }
-            mpsc::error::TrySendError::Closed(_) => ZeptoError::BusClosed,
-        })
+        if self.is_duplicate_outbound(&msg) {
+            return Ok(());
+        }
+        let msg = self.apply_outbound_ttl(msg);
+        self.outbound_tx.try_send(msg)
     }
 }
 
@@ -278,23 +487,48 @@ impl Default for MessageBus {
     }
 }
 
-impl Clone for MessageBus {
-    /// Clones the message bus, sharing the same underlying channels.
-    ///
-    /// This allows multiple components to share access to the same bus.
-    fn clone(&self) -> Self {
-        Self {
-            inbound_tx: self.inbound_tx.clone(),
-            inbound_rx: Arc::clone(&self.inbound_rx),
-            outbound_tx: self.outbound_tx.clone(),
-            outbound_rx: Arc::clone(&self.outbound_rx),
-        }
+/// Cloneable handle that publishes inbound messages to a [`MessageBus`].
+#[derive(Clone)]
+pub struct InboundSender {
+    bus: MessageBus,
+}
+
+impl InboundSender {
+    /// Publishes a message, waiting for space if the queue is full.
+    pub async fn send(&self, msg: InboundMessage) -> Result<()> {
+        self.bus.publish_inbound(msg).await
+    }
+
+    /// Publishes a message without waiting; see
+    /// [`MessageBus::try_publish_inbound`].
+    pub fn try_send(&self, msg: InboundMessage) -> Result<()> {
+        self.bus.try_publish_inbound(msg)
+    }
+}
+
+/// Cloneable handle that publishes outbound messages to a [`MessageBus`].
+#[derive(Clone)]
+pub struct OutboundSender {
+    bus: MessageBus,
+}
+
+impl OutboundSender {
+    /// Publishes a message, waiting for space if the queue is full.
+    pub async fn send(&self, msg: OutboundMessage) -> Result<()> {
+        self.bus.publish_outbound(msg).await
+    }
+
+    /// Publishes a message without waiting; see
+    /// [`MessageBus::try_publish_outbound`].
+    pub fn try_send(&self, msg: OutboundMessage) -> Result<()> {
+        self.bus.try_publish_outbound(msg)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::ZeptoError;
 
     #[test]
     fn test_inbound_message_creation() {
@@ -359,6 +593,31 @@ mod tests {
         assert_eq!(received.chat_id, "chat456");
     }
 
+    #[tokio::test]
+    async fn test_outbound_dedup_window() {
+        let bus = MessageBus::new().with_outbound_dedup_window(Duration::from_millis(200));
+        let msg = OutboundMessage::new("telegram", "chat456", "Something went wrong");
+
+        bus.publish_outbound(msg.clone()).await.unwrap();
+        bus.try_publish_outbound(msg.clone()).unwrap();
+        assert_eq!(bus.outbound_len(), 1);
+
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        bus.publish_outbound(msg).await.unwrap();
+        assert_eq!(bus.outbound_len(), 2);
+
+        // Off by default: repeated replies are all delivered
+        let plain = MessageBus::new();
+        assert_eq!(plain.outbound_dedup_window(), None);
+        for _ in 0..2 {
+            plain
+                .publish_outbound(OutboundMessage::new("telegram", "chat456", "ok"))
+                .await
+                .unwrap();
+        }
+        assert_eq!(plain.outbound_len(), 2);
+    }
+
     #[tokio::test]
     async fn test_bus_multiple_messages() {
         let bus = MessageBus::new();
@@ -396,6 +655,21 @@ mod tests {
         assert_eq!(received2.content, "From sender 2");
     }
 
+    #[tokio::test]
+    async fn test_outbound_sender_applies_ttl_and_dedup() {
+        let bus = MessageBus::new().with_outbound_dedup_window(Duration::from_secs(30));
+        bus.set_outbound_ttl("telegram", Duration::from_secs(60));
+        let sender = bus.outbound_sender();
+
+        let msg = OutboundMessage::new("telegram", "chat456", "Hello");
+        sender.send(msg.clone()).await.unwrap();
+        sender.try_send(msg).unwrap();
+        assert_eq!(bus.outbound_len(), 1);
+
+        let received = bus.consume_outbound().await.unwrap();
+        assert!(received.expires_at.is_some());
+    }
+
     #[tokio::test]
     async fn test_bus_concurrent_access() {
         let bus = Arc::new(MessageBus::new());
@@ -439,7 +713,7 @@ mod tests {
         // Third message should fail with buffer full
         let msg3 = InboundMessage::new("test", "user", "chat", "Msg 3");
         let result = bus.try_publish_inbound(msg3);
-        assert!(matches!(result, Err(ZeptoError::Channel(_))));
+        assert!(matches!(result, Err(ZeptoError::BusFull(_))));
     }
 
     #[tokio::test]
@@ -454,7 +728,96 @@ mod tests {
         // Third message should fail
         let msg3 = OutboundMessage::new("test", "chat", "Msg 3");
         let result = bus.try_publish_outbound(msg3);
-        assert!(matches!(result, Err(ZeptoError::Channel(_))));
+        assert!(matches!(result, Err(ZeptoError::BusFull(_))));
+    }
+
+    #[tokio::test]
+    async fn test_high_priority_inbound_consumed_first() {
+        let bus = MessageBus::new();
+        bus.publish_inbound(InboundMessage::new("test", "user", "chat", "chatter"))
+            .await
+            .unwrap();
+        bus.publish_inbound(
+            InboundMessage::new("test", "user", "chat", "approve")
+                .with_priority(MessagePriority::High),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(bus.inbound_len(), 2);
+        assert_eq!(bus.consume_inbound().await.unwrap().content, "approve");
+        assert_eq!(bus.consume_inbound().await.unwrap().content, "chatter");
+    }
+
+    #[tokio::test]
+    async fn test_outbound_priority_then_arrival_order() {
+        let bus = MessageBus::new();
+        let publish = [
+            ("low", MessagePriority::Low),
+            ("normal 1", MessagePriority::Normal),
+            ("high", MessagePriority::High),
+            ("normal 2", MessagePriority::Normal),
+        ];
+        for (content, priority) in publish {
+            bus.publish_outbound(
+                OutboundMessage::new("test", "chat", content).with_priority(priority),
+            )
+            .await
+            .unwrap();
+        }
+
+        let mut order = Vec::new();
+        while bus.outbound_len() > 0 {
+            order.push(bus.consume_outbound().await.unwrap().content);
+        }
+        assert_eq!(order, vec!["high", "normal 1", "normal 2", "low"]);
+    }
+
+    #[tokio::test]
+    async fn test_try_publish_full_even_for_high_priority() {
+        let bus = MessageBus::with_buffer_size(1);
+        assert_eq!(bus.capacity(), 1);
+        bus.try_publish_outbound(OutboundMessage::new("test", "chat", "first"))
+            .unwrap();
+
+        let urgent =
+            OutboundMessage::new("test", "chat", "urgent").with_priority(MessagePriority::High);
+        let err = bus.try_publish_outbound(urgent);
+        assert!(matches!(err, Err(ZeptoError::BusFull(ref q)) if q == "outbound"));
+
+        // Draining frees space again
+        bus.consume_outbound().await.unwrap();
+        bus.try_publish_outbound(OutboundMessage::new("test", "chat", "again"))
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_dead_letter_roundtrip() {
+        let bus = MessageBus::new();
+        bus.publish_dead_letter(OutboundMessage::new("slack", "c1", "lost"), "not running")
+            .unwrap();
+        assert_eq!(bus.dead_letter_len(), 1);
+
+        let letter = bus.consume_dead_letter().await.unwrap();
+        assert_eq!(letter.message.content, "lost");
+        assert_eq!(letter.reason, "not running");
+        assert_eq!(letter.attempts, 1);
+
+        bus.requeue_dead_letter(letter.failed_again("still down"))
+            .unwrap();
+        let letter = bus.consume_dead_letter().await.unwrap();
+        assert_eq!(letter.attempts, 2);
+        assert_eq!(letter.reason, "still down");
+        assert_eq!(bus.dead_letter_len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_dead_letter_queue_full_does_not_block() {
+        let bus = MessageBus::with_buffer_size(1);
+        bus.publish_dead_letter(OutboundMessage::new("a", "c", "1"), "x")
+            .unwrap();
+        let err = bus.publish_dead_letter(OutboundMessage::new("a", "c", "2"), "x");
+        assert!(matches!(err, Err(ZeptoError::BusFull(ref q)) if q == "dead-letter"));
     }
 
     #[tokio::test]
@@ -510,4 +873,46 @@ mod tests {
         assert_eq!(outgoing.chat_id, "chat456");
         assert_eq!(outgoing.content, "Hello human!");
     }
+
+    #[tokio::test]
+    async fn test_publish_outbound_stamps_channel_ttl() {
+        let bus = MessageBus::new();
+        bus.set_outbound_ttl("telegram", Duration::from_secs(60));
+
+        bus.publish_outbound(OutboundMessage::new("telegram", "c1", "ttl"))
+            .await
+            .unwrap();
+        bus.publish_outbound(OutboundMessage::new("slack", "c1", "no ttl"))
+            .await
+            .unwrap();
+
+        let stamped = bus.consume_outbound().await.unwrap();
+        assert!(stamped.expires_at.is_some());
+        assert!(!stamped.is_expired());
+
+        let unstamped = bus.consume_outbound().await.unwrap();
+        assert!(unstamped.expires_at.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_publish_outbound_keeps_explicit_expiry() {
+        let bus = MessageBus::new();
+        bus.set_outbound_ttl("telegram", Duration::from_secs(60));
+
+        let mut msg = OutboundMessage::new("telegram", "c1", "explicit");
+        msg.expires_at = Some(42);
+        bus.publish_outbound(msg).await.unwrap();
+
+        let received = bus.consume_outbound().await.unwrap();
+        assert_eq!(received.expires_at, Some(42));
+    }
+
+    #[test]
+    fn test_outbound_ttl_shared_between_clones() {
+        let bus1 = MessageBus::new();
+        let bus2 = bus1.clone();
+        bus1.set_outbound_ttl("discord", Duration::from_secs(5));
+        assert_eq!(bus2.outbound_ttl("discord"), Some(Duration::from_secs(5)));
+        assert_eq!(bus2.outbound_ttl("telegram"), None);
+    }
 }
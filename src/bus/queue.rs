@@ -0,0 +1,420 @@
+//! Bounded priority queue backing the message bus.
+//!
+//! Items are handed out highest [`MessagePriority`] first and, within a
+//! priority level, in arrival order. The queue holds at most `capacity`
+//! items: [`MessageQueue::send`] waits for space, while
+//! [`MessageQueue::try_send`] fails fast with [`ZeptoError::BusFull`].
+//!
+//! Producers hold a [`QueueSender`]. Once every sender is dropped and the
+//! queue has drained, receivers get `None`.
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::sync::atomic::{self, AtomicUsize};
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::Notify;
+
+use crate::error::{Result, ZeptoError};
+
+use super::message::MessagePriority;
+
+/// A message that carries a delivery priority.
+pub trait Prioritized {
+    /// The priority used to order this message in the queue.
+    fn priority(&self) -> MessagePriority;
+}
+
+/// Heap entry: ordered by priority, then by lowest sequence number.
+struct Entry<T> {
+    priority: MessagePriority,
+    seq: u64,
+    item: T,
+}
+
+impl<T> PartialEq for Entry<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority && self.seq == other.seq
+    }
+}
+
+impl<T> Eq for Entry<T> {}
+
+impl<T> PartialOrd for Entry<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for Entry<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // BinaryHeap is a max-heap: higher priority wins, then the earlier
+        // (smaller) sequence number.
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+struct State<T> {
+    heap: BinaryHeap<Entry<T>>,
+    next_seq: u64,
+}
+
+struct Inner<T> {
+    name: &'static str,
+    capacity: usize,
+    state: Mutex<State<T>>,
+    /// Signalled when an item is pushed.
+    items: Notify,
+    /// Signalled when an item is popped.
+    space: Notify,
+    /// Live [`QueueSender`]s.
+    senders: AtomicUsize,
+}
+
+/// Bounded, multi-producer multi-consumer priority queue.
+///
+/// Cloning is cheap and every clone shares the same queue.
+pub struct MessageQueue<T> {
+    inner: Arc<Inner<T>>,
+}
+
+impl<T> Clone for MessageQueue<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<T: Prioritized> MessageQueue<T> {
+    /// Creates a queue holding at most `capacity` items (minimum 1).
+    ///
+    /// `name` identifies the queue in [`ZeptoError::BusFull`] errors.
+    pub fn new(name: &'static str, capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                name,
+                capacity: capacity.max(1),
+                state: Mutex::new(State {
+                    heap: BinaryHeap::new(),
+                    next_seq: 0,
+                }),
+                items: Notify::new(),
+                space: Notify::new(),
+                senders: AtomicUsize::new(0),
+            }),
+        }
+    }
+
+    /// Returns a producer handle. The queue stays open while any exists.
+    pub fn sender(&self) -> QueueSender<T> {
+        self.inner.senders.fetch_add(1, atomic::Ordering::SeqCst);
+        QueueSender {
+            queue: self.clone(),
+        }
+    }
+
+    /// Returns `true` once every [`QueueSender`] has been dropped.
+    pub fn is_closed(&self) -> bool {
+        self.inner.senders.load(atomic::Ordering::SeqCst) == 0
+    }
+
+    /// Maximum number of queued items.
+    pub fn capacity(&self) -> usize {
+        self.inner.capacity
+    }
+
+    /// Number of items currently queued.
+    pub fn len(&self) -> usize {
+        self.inner.state.lock().unwrap().heap.len()
+    }
+
+    /// Returns `true` if nothing is queued.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Queues `item`, or hands it back if the queue is full.
+    fn push(&self, item: T) -> std::result::Result<(), T> {
+        let mut state = self.inner.state.lock().unwrap();
+        if state.heap.len() >= self.inner.capacity {
+            return Err(item);
+        }
+        let seq = state.next_seq;
+        state.next_seq += 1;
+        state.heap.push(Entry {
+            priority: item.priority(),
+            seq,
+            item,
+        });
+        drop(state);
+        self.inner.items.notify_one();
+        Ok(())
+    }
+
+    fn pop(&self) -> Option<T> {
+        let entry = self.inner.state.lock().unwrap().heap.pop()?;
+        self.inner.space.notify_one();
+        Some(entry.item)
+    }
+
+    /// Removes the highest-priority, oldest item that satisfies `pred`,
+    /// leaving the others in place.
+    fn pop_matching(&self, pred: &impl Fn(&T) -> bool) -> Option<T> {
+        let mut state = self.inner.state.lock().unwrap();
+        let best = state
+            .heap
+            .iter()
+            .filter(|entry| pred(&entry.item))
+            .max()
+            .map(|entry| entry.seq)?;
+        let mut entries = std::mem::take(&mut state.heap).into_vec();
+        let index = entries.iter().position(|entry| entry.seq == best)?;
+        let entry = entries.swap_remove(index);
+        state.heap = entries.into();
+        drop(state);
+        self.inner.space.notify_one();
+        Some(entry.item)
+    }
+
+    /// Queues `item`, waiting for space if the queue is full.
+    pub async fn send(&self, mut item: T) -> Result<()> {
+        loop {
+            let space = self.inner.space.notified();
+            tokio::pin!(space);
+            space.as_mut().enable();
+            match self.push(item) {
+                Ok(()) => return Ok(()),
+                Err(returned) => item = returned,
+            }
+            space.await;
+        }
+    }
+
+    /// Queues `item` without waiting.
+    ///
+    /// # Errors
+    /// Returns [`ZeptoError::BusFull`] if the queue is at capacity; the item
+    /// is dropped.
+    pub fn try_send(&self, item: T) -> Result<()> {
+        self.push(item)
+            .map_err(|_| ZeptoError::BusFull(self.inner.name.to_string()))
+    }
+
+    /// Waits for and removes the highest-priority, oldest item.
+    ///
+    /// Returns `None` once the queue is empty and closed.
+    ///
+    /// Cancel-safe: dropping the future never loses an item.
+    pub async fn recv(&self) -> Option<T> {
+        loop {
+            let items = self.inner.items.notified();
+            tokio::pin!(items);
+            items.as_mut().enable();
+            if let Some(item) = self.pop() {
+                return Some(item);
+            }
+            if self.is_closed() {
+                return None;
+            }
+            items.await;
+        }
+    }
+
+    /// Waits for and removes the highest-priority, oldest item matching
+    /// `pred`. Items that don't match stay queued in order.
+    ///
+    /// Only one receiver should wait at a time: a non-matching push wakes
+    /// this one instead of a plain [`recv`](Self::recv).
+    ///
+    /// Returns `None` once the queue is closed and holds no matching item.
+    ///
+    /// Cancel-safe: dropping the future never loses an item.
+    pub async fn recv_matching(&self, pred: impl Fn(&T) -> bool) -> Option<T> {
+        loop {
+            let items = self.inner.items.notified();
+            tokio::pin!(items);
+            items.as_mut().enable();
+            if let Some(item) = self.pop_matching(&pred) {
+                return Some(item);
+            }
+            if self.is_closed() {
+                return None;
+            }
+            items.await;
+        }
+    }
+}
+
+/// Producer handle for a [`MessageQueue`].
+///
+/// Cloning registers another sender; the queue closes when the last one
+/// is dropped.
+pub struct QueueSender<T> {
+    queue: MessageQueue<T>,
+}
+
+impl<T> Clone for QueueSender<T> {
+    fn clone(&self) -> Self {
+        self.queue
+            .inner
+            .senders
+            .fetch_add(1, atomic::Ordering::SeqCst);
+        Self {
+            queue: self.queue.clone(),
+        }
+    }
+}
+
+impl<T> Drop for QueueSender<T> {
+    fn drop(&mut self) {
+        if self
+            .queue
+            .inner
+            .senders
+            .fetch_sub(1, atomic::Ordering::SeqCst)
+            == 1
+        {
+            // Wake every waiting receiver so it can observe the close.
+            self.queue.inner.items.notify_waiters();
+        }
+    }
+}
+
+impl<T: Prioritized> QueueSender<T> {
+    /// Queues `item`, waiting for space if the queue is full.
+    pub async fn send(&self, item: T) -> Result<()> {
+        self.queue.send(item).await
+    }
+
+    /// Queues `item` without waiting; see [`MessageQueue::try_send`].
+    pub fn try_send(&self, item: T) -> Result<()> {
+        self.queue.try_send(item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Item(MessagePriority, u32);
+
+    impl Prioritized for Item {
+        fn priority(&self) -> MessagePriority {
+            self.0
+        }
+    }
+
+    #[tokio::test]
+    async fn test_orders_by_priority_then_arrival() {
+        let queue = MessageQueue::new("test", 10);
+        queue.try_send(Item(MessagePriority::Low, 1)).unwrap();
+        queue.try_send(Item(MessagePriority::Normal, 2)).unwrap();
+        queue.try_send(Item(MessagePriority::High, 3)).unwrap();
+        queue.try_send(Item(MessagePriority::Normal, 4)).unwrap();
+        queue.try_send(Item(MessagePriority::High, 5)).unwrap();
+
+        let mut order = Vec::new();
+        while !queue.is_empty() {
+            order.push(queue.recv().await.unwrap().1);
+        }
+        assert_eq!(order, vec![3, 5, 2, 4, 1]);
+    }
+
+    #[tokio::test]
+    async fn test_try_send_full() {
+        let queue = MessageQueue::new("test", 1);
+        queue.try_send(Item(MessagePriority::Normal, 1)).unwrap();
+        let err = queue.try_send(Item(MessagePriority::High, 2));
+        assert!(matches!(err, Err(ZeptoError::BusFull(ref name)) if name == "test"));
+        assert_eq!(queue.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_send_waits_for_space() {
+        let queue = MessageQueue::new("test", 1);
+        queue.try_send(Item(MessagePriority::Normal, 1)).unwrap();
+
+        let producer = {
+            let queue = queue.clone();
+            tokio::spawn(async move { queue.send(Item(MessagePriority::Normal, 2)).await })
+        };
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        assert!(!producer.is_finished());
+
+        assert_eq!(queue.recv().await.unwrap().1, 1);
+        producer.await.unwrap().unwrap();
+        assert_eq!(queue.recv().await.unwrap().1, 2);
+    }
+
+    #[tokio::test]
+    async fn test_recv_waits_for_item() {
+        let queue: MessageQueue<Item> = MessageQueue::new("test", 4);
+        let _tx = queue.sender();
+        let consumer = {
+            let queue = queue.clone();
+            tokio::spawn(async move { queue.recv().await.unwrap().1 })
+        };
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        queue.send(Item(MessagePriority::Low, 7)).await.unwrap();
+        assert_eq!(consumer.await.unwrap(), 7);
+    }
+
+    #[tokio::test]
+    async fn test_recv_matching_skips_other_items() {
+        let queue = MessageQueue::new("test", 10);
+        let _tx = queue.sender();
+        queue.try_send(Item(MessagePriority::Normal, 1)).unwrap();
+        queue.try_send(Item(MessagePriority::Normal, 2)).unwrap();
+        queue.try_send(Item(MessagePriority::Low, 4)).unwrap();
+        queue.try_send(Item(MessagePriority::Normal, 3)).unwrap();
+
+        let even = |item: &Item| item.1.is_multiple_of(2);
+        assert_eq!(queue.recv_matching(even).await.unwrap().1, 2);
+        assert_eq!(queue.recv_matching(even).await.unwrap().1, 4);
+
+        let consumer = {
+            let queue = queue.clone();
+            tokio::spawn(async move { queue.recv_matching(even).await.unwrap().1 })
+        };
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        assert!(!consumer.is_finished());
+        queue.send(Item(MessagePriority::Low, 6)).await.unwrap();
+        assert_eq!(consumer.await.unwrap(), 6);
+
+        assert_eq!(queue.recv().await.unwrap().1, 1);
+        assert_eq!(queue.recv().await.unwrap().1, 3);
+        assert!(queue.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_recv_returns_none_once_closed_and_drained() {
+        let queue = MessageQueue::new("test", 4);
+        let tx = queue.sender();
+        let tx2 = tx.clone();
+        tx.try_send(Item(MessagePriority::Normal, 1)).unwrap();
+        drop(tx);
+        assert!(!queue.is_closed());
+
+        let consumer = {
+            let queue = queue.clone();
+            tokio::spawn(async move {
+                let first = queue.recv().await.map(|item| item.1);
+                (first, queue.recv().await.is_none())
+            })
+        };
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        assert!(!consumer.is_finished());
+        drop(tx2);
+        assert_eq!(consumer.await.unwrap(), (Some(1), true));
+        assert!(queue.is_closed());
+    }
+
+    #[test]
+    fn test_capacity_minimum_one() {
+        let queue: MessageQueue<Item> = MessageQueue::new("test", 0);
+        assert_eq!(queue.capacity(), 1);
+    }
+}
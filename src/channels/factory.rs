@@ -12,132 +12,195 @@ use super::plugin::{default_channel_plugins_dir, discover_channel_plugins, Chann
 use super::webhook::{WebhookChannel, WebhookChannelConfig};
 use super::WhatsAppChannel;
 use super::WhatsAppCloudChannel;
-use super::{BaseChannelConfig, ChannelManager, DiscordChannel, SlackChannel, TelegramChannel};
+use super::{
+    BaseChannelConfig, Channel, ChannelManager, DiscordChannel, SlackChannel, TelegramChannel,
+};
 
-/// Register all configured channels that currently have implementations.
-///
-/// Returns the number of registered channels.
-pub async fn register_configured_channels(
-    manager: &ChannelManager,
-    bus: Arc<MessageBus>,
-    config: &Config,
-) -> usize {
-    // Telegram
-    if let Some(ref telegram_config) = config.channels.telegram {
-        if telegram_config.enabled {
-            if telegram_config.token.is_empty() {
-                warn!("Telegram channel enabled but token is empty");
-            } else {
-                manager
-                    .register(Box::new(TelegramChannel::new(
-                        telegram_config.clone(),
-                        bus.clone(),
-                    )))
-                    .await;
-                info!("Registered Telegram channel");
+/// Outcome of checking one built-in channel's config.
+enum ChannelSetup {
+    /// Not configured, or not enabled.
+    Disabled,
+    /// Enabled but a required credential is empty; holds the warning.
+    Incomplete(&'static str),
+    /// Enabled with credentials; builds the channel.
+    Ready(Box<dyn FnOnce(Arc<MessageBus>) -> Box<dyn Channel>>),
+}
+
+/// A built-in channel: its registry name, log label and config check.
+struct BuiltinChannel {
+    name: &'static str,
+    label: &'static str,
+    setup: fn(&Config) -> ChannelSetup,
+}
+
+/// Built-in channels in registration order. Both
+/// [`register_configured_channels`] and [`enabled_channel_names`] read this
+/// table, so they always agree on which channels are live.
+const BUILTIN_CHANNELS: &[BuiltinChannel] = &[
+    BuiltinChannel {
+        name: "telegram",
+        label: "Telegram",
+        setup: telegram_setup,
+    },
+    BuiltinChannel {
+        name: "slack",
+        label: "Slack",
+        setup: slack_setup,
+    },
+    BuiltinChannel {
+        name: "discord",
+        label: "Discord",
+        setup: discord_setup,
+    },
+    BuiltinChannel {
+        name: "webhook",
+        label: "Webhook",
+        setup: webhook_setup,
+    },
+    BuiltinChannel {
+        name: "whatsapp",
+        label: "WhatsApp",
+        setup: whatsapp_setup,
+    },
+    BuiltinChannel {
+        name: "whatsapp_cloud",
+        label: "WhatsApp Cloud API",
+        setup: whatsapp_cloud_setup,
+    },
+];
+
+fn telegram_setup(config: &Config) -> ChannelSetup {
+    match config.channels.telegram {
+        Some(ref c) if c.enabled => {
+            if c.token.is_empty() {
+                return ChannelSetup::Incomplete("Telegram channel enabled but token is empty");
             }
+            let c = c.clone();
+            ChannelSetup::Ready(Box::new(move |bus| Box::new(TelegramChannel::new(c, bus))))
         }
+        _ => ChannelSetup::Disabled,
     }
+}
 
-    // Slack
-    if let Some(ref slack_config) = config.channels.slack {
-        if slack_config.enabled {
-            if slack_config.bot_token.is_empty() {
-                warn!("Slack channel enabled but bot token is empty");
-            } else {
-                manager
-                    .register(Box::new(SlackChannel::new(
-                        slack_config.clone(),
-                        bus.clone(),
-                    )))
-                    .await;
-                info!("Registered Slack channel");
+fn slack_setup(config: &Config) -> ChannelSetup {
+    match config.channels.slack {
+        Some(ref c) if c.enabled => {
+            if c.bot_token.is_empty() {
+                return ChannelSetup::Incomplete("Slack channel enabled but bot token is empty");
             }
+            let c = c.clone();
+            ChannelSetup::Ready(Box::new(move |bus| Box::new(SlackChannel::new(c, bus))))
         }
+        _ => ChannelSetup::Disabled,
     }
+}
 
-    // Discord
-    if let Some(ref discord_config) = config.channels.discord {
-        if discord_config.enabled {
-            if discord_config.token.is_empty() {
-                warn!("Discord channel enabled but token is empty");
-            } else {
-                manager
-                    .register(Box::new(DiscordChannel::new(
-                        discord_config.clone(),
-                        bus.clone(),
-                    )))
-                    .await;
-                info!("Registered Discord channel");
+fn discord_setup(config: &Config) -> ChannelSetup {
+    match config.channels.discord {
+        Some(ref c) if c.enabled => {
+            if c.token.is_empty() {
+                return ChannelSetup::Incomplete("Discord channel enabled but token is empty");
             }
+            let c = c.clone();
+            ChannelSetup::Ready(Box::new(move |bus| Box::new(DiscordChannel::new(c, bus))))
         }
+        _ => ChannelSetup::Disabled,
     }
-    // Webhook
-    if let Some(ref webhook_config) = config.channels.webhook {
-        if webhook_config.enabled {
+}
+
+fn webhook_setup(config: &Config) -> ChannelSetup {
+    match config.channels.webhook {
+        Some(ref c) if c.enabled => {
             let runtime_config = WebhookChannelConfig {
-                bind_address: webhook_config.bind_address.clone(),
-                port: webhook_config.port,
-                path: webhook_config.path.clone(),
-                auth_token: webhook_config.auth_token.clone(),
+                bind_address: c.bind_address.clone(),
+                port: c.port,
+                path: c.path.clone(),
+                auth_token: c.auth_token.clone(),
             };
             let base_config = BaseChannelConfig {
                 name: "webhook".to_string(),
-                allowlist: webhook_config.allow_from.clone(),
-                deny_by_default: webhook_config.deny_by_default,
+                allowlist: c.allow_from.clone(),
+                deny_by_default: c.deny_by_default,
             };
-            manager
-                .register(Box::new(WebhookChannel::new(
-                    runtime_config,
-                    base_config,
-                    bus.clone(),
-                )))
-                .await;
-            info!(
-                "Registered Webhook channel on {}:{}",
-                webhook_config.bind_address, webhook_config.port
-            );
+            ChannelSetup::Ready(Box::new(move |bus| {
+                info!(
+                    "Webhook channel listening on {}:{}",
+                    runtime_config.bind_address, runtime_config.port
+                );
+                Box::new(WebhookChannel::new(runtime_config, base_config, bus))
+            }))
         }
+        _ => ChannelSetup::Disabled,
     }
+}
 
-    // WhatsApp (via bridge)
-    if let Some(ref whatsapp_config) = config.channels.whatsapp {
-        if whatsapp_config.enabled {
-            if whatsapp_config.bridge_url.is_empty() {
-                warn!("WhatsApp channel enabled but bridge_url is empty");
-            } else {
-                manager
-                    .register(Box::new(WhatsAppChannel::new(
-                        whatsapp_config.clone(),
-                        bus.clone(),
-                    )))
-                    .await;
-                info!("Registered WhatsApp channel");
+fn whatsapp_setup(config: &Config) -> ChannelSetup {
+    match config.channels.whatsapp {
+        Some(ref c) if c.enabled => {
+            if c.bridge_url.is_empty() {
+                return ChannelSetup::Incomplete(
+                    "WhatsApp channel enabled but bridge_url is empty",
+                );
             }
+            let c = c.clone();
+            ChannelSetup::Ready(Box::new(move |bus| Box::new(WhatsAppChannel::new(c, bus))))
         }
+        _ => ChannelSetup::Disabled,
     }
+}
 
-    // WhatsApp Cloud API (official)
-    if let Some(ref wac_config) = config.channels.whatsapp_cloud {
-        if wac_config.enabled {
-            if wac_config.phone_number_id.is_empty() || wac_config.access_token.is_empty() {
-                warn!(
-                    "WhatsApp Cloud channel enabled but phone_number_id or access_token is empty"
+fn whatsapp_cloud_setup(config: &Config) -> ChannelSetup {
+    match config.channels.whatsapp_cloud {
+        Some(ref c) if c.enabled => {
+            if c.phone_number_id.is_empty() || c.access_token.is_empty() {
+                return ChannelSetup::Incomplete(
+                    "WhatsApp Cloud channel enabled but phone_number_id or access_token is empty",
                 );
-            } else {
-                manager
-                    .register(Box::new(WhatsAppCloudChannel::new(
-                        wac_config.clone(),
-                        bus.clone(),
-                    )))
-                    .await;
+            }
+            let c = c.clone();
+            ChannelSetup::Ready(Box::new(move |bus| {
                 info!(
-                    "Registered WhatsApp Cloud API channel on {}:{}",
-                    wac_config.bind_address, wac_config.port
+                    "WhatsApp Cloud API channel listening on {}:{}",
+                    c.bind_address, c.port
                 );
+                Box::new(WhatsAppCloudChannel::new(c, bus))
+            }))
+        }
+        _ => ChannelSetup::Disabled,
+    }
+}
+
+/// Names of built-in channels that are enabled and have their required
+/// credentials set, i.e. the ones [`register_configured_channels`] registers.
+///
+/// Channel plugins are not included.
+pub fn enabled_channel_names(config: &Config) -> Vec<String> {
+    BUILTIN_CHANNELS
+        .iter()
+        .filter(|channel| matches!((channel.setup)(config), ChannelSetup::Ready(_)))
+        .map(|channel| channel.name.to_string())
+        .collect()
+}
+
+/// Register all configured channels that currently have implementations.
+///
+/// Returns the number of registered channels.
+pub async fn register_configured_channels(
+    manager: &ChannelManager,
+    bus: Arc<MessageBus>,
+    config: &Config,
+) -> usize {
+    for channel in BUILTIN_CHANNELS {
+        match (channel.setup)(config) {
+            ChannelSetup::Disabled => {}
+            ChannelSetup::Incomplete(warning) => warn!("{}", warning),
+            ChannelSetup::Ready(build) => {
+                manager.register(build(bus.clone())).await;
+                info!("Registered {} channel", channel.label);
             }
         }
     }
+
     if config
         .channels
         .feishu
@@ -188,7 +251,8 @@ pub async fn register_configured_channels(
         for (manifest, plugin_path) in discovered {
             let name = manifest.name.clone();
             let base_config = BaseChannelConfig::new(&name);
-            let adapter = ChannelPluginAdapter::new(manifest, plugin_path, base_config);
+            let adapter =
+                ChannelPluginAdapter::new(manifest, plugin_path, base_config, bus.clone());
             manager.register(Box::new(adapter)).await;
             info!("Registered channel plugin: {}", name);
         }
@@ -278,4 +342,60 @@ mod tests {
         assert_eq!(count, 1);
         assert!(manager.has_channel("whatsapp_cloud").await);
     }
+
+    #[test]
+    fn test_enabled_channel_names_requires_enabled_and_credentials() {
+        let mut config = Config::default();
+        assert!(enabled_channel_names(&config).is_empty());
+
+        config.channels.telegram = Some(TelegramConfig {
+            enabled: true,
+            token: "test-token".to_string(),
+            ..Default::default()
+        });
+        config.channels.slack = Some(SlackConfig {
+            enabled: true,
+            bot_token: String::new(),
+            ..Default::default()
+        });
+        config.channels.whatsapp = Some(WhatsAppConfig {
+            enabled: false,
+            bridge_url: "ws://localhost:3001".to_string(),
+            ..Default::default()
+        });
+
+        assert_eq!(enabled_channel_names(&config), vec!["telegram"]);
+    }
+
+    #[tokio::test]
+    async fn test_enabled_channel_names_match_registered_channels() {
+        let bus = Arc::new(MessageBus::new());
+        let mut config = Config::default();
+        // Point plugin discovery at an empty directory
+        let plugins = tempfile::tempdir().unwrap();
+        config.channels.channel_plugins_dir = Some(plugins.path().display().to_string());
+        config.channels.telegram = Some(TelegramConfig {
+            enabled: true,
+            token: "test-token".to_string(),
+            ..Default::default()
+        });
+        config.channels.slack = Some(SlackConfig {
+            enabled: true,
+            bot_token: String::new(),
+            ..Default::default()
+        });
+        config.channels.whatsapp = Some(WhatsAppConfig {
+            enabled: true,
+            bridge_url: "ws://localhost:3001".to_string(),
+            ..Default::default()
+        });
+
+        let manager = ChannelManager::new(bus.clone(), config.clone());
+        register_configured_channels(&manager, bus, &config).await;
+
+        let mut registered = manager.channels().await;
+        registered.sort();
+        assert_eq!(registered, vec!["telegram", "whatsapp"]);
+        assert_eq!(enabled_channel_names(&config), vec!["telegram", "whatsapp"]);
+    }
 }
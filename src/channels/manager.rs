@@ -7,6 +7,7 @@
 
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::{watch, Mutex, RwLock};
 use tokio::task::JoinHandle;
 use tracing::{error, info, warn};
@@ -103,6 +104,9 @@ impl ChannelManager {
     /// ```
     pub fn new(bus: Arc<MessageBus>, config: Config) -> Self {
         let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        for (channel, secs) in &config.channels.outbound_ttl_secs {
+            bus.set_outbound_ttl(channel, Duration::from_secs(*secs));
+        }
         Self {
             channels: Arc::new(RwLock::new(HashMap::new())),
             bus,
@@ -164,6 +168,24 @@ impl ChannelManager {
         channels.len()
     }
 
+    /// Returns whether each registered channel is running, sorted by name.
+    pub async fn channel_states(&self) -> Vec<(String, bool)> {
+        let channels = {
+            let channels = self.channels.read().await;
+            channels
+                .iter()
+                .map(|(name, channel)| (name.clone(), Arc::clone(channel)))
+                .collect::<Vec<_>>()
+        };
+        let mut states = Vec::with_capacity(channels.len());
+        for (name, channel) in channels {
+            let running = channel.lock().await.is_running();
+            states.push((name, running));
+        }
+        states.sort();
+        states
+    }
+
     /// Checks if a channel with the given name is registered.
     ///
     /// # Arguments
@@ -308,6 +330,10 @@ impl ChannelManager {
     /// manager.send("telegram", msg).await?;
     /// ```
     pub async fn send(&self, channel_name: &str, msg: OutboundMessage) -> Result<()> {
+        if is_stale(&msg) {
+            return Ok(());
+        }
+
         let channel = {
             let channels = self.channels.read().await;
             channels.get(channel_name).cloned()
@@ -328,11 +354,26 @@ impl ChannelManager {
     }
 }
 
+/// Returns true (and logs) if the message expired before it could be delivered.
+fn is_stale(msg: &OutboundMessage) -> bool {
+    if msg.is_expired() {
+        warn!(
+            "Dropping expired outbound message for {}:{}",
+            msg.channel, msg.chat_id
+        );
+        true
+    } else {
+        false
+    }
+}
+
 /// Background task that dispatches outbound messages from the bus to channels.
 ///
 /// This function runs in a loop, consuming outbound messages from the bus
 /// and routing them to the appropriate channel based on the message's
-/// `channel` field. It stops when the shutdown signal is received.
+/// `channel` field. Messages that fail to send, or target an unknown
+/// channel, are parked on the bus's dead-letter queue. It stops when the
+/// shutdown signal is received.
 ///
 /// # Arguments
 ///
@@ -357,19 +398,33 @@ async fn dispatch_outbound(
             // Wait for outbound messages
             msg = bus.consume_outbound() => {
                 if let Some(msg) = msg {
+                    if is_stale(&msg) {
+                        continue;
+                    }
                     let channel_name = msg.channel.clone();
                     let channel = {
                         let channels = channels.read().await;
                         channels.get(&channel_name).cloned()
                     };
 
-                    if let Some(channel) = channel {
+                    let failure = if let Some(channel) = channel {
                         let channel = channel.lock().await;
-                        if let Err(e) = channel.send(msg).await {
-                            error!("Failed to send message to {}: {}", channel_name, e);
+                        match channel.send(msg.clone()).await {
+                            Ok(()) => None,
+                            Err(e) => {
+                                error!("Failed to send message to {}: {}", channel_name, e);
+                                Some(e.to_string())
+                            }
                         }
                     } else {
                         warn!("Unknown channel for outbound message: {}", channel_name);
+                        Some(format!("unknown channel: {}", channel_name))
+                    };
+
+                    if let Some(reason) = failure {
+                        if let Err(e) = bus.publish_dead_letter(msg, &reason) {
+                            error!("Dropping undeliverable message for {}: {}", channel_name, e);
+                        }
                     }
                 } else {
                     // Channel closed
@@ -393,6 +448,7 @@ mod tests {
         name: String,
         running: Arc<AtomicBool>,
         allowlist: Vec<String>,
+        sent: Arc<std::sync::Mutex<Vec<String>>>,
     }
 
     impl MockChannel {
@@ -401,6 +457,7 @@ mod tests {
                 name: name.to_string(),
                 running: Arc::new(AtomicBool::new(false)),
                 allowlist: Vec::new(),
+                sent: Arc::new(std::sync::Mutex::new(Vec::new())),
             }
         }
 
@@ -409,6 +466,7 @@ mod tests {
                 name: name.to_string(),
                 running: Arc::new(AtomicBool::new(false)),
                 allowlist,
+                sent: Arc::new(std::sync::Mutex::new(Vec::new())),
             }
         }
     }
@@ -429,7 +487,8 @@ mod tests {
             Ok(())
         }
 
-        async fn send(&self, _msg: OutboundMessage) -> Result<()> {
+        async fn send(&self, msg: OutboundMessage) -> Result<()> {
+            self.sent.lock().unwrap().push(msg.content);
             Ok(())
         }
 
@@ -508,7 +567,15 @@ mod tests {
 
         manager.register(Box::new(MockChannel::new("test"))).await;
         manager.start_all().await.unwrap();
+        assert_eq!(
+            manager.channel_states().await,
+            vec![("test".to_string(), true)]
+        );
         manager.stop_all().await.unwrap();
+        assert_eq!(
+            manager.channel_states().await,
+            vec![("test".to_string(), false)]
+        );
     }
 
     #[tokio::test]
@@ -555,6 +622,121 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_send_drops_expired_message() {
+        let bus = Arc::new(MessageBus::new());
+        let manager = ChannelManager::new(bus, Config::default());
+
+        let channel = MockChannel::new("test");
+        let sent = Arc::clone(&channel.sent);
+        manager.register(Box::new(channel)).await;
+
+        let expired = OutboundMessage::new("test", "chat123", "stale").with_ttl(Duration::ZERO);
+        let fresh =
+            OutboundMessage::new("test", "chat123", "fresh").with_ttl(Duration::from_secs(60));
+        manager.send("test", expired).await.unwrap();
+        manager.send("test", fresh).await.unwrap();
+
+        assert_eq!(*sent.lock().unwrap(), vec!["fresh".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_dispatcher_drops_expired_and_delivers_fresh() {
+        let bus = Arc::new(MessageBus::new());
+        let mut config = Config::default();
+        config
+            .channels
+            .outbound_ttl_secs
+            .insert("test".to_string(), 60);
+        let manager = ChannelManager::new(bus.clone(), config);
+        assert_eq!(bus.outbound_ttl("test"), Some(Duration::from_secs(60)));
+
+        let channel = MockChannel::new("test");
+        let sent = Arc::clone(&channel.sent);
+        manager.register(Box::new(channel)).await;
+        manager.start_all().await.unwrap();
+
+        let mut expired = OutboundMessage::new("test", "chat123", "stale");
+        expired.expires_at = Some(chrono::Utc::now().timestamp_millis() - 1_000);
+        bus.publish_outbound(expired).await.unwrap();
+        bus.publish_outbound(OutboundMessage::new("test", "chat123", "fresh"))
+            .await
+            .unwrap();
+
+        for _ in 0..50 {
+            if !sent.lock().unwrap().is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        manager.stop_all().await.unwrap();
+
+        assert_eq!(*sent.lock().unwrap(), vec!["fresh".to_string()]);
+    }
+
+    /// A channel whose `send` always fails, like a plugin that is not running.
+    struct FailingChannel;
+
+    #[async_trait]
+    impl Channel for FailingChannel {
+        fn name(&self) -> &str {
+            "broken"
+        }
+
+        async fn start(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn stop(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn send(&self, _msg: OutboundMessage) -> Result<()> {
+            Err(crate::error::ZeptoError::Channel(
+                "plugin is not running".to_string(),
+            ))
+        }
+
+        fn is_running(&self) -> bool {
+            false
+        }
+
+        fn is_allowed(&self, _user_id: &str) -> bool {
+            true
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dispatcher_dead_letters_failed_and_unknown_sends() {
+        let bus = Arc::new(MessageBus::new());
+        let manager = ChannelManager::new(bus.clone(), Config::default());
+        manager.register(Box::new(FailingChannel)).await;
+        manager.start_all().await.unwrap();
+
+        bus.publish_outbound(OutboundMessage::new("broken", "chat1", "lost"))
+            .await
+            .unwrap();
+        bus.publish_outbound(OutboundMessage::new("nowhere", "chat2", "misrouted"))
+            .await
+            .unwrap();
+
+        let first = tokio::time::timeout(Duration::from_secs(2), bus.consume_dead_letter())
+            .await
+            .unwrap()
+            .unwrap();
+        let second = tokio::time::timeout(Duration::from_secs(2), bus.consume_dead_letter())
+            .await
+            .unwrap()
+            .unwrap();
+        manager.stop_all().await.unwrap();
+
+        assert_eq!(first.message.content, "lost");
+        assert_eq!(first.reason, "Channel error: plugin is not running");
+        assert_eq!(first.attempts, 1);
+        assert_eq!(second.message.content, "misrouted");
+        assert_eq!(second.reason, "unknown channel: nowhere");
+    }
+
     #[tokio::test]
     async fn test_channel_allowlist() {
         let channel = MockChannel::with_allowlist("test", vec!["user1".to_string()]);
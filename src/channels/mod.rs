@@ -120,7 +120,7 @@ pub mod whatsapp;
 pub mod whatsapp_cloud;
 
 pub use discord::DiscordChannel;
-pub use factory::register_configured_channels;
+pub use factory::{enabled_channel_names, register_configured_channels};
 pub use manager::ChannelManager;
 pub use plugin::ChannelPluginAdapter;
 pub use slack::SlackChannel;
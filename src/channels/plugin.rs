@@ -27,21 +27,53 @@
 //!     "description": "A custom channel plugin",
 //!     "binary": "my-channel-binary",
 //!     "env": {},
-//!     "timeout_secs": 30
+//!     "timeout_secs": 30,
+//!     "max_restarts": 5
 //! }
 //! ```
+//!
+//! # Handshake
+//!
+//! After spawning, ZeptoClaw sends an `initialize` request and waits up to
+//! `timeout_secs` for the plugin to declare its capabilities:
+//!
+//! ```json
+//! {"jsonrpc": "2.0", "id": 1, "result": {"version": "1.0.0", "methods": ["send"]}}
+//! ```
+//!
+//! If the plugin does not answer, answers with an error, or omits `methods`,
+//! `start()` fails and the process is killed. `send()` is refused if the
+//! plugin did not advertise `"send"`.
+//!
+//! # Inbound Messages
+//!
+//! The plugin pushes user messages into ZeptoClaw by writing newline-delimited
+//! JSON-RPC notifications to its stdout:
+//!
+//! ```json
+//! {"jsonrpc": "2.0", "method": "receive", "params": {"sender_id": "u1", "chat_id": "c1", "content": "hi"}}
+//! ```
+//!
+//! `params.metadata` (an object of string values) is optional. Other lines,
+//! such as responses to `send` requests, are ignored.
+//!
+//! Anything the plugin writes to stderr is logged at `warn` level, and the
+//! most recent lines are appended to `start()`/`send()` errors.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use tokio::sync::Mutex;
-use tracing::{info, warn};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, BufReader};
+use tokio::sync::{oneshot, watch, Mutex};
+use tokio::task::JoinHandle;
+use tracing::{debug, error, info, warn};
 
-use crate::bus::OutboundMessage;
+use crate::bus::{InboundMessage, MessageBus, OutboundMessage};
 use crate::error::{Result, ZeptoError};
 
 use super::{BaseChannelConfig, Channel};
@@ -76,6 +108,61 @@ struct ChannelJsonRpcError {
     data: Option<serde_json::Value>,
 }
 
+/// A line written by the plugin to its stdout: a notification such as
+/// `receive` (has `method`) or a response to one of our requests (has `id`).
+#[derive(Deserialize)]
+struct ChannelJsonRpcMessage {
+    id: Option<u64>,
+    method: Option<String>,
+    #[serde(default)]
+    params: serde_json::Value,
+    result: Option<serde_json::Value>,
+    error: Option<ChannelJsonRpcError>,
+}
+
+/// Outcome of a request, as delivered to whoever is waiting on it.
+type ChannelRpcResult = std::result::Result<serde_json::Value, ChannelJsonRpcError>;
+
+/// Requests awaiting a response, keyed by id. Owned by one process's stdout
+/// reader, which sets it to `None` (dropping all waiters) when stdout closes.
+type PendingRequests =
+    Arc<std::sync::Mutex<Option<HashMap<u64, oneshot::Sender<ChannelRpcResult>>>>>;
+
+/// What a stdout line from the plugin turned out to be.
+enum PluginOutput {
+    /// A `receive` notification to publish to the bus.
+    Inbound(InboundMessage),
+    /// A response to the request with this id.
+    Response(u64, ChannelRpcResult),
+}
+
+/// Capabilities a channel plugin declares in its `initialize` response.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChannelPluginCapabilities {
+    /// Version reported by the plugin binary.
+    #[serde(default)]
+    pub version: String,
+    /// JSON-RPC methods the plugin accepts (e.g. `"send"`).
+    pub methods: Vec<String>,
+}
+
+impl ChannelPluginCapabilities {
+    /// Returns `true` if the plugin advertised `method`.
+    pub fn supports(&self, method: &str) -> bool {
+        self.methods.iter().any(|m| m == method)
+    }
+}
+
+/// Params of a `receive` notification.
+#[derive(Deserialize)]
+struct ChannelReceiveParams {
+    sender_id: String,
+    chat_id: String,
+    content: String,
+    #[serde(default)]
+    metadata: HashMap<String, String>,
+}
+
 // ---- Channel Plugin Manifest ----
 
 /// Manifest describing a channel plugin binary.
@@ -98,19 +185,61 @@ pub struct ChannelPluginManifest {
     /// Timeout in seconds for JSON-RPC send operations.
     #[serde(default = "default_timeout")]
     pub timeout_secs: u64,
+    /// How many times a crashed binary is restarted before the channel
+    /// gives up and stops. The count resets once the binary stays up for
+    /// a minute.
+    #[serde(default = "default_max_restarts")]
+    pub max_restarts: u32,
 }
 
 fn default_timeout() -> u64 {
     30
 }
 
+fn default_max_restarts() -> u32 {
+    5
+}
+
 // ---- Channel Plugin Adapter ----
 
+/// Delay before the first restart of a crashed plugin binary.
+const RESTART_BACKOFF_INITIAL: Duration = Duration::from_secs(1);
+/// Upper bound for the restart delay.
+const RESTART_BACKOFF_MAX: Duration = Duration::from_secs(30);
+/// A binary that stays up this long is considered healthy again and its
+/// restart count is reset.
+const RESTART_RESET_UPTIME: Duration = Duration::from_secs(60);
+/// Number of recent stderr lines kept for error messages.
+const STDERR_TAIL_LINES: usize = 20;
+
+/// Most recent stderr lines of the plugin binary, oldest first.
+type StderrTail = Arc<std::sync::Mutex<VecDeque<String>>>;
+
+/// Appends the recent stderr lines to a channel error so failures caused by
+/// the plugin explain themselves.
+fn with_recent_stderr(err: ZeptoError, stderr_tail: &StderrTail) -> ZeptoError {
+    let lines: Vec<String> = stderr_tail
+        .lock()
+        .map(|tail| tail.iter().cloned().collect())
+        .unwrap_or_default();
+    match err {
+        ZeptoError::Channel(msg) if !lines.is_empty() => ZeptoError::Channel(format!(
+            "{}\nRecent plugin stderr:\n{}",
+            msg,
+            lines.join("\n")
+        )),
+        other => other,
+    }
+}
+
 /// Adapter that implements `Channel` by spawning an external binary plugin.
 ///
 /// The binary communicates via JSON-RPC 2.0 over stdin/stdout. On `start()`,
 /// the binary is spawned as a long-running child process. On `send()`, a
-/// JSON-RPC request is written to the process's stdin. On `stop()`, the child
+/// JSON-RPC request is written to the process's stdin. A background task reads
+/// `receive` notifications from the process's stdout and publishes them to the
+/// bus. If the binary exits while the channel is running it is restarted with
+/// exponential backoff, up to `max_restarts` times. On `stop()`, the child
 /// process is terminated.
 pub struct ChannelPluginAdapter {
     /// The parsed manifest for this plugin.
@@ -119,14 +248,25 @@ pub struct ChannelPluginAdapter {
     plugin_dir: PathBuf,
     /// Base channel configuration (name, allowlist).
     base_config: BaseChannelConfig,
+    /// Message bus for publishing inbound messages.
+    bus: Arc<MessageBus>,
     /// Atomic flag indicating if the channel is currently running.
     running: Arc<AtomicBool>,
     /// Handle to the child process stdin (for sending JSON-RPC requests).
+    /// `None` while the binary is down or being restarted.
     child_stdin: Arc<Mutex<Option<tokio::process::ChildStdin>>>,
-    /// Handle to the child process (for killing on stop).
-    child_handle: Arc<Mutex<Option<tokio::process::Child>>>,
+    /// Signals the supervisor task to kill the child and exit.
+    shutdown_tx: Option<watch::Sender<bool>>,
+    /// Supervisor task owning the child process.
+    supervisor_handle: Option<JoinHandle<()>>,
+    /// Restart delay bounds (initial, max).
+    restart_backoff: (Duration, Duration),
+    /// Capabilities negotiated by the last successful `initialize` handshake.
+    capabilities: Arc<std::sync::RwLock<Option<ChannelPluginCapabilities>>>,
+    /// Last `STDERR_TAIL_LINES` lines the binary wrote to stderr.
+    stderr_tail: StderrTail,
     /// Monotonically increasing JSON-RPC request ID.
-    request_id: std::sync::atomic::AtomicU64,
+    request_id: Arc<std::sync::atomic::AtomicU64>,
 }
 
 impl ChannelPluginAdapter {
@@ -137,19 +277,28 @@ impl ChannelPluginAdapter {
     /// * `manifest` - The parsed plugin manifest
     /// * `plugin_dir` - Absolute path to the plugin directory
     /// * `base_config` - Base channel configuration (name, allowlist)
+    /// * `bus` - Message bus that inbound messages are published to
     pub fn new(
         manifest: ChannelPluginManifest,
         plugin_dir: PathBuf,
         base_config: BaseChannelConfig,
+        bus: Arc<MessageBus>,
     ) -> Self {
         Self {
             manifest,
             plugin_dir,
             base_config,
+            bus,
             running: Arc::new(AtomicBool::new(false)),
             child_stdin: Arc::new(Mutex::new(None)),
-            child_handle: Arc::new(Mutex::new(None)),
-            request_id: std::sync::atomic::AtomicU64::new(1),
+            shutdown_tx: None,
+            supervisor_handle: None,
+            restart_backoff: (RESTART_BACKOFF_INITIAL, RESTART_BACKOFF_MAX),
+            capabilities: Arc::new(std::sync::RwLock::new(None)),
+            stderr_tail: Arc::new(std::sync::Mutex::new(VecDeque::with_capacity(
+                STDERR_TAIL_LINES,
+            ))),
+            request_id: Arc::new(std::sync::atomic::AtomicU64::new(1)),
         }
     }
 
@@ -163,11 +312,453 @@ impl ChannelPluginAdapter {
         &self.manifest
     }
 
+    /// Returns the capabilities the running plugin declared during the
+    /// `initialize` handshake, or `None` if it has not completed one.
+    pub fn capabilities(&self) -> Option<ChannelPluginCapabilities> {
+        self.capabilities.read().ok().and_then(|caps| caps.clone())
+    }
+
+    /// Returns the most recent lines the plugin wrote to stderr, oldest first.
+    pub fn recent_stderr(&self) -> Vec<String> {
+        self.stderr_tail
+            .lock()
+            .map(|tail| tail.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
     /// Returns the next JSON-RPC request ID.
     fn next_id(&self) -> u64 {
         self.request_id
             .fetch_add(1, std::sync::atomic::Ordering::Relaxed)
     }
+
+    /// Parses one stdout line into an inbound message or a response.
+    ///
+    /// Returns `None` for blank lines, other notifications, malformed JSON
+    /// and senders rejected by the allowlist; the latter two are logged.
+    fn parse_stdout_line(
+        line: &str,
+        channel_name: &str,
+        base_config: &BaseChannelConfig,
+    ) -> Option<PluginOutput> {
+        let line = line.trim();
+        if line.is_empty() {
+            return None;
+        }
+
+        let notification: ChannelJsonRpcMessage = match serde_json::from_str(line) {
+            Ok(n) => n,
+            Err(e) => {
+                warn!(
+                    "Channel plugin '{}' wrote malformed JSON to stdout, skipping: {}",
+                    channel_name, e
+                );
+                return None;
+            }
+        };
+
+        if let (None, Some(id)) = (notification.method.as_ref(), notification.id) {
+            let result = match notification.error {
+                Some(error) => Err(error),
+                None => Ok(notification.result.unwrap_or(serde_json::Value::Null)),
+            };
+            return Some(PluginOutput::Response(id, result));
+        }
+
+        if notification.method.as_deref() != Some("receive") {
+            debug!(
+                "Channel plugin '{}' stdout line is not a receive notification, ignoring",
+                channel_name
+            );
+            return None;
+        }
+
+        let params: ChannelReceiveParams = match serde_json::from_value(notification.params) {
+            Ok(p) => p,
+            Err(e) => {
+                warn!(
+                    "Channel plugin '{}' sent invalid receive params, skipping: {}",
+                    channel_name, e
+                );
+                return None;
+            }
+        };
+
+        if !base_config.is_allowed(&params.sender_id) {
+            info!(
+                "Channel plugin '{}': user {} not in allowlist, ignoring message",
+                channel_name, params.sender_id
+            );
+            return None;
+        }
+
+        let mut inbound = InboundMessage::new(
+            channel_name,
+            &params.sender_id,
+            &params.chat_id,
+            &params.content,
+        );
+        for (key, value) in &params.metadata {
+            inbound = inbound.with_metadata(key, value);
+        }
+        Some(PluginOutput::Inbound(inbound))
+    }
+
+    /// Reads newline-delimited JSON-RPC from the plugin's stdout until EOF
+    /// or a read error, publishing `receive` notifications to the bus and
+    /// handing responses to their waiters in `pending`.
+    ///
+    /// `BufReader::lines` buffers partial writes until the newline arrives.
+    /// Process exit is handled by the supervisor, not here.
+    async fn read_stdout<R>(
+        stdout: R,
+        channel_name: String,
+        base_config: BaseChannelConfig,
+        bus: Arc<MessageBus>,
+        pending: PendingRequests,
+    ) where
+        R: AsyncBufRead + Unpin,
+    {
+        let mut lines = stdout.lines();
+        loop {
+            match lines.next_line().await {
+                Ok(Some(line)) => {
+                    match Self::parse_stdout_line(&line, &channel_name, &base_config) {
+                        Some(PluginOutput::Inbound(inbound)) => {
+                            if let Err(e) = bus.publish_inbound(inbound).await {
+                                error!(
+                                    "Failed to publish inbound message from channel plugin '{}': {}",
+                                    channel_name, e
+                                );
+                            }
+                        }
+                        Some(PluginOutput::Response(id, result)) => {
+                            let waiter = pending
+                                .lock()
+                                .ok()
+                                .and_then(|mut pending| pending.as_mut()?.remove(&id));
+                            match waiter {
+                                Some(waiter) => {
+                                    let _ = waiter.send(result);
+                                }
+                                None => debug!(
+                                    "Channel plugin '{}' answered request {} with no waiter",
+                                    channel_name, id
+                                ),
+                            }
+                        }
+                        None => {}
+                    }
+                }
+                Ok(None) => {
+                    debug!("Channel plugin '{}' closed its stdout", channel_name);
+                    break;
+                }
+                Err(e) => {
+                    warn!(
+                        "Failed to read from channel plugin '{}' stdout: {}",
+                        channel_name, e
+                    );
+                    break;
+                }
+            }
+        }
+
+        // Nobody will answer outstanding requests now.
+        if let Ok(mut pending) = pending.lock() {
+            *pending = None;
+        }
+    }
+
+    /// Builds the supervisor for this adapter's process.
+    fn supervisor(&self, shutdown_rx: watch::Receiver<bool>) -> PluginSupervisor {
+        PluginSupervisor {
+            manifest: self.manifest.clone(),
+            binary_path: self.binary_path(),
+            plugin_dir: self.plugin_dir.clone(),
+            base_config: self.base_config.clone(),
+            bus: self.bus.clone(),
+            running: self.running.clone(),
+            child_stdin: self.child_stdin.clone(),
+            shutdown_rx,
+            restart_backoff: self.restart_backoff,
+            capabilities: self.capabilities.clone(),
+            stderr_tail: self.stderr_tail.clone(),
+            request_id: self.request_id.clone(),
+        }
+    }
+}
+
+/// Tasks attached to one spawned plugin process.
+struct AttachedProcess {
+    child: tokio::process::Child,
+    reader: JoinHandle<()>,
+    stderr: JoinHandle<()>,
+    started_at: Instant,
+}
+
+/// Owns the plugin child process: spawns it, wires up its stdio, and
+/// restarts it with backoff when it exits while the channel is running.
+struct PluginSupervisor {
+    manifest: ChannelPluginManifest,
+    binary_path: PathBuf,
+    plugin_dir: PathBuf,
+    base_config: BaseChannelConfig,
+    bus: Arc<MessageBus>,
+    running: Arc<AtomicBool>,
+    child_stdin: Arc<Mutex<Option<tokio::process::ChildStdin>>>,
+    shutdown_rx: watch::Receiver<bool>,
+    restart_backoff: (Duration, Duration),
+    capabilities: Arc<std::sync::RwLock<Option<ChannelPluginCapabilities>>>,
+    stderr_tail: StderrTail,
+    request_id: Arc<std::sync::atomic::AtomicU64>,
+}
+
+impl PluginSupervisor {
+    /// Spawns the binary, attaches the stdout reader and the stderr
+    /// collector, and performs the `initialize` handshake.
+    ///
+    /// Stdin is published for [`send`](ChannelPluginAdapter::send) only once
+    /// the handshake succeeded, so `initialize` is always the first request
+    /// the plugin reads. If the handshake fails the child is killed and an
+    /// error returned.
+    async fn spawn(&self) -> Result<AttachedProcess> {
+        let mut cmd = tokio::process::Command::new(&self.binary_path);
+        cmd.stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .current_dir(&self.plugin_dir)
+            .kill_on_drop(true);
+
+        // Set environment variables from manifest
+        for (key, value) in &self.manifest.env {
+            cmd.env(key, value);
+        }
+
+        let mut child = cmd.spawn().map_err(|e| {
+            ZeptoError::Channel(format!(
+                "Failed to spawn channel plugin '{}' ({}): {}",
+                self.manifest.name,
+                self.binary_path.display(),
+                e
+            ))
+        })?;
+
+        // Keep stdin private until the handshake is done
+        let mut stdin = child.stdin.take();
+
+        // Read notifications and responses from stdout in the background
+        let pending: PendingRequests = Arc::new(std::sync::Mutex::new(Some(HashMap::new())));
+        let reader = match child.stdout.take() {
+            Some(stdout) => tokio::spawn(ChannelPluginAdapter::read_stdout(
+                BufReader::new(stdout),
+                self.manifest.name.clone(),
+                self.base_config.clone(),
+                self.bus.clone(),
+                pending.clone(),
+            )),
+            None => tokio::spawn(async {}),
+        };
+
+        // Log stderr and keep its tail for error messages
+        let stderr = match child.stderr.take() {
+            Some(stderr) => {
+                let plugin = self.manifest.name.clone();
+                let stderr_tail = self.stderr_tail.clone();
+                tokio::spawn(async move {
+                    let mut lines = BufReader::new(stderr).lines();
+                    while let Ok(Some(line)) = lines.next_line().await {
+                        warn!(plugin = %plugin, "{}", line);
+                        if let Ok(mut tail) = stderr_tail.lock() {
+                            if tail.len() == STDERR_TAIL_LINES {
+                                tail.pop_front();
+                            }
+                            tail.push_back(line);
+                        }
+                    }
+                })
+            }
+            None => tokio::spawn(async {}),
+        };
+
+        let mut process = AttachedProcess {
+            child,
+            reader,
+            stderr,
+            started_at: Instant::now(),
+        };
+
+        match self.initialize(stdin.as_mut(), &pending).await {
+            Ok(capabilities) => {
+                *self.child_stdin.lock().await = stdin;
+                info!(
+                    "Channel plugin '{}' initialized (version {:?}, methods {:?})",
+                    self.manifest.name, capabilities.version, capabilities.methods
+                );
+                if let Ok(mut caps) = self.capabilities.write() {
+                    *caps = Some(capabilities);
+                }
+                Ok(process)
+            }
+            Err(e) => {
+                drop(stdin);
+                let _ = process.child.kill().await;
+                process.reader.abort();
+                // Collect what the plugin said on its way out.
+                let _ = tokio::time::timeout(Duration::from_secs(1), &mut process.stderr).await;
+                process.stderr.abort();
+                Err(with_recent_stderr(e, &self.stderr_tail))
+            }
+        }
+    }
+
+    /// Sends `initialize` on `stdin` and waits up to `timeout_secs` for the
+    /// plugin to declare its capabilities.
+    async fn initialize(
+        &self,
+        stdin: Option<&mut tokio::process::ChildStdin>,
+        pending: &PendingRequests,
+    ) -> Result<ChannelPluginCapabilities> {
+        let name = &self.manifest.name;
+        let id = self
+            .request_id
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        let (tx, rx) = oneshot::channel();
+        let registered = pending
+            .lock()
+            .ok()
+            .and_then(|mut pending| pending.as_mut().map(|p| p.insert(id, tx)))
+            .is_some();
+        if !registered {
+            return Err(ZeptoError::Channel(format!(
+                "Channel plugin '{}' exited before the initialize handshake",
+                name
+            )));
+        }
+
+        let request = ChannelJsonRpcRequest {
+            jsonrpc: "2.0".to_string(),
+            id,
+            method: "initialize".to_string(),
+            params: serde_json::json!({
+                "channel": name,
+                "client": "zeptoclaw",
+                "client_version": env!("CARGO_PKG_VERSION"),
+            }),
+        };
+        write_line(stdin, name, &request).await?;
+
+        let timeout_secs = self.manifest.timeout_secs;
+        let result = match tokio::time::timeout(Duration::from_secs(timeout_secs), rx).await {
+            Ok(Ok(result)) => result,
+            Ok(Err(_)) => {
+                return Err(ZeptoError::Channel(format!(
+                    "Channel plugin '{}' exited during the initialize handshake",
+                    name
+                )))
+            }
+            Err(_) => {
+                return Err(ZeptoError::Channel(format!(
+                    "Channel plugin '{}' did not answer initialize within {}s",
+                    name, timeout_secs
+                )))
+            }
+        };
+
+        let result = result.map_err(|e| {
+            ZeptoError::Channel(format!(
+                "Channel plugin '{}' rejected initialize: {} (code {})",
+                name, e.message, e.code
+            ))
+        })?;
+        serde_json::from_value(result).map_err(|e| {
+            ZeptoError::Channel(format!(
+                "Channel plugin '{}' sent an invalid initialize response: {}",
+                name, e
+            ))
+        })
+    }
+
+    /// Restart delay before restart number `attempt` (0-based).
+    fn backoff(&self, attempt: u32) -> Duration {
+        let (initial, max) = self.restart_backoff;
+        initial
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(max)
+    }
+
+    /// Supervises `process` until shutdown or until restarts are exhausted.
+    async fn run(mut self, mut process: AttachedProcess) {
+        let name = self.manifest.name.clone();
+        let mut restarts: u32 = 0;
+
+        loop {
+            let status = tokio::select! {
+                status = process.child.wait() => status,
+                _ = self.shutdown_rx.changed() => {
+                    *self.child_stdin.lock().await = None;
+                    if let Err(e) = process.child.kill().await {
+                        warn!("Failed to kill channel plugin '{}': {}", name, e);
+                    }
+                    process.reader.abort();
+                    process.stderr.abort();
+                    return;
+                }
+            };
+
+            *self.child_stdin.lock().await = None;
+            // Let the reader drain what the process wrote before exiting.
+            let _ = tokio::time::timeout(Duration::from_secs(1), &mut process.reader).await;
+            process.reader.abort();
+            let _ = tokio::time::timeout(Duration::from_secs(1), &mut process.stderr).await;
+            process.stderr.abort();
+
+            if !self.running.load(Ordering::SeqCst) {
+                return;
+            }
+
+            let status = status
+                .map(|s| s.to_string())
+                .unwrap_or_else(|e| format!("unknown ({})", e));
+            // Its stderr lines have already been logged as they arrived.
+            warn!("Channel plugin '{}' exited ({})", name, status);
+
+            if process.started_at.elapsed() >= RESTART_RESET_UPTIME {
+                restarts = 0;
+            }
+
+            // Restart, retrying failed spawns under the same budget.
+            process = loop {
+                if restarts >= self.manifest.max_restarts {
+                    error!(
+                        "Channel plugin '{}' exceeded {} restarts; giving up",
+                        name, self.manifest.max_restarts
+                    );
+                    self.running.store(false, Ordering::SeqCst);
+                    return;
+                }
+
+                let delay = self.backoff(restarts);
+                restarts += 1;
+                info!(
+                    "Restarting channel plugin '{}' in {:?} (attempt {}/{})",
+                    name, delay, restarts, self.manifest.max_restarts
+                );
+                tokio::select! {
+                    _ = tokio::time::sleep(delay) => {}
+                    _ = self.shutdown_rx.changed() => return,
+                }
+                if !self.running.load(Ordering::SeqCst) {
+                    return;
+                }
+
+                match self.spawn().await {
+                    Ok(process) => break process,
+                    Err(e) => warn!("{}", e),
+                }
+            };
+        }
+    }
 }
 
 impl std::fmt::Debug for ChannelPluginAdapter {
@@ -193,6 +784,11 @@ impl Channel for ChannelPluginAdapter {
             return Ok(());
         }
 
+        // Clean up a supervisor that gave up after too many restarts.
+        if let Some(handle) = self.supervisor_handle.take() {
+            handle.abort();
+        }
+
         let binary_path = self.binary_path();
 
         if !binary_path.exists() {
@@ -203,76 +799,53 @@ impl Channel for ChannelPluginAdapter {
             )));
         }
 
-        let mut cmd = tokio::process::Command::new(&binary_path);
-        cmd.stdin(std::process::Stdio::piped())
-            .stdout(std::process::Stdio::piped())
-            .stderr(std::process::Stdio::piped())
-            .current_dir(&self.plugin_dir);
-
-        // Set environment variables from manifest
-        for (key, value) in &self.manifest.env {
-            cmd.env(key, value);
+        if let Ok(mut tail) = self.stderr_tail.lock() {
+            tail.clear();
         }
 
-        let mut child = cmd.spawn().map_err(|e| {
-            self.running.store(false, Ordering::SeqCst);
-            ZeptoError::Channel(format!(
-                "Failed to spawn channel plugin '{}' ({}): {}",
-                self.manifest.name,
-                binary_path.display(),
-                e
-            ))
-        })?;
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let supervisor = self.supervisor(shutdown_rx);
+        let process = match supervisor.spawn().await {
+            Ok(process) => process,
+            Err(e) => {
+                self.running.store(false, Ordering::SeqCst);
+                return Err(e);
+            }
+        };
 
-        // Take ownership of stdin for sending JSON-RPC requests
-        let stdin = child.stdin.take();
-
-        {
-            let mut stdin_lock = self.child_stdin.lock().await;
-            *stdin_lock = stdin;
-        }
-        {
-            let mut child_lock = self.child_handle.lock().await;
-            *child_lock = Some(child);
-        }
+        let pid = process.child.id();
+        self.shutdown_tx = Some(shutdown_tx);
+        self.supervisor_handle = Some(tokio::spawn(supervisor.run(process)));
 
         info!(
-            "Channel plugin '{}' v{} started (pid: running)",
-            self.manifest.name, self.manifest.version
+            "Channel plugin '{}' v{} started (pid: {:?})",
+            self.manifest.name, self.manifest.version, pid
         );
 
         Ok(())
     }
 
     async fn stop(&mut self) -> Result<()> {
-        if !self.running.swap(false, Ordering::SeqCst) {
-            info!("Channel plugin '{}' already stopped", self.manifest.name);
-            return Ok(());
-        }
+        let was_running = self.running.swap(false, Ordering::SeqCst);
 
-        info!("Stopping channel plugin '{}'", self.manifest.name);
-
-        // Drop stdin to signal EOF to the child
-        {
-            let mut stdin_lock = self.child_stdin.lock().await;
-            *stdin_lock = None;
+        // Tell the supervisor to kill the child, then wait for it so the
+        // process is gone when stop() returns.
+        if let Some(shutdown_tx) = self.shutdown_tx.take() {
+            let _ = shutdown_tx.send(true);
         }
-
-        // Kill the child process
-        {
-            let mut child_lock = self.child_handle.lock().await;
-            if let Some(ref mut child) = *child_lock {
-                if let Err(e) = child.kill().await {
-                    warn!(
-                        "Failed to kill channel plugin '{}': {}",
-                        self.manifest.name, e
-                    );
-                }
-            }
-            *child_lock = None;
+        if let Some(handle) = self.supervisor_handle.take() {
+            let _ = handle.await;
+        }
+        *self.child_stdin.lock().await = None;
+        if let Ok(mut caps) = self.capabilities.write() {
+            *caps = None;
         }
 
-        info!("Channel plugin '{}' stopped", self.manifest.name);
+        if was_running {
+            info!("Channel plugin '{}' stopped", self.manifest.name);
+        } else {
+            info!("Channel plugin '{}' already stopped", self.manifest.name);
+        }
         Ok(())
     }
 
@@ -284,6 +857,15 @@ impl Channel for ChannelPluginAdapter {
             )));
         }
 
+        if let Some(capabilities) = self.capabilities() {
+            if !capabilities.supports("send") {
+                return Err(ZeptoError::Channel(format!(
+                    "Channel plugin '{}' does not support 'send' (advertised: {:?})",
+                    self.manifest.name, capabilities.methods
+                )));
+            }
+        }
+
         let request = ChannelJsonRpcRequest {
             jsonrpc: "2.0".to_string(),
             id: self.next_id(),
@@ -295,40 +877,9 @@ impl Channel for ChannelPluginAdapter {
             }),
         };
 
-        let mut request_json = serde_json::to_string(&request).map_err(|e| {
-            ZeptoError::Channel(format!(
-                "Failed to serialize JSON-RPC request for channel plugin '{}': {}",
-                self.manifest.name, e
-            ))
-        })?;
-        request_json.push('\n');
-
-        let mut stdin_lock = self.child_stdin.lock().await;
-        match stdin_lock.as_mut() {
-            Some(stdin) => {
-                use tokio::io::AsyncWriteExt;
-                stdin
-                    .write_all(request_json.as_bytes())
-                    .await
-                    .map_err(|e| {
-                        ZeptoError::Channel(format!(
-                            "Failed to write to channel plugin '{}' stdin: {}",
-                            self.manifest.name, e
-                        ))
-                    })?;
-                stdin.flush().await.map_err(|e| {
-                    ZeptoError::Channel(format!(
-                        "Failed to flush channel plugin '{}' stdin: {}",
-                        self.manifest.name, e
-                    ))
-                })?;
-                Ok(())
-            }
-            None => Err(ZeptoError::Channel(format!(
-                "Channel plugin '{}' stdin not available",
-                self.manifest.name
-            ))),
-        }
+        write_request(&self.child_stdin, &self.manifest.name, &request)
+            .await
+            .map_err(|e| with_recent_stderr(e, &self.stderr_tail))
     }
 
     fn is_running(&self) -> bool {
@@ -340,6 +891,55 @@ impl Channel for ChannelPluginAdapter {
     }
 }
 
+/// Writes one JSON-RPC request line to the plugin's stdin.
+async fn write_request(
+    child_stdin: &Mutex<Option<tokio::process::ChildStdin>>,
+    plugin_name: &str,
+    request: &ChannelJsonRpcRequest,
+) -> Result<()> {
+    let mut stdin_lock = child_stdin.lock().await;
+    write_line(stdin_lock.as_mut(), plugin_name, request).await
+}
+
+/// Writes one JSON-RPC request line to `stdin`, if the plugin has one.
+async fn write_line(
+    stdin: Option<&mut tokio::process::ChildStdin>,
+    plugin_name: &str,
+    request: &ChannelJsonRpcRequest,
+) -> Result<()> {
+    use tokio::io::AsyncWriteExt;
+
+    let mut request_json = serde_json::to_string(request).map_err(|e| {
+        ZeptoError::Channel(format!(
+            "Failed to serialize JSON-RPC request for channel plugin '{}': {}",
+            plugin_name, e
+        ))
+    })?;
+    request_json.push('\n');
+
+    let Some(stdin) = stdin else {
+        return Err(ZeptoError::Channel(format!(
+            "Channel plugin '{}' stdin not available (binary restarting)",
+            plugin_name
+        )));
+    };
+    stdin
+        .write_all(request_json.as_bytes())
+        .await
+        .map_err(|e| {
+            ZeptoError::Channel(format!(
+                "Failed to write to channel plugin '{}' stdin: {}",
+                plugin_name, e
+            ))
+        })?;
+    stdin.flush().await.map_err(|e| {
+        ZeptoError::Channel(format!(
+            "Failed to flush channel plugin '{}' stdin: {}",
+            plugin_name, e
+        ))
+    })
+}
+
 // ---- Plugin Discovery ----
 
 /// Discovers channel plugins in the given directory.
@@ -461,6 +1061,10 @@ mod tests {
     use serde_json::json;
     use tempfile::TempDir;
 
+    fn test_bus() -> Arc<MessageBus> {
+        Arc::new(MessageBus::new())
+    }
+
     // ---- Manifest parsing tests ----
 
     #[test]
@@ -494,6 +1098,7 @@ mod tests {
         assert_eq!(manifest.name, "minimal");
         assert!(manifest.env.is_empty());
         assert_eq!(manifest.timeout_secs, 30); // default
+        assert_eq!(manifest.max_restarts, 5); // default
     }
 
     #[test]
@@ -534,11 +1139,13 @@ mod tests {
                 m
             },
             timeout_secs: 45,
+            max_restarts: 2,
         };
         let json = serde_json::to_string(&manifest).unwrap();
         let parsed: ChannelPluginManifest = serde_json::from_str(&json).unwrap();
         assert_eq!(parsed.name, "roundtrip");
         assert_eq!(parsed.timeout_secs, 45);
+        assert_eq!(parsed.max_restarts, 2);
         assert_eq!(parsed.env.get("KEY").unwrap(), "VALUE");
     }
 
@@ -552,6 +1159,7 @@ mod tests {
             binary: "test-binary".to_string(),
             env: HashMap::new(),
             timeout_secs: 30,
+            max_restarts: 5,
         }
     }
 
@@ -561,6 +1169,7 @@ mod tests {
             test_manifest(),
             PathBuf::from("/tmp/plugins/test"),
             BaseChannelConfig::new("test-plugin"),
+            test_bus(),
         );
         assert_eq!(adapter.name(), "test-plugin");
     }
@@ -571,6 +1180,7 @@ mod tests {
             test_manifest(),
             PathBuf::from("/tmp/plugins/test"),
             BaseChannelConfig::new("test-plugin"),
+            test_bus(),
         );
         assert_eq!(
             adapter.binary_path(),
@@ -584,6 +1194,7 @@ mod tests {
             test_manifest(),
             PathBuf::from("/tmp/plugins/test"),
             BaseChannelConfig::new("test-plugin"),
+            test_bus(),
         );
         assert!(!adapter.is_running());
     }
@@ -594,6 +1205,7 @@ mod tests {
             test_manifest(),
             PathBuf::from("/tmp/plugins/test"),
             BaseChannelConfig::new("test-plugin"),
+            test_bus(),
         );
         assert_eq!(adapter.manifest().version, "0.1.0");
         assert_eq!(adapter.manifest().description, "Test plugin");
@@ -607,6 +1219,7 @@ mod tests {
             test_manifest(),
             PathBuf::from("/tmp"),
             BaseChannelConfig::new("test"),
+            test_bus(),
         );
         assert!(adapter.is_allowed("anyone"));
         assert!(adapter.is_allowed("user123"));
@@ -618,7 +1231,8 @@ mod tests {
             "test",
             vec!["user1".to_string(), "user2".to_string()],
         );
-        let adapter = ChannelPluginAdapter::new(test_manifest(), PathBuf::from("/tmp"), base);
+        let adapter =
+            ChannelPluginAdapter::new(test_manifest(), PathBuf::from("/tmp"), base, test_bus());
         assert!(adapter.is_allowed("user1"));
         assert!(adapter.is_allowed("user2"));
         assert!(!adapter.is_allowed("user3"));
@@ -631,7 +1245,8 @@ mod tests {
             allowlist: vec![],
             deny_by_default: true,
         };
-        let adapter = ChannelPluginAdapter::new(test_manifest(), PathBuf::from("/tmp"), base);
+        let adapter =
+            ChannelPluginAdapter::new(test_manifest(), PathBuf::from("/tmp"), base, test_bus());
         assert!(!adapter.is_allowed("anyone"));
     }
 
@@ -686,6 +1301,7 @@ mod tests {
             test_manifest(),
             PathBuf::from("/tmp"),
             BaseChannelConfig::new("test"),
+            test_bus(),
         );
         let msg = OutboundMessage::new("test", "chat1", "Hello");
         let result = adapter.send(msg).await;
@@ -702,6 +1318,7 @@ mod tests {
             test_manifest(),
             PathBuf::from("/nonexistent/path"),
             BaseChannelConfig::new("test"),
+            test_bus(),
         );
         let result = adapter.start().await;
         assert!(result.is_err());
@@ -847,6 +1464,7 @@ mod tests {
             test_manifest(),
             PathBuf::from("/tmp/plugins/test"),
             BaseChannelConfig::new("test-plugin"),
+            test_bus(),
         );
         let debug = format!("{:?}", adapter);
         assert!(debug.contains("test-plugin"));
@@ -875,6 +1493,7 @@ mod tests {
             test_manifest(),
             PathBuf::from("/tmp"),
             BaseChannelConfig::new("test"),
+            test_bus(),
         );
         adapter.running.store(true, Ordering::SeqCst);
         let result = adapter.start().await;
@@ -890,6 +1509,7 @@ mod tests {
             test_manifest(),
             PathBuf::from("/tmp"),
             BaseChannelConfig::new("test"),
+            test_bus(),
         );
         let result = adapter.stop().await;
         assert!(result.is_ok());
@@ -898,20 +1518,46 @@ mod tests {
 
     // ---- Start and stop with real binary ----
 
+    /// Shell snippet answering the `initialize` request with the given
+    /// methods, echoing the request id.
     #[cfg(unix)]
-    #[tokio::test]
-    async fn test_start_and_stop_real_binary() {
+    fn handshake(methods: &str) -> String {
+        format!(
+            "read -r line\n\
+             id=$(printf '%s' \"$line\" | sed 's/.*\"id\":\\([0-9]*\\).*/\\1/')\n\
+             printf '{{\"jsonrpc\":\"2.0\",\"id\":%s,\"result\":{{\"version\":\"0.1.0\",\"methods\":[{}]}}}}\\n' \"$id\"\n",
+            methods
+        )
+    }
+
+    #[cfg(unix)]
+    fn write_script(dir: &Path, body: &str) {
         use std::os::unix::fs::PermissionsExt;
 
+        let path = dir.join("channel-plugin");
+        std::fs::write(&path, format!("#!/bin/sh\n{}", body)).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    #[cfg(unix)]
+    fn count_lines(path: &Path) -> usize {
+        std::fs::read_to_string(path)
+            .map(|s| s.lines().count())
+            .unwrap_or(0)
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_start_and_stop_real_binary() {
         let dir = TempDir::new().unwrap();
-        let binary_path = dir.path().join("channel-plugin");
-        // Write a simple script that reads stdin and stays alive
-        std::fs::write(
-            &binary_path,
-            "#!/bin/sh\nwhile read line; do echo '{\"jsonrpc\":\"2.0\",\"result\":{\"status\":\"ok\"},\"id\":1}'; done\n",
-        )
-        .unwrap();
-        std::fs::set_permissions(&binary_path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        // Write a simple script that handshakes, reads stdin and stays alive
+        write_script(
+            dir.path(),
+            &format!(
+                "{}while read line; do echo '{{\"jsonrpc\":\"2.0\",\"result\":{{\"status\":\"ok\"}},\"id\":2}}'; done\n",
+                handshake("\"send\"")
+            ),
+        );
 
         let manifest = ChannelPluginManifest {
             name: "real-test".to_string(),
@@ -920,18 +1566,23 @@ mod tests {
             binary: "channel-plugin".to_string(),
             env: HashMap::new(),
             timeout_secs: 30,
+            max_restarts: 5,
         };
 
         let mut adapter = ChannelPluginAdapter::new(
             manifest,
             dir.path().to_path_buf(),
             BaseChannelConfig::new("real-test"),
+            test_bus(),
         );
 
         // Start
         let result = adapter.start().await;
         assert!(result.is_ok(), "start failed: {:?}", result);
         assert!(adapter.is_running());
+        let capabilities = adapter.capabilities().expect("handshake capabilities");
+        assert_eq!(capabilities.version, "0.1.0");
+        assert!(capabilities.supports("send"));
 
         // Send a message
         let msg = OutboundMessage::new("real-test", "chat1", "Hello from test");
@@ -942,5 +1593,510 @@ mod tests {
         let stop_result = adapter.stop().await;
         assert!(stop_result.is_ok(), "stop failed: {:?}", stop_result);
         assert!(!adapter.is_running());
+        assert!(adapter.capabilities().is_none());
+    }
+
+    // ---- Handshake ----
+
+    #[cfg(unix)]
+    fn handshake_adapter(dir: &Path, timeout_secs: u64) -> ChannelPluginAdapter {
+        let mut manifest = test_manifest();
+        manifest.binary = "channel-plugin".to_string();
+        manifest.timeout_secs = timeout_secs;
+        ChannelPluginAdapter::new(
+            manifest,
+            dir.to_path_buf(),
+            BaseChannelConfig::new("test-plugin"),
+            test_bus(),
+        )
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_handshake_timeout_fails_start_and_kills_child() {
+        let dir = TempDir::new().unwrap();
+        write_script(dir.path(), "echo $$ > pid\nexec sleep 30\n");
+
+        let mut adapter = handshake_adapter(dir.path(), 1);
+        let err = adapter.start().await.unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("did not answer initialize within 1s"),
+            "unexpected error: {}",
+            err
+        );
+        assert!(!adapter.is_running());
+        assert!(adapter.capabilities().is_none());
+
+        let pid = std::fs::read_to_string(dir.path().join("pid")).unwrap();
+        let proc_path = PathBuf::from(format!("/proc/{}", pid.trim()));
+        if PathBuf::from("/proc/self").exists() {
+            assert!(!proc_path.exists(), "plugin process should be killed");
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_handshake_rejects_exit_error_and_invalid_response() {
+        let dir = TempDir::new().unwrap();
+
+        // Depending on timing the exit surfaces as a closed pipe on write or
+        // as stdout closing before the response.
+        write_script(dir.path(), "exit 0\n");
+        let err = handshake_adapter(dir.path(), 5).start().await.unwrap_err();
+        let err = err.to_string();
+        assert!(
+            err.contains("initialize handshake") || err.contains("stdin"),
+            "{}",
+            err
+        );
+
+        write_script(
+            dir.path(),
+            "read -r line\necho '{\"jsonrpc\":\"2.0\",\"id\":1,\"error\":{\"code\":-32601,\"message\":\"nope\"}}'\nsleep 5\n",
+        );
+        let err = handshake_adapter(dir.path(), 5).start().await.unwrap_err();
+        assert!(
+            err.to_string().contains("rejected initialize: nope"),
+            "{}",
+            err
+        );
+
+        write_script(
+            dir.path(),
+            "read -r line\necho '{\"jsonrpc\":\"2.0\",\"id\":1,\"result\":{\"status\":\"ok\"}}'\nsleep 5\n",
+        );
+        let mut adapter = handshake_adapter(dir.path(), 5);
+        let err = adapter.start().await.unwrap_err();
+        assert!(
+            err.to_string().contains("invalid initialize response"),
+            "{}",
+            err
+        );
+        assert!(!adapter.is_running());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_send_refused_without_send_capability() {
+        let dir = TempDir::new().unwrap();
+        write_script(
+            dir.path(),
+            &format!("{}while read line; do :; done\n", handshake("\"receive\"")),
+        );
+
+        let mut adapter = handshake_adapter(dir.path(), 5);
+        adapter.start().await.unwrap();
+        let err = adapter
+            .send(OutboundMessage::new("test-plugin", "c1", "hi"))
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("does not support 'send'"),
+            "{}",
+            err
+        );
+        adapter.stop().await.unwrap();
+    }
+
+    // ---- Inbound notifications ----
+
+    #[test]
+    fn test_parse_inbound_line_receive() {
+        let line = r#"{"jsonrpc":"2.0","method":"receive","params":{"sender_id":"u1","chat_id":"c1","content":"hi","metadata":{"thread":"t9"}}}"#;
+        let Some(PluginOutput::Inbound(inbound)) = ChannelPluginAdapter::parse_stdout_line(
+            line,
+            "matrix",
+            &BaseChannelConfig::new("matrix"),
+        ) else {
+            panic!("receive notification should parse");
+        };
+        assert_eq!(inbound.channel, "matrix");
+        assert_eq!(inbound.sender_id, "u1");
+        assert_eq!(inbound.chat_id, "c1");
+        assert_eq!(inbound.content, "hi");
+        assert_eq!(inbound.session_key, "matrix:c1");
+        assert_eq!(
+            inbound.metadata.get("thread").map(String::as_str),
+            Some("t9")
+        );
+    }
+
+    #[test]
+    fn test_parse_inbound_line_ignores_other_lines() {
+        let base = BaseChannelConfig::new("matrix");
+        for line in [
+            "",
+            "   ",
+            "not json",
+            r#"{"jsonrpc":"2.0","method":"log","params":{}}"#,
+            r#"{"jsonrpc":"2.0","method":"receive","params":{"chat_id":"c1"}}"#,
+        ] {
+            assert!(
+                ChannelPluginAdapter::parse_stdout_line(line, "matrix", &base).is_none(),
+                "line should be skipped: {:?}",
+                line
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_inbound_line_respects_allowlist() {
+        let base = BaseChannelConfig::with_allowlist("matrix", vec!["u1".to_string()]);
+        let allowed =
+            r#"{"method":"receive","params":{"sender_id":"u1","chat_id":"c1","content":"hi"}}"#;
+        let denied =
+            r#"{"method":"receive","params":{"sender_id":"u2","chat_id":"c1","content":"hi"}}"#;
+        assert!(ChannelPluginAdapter::parse_stdout_line(allowed, "matrix", &base).is_some());
+        assert!(ChannelPluginAdapter::parse_stdout_line(denied, "matrix", &base).is_none());
+    }
+
+    #[test]
+    fn test_parse_stdout_line_responses() {
+        let base = BaseChannelConfig::new("matrix");
+        let ok = r#"{"jsonrpc":"2.0","result":{"status":"ok"},"id":1}"#;
+        match ChannelPluginAdapter::parse_stdout_line(ok, "matrix", &base) {
+            Some(PluginOutput::Response(1, Ok(result))) => assert_eq!(result["status"], "ok"),
+            _ => panic!("expected a successful response"),
+        }
+
+        let err = r#"{"jsonrpc":"2.0","error":{"code":-1,"message":"bad"},"id":7}"#;
+        match ChannelPluginAdapter::parse_stdout_line(err, "matrix", &base) {
+            Some(PluginOutput::Response(7, Err(e))) => assert_eq!(e.message, "bad"),
+            _ => panic!("expected an error response"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_stdout_handles_partial_lines_and_eof() {
+        use tokio::io::AsyncWriteExt;
+
+        let (mut writer, reader) = tokio::io::duplex(64);
+        let bus = test_bus();
+        let pending: PendingRequests = Arc::new(std::sync::Mutex::new(Some(HashMap::new())));
+        let (response_tx, response_rx) = oneshot::channel();
+        let (orphan_tx, orphan_rx) = oneshot::channel();
+        {
+            let mut guard = pending.lock().unwrap();
+            let map = guard.as_mut().unwrap();
+            map.insert(3, response_tx);
+            map.insert(4, orphan_tx);
+        }
+        let task = tokio::spawn(ChannelPluginAdapter::read_stdout(
+            BufReader::new(reader),
+            "matrix".to_string(),
+            BaseChannelConfig::new("matrix"),
+            bus.clone(),
+            pending.clone(),
+        ));
+
+        // A notification split across writes, then a malformed line.
+        writer
+            .write_all(br#"{"method":"receive","params":{"sender_id":"u1","#)
+            .await
+            .unwrap();
+        writer.flush().await.unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        writer
+            .write_all(b"\"chat_id\":\"c1\",\"content\":\"first\"}}\n{oops\n")
+            .await
+            .unwrap();
+        writer
+            .write_all(br#"{"method":"receive","params":{"sender_id":"u1","chat_id":"c1","content":"second"}}"#)
+            .await
+            .unwrap();
+        writer.write_all(b"\n").await.unwrap();
+        writer
+            .write_all(b"{\"jsonrpc\":\"2.0\",\"id\":3,\"result\":{\"methods\":[]}}\n")
+            .await
+            .unwrap();
+        drop(writer);
+
+        // EOF ends the reader and drops waiters that got no answer.
+        tokio::time::timeout(std::time::Duration::from_secs(2), task)
+            .await
+            .expect("reader should finish on EOF")
+            .unwrap();
+        assert!(response_rx.await.unwrap().is_ok());
+        assert!(orphan_rx.await.is_err());
+        assert!(pending.lock().unwrap().is_none());
+        assert_eq!(bus.consume_inbound().await.unwrap().content, "first");
+        assert_eq!(bus.consume_inbound().await.unwrap().content, "second");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_real_binary_publishes_inbound_and_exits() {
+        let dir = TempDir::new().unwrap();
+        write_script(
+            dir.path(),
+            &format!(
+                "{}echo '{{\"jsonrpc\":\"2.0\",\"method\":\"receive\",\"params\":{{\"sender_id\":\"u1\",\"chat_id\":\"room\",\"content\":\"hello bot\"}}}}'\n",
+                handshake("\"send\"")
+            ),
+        );
+
+        let mut manifest = test_manifest();
+        manifest.binary = "channel-plugin".to_string();
+        manifest.max_restarts = 0;
+        let bus = test_bus();
+        let mut adapter = ChannelPluginAdapter::new(
+            manifest,
+            dir.path().to_path_buf(),
+            BaseChannelConfig::new("test-plugin"),
+            bus.clone(),
+        );
+        adapter.start().await.unwrap();
+
+        let inbound =
+            tokio::time::timeout(std::time::Duration::from_secs(5), bus.consume_inbound())
+                .await
+                .expect("inbound message within timeout")
+                .unwrap();
+        assert_eq!(inbound.chat_id, "room");
+        assert_eq!(inbound.content, "hello bot");
+
+        // The script exits after one line; with no restarts allowed the
+        // channel is marked stopped.
+        for _ in 0..50 {
+            if !adapter.is_running() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        assert!(!adapter.is_running());
+        assert!(adapter.stop().await.is_ok());
+    }
+
+    // ---- Supervision ----
+
+    #[test]
+    fn test_restart_backoff_doubles_and_caps() {
+        let adapter = ChannelPluginAdapter::new(
+            test_manifest(),
+            PathBuf::from("/tmp"),
+            BaseChannelConfig::new("test"),
+            test_bus(),
+        );
+        let (_tx, rx) = watch::channel(false);
+        let supervisor = adapter.supervisor(rx);
+        assert_eq!(supervisor.backoff(0), Duration::from_secs(1));
+        assert_eq!(supervisor.backoff(1), Duration::from_secs(2));
+        assert_eq!(supervisor.backoff(3), Duration::from_secs(8));
+        assert_eq!(supervisor.backoff(5), Duration::from_secs(30));
+        assert_eq!(supervisor.backoff(40), Duration::from_secs(30));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_crashed_binary_is_restarted_and_stdin_reattached() {
+        let dir = TempDir::new().unwrap();
+        // First run crashes after the handshake; later runs stay up and
+        // record what they receive.
+        write_script(
+            dir.path(),
+            &format!(
+                "echo run >> runs.log\n{}\
+                 if [ \"$(wc -l < runs.log)\" -lt 2 ]; then echo boom >&2; exit 1; fi\n\
+                 while read line; do echo \"$line\" >> sent.log; done\n",
+                handshake("\"send\"")
+            ),
+        );
+
+        let mut manifest = test_manifest();
+        manifest.binary = "channel-plugin".to_string();
+        let mut adapter = ChannelPluginAdapter::new(
+            manifest,
+            dir.path().to_path_buf(),
+            BaseChannelConfig::new("test-plugin"),
+            test_bus(),
+        );
+        adapter.restart_backoff = (Duration::from_millis(10), Duration::from_millis(40));
+        adapter.start().await.unwrap();
+
+        let mut sent = false;
+        for _ in 0..100 {
+            if count_lines(&dir.path().join("runs.log")) >= 2
+                && adapter
+                    .send(OutboundMessage::new("test-plugin", "c1", "after restart"))
+                    .await
+                    .is_ok()
+            {
+                sent = true;
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert!(sent, "send should succeed once the binary is restarted");
+        assert!(adapter.is_running());
+
+        for _ in 0..100 {
+            if count_lines(&dir.path().join("sent.log")) >= 1 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        let sent_log = std::fs::read_to_string(dir.path().join("sent.log")).unwrap();
+        assert!(sent_log.contains("after restart"));
+
+        adapter.stop().await.unwrap();
+        assert_eq!(count_lines(&dir.path().join("runs.log")), 2);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_send_during_restart_arrives_after_initialize() {
+        let dir = TempDir::new().unwrap();
+        // The first run crashes after the handshake. The restarted run takes
+        // a while to answer initialize, marks when it does, and logs every
+        // line it receives, initialize included.
+        write_script(
+            dir.path(),
+            &format!(
+                "echo run >> runs.log\n\
+                 if [ \"$(wc -l < runs.log)\" -lt 2 ]; then\n{}exit 1\nfi\n\
+                 sleep 0.3\n\
+                 read -r line\n\
+                 echo \"$line\" >> in.log\n\
+                 id=$(printf '%s' \"$line\" | sed 's/.*\"id\":\\([0-9]*\\).*/\\1/')\n\
+                 touch answered\n\
+                 printf '{{\"jsonrpc\":\"2.0\",\"id\":%s,\"result\":{{\"version\":\"0.1.0\",\"methods\":[\"send\"]}}}}\\n' \"$id\"\n\
+                 while read line; do echo \"$line\" >> in.log; done\n",
+                handshake("\"send\"")
+            ),
+        );
+
+        let mut manifest = test_manifest();
+        manifest.binary = "channel-plugin".to_string();
+        let mut adapter = ChannelPluginAdapter::new(
+            manifest,
+            dir.path().to_path_buf(),
+            BaseChannelConfig::new("test-plugin"),
+            test_bus(),
+        );
+        adapter.restart_backoff = (Duration::from_millis(10), Duration::from_millis(40));
+        adapter.start().await.unwrap();
+
+        // Keep sending through the restart until a send is accepted; none
+        // may get through before the plugin answered initialize.
+        let mut sent = false;
+        for _ in 0..300 {
+            if count_lines(&dir.path().join("runs.log")) >= 2
+                && adapter
+                    .send(OutboundMessage::new("test-plugin", "c1", "during restart"))
+                    .await
+                    .is_ok()
+            {
+                assert!(
+                    dir.path().join("answered").exists(),
+                    "send accepted before the initialize handshake finished"
+                );
+                sent = true;
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        assert!(sent, "send should succeed once the binary is restarted");
+
+        for _ in 0..100 {
+            if count_lines(&dir.path().join("in.log")) >= 2 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        let received = std::fs::read_to_string(dir.path().join("in.log")).unwrap();
+        let lines: Vec<&str> = received.lines().collect();
+        assert_eq!(lines.len(), 2, "{}", received);
+        assert!(lines[0].contains("\"initialize\""), "{}", received);
+        assert!(lines[1].contains("during restart"), "{}", received);
+
+        adapter.stop().await.unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_gives_up_after_max_restarts() {
+        let dir = TempDir::new().unwrap();
+        write_script(
+            dir.path(),
+            &format!(
+                "echo run >> runs.log\n{}echo boom >&2\nexit 3\n",
+                handshake("\"send\"")
+            ),
+        );
+
+        let mut manifest = test_manifest();
+        manifest.binary = "channel-plugin".to_string();
+        manifest.max_restarts = 2;
+        let mut adapter = ChannelPluginAdapter::new(
+            manifest,
+            dir.path().to_path_buf(),
+            BaseChannelConfig::new("test-plugin"),
+            test_bus(),
+        );
+        adapter.restart_backoff = (Duration::from_millis(10), Duration::from_millis(40));
+        adapter.start().await.unwrap();
+
+        for _ in 0..200 {
+            if !adapter.is_running() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert!(!adapter.is_running(), "adapter should give up");
+        // Initial spawn plus two restarts.
+        assert_eq!(count_lines(&dir.path().join("runs.log")), 3);
+
+        let err = adapter
+            .send(OutboundMessage::new("test-plugin", "c1", "hi"))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not running"));
+        assert!(adapter.stop().await.is_ok());
+    }
+
+    // ---- Stderr capture ----
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_stderr_lines_are_captured() {
+        let dir = TempDir::new().unwrap();
+        write_script(
+            dir.path(),
+            &format!(
+                "echo 'warming up' >&2\n{}i=1\nwhile [ $i -le 25 ]; do echo \"line $i\" >&2; i=$((i+1)); done\nwhile read line; do :; done\n",
+                handshake("\"send\"")
+            ),
+        );
+
+        let mut adapter = handshake_adapter(dir.path(), 5);
+        adapter.start().await.unwrap();
+
+        let mut recent = Vec::new();
+        for _ in 0..100 {
+            recent = adapter.recent_stderr();
+            if recent.last().map(String::as_str) == Some("line 25") {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        // Only the last STDERR_TAIL_LINES lines are kept.
+        assert_eq!(recent.len(), STDERR_TAIL_LINES);
+        assert_eq!(recent.first().map(String::as_str), Some("line 6"));
+        assert_eq!(recent.last().map(String::as_str), Some("line 25"));
+        adapter.stop().await.unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_start_error_includes_recent_stderr() {
+        let dir = TempDir::new().unwrap();
+        write_script(dir.path(), "echo 'fatal: missing API token' >&2\nexit 1\n");
+
+        let mut adapter = handshake_adapter(dir.path(), 5);
+        let err = adapter.start().await.unwrap_err().to_string();
+        assert!(err.contains("Recent plugin stderr:"), "{}", err);
+        assert!(err.contains("fatal: missing API token"), "{}", err);
+        assert_eq!(adapter.recent_stderr(), vec!["fatal: missing API token"]);
     }
 }
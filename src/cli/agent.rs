@@ -1,15 +1,17 @@
 //! Agent command handlers (interactive + stdin mode).
 
-use std::io::{self, BufRead, Write};
+use std::io::{self, BufRead, IsTerminal, Write};
 use std::sync::Arc;
 
 use anyhow::{Context, Result};
 
+use zeptoclaw::agent::{PlanApprovalHandler, ToolPlan};
 use zeptoclaw::bus::{InboundMessage, MessageBus};
 use zeptoclaw::config::Config;
 use zeptoclaw::providers::{
     configured_provider_names, resolve_runtime_provider, RUNTIME_SUPPORTED_PROVIDERS,
 };
+use zeptoclaw::tools::approval::ApprovalResponse;
 
 use super::common::{create_agent, create_agent_with_template, resolve_template};
 
@@ -27,7 +29,7 @@ pub(crate) async fn cmd_agent(
     let bus = Arc::new(MessageBus::new());
 
     let template = if let Some(name) = template_name.as_deref() {
-        Some(resolve_template(name)?)
+        Some(resolve_template(&config, name)?)
     } else {
         None
     };
@@ -39,10 +41,17 @@ pub(crate) async fn cmd_agent(
         create_agent(config.clone(), bus.clone()).await?
     };
 
+    // Plan-first mode: confirm each tool plan on the terminal
+    if config.agents.defaults.plan_first {
+        agent
+            .set_plan_approval_handler(Arc::new(StdinPlanApproval))
+            .await;
+    }
+
     // Enable dry-run mode if requested
     if dry_run {
         agent.set_dry_run(true);
-        eprintln!("[DRY RUN] Tool execution disabled — showing what would happen");
+        eprintln!("[DRY RUN] Plan-only mode — mutating tools report what they would do");
     }
 
     // Set up tool execution feedback (shows progress on stderr)
@@ -88,6 +97,16 @@ pub(crate) async fn cmd_agent(
         eprintln!();
     }
 
+    // Without --message, piped stdin supplies the single message.
+    let message = match message {
+        Some(msg) => Some(msg),
+        None if !io::stdin().is_terminal() => Some(
+            read_single_message(io::stdin().lock())?
+                .context("No message given: pass --message or pipe one line on stdin")?,
+        ),
+        None => None,
+    };
+
     if let Some(msg) = message {
         // Single message mode
         let inbound = InboundMessage::new("cli", "user", "cli", &msg);
@@ -211,9 +230,55 @@ pub(crate) async fn cmd_agent(
         }
     }
 
+    // Cost summary (only when `cost.enabled` is set)
+    if let Some(tracker) = agent.cost_tracker() {
+        if tracker.call_count() > 0 {
+            eprintln!("[cost] {}", tracker.summary());
+        }
+    }
+
     Ok(())
 }
 
+/// Read one non-empty message line from `reader`, trimming surrounding whitespace.
+///
+/// Returns `None` at EOF or when the line is blank.
+fn read_single_message(mut reader: impl BufRead) -> Result<Option<String>> {
+    let mut line = String::new();
+    reader
+        .read_line(&mut line)
+        .with_context(|| "Failed to read message from stdin")?;
+    let line = line.trim();
+    Ok((!line.is_empty()).then(|| line.to_string()))
+}
+
+/// Plan reviewer that prints the proposed tool plan and asks for confirmation on stdin.
+struct StdinPlanApproval;
+
+#[async_trait::async_trait]
+impl PlanApprovalHandler for StdinPlanApproval {
+    async fn review_plan(&self, plan: &ToolPlan) -> ApprovalResponse {
+        eprintln!();
+        eprintln!("Proposed plan:");
+        eprintln!("{}", plan.plan);
+        eprint!("Execute this plan? [y/N] ");
+        let _ = io::stderr().flush();
+
+        let answer = tokio::task::spawn_blocking(|| {
+            let mut line = String::new();
+            io::stdin().lock().read_line(&mut line).map(|_| line)
+        })
+        .await;
+
+        match answer {
+            Ok(Ok(line)) if matches!(line.trim().to_ascii_lowercase().as_str(), "y" | "yes") => {
+                ApprovalResponse::Approved
+            }
+            _ => ApprovalResponse::Denied("declined at prompt".to_string()),
+        }
+    }
+}
+
 /// Run agent in stdin/stdout mode for containerized execution.
 pub(crate) async fn cmd_agent_stdin() -> Result<()> {
     let mut config = Config::load().with_context(|| "Failed to load configuration")?;
@@ -344,6 +409,17 @@ mod tests {
         assert!(msg.contains("Wait"));
     }
 
+    #[test]
+    fn test_read_single_message() {
+        let input = io::Cursor::new("  summarize README.md \nsecond line\n");
+        assert_eq!(
+            read_single_message(input).unwrap().as_deref(),
+            Some("summarize README.md")
+        );
+        assert_eq!(read_single_message(io::Cursor::new("")).unwrap(), None);
+        assert_eq!(read_single_message(io::Cursor::new("  \n")).unwrap(), None);
+    }
+
     #[test]
     fn test_format_cli_error_generic() {
         let e = anyhow::anyhow!("Something went wrong");
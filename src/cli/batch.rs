@@ -6,6 +6,7 @@ use std::time::Instant;
 
 use anyhow::{Context, Result};
 
+use zeptoclaw::agent::TurnResult;
 use zeptoclaw::batch::{format_results, load_prompts, BatchOutputFormat, BatchResult};
 use zeptoclaw::bus::{InboundMessage, MessageBus};
 use zeptoclaw::config::Config;
@@ -35,7 +36,7 @@ pub(crate) async fn cmd_batch(
 
     let bus = Arc::new(MessageBus::new());
     let agent = if let Some(name) = template.as_deref() {
-        let tpl = resolve_template(name)?;
+        let tpl = resolve_template(&config, name)?;
         create_agent_with_template(config, bus, Some(tpl)).await?
     } else {
         create_agent(config, bus).await?
@@ -52,19 +53,20 @@ pub(crate) async fn cmd_batch(
             process_streaming(&agent, &inbound).await
         } else {
             agent
-                .process_message(&inbound)
+                .process_message_traced(&inbound)
                 .await
                 .map_err(anyhow::Error::from)
         };
 
         let duration_ms = start.elapsed().as_millis() as u64;
         match response {
-            Ok(content) => results.push(BatchResult {
+            Ok(turn) => results.push(BatchResult {
                 index,
                 prompt,
-                response: Some(content),
+                response: Some(turn.content),
                 error: None,
                 duration_ms,
+                tool_calls: turn.tool_calls,
             }),
             Err(err) => {
                 failed += 1;
@@ -74,6 +76,7 @@ pub(crate) async fn cmd_batch(
                     response: None,
                     error: Some(err.to_string()),
                     duration_ms,
+                    tool_calls: Vec::new(),
                 });
                 if stop_on_error {
                     break;
@@ -107,12 +110,14 @@ pub(crate) async fn cmd_batch(
     Ok(())
 }
 
+/// Streams a single prompt, keeping the tool calls made before the final
+/// response.
 async fn process_streaming(
     agent: &Arc<zeptoclaw::agent::AgentLoop>,
     inbound: &InboundMessage,
-) -> Result<String> {
+) -> Result<TurnResult> {
     let mut response = String::new();
-    let mut rx = agent.process_message_streaming(inbound).await?;
+    let (tool_calls, mut rx) = agent.process_message_streaming_traced(inbound).await?;
     while let Some(event) = rx.recv().await {
         match event {
             StreamEvent::Delta(chunk) => response.push_str(&chunk),
@@ -121,5 +126,8 @@ async fn process_streaming(
             StreamEvent::ToolCalls(_) => {}
         }
     }
-    Ok(response)
+    Ok(TurnResult {
+        content: response,
+        tool_calls,
+    })
 }
@@ -11,26 +11,29 @@ use tracing::{info, warn};
 use zeptoclaw::agent::{AgentLoop, ContextBuilder, RuntimeContext};
 use zeptoclaw::auth::{self, AuthMethod};
 use zeptoclaw::bus::MessageBus;
+use zeptoclaw::channels::enabled_channel_names;
 use zeptoclaw::config::templates::{AgentTemplate, TemplateRegistry};
 use zeptoclaw::config::{Config, MemoryBackend, MemoryCitationsMode};
 use zeptoclaw::cron::CronService;
 use zeptoclaw::memory::factory::create_searcher;
 use zeptoclaw::providers::{
     provider_config_by_name, resolve_runtime_providers, ClaudeProvider, FallbackProvider,
-    LLMProvider, OpenAIProvider, RetryProvider, RuntimeProviderSelection,
+    GeminiProvider, HttpRetryPolicy, LLMProvider, OllamaProvider, OpenAIProvider, RetryProvider,
+    RuntimeProviderSelection,
 };
 use zeptoclaw::runtime::{create_runtime, NativeRuntime};
 use zeptoclaw::session::SessionManager;
-use zeptoclaw::skills::SkillsLoader;
+use zeptoclaw::skills::{SkillRegistry, SkillsLoader};
 use zeptoclaw::tools::cron::CronTool;
 use zeptoclaw::tools::delegate::DelegateTool;
 use zeptoclaw::tools::filesystem::{EditFileTool, ListDirTool, ReadFileTool, WriteFileTool};
 use zeptoclaw::tools::shell::ShellTool;
 use zeptoclaw::tools::spawn::SpawnTool;
 use zeptoclaw::tools::{
-    EchoTool, GoogleSheetsTool, MemoryGetTool, MemorySearchTool, MessageTool, R8rTool,
-    WebFetchTool, WebSearchTool, WhatsAppTool,
+    EchoTool, GoogleSheetsTool, GrepTool, HttpTool, ListToolsTool, MemoryGetTool, MemorySearchTool,
+    MemoryWriteTool, MessageTool, R8rTool, TreeTool, WebFetchTool, WebSearchTool, WhatsAppTool,
 };
+use zeptoclaw::utils::url_policy::UrlPolicy;
 
 /// Read a line from stdin, trimming whitespace.
 pub(crate) fn read_line() -> Result<String> {
@@ -92,8 +95,8 @@ pub(crate) fn skills_loader_from_config(config: &Config) -> SkillsLoader {
     SkillsLoader::new(workspace_dir, None)
 }
 
-pub(crate) fn load_template_registry() -> Result<TemplateRegistry> {
-    let mut registry = TemplateRegistry::new();
+pub(crate) fn load_template_registry(config: &Config) -> Result<TemplateRegistry> {
+    let mut registry = TemplateRegistry::with_disabled(&config.disabled_templates);
     let template_dir = Config::dir().join("templates");
     registry
         .merge_from_dir(&template_dir)
@@ -101,8 +104,8 @@ pub(crate) fn load_template_registry() -> Result<TemplateRegistry> {
     Ok(registry)
 }
 
-pub(crate) fn resolve_template(name: &str) -> Result<AgentTemplate> {
-    let registry = load_template_registry()?;
+pub(crate) fn resolve_template(config: &Config, name: &str) -> Result<AgentTemplate> {
+    let registry = load_template_registry(config)?;
     if let Some(template) = registry.get(name) {
         return Ok(template.clone());
     }
@@ -121,28 +124,72 @@ pub(crate) fn resolve_template(name: &str) -> Result<AgentTemplate> {
     );
 }
 
+/// In-client HTTP retry policy for a provider.
+///
+/// Only one layer retries: when the `providers.retry` wrapper is on it owns
+/// retries, so in-client retries are turned off rather than multiplying
+/// attempts underneath it.
+fn http_retry_policy(selection: &RuntimeProviderSelection, config: &Config) -> HttpRetryPolicy {
+    if config.providers.retry.enabled {
+        return HttpRetryPolicy::disabled();
+    }
+    HttpRetryPolicy::from_config(&selection.http_retry)
+}
+
 fn provider_from_runtime_selection(
     selection: &RuntimeProviderSelection,
+    retry: HttpRetryPolicy,
 ) -> Option<Box<dyn LLMProvider>> {
+    let openai = |base_url: &str| -> Box<dyn LLMProvider> {
+        Box::new(
+            OpenAIProvider::with_base_url(&selection.api_key, base_url)
+                .with_http_retry(retry.clone()),
+        )
+    };
     match selection.backend {
         "anthropic" => {
             // Use credential-aware constructor when OAuth token is available
-            if selection.credential.is_bearer() {
-                Some(Box::new(ClaudeProvider::with_credential(
-                    selection.credential.clone(),
-                )))
+            let provider = if selection.credential.is_bearer() {
+                ClaudeProvider::with_credential(selection.credential.clone())
             } else {
-                Some(Box::new(ClaudeProvider::new(&selection.api_key)))
-            }
-        }
-        "openai" => {
-            let provider = if let Some(base_url) = selection.api_base.as_deref() {
-                OpenAIProvider::with_base_url(&selection.api_key, base_url)
-            } else {
-                OpenAIProvider::new(&selection.api_key)
+                ClaudeProvider::new(&selection.api_key)
             };
-            Some(Box::new(provider))
+            Some(Box::new(provider.with_http_retry(retry)))
         }
+        "openai" => match selection.api_base.as_deref() {
+            Some(base_url) => Some(openai(base_url)),
+            None => Some(Box::new(
+                OpenAIProvider::new(&selection.api_key).with_http_retry(retry),
+            )),
+        },
+        "gemini" => match selection.api_base.as_deref() {
+            // Configs pointing at the OpenAI-compatible endpoint keep using it.
+            Some(base_url) if base_url.trim_end_matches('/').ends_with("/openai") => {
+                Some(openai(base_url))
+            }
+            Some(base_url) => Some(Box::new(
+                GeminiProvider::with_base_url(&selection.api_key, base_url).with_http_retry(retry),
+            )),
+            None => Some(Box::new(
+                GeminiProvider::new(&selection.api_key).with_http_retry(retry),
+            )),
+        },
+        "ollama" => match selection.api_base.as_deref() {
+            // Configs pointing at Ollama's OpenAI-compatible `/v1` API keep using it.
+            Some(base_url) if base_url.trim_end_matches('/').ends_with("/v1") => {
+                Some(openai(base_url))
+            }
+            Some(base_url) => Some(Box::new(
+                OllamaProvider::with_base_url(base_url)
+                    .with_api_key(&selection.api_key)
+                    .with_http_retry(retry),
+            )),
+            None => Some(Box::new(
+                OllamaProvider::new()
+                    .with_api_key(&selection.api_key)
+                    .with_http_retry(retry),
+            )),
+        },
         _ => None,
     }
 }
@@ -201,7 +248,8 @@ fn build_runtime_provider_chain(
     let mut candidates: Vec<RuntimeProviderCandidate> = Vec::new();
 
     for selection in resolve_runtime_providers(config) {
-        if let Some(provider) = provider_from_runtime_selection(&selection) {
+        let retry = http_retry_policy(&selection, config);
+        if let Some(provider) = provider_from_runtime_selection(&selection, retry) {
             candidates.push(RuntimeProviderCandidate {
                 name: selection.name,
                 provider,
@@ -302,6 +350,27 @@ async fn refresh_oauth_credentials_if_needed(config: &Config) {
     }
 }
 
+/// Keyword-triggered skills from the workspace and builtin skill directories,
+/// minus disabled ones. `None` when skills are off or none were found.
+fn build_skill_registry(config: &Config) -> Option<SkillRegistry> {
+    if !config.skills.enabled {
+        return None;
+    }
+    let loader = skills_loader_from_config(config);
+    let dirs = [
+        loader.workspace_dir().to_path_buf(),
+        loader.builtin_dir().to_path_buf(),
+    ];
+    let registry = match SkillRegistry::discover(&dirs) {
+        Ok(registry) => registry.without(&config.skills.disabled),
+        Err(e) => {
+            warn!("Failed to discover skills: {}", e);
+            return None;
+        }
+    };
+    (!registry.is_empty()).then_some(registry)
+}
+
 fn build_skills_prompt(config: &Config) -> String {
     if !config.skills.enabled {
         return String::new();
@@ -375,6 +444,34 @@ fn escape_xml(input: &str) -> String {
         .replace('>', "&gt;")
 }
 
+/// Lowercased tool names allowed by the configured tool profile, or `None`
+/// when every tool is allowed.
+pub(crate) fn profile_tool_names(config: &Config) -> Option<HashSet<String>> {
+    let profile_name = config.agents.defaults.tool_profile.as_ref()?;
+    match config.tool_profiles.get(profile_name) {
+        Some(tools) => tools
+            .as_ref()
+            .map(|names| names.iter().map(|n| n.to_ascii_lowercase()).collect()),
+        None => {
+            warn!(
+                "Tool profile '{}' not found in tool_profiles config — all tools enabled",
+                profile_name
+            );
+            None
+        }
+    }
+}
+
+/// Plugin directories from config, with `~` expanded.
+pub(crate) fn plugin_dirs(config: &Config) -> Vec<PathBuf> {
+    config
+        .plugins
+        .plugin_dirs
+        .iter()
+        .map(|d| expand_tilde(d))
+        .collect()
+}
+
 /// Create and configure an agent with all tools registered.
 pub(crate) async fn create_agent(config: Config, bus: Arc<MessageBus>) -> Result<Arc<AgentLoop>> {
     create_agent_with_template(config, bus, None).await
@@ -387,58 +484,11 @@ pub(crate) async fn create_agent_with_template(
     template: Option<AgentTemplate>,
 ) -> Result<Arc<AgentLoop>> {
     if let Some(tpl) = &template {
-        if let Some(model) = &tpl.model {
-            config.agents.defaults.model = model.clone();
-        }
-        if let Some(max_tokens) = tpl.max_tokens {
-            config.agents.defaults.max_tokens = max_tokens;
-        }
-        if let Some(temperature) = tpl.temperature {
-            config.agents.defaults.temperature = temperature;
-        }
-        if let Some(max_tool_iterations) = tpl.max_tool_iterations {
-            config.agents.defaults.max_tool_iterations = max_tool_iterations;
-        }
+        tpl.apply_to(&mut config.agents.defaults);
     }
 
-    let allowed_tools = template
-        .as_ref()
-        .and_then(|tpl| tpl.allowed_tools.as_ref())
-        .map(|names| {
-            names
-                .iter()
-                .map(|name| name.to_ascii_lowercase())
-                .collect::<HashSet<_>>()
-        });
-    let blocked_tools = template
-        .as_ref()
-        .and_then(|tpl| tpl.blocked_tools.as_ref())
-        .map(|names| {
-            names
-                .iter()
-                .map(|name| name.to_ascii_lowercase())
-                .collect::<HashSet<_>>()
-        })
-        .unwrap_or_default();
-
     // Resolve tool profile: config default > template override > all tools
-    let profile_tools: Option<HashSet<String>> =
-        if let Some(ref profile_name) = config.agents.defaults.tool_profile {
-            match config.tool_profiles.get(profile_name) {
-                Some(tools) => tools
-                    .as_ref()
-                    .map(|names| names.iter().map(|n| n.to_ascii_lowercase()).collect()),
-                None => {
-                    warn!(
-                        "Tool profile '{}' not found in tool_profiles config — all tools enabled",
-                        profile_name
-                    );
-                    None
-                }
-            }
-        } else {
-            None
-        };
+    let profile_tools = profile_tool_names(&config);
 
     let tool_enabled = |name: &str| {
         let key = name.to_ascii_lowercase();
@@ -448,17 +498,12 @@ pub(crate) async fn create_agent_with_template(
                 return false;
             }
         }
-        // Template allowed filter
-        if let Some(allowed) = &allowed_tools {
-            if !allowed.contains(&key) {
-                return false;
-            }
-        }
-        !blocked_tools.contains(&key)
+        // Template allowed/blocked filter
+        template.as_ref().is_none_or(|tpl| tpl.allows_tool(&key))
     };
 
     // Create session manager
-    let session_manager = SessionManager::new().unwrap_or_else(|_| {
+    let session_manager = SessionManager::from_config(&config).unwrap_or_else(|_| {
         warn!("Failed to create persistent session manager, using in-memory");
         SessionManager::new_memory()
     });
@@ -482,11 +527,16 @@ pub(crate) async fn create_agent_with_template(
     }
 
     if let Some(tpl) = &template {
-        context_builder = context_builder.with_system_prompt(&tpl.system_prompt);
+        context_builder = context_builder
+            .with_system_prompt(&tpl.system_prompt)
+            .with_prompt_vars(Some(&config.workspace_path()), tpl.vars.clone());
     }
     if !skills_prompt.is_empty() {
         context_builder = context_builder.with_skills(&skills_prompt);
     }
+    if let Some(registry) = build_skill_registry(&config) {
+        context_builder = context_builder.with_skill_registry(Arc::new(registry));
+    }
 
     // Create memory searcher from config (reused for injection + tool registration)
     let memory_searcher = create_searcher(&config.memory);
@@ -550,7 +600,7 @@ pub(crate) async fn create_agent_with_template(
                     "Failed to create configured runtime: {}. Falling back to native.",
                     e
                 );
-                Arc::new(NativeRuntime::new())
+                Arc::new(NativeRuntime::new().with_limits(config.runtime.native.limits))
             } else {
                 return Err(anyhow::anyhow!(
                     "Configured runtime '{:?}' unavailable: {}. \
@@ -578,6 +628,12 @@ Enable runtime.allow_fallback_to_native to opt in to native fallback.",
     if tool_enabled("edit_file") {
         agent.register_tool(Box::new(EditFileTool)).await;
     }
+    if tool_enabled("grep") {
+        agent.register_tool(Box::new(GrepTool)).await;
+    }
+    if tool_enabled("tree") {
+        agent.register_tool(Box::new(TreeTool)).await;
+    }
     if tool_enabled("shell") {
         agent
             .register_tool(Box::new(ShellTool::with_runtime(runtime)))
@@ -599,15 +655,32 @@ Enable runtime.allow_fallback_to_native to opt in to native fallback.",
             }
         }
     }
+    let url_policy = UrlPolicy::from_config(&config.tools.url_policy);
     if tool_enabled("web_fetch") {
-        agent.register_tool(Box::new(WebFetchTool::new())).await;
+        agent
+            .register_tool(Box::new(
+                WebFetchTool::new()
+                    .with_render(&config.tools.web.fetch)
+                    .with_url_policy(url_policy.clone()),
+            ))
+            .await;
         info!("Registered web_fetch tool");
     }
+    if tool_enabled("http_request") {
+        agent
+            .register_tool(Box::new(
+                HttpTool::new(&config.tools.http).with_url_policy(url_policy.clone()),
+            ))
+            .await;
+        info!("Registered http_request tool");
+    }
 
     // Register proactive messaging tool.
     if tool_enabled("message") {
         agent
-            .register_tool(Box::new(MessageTool::new(agent.bus().clone())))
+            .register_tool(Box::new(
+                MessageTool::new(agent.bus().clone()).with_channels(enabled_channel_names(&config)),
+            ))
             .await;
         info!("Registered message tool");
     }
@@ -667,6 +740,11 @@ Enable runtime.allow_fallback_to_native to opt in to native fallback.",
                 .register_tool(Box::new(MemoryGetTool::new(config.memory.clone())))
                 .await;
         }
+        if tool_enabled("memory_write") {
+            agent
+                .register_tool(Box::new(MemoryWriteTool::new(config.memory.clone())))
+                .await;
+        }
         if tool_enabled("longterm_memory") {
             let ltm_path = zeptoclaw::config::Config::dir()
                 .join("memory")
@@ -712,6 +790,13 @@ Enable runtime.allow_fallback_to_native to opt in to native fallback.",
     if tool_enabled("r8r") {
         agent.register_tool(Box::new(R8rTool::default())).await;
     }
+    if tool_enabled("list_tools") {
+        agent
+            .register_tool(Box::new(
+                ListToolsTool::new(Arc::downgrade(&agent)).with_template(template.clone()),
+            ))
+            .await;
+    }
     if tool_enabled("reminder") {
         match zeptoclaw::tools::reminder::ReminderTool::new(Some(cron_service.clone())) {
             Ok(tool) => {
@@ -722,72 +807,16 @@ Enable runtime.allow_fallback_to_native to opt in to native fallback.",
         }
     }
 
-    // Register plugin tools (command-mode and binary-mode)
+    // Register plugin tools (command-mode, binary-mode and wasm-mode)
     if config.plugins.enabled {
-        let plugin_dirs: Vec<PathBuf> = config
-            .plugins
-            .plugin_dirs
-            .iter()
-            .map(|d| expand_tilde(d))
-            .collect();
-        match zeptoclaw::plugins::discover_plugins(&plugin_dirs) {
+        match zeptoclaw::plugins::discover_plugins(&plugin_dirs(&config)) {
             Ok(plugins) => {
                 for plugin in plugins {
                     if !config.plugins.is_plugin_permitted(plugin.name()) {
                         info!(plugin = %plugin.name(), "Plugin blocked by config");
                         continue;
                     }
-                    for tool_def in &plugin.manifest.tools {
-                        if !tool_enabled(&tool_def.name) {
-                            continue;
-                        }
-                        if plugin.manifest.is_binary() {
-                            if let Some(ref bin_cfg) = plugin.manifest.binary {
-                                match zeptoclaw::plugins::validate_binary_path(
-                                    &plugin.path,
-                                    bin_cfg,
-                                ) {
-                                    Ok(bin_path) => {
-                                        let timeout = bin_cfg
-                                            .timeout_secs
-                                            .unwrap_or_else(|| tool_def.effective_timeout());
-                                        agent
-                                            .register_tool(Box::new(
-                                                zeptoclaw::tools::binary_plugin::BinaryPluginTool::new(
-                                                    tool_def.clone(),
-                                                    plugin.name(),
-                                                    bin_path,
-                                                    timeout,
-                                                ),
-                                            ))
-                                            .await;
-                                        info!(
-                                            plugin = %plugin.name(),
-                                            tool = %tool_def.name,
-                                            "Registered binary plugin tool"
-                                        );
-                                    }
-                                    Err(e) => warn!(
-                                        plugin = %plugin.name(),
-                                        error = %e,
-                                        "Binary validation failed"
-                                    ),
-                                }
-                            }
-                        } else {
-                            agent
-                                .register_tool(Box::new(zeptoclaw::tools::plugin::PluginTool::new(
-                                    tool_def.clone(),
-                                    plugin.name(),
-                                )))
-                                .await;
-                            info!(
-                                plugin = %plugin.name(),
-                                tool = %tool_def.name,
-                                "Registered command plugin tool"
-                            );
-                        }
-                    }
+                    register_plugin_tools(&agent, &plugin, &tool_enabled).await;
                 }
             }
             Err(e) => warn!(error = %e, "Plugin discovery failed"),
@@ -877,6 +906,195 @@ Enable runtime.allow_fallback_to_native to opt in to native fallback.",
     Ok(agent)
 }
 
+/// Register the enabled tools of one plugin on the agent.
+pub(crate) async fn register_plugin_tools(
+    agent: &AgentLoop,
+    plugin: &zeptoclaw::plugins::Plugin,
+    tool_enabled: &impl Fn(&str) -> bool,
+) {
+    let grpc_config = plugin
+        .manifest
+        .binary
+        .as_ref()
+        .filter(|bin_cfg| plugin.manifest.is_binary() && bin_cfg.is_grpc());
+    if let Some(bin_cfg) = grpc_config {
+        register_grpc_plugin_tools(agent, plugin, bin_cfg, tool_enabled).await;
+        return;
+    }
+    for tool_def in &plugin.manifest.tools {
+        if !tool_enabled(&tool_def.name) {
+            continue;
+        }
+        if plugin.manifest.is_binary() {
+            if let Some(ref bin_cfg) = plugin.manifest.binary {
+                match zeptoclaw::plugins::validate_binary_path(&plugin.path, bin_cfg) {
+                    Ok(bin_path) => {
+                        let timeout = bin_cfg
+                            .timeout_secs
+                            .unwrap_or_else(|| tool_def.effective_timeout());
+                        agent
+                            .register_tool(Box::new(
+                                zeptoclaw::tools::binary_plugin::BinaryPluginTool::new(
+                                    tool_def.clone(),
+                                    plugin.name(),
+                                    bin_path,
+                                    timeout,
+                                ),
+                            ))
+                            .await;
+                        info!(
+                            plugin = %plugin.name(),
+                            tool = %tool_def.name,
+                            "Registered binary plugin tool"
+                        );
+                    }
+                    Err(e) => warn!(
+                        plugin = %plugin.name(),
+                        error = %e,
+                        "Binary validation failed"
+                    ),
+                }
+            }
+        } else if plugin.manifest.is_wasm() {
+            if let Some(ref wasm_cfg) = plugin.manifest.wasm {
+                register_wasm_plugin_tool(agent, plugin, wasm_cfg, tool_def).await;
+            }
+        } else {
+            agent
+                .register_tool(Box::new(
+                    zeptoclaw::tools::plugin::PluginTool::new(tool_def.clone(), plugin.name())
+                        .with_allowed_env(plugin.manifest.allowed_env.clone()),
+                ))
+                .await;
+            info!(
+                plugin = %plugin.name(),
+                tool = %tool_def.name,
+                "Registered command plugin tool"
+            );
+        }
+    }
+}
+
+/// Register the tools of a gRPC binary plugin, sharing one plugin process.
+#[cfg(feature = "grpc-plugins")]
+async fn register_grpc_plugin_tools(
+    agent: &AgentLoop,
+    plugin: &zeptoclaw::plugins::Plugin,
+    bin_cfg: &zeptoclaw::plugins::BinaryPluginConfig,
+    tool_enabled: &impl Fn(&str) -> bool,
+) {
+    use zeptoclaw::tools::grpc_plugin::{GrpcPluginProcess, GrpcPluginTool};
+
+    let paths =
+        zeptoclaw::plugins::validate_binary_path(&plugin.path, bin_cfg).and_then(|bin_path| {
+            zeptoclaw::plugins::grpc_socket_path(&plugin.path, bin_cfg)
+                .map(|socket_path| (bin_path, socket_path))
+        });
+    let (bin_path, socket_path) = match paths {
+        Ok(paths) => paths,
+        Err(e) => {
+            warn!(plugin = %plugin.name(), error = %e, "Binary validation failed");
+            return;
+        }
+    };
+
+    // Tool-level env vars all apply to the one shared process.
+    let env = plugin
+        .manifest
+        .tools
+        .iter()
+        .filter_map(|tool_def| tool_def.env.clone())
+        .flatten()
+        .collect();
+    let process =
+        Arc::new(GrpcPluginProcess::new(plugin.name(), bin_path, socket_path).with_env(env));
+
+    for tool_def in &plugin.manifest.tools {
+        if !tool_enabled(&tool_def.name) {
+            continue;
+        }
+        let timeout = bin_cfg
+            .timeout_secs
+            .unwrap_or_else(|| tool_def.effective_timeout());
+        agent
+            .register_tool(Box::new(GrpcPluginTool::new(
+                tool_def.clone(),
+                plugin.name(),
+                process.clone(),
+                timeout,
+            )))
+            .await;
+        info!(
+            plugin = %plugin.name(),
+            tool = %tool_def.name,
+            "Registered gRPC plugin tool"
+        );
+    }
+}
+
+/// gRPC plugins need the `grpc-plugins` feature; skip their tools.
+#[cfg(not(feature = "grpc-plugins"))]
+async fn register_grpc_plugin_tools(
+    _agent: &AgentLoop,
+    plugin: &zeptoclaw::plugins::Plugin,
+    _bin_cfg: &zeptoclaw::plugins::BinaryPluginConfig,
+    _tool_enabled: &impl Fn(&str) -> bool,
+) {
+    warn!(
+        plugin = %plugin.name(),
+        "gRPC plugin support is not compiled in (build with --features grpc-plugins); skipping plugin"
+    );
+}
+
+/// Register one tool of a WASM plugin.
+#[cfg(feature = "wasm-plugins")]
+async fn register_wasm_plugin_tool(
+    agent: &AgentLoop,
+    plugin: &zeptoclaw::plugins::Plugin,
+    wasm_cfg: &zeptoclaw::plugins::WasmPluginConfig,
+    tool_def: &zeptoclaw::plugins::PluginToolDef,
+) {
+    use zeptoclaw::tools::wasm_plugin::WasmPluginTool;
+
+    let timeout = wasm_cfg
+        .timeout_secs
+        .unwrap_or_else(|| tool_def.effective_timeout());
+    let tool =
+        zeptoclaw::plugins::validate_wasm_path(&plugin.path, wasm_cfg).and_then(|module_path| {
+            WasmPluginTool::new(tool_def.clone(), plugin.name(), module_path, timeout)
+        });
+    match tool {
+        Ok(tool) => {
+            agent.register_tool(Box::new(tool)).await;
+            info!(
+                plugin = %plugin.name(),
+                tool = %tool_def.name,
+                "Registered WASM plugin tool"
+            );
+        }
+        Err(e) => warn!(
+            plugin = %plugin.name(),
+            error = %e,
+            "WASM module validation failed"
+        ),
+    }
+}
+
+/// WASM plugins need the `wasm-plugins` feature; skip their tools.
+#[cfg(not(feature = "wasm-plugins"))]
+async fn register_wasm_plugin_tool(
+    _agent: &AgentLoop,
+    plugin: &zeptoclaw::plugins::Plugin,
+    _wasm_cfg: &zeptoclaw::plugins::WasmPluginConfig,
+    tool_def: &zeptoclaw::plugins::PluginToolDef,
+) {
+    warn!(
+        plugin = %plugin.name(),
+        tool = %tool_def.name,
+        "WASM plugin support is not compiled in (build with --features wasm-plugins); skipping tool"
+    );
+}
+
 /// Validate an API key by making a minimal API call.
 /// Returns Ok(()) if key works, Err with user-friendly message if not.
 pub(crate) async fn validate_api_key(
@@ -1116,6 +1334,45 @@ mod tests {
         assert_eq!(provider.name(), "openai");
     }
 
+    #[test]
+    fn test_build_runtime_provider_chain_gemini_native_and_compat() {
+        let mut config = Config::default();
+        config.providers.gemini = Some(zeptoclaw::config::ProviderConfig {
+            api_key: Some("AIza-test".to_string()),
+            ..Default::default()
+        });
+        let (provider, _) =
+            build_runtime_provider_chain(&config).expect("provider chain should resolve");
+        assert_eq!(provider.name(), "gemini");
+
+        config.providers.gemini = Some(zeptoclaw::config::ProviderConfig {
+            api_key: Some("AIza-test".to_string()),
+            api_base: Some("https://generativelanguage.googleapis.com/v1beta/openai/".to_string()),
+            ..Default::default()
+        });
+        let (provider, _) =
+            build_runtime_provider_chain(&config).expect("provider chain should resolve");
+        assert_eq!(provider.name(), "openai");
+    }
+
+    #[test]
+    fn test_build_runtime_provider_chain_ollama_native_and_compat() {
+        let mut config = Config::default();
+        config.providers.ollama = Some(zeptoclaw::config::ProviderConfig::default());
+        let (provider, names) =
+            build_runtime_provider_chain(&config).expect("keyless ollama should resolve");
+        assert_eq!(names, vec!["ollama"]);
+        assert_eq!(provider.name(), "ollama");
+
+        config.providers.ollama = Some(zeptoclaw::config::ProviderConfig {
+            api_base: Some("http://localhost:11434/v1".to_string()),
+            ..Default::default()
+        });
+        let (provider, _) =
+            build_runtime_provider_chain(&config).expect("provider chain should resolve");
+        assert_eq!(provider.name(), "openai");
+    }
+
     #[test]
     fn test_build_runtime_provider_chain_preserves_registry_order() {
         let mut config = Config::default();
@@ -1243,4 +1500,25 @@ mod tests {
         assert!(err.to_string().contains("rate limit"));
         assert_eq!(calls.load(Ordering::SeqCst), 1);
     }
+
+    #[test]
+    fn test_http_retry_disabled_when_retry_wrapper_enabled() {
+        let mut config = Config::default();
+        config.providers.anthropic = Some(zeptoclaw::config::ProviderConfig {
+            api_key: Some("sk-ant-test".to_string()),
+            ..Default::default()
+        });
+        let selection = resolve_runtime_providers(&config).remove(0);
+
+        assert_eq!(
+            http_retry_policy(&selection, &config),
+            HttpRetryPolicy::from_config(&selection.http_retry)
+        );
+
+        config.providers.retry.enabled = true;
+        assert_eq!(
+            http_retry_policy(&selection, &config),
+            HttpRetryPolicy::disabled()
+        );
+    }
 }
@@ -0,0 +1,83 @@
+//! Shell completion script generation.
+//!
+//! Scripts are generated by `clap_complete` from the clap command tree, so
+//! new subcommands and flags are picked up automatically.
+
+use std::io::Write;
+
+use anyhow::Result;
+use clap::{Command, CommandFactory};
+use clap_complete::Shell;
+
+use super::{Cli, CompletionShell};
+
+/// Print a completion script for `shell` to stdout.
+pub(crate) fn cmd_completions(shell: CompletionShell) -> Result<()> {
+    let mut stdout = std::io::stdout();
+    generate(shell, &mut Cli::command(), &mut stdout);
+    stdout.flush()?;
+    Ok(())
+}
+
+/// Write a completion script for `shell` and `cmd` to `out`.
+pub(crate) fn generate(shell: CompletionShell, cmd: &mut Command, out: &mut dyn Write) {
+    let shell = match shell {
+        CompletionShell::Bash => Shell::Bash,
+        CompletionShell::Zsh => Shell::Zsh,
+        CompletionShell::Fish => Shell::Fish,
+        CompletionShell::Powershell => Shell::PowerShell,
+    };
+    let bin = cmd.get_name().to_string();
+    clap_complete::generate(shell, cmd, bin, out);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    use crate::cli::Commands;
+
+    fn script(shell: CompletionShell) -> String {
+        let mut out = Vec::new();
+        generate(shell, &mut Cli::command(), &mut out);
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_bash_completions_name_the_binary() {
+        let script = script(CompletionShell::Bash);
+        assert!(!script.is_empty());
+        assert!(script.contains("zeptoclaw"));
+    }
+
+    #[test]
+    fn test_every_shell_generates_a_script() {
+        for shell in [
+            CompletionShell::Bash,
+            CompletionShell::Zsh,
+            CompletionShell::Fish,
+            CompletionShell::Powershell,
+        ] {
+            let script = script(shell);
+            assert!(script.contains("zeptoclaw"), "{:?}", shell);
+            assert!(script.contains("gateway"), "{:?}", shell);
+        }
+    }
+
+    #[test]
+    fn test_invalid_shell_is_rejected() {
+        let err = Cli::try_parse_from(["zeptoclaw", "completions", "tcsh"])
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), clap::error::ErrorKind::InvalidValue);
+        assert!(matches!(
+            Cli::try_parse_from(["zeptoclaw", "completions", "fish"])
+                .unwrap()
+                .command,
+            Some(Commands::Completions {
+                shell: CompletionShell::Fish
+            })
+        ));
+    }
+}
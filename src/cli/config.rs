@@ -1,66 +1,133 @@
 //! Config check command handler.
 
+use std::path::Path;
+
 use anyhow::{Context, Result};
 
+use zeptoclaw::config::validate::{Diagnostic, DiagnosticLevel};
 use zeptoclaw::config::Config;
 
 use super::ConfigAction;
 
 /// Validate configuration file.
 pub(crate) async fn cmd_config(action: ConfigAction) -> Result<()> {
-    match action {
-        ConfigAction::Check => {
-            let config_path = Config::path();
-            println!("Config file: {}", config_path.display());
+    let config_path = Config::path();
+    println!("Config file: {}", config_path.display());
 
-            if !config_path.exists() {
-                println!("[OK] No config file found (using defaults)");
-                return Ok(());
-            }
+    if !config_path.exists() {
+        println!("[OK] No config file found (using defaults)");
+        return Ok(());
+    }
 
-            let content =
-                std::fs::read_to_string(&config_path).context("Failed to read config file")?;
+    let diagnostics = config_diagnostics(action, &config_path)?;
 
-            let raw: serde_json::Value = match serde_json::from_str(&content) {
-                Ok(v) => v,
-                Err(e) => {
-                    println!("[ERROR] Invalid JSON: {}", e);
-                    anyhow::bail!("Configuration file is not valid JSON");
-                }
-            };
+    for diag in &diagnostics {
+        println!("{}", diag);
+    }
 
-            let diagnostics = zeptoclaw::config::validate::validate_config(&raw);
-            for diag in &diagnostics {
-                println!("{}", diag);
-            }
+    let count = |level: DiagnosticLevel| diagnostics.iter().filter(|d| d.level == level).count();
+    let errors = count(DiagnosticLevel::Error);
+    let warnings = count(DiagnosticLevel::Warn);
 
-            let errors = diagnostics
-                .iter()
-                .filter(|d| d.level == zeptoclaw::config::validate::DiagnosticLevel::Error)
-                .count();
-            let mut warnings = diagnostics
-                .iter()
-                .filter(|d| d.level == zeptoclaw::config::validate::DiagnosticLevel::Warn)
-                .count();
+    if errors == 0 && warnings == 0 {
+        println!("\nConfiguration looks good!");
+    } else {
+        println!("\nFound {} error(s), {} warning(s)", errors, warnings);
+    }
 
-            // Validate custom tool definitions
-            let config = Config::load().unwrap_or_default();
-            let tool_warnings = zeptoclaw::config::validate::validate_custom_tools(&config);
-            for w in &tool_warnings {
-                println!("[WARN] {}", w);
-            }
-            warnings += tool_warnings.len();
+    if errors > 0 {
+        anyhow::bail!("Configuration validation failed with {} error(s)", errors);
+    }
+    Ok(())
+}
 
-            if errors == 0 && warnings == 0 {
-                println!("\nConfiguration looks good!");
-            } else {
-                println!("\nFound {} error(s), {} warning(s)", errors, warnings);
-            }
+/// Diagnostics for the config file at `config_path`.
+///
+/// Semantic checks run on the effective config, as `Config::load` builds it:
+/// stored API keys and `ZEPTOCLAW_*` environment overrides included.
+fn config_diagnostics(action: ConfigAction, config_path: &Path) -> Result<Vec<Diagnostic>> {
+    let content = std::fs::read_to_string(config_path).context("Failed to read config file")?;
+
+    let raw: serde_json::Value = match serde_json::from_str(&content) {
+        Ok(v) => v,
+        Err(e) => {
+            println!("[ERROR] Invalid JSON: {}", e);
+            anyhow::bail!("Configuration file is not valid JSON");
+        }
+    };
+
+    let mut diagnostics = zeptoclaw::config::validate::validate_config(&raw);
 
-            if errors > 0 {
-                anyhow::bail!("Configuration validation failed with {} error(s)", errors);
+    match action {
+        ConfigAction::Check => {
+            // Validate custom tool definitions
+            let config = Config::load_from_path(&config_path.to_path_buf()).unwrap_or_default();
+            diagnostics.extend(custom_tool_warnings(&config));
+        }
+        ConfigAction::Validate => {
+            let loaded = serde_json::from_str::<Config>(&content)
+                .map_err(|e| e.to_string())
+                .and_then(|_| {
+                    Config::load_from_path(&config_path.to_path_buf()).map_err(|e| e.to_string())
+                });
+            match loaded {
+                Ok(config) => {
+                    let config_dir = config_path.parent().unwrap_or(config_path);
+                    diagnostics.extend(zeptoclaw::config::validate::validate_semantics(
+                        &config, config_dir,
+                    ));
+                    diagnostics.extend(custom_tool_warnings(&config));
+                }
+                Err(e) => diagnostics.push(Diagnostic {
+                    level: DiagnosticLevel::Error,
+                    path: String::new(),
+                    message: format!("Invalid config: {}", e),
+                }),
             }
         }
     }
-    Ok(())
+    Ok(diagnostics)
+}
+
+/// Custom tool definition problems, as warnings.
+fn custom_tool_warnings(config: &Config) -> Vec<Diagnostic> {
+    zeptoclaw::config::validate::validate_custom_tools(config)
+        .into_iter()
+        .map(|message| Diagnostic {
+            level: DiagnosticLevel::Warn,
+            path: String::new(),
+            message,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_sees_env_supplied_api_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        std::fs::write(
+            &path,
+            r#"{"providers": {"rotation": {"order": ["nvidia"]}}}"#,
+        )
+        .unwrap();
+
+        let not_configured = |diagnostics: &[Diagnostic]| {
+            diagnostics
+                .iter()
+                .any(|d| d.path == "providers.rotation.order[0]")
+        };
+
+        std::env::remove_var("ZEPTOCLAW_PROVIDERS_NVIDIA_API_KEY");
+        let diagnostics = config_diagnostics(ConfigAction::Validate, &path).unwrap();
+        assert!(not_configured(&diagnostics), "{:?}", diagnostics);
+
+        std::env::set_var("ZEPTOCLAW_PROVIDERS_NVIDIA_API_KEY", "nvapi-env-only");
+        let diagnostics = config_diagnostics(ConfigAction::Validate, &path).unwrap();
+        std::env::remove_var("ZEPTOCLAW_PROVIDERS_NVIDIA_API_KEY");
+        assert!(!not_configured(&diagnostics), "{:?}", diagnostics);
+    }
 }
@@ -6,19 +6,24 @@ use std::time::Duration;
 use anyhow::{Context, Result};
 use tracing::{error, info, warn};
 
+use zeptoclaw::agent::AgentLoop;
 use zeptoclaw::bus::MessageBus;
 use zeptoclaw::channels::{register_configured_channels, ChannelManager, WhatsAppChannel};
 use zeptoclaw::config::{Config, ContainerAgentBackend};
 use zeptoclaw::deps::{fetcher::RealFetcher, DepManager, HasDependencies};
+use zeptoclaw::gateway::{start_metrics_server, GatewayMetrics};
 use zeptoclaw::health::{
     health_port, start_health_server, start_periodic_usage_flush, UsageMetrics,
 };
 use zeptoclaw::heartbeat::{ensure_heartbeat_file, HeartbeatService};
+use zeptoclaw::plugins::{PluginRegistry, PluginReloadSummary};
 use zeptoclaw::providers::{
     configured_provider_names, resolve_runtime_provider, RUNTIME_SUPPORTED_PROVIDERS,
 };
+use zeptoclaw::session::SessionManager;
+use zeptoclaw::tools::approval::AsyncApprovalBroker;
 
-use super::common::create_agent;
+use super::common::{create_agent, plugin_dirs, profile_tool_names, register_plugin_tools};
 use super::heartbeat::heartbeat_file_path;
 
 /// Start multi-channel gateway.
@@ -31,23 +36,27 @@ pub(crate) async fn cmd_gateway(
     // Load configuration
     let mut config = Config::load().with_context(|| "Failed to load configuration")?;
 
-    // --containerized [docker|apple] overrides config backend
+    // --containerized [docker|podman|apple] overrides config backend
     let containerized = containerized_flag.is_some();
     if let Some(ref b) = containerized_flag {
         if b != "auto" {
             config.container_agent.backend = match b.to_lowercase().as_str() {
                 "docker" => ContainerAgentBackend::Docker,
+                "podman" => ContainerAgentBackend::Podman,
                 #[cfg(target_os = "macos")]
                 "apple" => ContainerAgentBackend::Apple,
                 "auto" => ContainerAgentBackend::Auto,
                 other => {
                     #[cfg(target_os = "macos")]
                     return Err(anyhow::anyhow!(
-                        "Unknown backend '{}'. Use: docker or apple",
+                        "Unknown backend '{}'. Use: docker, podman, or apple",
                         other
                     ));
                     #[cfg(not(target_os = "macos"))]
-                    return Err(anyhow::anyhow!("Unknown backend '{}'. Use: docker", other));
+                    return Err(anyhow::anyhow!(
+                        "Unknown backend '{}'. Use: docker or podman",
+                        other
+                    ));
                 }
             };
         }
@@ -73,7 +82,10 @@ pub(crate) async fn cmd_gateway(
     }
 
     // Create message bus
-    let bus = Arc::new(MessageBus::new());
+    let bus = Arc::new(
+        MessageBus::with_buffer_size(config.gateway.bus_capacity)
+            .with_outbound_dedup_window(Duration::from_secs(config.gateway.outbound_dedup_secs)),
+    );
 
     // Create usage metrics tracker
     let metrics = Arc::new(UsageMetrics::new());
@@ -116,16 +128,26 @@ pub(crate) async fn cmd_gateway(
                 validate_docker_available(configured_docker_binary(&config.container_agent))
                     .await?;
             }
+            zeptoclaw::gateway::ResolvedBackend::Podman => {
+                validate_podman_available().await?;
+            }
             #[cfg(target_os = "macos")]
             zeptoclaw::gateway::ResolvedBackend::Apple => {
                 validate_apple_available().await?;
             }
         }
 
-        // Check image exists (Docker-specific)
+        // Check image exists (Docker and Podman)
         let image = &config.container_agent.image;
-        if backend == zeptoclaw::gateway::ResolvedBackend::Docker {
-            let docker_binary = configured_docker_binary(&config.container_agent);
+        let image_check_binary = match backend {
+            zeptoclaw::gateway::ResolvedBackend::Docker => {
+                Some(configured_docker_binary(&config.container_agent))
+            }
+            zeptoclaw::gateway::ResolvedBackend::Podman => Some("podman"),
+            #[cfg(target_os = "macos")]
+            zeptoclaw::gateway::ResolvedBackend::Apple => None,
+        };
+        if let Some(docker_binary) = image_check_binary {
             let image_check = tokio::process::Command::new(docker_binary)
                 .args(["image", "inspect", image])
                 .stdout(std::process::Stdio::null())
@@ -135,12 +157,12 @@ pub(crate) async fn cmd_gateway(
 
             if !image_check.map(|s| s.success()).unwrap_or(false) {
                 eprintln!(
-                    "Warning: Docker image '{}' not found (checked via '{}').",
+                    "Warning: Container image '{}' not found (checked via '{}').",
                     image, docker_binary
                 );
                 eprintln!("Build it with: {} build -t {} .", docker_binary, image);
                 return Err(anyhow::anyhow!(
-                    "Docker image '{}' not found (checked via '{}')",
+                    "Container image '{}' not found (checked via '{}')",
                     image,
                     docker_binary
                 ));
@@ -193,13 +215,21 @@ pub(crate) async fn cmd_gateway(
     let agent = if !containerized {
         let agent = create_agent(config.clone(), bus.clone()).await?;
         agent.set_usage_metrics(Arc::clone(&metrics)).await;
+        if config.approval.enabled || config.agents.defaults.plan_first {
+            // Ask for tool and plan approvals in the chat that triggered them.
+            let broker = Arc::new(AsyncApprovalBroker::new(bus.clone(), &config.approval));
+            agent.set_approval_broker(broker.clone()).await;
+            if config.agents.defaults.plan_first {
+                agent.set_plan_approval_handler(broker).await;
+            }
+        }
         Some(agent)
     } else {
         None
     };
 
     // Create channel manager
-    let channel_manager = ChannelManager::new(bus.clone(), config.clone());
+    let channel_manager = Arc::new(ChannelManager::new(bus.clone(), config.clone()));
 
     // Install and start channel dependencies (if any)
     let deps_dir = DepManager::default_dir();
@@ -239,12 +269,12 @@ pub(crate) async fn cmd_gateway(
             Err(e) => warn!("Failed to initialize heartbeat file {:?}: {}", hb_path, e),
         }
 
-        let service = Arc::new(HeartbeatService::new(
+        let service = Arc::new(HeartbeatService::from_config(
             hb_path,
-            config.heartbeat.interval_secs,
+            &config.heartbeat,
             bus.clone(),
             "heartbeat:system",
-        ));
+        )?);
         service.start().await?;
         Some(service)
     } else {
@@ -266,9 +296,50 @@ pub(crate) async fn cmd_gateway(
         None
     };
 
+    // Reload plugins on SIGHUP (only for in-process mode)
+    let plugin_reload_handle = agent
+        .as_ref()
+        .filter(|_| config.plugins.enabled)
+        .and_then(|agent| spawn_plugin_reloader(Arc::clone(agent), config.clone()));
+
     // Mark gateway as ready for /readyz
     metrics.set_ready(true);
 
+    // Optional monitoring endpoint (/healthz + /metrics)
+    let metrics_handle = match config.gateway.metrics_addr.as_deref() {
+        Some(addr) => {
+            let mut gateway_metrics = GatewayMetrics::new(bus.clone(), Arc::clone(&metrics))
+                .with_channels(Arc::clone(&channel_manager));
+            if let Some(agent) = &agent {
+                gateway_metrics = gateway_metrics.with_hooks(Arc::clone(agent.hook_engine()));
+                if let Some(provider) = agent.provider().await {
+                    gateway_metrics = gateway_metrics.with_provider(provider);
+                }
+            }
+            match start_metrics_server(addr, Arc::new(gateway_metrics)).await {
+                Ok((bound, handle)) => {
+                    info!(addr = %bound, "Metrics endpoints available at /healthz and /metrics");
+                    Some(handle)
+                }
+                Err(e) => {
+                    warn!(addr = addr, error = %e, "Failed to start metrics server");
+                    None
+                }
+            }
+        }
+        None => None,
+    };
+
+    // Expire idle sessions when session.ttl_secs is set
+    let session_manager = match (&agent, &proxy) {
+        (Some(agent), _) => Some(agent.session_manager().as_ref().clone()),
+        (None, Some(proxy)) => proxy.session_manager().cloned(),
+        (None, None) => None,
+    };
+    let prune_handle = session_manager
+        .filter(|manager| manager.ttl().is_some())
+        .map(spawn_session_pruner);
+
     println!();
     if containerized {
         println!("Gateway is running (containerized mode). Press Ctrl+C to stop.");
@@ -304,29 +375,154 @@ pub(crate) async fn cmd_gateway(
         proxy.stop();
     }
 
+    // Let the proxy drain in-flight requests while channels can still
+    // deliver their responses.
+    if let Some(handle) = proxy_handle {
+        let drain_timeout = Duration::from_secs(config.container_agent.drain_timeout_secs);
+        let _ = tokio::time::timeout(drain_timeout + Duration::from_secs(5), handle).await;
+    }
+
     // Stop all channels
     channel_manager
         .stop_all()
         .await
         .with_context(|| "Failed to stop channels")?;
 
-    // Wait for agent/proxy to stop
+    // Wait for agent to stop
     if let Some(handle) = agent_handle {
         let _ = tokio::time::timeout(std::time::Duration::from_secs(5), handle).await;
     }
-    if let Some(handle) = proxy_handle {
-        let _ = tokio::time::timeout(std::time::Duration::from_secs(5), handle).await;
-    }
 
-    // Stop health server
+    // Stop health and metrics servers
     if let Some(handle) = health_handle {
         handle.abort();
     }
+    if let Some(handle) = metrics_handle {
+        handle.abort();
+    }
+    if let Some(handle) = prune_handle {
+        handle.abort();
+    }
+    if let Some(handle) = plugin_reload_handle {
+        handle.abort();
+    }
 
     println!("Gateway stopped.");
     Ok(())
 }
 
+/// Periodically delete sessions older than the manager's TTL.
+///
+/// Runs once at startup, then every `min(ttl, 1h)` (at least one minute).
+fn spawn_session_pruner(manager: SessionManager) -> tokio::task::JoinHandle<()> {
+    let period = session_prune_interval(manager.ttl().unwrap_or_default());
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            if let Err(e) = manager.prune_expired().await {
+                warn!(error = %e, "Failed to prune expired sessions");
+            }
+        }
+    })
+}
+
+fn session_prune_interval(ttl: Duration) -> Duration {
+    ttl.clamp(Duration::from_secs(60), Duration::from_secs(3600))
+}
+
+/// Re-discover plugins on every SIGHUP and swap their tools on the live agent.
+#[cfg(unix)]
+fn spawn_plugin_reloader(
+    agent: Arc<AgentLoop>,
+    config: Config,
+) -> Option<tokio::task::JoinHandle<()>> {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut hangup = match signal(SignalKind::hangup()) {
+        Ok(hangup) => hangup,
+        Err(e) => {
+            warn!(error = %e, "Failed to listen for SIGHUP; plugin reload disabled");
+            return None;
+        }
+    };
+    let dirs = plugin_dirs(&config);
+    // Mirror what create_agent registered at startup.
+    let mut registry = PluginRegistry::new();
+    if let Err(e) = registry.reload(&dirs) {
+        warn!(error = %e, "Plugin discovery failed");
+    }
+    info!("Send SIGHUP to reload plugins");
+    Some(tokio::spawn(async move {
+        while hangup.recv().await.is_some() {
+            match reload_plugin_tools(&agent, &mut registry, &config).await {
+                Ok(summary) if summary.has_changes() || !summary.rejected.is_empty() => info!(
+                    added = ?summary.added,
+                    updated = ?summary.updated,
+                    removed = ?summary.removed,
+                    rejected = ?summary.rejected,
+                    "Reloaded plugins"
+                ),
+                Ok(_) => info!("Reloaded plugins; nothing changed"),
+                Err(e) => warn!(error = %e, "Plugin reload failed; keeping current plugins"),
+            }
+        }
+    }))
+}
+
+/// SIGHUP plugin reload is only available on unix.
+#[cfg(not(unix))]
+fn spawn_plugin_reloader(
+    _agent: Arc<AgentLoop>,
+    _config: Config,
+) -> Option<tokio::task::JoinHandle<()>> {
+    None
+}
+
+/// Reload `registry` from the configured plugin directories and apply the
+/// difference to `agent`: tools of removed and updated plugins are dropped,
+/// tools of added and updated plugins are registered.
+async fn reload_plugin_tools(
+    agent: &AgentLoop,
+    registry: &mut PluginRegistry,
+    config: &Config,
+) -> zeptoclaw::error::Result<PluginReloadSummary> {
+    let previous_tools: Vec<(String, Vec<String>)> = registry
+        .list_plugins()
+        .into_iter()
+        .map(|plugin| {
+            let tools = plugin.manifest.tools.iter().map(|t| t.name.clone());
+            (plugin.name().to_string(), tools.collect())
+        })
+        .collect();
+    let summary = registry.reload(&plugin_dirs(config))?;
+
+    for (name, tools) in &previous_tools {
+        if summary.updated.contains(name) || summary.removed.contains(name) {
+            for tool in tools {
+                agent.unregister_tool(tool).await;
+            }
+        }
+    }
+
+    let profile_tools = profile_tool_names(config);
+    let tool_enabled = |name: &str| {
+        profile_tools
+            .as_ref()
+            .is_none_or(|profile| profile.contains(&name.to_ascii_lowercase()))
+    };
+    for name in summary.added.iter().chain(&summary.updated) {
+        if !config.plugins.is_plugin_permitted(name) {
+            info!(plugin = %name, "Plugin blocked by config");
+            continue;
+        }
+        if let Some(plugin) = registry.get_plugin(name) {
+            register_plugin_tools(agent, plugin, &tool_enabled).await;
+        }
+    }
+    Ok(summary)
+}
+
 /// Validate that Docker is available.
 async fn validate_docker_available(docker_binary: &str) -> Result<()> {
     if !zeptoclaw::gateway::is_docker_available_with_binary(docker_binary).await {
@@ -338,6 +534,16 @@ async fn validate_docker_available(docker_binary: &str) -> Result<()> {
     Ok(())
 }
 
+/// Validate that Podman is available.
+async fn validate_podman_available() -> Result<()> {
+    if !zeptoclaw::gateway::is_podman_available().await {
+        return Err(anyhow::anyhow!(
+            "Podman is not available. Install Podman or run without --containerized."
+        ));
+    }
+    Ok(())
+}
+
 fn configured_docker_binary(config: &zeptoclaw::config::ContainerAgentConfig) -> &str {
     config
         .docker_binary
@@ -416,6 +622,86 @@ async fn install_and_start_dep(mgr: &DepManager, dep: &zeptoclaw::deps::Dependen
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_session_prune_interval_is_clamped() {
+        assert_eq!(
+            session_prune_interval(Duration::from_secs(5)),
+            Duration::from_secs(60)
+        );
+        assert_eq!(
+            session_prune_interval(Duration::from_secs(600)),
+            Duration::from_secs(600)
+        );
+        assert_eq!(
+            session_prune_interval(Duration::from_secs(86400)),
+            Duration::from_secs(3600)
+        );
+    }
+
+    fn write_plugin(root: &std::path::Path, name: &str, tools: &[&str]) {
+        let tools: Vec<_> = tools
+            .iter()
+            .map(|tool| {
+                serde_json::json!({
+                    "name": tool,
+                    "description": "test tool",
+                    "parameters": {"type": "object"},
+                    "command": "echo hi"
+                })
+            })
+            .collect();
+        let dir = root.join(name);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("plugin.json"),
+            serde_json::json!({
+                "name": name,
+                "version": "1.0.0",
+                "description": "test plugin",
+                "tools": tools
+            })
+            .to_string(),
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_reload_plugin_tools_swaps_tools_on_live_agent() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut config = Config::default();
+        config.plugins.enabled = true;
+        config.plugins.plugin_dirs = vec![tmp.path().to_string_lossy().into_owned()];
+        let agent = AgentLoop::new(
+            config.clone(),
+            SessionManager::new_memory(),
+            Arc::new(MessageBus::new()),
+        );
+        let mut registry = PluginRegistry::new();
+
+        write_plugin(tmp.path(), "alpha", &["alpha_tool"]);
+        let summary = reload_plugin_tools(&agent, &mut registry, &config)
+            .await
+            .unwrap();
+        assert_eq!(summary.added, vec!["alpha"]);
+        assert!(agent.has_tool("alpha_tool").await);
+
+        // Same version, different tools: still picked up.
+        write_plugin(tmp.path(), "alpha", &["alpha_renamed"]);
+        let summary = reload_plugin_tools(&agent, &mut registry, &config)
+            .await
+            .unwrap();
+        assert_eq!(summary.updated, vec!["alpha"]);
+        assert!(!agent.has_tool("alpha_tool").await);
+        assert!(agent.has_tool("alpha_renamed").await);
+
+        std::fs::remove_dir_all(tmp.path().join("alpha")).unwrap();
+        let summary = reload_plugin_tools(&agent, &mut registry, &config)
+            .await
+            .unwrap();
+        assert_eq!(summary.removed, vec!["alpha"]);
+        assert!(!agent.has_tool("alpha_renamed").await);
+    }
+
     #[test]
     fn test_collect_enabled_channel_deps_whatsapp_managed() {
         let mut config = Config::default();
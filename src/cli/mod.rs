@@ -6,6 +6,7 @@ pub mod agent;
 pub mod batch;
 pub mod channel;
 pub mod common;
+pub mod completions;
 pub mod config;
 pub mod gateway;
 pub mod heartbeat;
@@ -14,6 +15,7 @@ pub mod memory;
 pub mod migrate;
 pub mod onboard;
 pub mod secrets;
+pub mod session;
 pub mod skills;
 pub mod status;
 pub mod template;
@@ -40,10 +42,16 @@ enum Commands {
         /// Run full 10-step wizard (express mode by default)
         #[arg(long)]
         full: bool,
+        /// Configure this provider without the selection menu (e.g. anthropic, openai)
+        #[arg(long)]
+        provider: Option<String>,
+        /// API key for --provider (prompted for if omitted)
+        #[arg(long, requires = "provider")]
+        api_key: Option<String>,
     },
     /// Start interactive agent mode
     Agent {
-        /// Direct message to process (non-interactive mode)
+        /// Direct message to process (non-interactive mode; read from piped stdin if omitted)
         #[arg(short, long)]
         message: Option<String>,
         /// Apply an agent template (built-in or ~/.zeptoclaw/templates/*.json)
@@ -52,7 +60,7 @@ enum Commands {
         /// Stream the response token-by-token
         #[arg(long)]
         stream: bool,
-        /// Show what tools would be called without executing them
+        /// Plan only: mutating tools report what they would do; read-only tools still run
         #[arg(long)]
         dry_run: bool,
     },
@@ -79,7 +87,7 @@ enum Commands {
     },
     /// Start multi-channel gateway
     Gateway {
-        /// Run in container isolation [optional: docker, apple]
+        /// Run in container isolation [optional: docker, podman, apple]
         #[arg(long, num_args = 0..=1, default_missing_value = "auto", value_name = "BACKEND")]
         containerized: Option<String>,
         /// Start a tunnel to expose gateway publicly [cloudflare, ngrok, tailscale, auto]
@@ -102,6 +110,11 @@ enum Commands {
         #[command(subcommand)]
         action: HistoryAction,
     },
+    /// Inspect and delete stored sessions
+    Session {
+        #[command(subcommand)]
+        action: SessionAction,
+    },
     /// Manage long-term memory
     Memory {
         #[command(subcommand)]
@@ -129,6 +142,12 @@ enum Commands {
     },
     /// Show version information
     Version,
+    /// Print a shell completion script to stdout
+    Completions {
+        /// Target shell
+        #[arg(value_enum)]
+        shell: CompletionShell,
+    },
     /// Show system status
     Status,
     /// Manage communication channels
@@ -234,8 +253,12 @@ pub enum SkillsAction {
 
 #[derive(Subcommand)]
 pub enum ToolsAction {
-    /// List all available tools with status
-    List,
+    /// List registered tools with their source and description
+    List {
+        /// Print JSON including each tool's parameter schema
+        #[arg(long)]
+        json: bool,
+    },
     /// Show details for a specific tool
     Info {
         /// Tool name
@@ -245,12 +268,15 @@ pub enum ToolsAction {
 
 #[derive(Subcommand)]
 pub enum AuthAction {
-    /// Log in to AI provider via OAuth browser sign-in
+    /// Log in to AI provider via OAuth browser sign-in, or store an API key
     Login {
         /// Provider to authenticate with (e.g., "anthropic")
         provider: Option<String>,
+        /// Prompt for an API key and store it in the OS keyring instead of using OAuth
+        #[arg(long)]
+        api_key: bool,
     },
-    /// Log out from AI provider (delete stored OAuth tokens)
+    /// Log out from AI provider (delete stored OAuth tokens and API key)
     Logout {
         /// Provider to log out from (e.g., "anthropic")
         provider: Option<String>,
@@ -268,6 +294,9 @@ pub enum AuthAction {
 pub enum ConfigAction {
     /// Check configuration for errors and warnings
     Check,
+    /// Deserialize the config and check cross-references between sections
+    /// (rotation providers, approval and hook tools/channels, templates)
+    Validate,
 }
 
 #[derive(Subcommand)]
@@ -307,6 +336,46 @@ pub enum HistoryAction {
     },
 }
 
+#[derive(Subcommand)]
+pub enum SessionAction {
+    /// List stored sessions with message counts
+    List,
+    /// Print a session transcript
+    Show {
+        /// Session key (e.g. "telegram:chat123")
+        key: String,
+        /// Print the raw session as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Delete a session, or every session with --all
+    Rm {
+        /// Session key to delete
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        key: Option<String>,
+        /// Delete all stored sessions
+        #[arg(long)]
+        all: bool,
+        /// Skip the confirmation prompt
+        #[arg(long, short)]
+        yes: bool,
+    },
+    /// Delete sessions older than session.ttl_secs
+    Prune {
+        /// Override the configured TTL (seconds)
+        #[arg(long)]
+        ttl_secs: Option<u64>,
+    },
+    /// Pin a session so TTL expiry skips it
+    Keep {
+        /// Session key to pin
+        key: String,
+        /// Unpin the session instead
+        #[arg(long)]
+        unset: bool,
+    },
+}
+
 #[derive(Subcommand)]
 pub enum TemplateAction {
     /// List available templates (built-in + user-defined)
@@ -316,6 +385,8 @@ pub enum TemplateAction {
         /// Template name
         name: String,
     },
+    /// Check template tool lists against the registered tools
+    Validate,
 }
 
 #[derive(Subcommand)]
@@ -328,6 +399,14 @@ pub enum SecretsAction {
     Rotate,
 }
 
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
+pub enum CompletionShell {
+    Bash,
+    Zsh,
+    Fish,
+    Powershell,
+}
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
 pub enum BatchFormat {
     Text,
@@ -336,7 +415,8 @@ pub enum BatchFormat {
 
 /// Entry point for the CLI — called from main().
 pub async fn run() -> Result<()> {
-    // Initialize logging (JSON format when RUST_LOG_FORMAT=json)
+    // Initialize logging (JSON format when RUST_LOG_FORMAT=json). Logs go to
+    // stderr so command output on stdout stays machine-readable.
     let env_filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("warn"));
     let use_json = std::env::var("RUST_LOG_FORMAT")
         .map(|v| v.eq_ignore_ascii_case("json"))
@@ -347,9 +427,13 @@ pub async fn run() -> Result<()> {
             .with_env_filter(env_filter)
             .with_target(true)
             .with_thread_ids(false)
+            .with_writer(std::io::stderr)
             .init();
     } else {
-        tracing_subscriber::fmt().with_env_filter(env_filter).init();
+        tracing_subscriber::fmt()
+            .with_env_filter(env_filter)
+            .with_writer(std::io::stderr)
+            .init();
     }
 
     let cli = Cli::parse();
@@ -363,8 +447,15 @@ pub async fn run() -> Result<()> {
         Some(Commands::Version) => {
             cmd_version();
         }
-        Some(Commands::Onboard { full }) => {
-            onboard::cmd_onboard(full).await?;
+        Some(Commands::Completions { shell }) => {
+            completions::cmd_completions(shell)?;
+        }
+        Some(Commands::Onboard {
+            full,
+            provider,
+            api_key,
+        }) => {
+            onboard::cmd_onboard(full, provider, api_key).await?;
         }
         Some(Commands::Agent {
             message,
@@ -399,6 +490,9 @@ pub async fn run() -> Result<()> {
         Some(Commands::History { action }) => {
             history::cmd_history(action).await?;
         }
+        Some(Commands::Session { action }) => {
+            session::cmd_session(action).await?;
+        }
         Some(Commands::Memory { action }) => {
             memory::cmd_memory(action).await?;
         }
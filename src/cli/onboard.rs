@@ -1,11 +1,14 @@
 //! Interactive onboarding wizard (zeptoclaw onboard).
 
 use std::io::{self, Write};
+use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 
 use zeptoclaw::config::{Config, MemoryBackend, MemoryCitationsMode, RuntimeType};
-use zeptoclaw::providers::configured_provider_names;
+use zeptoclaw::providers::{
+    configured_provider_names, provider_config_slot_mut, PROVIDER_REGISTRY,
+};
 
 use super::common::{memory_backend_label, memory_citations_label, read_line, read_secret};
 
@@ -136,12 +139,148 @@ fn express_next_steps() -> String {
     .join("\n")
 }
 
+/// Starter contents for `workspace/MEMORY.md`.
+const STARTER_MEMORY: &str = "# Memory
+
+Long-term notes for ZeptoClaw. The agent reads this file for context and can
+append to it with the `memory` tools. Add facts you want remembered across
+sessions, for example:
+
+- Preferred languages and tools
+- Project names and where they live
+";
+
+/// Directories created under the config directory by `onboard`.
+const SCAFFOLD_DIRS: &[&str] = &["workspace", "sessions", "plugins", "channels", "templates"];
+
+/// A path `onboard` ensured exists, and whether this run created it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ScaffoldEntry {
+    path: PathBuf,
+    created: bool,
+}
+
+/// Create the config directory layout and starter files under `config_dir`.
+///
+/// Existing directories and files are left untouched, so re-running is safe.
+fn scaffold_layout(config_dir: &Path) -> Result<Vec<ScaffoldEntry>> {
+    let mut entries = Vec::new();
+
+    for dir in std::iter::once(config_dir.to_path_buf())
+        .chain(SCAFFOLD_DIRS.iter().map(|name| config_dir.join(name)))
+    {
+        let created = !dir.is_dir();
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create directory: {:?}", dir))?;
+        entries.push(ScaffoldEntry { path: dir, created });
+    }
+
+    let memory_path = config_dir.join("workspace").join("MEMORY.md");
+    let created = !memory_path.exists();
+    if created {
+        std::fs::write(&memory_path, STARTER_MEMORY)
+            .with_context(|| format!("Failed to write {:?}", memory_path))?;
+    }
+    entries.push(ScaffoldEntry {
+        path: memory_path,
+        created,
+    });
+
+    Ok(entries)
+}
+
+/// Format the created / already-present summary printed after onboarding.
+fn scaffold_summary(entries: &[ScaffoldEntry]) -> String {
+    let mut lines = Vec::new();
+    for (label, created) in [("Created:", true), ("Already present:", false)] {
+        let paths: Vec<_> = entries.iter().filter(|e| e.created == created).collect();
+        if paths.is_empty() {
+            continue;
+        }
+        lines.push(format!("  {}", label));
+        lines.extend(paths.iter().map(|e| format!("    {}", e.path.display())));
+    }
+    lines.join("\n")
+}
+
+/// Normalize a `--provider` value and check it names a registered provider.
+fn known_provider(name: &str) -> Result<String> {
+    let name = name.trim().to_ascii_lowercase();
+    if !PROVIDER_REGISTRY.iter().any(|spec| spec.name == name) {
+        let known: Vec<_> = PROVIDER_REGISTRY.iter().map(|spec| spec.name).collect();
+        anyhow::bail!(
+            "Unknown provider '{}'. Expected one of: {}",
+            name,
+            known.join(", ")
+        );
+    }
+    Ok(name)
+}
+
+/// Store `api_key` for the provider named on the command line.
+///
+/// Known providers also get their default model, matching the interactive
+/// setup. An empty key leaves the provider config unchanged.
+fn apply_provider_flag(config: &mut Config, provider: &str, api_key: &str) -> Result<()> {
+    let provider = known_provider(provider)?;
+    let slot = provider_config_slot_mut(config, &provider)
+        .with_context(|| format!("Provider '{}' has no config section", provider))?;
+    if api_key.is_empty() {
+        return Ok(());
+    }
+    slot.get_or_insert_with(Default::default).api_key = Some(api_key.to_string());
+
+    let default_model = match provider.as_str() {
+        "anthropic" => Some("claude-sonnet-4-5-20250929"),
+        "openai" => Some("gpt-5.1"),
+        "openrouter" => Some("openrouter/auto"),
+        _ => None,
+    };
+    if let Some(model) = default_model {
+        config.agents.defaults.model = model.to_string();
+    }
+    Ok(())
+}
+
+/// Configure the provider passed with `--provider`, prompting for the key
+/// when `--api-key` was not given.
+fn configure_provider_from_flags(
+    config: &mut Config,
+    provider: &str,
+    api_key: Option<String>,
+) -> Result<()> {
+    let provider = known_provider(provider)?;
+    let api_key = match api_key {
+        Some(key) => key,
+        None => {
+            print!("Enter {} API key (or press Enter to skip): ", provider);
+            io::stdout().flush()?;
+            read_secret()?
+        }
+    };
+    apply_provider_flag(config, &provider, api_key.trim())?;
+    if api_key.trim().is_empty() {
+        println!("  Skipped {} API key.", provider);
+    } else {
+        println!("  {} API key configured.", provider);
+    }
+    Ok(())
+}
+
 /// Initialize configuration directory and save default config.
 ///
 /// When `full` is false (default), runs express mode: creates directories
 /// silently, configures the LLM provider, saves, and prints guided next
 /// steps.  When `full` is true, runs the full 10-step interactive wizard.
-pub(crate) async fn cmd_onboard(full: bool) -> Result<()> {
+/// `provider` (with an optional `api_key`) replaces the provider menu.
+///
+/// Existing directories, `MEMORY.md` and config values are kept; the config
+/// is loaded, updated with the answers, and saved back.
+pub(crate) async fn cmd_onboard(
+    full: bool,
+    provider: Option<String>,
+    api_key: Option<String>,
+) -> Result<()> {
     // Check for existing OpenClaw installation
     if let Some(oc_dir) = zeptoclaw::migrate::detect_openclaw_dir() {
         println!("Detected OpenClaw installation at: {}", oc_dir.display());
@@ -149,43 +288,35 @@ pub(crate) async fn cmd_onboard(full: bool) -> Result<()> {
         println!();
     }
 
-    // --- common: create directories ---
+    // --- common: create directories and starter files ---
     let config_dir = Config::dir();
-    std::fs::create_dir_all(&config_dir)
-        .with_context(|| format!("Failed to create config directory: {:?}", config_dir))?;
-
-    let workspace_dir = config_dir.join("workspace");
-    std::fs::create_dir_all(&workspace_dir)
-        .with_context(|| format!("Failed to create workspace directory: {:?}", workspace_dir))?;
-
-    let sessions_dir = config_dir.join("sessions");
-    std::fs::create_dir_all(&sessions_dir)
-        .with_context(|| format!("Failed to create sessions directory: {:?}", sessions_dir))?;
+    let mut scaffold = scaffold_layout(&config_dir)?;
 
     // --- common: load or create config ---
     let config_path = Config::path();
-    let mut config = if config_path.exists() {
+    let config_existed = config_path.exists();
+    let mut config = if config_existed {
         Config::load()
             .with_context(|| format!("Failed to load existing config at {:?}", config_path))?
     } else {
         Config::default()
     };
+    scaffold.push(ScaffoldEntry {
+        path: config_path.clone(),
+        created: !config_existed,
+    });
 
     if full {
         // ---------- full 10-step wizard ----------
         println!("Initializing ZeptoClaw (full wizard)...");
         println!();
-        println!("  Config directory: {:?}", config_dir);
-        println!("  Workspace directory: {:?}", workspace_dir);
-        println!("  Sessions directory: {:?}", sessions_dir);
-        if config_path.exists() {
-            println!("  Config already exists: {:?}", config_path);
-        } else {
-            println!("  Creating new config: {:?}", config_path);
-        }
+        println!("{}", scaffold_summary(&scaffold));
 
         println!();
-        configure_providers(&mut config).await?;
+        match provider.as_deref() {
+            Some(name) => configure_provider_from_flags(&mut config, name, api_key)?,
+            None => configure_providers(&mut config).await?,
+        }
 
         // Configure web search integration
         configure_web_search(&mut config)?;
@@ -226,13 +357,19 @@ pub(crate) async fn cmd_onboard(full: bool) -> Result<()> {
         println!("Initializing ZeptoClaw...");
         println!();
 
-        configure_providers(&mut config).await?;
+        match provider.as_deref() {
+            Some(name) => configure_provider_from_flags(&mut config, name, api_key)?,
+            None => configure_providers(&mut config).await?,
+        }
 
         // Save config
         config
             .save()
             .with_context(|| "Failed to save configuration")?;
 
+        println!();
+        println!("{}", scaffold_summary(&scaffold));
+
         // Print guided next steps
         println!("{}", express_next_steps());
     }
@@ -777,6 +914,50 @@ fn configure_runtime(config: &mut Config) -> Result<()> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_scaffold_layout_is_idempotent() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_dir = dir.path().join(".zeptoclaw");
+
+        let first = scaffold_layout(&config_dir).unwrap();
+        assert!(first.iter().all(|e| e.created));
+        for name in SCAFFOLD_DIRS {
+            assert!(config_dir.join(name).is_dir(), "{} missing", name);
+        }
+        let memory = config_dir.join("workspace/MEMORY.md");
+        assert_eq!(std::fs::read_to_string(&memory).unwrap(), STARTER_MEMORY);
+
+        std::fs::write(&memory, "my notes").unwrap();
+        let second = scaffold_layout(&config_dir).unwrap();
+        assert!(second.iter().all(|e| !e.created));
+        assert_eq!(std::fs::read_to_string(&memory).unwrap(), "my notes");
+
+        let summary = scaffold_summary(&second);
+        assert!(summary.contains("Already present:"));
+        assert!(!summary.contains("Created:"));
+    }
+
+    #[test]
+    fn test_apply_provider_flag() {
+        let mut config = Config::default();
+        apply_provider_flag(&mut config, "OpenAI", "sk-test").unwrap();
+        assert_eq!(
+            config.providers.openai.as_ref().unwrap().api_key.as_deref(),
+            Some("sk-test")
+        );
+        assert_eq!(config.agents.defaults.model, "gpt-5.1");
+
+        apply_provider_flag(&mut config, "groq", "gsk-test").unwrap();
+        assert!(config.providers.groq.is_some());
+        assert_eq!(config.agents.defaults.model, "gpt-5.1");
+
+        apply_provider_flag(&mut config, "gemini", "").unwrap();
+        assert!(config.providers.gemini.is_none());
+
+        let err = apply_provider_flag(&mut config, "nope", "key").unwrap_err();
+        assert!(err.to_string().contains("Unknown provider 'nope'"));
+    }
+
     #[test]
     fn test_express_next_steps_message() {
         let msg = express_next_steps();
@@ -0,0 +1,201 @@
+//! Session store command handler.
+
+use std::io::{self, Write};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+
+use zeptoclaw::config::Config;
+use zeptoclaw::session::{Role, Session, SessionManager};
+
+use super::common::read_line;
+use super::SessionAction;
+
+/// Inspect and delete sessions in the configured session store.
+pub(crate) async fn cmd_session(action: SessionAction) -> Result<()> {
+    let config = Config::load().with_context(|| "Failed to load configuration")?;
+    let manager =
+        SessionManager::from_config(&config).with_context(|| "Failed to open session store")?;
+
+    match action {
+        SessionAction::List => {
+            let mut sessions = Vec::new();
+            for key in manager.list().await? {
+                if let Some(session) = manager.get(&key).await? {
+                    sessions.push(session);
+                }
+            }
+            if sessions.is_empty() {
+                println!("No sessions found.");
+                return Ok(());
+            }
+
+            sessions.sort_by_key(|s| std::cmp::Reverse(s.updated_at));
+            println!("{} session(s):", sessions.len());
+            for session in &sessions {
+                println!("{}", list_line(session));
+            }
+        }
+        SessionAction::Show { key, json } => {
+            let Some(session) = manager.get(&key).await? else {
+                anyhow::bail!("No session found for key '{}'", key);
+            };
+
+            if json {
+                println!("{}", serde_json::to_string_pretty(&session)?);
+                return Ok(());
+            }
+
+            println!("Session: {}", session.key);
+            println!("Created: {}", session.created_at.to_rfc3339());
+            println!("Updated: {}", session.updated_at.to_rfc3339());
+            println!("Messages: {}", session.messages.len());
+            if let Some(summary) = &session.summary {
+                println!("Summary: {}", summary);
+            }
+            println!();
+
+            for message in session.messages {
+                println!("[{}]", role_label(&message.role));
+                println!("{}", message.content);
+                println!();
+            }
+        }
+        SessionAction::Rm { key, all, yes } => {
+            let keys = if all {
+                manager.list().await?
+            } else {
+                let key = key.context("Specify a session key or --all")?;
+                if !manager.exists(&key).await {
+                    anyhow::bail!("No session found for key '{}'", key);
+                }
+                vec![key]
+            };
+
+            if keys.is_empty() {
+                println!("No sessions found.");
+                return Ok(());
+            }
+
+            if !yes {
+                let target = if all {
+                    format!("all {} session(s)", keys.len())
+                } else {
+                    format!("session '{}'", keys[0])
+                };
+                print!("Delete {}? [y/N]: ", target);
+                io::stdout().flush()?;
+                let answer = read_line()?.to_ascii_lowercase();
+                if answer != "y" && answer != "yes" {
+                    println!("Aborted.");
+                    return Ok(());
+                }
+            }
+
+            for key in &keys {
+                manager.delete(key).await?;
+            }
+            if all {
+                println!("Deleted {} session(s).", keys.len());
+            } else {
+                println!("Deleted session '{}'.", keys[0]);
+            }
+        }
+        SessionAction::Prune { ttl_secs } => {
+            let manager = match ttl_secs {
+                Some(secs) => manager.with_ttl(Duration::from_secs(secs)),
+                None => manager,
+            };
+            let Some(ttl) = manager.ttl().filter(|ttl| !ttl.is_zero()) else {
+                anyhow::bail!("No session TTL configured. Set session.ttl_secs or pass --ttl-secs");
+            };
+            let removed = manager.prune_expired().await?;
+            println!(
+                "Pruned {} session(s) not updated in the last {}s.",
+                removed.len(),
+                ttl.as_secs()
+            );
+        }
+        SessionAction::Keep { key, unset } => {
+            if !manager.set_keep(&key, !unset).await? {
+                anyhow::bail!("No session found for key '{}'", key);
+            }
+            if unset {
+                println!("Session '{}' unpinned.", key);
+            } else {
+                println!("Session '{}' pinned; it will not expire.", key);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn list_line(session: &Session) -> String {
+    format!(
+        "- {} | {} msgs | {}{}",
+        session.key,
+        session.messages.len(),
+        session.updated_at.to_rfc3339(),
+        if session.keep { " | pinned" } else { "" }
+    )
+}
+
+fn role_label(role: &Role) -> &'static str {
+    match role {
+        Role::System => "system",
+        Role::User => "user",
+        Role::Assistant => "assistant",
+        Role::Tool => "tool",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    use crate::cli::{Cli, Commands};
+
+    fn parse_rm(args: &[&str]) -> Result<SessionAction, clap::Error> {
+        let argv = ["zeptoclaw", "session", "rm"].iter().chain(args);
+        match Cli::try_parse_from(argv)?.command {
+            Some(Commands::Session { action }) => Ok(action),
+            _ => unreachable!("expected session command"),
+        }
+    }
+
+    #[test]
+    fn test_list_line_shows_key_count_and_updated_at() {
+        let mut session = Session::new("telegram:chat1");
+        session.add_message(zeptoclaw::session::Message::user("hi"));
+        let line = list_line(&session);
+        assert!(line.starts_with("- telegram:chat1 | 1 msgs | "));
+        assert!(line.ends_with(&session.updated_at.to_rfc3339()));
+
+        session.keep = true;
+        assert!(list_line(&session).ends_with(" | pinned"));
+    }
+
+    #[test]
+    fn test_rm_requires_key_or_all() {
+        assert!(parse_rm(&[]).is_err());
+        assert!(parse_rm(&["cli:1", "--all"]).is_err());
+        assert!(matches!(
+            parse_rm(&["--all", "--yes"]),
+            Ok(SessionAction::Rm {
+                key: None,
+                all: true,
+                yes: true
+            })
+        ));
+        assert!(matches!(
+            parse_rm(&["cli:1"]),
+            Ok(SessionAction::Rm {
+                key: Some(_),
+                all: false,
+                yes: false
+            })
+        ));
+    }
+}
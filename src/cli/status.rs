@@ -1,23 +1,32 @@
 //! Status and auth command handlers.
 
+use std::io::{self, Write};
+
 use anyhow::{Context, Result};
 
 use zeptoclaw::auth;
 use zeptoclaw::config::{Config, ContainerAgentBackend, ProviderConfig};
 use zeptoclaw::providers::{
-    configured_unsupported_provider_names, resolve_runtime_provider, RUNTIME_SUPPORTED_PROVIDERS,
+    configured_unsupported_provider_names, resolve_runtime_provider, PROVIDER_REGISTRY,
+    RUNTIME_SUPPORTED_PROVIDERS,
 };
 use zeptoclaw::runtime::available_runtimes;
 
-use super::common::{memory_backend_label, memory_citations_label, skills_loader_from_config};
+use super::common::{
+    memory_backend_label, memory_citations_label, read_line, read_secret, skills_loader_from_config,
+};
 use super::heartbeat::heartbeat_file_path;
 use super::AuthAction;
 
 /// Manage authentication.
 pub(crate) async fn cmd_auth(action: AuthAction) -> Result<()> {
     match action {
-        AuthAction::Login { provider } => {
-            cmd_auth_login(provider).await?;
+        AuthAction::Login { provider, api_key } => {
+            if api_key {
+                cmd_auth_login_api_key(provider)?;
+            } else {
+                cmd_auth_login(provider).await?;
+            }
         }
         AuthAction::Logout { provider } => {
             cmd_auth_logout(provider)?;
@@ -47,10 +56,13 @@ async fn cmd_auth_login(provider: Option<String>) -> Result<()> {
         anyhow::anyhow!(
             "Provider '{}' does not support OAuth authentication.\n\
              Supported providers: {}\n\n\
-             To configure API keys instead:\n  \
+             To store an API key instead:\n  \
+             zeptoclaw auth login {} --api-key\n\n\
+             or set it in the environment:\n  \
              export ZEPTOCLAW_PROVIDERS_{}_API_KEY=your-key-here",
             provider,
             auth::oauth_supported_providers().join(", "),
+            provider,
             provider.to_uppercase()
         )
     })?;
@@ -116,21 +128,99 @@ async fn cmd_auth_login(provider: Option<String>) -> Result<()> {
     Ok(())
 }
 
-/// OAuth logout.
+/// Prompt for a provider API key and store it in the OS keyring, falling
+/// back to the encrypted key store when no keyring backend is reachable.
+fn cmd_auth_login_api_key(provider: Option<String>) -> Result<()> {
+    let known: Vec<&str> = PROVIDER_REGISTRY.iter().map(|spec| spec.name).collect();
+    let provider = match provider {
+        Some(provider) => provider,
+        None => {
+            print!("Provider ({}): ", known.join(", "));
+            io::stdout().flush()?;
+            read_line()?
+        }
+    };
+    let provider = provider.trim().to_ascii_lowercase();
+    if !known.contains(&provider.as_str()) {
+        anyhow::bail!(
+            "Unknown provider '{}'. Known providers: {}",
+            provider,
+            known.join(", ")
+        );
+    }
+
+    print!("Enter {} API key: ", provider);
+    io::stdout().flush()?;
+    let api_key = read_secret()?;
+    if api_key.trim().is_empty() {
+        anyhow::bail!("No API key entered; nothing stored.");
+    }
+
+    println!();
+    match auth::api_keys::KeyringApiKeys::new().set(&provider, &api_key) {
+        Ok(()) => println!("Stored API key for {} in the OS keyring.", provider),
+        Err(e) => {
+            println!("WARNING: The OS keyring is not available: {}", e);
+            let encryption =
+                zeptoclaw::security::encryption::resolve_master_key(true).map_err(|e| {
+                    anyhow::anyhow!("Cannot store API key without encryption key: {}", e)
+                })?;
+            let store = auth::api_keys::ApiKeyStore::new(encryption);
+            store
+                .set(&provider, &api_key)
+                .map_err(|e| anyhow::anyhow!("Failed to save API key: {}", e))?;
+            println!(
+                "Stored API key for {} encrypted at {} instead.",
+                provider,
+                store.path().display()
+            );
+            println!("Loading it requires ZEPTOCLAW_MASTER_KEY or the master passphrase.");
+        }
+    }
+    println!(
+        "It takes precedence over any api_key for {} in config.json.",
+        provider
+    );
+    Ok(())
+}
+
+/// OAuth and stored API key logout.
 fn cmd_auth_logout(provider: Option<String>) -> Result<()> {
+    // Keyring keys need no master key, so remove them first
+    let keyring = auth::api_keys::KeyringApiKeys::new();
+    if let Some(ref provider) = provider {
+        match keyring.delete(provider) {
+            Ok(true) => println!("Removed API key for {} from the OS keyring.", provider),
+            Ok(false) => {}
+            Err(e) => println!("Failed to remove API key from the OS keyring: {}", e),
+        }
+    } else {
+        match keyring.providers() {
+            Ok(names) if !names.is_empty() => {
+                println!("API keys in the OS keyring: {}", names.join(", "));
+            }
+            Ok(_) => {}
+            Err(e) => println!("Failed to read keyring index: {}", e),
+        }
+    }
+
     let encryption = match zeptoclaw::security::encryption::resolve_master_key(true) {
         Ok(enc) => enc,
         Err(_) => {
-            println!("No encryption key available. If you have stored tokens,");
-            let path = Config::dir().join("auth").join("tokens.json.enc");
-            #[cfg(windows)]
-            println!("delete them manually: del {}", path.display());
-            #[cfg(not(windows))]
-            println!("delete them manually: rm {}", path.display());
+            println!("No encryption key available. If you have stored credentials,");
+            let auth_dir = Config::dir().join("auth");
+            for file in ["tokens.json.enc", "api_keys.json.enc"] {
+                let path = auth_dir.join(file);
+                #[cfg(windows)]
+                println!("delete them manually: del {}", path.display());
+                #[cfg(not(windows))]
+                println!("delete them manually: rm {}", path.display());
+            }
             return Ok(());
         }
     };
 
+    let key_store = auth::api_keys::ApiKeyStore::new(encryption.clone());
     let store = auth::store::TokenStore::new(encryption);
 
     if let Some(provider) = provider {
@@ -139,7 +229,19 @@ fn cmd_auth_logout(provider: Option<String>) -> Result<()> {
             Ok(false) => println!("No OAuth tokens stored for '{}'.", provider),
             Err(e) => println!("Failed to remove tokens: {}", e),
         }
+        match key_store.delete(&provider) {
+            Ok(true) => println!("Removed encrypted API key for {}.", provider),
+            Ok(false) => println!("No encrypted API key stored for '{}'.", provider),
+            Err(e) => println!("Failed to remove API key: {}", e),
+        }
     } else {
+        match key_store.providers() {
+            Ok(names) if !names.is_empty() => {
+                println!("Encrypted API keys: {}", names.join(", "));
+            }
+            Ok(_) => {}
+            Err(e) => println!("Failed to read API key store: {}", e),
+        }
         // Show what's stored and ask which to remove
         match store.list() {
             Ok(entries) if entries.is_empty() => {
@@ -203,15 +305,37 @@ async fn cmd_auth_status() -> Result<()> {
     println!("=====================");
     println!();
 
-    // Load OAuth token store (best-effort)
-    let token_store = match zeptoclaw::security::encryption::resolve_master_key(false) {
-        Ok(enc) => Ok(auth::store::TokenStore::new(enc)),
-        Err(err) => {
-            println!("OAuth token store unavailable: {}", err);
+    // Providers with a key in the OS keyring (from the index, no keyring access)
+    let keyring_keys = auth::api_keys::KeyringApiKeys::new()
+        .providers()
+        .unwrap_or_else(|err| {
+            println!("Keyring index unreadable: {}", err);
             println!();
-            Err(err)
-        }
-    };
+            Vec::new()
+        });
+
+    // Load OAuth token store and API key store (best-effort)
+    let (token_store, stored_keys) =
+        match zeptoclaw::security::encryption::resolve_master_key(false) {
+            Ok(enc) => {
+                let key_store = auth::api_keys::ApiKeyStore::new(enc.clone());
+                let stored_keys = if key_store.exists() {
+                    key_store.providers().unwrap_or_else(|err| {
+                        println!("API key store unreadable: {}", err);
+                        println!();
+                        Vec::new()
+                    })
+                } else {
+                    Vec::new()
+                };
+                (Ok(auth::store::TokenStore::new(enc)), stored_keys)
+            }
+            Err(err) => {
+                println!("OAuth token store unavailable: {}", err);
+                println!();
+                (Err(err), Vec::new())
+            }
+        };
 
     let oauth_status = |name: &str| -> String {
         if let Ok(ref store) = token_store {
@@ -239,7 +363,13 @@ async fn cmd_auth_status() -> Result<()> {
     };
 
     let provider_display = |name: &str, label: &str, provider: &Option<ProviderConfig>| {
-        let api = provider_status(provider);
+        let api = if keyring_keys.iter().any(|stored| stored == name) {
+            "stored (keyring)"
+        } else if stored_keys.iter().any(|stored| stored == name) {
+            "stored (encrypted)"
+        } else {
+            provider_status(provider)
+        };
         let oauth = oauth_status(name);
         let auth_method = provider
             .as_ref()
@@ -269,6 +399,12 @@ async fn cmd_auth_status() -> Result<()> {
     provider_display("zhipu", "Zhipu             ", &config.providers.zhipu);
 
     println!();
+    if !keyring_keys.is_empty() {
+        println!("API keys in the OS keyring: {}", keyring_keys.join(", "));
+    }
+    if !stored_keys.is_empty() {
+        println!("Encrypted API keys: {}", stored_keys.join(", "));
+    }
     println!(
         "OAuth-supported providers: {}",
         auth::oauth_supported_providers().join(", ")
@@ -432,6 +568,7 @@ pub(crate) async fn cmd_status() -> Result<()> {
     let backend_label = match config.container_agent.backend {
         ContainerAgentBackend::Auto => "auto",
         ContainerAgentBackend::Docker => "docker",
+        ContainerAgentBackend::Podman => "podman",
         #[cfg(target_os = "macos")]
         ContainerAgentBackend::Apple => "apple",
     };
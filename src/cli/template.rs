@@ -1,15 +1,20 @@
 //! Template command handler.
 
-use anyhow::Result;
+use std::sync::Arc;
 
+use anyhow::{Context, Result};
+
+use zeptoclaw::bus::MessageBus;
 use zeptoclaw::config::templates::TemplateRegistry;
+use zeptoclaw::config::Config;
 
-use super::common::load_template_registry;
+use super::common::{create_agent, load_template_registry};
 use super::TemplateAction;
 
 /// Manage agent templates.
 pub(crate) async fn cmd_template(action: TemplateAction) -> Result<()> {
-    let registry = load_template_registry()?;
+    let config = Config::load().with_context(|| "Failed to load configuration")?;
+    let registry = load_template_registry(&config)?;
 
     match action {
         TemplateAction::List => {
@@ -44,6 +49,9 @@ pub(crate) async fn cmd_template(action: TemplateAction) -> Result<()> {
 
             println!("Name: {}", tpl.name);
             println!("Description: {}", tpl.description);
+            if let Some(parent) = &tpl.extends {
+                println!("Extends: {}", parent);
+            }
             if let Some(model) = &tpl.model {
                 println!("Model override: {}", model);
             }
@@ -56,12 +64,24 @@ pub(crate) async fn cmd_template(action: TemplateAction) -> Result<()> {
             if let Some(max_tool_iterations) = tpl.max_tool_iterations {
                 println!("Max tool iterations override: {}", max_tool_iterations);
             }
+            if let Some(stop) = &tpl.stop_sequences {
+                println!("Stop sequences: {:?}", stop);
+            }
             if let Some(allowed) = &tpl.allowed_tools {
                 println!("Allowed tools: {}", allowed.join(", "));
             }
             if let Some(blocked) = &tpl.blocked_tools {
                 println!("Blocked tools: {}", blocked.join(", "));
             }
+            if !tpl.vars.is_empty() {
+                let mut vars = tpl
+                    .vars
+                    .iter()
+                    .map(|(k, v)| format!("{}={}", k, v))
+                    .collect::<Vec<_>>();
+                vars.sort();
+                println!("Prompt variables: {}", vars.join(", "));
+            }
             if !tpl.tags.is_empty() {
                 println!("Tags: {}", tpl.tags.join(", "));
             }
@@ -69,6 +89,39 @@ pub(crate) async fn cmd_template(action: TemplateAction) -> Result<()> {
             println!("System prompt:");
             println!("{}", tpl.system_prompt);
         }
+        TemplateAction::Validate => {
+            // Check against every tool the config can register, not just the
+            // ones the default tool profile enables.
+            let mut agent_config = config.clone();
+            agent_config.agents.defaults.tool_profile = None;
+            let agent = create_agent(agent_config, Arc::new(MessageBus::new())).await?;
+            let mut known_tools = agent
+                .tool_definitions(false)
+                .await
+                .into_iter()
+                .map(|def| def.name)
+                .collect::<Vec<_>>();
+            known_tools.sort();
+
+            let warnings = registry.validate_against_tools(&known_tools);
+            if warnings.is_empty() {
+                println!(
+                    "All {} templates reference only registered tools.",
+                    registry.list().len()
+                );
+            } else {
+                println!("Template warnings:");
+                for warning in &warnings {
+                    println!("  - {}", warning);
+                }
+                println!();
+                println!(
+                    "{} warning(s). Registered tools: {}",
+                    warnings.len(),
+                    known_tools.join(", ")
+                );
+            }
+        }
     }
 
     Ok(())
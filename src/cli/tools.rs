@@ -1,8 +1,12 @@
 //! Tools CLI command handlers — tool discovery and info.
 
+use std::sync::Arc;
+
 use anyhow::Result;
+use zeptoclaw::bus::MessageBus;
 use zeptoclaw::config::Config;
 
+use super::common::create_agent;
 use super::ToolsAction;
 
 /// Static tool info for CLI display.
@@ -44,6 +48,18 @@ const TOOLS: &[ToolInfo] = &[
         requires_config: false,
         config_hint: "",
     },
+    ToolInfo {
+        name: "grep",
+        description: "Search workspace file contents by regex",
+        requires_config: false,
+        config_hint: "",
+    },
+    ToolInfo {
+        name: "tree",
+        description: "Show a directory tree of the workspace",
+        requires_config: false,
+        config_hint: "",
+    },
     ToolInfo {
         name: "shell",
         description: "Execute shell commands (with runtime isolation)",
@@ -62,6 +78,12 @@ const TOOLS: &[ToolInfo] = &[
         requires_config: false,
         config_hint: "",
     },
+    ToolInfo {
+        name: "http_request",
+        description: "Send HTTP requests to APIs (method, headers, body)",
+        requires_config: false,
+        config_hint: "",
+    },
     ToolInfo {
         name: "memory_search",
         description: "Search workspace markdown memory",
@@ -74,6 +96,12 @@ const TOOLS: &[ToolInfo] = &[
         requires_config: false,
         config_hint: "",
     },
+    ToolInfo {
+        name: "memory_write",
+        description: "Append dated notes to workspace memory",
+        requires_config: false,
+        config_hint: "",
+    },
     ToolInfo {
         name: "longterm_memory",
         description: "Persistent key-value memory (set/get/search/delete/list)",
@@ -128,32 +156,54 @@ const TOOLS: &[ToolInfo] = &[
         requires_config: false,
         config_hint: "",
     },
+    ToolInfo {
+        name: "list_tools",
+        description: "List the agent's available tools (self-discovery)",
+        requires_config: false,
+        config_hint: "",
+    },
 ];
 
 pub(crate) async fn cmd_tools(action: ToolsAction) -> Result<()> {
     match action {
-        ToolsAction::List => cmd_tools_list().await,
+        ToolsAction::List { json } => cmd_tools_list(json).await,
         ToolsAction::Info { name } => cmd_tools_info(name).await,
     }
 }
 
-async fn cmd_tools_list() -> Result<()> {
+/// List the tools the agent actually registers for the current config.
+///
+/// Known tools that are missing because they need setup are listed after.
+async fn cmd_tools_list(json: bool) -> Result<()> {
     let config = Config::load().unwrap_or_default();
+    let agent = create_agent(config.clone(), Arc::new(MessageBus::new())).await?;
+    let described = agent.describe_tools().await;
 
-    println!("Available Tools ({} total)", TOOLS.len());
+    if json {
+        println!("{}", serde_json::to_string_pretty(&described)?);
+        return Ok(());
+    }
+
+    println!("Registered Tools ({} total)", described.len());
     println!("{}", "=".repeat(60));
     println!();
 
-    for tool in TOOLS {
-        let configured = !tool.requires_config || is_tool_configured(&config, tool.name);
-        let status_icon = if configured { "+" } else { "-" };
+    for tool in &described {
+        println!("  {} [{}]", tool.name, tool.source);
+        println!("      {}", tool.compact_description);
+        println!();
+    }
 
-        println!("  [{}] {}", status_icon, tool.name);
-        println!("      {}", tool.description);
-        if !configured {
-            println!("      Setup: {}", tool.config_hint);
+    let needs_setup: Vec<&ToolInfo> = TOOLS
+        .iter()
+        .filter(|t| t.requires_config && !is_tool_configured(&config, t.name))
+        .filter(|t| !described.iter().any(|d| d.name == t.name))
+        .collect();
+    if !needs_setup.is_empty() {
+        println!("Needs setup:");
+        for tool in needs_setup {
+            println!("  - {}: {}", tool.name, tool.config_hint);
         }
-        println!();
     }
 
     Ok(())
@@ -260,7 +310,7 @@ mod tests {
 
     #[test]
     fn test_tools_list_count() {
-        assert_eq!(TOOLS.len(), 19);
+        assert_eq!(TOOLS.len(), 24);
     }
 
     #[test]
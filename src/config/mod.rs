@@ -10,6 +10,7 @@ pub mod validate;
 pub use types::*;
 
 use crate::error::{Result, ZeptoError};
+use crate::providers::ToolCallMode;
 use once_cell::sync::OnceCell;
 use std::io::IsTerminal;
 use std::path::PathBuf;
@@ -46,16 +47,24 @@ impl Config {
     /// transparently decrypted before the JSON is deserialized into `Config`.
     /// The master key is resolved via `ZEPTOCLAW_MASTER_KEY` env var or, when
     /// running in an interactive terminal, an interactive passphrase prompt.
+    ///
+    /// API keys stored with `zeptoclaw auth login --api-key` override keys
+    /// from the file: first those in the encrypted fallback store
+    /// (`auth/api_keys.json.enc` next to the config file), then those in the
+    /// OS keyring. Environment variables override all of them. If a store
+    /// cannot be opened, a warning is logged and the remaining keys are used.
     pub fn load_from_path(path: &PathBuf) -> Result<Self> {
+        let interactive = std::io::stdin().is_terminal();
+        let mut encryption = None;
         let mut config = if path.exists() {
             let content = std::fs::read_to_string(path)?;
             let mut raw: serde_json::Value = serde_json::from_str(&content)?;
 
             // Decrypt ENC[...] values if present
             if has_encrypted_values(&raw) {
-                let interactive = std::io::stdin().is_terminal();
                 let enc = crate::security::encryption::resolve_master_key(interactive)?;
                 decrypt_config_values(&mut raw, &enc)?;
+                encryption = Some(enc);
             }
 
             serde_json::from_value(raw)?
@@ -63,12 +72,49 @@ impl Config {
             Config::default()
         };
 
+        // Stored API keys take precedence over config.json, keyring keys last
+        let store_path = crate::auth::api_keys::ApiKeyStore::path_for_config(path);
+        if store_path.exists() {
+            let enc = match encryption {
+                Some(enc) => Ok(enc),
+                None => crate::security::encryption::resolve_master_key(interactive),
+            };
+            let applied = enc.and_then(|enc| {
+                let store = crate::auth::api_keys::ApiKeyStore::with_path(store_path, enc);
+                config.apply_stored_api_keys(&store.all()?);
+                Ok(())
+            });
+            if let Err(e) = applied {
+                tracing::warn!("Stored API keys not loaded, using config.json: {}", e);
+            }
+        }
+        let keyring = crate::auth::api_keys::KeyringApiKeys::for_config(path);
+        if keyring.exists() {
+            match keyring.all() {
+                Ok(keys) => config.apply_stored_api_keys(&keys),
+                Err(e) => tracing::warn!("Keyring API keys not loaded: {}", e),
+            }
+        }
+
         // Apply environment variable overrides
         config.apply_env_overrides();
 
         Ok(config)
     }
 
+    /// Set provider API keys from `keys` (provider name to key), replacing
+    /// keys from the config file. Unknown provider names are ignored.
+    pub fn apply_stored_api_keys(&mut self, keys: &std::collections::BTreeMap<String, String>) {
+        for (name, key) in keys {
+            if key.is_empty() {
+                continue;
+            }
+            if let Some(slot) = crate::providers::provider_config_slot_mut(self, name) {
+                slot.get_or_insert_with(ProviderConfig::default).api_key = Some(key.clone());
+            }
+        }
+    }
+
     /// Apply environment variable overrides to the configuration.
     ///
     /// Environment variables follow the pattern: ZEPTOCLAW_SECTION_SUBSECTION_KEY
@@ -115,6 +161,27 @@ impl Config {
         if let Ok(val) = std::env::var("ZEPTOCLAW_AGENTS_DEFAULTS_COMPACT_TOOLS") {
             self.agents.defaults.compact_tools = val == "true" || val == "1";
         }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_AGENTS_DEFAULTS_MAX_PARALLEL_TOOLS") {
+            if let Ok(v) = val.parse::<usize>() {
+                self.agents.defaults.max_parallel_tools = v;
+            }
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_AGENTS_DEFAULTS_TOOL_CACHE_SIZE") {
+            if let Ok(v) = val.parse::<usize>() {
+                self.agents.defaults.tool_cache_size = v;
+            }
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_AGENTS_DEFAULTS_PROMPT_CACHING") {
+            self.agents.defaults.prompt_caching = val == "true" || val == "1";
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_AGENTS_DEFAULTS_TOOL_CALL_MODE") {
+            match val.trim().to_ascii_lowercase().as_str() {
+                "native" => self.agents.defaults.tool_call_mode = ToolCallMode::Native,
+                "prompted" => self.agents.defaults.tool_call_mode = ToolCallMode::Prompted,
+                "auto" => self.agents.defaults.tool_call_mode = ToolCallMode::Auto,
+                _ => {}
+            }
+        }
         if let Ok(val) = std::env::var("ZEPTOCLAW_AGENTS_DEFAULTS_TOOL_PROFILE") {
             self.agents.defaults.tool_profile = if val.is_empty() { None } else { Some(val) };
         }
@@ -131,6 +198,31 @@ impl Config {
                 self.gateway.port = v;
             }
         }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_GATEWAY_MAX_CONCURRENT_AGENTS") {
+            if let Ok(v) = val.parse::<usize>() {
+                self.gateway.max_concurrent_agents = v.max(1);
+            }
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_GATEWAY_BUS_CAPACITY") {
+            if let Ok(v) = val.parse::<usize>() {
+                self.gateway.bus_capacity = v.max(1);
+            }
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_GATEWAY_METRICS_ADDR") {
+            self.gateway.metrics_addr = if val.is_empty() { None } else { Some(val) };
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_GATEWAY_OUTBOUND_DEDUP_SECS") {
+            if let Ok(v) = val.parse::<u64>() {
+                self.gateway.outbound_dedup_secs = v;
+            }
+        }
+
+        // Session store
+        if let Ok(val) = std::env::var("ZEPTOCLAW_SESSION_TTL_SECS") {
+            if let Ok(v) = val.parse::<u64>() {
+                self.session.ttl_secs = v;
+            }
+        }
 
         // Provider API keys
         self.apply_provider_env_overrides();
@@ -471,6 +563,10 @@ impl Config {
             }
         }
 
+        if let Ok(val) = std::env::var("ZEPTOCLAW_TOOLS_WEB_FETCH_RENDER_ENDPOINT") {
+            self.tools.web.fetch.render_endpoint = Some(val);
+        }
+
         // WhatsApp tool configuration
         if let Ok(val) = std::env::var("ZEPTOCLAW_TOOLS_WHATSAPP_PHONE_NUMBER_ID") {
             self.tools.whatsapp.phone_number_id = Some(val);
@@ -554,6 +650,18 @@ impl Config {
             }
         }
 
+        if let Ok(val) = std::env::var("ZEPTOCLAW_MEMORY_CHUNK_LINES") {
+            if let Ok(v) = val.parse::<u32>() {
+                self.memory.chunk_lines = v.clamp(1, 400);
+            }
+        }
+
+        if let Ok(val) = std::env::var("ZEPTOCLAW_MEMORY_CHUNK_OVERLAP") {
+            if let Ok(v) = val.parse::<u32>() {
+                self.memory.chunk_overlap = v;
+            }
+        }
+
         if let Ok(val) = std::env::var("ZEPTOCLAW_MEMORY_INCLUDE_DEFAULT_MEMORY") {
             if let Ok(v) = val.parse::<bool>() {
                 self.memory.include_default_memory = v;
@@ -590,6 +698,37 @@ impl Config {
             }
         }
 
+        if let Ok(val) = std::env::var("ZEPTOCLAW_HEARTBEAT_SCHEDULE") {
+            let val = val.trim();
+            self.heartbeat.schedule = if val.is_empty() {
+                None
+            } else {
+                Some(val.to_string())
+            };
+        }
+
+        if let Ok(val) = std::env::var("ZEPTOCLAW_HEARTBEAT_TIMEZONE") {
+            match val.trim().to_ascii_lowercase().as_str() {
+                "local" => self.heartbeat.timezone = ScheduleTimezone::Local,
+                "utc" => self.heartbeat.timezone = ScheduleTimezone::Utc,
+                _ => {}
+            }
+        }
+
+        if let Ok(val) = std::env::var("ZEPTOCLAW_HEARTBEAT_JITTER_SECS") {
+            if let Ok(v) = val.parse::<u64>() {
+                self.heartbeat.jitter_secs = v;
+            }
+        }
+
+        if let Ok(val) = std::env::var("ZEPTOCLAW_HEARTBEAT_CATCH_UP") {
+            match val.trim().to_ascii_lowercase().as_str() {
+                "skip" => self.heartbeat.catch_up = HeartbeatCatchUp::Skip,
+                "once" => self.heartbeat.catch_up = HeartbeatCatchUp::Once,
+                _ => {}
+            }
+        }
+
         if let Ok(val) = std::env::var("ZEPTOCLAW_HEARTBEAT_FILE_PATH") {
             if !val.trim().is_empty() {
                 self.heartbeat.file_path = Some(val);
@@ -667,6 +806,14 @@ impl Config {
                 self.compaction.threshold = v.clamp(0.1, 1.0);
             }
         }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_COMPACTION_SUMMARIZE") {
+            self.compaction.summarize = val.eq_ignore_ascii_case("true") || val == "1";
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_COMPACTION_KEEP_RECENT") {
+            if let Ok(v) = val.parse::<usize>() {
+                self.compaction.keep_recent = v.clamp(1, 1_000);
+            }
+        }
     }
 
     /// Apply routines environment variable overrides.
@@ -855,10 +1002,10 @@ fn decrypt_config_values(
     enc: &crate::security::encryption::SecretEncryption,
 ) -> Result<()> {
     match value {
-        serde_json::Value::String(s) => {
-            if crate::security::encryption::SecretEncryption::is_encrypted(s) {
-                *s = enc.decrypt(s)?;
-            }
+        serde_json::Value::String(s)
+            if crate::security::encryption::SecretEncryption::is_encrypted(s) =>
+        {
+            *s = enc.decrypt(s)?;
         }
         serde_json::Value::Object(map) => {
             for val in map.values_mut() {
@@ -912,6 +1059,8 @@ mod tests {
         assert_eq!(config.memory.citations, MemoryCitationsMode::Auto);
         assert_eq!(config.memory.max_results, 6);
         assert_eq!(config.memory.min_score, 0.2);
+        assert_eq!(config.memory.chunk_lines, 18);
+        assert_eq!(config.memory.chunk_overlap, 4);
         assert!(!config.heartbeat.enabled);
         assert_eq!(config.heartbeat.interval_secs, 30 * 60);
         assert!(config.skills.enabled);
@@ -1310,6 +1459,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_tool_call_mode_from_json() {
+        assert_eq!(
+            Config::default().agents.defaults.tool_call_mode,
+            ToolCallMode::Auto
+        );
+        let json = r#"{"agents": {"defaults": {"tool_call_mode": "prompted"}}}"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            config.agents.defaults.tool_call_mode,
+            ToolCallMode::Prompted
+        );
+    }
+
     #[test]
     fn test_env_override_compact_tools() {
         std::env::set_var("ZEPTOCLAW_AGENTS_DEFAULTS_COMPACT_TOOLS", "true");
@@ -1318,4 +1481,71 @@ mod tests {
         assert!(config.agents.defaults.compact_tools);
         std::env::remove_var("ZEPTOCLAW_AGENTS_DEFAULTS_COMPACT_TOOLS");
     }
+
+    #[test]
+    fn test_stored_api_keys_override_config_file() {
+        let json = r#"{"providers": {
+            "openai": {"api_key": "file-key", "api_base": "https://proxy.example/v1"},
+            "groq": {"api_key": "file-groq"}
+        }}"#;
+        let mut config: Config = serde_json::from_str(json).unwrap();
+        let stored = std::collections::BTreeMap::from([
+            ("openai".to_string(), "stored-openai".to_string()),
+            ("anthropic".to_string(), "stored-anthropic".to_string()),
+            ("not-a-provider".to_string(), "ignored".to_string()),
+        ]);
+        config.apply_stored_api_keys(&stored);
+
+        let openai = config.providers.openai.as_ref().unwrap();
+        assert_eq!(openai.api_key.as_deref(), Some("stored-openai"));
+        assert_eq!(openai.api_base.as_deref(), Some("https://proxy.example/v1"));
+        assert_eq!(
+            config
+                .providers
+                .anthropic
+                .as_ref()
+                .unwrap()
+                .api_key
+                .as_deref(),
+            Some("stored-anthropic")
+        );
+        assert_eq!(
+            config.providers.groq.as_ref().unwrap().api_key.as_deref(),
+            Some("file-groq")
+        );
+    }
+
+    #[test]
+    fn test_load_precedence_env_over_stored_over_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join("config.json");
+        std::fs::write(
+            &config_path,
+            r#"{"providers": {
+                "zhipu": {"api_key": "file-zhipu"},
+                "nvidia": {"api_key": "file-nvidia"},
+                "gemini": {"api_key": "file-gemini"}
+            }}"#,
+        )
+        .unwrap();
+
+        let key = [0x5au8; 32];
+        let store = crate::auth::api_keys::ApiKeyStore::with_path(
+            crate::auth::api_keys::ApiKeyStore::path_for_config(&config_path),
+            crate::security::encryption::SecretEncryption::from_raw_key(&key),
+        );
+        store.set("zhipu", "stored-zhipu").unwrap();
+        store.set("nvidia", "stored-nvidia").unwrap();
+
+        std::env::set_var("ZEPTOCLAW_MASTER_KEY", hex::encode(key));
+        std::env::set_var("ZEPTOCLAW_PROVIDERS_NVIDIA_API_KEY", "env-nvidia");
+        let config = Config::load_from_path(&config_path).unwrap();
+        std::env::remove_var("ZEPTOCLAW_PROVIDERS_NVIDIA_API_KEY");
+        std::env::remove_var("ZEPTOCLAW_MASTER_KEY");
+
+        let key_of = |p: &Option<ProviderConfig>| p.as_ref().unwrap().api_key.clone().unwrap();
+        assert_eq!(key_of(&config.providers.zhipu), "stored-zhipu");
+        assert_eq!(key_of(&config.providers.nvidia), "env-nvidia");
+        assert_eq!(key_of(&config.providers.gemini), "file-gemini");
+    }
 }
@@ -7,8 +7,11 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt;
 use std::path::Path;
 
+use crate::config::validate::suggest_field;
+use crate::config::AgentDefaults;
 use crate::error::{Result, ZeptoError};
 
 // ============================================================================
@@ -26,11 +29,25 @@ pub struct AgentTemplate {
     pub name: String,
 
     /// Human-readable description of this template's purpose.
+    /// Inherited from the parent when empty.
+    #[serde(default)]
     pub description: String,
 
     /// The system prompt injected for this agent role.
+    ///
+    /// For a template that `extends` another, this is appended to the
+    /// parent's prompt; leave it empty to inherit the parent's prompt as is.
+    #[serde(default)]
     pub system_prompt: String,
 
+    /// Optional name of a template this one inherits from.
+    ///
+    /// Fields left unset here are taken from the parent; tool lists and tags
+    /// are merged. Resolved by [`TemplateRegistry`] once all templates are
+    /// registered.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extends: Option<String>,
+
     /// Optional model override (e.g., "claude-sonnet-4-5-20250929").
     /// When `None`, the agent uses the default model from config.
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -58,11 +75,220 @@ pub struct AgentTemplate {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub max_tool_iterations: Option<u32>,
 
+    /// Optional stop sequences that halt generation (e.g., to keep a persona terse).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stop_sequences: Option<Vec<String>>,
+
+    /// Values for `{{key}}` placeholders in `system_prompt`.
+    ///
+    /// Merged over the built-in variables (see [`builtin_prompt_vars`]) when
+    /// the prompt is rendered, so a template can also override those.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub vars: HashMap<String, String>,
+
     /// Metadata tags for categorization and filtering.
     #[serde(default)]
     pub tags: Vec<String>,
 }
 
+impl AgentTemplate {
+    /// Layer this template over its resolved `parent`.
+    ///
+    /// Fields set on `self` win; unset ones are inherited. A non-empty system
+    /// prompt is appended to the parent's, while tool lists and tags are
+    /// merged without duplicates.
+    fn layer_over(&self, parent: &AgentTemplate) -> AgentTemplate {
+        let system_prompt = match (
+            parent.system_prompt.trim().is_empty(),
+            self.system_prompt.trim().is_empty(),
+        ) {
+            (_, true) => parent.system_prompt.clone(),
+            (true, false) => self.system_prompt.clone(),
+            (false, false) => format!("{}\n\n{}", parent.system_prompt, self.system_prompt),
+        };
+
+        AgentTemplate {
+            name: self.name.clone(),
+            description: if self.description.is_empty() {
+                parent.description.clone()
+            } else {
+                self.description.clone()
+            },
+            system_prompt,
+            extends: self.extends.clone(),
+            model: self.model.clone().or_else(|| parent.model.clone()),
+            max_tokens: self.max_tokens.or(parent.max_tokens),
+            temperature: self.temperature.or(parent.temperature),
+            allowed_tools: merge_optional_lists(&parent.allowed_tools, &self.allowed_tools),
+            blocked_tools: merge_optional_lists(&parent.blocked_tools, &self.blocked_tools),
+            max_tool_iterations: self.max_tool_iterations.or(parent.max_tool_iterations),
+            stop_sequences: self
+                .stop_sequences
+                .clone()
+                .or_else(|| parent.stop_sequences.clone()),
+            vars: parent
+                .vars
+                .iter()
+                .chain(&self.vars)
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+            tags: merge_lists(&parent.tags, &self.tags),
+        }
+    }
+
+    /// Render `system_prompt`, replacing `{{key}}` placeholders with `vars`.
+    ///
+    /// Placeholders without a value are left intact, as is any other brace
+    /// text (JSON snippets, `{single}` braces).
+    pub fn render_system_prompt(&self, vars: &HashMap<String, String>) -> String {
+        render_prompt(&self.system_prompt, vars)
+    }
+
+    /// Like [`render_system_prompt`](Self::render_system_prompt), but fails
+    /// if any placeholder has no value.
+    pub fn try_render_system_prompt(&self, vars: &HashMap<String, String>) -> Result<String> {
+        let mut missing = Vec::new();
+        let rendered = substitute(&self.system_prompt, vars, &mut missing);
+        if missing.is_empty() {
+            return Ok(rendered);
+        }
+        Err(ZeptoError::Config(format!(
+            "Template '{}' system prompt has no value for: {}",
+            self.name,
+            missing.join(", ")
+        )))
+    }
+
+    /// Apply this template's model and generation overrides to agent defaults.
+    ///
+    /// Fields left as `None` on the template keep the configured value.
+    pub fn apply_to(&self, defaults: &mut AgentDefaults) {
+        if let Some(model) = &self.model {
+            defaults.model = model.clone();
+        }
+        if let Some(max_tokens) = self.max_tokens {
+            defaults.max_tokens = max_tokens;
+        }
+        if let Some(temperature) = self.temperature {
+            defaults.temperature = temperature;
+        }
+        if let Some(max_tool_iterations) = self.max_tool_iterations {
+            defaults.max_tool_iterations = max_tool_iterations;
+        }
+        if let Some(stop_sequences) = &self.stop_sequences {
+            defaults.stop_sequences = Some(stop_sequences.clone());
+        }
+    }
+
+    /// Returns whether this template permits the named tool.
+    ///
+    /// A tool is permitted when it appears in `allowed_tools` (or no whitelist
+    /// is set) and does not appear in `blocked_tools`. Matching is
+    /// case-insensitive.
+    pub fn allows_tool(&self, name: &str) -> bool {
+        let matches = |names: &Vec<String>| names.iter().any(|n| n.eq_ignore_ascii_case(name));
+        if let Some(allowed) = &self.allowed_tools {
+            if !matches(allowed) {
+                return false;
+            }
+        }
+        !self.blocked_tools.as_ref().is_some_and(matches)
+    }
+}
+
+// ============================================================================
+// Prompt variables
+// ============================================================================
+
+/// Built-in prompt variables: `date` (local, `YYYY-MM-DD`) and, when given,
+/// `workspace`.
+///
+/// Computed at call time, so callers should build these per turn.
+pub fn builtin_prompt_vars(workspace: Option<&Path>) -> HashMap<String, String> {
+    let mut vars = HashMap::new();
+    vars.insert(
+        "date".to_string(),
+        chrono::Local::now().format("%Y-%m-%d").to_string(),
+    );
+    if let Some(workspace) = workspace {
+        vars.insert("workspace".to_string(), workspace.display().to_string());
+    }
+    vars
+}
+
+/// Replace `{{key}}` placeholders in `text` with values from `vars`.
+///
+/// Keys may be padded with spaces (`{{ key }}`) and consist of ASCII
+/// letters, digits, `_`, `-` and `.`. Unknown keys and anything that is not a
+/// placeholder are left untouched.
+pub fn render_prompt(text: &str, vars: &HashMap<String, String>) -> String {
+    substitute(text, vars, &mut Vec::new())
+}
+
+/// Substitute placeholders, collecting keys that have no value in `missing`.
+fn substitute(text: &str, vars: &HashMap<String, String>, missing: &mut Vec<String>) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let key = after
+            .find("}}")
+            .map(|end| (end, after[..end].trim()))
+            .filter(|(_, key)| is_prompt_var_key(key));
+        match key {
+            Some((end, key)) => {
+                match vars.get(key) {
+                    Some(value) => out.push_str(value),
+                    None => {
+                        if !missing.iter().any(|k| k == key) {
+                            missing.push(key.to_string());
+                        }
+                        out.push_str(&rest[start..start + 2 + end + 2]);
+                    }
+                }
+                rest = &after[end + 2..];
+            }
+            None => {
+                out.push_str("{{");
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn is_prompt_var_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+/// Concatenate two lists, keeping the first occurrence of each entry.
+fn merge_lists(parent: &[String], child: &[String]) -> Vec<String> {
+    let mut merged: Vec<String> = Vec::with_capacity(parent.len() + child.len());
+    for item in parent.iter().chain(child) {
+        if !merged.contains(item) {
+            merged.push(item.clone());
+        }
+    }
+    merged
+}
+
+/// Merge optional lists: `None` on either side yields the other side.
+fn merge_optional_lists(
+    parent: &Option<Vec<String>>,
+    child: &Option<Vec<String>>,
+) -> Option<Vec<String>> {
+    match (parent, child) {
+        (Some(parent), Some(child)) => Some(merge_lists(parent, child)),
+        (parent, None) => parent.clone(),
+        (None, child) => child.clone(),
+    }
+}
+
 // ============================================================================
 // Built-in Templates
 // ============================================================================
@@ -82,12 +308,15 @@ fn builtin_coder() -> AgentTemplate {
             "simple, readable solutions over clever ones."
         )
         .to_string(),
+        extends: None,
         model: None,
         max_tokens: None,
         temperature: None,
         allowed_tools: None, // all tools
         blocked_tools: None,
         max_tool_iterations: None,
+        stop_sequences: None,
+        vars: HashMap::new(),
         tags: vec!["development".to_string(), "coding".to_string()],
     }
 }
@@ -107,6 +336,7 @@ fn builtin_researcher() -> AgentTemplate {
             "and present multiple perspectives when they exist."
         )
         .to_string(),
+        extends: None,
         model: None,
         max_tokens: None,
         temperature: None,
@@ -119,6 +349,8 @@ fn builtin_researcher() -> AgentTemplate {
         ]),
         blocked_tools: None,
         max_tool_iterations: None,
+        stop_sequences: None,
+        vars: HashMap::new(),
         tags: vec!["research".to_string(), "information".to_string()],
     }
 }
@@ -138,6 +370,7 @@ fn builtin_writer() -> AgentTemplate {
             "You organize ideas logically and use examples to illustrate points."
         )
         .to_string(),
+        extends: None,
         model: None,
         max_tokens: None,
         temperature: None,
@@ -150,6 +383,8 @@ fn builtin_writer() -> AgentTemplate {
         ]),
         blocked_tools: None,
         max_tool_iterations: None,
+        stop_sequences: None,
+        vars: HashMap::new(),
         tags: vec!["writing".to_string(), "content".to_string()],
     }
 }
@@ -168,12 +403,15 @@ fn builtin_assistant() -> AgentTemplate {
             "complete responses."
         )
         .to_string(),
+        extends: None,
         model: None,
         max_tokens: None,
         temperature: None,
         allowed_tools: None, // all tools
         blocked_tools: None,
         max_tool_iterations: None,
+        stop_sequences: None,
+        vars: HashMap::new(),
         tags: vec!["general".to_string()],
     }
 }
@@ -201,6 +439,7 @@ fn builtin_task_manager() -> AgentTemplate {
             "Always confirm actions taken (e.g., 'Added reminder: Call dentist at 2pm').",
         )
         .to_string(),
+        extends: None,
         model: None,
         max_tokens: None,
         temperature: None,
@@ -212,6 +451,8 @@ fn builtin_task_manager() -> AgentTemplate {
         ]),
         blocked_tools: None,
         max_tool_iterations: None,
+        stop_sequences: None,
+        vars: HashMap::new(),
         tags: vec![
             "productivity".to_string(),
             "tasks".to_string(),
@@ -231,6 +472,76 @@ fn builtin_templates() -> Vec<AgentTemplate> {
     ]
 }
 
+// ============================================================================
+// TemplateWarning
+// ============================================================================
+
+/// A problem found by [`TemplateRegistry::validate_against_tools`].
+///
+/// Warnings never prevent a template from loading; they point at tool
+/// policies that will not behave as the author likely intended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateWarning {
+    /// `allowed_tools` or `blocked_tools` names a tool that is not registered.
+    UnknownTool {
+        /// Template containing the reference.
+        template: String,
+        /// Field the reference is in (`allowed_tools` or `blocked_tools`).
+        field: &'static str,
+        /// The unknown tool name as written.
+        tool: String,
+        /// "did you mean" hint for a close registered name, if any.
+        suggestion: Option<String>,
+    },
+    /// A tool is listed in both `allowed_tools` and `blocked_tools`, so it is
+    /// never available.
+    AllowedAndBlocked {
+        /// Template containing the overlap.
+        template: String,
+        /// The tool listed in both fields.
+        tool: String,
+    },
+}
+
+impl TemplateWarning {
+    /// Name of the template the warning is about.
+    pub fn template(&self) -> &str {
+        match self {
+            Self::UnknownTool { template, .. } | Self::AllowedAndBlocked { template, .. } => {
+                template
+            }
+        }
+    }
+}
+
+impl fmt::Display for TemplateWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownTool {
+                template,
+                field,
+                tool,
+                suggestion,
+            } => {
+                write!(
+                    f,
+                    "template '{}': {} references unknown tool '{}'",
+                    template, field, tool
+                )?;
+                if let Some(suggestion) = suggestion {
+                    write!(f, " ({})", suggestion)?;
+                }
+                Ok(())
+            }
+            Self::AllowedAndBlocked { template, tool } => write!(
+                f,
+                "template '{}': tool '{}' is in both allowed_tools and blocked_tools and will never be available",
+                template, tool
+            ),
+        }
+    }
+}
+
 // ============================================================================
 // TemplateRegistry
 // ============================================================================
@@ -240,24 +551,57 @@ fn builtin_templates() -> Vec<AgentTemplate> {
 /// Templates are stored in a `HashMap` keyed by name. User-defined templates
 /// loaded from `~/.zeptoclaw/templates/` can override built-in templates with
 /// the same name.
+///
+/// Templates are kept as registered and re-resolved after every change, so
+/// lookups always return templates with their `extends` chain applied.
 #[derive(Debug, Clone)]
 pub struct TemplateRegistry {
+    /// Templates as registered, before inheritance is applied.
+    definitions: HashMap<String, AgentTemplate>,
+    /// Templates with inheritance applied, returned by lookups.
     templates: HashMap<String, AgentTemplate>,
 }
 
 impl TemplateRegistry {
     /// Creates a new registry pre-loaded with all built-in templates.
     pub fn new() -> Self {
+        Self::with_disabled(&[])
+    }
+
+    /// Creates a registry pre-loaded with the built-in templates, except those
+    /// named in `disabled`.
+    ///
+    /// Only built-ins are affected; user-defined templates merged later are
+    /// still registered even if they share a disabled name.
+    pub fn with_disabled(disabled: &[String]) -> Self {
         let mut templates = HashMap::new();
         for tpl in builtin_templates() {
+            if disabled.iter().any(|name| name == &tpl.name) {
+                continue;
+            }
             templates.insert(tpl.name.clone(), tpl);
         }
-        Self { templates }
+        Self {
+            definitions: templates.clone(),
+            templates,
+        }
     }
 
     /// Registers a template, overriding any existing template with the same name.
-    pub fn register(&mut self, template: AgentTemplate) {
-        self.templates.insert(template.name.clone(), template);
+    ///
+    /// Returns `Err` (and leaves the registry unchanged) if the template's
+    /// `extends` chain names an unknown template or forms a cycle.
+    pub fn register(&mut self, template: AgentTemplate) -> Result<()> {
+        let name = template.name.clone();
+        let previous = self.definitions.insert(name.clone(), template);
+        if let Err(e) = self.resolve_all() {
+            match previous {
+                Some(previous) => self.definitions.insert(name, previous),
+                None => self.definitions.remove(&name),
+            };
+            return Err(e);
+        }
+        Ok(())
     }
 
     /// Looks up a template by name.
@@ -283,6 +627,55 @@ impl TemplateRegistry {
         self.templates.keys().map(|k| k.as_str()).collect()
     }
 
+    /// Checks every template's tool policy against the registered tool names.
+    ///
+    /// Reports `allowed_tools`/`blocked_tools` entries that match no entry in
+    /// `known_tools` (compared case-insensitively, like
+    /// [`AgentTemplate::allows_tool`]) and tools listed as both allowed and
+    /// blocked. Warnings are ordered by template name.
+    pub fn validate_against_tools(&self, known_tools: &[String]) -> Vec<TemplateWarning> {
+        let known_lower: Vec<String> = known_tools.iter().map(|t| t.to_ascii_lowercase()).collect();
+        let known_refs: Vec<&str> = known_lower.iter().map(String::as_str).collect();
+
+        let mut templates: Vec<&AgentTemplate> = self.templates.values().collect();
+        templates.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let mut warnings = Vec::new();
+        for template in templates {
+            let lists = [
+                ("allowed_tools", &template.allowed_tools),
+                ("blocked_tools", &template.blocked_tools),
+            ];
+            for (field, tools) in lists {
+                for tool in tools.iter().flatten() {
+                    let lower = tool.to_ascii_lowercase();
+                    if !known_refs.contains(&lower.as_str()) {
+                        warnings.push(TemplateWarning::UnknownTool {
+                            template: template.name.clone(),
+                            field,
+                            tool: tool.clone(),
+                            suggestion: suggest_field(&lower, &known_refs),
+                        });
+                    }
+                }
+            }
+
+            if let (Some(allowed), Some(blocked)) =
+                (&template.allowed_tools, &template.blocked_tools)
+            {
+                for tool in allowed {
+                    if blocked.iter().any(|b| b.eq_ignore_ascii_case(tool)) {
+                        warnings.push(TemplateWarning::AllowedAndBlocked {
+                            template: template.name.clone(),
+                            tool: tool.clone(),
+                        });
+                    }
+                }
+            }
+        }
+        warnings
+    }
+
     /// Loads all `.json` template files from a directory.
     ///
     /// Returns the successfully parsed templates. Files that are not valid JSON
@@ -345,14 +738,78 @@ impl TemplateRegistry {
     ///
     /// Returns the number of templates successfully loaded and registered.
     /// User-defined templates override any existing templates with the same name.
+    ///
+    /// Inheritance is resolved after every template in the directory has been
+    /// registered, so a template may extend another one from the same
+    /// directory. Returns `Err` (and leaves the registry unchanged) if any
+    /// `extends` chain is broken or cyclic.
     pub fn merge_from_dir(&mut self, dir: &Path) -> Result<usize> {
         let templates = Self::load_from_dir(dir)?;
         let count = templates.len();
+        let previous = self.definitions.clone();
         for template in templates {
-            self.register(template);
+            self.definitions.insert(template.name.clone(), template);
+        }
+        if let Err(e) = self.resolve_all() {
+            self.definitions = previous;
+            return Err(e);
         }
         Ok(count)
     }
+
+    /// Rebuilds the resolved templates from the registered definitions.
+    ///
+    /// Only replaces the resolved set if every template resolves.
+    fn resolve_all(&mut self) -> Result<()> {
+        let mut resolved = HashMap::with_capacity(self.definitions.len());
+        for name in self.definitions.keys() {
+            self.resolve(name, &mut Vec::new(), &mut resolved)?;
+        }
+        self.templates = resolved;
+        Ok(())
+    }
+
+    /// Resolves one template's `extends` chain, memoizing into `resolved`.
+    ///
+    /// `chain` holds the names currently being resolved and is used to detect
+    /// inheritance cycles.
+    fn resolve(
+        &self,
+        name: &str,
+        chain: &mut Vec<String>,
+        resolved: &mut HashMap<String, AgentTemplate>,
+    ) -> Result<AgentTemplate> {
+        if let Some(template) = resolved.get(name) {
+            return Ok(template.clone());
+        }
+        if chain.iter().any(|n| n == name) {
+            chain.push(name.to_string());
+            return Err(ZeptoError::Config(format!(
+                "Template inheritance cycle: {}",
+                chain.join(" -> ")
+            )));
+        }
+
+        let Some(definition) = self.definitions.get(name) else {
+            let child = chain.last().map(String::as_str).unwrap_or(name);
+            return Err(ZeptoError::Config(format!(
+                "Template '{}' extends unknown template '{}'",
+                child, name
+            )));
+        };
+
+        let template = match &definition.extends {
+            Some(parent_name) => {
+                chain.push(name.to_string());
+                let parent = self.resolve(parent_name, chain, resolved)?;
+                chain.pop();
+                definition.layer_over(&parent)
+            }
+            None => definition.clone(),
+        };
+        resolved.insert(name.to_string(), template.clone());
+        Ok(template)
+    }
 }
 
 impl Default for TemplateRegistry {
@@ -497,16 +954,19 @@ mod tests {
             name: "devops".to_string(),
             description: "DevOps engineer".to_string(),
             system_prompt: "You are a DevOps engineer.".to_string(),
+            extends: None,
             model: Some("gpt-5.1".to_string()),
             max_tokens: Some(4096),
             temperature: Some(0.3),
             allowed_tools: Some(vec!["shell".to_string()]),
             blocked_tools: None,
             max_tool_iterations: Some(10),
+            stop_sequences: None,
+            vars: HashMap::new(),
             tags: vec!["devops".to_string(), "infrastructure".to_string()],
         };
 
-        registry.register(custom);
+        registry.register(custom).unwrap();
         assert_eq!(registry.list().len(), 6);
 
         let devops = registry.get("devops").unwrap();
@@ -529,15 +989,18 @@ mod tests {
             name: "coder".to_string(),
             description: "Custom coder".to_string(),
             system_prompt: "You are a Rust expert.".to_string(),
+            extends: None,
             model: Some("claude-sonnet-4-5-20250929".to_string()),
             max_tokens: None,
             temperature: None,
             allowed_tools: Some(vec!["shell".to_string(), "read_file".to_string()]),
             blocked_tools: None,
             max_tool_iterations: None,
+            stop_sequences: None,
+            vars: HashMap::new(),
             tags: vec!["development".to_string(), "rust".to_string()],
         };
-        registry.register(custom_coder);
+        registry.register(custom_coder).unwrap();
 
         // Verify override
         let overridden = registry.get("coder").unwrap();
@@ -552,6 +1015,25 @@ mod tests {
         assert_eq!(registry.list().len(), 5);
     }
 
+    #[test]
+    fn test_disabled_builtin_excluded() {
+        let registry = TemplateRegistry::with_disabled(&["assistant".to_string()]);
+
+        assert!(registry.get("assistant").is_none());
+        assert!(registry.list().iter().all(|t| t.name != "assistant"));
+        assert_eq!(registry.list().len(), 4);
+        assert!(registry.get("coder").is_some());
+        assert!(registry.get("researcher").is_some());
+        assert!(registry.get("writer").is_some());
+        assert!(registry.get("task-manager").is_some());
+    }
+
+    #[test]
+    fn test_disabled_unknown_name_is_ignored() {
+        let registry = TemplateRegistry::with_disabled(&["no-such-template".to_string()]);
+        assert_eq!(registry.list().len(), 5);
+    }
+
     #[test]
     fn test_names_list() {
         let registry = TemplateRegistry::new();
@@ -658,12 +1140,15 @@ mod tests {
             name: "roundtrip".to_string(),
             description: "Roundtrip test".to_string(),
             system_prompt: "Test prompt.".to_string(),
+            extends: None,
             model: Some("gpt-5.1".to_string()),
             max_tokens: Some(2048),
             temperature: Some(0.5),
             allowed_tools: Some(vec!["shell".to_string(), "read_file".to_string()]),
             blocked_tools: Some(vec!["web_search".to_string()]),
             max_tool_iterations: Some(15),
+            stop_sequences: Some(vec!["END".to_string()]),
+            vars: HashMap::new(),
             tags: vec!["test".to_string()],
         };
 
@@ -685,6 +1170,7 @@ mod tests {
             Some(vec!["web_search".to_string()])
         );
         assert_eq!(deserialized.max_tool_iterations, Some(15));
+        assert_eq!(deserialized.stop_sequences, Some(vec!["END".to_string()]));
         assert_eq!(deserialized.tags, vec!["test".to_string()]);
     }
 
@@ -802,12 +1288,15 @@ mod tests {
             name: "sparse".to_string(),
             description: "Sparse template".to_string(),
             system_prompt: "Hello.".to_string(),
+            extends: None,
             model: None,
             max_tokens: None,
             temperature: None,
             allowed_tools: None,
             blocked_tools: None,
             max_tool_iterations: None,
+            stop_sequences: None,
+            vars: HashMap::new(),
             tags: vec![],
         };
 
@@ -818,6 +1307,22 @@ mod tests {
         assert!(!json.contains("allowed_tools"));
         assert!(!json.contains("blocked_tools"));
         assert!(!json.contains("max_tool_iterations"));
+        assert!(!json.contains("stop_sequences"));
+    }
+
+    #[test]
+    fn test_allows_tool_whitelist_and_blacklist() {
+        let registry = TemplateRegistry::new();
+        let researcher = registry.get("researcher").unwrap();
+        assert!(researcher.allows_tool("web_search"));
+        assert!(researcher.allows_tool("WEB_SEARCH"));
+        assert!(!researcher.allows_tool("shell"));
+
+        let mut coder = registry.get("coder").unwrap().clone();
+        assert!(coder.allows_tool("shell"));
+        coder.blocked_tools = Some(vec!["Shell".to_string()]);
+        assert!(!coder.allows_tool("shell"));
+        assert!(coder.allows_tool("read_file"));
     }
 
     #[test]
@@ -833,4 +1338,301 @@ mod tests {
 
         fs::remove_file(&temp_file).ok();
     }
+
+    fn child_template(name: &str, extends: &str) -> AgentTemplate {
+        serde_json::from_value(serde_json::json!({
+            "name": name,
+            "extends": extends,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_extends_layers_child_over_parent() {
+        let mut registry = TemplateRegistry::new();
+        let child: AgentTemplate = serde_json::from_value(serde_json::json!({
+            "name": "rust-coder",
+            "extends": "coder",
+            "system_prompt": "You specialise in Rust.",
+            "temperature": 0.2,
+            "blocked_tools": ["shell"],
+            "tags": ["rust", "coding"]
+        }))
+        .unwrap();
+        registry.register(child).unwrap();
+
+        let coder = registry.get("coder").unwrap().clone();
+        let resolved = registry.get("rust-coder").unwrap();
+        assert_eq!(resolved.description, coder.description);
+        assert!(resolved.system_prompt.starts_with(&coder.system_prompt));
+        assert!(resolved
+            .system_prompt
+            .ends_with("\n\nYou specialise in Rust."));
+        assert_eq!(resolved.temperature, Some(0.2));
+        assert_eq!(resolved.max_tokens, coder.max_tokens);
+        assert!(resolved.allowed_tools.is_none());
+        assert_eq!(resolved.blocked_tools, Some(vec!["shell".to_string()]));
+        assert_eq!(resolved.tags, vec!["development", "coding", "rust"]);
+        assert_eq!(resolved.extends.as_deref(), Some("coder"));
+    }
+
+    #[test]
+    fn test_extends_merges_tool_lists_and_inherits_prompt() {
+        let mut registry = TemplateRegistry::new();
+        let mut child = child_template("deep-researcher", "researcher");
+        child.allowed_tools = Some(vec!["web_fetch".to_string(), "shell".to_string()]);
+        registry.register(child).unwrap();
+
+        let researcher = registry.get("researcher").unwrap().clone();
+        let resolved = registry.get("deep-researcher").unwrap();
+        assert_eq!(resolved.system_prompt, researcher.system_prompt);
+
+        let mut expected = researcher.allowed_tools.clone().unwrap();
+        expected.push("shell".to_string());
+        assert_eq!(resolved.allowed_tools, Some(expected));
+    }
+
+    #[test]
+    fn test_extends_chain_in_same_directory() {
+        let temp_dir = std::env::temp_dir().join("zeptoclaw_test_templates_extends");
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir_all(&temp_dir).unwrap();
+
+        // "b" extends "a", which is only loaded from the same directory.
+        fs::write(
+            temp_dir.join("b.json"),
+            r#"{"name": "b", "extends": "a", "model": "model-b"}"#,
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.join("a.json"),
+            r#"{"name": "a", "extends": "writer", "description": "A", "max_tokens": 100}"#,
+        )
+        .unwrap();
+
+        let mut registry = TemplateRegistry::new();
+        assert_eq!(registry.merge_from_dir(&temp_dir).unwrap(), 2);
+
+        let b = registry.get("b").unwrap();
+        assert_eq!(b.description, "A");
+        assert_eq!(b.max_tokens, Some(100));
+        assert_eq!(b.model.as_deref(), Some("model-b"));
+        assert_eq!(
+            b.system_prompt,
+            registry.get("writer").unwrap().system_prompt
+        );
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn test_extends_cycle_rejected() {
+        let mut registry = TemplateRegistry::new();
+        let err = registry
+            .register(child_template("loop", "loop"))
+            .unwrap_err();
+        assert!(matches!(err, ZeptoError::Config(_)));
+        assert!(err.to_string().contains("cycle: loop -> loop"));
+        assert!(registry.get("loop").is_none());
+
+        let temp_dir = std::env::temp_dir().join("zeptoclaw_test_templates_cycle");
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir_all(&temp_dir).unwrap();
+        fs::write(temp_dir.join("x.json"), r#"{"name": "x", "extends": "y"}"#).unwrap();
+        fs::write(temp_dir.join("y.json"), r#"{"name": "y", "extends": "x"}"#).unwrap();
+
+        let err = registry.merge_from_dir(&temp_dir).unwrap_err();
+        assert!(err.to_string().contains("inheritance cycle"));
+        assert!(registry.get("x").is_none());
+        assert_eq!(registry.list().len(), 5);
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn test_extends_unknown_parent_rejected() {
+        let mut registry = TemplateRegistry::with_disabled(&["coder".to_string()]);
+        let err = registry
+            .register(child_template("child", "coder"))
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Template 'child' extends unknown template 'coder'"));
+        assert!(registry.get("child").is_none());
+    }
+
+    #[test]
+    fn test_overriding_parent_re_resolves_children() {
+        let mut registry = TemplateRegistry::new();
+        registry
+            .register(child_template("my-assistant", "assistant"))
+            .unwrap();
+
+        let mut assistant = registry.get("assistant").unwrap().clone();
+        assistant.model = Some("custom-model".to_string());
+        registry.register(assistant).unwrap();
+
+        assert_eq!(
+            registry.get("my-assistant").unwrap().model.as_deref(),
+            Some("custom-model")
+        );
+    }
+
+    fn prompt_template(prompt: &str) -> AgentTemplate {
+        let mut template = TemplateRegistry::new().get("assistant").unwrap().clone();
+        template.system_prompt = prompt.to_string();
+        template
+    }
+
+    fn vars(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_render_system_prompt_substitutes_vars() {
+        let template = prompt_template("Hi {{user_name}}, today is {{ date }}. {{user_name}}!");
+        let rendered =
+            template.render_system_prompt(&vars(&[("user_name", "Ana"), ("date", "2026-01-02")]));
+        assert_eq!(rendered, "Hi Ana, today is 2026-01-02. Ana!");
+    }
+
+    #[test]
+    fn test_render_system_prompt_leaves_missing_keys() {
+        let template = prompt_template("Hello {{user_name}} in {{workspace}}");
+        let rendered = template.render_system_prompt(&vars(&[("workspace", "/w")]));
+        assert_eq!(rendered, "Hello {{user_name}} in /w");
+
+        let err = template
+            .try_render_system_prompt(&HashMap::new())
+            .unwrap_err();
+        assert!(matches!(err, ZeptoError::Config(_)));
+        assert!(err
+            .to_string()
+            .contains("has no value for: user_name, workspace"));
+        assert_eq!(
+            template
+                .try_render_system_prompt(&vars(&[("user_name", "a"), ("workspace", "b")]))
+                .unwrap(),
+            "Hello a in b"
+        );
+    }
+
+    #[test]
+    fn test_render_system_prompt_keeps_literal_braces() {
+        let prompt =
+            r#"Reply as {"ok": true}, use {single}, {{ }}, {{not a key}}, {{{x}} and {{open"#;
+        let template = prompt_template(prompt);
+        let rendered = template.render_system_prompt(&vars(&[("x", "X"), ("single", "S")]));
+        assert_eq!(
+            rendered,
+            r#"Reply as {"ok": true}, use {single}, {{ }}, {{not a key}}, {{{x}} and {{open"#
+        );
+        assert!(template
+            .try_render_system_prompt(&vars(&[("x", "X")]))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_builtin_prompt_vars() {
+        let vars = builtin_prompt_vars(Some(Path::new("/tmp/ws")));
+        assert_eq!(vars["workspace"], "/tmp/ws");
+        assert_eq!(
+            vars["date"],
+            chrono::Local::now().format("%Y-%m-%d").to_string()
+        );
+        assert!(!builtin_prompt_vars(None).contains_key("workspace"));
+    }
+
+    #[test]
+    fn test_extends_merges_vars() {
+        let mut registry = TemplateRegistry::new();
+        let parent: AgentTemplate = serde_json::from_value(serde_json::json!({
+            "name": "base",
+            "system_prompt": "Base",
+            "vars": {"user_name": "Ana", "team": "core"}
+        }))
+        .unwrap();
+        registry.register(parent).unwrap();
+        let mut child = child_template("derived", "base");
+        child.vars = vars(&[("team", "infra")]);
+        registry.register(child).unwrap();
+
+        let derived = registry.get("derived").unwrap();
+        assert_eq!(
+            derived.vars,
+            vars(&[("user_name", "Ana"), ("team", "infra")])
+        );
+    }
+
+    fn tool_names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn test_validate_against_tools_reports_unknown_and_overlap() {
+        let mut registry = TemplateRegistry::with_disabled(
+            &builtin_templates()
+                .into_iter()
+                .map(|t| t.name)
+                .collect::<Vec<_>>(),
+        );
+        let template: AgentTemplate = serde_json::from_value(serde_json::json!({
+            "name": "ops",
+            "system_prompt": "Ops",
+            "allowed_tools": ["Shell", "web_serch", "read_file"],
+            "blocked_tools": ["read_file", "nonexistent_tool_xyz"]
+        }))
+        .unwrap();
+        registry.register(template).unwrap();
+
+        let warnings =
+            registry.validate_against_tools(&tool_names(&["shell", "web_search", "read_file"]));
+        assert_eq!(
+            warnings,
+            vec![
+                TemplateWarning::UnknownTool {
+                    template: "ops".to_string(),
+                    field: "allowed_tools",
+                    tool: "web_serch".to_string(),
+                    suggestion: Some("did you mean 'web_search'?".to_string()),
+                },
+                TemplateWarning::UnknownTool {
+                    template: "ops".to_string(),
+                    field: "blocked_tools",
+                    tool: "nonexistent_tool_xyz".to_string(),
+                    suggestion: None,
+                },
+                TemplateWarning::AllowedAndBlocked {
+                    template: "ops".to_string(),
+                    tool: "read_file".to_string(),
+                },
+            ]
+        );
+        assert_eq!(warnings[0].template(), "ops");
+        assert_eq!(
+            warnings[0].to_string(),
+            "template 'ops': allowed_tools references unknown tool 'web_serch' (did you mean 'web_search'?)"
+        );
+        assert!(warnings[2].to_string().contains("will never be available"));
+    }
+
+    #[test]
+    fn test_validate_against_tools_clean_registry() {
+        let registry = TemplateRegistry::new();
+        let mut known: Vec<String> = Vec::new();
+        for template in registry.list() {
+            for tool in template
+                .allowed_tools
+                .iter()
+                .chain(template.blocked_tools.iter())
+                .flatten()
+            {
+                known.push(tool.clone());
+            }
+        }
+        assert!(registry.validate_against_tools(&known).is_empty());
+    }
 }
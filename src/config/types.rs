@@ -49,6 +49,9 @@ pub struct Config {
     pub safety: crate::safety::SafetyConfig,
     /// Context compaction configuration
     pub compaction: CompactionConfig,
+    /// Session storage configuration (`sessions` is accepted as an alias)
+    #[serde(alias = "sessions")]
+    pub session: crate::session::SessionConfig,
     /// MCP (Model Context Protocol) server configuration
     pub mcp: McpConfig,
     /// Routines (event/webhook/cron triggers) configuration
@@ -62,6 +65,9 @@ pub struct Config {
     /// Key = profile name, Value = None means all tools, Some(vec) means only those tools.
     #[serde(default)]
     pub tool_profiles: HashMap<String, Option<Vec<String>>>,
+    /// Names of built-in agent templates to exclude from the template registry.
+    #[serde(default)]
+    pub disabled_templates: Vec<String>,
 }
 
 // ============================================================================
@@ -78,6 +84,15 @@ pub struct CompactionConfig {
     pub context_limit: usize,
     /// Fraction (0.0-1.0) of context_limit that triggers compaction.
     pub threshold: f64,
+    /// Summarize older messages with the LLM before falling back to truncation.
+    pub summarize: bool,
+    /// Number of most recent messages kept verbatim when compacting.
+    pub keep_recent: usize,
+    /// Characters-per-token ratio used to estimate context size, keyed by
+    /// provider family (`anthropic`, `openai`, ...). Families not listed use
+    /// [`crate::session::DEFAULT_CHARS_PER_TOKEN`].
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub chars_per_token: HashMap<String, f64>,
 }
 
 impl Default for CompactionConfig {
@@ -86,10 +101,39 @@ impl Default for CompactionConfig {
             enabled: true,
             context_limit: 12000,
             threshold: 0.80,
+            summarize: false,
+            keep_recent: 8,
+            chars_per_token: HashMap::new(),
         }
     }
 }
 
+impl CompactionConfig {
+    /// Chars-per-token ratio for the provider reported as `provider_name`.
+    ///
+    /// The family is the leading name of the (possibly composite, e.g.
+    /// `"claude -> openai"`) provider name, with `claude` mapped to
+    /// `anthropic`. Missing or non-positive entries fall back to the default.
+    pub fn chars_per_token_for(&self, provider_name: &str) -> f64 {
+        let family: String = provider_name
+            .trim_start()
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric() || *c == '_' || *c == '-')
+            .collect::<String>()
+            .to_ascii_lowercase();
+        let family = if family == "claude" {
+            "anthropic"
+        } else {
+            family.as_str()
+        };
+        self.chars_per_token
+            .get(family)
+            .copied()
+            .filter(|ratio| *ratio > 0.0)
+            .unwrap_or(crate::session::DEFAULT_CHARS_PER_TOKEN)
+    }
+}
+
 // ============================================================================
 // MCP Configuration
 // ============================================================================
@@ -249,6 +293,28 @@ pub struct AgentDefaults {
     /// Defaults to system local timezone, falls back to "UTC".
     #[serde(default = "default_timezone")]
     pub timezone: String,
+    /// Stop sequences passed to the provider on every request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stop_sequences: Option<Vec<String>>,
+    /// Ask the model for a plan of tool calls and require approval before
+    /// executing any of them.
+    #[serde(default)]
+    pub plan_first: bool,
+    /// Mark the system prompt and tool definitions as cacheable on every
+    /// request. Only Anthropic supports this; other providers ignore it.
+    pub prompt_caching: bool,
+    /// Maximum parallel-safe tool calls run at once within a turn. Tools
+    /// that are not parallel-safe always run one at a time. 1 = sequential.
+    pub max_parallel_tools: usize,
+    /// Entries kept in the tool result cache, which reuses results of
+    /// cacheable tools (web_search, web_fetch) for identical calls.
+    /// 0 = disabled.
+    pub tool_cache_size: usize,
+    /// How tools are offered to the model: `native`, `prompted` (schemas
+    /// in the system prompt, calls parsed from fenced JSON) or `auto`
+    /// (native, switching to prompted when the model rejects tools).
+    /// Only the OpenAI-compatible and Ollama providers support prompting.
+    pub tool_call_mode: crate::providers::ToolCallMode,
 }
 
 /// Detect the system's IANA timezone.
@@ -294,6 +360,12 @@ impl Default for AgentDefaults {
             compact_tools: false,
             tool_profile: None,
             timezone: default_timezone(),
+            stop_sequences: None,
+            plan_first: false,
+            prompt_caching: true,
+            max_parallel_tools: 4,
+            tool_cache_size: 0,
+            tool_call_mode: crate::providers::ToolCallMode::default(),
         }
     }
 }
@@ -340,6 +412,10 @@ pub struct ChannelsConfig {
     /// Directory for channel plugins (default: ~/.zeptoclaw/channels/)
     #[serde(default)]
     pub channel_plugins_dir: Option<String>,
+    /// Per-channel outbound message TTL in seconds, keyed by channel name.
+    /// Replies still queued after their TTL are dropped instead of sent.
+    #[serde(default)]
+    pub outbound_ttl_secs: HashMap<String, u64>,
 }
 
 /// Webhook inbound channel configuration
@@ -688,7 +764,9 @@ pub struct ProvidersConfig {
 }
 
 /// Generic provider configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+///
+/// `Debug` output masks the API key.
+#[derive(Clone, Serialize, Deserialize, Default)]
 pub struct ProviderConfig {
     /// API key for authentication
     #[serde(default)]
@@ -699,6 +777,21 @@ pub struct ProviderConfig {
     /// Authentication method: "api_key" (default), "oauth", or "auto"
     #[serde(default)]
     pub auth_method: Option<String>,
+    /// Retries of transient HTTP failures (429, 5xx) inside this provider's client
+    #[serde(default)]
+    pub http_retry: HttpRetryConfig,
+}
+
+impl std::fmt::Debug for ProviderConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use crate::utils::redact::REDACTED;
+        f.debug_struct("ProviderConfig")
+            .field("api_key", &self.api_key.as_ref().map(|_| REDACTED))
+            .field("api_base", &self.api_base)
+            .field("auth_method", &self.auth_method)
+            .field("http_retry", &self.http_retry)
+            .finish()
+    }
 }
 
 impl ProviderConfig {
@@ -708,6 +801,41 @@ impl ProviderConfig {
     }
 }
 
+/// Retries of transient HTTP failures inside a provider's HTTP client.
+///
+/// Applies to 429 (honoring `Retry-After`), 500, 502, 503 and 504 responses
+/// and to connection failures; auth, billing and other 4xx errors are never
+/// retried. Runs underneath fallback and rotation. Only one layer retries:
+/// while `providers.retry` is enabled the wrapper owns retries and this
+/// setting is ignored.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HttpRetryConfig {
+    /// Enable in-client retries.
+    pub enabled: bool,
+    /// Retries after the first attempt.
+    pub max_retries: u32,
+    /// Base delay in milliseconds, doubled on each retry.
+    pub base_delay_ms: u64,
+    /// Longest wait between attempts. A `Retry-After` beyond this is not
+    /// waited for and the response is returned as-is.
+    pub max_delay_ms: u64,
+    /// Add up to `base_delay_ms` of random jitter to each backoff delay.
+    pub jitter: bool,
+}
+
+impl Default for HttpRetryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_retries: 2,
+            base_delay_ms: 500,
+            max_delay_ms: 10_000,
+            jitter: true,
+        }
+    }
+}
+
 /// Retry behavior for runtime provider calls.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -753,7 +881,8 @@ pub struct RotationConfig {
     /// Provider names in rotation order (e.g., \["anthropic", "openai", "groq"\]).
     #[serde(default)]
     pub order: Vec<String>,
-    /// Rotation strategy (priority or round_robin).
+    /// Rotation strategy (priority, round_robin or `{"weighted": [..]}` with
+    /// one weight per provider in `order`).
     #[serde(default)]
     pub strategy: crate::providers::rotation::RotationStrategy,
     /// Consecutive failures before marking provider unhealthy (default: 3).
@@ -796,6 +925,21 @@ pub struct GatewayConfig {
     pub host: String,
     /// Port to listen on
     pub port: u16,
+    /// Per-chat inbound rate limiting
+    pub rate_limit: crate::gateway::rate_limit::ChatRateLimitConfig,
+    /// Maximum number of inbound messages processed concurrently.
+    /// Messages for the same session are always handled in order.
+    pub max_concurrent_agents: usize,
+    /// Capacity of each message bus queue (inbound and outbound).
+    /// Publishers wait, or get a "queue full" error, once it is reached.
+    pub bus_capacity: usize,
+    /// Drop an outbound message identical (same channel, chat and content)
+    /// to one published within this many seconds. 0 = disabled.
+    pub outbound_dedup_secs: u64,
+    /// Address (e.g. "127.0.0.1:9091") for the `/healthz` and `/metrics`
+    /// monitoring endpoint. Unset = disabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metrics_addr: Option<String>,
 }
 
 impl Default for GatewayConfig {
@@ -803,6 +947,11 @@ impl Default for GatewayConfig {
         Self {
             host: "0.0.0.0".to_string(),
             port: 8080,
+            rate_limit: Default::default(),
+            max_concurrent_agents: 1,
+            bus_capacity: crate::bus::DEFAULT_BUFFER_SIZE,
+            outbound_dedup_secs: 0,
+            metrics_addr: None,
         }
     }
 }
@@ -821,6 +970,42 @@ pub struct ToolsConfig {
     pub whatsapp: WhatsAppToolConfig,
     /// Google Sheets tool configuration
     pub google_sheets: GoogleSheetsToolConfig,
+    /// HTTP request tool configuration
+    pub http: HttpToolConfig,
+    /// URL policy shared by every URL-accepting tool
+    pub url_policy: UrlPolicyConfig,
+}
+
+/// URL policy shared by `web_fetch`, `web_screenshot` and `http_request`.
+///
+/// Private, loopback and link-local addresses are always blocked, and URLs
+/// without a host (`file://`, `data:`) never pass.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UrlPolicyConfig {
+    /// URL schemes tools may open
+    pub allowed_schemes: Vec<String>,
+    /// Hosts tools may open; subdomains match too. Empty allows any public
+    /// host.
+    pub allowed_hosts: Vec<String>,
+    /// Hosts tools may never open; subdomains match too
+    pub denied_hosts: Vec<String>,
+    /// Ports tools may connect to. Empty allows any port.
+    pub allowed_ports: Vec<u16>,
+    /// Ports tools may never connect to
+    pub denied_ports: Vec<u16>,
+}
+
+impl Default for UrlPolicyConfig {
+    fn default() -> Self {
+        Self {
+            allowed_schemes: vec!["http".to_string(), "https".to_string()],
+            allowed_hosts: Vec::new(),
+            denied_hosts: Vec::new(),
+            allowed_ports: Vec::new(),
+            denied_ports: Vec::new(),
+        }
+    }
 }
 
 /// Web tools configuration
@@ -829,6 +1014,8 @@ pub struct ToolsConfig {
 pub struct WebToolsConfig {
     /// Web search configuration
     pub search: WebSearchConfig,
+    /// Web fetch configuration
+    pub fetch: WebFetchConfig,
 }
 
 /// Web search configuration
@@ -851,6 +1038,69 @@ impl Default for WebSearchConfig {
     }
 }
 
+/// HTTP request tool configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HttpToolConfig {
+    /// Hosts the tool may call; subdomains match too. Empty allows any
+    /// public host.
+    pub allowed_hosts: Vec<String>,
+    /// Hosts the tool may never call; subdomains match too. Private and
+    /// loopback addresses are always blocked.
+    pub denied_hosts: Vec<String>,
+    /// Maximum request timeout in seconds
+    pub timeout_secs: u64,
+    /// Response bodies are truncated beyond this many bytes
+    pub max_response_bytes: usize,
+}
+
+impl Default for HttpToolConfig {
+    fn default() -> Self {
+        Self {
+            allowed_hosts: Vec::new(),
+            denied_hosts: vec![
+                "localhost".to_string(),
+                "169.254.169.254".to_string(),
+                "metadata.google.internal".to_string(),
+            ],
+            timeout_secs: 30,
+            max_response_bytes: 512 * 1024,
+        }
+    }
+}
+
+/// Web fetch configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WebFetchConfig {
+    /// Rendering service for `render: true` fetches. The page URL is POSTed
+    /// as `{"url": ...}` and the response is the rendered HTML (e.g.
+    /// browserless `/content`).
+    #[serde(default)]
+    pub render_endpoint: Option<String>,
+    /// Timeout for rendering one page, in seconds
+    pub render_timeout_secs: u64,
+    /// Render with a local headless Chromium when `render_endpoint` is unset
+    /// (requires the `screenshot` feature). Off by default: only the
+    /// requested and final URLs are checked, not redirects in between,
+    /// subresource/XHR requests, or hosts resolving to private addresses.
+    pub render_chromium: bool,
+    /// Launch that Chromium with `--no-sandbox`, e.g. when running as root
+    /// in a container.
+    pub render_chromium_no_sandbox: bool,
+}
+
+impl Default for WebFetchConfig {
+    fn default() -> Self {
+        Self {
+            render_endpoint: None,
+            render_timeout_secs: 30,
+            render_chromium: false,
+            render_chromium_no_sandbox: false,
+        }
+    }
+}
+
 /// WhatsApp Cloud API tool configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -950,6 +1200,10 @@ pub struct MemoryConfig {
     pub min_score: f32,
     /// Maximum snippet length returned per result.
     pub max_snippet_chars: u32,
+    /// Lines per chunk when splitting memory files for search.
+    pub chunk_lines: u32,
+    /// Lines shared between consecutive chunks. Must be less than `chunk_lines`.
+    pub chunk_overlap: u32,
     /// Extra workspace-relative file/dir paths to include.
     #[serde(default)]
     pub extra_paths: Vec<String>,
@@ -976,6 +1230,8 @@ impl Default for MemoryConfig {
             max_results: 6,
             min_score: 0.2,
             max_snippet_chars: 700,
+            chunk_lines: 18,
+            chunk_overlap: 4,
             extra_paths: Vec::new(),
             embedding_provider: None,
             embedding_model: None,
@@ -995,8 +1251,22 @@ impl Default for MemoryConfig {
 pub struct HeartbeatConfig {
     /// Enable or disable heartbeat service.
     pub enabled: bool,
-    /// Heartbeat interval in seconds.
+    /// Heartbeat interval in seconds (used when no `schedule` is set).
     pub interval_secs: u64,
+    /// Optional five-field cron expression (e.g. "0 9 * * 1-5" for weekdays
+    /// at 9am). Replaces the fixed interval when set.
+    #[serde(default)]
+    pub schedule: Option<String>,
+    /// Timezone the cron `schedule` is evaluated in.
+    #[serde(default)]
+    pub timezone: ScheduleTimezone,
+    /// Random offset of up to ± this many seconds applied to each fire
+    /// time, so instances started together do not fire in lockstep.
+    #[serde(default)]
+    pub jitter_secs: u64,
+    /// What to do with ticks missed while the process was suspended.
+    #[serde(default)]
+    pub catch_up: HeartbeatCatchUp,
     /// Optional heartbeat file path override.
     #[serde(default)]
     pub file_path: Option<String>,
@@ -1004,6 +1274,10 @@ pub struct HeartbeatConfig {
     /// If empty/none, heartbeat runs but results are not pushed.
     #[serde(default)]
     pub deliver_to: Option<String>,
+    /// Named heartbeats sent to specific channels. When non-empty, these
+    /// replace the default heartbeat and each runs on its own timer.
+    #[serde(default)]
+    pub targets: Vec<HeartbeatTarget>,
 }
 
 impl Default for HeartbeatConfig {
@@ -1011,12 +1285,69 @@ impl Default for HeartbeatConfig {
         Self {
             enabled: false,
             interval_secs: 30 * 60,
+            schedule: None,
+            timezone: ScheduleTimezone::default(),
+            jitter_secs: 0,
+            catch_up: HeartbeatCatchUp::default(),
             file_path: None,
             deliver_to: None,
+            targets: Vec::new(),
         }
     }
 }
 
+/// A named heartbeat delivered to one channel and chat.
+///
+/// Unset schedule fields fall back to the top-level heartbeat settings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HeartbeatTarget {
+    /// Name used in logs and message metadata.
+    pub name: String,
+    /// Channel to publish on (e.g., "telegram", "slack").
+    pub channel: String,
+    /// Chat to publish to within the channel.
+    pub chat_id: String,
+    /// Prompt sent to the agent. Defaults to the HEARTBEAT.md prompt.
+    #[serde(default)]
+    pub prompt: Option<String>,
+    /// Interval in seconds for this heartbeat.
+    #[serde(default)]
+    pub interval_secs: Option<u64>,
+    /// Cron expression for this heartbeat; takes precedence over the interval.
+    #[serde(default)]
+    pub schedule: Option<String>,
+    /// Timezone for this heartbeat's cron schedule.
+    #[serde(default)]
+    pub timezone: Option<ScheduleTimezone>,
+    /// Whether this heartbeat runs.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+/// How the heartbeat handles ticks missed while the process was asleep.
+///
+/// Either way, a long gap never produces a burst of backlogged heartbeats.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HeartbeatCatchUp {
+    /// Drop missed ticks and wait for the next scheduled one.
+    Skip,
+    /// Fire once on wake-up, then resume the schedule.
+    #[default]
+    Once,
+}
+
+/// Timezone used to evaluate cron schedules.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScheduleTimezone {
+    /// The host's local timezone (follows its DST rules).
+    #[default]
+    Local,
+    /// Coordinated Universal Time.
+    Utc,
+}
+
 // ============================================================================
 // Skills Configuration
 // ============================================================================
@@ -1101,6 +1432,8 @@ pub enum RuntimeType {
     Native,
     /// Docker container isolation
     Docker,
+    /// Podman container isolation (rootless-friendly Docker alternative)
+    Podman,
     /// Apple Container isolation (macOS only)
     #[serde(rename = "apple")]
     AppleContainer,
@@ -1119,8 +1452,13 @@ pub struct RuntimeConfig {
     pub mount_allowlist_path: String,
     /// Docker-specific configuration
     pub docker: DockerConfig,
+    /// Podman-specific configuration
+    pub podman: PodmanConfig,
     /// Apple Container-specific configuration (macOS)
     pub apple: AppleContainerConfig,
+    /// Native runtime configuration
+    #[serde(default)]
+    pub native: NativeConfig,
 }
 
 fn default_mount_allowlist_path() -> String {
@@ -1134,11 +1472,21 @@ impl Default for RuntimeConfig {
             allow_fallback_to_native: false,
             mount_allowlist_path: default_mount_allowlist_path(),
             docker: DockerConfig::default(),
+            podman: PodmanConfig::default(),
             apple: AppleContainerConfig::default(),
+            native: NativeConfig::default(),
         }
     }
 }
 
+/// Native runtime configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NativeConfig {
+    /// Resource limits for shell commands (Unix only, best-effort)
+    pub limits: crate::runtime::ResourceLimits,
+}
+
 /// Docker runtime configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -1153,6 +1501,9 @@ pub struct DockerConfig {
     pub cpu_limit: Option<String>,
     /// Network mode (default: none for security)
     pub network: String,
+    /// Hosts, IPs or CIDRs (optionally `:port`) the container may reach.
+    /// Empty = no filtering beyond `network`. See `security::egress`.
+    pub network_allowlist: Vec<String>,
 }
 
 impl Default for DockerConfig {
@@ -1163,10 +1514,17 @@ impl Default for DockerConfig {
             memory_limit: Some("512m".to_string()),
             cpu_limit: Some("1.0".to_string()),
             network: "none".to_string(),
+            network_allowlist: Vec::new(),
         }
     }
 }
 
+/// Podman runtime configuration.
+///
+/// Same options as [`DockerConfig`]. A `network` of `"bridge"` is treated as
+/// Podman's default network, since rootless Podman has no bridge to join.
+pub type PodmanConfig = DockerConfig;
+
 /// Apple Container runtime configuration (macOS only)
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(default)]
@@ -1188,11 +1546,13 @@ pub struct AppleContainerConfig {
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum ContainerAgentBackend {
-    /// Auto-detect: on macOS try Apple Container first, then Docker.
+    /// Auto-detect: on macOS try Apple Container first, then Docker, then Podman.
     #[default]
     Auto,
     /// Always use Docker.
     Docker,
+    /// Always use Podman.
+    Podman,
     /// Use Apple Container (macOS only).
     #[cfg(target_os = "macos")]
     #[serde(rename = "apple")]
@@ -1206,7 +1566,7 @@ pub enum ContainerAgentBackend {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct ContainerAgentConfig {
-    /// Container backend to use (auto, docker, apple).
+    /// Container backend to use (auto, docker, podman, apple).
     pub backend: ContainerAgentBackend,
     /// Container image for the agent.
     pub image: String,
@@ -1220,10 +1580,20 @@ pub struct ContainerAgentConfig {
     pub timeout_secs: u64,
     /// Network mode (default: "none" for security) — Docker only.
     pub network: String,
+    /// Hosts, IPs or CIDRs (optionally `:port`) the agent container may
+    /// reach; requires a network other than "none". Empty = no filtering.
+    /// Docker and Podman only.
+    pub network_allowlist: Vec<String>,
     /// Extra volume mounts (host:container format).
     pub extra_mounts: Vec<String>,
     /// Maximum number of concurrent container invocations.
     pub max_concurrent: usize,
+    /// Number of warm containers kept alive between requests (0 = spawn a
+    /// fresh container per request).
+    pub pool_size: usize,
+    /// Seconds to wait on shutdown for in-flight requests to publish their
+    /// responses before they are aborted.
+    pub drain_timeout_secs: u64,
 }
 
 impl Default for ContainerAgentConfig {
@@ -1236,8 +1606,11 @@ impl Default for ContainerAgentConfig {
             cpu_limit: Some("2.0".to_string()),
             timeout_secs: 300,
             network: "none".to_string(),
+            network_allowlist: Vec::new(),
             extra_mounts: Vec::new(),
             max_concurrent: 5,
+            pool_size: 0,
+            drain_timeout_secs: 30,
         }
     }
 }
@@ -1291,6 +1664,35 @@ pub struct CustomToolDef {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_compaction_chars_per_token_for_family() {
+        let config: CompactionConfig = serde_json::from_str(
+            r#"{"chars_per_token": {"anthropic": 3.5, "openai": 4.2, "groq": 0}}"#,
+        )
+        .unwrap();
+        assert_eq!(config.chars_per_token_for("claude"), 3.5);
+        assert_eq!(config.chars_per_token_for("claude -> openai"), 3.5);
+        assert_eq!(config.chars_per_token_for("OpenAI"), 4.2);
+        assert_eq!(
+            config.chars_per_token_for("groq"),
+            crate::session::DEFAULT_CHARS_PER_TOKEN
+        );
+        assert_eq!(
+            CompactionConfig::default().chars_per_token_for("claude"),
+            crate::session::DEFAULT_CHARS_PER_TOKEN
+        );
+    }
+
+    #[test]
+    fn test_sessions_section_alias() {
+        let config: Config =
+            serde_json::from_str(r#"{"sessions": {"backend": "memory"}}"#).unwrap();
+        assert_eq!(
+            config.session.backend,
+            crate::session::SessionBackend::Memory
+        );
+    }
+
     #[test]
     fn test_swarm_config_defaults() {
         let config = SwarmConfig::default();
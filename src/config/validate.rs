@@ -2,6 +2,11 @@
 
 use serde_json::Value;
 use std::collections::HashSet;
+use std::path::Path;
+
+use crate::config::templates::TemplateRegistry;
+use crate::config::{Config, MemoryConfig};
+use crate::hooks::HookRule;
 
 /// Known top-level config field names.
 const KNOWN_TOP_LEVEL: &[&str] = &[
@@ -24,10 +29,57 @@ const KNOWN_TOP_LEVEL: &[&str] = &[
     "hooks",
     "safety",
     "compaction",
+    "session",
+    "sessions",
     "mcp",
     "routines",
     "custom_tools",
     "tool_profiles",
+    "disabled_templates",
+];
+
+/// Names of the built-in tools.
+const BUILTIN_TOOLS: &[&str] = &[
+    "echo",
+    "read_file",
+    "write_file",
+    "list_dir",
+    "edit_file",
+    "grep",
+    "tree",
+    "shell",
+    "web_search",
+    "web_fetch",
+    "web_screenshot",
+    "http_request",
+    "message",
+    "memory_search",
+    "memory_get",
+    "memory_write",
+    "longterm_memory",
+    "whatsapp_send",
+    "google_sheets",
+    "cron",
+    "reminder",
+    "spawn",
+    "delegate",
+    "r8r",
+    "list_tools",
+];
+
+/// Channel names hook rules can match, including the CLI.
+const KNOWN_CHANNELS: &[&str] = &[
+    "cli",
+    "telegram",
+    "discord",
+    "slack",
+    "whatsapp",
+    "whatsapp_cloud",
+    "feishu",
+    "maixcam",
+    "qq",
+    "dingtalk",
+    "webhook",
 ];
 
 /// Known fields for each section. Nested as section.field.
@@ -43,10 +95,24 @@ const KNOWN_AGENTS_DEFAULTS: &[&str] = &[
     "token_budget",
     "compact_tools",
     "tool_profile",
+    "stop_sequences",
+    "plan_first",
+    "prompt_caching",
+    "max_parallel_tools",
+    "tool_cache_size",
+    "tool_call_mode",
 ];
 
 #[allow(dead_code)]
-const KNOWN_GATEWAY: &[&str] = &["host", "port"];
+const KNOWN_GATEWAY: &[&str] = &[
+    "host",
+    "port",
+    "rate_limit",
+    "max_concurrent_agents",
+    "bus_capacity",
+    "outbound_dedup_secs",
+    "metrics_addr",
+];
 
 /// A validation diagnostic.
 #[derive(Debug)]
@@ -212,6 +278,85 @@ pub fn validate_config(raw: &Value) -> Vec<Diagnostic> {
         }
     }
 
+    if let Some(memory) = obj.get("memory").and_then(|v| v.as_object()) {
+        let defaults = MemoryConfig::default();
+        let field = |key: &str, default: u32| {
+            memory
+                .get(key)
+                .and_then(|v| v.as_u64())
+                .map_or(default, |n| n.min(u32::MAX as u64) as u32)
+        };
+        let configured = MemoryConfig {
+            chunk_lines: field("chunk_lines", defaults.chunk_lines),
+            chunk_overlap: field("chunk_overlap", defaults.chunk_overlap),
+            ..defaults
+        };
+        // Report what the memory search will actually use.
+        let (chunk_lines, chunk_overlap) = crate::memory::chunk_params(&configured);
+        if configured.chunk_lines as usize != chunk_lines {
+            diagnostics.push(Diagnostic {
+                level: DiagnosticLevel::Warn,
+                path: "memory.chunk_lines".to_string(),
+                message: format!("Out of range; it will be clamped to {}", chunk_lines),
+            });
+        }
+        if configured.chunk_overlap as usize != chunk_overlap {
+            diagnostics.push(Diagnostic {
+                level: DiagnosticLevel::Warn,
+                path: "memory.chunk_overlap".to_string(),
+                message: format!(
+                    "Must be less than chunk_lines ({}); it will be clamped to {}",
+                    chunk_lines, chunk_overlap
+                ),
+            });
+        }
+    }
+
+    if let Some(schedule) = obj
+        .get("heartbeat")
+        .and_then(|v| v.get("schedule"))
+        .and_then(|v| v.as_str())
+    {
+        if !crate::cron::is_valid_cron_expr(schedule) {
+            diagnostics.push(Diagnostic {
+                level: DiagnosticLevel::Error,
+                path: "heartbeat.schedule".to_string(),
+                message: format!("Invalid cron expression '{}'", schedule),
+            });
+        }
+    }
+
+    if let Some(targets) = obj
+        .get("heartbeat")
+        .and_then(|v| v.get("targets"))
+        .and_then(|v| v.as_array())
+    {
+        for (i, target) in targets.iter().enumerate() {
+            for field in ["name", "channel", "chat_id"] {
+                let present = target
+                    .get(field)
+                    .and_then(|v| v.as_str())
+                    .is_some_and(|v| !v.trim().is_empty());
+                if !present {
+                    diagnostics.push(Diagnostic {
+                        level: DiagnosticLevel::Error,
+                        path: format!("heartbeat.targets[{}].{}", i, field),
+                        message: "Required field is missing or empty".to_string(),
+                    });
+                }
+            }
+            if let Some(schedule) = target.get("schedule").and_then(|v| v.as_str()) {
+                if !crate::cron::is_valid_cron_expr(schedule) {
+                    diagnostics.push(Diagnostic {
+                        level: DiagnosticLevel::Error,
+                        path: format!("heartbeat.targets[{}].schedule", i),
+                        message: format!("Invalid cron expression '{}'", schedule),
+                    });
+                }
+            }
+        }
+    }
+
     diagnostics
 }
 
@@ -221,29 +366,7 @@ pub fn validate_custom_tools(config: &crate::config::Config) -> Vec<String> {
     let name_re = regex::Regex::new(r"^[a-zA-Z][a-zA-Z0-9_]*$").unwrap();
 
     // Built-in tool names to check for conflicts
-    let builtin_names: HashSet<&str> = [
-        "echo",
-        "read_file",
-        "write_file",
-        "list_dir",
-        "edit_file",
-        "shell",
-        "web_search",
-        "web_fetch",
-        "message",
-        "memory_search",
-        "memory_get",
-        "longterm_memory",
-        "whatsapp_send",
-        "google_sheets",
-        "cron",
-        "spawn",
-        "delegate",
-        "r8r",
-    ]
-    .iter()
-    .copied()
-    .collect();
+    let builtin_names: HashSet<&str> = BUILTIN_TOOLS.iter().copied().collect();
 
     for (i, tool) in config.custom_tools.iter().enumerate() {
         if !name_re.is_match(&tool.name) {
@@ -271,6 +394,184 @@ pub fn validate_custom_tools(config: &crate::config::Config) -> Vec<String> {
     }
     warnings
 }
+/// Semantic checks on a deserialized config: cross-references between
+/// sections and directories the config depends on.
+///
+/// `config_dir` is the directory holding `config.json` (its `templates/`
+/// subdirectory is loaded). Unknown tools and channels are warnings, since
+/// MCP servers and plugins can add more at runtime.
+pub fn validate_semantics(config: &Config, config_dir: &Path) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    // Rotation order must name configured providers.
+    let configured = crate::providers::configured_provider_names(config);
+    for (i, name) in config.providers.rotation.order.iter().enumerate() {
+        let path = format!("providers.rotation.order[{}]", i);
+        let known: Vec<&str> = crate::providers::PROVIDER_REGISTRY
+            .iter()
+            .map(|spec| spec.name)
+            .collect();
+        if !known.contains(&name.as_str()) {
+            diagnostics.push(unknown(
+                "provider",
+                name,
+                &known,
+                path,
+                DiagnosticLevel::Error,
+            ));
+        } else if !configured.contains(&name.as_str()) {
+            diagnostics.push(Diagnostic {
+                level: DiagnosticLevel::Error,
+                path,
+                message: format!(
+                    "Provider '{}' is not configured (no providers.{} API key)",
+                    name, name
+                ),
+            });
+        }
+    }
+
+    // Approval and hook rules must name known tools and channels.
+    let mut tools: Vec<&str> = BUILTIN_TOOLS.to_vec();
+    tools.extend(config.custom_tools.iter().map(|t| t.name.as_str()));
+    for (field, names) in [
+        ("dangerous_tools", &config.approval.dangerous_tools),
+        ("require_for", &config.approval.require_for),
+    ] {
+        for (i, name) in names.iter().enumerate() {
+            if !tools.contains(&name.as_str()) {
+                let path = format!("approval.{}[{}]", field, i);
+                diagnostics.push(unknown("tool", name, &tools, path, DiagnosticLevel::Warn));
+            }
+        }
+    }
+
+    let hooks = &config.hooks;
+    for (event, rules, match_tools) in [
+        ("before_tool", &hooks.before_tool, true),
+        ("after_tool", &hooks.after_tool, true),
+        ("on_error", &hooks.on_error, true),
+        ("on_turn_start", &hooks.on_turn_start, false),
+        ("on_turn_end", &hooks.on_turn_end, false),
+    ] {
+        for (i, rule) in rules.iter().enumerate() {
+            let path = format!("hooks.{}[{}]", event, i);
+            check_hook_rule(rule, &path, match_tools, &tools, &mut diagnostics);
+        }
+    }
+
+    // Directories the config points at.
+    let template_dir = config_dir.join("templates");
+    if let Err(e) =
+        TemplateRegistry::with_disabled(&config.disabled_templates).merge_from_dir(&template_dir)
+    {
+        diagnostics.push(Diagnostic {
+            level: DiagnosticLevel::Error,
+            path: "templates".to_string(),
+            message: format!("Failed to load {}: {}", template_dir.display(), e),
+        });
+    }
+    let builtin_templates = TemplateRegistry::new();
+    let template_names = builtin_templates.names();
+    for (i, name) in config.disabled_templates.iter().enumerate() {
+        if !template_names.contains(&name.as_str()) {
+            let path = format!("disabled_templates[{}]", i);
+            diagnostics.push(unknown(
+                "template",
+                name,
+                &template_names,
+                path,
+                DiagnosticLevel::Warn,
+            ));
+        }
+    }
+
+    let mut dirs: Vec<(String, &str)> = Vec::new();
+    if let Some(dir) = config.skills.workspace_dir.as_deref() {
+        dirs.push(("skills.workspace_dir".to_string(), dir));
+    }
+    if let Some(dir) = config.channels.channel_plugins_dir.as_deref() {
+        dirs.push(("channels.channel_plugins_dir".to_string(), dir));
+    }
+    if config.plugins.enabled {
+        for (i, dir) in config.plugins.plugin_dirs.iter().enumerate() {
+            dirs.push((format!("plugins.plugin_dirs[{}]", i), dir));
+        }
+    }
+    for (path, dir) in dirs {
+        if !super::expand_home(dir).is_dir() {
+            diagnostics.push(Diagnostic {
+                level: DiagnosticLevel::Warn,
+                path,
+                message: format!("Directory '{}' does not exist", dir),
+            });
+        }
+    }
+
+    diagnostics
+}
+
+/// Check one hook rule's tool and channel references.
+fn check_hook_rule(
+    rule: &HookRule,
+    path: &str,
+    match_tools: bool,
+    tools: &[&str],
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    if match_tools {
+        for (i, name) in rule.tools.iter().enumerate() {
+            if name != "*" && !tools.contains(&name.as_str()) {
+                let path = format!("{}.tools[{}]", path, i);
+                diagnostics.push(unknown("tool", name, tools, path, DiagnosticLevel::Warn));
+            }
+        }
+    }
+    for (i, name) in rule.channels.iter().enumerate() {
+        if !KNOWN_CHANNELS.contains(&name.as_str()) {
+            let path = format!("{}.channels[{}]", path, i);
+            diagnostics.push(unknown(
+                "channel",
+                name,
+                KNOWN_CHANNELS,
+                path,
+                DiagnosticLevel::Warn,
+            ));
+        }
+    }
+    if let Some(name) = rule.channel.as_deref() {
+        if !KNOWN_CHANNELS.contains(&name) {
+            let path = format!("{}.channel", path);
+            diagnostics.push(unknown(
+                "channel",
+                name,
+                KNOWN_CHANNELS,
+                path,
+                DiagnosticLevel::Warn,
+            ));
+        }
+    }
+}
+
+/// Diagnostic for a reference to an unknown `kind`, with a suggestion.
+fn unknown(
+    kind: &str,
+    name: &str,
+    known: &[&str],
+    path: String,
+    level: DiagnosticLevel,
+) -> Diagnostic {
+    let message = match suggest_field(name, known) {
+        Some(suggestion) => format!("Unknown {} '{}' \u{2014} {}", kind, name, suggestion),
+        None => format!("Unknown {} '{}'", kind, name),
+    };
+    Diagnostic {
+        level,
+        path,
+        message,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -341,6 +642,70 @@ mod tests {
         }));
     }
 
+    #[test]
+    fn test_validate_memory_chunk_overlap_warning() {
+        let raw = serde_json::json!({"memory": {"chunk_lines": 10, "chunk_overlap": 10}});
+        let diags = validate_config(&raw);
+        assert!(diags
+            .iter()
+            .any(|d| d.level == DiagnosticLevel::Warn && d.path == "memory.chunk_overlap"));
+
+        let raw = serde_json::json!({"memory": {"chunk_lines": 40}});
+        let diags = validate_config(&raw);
+        assert!(!diags.iter().any(|d| d.path == "memory.chunk_overlap"));
+
+        // chunk_lines = 0 runs as 1 line with no overlap.
+        let raw = serde_json::json!({"memory": {"chunk_lines": 0}});
+        let diags = validate_config(&raw);
+        let lines = diags
+            .iter()
+            .find(|d| d.path == "memory.chunk_lines")
+            .unwrap();
+        assert!(lines.message.contains("clamped to 1"));
+        let overlap = diags
+            .iter()
+            .find(|d| d.path == "memory.chunk_overlap")
+            .unwrap();
+        assert_eq!(
+            overlap.message,
+            "Must be less than chunk_lines (1); it will be clamped to 0"
+        );
+    }
+
+    #[test]
+    fn test_validate_heartbeat_schedule() {
+        let raw = json!({"heartbeat": {"schedule": "0 9 * * 1-5"}});
+        assert!(!validate_config(&raw)
+            .iter()
+            .any(|d| d.path == "heartbeat.schedule"));
+
+        let raw = json!({"heartbeat": {"schedule": "every morning"}});
+        assert!(validate_config(&raw)
+            .iter()
+            .any(|d| d.level == DiagnosticLevel::Error && d.path == "heartbeat.schedule"));
+    }
+
+    #[test]
+    fn test_validate_heartbeat_targets() {
+        let raw = json!({"heartbeat": {"targets": [
+            {"name": "standup", "channel": "slack", "chat_id": "C1", "schedule": "0 9 * * 1-5"},
+            {"name": "bad", "channel": "telegram", "schedule": "soon"}
+        ]}});
+        let diags = validate_config(&raw);
+        assert!(!diags
+            .iter()
+            .any(|d| d.path.starts_with("heartbeat.targets[0]")));
+        assert!(
+            diags
+                .iter()
+                .any(|d| d.level == DiagnosticLevel::Error
+                    && d.path == "heartbeat.targets[1].chat_id")
+        );
+        assert!(diags.iter().any(
+            |d| d.level == DiagnosticLevel::Error && d.path == "heartbeat.targets[1].schedule"
+        ));
+    }
+
     #[test]
     fn test_validate_not_an_object() {
         let raw = json!("not an object");
@@ -352,16 +717,18 @@ mod tests {
 
     #[test]
     fn test_validate_custom_tools_valid() {
-        let mut config = Config::default();
-        config.custom_tools = vec![CustomToolDef {
-            name: "cpu_temp".to_string(),
-            description: "Read CPU temp".to_string(),
-            command: "cat /sys/class/thermal/thermal_zone0/temp".to_string(),
-            parameters: None,
-            working_dir: None,
-            timeout_secs: None,
-            env: None,
-        }];
+        let config = Config {
+            custom_tools: vec![CustomToolDef {
+                name: "cpu_temp".to_string(),
+                description: "Read CPU temp".to_string(),
+                command: "cat /sys/class/thermal/thermal_zone0/temp".to_string(),
+                parameters: None,
+                working_dir: None,
+                timeout_secs: None,
+                env: None,
+            }],
+            ..Default::default()
+        };
         let warnings = validate_custom_tools(&config);
         assert!(
             warnings.is_empty(),
@@ -372,68 +739,140 @@ mod tests {
 
     #[test]
     fn test_validate_custom_tool_name_invalid() {
-        let mut config = Config::default();
-        config.custom_tools = vec![CustomToolDef {
-            name: "123bad".to_string(),
-            description: "Bad".to_string(),
-            command: "echo".to_string(),
-            parameters: None,
-            working_dir: None,
-            timeout_secs: None,
-            env: None,
-        }];
+        let config = Config {
+            custom_tools: vec![CustomToolDef {
+                name: "123bad".to_string(),
+                description: "Bad".to_string(),
+                command: "echo".to_string(),
+                parameters: None,
+                working_dir: None,
+                timeout_secs: None,
+                env: None,
+            }],
+            ..Default::default()
+        };
         let warnings = validate_custom_tools(&config);
         assert!(warnings.iter().any(|w| w.contains("invalid")));
     }
 
     #[test]
     fn test_validate_custom_tool_name_builtin_conflict() {
-        let mut config = Config::default();
-        config.custom_tools = vec![CustomToolDef {
-            name: "shell".to_string(),
-            description: "Conflict".to_string(),
-            command: "echo".to_string(),
-            parameters: None,
-            working_dir: None,
-            timeout_secs: None,
-            env: None,
-        }];
+        let config = Config {
+            custom_tools: vec![CustomToolDef {
+                name: "shell".to_string(),
+                description: "Conflict".to_string(),
+                command: "echo".to_string(),
+                parameters: None,
+                working_dir: None,
+                timeout_secs: None,
+                env: None,
+            }],
+            ..Default::default()
+        };
         let warnings = validate_custom_tools(&config);
         assert!(warnings.iter().any(|w| w.contains("conflicts")));
     }
 
     #[test]
     fn test_validate_custom_tool_empty_command() {
-        let mut config = Config::default();
-        config.custom_tools = vec![CustomToolDef {
-            name: "test_tool".to_string(),
-            description: "Test".to_string(),
-            command: "  ".to_string(),
-            parameters: None,
-            working_dir: None,
-            timeout_secs: None,
-            env: None,
-        }];
+        let config = Config {
+            custom_tools: vec![CustomToolDef {
+                name: "test_tool".to_string(),
+                description: "Test".to_string(),
+                command: "  ".to_string(),
+                parameters: None,
+                working_dir: None,
+                timeout_secs: None,
+                env: None,
+            }],
+            ..Default::default()
+        };
         let warnings = validate_custom_tools(&config);
         assert!(warnings.iter().any(|w| w.contains("empty")));
     }
 
     #[test]
     fn test_validate_custom_tool_long_description() {
-        let mut config = Config::default();
-        config.custom_tools = vec![CustomToolDef {
-            name: "verbose_tool".to_string(),
-            description: "A".repeat(61),
-            command: "echo hi".to_string(),
-            parameters: None,
-            working_dir: None,
-            timeout_secs: None,
-            env: None,
-        }];
+        let config = Config {
+            custom_tools: vec![CustomToolDef {
+                name: "verbose_tool".to_string(),
+                description: "A".repeat(61),
+                command: "echo hi".to_string(),
+                parameters: None,
+                working_dir: None,
+                timeout_secs: None,
+                env: None,
+            }],
+            ..Default::default()
+        };
         let warnings = validate_custom_tools(&config);
         assert!(warnings.iter().any(|w| w.contains("60 chars")));
     }
 
+    #[test]
+    fn test_semantics_default_config_is_clean() {
+        let dir = tempfile::tempdir().unwrap();
+        let diags = validate_semantics(&Config::default(), dir.path());
+        assert!(diags.is_empty(), "{:?}", diags);
+    }
+
+    #[test]
+    fn test_semantics_cross_references() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = Config::default();
+        config.providers.anthropic = Some(crate::config::ProviderConfig {
+            api_key: Some("sk-ant".to_string()),
+            ..Default::default()
+        });
+        config.providers.rotation.order = vec![
+            "anthropic".to_string(),
+            "openai".to_string(),
+            "antropic".to_string(),
+        ];
+        config.approval.dangerous_tools = vec!["shell".to_string(), "shel".to_string()];
+        config.hooks.before_tool = vec![HookRule {
+            tools: vec!["*".to_string(), "web_fetch".to_string(), "nope".to_string()],
+            channels: vec!["telegram".to_string(), "telegarm".to_string()],
+            ..Default::default()
+        }];
+        config.skills.workspace_dir = Some(dir.path().join("missing").display().to_string());
+
+        let diags = validate_semantics(&config, dir.path());
+        let find = |path: &str| diags.iter().find(|d| d.path == path);
+
+        let d = find("providers.rotation.order[1]").unwrap();
+        assert_eq!(d.level, DiagnosticLevel::Error);
+        assert!(d.message.contains("not configured"));
+        let d = find("providers.rotation.order[2]").unwrap();
+        assert_eq!(d.level, DiagnosticLevel::Error);
+        assert!(d.message.contains("did you mean 'anthropic'"));
+
+        let d = find("approval.dangerous_tools[1]").unwrap();
+        assert_eq!(d.level, DiagnosticLevel::Warn);
+        assert!(d.message.contains("did you mean 'shell'"));
+        assert!(find("hooks.before_tool[0].tools[2]").is_some());
+        assert!(find("hooks.before_tool[0].channels[1]").is_some());
+        assert!(find("skills.workspace_dir").is_some());
+        assert_eq!(diags.len(), 6, "{:?}", diags);
+    }
+
+    #[test]
+    fn test_semantics_broken_template_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let templates = dir.path().join("templates");
+        std::fs::create_dir(&templates).unwrap();
+        std::fs::write(
+            templates.join("child.json"),
+            r#"{"name": "child", "description": "x", "system_prompt": "x", "extends": "ghost"}"#,
+        )
+        .unwrap();
+
+        let diags = validate_semantics(&Config::default(), dir.path());
+        assert!(diags
+            .iter()
+            .any(|d| d.path == "templates" && d.level == DiagnosticLevel::Error));
+    }
+
     #[test]
     fn test_validate_compact_tools_known() {
         let json = json!({"agents": {"defaults": {"compact_tools": true}}});
@@ -4,7 +4,7 @@ use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
-use chrono::{DateTime, Datelike, Duration, Timelike, Utc};
+use chrono::{DateTime, Datelike, Duration, NaiveDateTime, TimeZone, Timelike, Utc};
 use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 use tokio::task::JoinHandle;
@@ -69,26 +69,34 @@ fn now_ms() -> i64 {
     Utc::now().timestamp_millis()
 }
 
+/// Parse one cron field: `*`, `*/step`, values, `a-b` ranges and
+/// `a-b/step`, combined with commas.
 fn parse_cron_field(field: &str, min: u32, max: u32) -> Option<Vec<u32>> {
-    if field == "*" {
-        return Some((min..=max).collect());
-    }
-    if let Some(step_str) = field.strip_prefix("*/") {
-        let step = step_str.parse::<u32>().ok()?;
+    let mut values = Vec::new();
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, step.parse::<usize>().ok()?),
+            None => (part, 1),
+        };
         if step == 0 {
             return None;
         }
-        return Some((min..=max).step_by(step as usize).collect());
-    }
-
-    let mut values = Vec::new();
-    for part in field.split(',') {
-        let value = part.parse::<u32>().ok()?;
-        if !(min..=max).contains(&value) {
+        let (lo, hi) = if range == "*" {
+            (min, max)
+        } else if let Some((lo, hi)) = range.split_once('-') {
+            (lo.parse::<u32>().ok()?, hi.parse::<u32>().ok()?)
+        } else {
+            let value = range.parse::<u32>().ok()?;
+            // `5/15` means "from 5 to the end, every 15"
+            (value, if step > 1 { max } else { value })
+        };
+        if lo < min || hi > max || lo > hi {
             return None;
         }
-        values.push(value);
+        values.extend((lo..=hi).step_by(step));
     }
+    values.sort_unstable();
+    values.dedup();
     if values.is_empty() {
         None
     } else {
@@ -96,42 +104,99 @@ fn parse_cron_field(field: &str, min: u32, max: u32) -> Option<Vec<u32>> {
     }
 }
 
-fn next_run_from_cron_expr(expr: &str, now: i64) -> Option<i64> {
-    let fields: Vec<&str> = expr.split_whitespace().collect();
-    if fields.len() != 5 {
-        return None;
-    }
-
-    let minutes = parse_cron_field(fields[0], 0, 59)?;
-    let hours = parse_cron_field(fields[1], 0, 23)?;
-    let dom = parse_cron_field(fields[2], 1, 31)?;
-    let month = parse_cron_field(fields[3], 1, 12)?;
-    let dow = parse_cron_field(fields[4], 0, 6)?;
-
-    let mut candidate = DateTime::from_timestamp_millis(now)?
-        .with_second(0)?
-        .with_nanosecond(0)?
-        + Duration::minutes(1);
-    let limit = candidate + Duration::days(366);
-
-    while candidate <= limit {
-        let m = candidate.minute();
-        let h = candidate.hour();
-        let d = candidate.day();
-        let mon = candidate.month();
-        let wd = candidate.weekday().num_days_from_sunday();
-        if minutes.contains(&m)
-            && hours.contains(&h)
-            && dom.contains(&d)
-            && month.contains(&mon)
-            && dow.contains(&wd)
-        {
-            return Some(candidate.timestamp_millis());
+/// A parsed five-field cron expression (minute hour day-of-month month day-of-week).
+struct CronSpec {
+    minutes: Vec<u32>,
+    hours: Vec<u32>,
+    dom: Vec<u32>,
+    month: Vec<u32>,
+    dow: Vec<u32>,
+}
+
+impl CronSpec {
+    fn parse(expr: &str) -> Option<Self> {
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        if fields.len() != 5 {
+            return None;
+        }
+        Some(Self {
+            minutes: parse_cron_field(fields[0], 0, 59)?,
+            hours: parse_cron_field(fields[1], 0, 23)?,
+            dom: parse_cron_field(fields[2], 1, 31)?,
+            month: parse_cron_field(fields[3], 1, 12)?,
+            dow: parse_cron_field(fields[4], 0, 6)?,
+        })
+    }
+
+    fn matches(&self, t: &NaiveDateTime) -> bool {
+        self.minutes.contains(&t.minute())
+            && self.hours.contains(&t.hour())
+            && self.dom.contains(&t.day())
+            && self.month.contains(&t.month())
+            && self.dow.contains(&t.weekday().num_days_from_sunday())
+    }
+
+    /// First instant after `after` whose wall-clock time (per `local`) matches.
+    ///
+    /// Walks forward minute by minute in absolute time, so DST shifts are
+    /// handled without special cases for the timezone:
+    /// - wall-clock minutes skipped by a spring-forward gap that would have
+    ///   matched fire at the first instant after the gap;
+    /// - wall-clock minutes repeated by a fall-back overlap fire only once.
+    fn next_after(
+        &self,
+        after: DateTime<Utc>,
+        local: impl Fn(DateTime<Utc>) -> NaiveDateTime,
+    ) -> Option<DateTime<Utc>> {
+        let start = after.with_second(0)?.with_nanosecond(0)?;
+        // Latest wall-clock minute already seen; a clock set back by DST
+        // revisits earlier minutes, which must not fire twice.
+        let mut high_water = (0..=12)
+            .map(|k| local(start - Duration::minutes(15 * k)))
+            .max()?;
+        let mut prev = local(start);
+        let mut candidate = start + Duration::minutes(1);
+        let limit = candidate + Duration::days(366);
+
+        while candidate <= limit {
+            let wall = local(candidate);
+            if wall > high_water {
+                let skipped_match = wall - prev > Duration::minutes(1)
+                    && std::iter::successors(Some(prev + Duration::minutes(1)), |t| {
+                        Some(*t + Duration::minutes(1))
+                    })
+                    .take_while(|t| *t < wall)
+                    .any(|t| self.matches(&t));
+                if skipped_match || self.matches(&wall) {
+                    return Some(candidate);
+                }
+                high_water = wall;
+            }
+            prev = wall;
+            candidate += Duration::minutes(1);
         }
-        candidate += Duration::minutes(1);
+
+        None
     }
+}
 
-    None
+/// Next time a five-field cron expression fires after `after`, evaluated
+/// against wall-clock time in `after`'s timezone.
+///
+/// Supports `*`, `*/n`, lists, and `a-b` / `a-b/n` ranges; day-of-week is
+/// 0-6 with 0 = Sunday. Returns `None` for an invalid expression or one with
+/// no run in the next year.
+pub fn next_cron_time<Tz: TimeZone>(expr: &str, after: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+    let spec = CronSpec::parse(expr)?;
+    let tz = after.timezone();
+    let next = spec.next_after(after.with_timezone(&Utc), |t| {
+        t.with_timezone(&tz).naive_local()
+    })?;
+    Some(next.with_timezone(&tz))
+}
+
+fn next_run_from_cron_expr(expr: &str, now: i64) -> Option<i64> {
+    next_cron_time(expr, &DateTime::from_timestamp_millis(now)?).map(|t| t.timestamp_millis())
 }
 
 /// Returns true if the cron expression is valid and has a future run time.
@@ -490,6 +555,93 @@ mod tests {
         assert_eq!(next, 1_500);
     }
 
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_parse_cron_field_forms() {
+        assert_eq!(parse_cron_field("*/20", 0, 59), Some(vec![0, 20, 40]));
+        assert_eq!(parse_cron_field("1-5", 0, 6), Some(vec![1, 2, 3, 4, 5]));
+        assert_eq!(
+            parse_cron_field("0-10/5,30", 0, 59),
+            Some(vec![0, 5, 10, 30])
+        );
+        assert_eq!(parse_cron_field("45/5", 0, 59), Some(vec![45, 50, 55]));
+        assert_eq!(parse_cron_field("6,0", 0, 6), Some(vec![0, 6]));
+        assert_eq!(parse_cron_field("5-1", 0, 6), None);
+        assert_eq!(parse_cron_field("7", 0, 6), None);
+        assert_eq!(parse_cron_field("*/0", 0, 59), None);
+    }
+
+    #[test]
+    fn test_next_cron_time_known_expressions() {
+        // 2026-03-06 is a Friday
+        let after = utc("2026-03-06T10:15:30Z");
+        let cases = [
+            ("*/15 * * * *", "2026-03-06T10:30:00Z"),
+            ("0 9 * * 1-5", "2026-03-09T09:00:00Z"),
+            ("0 12 * * *", "2026-03-06T12:00:00Z"),
+            ("30 8 1 * *", "2026-04-01T08:30:00Z"),
+        ];
+        for (expr, expected) in cases {
+            assert_eq!(
+                next_cron_time(expr, &after),
+                Some(utc(expected)),
+                "{}",
+                expr
+            );
+        }
+        // No Feb 29 within the one-year search window
+        assert!(next_cron_time("0 0 29 2 *", &after).is_none());
+        assert!(next_cron_time("0 9 * *", &after).is_none());
+    }
+
+    #[test]
+    fn test_next_cron_time_respects_timezone() {
+        // 09:00 at UTC+02:00 is 07:00 UTC
+        let tz = chrono::FixedOffset::east_opt(2 * 3600).unwrap();
+        let after = utc("2026-03-06T05:00:00Z").with_timezone(&tz);
+        let next = next_cron_time("0 9 * * *", &after).unwrap();
+        assert_eq!(next.with_timezone(&Utc), utc("2026-03-06T07:00:00Z"));
+        assert_eq!(next.hour(), 9);
+    }
+
+    /// Wall clock for a zone at UTC+1 that moves to UTC+2 at 01:00 UTC on
+    /// 2026-03-29 and back to UTC+1 at 01:00 UTC on 2026-10-25.
+    fn dst_local(t: DateTime<Utc>) -> NaiveDateTime {
+        let spring = utc("2026-03-29T01:00:00Z");
+        let fall = utc("2026-10-25T01:00:00Z");
+        let offset = if t >= spring && t < fall { 2 } else { 1 };
+        (t + Duration::hours(offset)).naive_utc()
+    }
+
+    #[test]
+    fn test_cron_spring_forward_gap_fires_after_gap() {
+        // Local 02:30 does not exist on 2026-03-29 (02:00 -> 03:00)
+        let spec = CronSpec::parse("30 2 * * *").unwrap();
+        let next = spec
+            .next_after(utc("2026-03-28T23:00:00Z"), dst_local)
+            .unwrap();
+        // First instant after the gap: local 03:00 = 01:00 UTC
+        assert_eq!(next, utc("2026-03-29T01:00:00Z"));
+        // The next day runs normally at local 02:30 (UTC+2)
+        let next = spec.next_after(next, dst_local).unwrap();
+        assert_eq!(next, utc("2026-03-30T00:30:00Z"));
+    }
+
+    #[test]
+    fn test_cron_fall_back_overlap_fires_once() {
+        // Local 02:30 happens twice on 2026-10-25
+        let spec = CronSpec::parse("30 2 * * *").unwrap();
+        let first = spec
+            .next_after(utc("2026-10-24T23:00:00Z"), dst_local)
+            .unwrap();
+        assert_eq!(first, utc("2026-10-25T00:30:00Z"));
+        let second = spec.next_after(first, dst_local).unwrap();
+        assert_eq!(second, utc("2026-10-26T01:30:00Z"));
+    }
+
     #[test]
     fn test_parse_at_datetime_ms_rfc3339() {
         let ms = parse_at_datetime_ms("2026-02-12T12:34:56Z").unwrap();
@@ -141,6 +141,10 @@ pub enum ZeptoError {
     #[error("Bus error: channel closed")]
     BusClosed,
 
+    /// Message bus queue is at capacity (backpressure); holds the queue name
+    #[error("Bus error: {0} queue full")]
+    BusFull(String),
+
     /// Resource not found (sessions, tools, providers, etc.)
     #[error("Not found: {0}")]
     NotFound(String),
@@ -200,6 +204,7 @@ mod tests {
         let _ = ZeptoError::Tool("test".into());
         let _ = ZeptoError::Session("test".into());
         let _ = ZeptoError::BusClosed;
+        let _ = ZeptoError::BusFull("outbound".into());
         let _ = ZeptoError::NotFound("test".into());
         let _ = ZeptoError::Unauthorized("test".into());
         let _ = ZeptoError::SecurityViolation("test".into());
@@ -1,7 +1,7 @@
 //! Container-based agent proxy that spawns containers for each request
 //!
 //! This module provides the `ContainerAgentProxy` which runs agents in isolated
-//! containers (Docker or Apple Container), enabling multi-user scenarios with
+//! containers (Docker, Podman, or Apple Container), enabling multi-user scenarios with
 //! proper isolation.
 
 use std::path::Path;
@@ -13,6 +13,7 @@ use std::time::Duration;
 use tokio::io::AsyncWriteExt;
 use tokio::process::Command;
 use tokio::sync::{watch, Semaphore};
+use tokio::task::JoinSet;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
@@ -20,15 +21,24 @@ use crate::bus::{InboundMessage, MessageBus, OutboundMessage};
 use crate::config::{Config, ContainerAgentBackend, ContainerAgentConfig};
 use crate::error::{Result, ZeptoError};
 use crate::health::UsageMetrics;
+use crate::runtime::podman::is_podman_default_network;
 use crate::security::mount::validate_mount_not_blocked;
+use crate::security::EgressPolicy;
 use crate::session::SessionManager;
 
+use super::container_pool::{
+    container_timeout_error, ContainerPool, ExchangeError, PooledContainer, PERSISTENT_AGENT_LOOP,
+};
 use super::ipc::{parse_marked_response, AgentRequest, AgentResponse, AgentResult};
+use super::rate_limit::ChatRateLimiter;
 
 const CONTAINER_WORKSPACE_DIR: &str = "/data/.zeptoclaw/workspace";
 const CONTAINER_SESSIONS_DIR: &str = "/data/.zeptoclaw/sessions";
 const CONTAINER_CONFIG_PATH: &str = "/data/.zeptoclaw/config.json";
 
+/// Binary used by the Podman backend.
+const PODMAN_BINARY: &str = "podman";
+
 /// Path inside the container where the env file is mounted (Apple Container only).
 #[cfg(target_os = "macos")]
 const CONTAINER_ENV_DIR: &str = "/tmp/zeptoclaw-env";
@@ -37,6 +47,7 @@ const CONTAINER_ENV_DIR: &str = "/tmp/zeptoclaw-env";
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ResolvedBackend {
     Docker,
+    Podman,
     #[cfg(target_os = "macos")]
     Apple,
 }
@@ -45,12 +56,22 @@ impl std::fmt::Display for ResolvedBackend {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             ResolvedBackend::Docker => write!(f, "docker"),
+            ResolvedBackend::Podman => write!(f, "podman"),
             #[cfg(target_os = "macos")]
             ResolvedBackend::Apple => write!(f, "apple-container"),
         }
     }
 }
 
+/// How the agent process inside the container handles requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AgentMode {
+    /// Handle a single request, then exit (container per request).
+    OneShot,
+    /// Handle requests line by line until stdin closes (warm pool).
+    Persistent,
+}
+
 #[derive(Debug, Clone)]
 struct ContainerInvocation {
     binary: String,
@@ -60,10 +81,33 @@ struct ContainerInvocation {
     temp_dir: Option<std::path::PathBuf>,
 }
 
+impl ContainerInvocation {
+    /// Build the process command for this invocation.
+    fn command(&self) -> Command {
+        let mut command = Command::new(&self.binary);
+        command.args(&self.args);
+        for (name, value) in &self.env {
+            command.env(name, value);
+        }
+        command
+    }
+}
+
 /// Proxy that spawns containers to process agent requests.
 ///
+/// Outcome of draining in-flight requests when the proxy stops.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DrainSummary {
+    /// Requests that finished during the drain and published their response.
+    pub drained: usize,
+    /// Requests aborted because they outlived the drain timeout.
+    pub cancelled: usize,
+}
+
 /// Each inbound message is processed in an isolated container, providing
-/// security isolation for multi-user scenarios.
+/// security isolation for multi-user scenarios. With `pool_size > 0`,
+/// requests are handed to warm containers that are reused between requests
+/// instead of paying a cold start each time.
 pub struct ContainerAgentProxy {
     config: Config,
     container_config: ContainerAgentConfig,
@@ -75,6 +119,8 @@ pub struct ContainerAgentProxy {
     usage_metrics: RwLock<Option<Arc<UsageMetrics>>>,
     resolved_backend: ResolvedBackend,
     semaphore: Arc<Semaphore>,
+    rate_limiter: Option<ChatRateLimiter>,
+    pool: Option<ContainerPool>,
 }
 
 impl ContainerAgentProxy {
@@ -83,7 +129,10 @@ impl ContainerAgentProxy {
         let (shutdown_tx, shutdown_rx) = watch::channel(false);
         let container_config = config.container_agent.clone();
         let max_concurrent = container_config.max_concurrent.max(1);
-        let session_manager = match SessionManager::new() {
+        let rate_limiter = ChatRateLimiter::from_config(&config.gateway.rate_limit);
+        let pool = (container_config.pool_size > 0)
+            .then(|| ContainerPool::new(container_config.pool_size));
+        let session_manager = match SessionManager::from_config(&config) {
             Ok(manager) => Some(manager),
             Err(e) => {
                 warn!(
@@ -105,6 +154,8 @@ impl ContainerAgentProxy {
             usage_metrics: RwLock::new(None),
             resolved_backend: backend,
             semaphore: Arc::new(Semaphore::new(max_concurrent)),
+            rate_limiter,
+            pool,
         }
     }
 
@@ -121,12 +172,22 @@ impl ContainerAgentProxy {
         }
     }
 
+    /// Session store the proxy persists container snapshots to, if any.
+    pub fn session_manager(&self) -> Option<&SessionManager> {
+        self.session_manager.as_ref()
+    }
+
     /// Start the proxy loop, processing messages from the bus.
     ///
     /// Each inbound message is processed concurrently in its own spawned task,
     /// gated by a semaphore that limits the number of simultaneous container
     /// invocations to `container_agent.max_concurrent` (default: 5).
-    pub async fn start(self: Arc<Self>) -> Result<()> {
+    ///
+    /// After [`stop`](Self::stop), no further inbound messages are taken and
+    /// in-flight requests are drained (their responses are still published)
+    /// for up to `container_agent.drain_timeout_secs`. Stragglers are then
+    /// aborted, and the returned [`DrainSummary`] counts both.
+    pub async fn start(self: Arc<Self>) -> Result<DrainSummary> {
         if self.running.swap(true, Ordering::SeqCst) {
             return Err(ZeptoError::Config(
                 "Container agent proxy already running".into(),
@@ -134,11 +195,16 @@ impl ContainerAgentProxy {
         }
 
         info!(
-            "Starting containerized agent proxy (backend={}, max_concurrent={})",
-            self.resolved_backend, self.container_config.max_concurrent,
+            "Starting containerized agent proxy (backend={}, max_concurrent={}, pool_size={})",
+            self.resolved_backend,
+            self.container_config.max_concurrent,
+            self.container_config.pool_size,
         );
 
+        self.warm_pool().await;
+
         let mut shutdown_rx = self.shutdown_rx.clone();
+        let mut in_flight = JoinSet::new();
 
         loop {
             tokio::select! {
@@ -148,14 +214,29 @@ impl ContainerAgentProxy {
                         break;
                     }
                 }
+                // Reap finished request tasks so the set does not grow.
+                Some(_) = in_flight.join_next(), if !in_flight.is_empty() => {}
                 msg = self.bus.consume_inbound() => {
                     match msg {
                         Some(inbound) => {
+                            if let Some(limiter) = self.rate_limiter.as_ref() {
+                                if !limiter.check(&inbound) {
+                                    info!(
+                                        channel = %inbound.channel,
+                                        chat_id = %inbound.chat_id,
+                                        "Chat rate limit exceeded, sending throttle notice"
+                                    );
+                                    if let Err(e) = self.bus.publish_outbound(limiter.throttled_reply(&inbound)).await {
+                                        error!("Failed to publish throttle notice: {}", e);
+                                    }
+                                    continue;
+                                }
+                            }
                             let permit = self.semaphore.clone().acquire_owned().await;
                             match permit {
                                 Ok(permit) => {
                                     let proxy = Arc::clone(&self);
-                                    tokio::spawn(async move {
+                                    in_flight.spawn(async move {
                                         let response = proxy.process_in_container(&inbound).await;
                                         if let Err(e) = proxy.bus.publish_outbound(response).await {
                                             error!("Failed to publish response: {}", e);
@@ -178,11 +259,54 @@ impl ContainerAgentProxy {
             }
         }
 
+        let summary = self.drain_in_flight(&mut in_flight).await;
+        if let Some(pool) = self.pool.as_ref() {
+            pool.drain();
+        }
         self.running.store(false, Ordering::SeqCst);
-        Ok(())
+        Ok(summary)
+    }
+
+    /// Wait for in-flight request tasks, aborting them if they outlive the
+    /// drain timeout.
+    async fn drain_in_flight(&self, in_flight: &mut JoinSet<()>) -> DrainSummary {
+        let mut summary = DrainSummary::default();
+        if in_flight.is_empty() {
+            return summary;
+        }
+        info!(
+            in_flight = in_flight.len(),
+            drain_timeout_secs = self.container_config.drain_timeout_secs,
+            "Waiting for in-flight container requests"
+        );
+        let grace = Duration::from_secs(self.container_config.drain_timeout_secs);
+        let finished = tokio::time::timeout(grace, async {
+            while let Some(result) = in_flight.join_next().await {
+                if result.is_ok() {
+                    summary.drained += 1;
+                }
+            }
+        })
+        .await;
+        if finished.is_err() {
+            summary.cancelled = in_flight.len();
+            warn!(
+                remaining = summary.cancelled,
+                "In-flight container requests did not finish in time; aborting"
+            );
+            in_flight.shutdown().await;
+        }
+        info!(
+            drained = summary.drained,
+            cancelled = summary.cancelled,
+            "Container agent proxy drained"
+        );
+        summary
     }
 
     /// Stop the proxy loop.
+    ///
+    /// In-flight requests are drained before [`start`](Self::start) returns.
     pub fn stop(&self) {
         let _ = self.shutdown_tx.send(true);
     }
@@ -213,7 +337,7 @@ impl ContainerAgentProxy {
             session: session_snapshot,
         };
 
-        match self.spawn_container(&request).await {
+        match self.run_request(&request).await {
             Ok(response) => match response.result {
                 AgentResult::Success { content, session } => {
                     self.persist_session_snapshot(&message.session_key, session)
@@ -276,6 +400,10 @@ impl ContainerAgentProxy {
         }
 
         let Some(manager) = self.session_manager.as_ref() else {
+            debug!(
+                session = %expected_session_key,
+                "No session manager; dropping container session snapshot"
+            );
             return;
         };
 
@@ -288,8 +416,87 @@ impl ContainerAgentProxy {
         }
     }
 
-    /// Spawn a container and communicate via stdin/stdout.
-    async fn spawn_container(&self, request: &AgentRequest) -> Result<AgentResponse> {
+    /// Run a request on a pooled container when pooling is enabled,
+    /// otherwise in a fresh container.
+    async fn run_request(&self, request: &AgentRequest) -> Result<AgentResponse> {
+        match self.pool.as_ref() {
+            Some(pool) => self.run_pooled(pool, request).await,
+            None => self.spawn_container(request).await,
+        }
+    }
+
+    /// Start containers until the pool holds `pool_size` idle ones.
+    ///
+    /// Failures are logged and leave the pool partially filled; requests
+    /// spawn containers on demand in that case.
+    async fn warm_pool(&self) {
+        let Some(pool) = self.pool.as_ref() else {
+            return;
+        };
+        while pool.idle_count() < pool.size() {
+            match self.spawn_pooled_container().await {
+                Ok(container) => pool.put_back(container),
+                Err(e) => {
+                    warn!("Failed to pre-warm container pool: {}", e);
+                    break;
+                }
+            }
+        }
+        info!(idle = pool.idle_count(), "Container pool warmed");
+    }
+
+    /// Hand a request to an idle pooled container (or a newly started one)
+    /// and return the container to the pool afterwards.
+    ///
+    /// If the request cannot be delivered because the container died, it is
+    /// retried once on a freshly spawned container.
+    async fn run_pooled(
+        &self,
+        pool: &ContainerPool,
+        request: &AgentRequest,
+    ) -> Result<AgentResponse> {
+        let request_json = serde_json::to_string(request)
+            .map_err(|e| ZeptoError::Config(format!("Failed to serialize request: {}", e)))?;
+        let timeout = Duration::from_secs(self.container_config.timeout_secs);
+
+        let mut container = match pool.take_idle() {
+            Some(container) => container,
+            None => self.spawn_pooled_container().await?,
+        };
+        let mut respawned = false;
+        loop {
+            debug!(
+                request_id = %request.request_id,
+                backend = %self.resolved_backend,
+                "Dispatching request to pooled container"
+            );
+            match container.exchange(&request_json, timeout).await {
+                Ok(response) => {
+                    pool.put_back(container);
+                    return Ok(response);
+                }
+                Err(ExchangeError::Unavailable(e)) if !respawned => {
+                    warn!("Pooled container unavailable ({}); respawning", e);
+                    respawned = true;
+                    container = self.spawn_pooled_container().await?;
+                }
+                // The container is dropped (and killed): its state is unknown.
+                Err(ExchangeError::Unavailable(e)) | Err(ExchangeError::Failed(e)) => {
+                    return Err(e)
+                }
+            }
+        }
+    }
+
+    /// Spawn a long-lived container running the agent in a request loop.
+    async fn spawn_pooled_container(&self) -> Result<PooledContainer> {
+        let invocation = self.build_invocation(AgentMode::Persistent).await?;
+        let mut command = invocation.command();
+        PooledContainer::spawn(&mut command, invocation.temp_dir)
+    }
+
+    /// Create the host directories and build the backend-specific invocation.
+    async fn build_invocation(&self, mode: AgentMode) -> Result<ContainerInvocation> {
         let config_root = dirs::home_dir().unwrap_or_default().join(".zeptoclaw");
         let workspace_dir = config_root.join("workspace");
         let sessions_dir = config_root.join("sessions");
@@ -305,16 +512,24 @@ impl ContainerAgentProxy {
             .await
             .map_err(|e| ZeptoError::Config(format!("Failed to create config dir: {}", e)))?;
 
-        let invocation = match self.resolved_backend {
+        match self.resolved_backend {
             ResolvedBackend::Docker => {
-                self.build_docker_invocation(&workspace_dir, &sessions_dir, &config_path)?
+                self.build_docker_invocation(&workspace_dir, &sessions_dir, &config_path, mode)
+            }
+            ResolvedBackend::Podman => {
+                self.build_podman_invocation(&workspace_dir, &sessions_dir, &config_path, mode)
             }
             #[cfg(target_os = "macos")]
             ResolvedBackend::Apple => {
-                self.build_apple_invocation(&workspace_dir, &sessions_dir, &config_path)
-                    .await?
+                self.build_apple_invocation(&workspace_dir, &sessions_dir, &config_path, mode)
+                    .await
             }
-        };
+        }
+    }
+
+    /// Spawn a container and communicate via stdin/stdout.
+    async fn spawn_container(&self, request: &AgentRequest) -> Result<AgentResponse> {
+        let invocation = self.build_invocation(AgentMode::OneShot).await?;
 
         debug!(
             request_id = %request.request_id,
@@ -325,12 +540,7 @@ impl ContainerAgentProxy {
             "Spawning containerized agent request"
         );
 
-        let mut command = Command::new(&invocation.binary);
-        command.args(&invocation.args);
-        for (name, value) in &invocation.env {
-            command.env(name, value);
-        }
-
+        let mut command = invocation.command();
         let result = self.run_container_process(&mut command, request).await;
 
         // Clean up temp dir (Apple Container env file) regardless of outcome.
@@ -353,6 +563,7 @@ impl ContainerAgentProxy {
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
+            .kill_on_drop(true)
             .spawn()
             .map_err(|e| ZeptoError::Config(format!("Failed to spawn container: {}", e)))?;
 
@@ -371,10 +582,11 @@ impl ContainerAgentProxy {
 
         // Wait for output with timeout.
         //
-        // On timeout the inner future (and the `Child`) is dropped. On Unix,
-        // dropping a `tokio::process::Child` sends SIGKILL if the process is
-        // still running, so the container process IS cleaned up.  We log a
-        // warning here to make this implicit behaviour visible in traces.
+        // On timeout the inner future (and the `Child`) is dropped. The child
+        // is spawned with `kill_on_drop`, so dropping it sends SIGKILL if the
+        // process is still running and the container process IS cleaned up.
+        // We log a warning here to make this implicit behaviour visible in
+        // traces.
         let timeout_duration = Duration::from_secs(self.container_config.timeout_secs);
         let output = tokio::time::timeout(timeout_duration, child.wait_with_output())
             .await
@@ -383,10 +595,7 @@ impl ContainerAgentProxy {
                     timeout_secs = self.container_config.timeout_secs,
                     "Container process timed out; child will be killed on drop (SIGKILL)"
                 );
-                ZeptoError::Config(format!(
-                    "Container timeout after {}s: process killed",
-                    self.container_config.timeout_secs
-                ))
+                container_timeout_error(self.container_config.timeout_secs)
             })?
             .map_err(|e| ZeptoError::Config(format!("Container failed: {}", e)))?;
 
@@ -480,14 +689,70 @@ impl ContainerAgentProxy {
         workspace_dir: &Path,
         sessions_dir: &Path,
         config_path: &Path,
+        mode: AgentMode,
     ) -> Result<ContainerInvocation> {
-        let mut args = vec![
-            "run".to_string(),
-            "--rm".to_string(),
-            "-i".to_string(),
-            "--network".to_string(),
-            self.container_config.network.clone(),
-        ];
+        let binary = validate_docker_binary(&self.container_config)?;
+        let network = Some(self.container_config.network.as_str());
+        self.build_oci_invocation(
+            binary,
+            network,
+            workspace_dir,
+            sessions_dir,
+            config_path,
+            mode,
+        )
+    }
+
+    /// Build Podman invocation arguments.
+    ///
+    /// Same CLI as Docker, with two differences:
+    /// - Binary: always `podman` (`docker_binary` applies to Docker only)
+    /// - A `bridge`/`default` network omits `--network`, so rootless Podman
+    ///   uses its own default network instead of failing to find a bridge
+    fn build_podman_invocation(
+        &self,
+        workspace_dir: &Path,
+        sessions_dir: &Path,
+        config_path: &Path,
+        mode: AgentMode,
+    ) -> Result<ContainerInvocation> {
+        let network = Some(self.container_config.network.as_str())
+            .filter(|network| !is_podman_default_network(network));
+        self.build_oci_invocation(
+            PODMAN_BINARY.to_string(),
+            network,
+            workspace_dir,
+            sessions_dir,
+            config_path,
+            mode,
+        )
+    }
+
+    /// Build `run` arguments for a Docker-compatible CLI.
+    fn build_oci_invocation(
+        &self,
+        binary: String,
+        network: Option<&str>,
+        workspace_dir: &Path,
+        sessions_dir: &Path,
+        config_path: &Path,
+        mode: AgentMode,
+    ) -> Result<ContainerInvocation> {
+        // Validate the egress allowlist before building anything. Without
+        // `--network`, Podman uses its default network, which has egress.
+        let egress = EgressPolicy::new(
+            network.unwrap_or("default"),
+            &self.container_config.network_allowlist,
+        )?;
+
+        let mut args = vec!["run".to_string(), "--rm".to_string(), "-i".to_string()];
+        if let Some(network) = network {
+            args.push("--network".to_string());
+            args.push(network.to_string());
+        }
+        if let Some(ref egress) = egress {
+            args.extend(egress.run_args());
+        }
         let env_vars = self.collect_env_vars();
 
         // Resource limits
@@ -522,7 +787,7 @@ impl ContainerAgentProxy {
             ));
         }
 
-        // Environment variables — Docker uses `-e NAME` with process env for secrets
+        // Environment variables — `-e NAME` with process env keeps secrets out of argv
         let mut process_env = Vec::new();
         for (name, value) in &env_vars {
             args.push("-e".to_string());
@@ -537,12 +802,20 @@ impl ContainerAgentProxy {
             args.push(mount.clone());
         }
 
-        // Image and command
+        // Image and command, behind the egress rules if an allowlist is set
         args.push(self.container_config.image.clone());
-        args.push("zeptoclaw".to_string());
-        args.push("agent-stdin".to_string());
-
-        let binary = validate_docker_binary(&self.container_config)?;
+        let command = match mode {
+            AgentMode::OneShot => vec!["zeptoclaw".to_string(), "agent-stdin".to_string()],
+            AgentMode::Persistent => vec![
+                "sh".to_string(),
+                "-c".to_string(),
+                PERSISTENT_AGENT_LOOP.to_string(),
+            ],
+        };
+        match egress {
+            Some(egress) => args.extend(egress.wrap_command(command)),
+            None => args.extend(command),
+        }
 
         Ok(ContainerInvocation {
             binary,
@@ -566,6 +839,7 @@ impl ContainerAgentProxy {
         workspace_dir: &Path,
         sessions_dir: &Path,
         config_path: &Path,
+        mode: AgentMode,
     ) -> Result<ContainerInvocation> {
         let container_name = format!("zeptoclaw-{}", Uuid::new_v4());
         let mut args = vec![
@@ -628,11 +902,15 @@ impl ContainerAgentProxy {
         args.push(self.container_config.image.clone());
 
         // Wrap command: source env file then exec zeptoclaw
+        let agent_command = match mode {
+            AgentMode::OneShot => "exec zeptoclaw agent-stdin",
+            AgentMode::Persistent => PERSISTENT_AGENT_LOOP,
+        };
         args.push("sh".to_string());
         args.push("-c".to_string());
         args.push(format!(
-            ". {}/env.sh && exec zeptoclaw agent-stdin",
-            CONTAINER_ENV_DIR
+            ". {}/env.sh && {}",
+            CONTAINER_ENV_DIR, agent_command
         ));
 
         // Keep temp_dir alive — `keep` prevents automatic cleanup on drop.
@@ -667,13 +945,14 @@ pub fn generate_env_file_content(env_vars: &[(String, String)]) -> String {
 pub async fn resolve_backend(config: &ContainerAgentConfig) -> Result<ResolvedBackend> {
     match config.backend {
         ContainerAgentBackend::Docker => Ok(ResolvedBackend::Docker),
+        ContainerAgentBackend::Podman => Ok(ResolvedBackend::Podman),
         #[cfg(target_os = "macos")]
         ContainerAgentBackend::Apple => Ok(ResolvedBackend::Apple),
         ContainerAgentBackend::Auto => auto_detect_backend(config).await,
     }
 }
 
-/// Auto-detect: on macOS try Apple Container first, then Docker.
+/// Auto-detect: on macOS try Apple Container first, then Docker, then Podman.
 async fn auto_detect_backend(config: &ContainerAgentConfig) -> Result<ResolvedBackend> {
     #[cfg(target_os = "macos")]
     {
@@ -686,11 +965,21 @@ async fn auto_detect_backend(config: &ContainerAgentConfig) -> Result<ResolvedBa
         return Ok(ResolvedBackend::Docker);
     }
 
+    if is_podman_available().await {
+        return Ok(ResolvedBackend::Podman);
+    }
+
     Err(ZeptoError::Config(
-        "No container backend available. Install Docker or Apple Container (macOS 15+).".into(),
+        "No container backend available. Install Docker, Podman, or Apple Container (macOS 15+)."
+            .into(),
     ))
 }
 
+/// Check if Podman is available (`podman info` succeeds).
+pub async fn is_podman_available() -> bool {
+    is_docker_available_with_binary(PODMAN_BINARY).await
+}
+
 /// Check if Docker is available and the daemon is running.
 pub async fn is_docker_available() -> bool {
     is_docker_available_with_binary("docker").await
@@ -864,7 +1153,12 @@ mod tests {
         std::fs::write(&config_path, "{}").unwrap();
 
         let invocation = proxy
-            .build_docker_invocation(&workspace_dir, &sessions_dir, &config_path)
+            .build_docker_invocation(
+                &workspace_dir,
+                &sessions_dir,
+                &config_path,
+                AgentMode::OneShot,
+            )
             .expect("build_docker_invocation should succeed with default binary");
 
         assert_eq!(invocation.binary, "docker");
@@ -980,7 +1274,12 @@ mod tests {
         std::fs::create_dir_all(&sessions_dir).unwrap();
         std::fs::write(&config_path, "{}").unwrap();
 
-        let result = proxy.build_docker_invocation(&workspace_dir, &sessions_dir, &config_path);
+        let result = proxy.build_docker_invocation(
+            &workspace_dir,
+            &sessions_dir,
+            &config_path,
+            AgentMode::OneShot,
+        );
         assert!(result.is_err());
         let err_msg = result.unwrap_err().to_string();
         assert!(
@@ -1008,7 +1307,12 @@ mod tests {
         std::fs::write(&config_path, "{}").unwrap();
 
         let invocation = proxy
-            .build_docker_invocation(&workspace_dir, &sessions_dir, &config_path)
+            .build_docker_invocation(
+                &workspace_dir,
+                &sessions_dir,
+                &config_path,
+                AgentMode::OneShot,
+            )
             .expect("default 'docker' binary should be accepted");
         assert_eq!(invocation.binary, "docker");
 
@@ -1019,7 +1323,12 @@ mod tests {
         let proxy2 = ContainerAgentProxy::new(config2, bus2, ResolvedBackend::Docker);
 
         let invocation2 = proxy2
-            .build_docker_invocation(&workspace_dir, &sessions_dir, &config_path)
+            .build_docker_invocation(
+                &workspace_dir,
+                &sessions_dir,
+                &config_path,
+                AgentMode::OneShot,
+            )
             .expect("'podman' binary should be accepted");
         assert_eq!(invocation2.binary, "podman");
 
@@ -1028,8 +1337,10 @@ mod tests {
 
     #[test]
     fn test_validate_docker_binary_rejects_relative_path() {
-        let mut config = ContainerAgentConfig::default();
-        config.docker_binary = Some("./my-docker".to_string());
+        let config = ContainerAgentConfig {
+            docker_binary: Some("./my-docker".to_string()),
+            ..Default::default()
+        };
         let result = validate_docker_binary(&config);
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("absolute path"));
@@ -1037,8 +1348,10 @@ mod tests {
 
     #[test]
     fn test_validate_docker_binary_rejects_nonexistent_absolute_path() {
-        let mut config = ContainerAgentConfig::default();
-        config.docker_binary = Some("/usr/local/bin/nonexistent-docker-zzz".to_string());
+        let config = ContainerAgentConfig {
+            docker_binary: Some("/usr/local/bin/nonexistent-docker-zzz".to_string()),
+            ..Default::default()
+        };
         let result = validate_docker_binary(&config);
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("does not exist"));
@@ -1046,10 +1359,11 @@ mod tests {
 
     #[test]
     fn test_validate_docker_binary_defaults_to_docker_when_empty() {
-        let mut config = ContainerAgentConfig::default();
-
         // None
-        config.docker_binary = None;
+        let mut config = ContainerAgentConfig {
+            docker_binary: None,
+            ..Default::default()
+        };
         assert_eq!(validate_docker_binary(&config).unwrap(), "docker");
 
         // Empty string
@@ -1064,9 +1378,11 @@ mod tests {
     #[cfg(not(target_os = "macos"))]
     #[tokio::test]
     async fn test_resolve_backend_auto_respects_docker_binary_override() {
-        let mut config = ContainerAgentConfig::default();
-        config.backend = ContainerAgentBackend::Auto;
-        config.docker_binary = Some("/definitely-not-a-real-docker-binary".to_string());
+        let config = ContainerAgentConfig {
+            backend: ContainerAgentBackend::Auto,
+            docker_binary: Some("/definitely-not-a-real-docker-binary".to_string()),
+            ..Default::default()
+        };
 
         let result = resolve_backend(&config).await;
         assert!(result.is_err());
@@ -1153,6 +1469,438 @@ EOF
         let _ = std::fs::remove_file(&script_path);
     }
 
+    #[tokio::test]
+    async fn test_persist_session_snapshot_saves_matching_session_only() {
+        let bus = Arc::new(MessageBus::new());
+        let mut proxy = ContainerAgentProxy::new(Config::default(), bus, ResolvedBackend::Docker);
+        proxy.session_manager = Some(SessionManager::new_memory());
+
+        // No snapshot returned: nothing is written.
+        proxy.persist_session_snapshot("test:chat1", None).await;
+        assert!(proxy.load_session_snapshot("test:chat1").await.is_none());
+
+        // A snapshot for another session is ignored.
+        let other = crate::session::Session::new("test:other");
+        proxy
+            .persist_session_snapshot("test:chat1", Some(other))
+            .await;
+        assert!(proxy.load_session_snapshot("test:other").await.is_none());
+
+        let mut session = crate::session::Session::new("test:chat1");
+        session.add_message(crate::session::Message::user("hello"));
+        proxy
+            .persist_session_snapshot("test:chat1", Some(session))
+            .await;
+        let saved = proxy
+            .load_session_snapshot("test:chat1")
+            .await
+            .expect("session should be persisted");
+        assert_eq!(saved.messages.len(), 1);
+
+        // Without a session manager the snapshot is dropped without error.
+        proxy.session_manager = None;
+        proxy
+            .persist_session_snapshot(
+                "test:chat1",
+                Some(crate::session::Session::new("test:chat1")),
+            )
+            .await;
+    }
+
+    #[test]
+    fn test_build_docker_invocation_persistent_runs_agent_loop() {
+        let config = Config::default();
+        let bus = Arc::new(MessageBus::new());
+        let proxy = ContainerAgentProxy::new(config, bus, ResolvedBackend::Docker);
+
+        let temp_root =
+            std::env::temp_dir().join(format!("zeptoclaw-persistent-test-{}", Uuid::new_v4()));
+        let workspace_dir = temp_root.join("workspace");
+        let sessions_dir = temp_root.join("sessions");
+        let config_path = temp_root.join("config.json");
+
+        let invocation = proxy
+            .build_docker_invocation(
+                &workspace_dir,
+                &sessions_dir,
+                &config_path,
+                AgentMode::Persistent,
+            )
+            .unwrap();
+        let tail = &invocation.args[invocation.args.len() - 4..];
+        assert_eq!(tail[0], "zeptoclaw:latest");
+        assert_eq!(tail[1..], ["sh", "-c", PERSISTENT_AGENT_LOOP]);
+        assert!(proxy.pool.is_none(), "pooling is off by default");
+    }
+
+    #[test]
+    fn test_build_docker_invocation_applies_network_allowlist() {
+        let mut config = Config::default();
+        config.container_agent.network = "bridge".to_string();
+        config.container_agent.network_allowlist = vec![
+            "api.anthropic.com:443".to_string(),
+            "10.0.0.0/8".to_string(),
+        ];
+        let bus = Arc::new(MessageBus::new());
+        let proxy = ContainerAgentProxy::new(config, bus, ResolvedBackend::Docker);
+
+        let temp_root =
+            std::env::temp_dir().join(format!("zeptoclaw-egress-test-{}", Uuid::new_v4()));
+        let invocation = proxy
+            .build_docker_invocation(
+                &temp_root.join("workspace"),
+                &temp_root.join("sessions"),
+                &temp_root.join("config.json"),
+                AgentMode::OneShot,
+            )
+            .unwrap();
+        let args = &invocation.args;
+
+        let network = args.iter().position(|a| a == "--network").unwrap();
+        assert_eq!(args[network + 1], "bridge");
+        let cap = args.iter().position(|a| a == "--cap-add").unwrap();
+        assert_eq!(args[cap + 1], "NET_ADMIN");
+
+        let image = args.iter().position(|a| a == "zeptoclaw:latest").unwrap();
+        assert_eq!(args[image + 1..image + 3], ["sh", "-c"]);
+        assert!(args[image + 3].contains("-d api.anthropic.com -p tcp --dport 443 -j ACCEPT"));
+        assert!(args[image + 3].contains("-d 10.0.0.0/8 -j ACCEPT"));
+        assert_eq!(
+            args[image + 4..],
+            ["zeptoclaw-egress", "zeptoclaw", "agent-stdin"]
+        );
+    }
+
+    #[test]
+    fn test_build_docker_invocation_rejects_allowlist_without_network() {
+        let mut config = Config::default();
+        config.container_agent.network_allowlist = vec!["api.anthropic.com".to_string()];
+        let bus = Arc::new(MessageBus::new());
+        let proxy = ContainerAgentProxy::new(config, bus, ResolvedBackend::Docker);
+
+        let temp_root =
+            std::env::temp_dir().join(format!("zeptoclaw-egress-test-{}", Uuid::new_v4()));
+        let err = proxy
+            .build_docker_invocation(
+                &temp_root.join("workspace"),
+                &temp_root.join("sessions"),
+                &temp_root.join("config.json"),
+                AgentMode::OneShot,
+            )
+            .unwrap_err();
+        assert!(err.to_string().contains("network_allowlist"));
+    }
+
+    #[test]
+    fn test_build_podman_invocation_uses_podman_binary_and_default_network() {
+        let mut config = Config::default();
+        config.container_agent.network = "bridge".to_string();
+        // docker_binary applies to the Docker backend only.
+        config.container_agent.docker_binary = Some("/nonexistent/docker".to_string());
+        config.providers.anthropic = Some(ProviderConfig {
+            api_key: Some("secret-anthropic-key".to_string()),
+            ..Default::default()
+        });
+        let bus = Arc::new(MessageBus::new());
+        let proxy = ContainerAgentProxy::new(config, bus, ResolvedBackend::Podman);
+
+        let temp_root =
+            std::env::temp_dir().join(format!("zeptoclaw-podman-test-{}", Uuid::new_v4()));
+        let workspace_dir = temp_root.join("workspace");
+        let sessions_dir = temp_root.join("sessions");
+        let config_path = temp_root.join("config.json");
+
+        let invocation = proxy
+            .build_podman_invocation(
+                &workspace_dir,
+                &sessions_dir,
+                &config_path,
+                AgentMode::OneShot,
+            )
+            .unwrap();
+        assert_eq!(invocation.binary, "podman");
+        assert!(!invocation.args.iter().any(|arg| arg == "--network"));
+        assert!(has_arg_pair(
+            &invocation.args,
+            "-e",
+            "ZEPTOCLAW_PROVIDERS_ANTHROPIC_API_KEY"
+        ));
+        assert!(!invocation
+            .args
+            .iter()
+            .any(|arg| arg.contains("secret-anthropic-key")));
+        let tail = &invocation.args[invocation.args.len() - 3..];
+        assert_eq!(tail, ["zeptoclaw:latest", "zeptoclaw", "agent-stdin"]);
+        assert_eq!(ResolvedBackend::Podman.to_string(), "podman");
+    }
+
+    #[test]
+    fn test_build_podman_invocation_keeps_explicit_network() {
+        let config = Config::default();
+        let bus = Arc::new(MessageBus::new());
+        let proxy = ContainerAgentProxy::new(config, bus, ResolvedBackend::Podman);
+
+        let temp_root =
+            std::env::temp_dir().join(format!("zeptoclaw-podman-test-{}", Uuid::new_v4()));
+        let invocation = proxy
+            .build_podman_invocation(
+                &temp_root.join("workspace"),
+                &temp_root.join("sessions"),
+                &temp_root.join("config.json"),
+                AgentMode::Persistent,
+            )
+            .unwrap();
+        assert!(has_arg_pair(&invocation.args, "--network", "none"));
+        let tail = &invocation.args[invocation.args.len() - 3..];
+        assert_eq!(tail, ["sh", "-c", PERSISTENT_AGENT_LOOP]);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_proxy_pool_reuses_and_respawns_containers() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let manifest_dir = env!("CARGO_MANIFEST_DIR");
+        let mock_dir = std::path::PathBuf::from(manifest_dir)
+            .join("target")
+            .join("test-mocks");
+        std::fs::create_dir_all(&mock_dir).unwrap();
+        let id = Uuid::new_v4();
+        let script_path = mock_dir.join(format!("mock-docker-pool-{}.sh", id));
+        let spawn_log = mock_dir.join(format!("mock-docker-pool-{}.log", id));
+
+        // Each "container" logs its start, answers two requests, then exits
+        // so the proxy has to notice and respawn.
+        let response = r#"{"request_id":"mock-req","result":{"Success":{"content":"pooled response","session":null}}}"#;
+        let script = format!(
+            r#"#!/bin/sh
+echo started >> '{}'
+for i in 1 2; do
+  IFS= read -r line || exit 0
+  printf '<<<AGENT_RESPONSE_START>>>\n%s\n<<<AGENT_RESPONSE_END>>>\n' '{}'
+done
+"#,
+            spawn_log.display(),
+            response
+        );
+        std::fs::write(&script_path, script).unwrap();
+        let mut permissions = std::fs::metadata(&script_path).unwrap().permissions();
+        permissions.set_mode(0o755);
+        std::fs::set_permissions(&script_path, permissions).unwrap();
+
+        let mut config = Config::default();
+        config.container_agent.image = "mock-image:latest".to_string();
+        config.container_agent.timeout_secs = 5;
+        config.container_agent.max_concurrent = 1;
+        config.container_agent.pool_size = 1;
+        config.container_agent.docker_binary = Some(script_path.to_string_lossy().to_string());
+
+        let bus = Arc::new(MessageBus::new());
+        let proxy = Arc::new(ContainerAgentProxy::new(
+            config,
+            bus.clone(),
+            ResolvedBackend::Docker,
+        ));
+        let proxy_task = Arc::clone(&proxy);
+        let handle = tokio::spawn(async move { proxy_task.start().await });
+
+        for i in 0..3 {
+            let chat_id = format!("pool-chat-{}-{}", id, i);
+            bus.publish_inbound(InboundMessage::new("test", "u1", &chat_id, "hello"))
+                .await
+                .unwrap();
+            let outbound = timeout(Duration::from_secs(5), bus.consume_outbound())
+                .await
+                .expect("should receive outbound within timeout")
+                .expect("outbound should be present");
+            assert_eq!(outbound.chat_id, chat_id);
+            assert_eq!(outbound.content, "pooled response");
+            // Let the mock exit after its second answer before the next request.
+            sleep(Duration::from_millis(100)).await;
+        }
+
+        // One warm container served two requests, then one respawn.
+        let spawns = std::fs::read_to_string(&spawn_log).unwrap();
+        assert_eq!(spawns.lines().count(), 2, "spawn log: {:?}", spawns);
+
+        proxy.stop();
+        timeout(Duration::from_secs(2), handle)
+            .await
+            .expect("proxy should stop quickly")
+            .expect("proxy task join should succeed")
+            .expect("proxy start should return ok");
+        assert_eq!(proxy.pool.as_ref().unwrap().idle_count(), 0);
+
+        let _ = std::fs::remove_file(&script_path);
+        let _ = std::fs::remove_file(&spawn_log);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_proxy_processes_messages_concurrently_and_drains_on_stop() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let manifest_dir = env!("CARGO_MANIFEST_DIR");
+        let mock_dir = std::path::PathBuf::from(manifest_dir)
+            .join("target")
+            .join("test-mocks");
+        std::fs::create_dir_all(&mock_dir).unwrap();
+        let script_path = mock_dir.join(format!("mock-docker-slow-{}.sh", Uuid::new_v4()));
+
+        // Every container takes 2s to answer.
+        let response = r#"{"request_id":"mock-req","result":{"Success":{"content":"slow response","session":null}}}"#;
+        let script = format!(
+            r#"#!/bin/sh
+cat >/dev/null
+sleep 2
+printf '<<<AGENT_RESPONSE_START>>>\n%s\n<<<AGENT_RESPONSE_END>>>\n' '{}'
+"#,
+            response
+        );
+        std::fs::write(&script_path, script).unwrap();
+        let mut permissions = std::fs::metadata(&script_path).unwrap().permissions();
+        permissions.set_mode(0o755);
+        std::fs::set_permissions(&script_path, permissions).unwrap();
+
+        let mut config = Config::default();
+        config.container_agent.image = "mock-image:latest".to_string();
+        config.container_agent.timeout_secs = 10;
+        config.container_agent.max_concurrent = 2;
+        config.container_agent.docker_binary = Some(script_path.to_string_lossy().to_string());
+
+        let bus = Arc::new(MessageBus::new());
+        let proxy = Arc::new(ContainerAgentProxy::new(
+            config,
+            bus.clone(),
+            ResolvedBackend::Docker,
+        ));
+        let proxy_task = Arc::clone(&proxy);
+        let handle = tokio::spawn(async move { proxy_task.start().await });
+
+        let started = std::time::Instant::now();
+        for i in 0..2 {
+            let chat_id = format!("slow-chat-{}", i);
+            bus.publish_inbound(InboundMessage::new("test", "u1", &chat_id, "hello"))
+                .await
+                .unwrap();
+        }
+
+        // Stop right away: both in-flight requests must still be answered.
+        sleep(Duration::from_millis(200)).await;
+        proxy.stop();
+
+        let mut chats = Vec::new();
+        for _ in 0..2 {
+            let outbound = timeout(Duration::from_secs(5), bus.consume_outbound())
+                .await
+                .expect("should receive outbound within timeout")
+                .expect("outbound should be present");
+            assert_eq!(outbound.content, "slow response");
+            chats.push(outbound.chat_id);
+        }
+        chats.sort();
+        assert_eq!(chats, vec!["slow-chat-0", "slow-chat-1"]);
+        // Serialized handling would take at least 4s.
+        assert!(
+            started.elapsed() < Duration::from_millis(3500),
+            "messages were not handled concurrently: {:?}",
+            started.elapsed()
+        );
+
+        let summary = timeout(Duration::from_secs(2), handle)
+            .await
+            .expect("proxy should stop after draining")
+            .expect("proxy task join should succeed")
+            .expect("proxy start should return ok");
+        assert_eq!(
+            summary,
+            DrainSummary {
+                drained: 2,
+                cancelled: 0
+            }
+        );
+        assert!(!proxy.is_running());
+
+        let _ = std::fs::remove_file(&script_path);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_drain_publishes_in_flight_response_and_cancels_stragglers() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let manifest_dir = env!("CARGO_MANIFEST_DIR");
+        let mock_dir = std::path::PathBuf::from(manifest_dir)
+            .join("target")
+            .join("test-mocks");
+        std::fs::create_dir_all(&mock_dir).unwrap();
+        let script_path = mock_dir.join(format!("mock-docker-drain-{}.sh", Uuid::new_v4()));
+
+        // Requests mentioning "stuck" never answer; the rest take 1s.
+        let response = r#"{"request_id":"mock-req","result":{"Success":{"content":"drained response","session":null}}}"#;
+        let script = format!(
+            r#"#!/bin/sh
+input=$(cat)
+case "$input" in
+  *stuck*) sleep 30 ;;
+  *) sleep 1 ;;
+esac
+printf '<<<AGENT_RESPONSE_START>>>\n%s\n<<<AGENT_RESPONSE_END>>>\n' '{}'
+"#,
+            response
+        );
+        std::fs::write(&script_path, script).unwrap();
+        let mut permissions = std::fs::metadata(&script_path).unwrap().permissions();
+        permissions.set_mode(0o755);
+        std::fs::set_permissions(&script_path, permissions).unwrap();
+
+        let mut config = Config::default();
+        config.container_agent.image = "mock-image:latest".to_string();
+        config.container_agent.timeout_secs = 60;
+        config.container_agent.drain_timeout_secs = 3;
+        config.container_agent.docker_binary = Some(script_path.to_string_lossy().to_string());
+
+        let bus = Arc::new(MessageBus::new());
+        let proxy = Arc::new(ContainerAgentProxy::new(
+            config,
+            bus.clone(),
+            ResolvedBackend::Docker,
+        ));
+        let proxy_task = Arc::clone(&proxy);
+        let handle = tokio::spawn(async move { proxy_task.start().await });
+
+        for (chat_id, content) in [("drain-ok", "hello"), ("drain-stuck", "stuck")] {
+            bus.publish_inbound(InboundMessage::new("test", "u1", chat_id, content))
+                .await
+                .unwrap();
+        }
+        sleep(Duration::from_millis(200)).await;
+        proxy.stop();
+
+        let summary = timeout(Duration::from_secs(10), handle)
+            .await
+            .expect("proxy should stop once the drain timeout expires")
+            .expect("proxy task join should succeed")
+            .expect("proxy start should return ok");
+        assert_eq!(
+            summary,
+            DrainSummary {
+                drained: 1,
+                cancelled: 1
+            }
+        );
+
+        let outbound = timeout(Duration::from_secs(1), bus.consume_outbound())
+            .await
+            .expect("drained response should be published")
+            .expect("outbound should be present");
+        assert_eq!(outbound.chat_id, "drain-ok");
+        assert_eq!(outbound.content, "drained response");
+        assert_eq!(bus.outbound_len(), 0);
+
+        let _ = std::fs::remove_file(&script_path);
+    }
+
     #[test]
     fn test_container_agent_backend_serde_roundtrip() {
         // Auto
@@ -1167,6 +1915,12 @@ EOF
         let back: ContainerAgentBackend = serde_json::from_str(&json).unwrap();
         assert_eq!(back, ContainerAgentBackend::Docker);
 
+        // Podman
+        let json = serde_json::to_string(&ContainerAgentBackend::Podman).unwrap();
+        assert_eq!(json, "\"podman\"");
+        let back: ContainerAgentBackend = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, ContainerAgentBackend::Podman);
+
         // Apple (macOS only)
         #[cfg(target_os = "macos")]
         {
@@ -1197,7 +1951,12 @@ EOF
         std::fs::create_dir_all(&workspace_dir).unwrap();
         std::fs::create_dir_all(&sessions_dir).unwrap();
 
-        let result = proxy.build_docker_invocation(&workspace_dir, &sessions_dir, &config_path);
+        let result = proxy.build_docker_invocation(
+            &workspace_dir,
+            &sessions_dir,
+            &config_path,
+            AgentMode::OneShot,
+        );
         assert!(result.is_err());
         let err_msg = result.unwrap_err().to_string();
         assert!(
@@ -1224,7 +1983,12 @@ EOF
         std::fs::create_dir_all(&workspace_dir).unwrap();
         std::fs::create_dir_all(&sessions_dir).unwrap();
 
-        let result = proxy.build_docker_invocation(&workspace_dir, &sessions_dir, &config_path);
+        let result = proxy.build_docker_invocation(
+            &workspace_dir,
+            &sessions_dir,
+            &config_path,
+            AgentMode::OneShot,
+        );
         assert!(result.is_err());
         let err_msg = result.unwrap_err().to_string();
         assert!(
@@ -1251,7 +2015,12 @@ EOF
         std::fs::create_dir_all(&workspace_dir).unwrap();
         std::fs::create_dir_all(&sessions_dir).unwrap();
 
-        let result = proxy.build_docker_invocation(&workspace_dir, &sessions_dir, &config_path);
+        let result = proxy.build_docker_invocation(
+            &workspace_dir,
+            &sessions_dir,
+            &config_path,
+            AgentMode::OneShot,
+        );
         assert!(result.is_err());
         let err_msg = result.unwrap_err().to_string();
         assert!(
@@ -1280,7 +2049,12 @@ EOF
         std::fs::create_dir_all(&workspace_dir).unwrap();
         std::fs::create_dir_all(&sessions_dir).unwrap();
 
-        let result = proxy.build_docker_invocation(&workspace_dir, &sessions_dir, &config_path);
+        let result = proxy.build_docker_invocation(
+            &workspace_dir,
+            &sessions_dir,
+            &config_path,
+            AgentMode::OneShot,
+        );
         assert!(result.is_err());
         let err_msg = result.unwrap_err().to_string();
         assert!(
@@ -1315,7 +2089,12 @@ EOF
         std::fs::create_dir_all(&workspace_dir).unwrap();
         std::fs::create_dir_all(&sessions_dir).unwrap();
 
-        let result = proxy.build_docker_invocation(&workspace_dir, &sessions_dir, &config_path);
+        let result = proxy.build_docker_invocation(
+            &workspace_dir,
+            &sessions_dir,
+            &config_path,
+            AgentMode::OneShot,
+        );
         assert!(result.is_ok(), "Safe mount should be accepted");
         let invocation = result.unwrap();
         assert!(
@@ -0,0 +1,298 @@
+//! Warm pool of long-lived agent containers
+//!
+//! With `container_agent.pool_size > 0` the proxy keeps containers running
+//! `zeptoclaw agent-stdin` in a loop instead of starting one per request.
+//! Each request is written as one JSON line to an idle container's stdin and
+//! its stdout is read up to the response end marker; the container is then
+//! returned to the pool for the next request.
+
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, ChildStdout, Command};
+use tracing::{debug, warn};
+
+use crate::error::ZeptoError;
+
+use super::ipc::{parse_marked_response, AgentResponse, RESPONSE_END_MARKER};
+
+/// Shell loop run inside pooled containers: handle requests until stdin is
+/// closed (`agent-stdin` fails on EOF, which ends the loop).
+pub(super) const PERSISTENT_AGENT_LOOP: &str = "while zeptoclaw agent-stdin; do :; done";
+
+/// Error returned when a container does not answer within the timeout.
+pub(super) fn container_timeout_error(timeout_secs: u64) -> ZeptoError {
+    ZeptoError::Config(format!(
+        "Container timeout after {}s: process killed",
+        timeout_secs
+    ))
+}
+
+/// Why a request on a pooled container did not produce a response.
+#[derive(Debug)]
+pub(super) enum ExchangeError {
+    /// The request could not be delivered (container gone, stdin broken).
+    /// Safe to retry on a fresh container.
+    Unavailable(ZeptoError),
+    /// The request was delivered but no valid response came back.
+    Failed(ZeptoError),
+}
+
+/// A running container process handling one request at a time.
+///
+/// Dropping it kills the container client process and removes its temp dir.
+pub(super) struct PooledContainer {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    temp_dir: Option<PathBuf>,
+}
+
+impl PooledContainer {
+    /// Spawn a pooled container from a prepared command.
+    pub(super) fn spawn(
+        command: &mut Command,
+        temp_dir: Option<PathBuf>,
+    ) -> Result<Self, ZeptoError> {
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| ZeptoError::Config(format!("Failed to spawn container: {}", e)))?;
+
+        let (Some(stdin), Some(stdout)) = (child.stdin.take(), child.stdout.take()) else {
+            return Err(ZeptoError::Config(
+                "Failed to attach to container stdio".into(),
+            ));
+        };
+
+        // Drain stderr so a chatty container cannot block on a full pipe.
+        if let Some(stderr) = child.stderr.take() {
+            tokio::spawn(async move {
+                let mut lines = BufReader::new(stderr).lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    debug!(target: "zeptoclaw::gateway::container", "{}", line);
+                }
+            });
+        }
+
+        Ok(Self {
+            child,
+            stdin,
+            stdout: BufReader::new(stdout),
+            temp_dir,
+        })
+    }
+
+    /// Returns `true` if the container process is still running.
+    pub(super) fn is_alive(&mut self) -> bool {
+        matches!(self.child.try_wait(), Ok(None))
+    }
+
+    /// Send one request line and read stdout up to the response end marker.
+    pub(super) async fn exchange(
+        &mut self,
+        request_json: &str,
+        timeout: Duration,
+    ) -> Result<AgentResponse, ExchangeError> {
+        let write = async {
+            self.stdin.write_all(request_json.as_bytes()).await?;
+            self.stdin.write_all(b"\n").await?;
+            self.stdin.flush().await
+        };
+        write.await.map_err(|e| {
+            ExchangeError::Unavailable(ZeptoError::Config(format!(
+                "Failed to write to container stdin: {}",
+                e
+            )))
+        })?;
+
+        let read = async {
+            let mut output = String::new();
+            loop {
+                let mut line = String::new();
+                let read = self.stdout.read_line(&mut line).await.map_err(|e| {
+                    ZeptoError::Config(format!("Failed to read container output: {}", e))
+                })?;
+                if read == 0 {
+                    return Err(ZeptoError::Config(
+                        "Container exited before responding".into(),
+                    ));
+                }
+                output.push_str(&line);
+                if line.trim() == RESPONSE_END_MARKER {
+                    return parse_marked_response(&output).ok_or_else(|| {
+                        ZeptoError::Config("Failed to parse container response".into())
+                    });
+                }
+            }
+        };
+
+        match tokio::time::timeout(timeout, read).await {
+            Ok(result) => result.map_err(ExchangeError::Failed),
+            Err(_) => {
+                warn!(
+                    timeout_secs = timeout.as_secs(),
+                    "Pooled container timed out; discarding it"
+                );
+                Err(ExchangeError::Failed(container_timeout_error(
+                    timeout.as_secs(),
+                )))
+            }
+        }
+    }
+}
+
+impl Drop for PooledContainer {
+    fn drop(&mut self) {
+        if let Some(ref temp_dir) = self.temp_dir {
+            if let Err(e) = std::fs::remove_dir_all(temp_dir) {
+                warn!("Failed to clean up temp env dir {:?}: {}", temp_dir, e);
+            }
+        }
+    }
+}
+
+/// Idle pooled containers, capped at `size`.
+pub(super) struct ContainerPool {
+    size: usize,
+    idle: Mutex<Vec<PooledContainer>>,
+}
+
+impl ContainerPool {
+    /// Create an empty pool that retains at most `size` idle containers.
+    pub(super) fn new(size: usize) -> Self {
+        Self {
+            size,
+            idle: Mutex::new(Vec::with_capacity(size)),
+        }
+    }
+
+    /// Maximum number of idle containers kept alive.
+    pub(super) fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Number of idle containers currently in the pool.
+    pub(super) fn idle_count(&self) -> usize {
+        self.idle.lock().map(|idle| idle.len()).unwrap_or(0)
+    }
+
+    /// Take an idle, still-running container, discarding dead ones.
+    pub(super) fn take_idle(&self) -> Option<PooledContainer> {
+        let mut idle = self.idle.lock().ok()?;
+        while let Some(mut container) = idle.pop() {
+            if container.is_alive() {
+                return Some(container);
+            }
+            debug!("Discarding exited pooled container");
+        }
+        None
+    }
+
+    /// Return a container to the pool; it is dropped (killed) if the pool is
+    /// already full.
+    pub(super) fn put_back(&self, container: PooledContainer) {
+        if let Ok(mut idle) = self.idle.lock() {
+            if idle.len() < self.size {
+                idle.push(container);
+            }
+        }
+    }
+
+    /// Drop every idle container.
+    pub(super) fn drain(&self) {
+        if let Ok(mut idle) = self.idle.lock() {
+            idle.clear();
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    const RESPONSE: &str =
+        r#"{"request_id":"r1","result":{"Success":{"content":"pong","session":null}}}"#;
+
+    fn echo_agent() -> Command {
+        // Answers every input line with a marked response, like the
+        // persistent agent loop does.
+        let script = format!(
+            "while IFS= read -r line; do printf '<<<AGENT_RESPONSE_START>>>\\n%s\\n<<<AGENT_RESPONSE_END>>>\\n' '{}'; done",
+            RESPONSE
+        );
+        let mut command = Command::new("sh");
+        command.arg("-c").arg(script);
+        command
+    }
+
+    #[tokio::test]
+    async fn test_exchange_reuses_container() {
+        let mut container = PooledContainer::spawn(&mut echo_agent(), None).unwrap();
+        for _ in 0..3 {
+            let response = container
+                .exchange("{}", Duration::from_secs(5))
+                .await
+                .unwrap();
+            assert_eq!(response.request_id, "r1");
+        }
+        assert!(container.is_alive());
+    }
+
+    #[tokio::test]
+    async fn test_exchange_detects_dead_container() {
+        let mut command = Command::new("sh");
+        command.arg("-c").arg("exit 0");
+        let mut container = PooledContainer::spawn(&mut command, None).unwrap();
+        let _ = container.child.wait().await;
+        assert!(!container.is_alive());
+
+        let err = container
+            .exchange("{}", Duration::from_secs(5))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ExchangeError::Unavailable(_) | ExchangeError::Failed(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_exchange_timeout() {
+        let mut command = Command::new("sh");
+        command.arg("-c").arg("read line; sleep 10");
+        let mut container = PooledContainer::spawn(&mut command, None).unwrap();
+        let err = container
+            .exchange("{}", Duration::from_secs(1))
+            .await
+            .unwrap_err();
+        match err {
+            ExchangeError::Failed(e) => assert!(e.to_string().contains("timeout after 1s")),
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pool_caps_idle_and_skips_dead() {
+        let pool = ContainerPool::new(1);
+        pool.put_back(PooledContainer::spawn(&mut echo_agent(), None).unwrap());
+        pool.put_back(PooledContainer::spawn(&mut echo_agent(), None).unwrap());
+        assert_eq!(pool.idle_count(), 1);
+
+        let mut container = pool.take_idle().expect("idle container");
+        assert!(pool.take_idle().is_none());
+        container.child.kill().await.unwrap();
+        pool.put_back(container);
+        assert!(pool.take_idle().is_none(), "dead container must be skipped");
+
+        pool.put_back(PooledContainer::spawn(&mut echo_agent(), None).unwrap());
+        pool.drain();
+        assert_eq!(pool.idle_count(), 0);
+    }
+}
@@ -0,0 +1,492 @@
+//! Monitoring endpoint for a running gateway.
+//!
+//! When `gateway.metrics_addr` is set, [`start_metrics_server`] serves:
+//! - `GET /healthz` → 200 while the gateway message loop is running, 503 after
+//!   it stopped
+//! - `GET /metrics` → a [`MetricsSnapshot`] as JSON, or in the Prometheus text
+//!   format when asked for with `?format=prometheus` or an `Accept` header
+//!   naming `text/plain` / `openmetrics`
+//!
+//! The snapshot combines the usage counters, [`MessageBus`] queue depths,
+//! per-channel running state, the provider health snapshot and hook rule
+//! counters. Like the
+//! health server it speaks minimal HTTP over raw TCP to avoid a web
+//! framework dependency.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::net::SocketAddr;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Serialize;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::bus::MessageBus;
+use crate::channels::ChannelManager;
+use crate::health::UsageMetrics;
+use crate::hooks::{HookEngine, HookStats};
+use crate::providers::{LLMProvider, ProviderHealthStatus};
+
+/// Sources the metrics endpoint reads from.
+pub struct GatewayMetrics {
+    bus: Arc<MessageBus>,
+    usage: Arc<UsageMetrics>,
+    channels: Option<Arc<ChannelManager>>,
+    provider: Option<Arc<dyn LLMProvider>>,
+    hooks: Option<Arc<HookEngine>>,
+}
+
+/// Point-in-time view of the gateway served by `/metrics`.
+#[derive(Debug, Clone, Serialize)]
+pub struct MetricsSnapshot {
+    /// Whether the gateway message loop is running.
+    pub alive: bool,
+    /// Request, tool call, token and error counters.
+    pub usage: UsageCounters,
+    /// Message bus queue depths.
+    pub bus: BusDepths,
+    /// Running state of each registered channel.
+    pub channels: BTreeMap<String, bool>,
+    /// Provider health, when the provider tracks it (rotation).
+    pub providers: Vec<ProviderHealthStatus>,
+    /// Hook rule counters, when the gateway runs an in-process agent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hooks: Option<HookStats>,
+}
+
+/// Usage counters since the gateway started.
+#[derive(Debug, Clone, Serialize)]
+pub struct UsageCounters {
+    pub requests: u64,
+    pub tool_calls: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub errors: u64,
+}
+
+/// Messages waiting in each [`MessageBus`] queue.
+#[derive(Debug, Clone, Serialize)]
+pub struct BusDepths {
+    pub inbound: usize,
+    pub outbound: usize,
+    pub dead_letters: usize,
+    /// Capacity of each queue.
+    pub capacity: usize,
+}
+
+impl GatewayMetrics {
+    /// Report bus depths and usage counters.
+    pub fn new(bus: Arc<MessageBus>, usage: Arc<UsageMetrics>) -> Self {
+        Self {
+            bus,
+            usage,
+            channels: None,
+            provider: None,
+            hooks: None,
+        }
+    }
+
+    /// Also report per-channel running state.
+    pub fn with_channels(mut self, channels: Arc<ChannelManager>) -> Self {
+        self.channels = Some(channels);
+        self
+    }
+
+    /// Also report the provider health snapshot.
+    pub fn with_provider(mut self, provider: Arc<dyn LLMProvider>) -> Self {
+        self.provider = Some(provider);
+        self
+    }
+
+    /// Also report hook rule counters.
+    pub fn with_hooks(mut self, hooks: Arc<HookEngine>) -> Self {
+        self.hooks = Some(hooks);
+        self
+    }
+
+    /// Whether the gateway message loop is running.
+    pub fn is_alive(&self) -> bool {
+        self.usage.ready.load(Ordering::SeqCst)
+    }
+
+    /// Collect the current metrics.
+    pub async fn snapshot(&self) -> MetricsSnapshot {
+        let channels = match &self.channels {
+            Some(manager) => manager.channel_states().await.into_iter().collect(),
+            None => BTreeMap::new(),
+        };
+        MetricsSnapshot {
+            alive: self.is_alive(),
+            usage: UsageCounters {
+                requests: self.usage.requests.load(Ordering::Relaxed),
+                tool_calls: self.usage.tool_calls.load(Ordering::Relaxed),
+                input_tokens: self.usage.input_tokens.load(Ordering::Relaxed),
+                output_tokens: self.usage.output_tokens.load(Ordering::Relaxed),
+                errors: self.usage.errors.load(Ordering::Relaxed),
+            },
+            bus: BusDepths {
+                inbound: self.bus.inbound_len(),
+                outbound: self.bus.outbound_len(),
+                dead_letters: self.bus.dead_letter_len(),
+                capacity: self.bus.capacity(),
+            },
+            channels,
+            providers: self
+                .provider
+                .as_ref()
+                .map(|provider| provider.health_snapshot())
+                .unwrap_or_default(),
+            hooks: self.hooks.as_ref().map(|hooks| hooks.stats()),
+        }
+    }
+}
+
+impl MetricsSnapshot {
+    /// Render in the Prometheus text exposition format.
+    pub fn to_prometheus(&self) -> String {
+        let mut out = String::new();
+        let mut metric = |name: &str, kind: &str, help: &str, samples: &[(String, u64)]| {
+            let _ = writeln!(out, "# HELP zeptoclaw_{} {}", name, help);
+            let _ = writeln!(out, "# TYPE zeptoclaw_{} {}", name, kind);
+            for (labels, value) in samples {
+                let _ = writeln!(out, "zeptoclaw_{}{} {}", name, labels, value);
+            }
+        };
+        let plain = |value: u64| vec![(String::new(), value)];
+
+        metric(
+            "up",
+            "gauge",
+            "Whether the gateway message loop is running.",
+            &plain(self.alive as u64),
+        );
+        let usage = &self.usage;
+        for (name, help, value) in [
+            ("requests_total", "Requests processed.", usage.requests),
+            ("tool_calls_total", "Tool calls executed.", usage.tool_calls),
+            (
+                "input_tokens_total",
+                "LLM input tokens.",
+                usage.input_tokens,
+            ),
+            (
+                "output_tokens_total",
+                "LLM output tokens.",
+                usage.output_tokens,
+            ),
+            ("errors_total", "Errors encountered.", usage.errors),
+        ] {
+            metric(name, "counter", help, &plain(value));
+        }
+        metric(
+            "bus_queue_depth",
+            "gauge",
+            "Messages waiting in a message bus queue.",
+            &[
+                ("inbound", self.bus.inbound),
+                ("outbound", self.bus.outbound),
+                ("dead_letter", self.bus.dead_letters),
+            ]
+            .map(|(queue, depth)| (labels(&[("queue", queue)]), depth as u64)),
+        );
+        metric(
+            "bus_queue_capacity",
+            "gauge",
+            "Capacity of each message bus queue.",
+            &plain(self.bus.capacity as u64),
+        );
+        metric(
+            "channel_running",
+            "gauge",
+            "Whether a channel is running.",
+            &self
+                .channels
+                .iter()
+                .map(|(name, running)| (labels(&[("channel", name)]), *running as u64))
+                .collect::<Vec<_>>(),
+        );
+        metric(
+            "provider_healthy",
+            "gauge",
+            "Whether a provider is eligible for selection.",
+            &self
+                .providers
+                .iter()
+                .map(|p| (labels(&[("provider", &p.name)]), p.healthy as u64))
+                .collect::<Vec<_>>(),
+        );
+        metric(
+            "provider_consecutive_failures",
+            "gauge",
+            "Consecutive failures of a provider.",
+            &self
+                .providers
+                .iter()
+                .map(|p| (labels(&[("provider", &p.name)]), p.failure_count as u64))
+                .collect::<Vec<_>>(),
+        );
+        if let Some(hooks) = &self.hooks {
+            metric(
+                "hook_rule_matches_total",
+                "counter",
+                "Hook rules matched, by hook point and action.",
+                &hooks
+                    .matched
+                    .iter()
+                    .flat_map(|(hook, actions)| {
+                        actions.iter().map(|(action, count)| {
+                            (labels(&[("hook", hook), ("action", action)]), *count)
+                        })
+                    })
+                    .collect::<Vec<_>>(),
+            );
+            metric(
+                "hook_blocked_total",
+                "counter",
+                "Tool calls blocked by hook rules.",
+                &plain(hooks.blocked),
+            );
+            metric(
+                "hook_rewritten_total",
+                "counter",
+                "Tool calls whose arguments hook rules rewrote.",
+                &plain(hooks.rewritten),
+            );
+        }
+        out
+    }
+}
+
+/// A label set, escaped for the Prometheus text format.
+fn labels(pairs: &[(&str, &str)]) -> String {
+    let pairs: Vec<String> = pairs
+        .iter()
+        .map(|(key, value)| {
+            let escaped = value
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('\n', "\\n");
+            format!("{}=\"{}\"", key, escaped)
+        })
+        .collect();
+    format!("{{{}}}", pairs.join(","))
+}
+
+/// Start the metrics server on `addr` (e.g. `127.0.0.1:9091`).
+///
+/// Returns the bound address and the server task, which the caller aborts
+/// on shutdown.
+pub async fn start_metrics_server(
+    addr: &str,
+    metrics: Arc<GatewayMetrics>,
+) -> std::io::Result<(SocketAddr, JoinHandle<()>)> {
+    let listener = TcpListener::bind(addr).await?;
+    let local_addr = listener.local_addr()?;
+    info!(addr = %local_addr, "Gateway metrics server listening");
+
+    let handle = tokio::spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((stream, _)) => {
+                    tokio::spawn(handle_connection(stream, Arc::clone(&metrics)));
+                }
+                Err(e) => warn!(error = %e, "Metrics server accept error"),
+            }
+        }
+    });
+    Ok((local_addr, handle))
+}
+
+async fn handle_connection(mut stream: TcpStream, metrics: Arc<GatewayMetrics>) {
+    // Read the request with a 5s timeout to prevent slowloris DoS
+    let mut buf = [0u8; 2048];
+    let n = match tokio::time::timeout(Duration::from_secs(5), stream.read(&mut buf)).await {
+        Ok(Ok(n)) => n,
+        _ => return,
+    };
+    let request = String::from_utf8_lossy(&buf[..n]);
+    let mut lines = request.lines();
+    let mut parts = lines.next().unwrap_or_default().split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let target = parts.next().unwrap_or_default();
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let accept = lines
+        .find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            name.trim()
+                .eq_ignore_ascii_case("accept")
+                .then(|| value.to_ascii_lowercase())
+        })
+        .unwrap_or_default();
+
+    let (status, content_type, body) = match (method, path) {
+        ("GET", "/healthz") if metrics.is_alive() => (
+            "200 OK",
+            "application/json",
+            r#"{"status":"ok"}"#.to_string(),
+        ),
+        ("GET", "/healthz") => (
+            "503 Service Unavailable",
+            "application/json",
+            r#"{"status":"down"}"#.to_string(),
+        ),
+        ("GET", "/metrics") => {
+            let snapshot = metrics.snapshot().await;
+            let prometheus = query.split('&').any(|pair| pair == "format=prometheus")
+                || accept.contains("text/plain")
+                || accept.contains("openmetrics");
+            if prometheus {
+                (
+                    "200 OK",
+                    "text/plain; version=0.0.4",
+                    snapshot.to_prometheus(),
+                )
+            } else {
+                (
+                    "200 OK",
+                    "application/json",
+                    serde_json::to_string(&snapshot).unwrap_or_default(),
+                )
+            }
+        }
+        _ => (
+            "404 Not Found",
+            "application/json",
+            r#"{"error":"not_found"}"#.to_string(),
+        ),
+    };
+
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    );
+    let _ = stream.write_all(response.as_bytes()).await;
+    let _ = stream.shutdown().await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bus::OutboundMessage;
+    use crate::config::Config;
+
+    async fn get(addr: SocketAddr, request: &str) -> String {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    #[tokio::test]
+    async fn test_healthz_follows_message_loop() {
+        let usage = Arc::new(UsageMetrics::new());
+        usage.set_ready(true);
+        let metrics = GatewayMetrics::new(Arc::new(MessageBus::new()), Arc::clone(&usage));
+        let (addr, handle) = start_metrics_server("127.0.0.1:0", Arc::new(metrics))
+            .await
+            .unwrap();
+
+        let response = get(addr, "GET /healthz HTTP/1.1\r\nHost: localhost\r\n\r\n").await;
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.ends_with(r#"{"status":"ok"}"#));
+
+        usage.set_ready(false);
+        let response = get(addr, "GET /healthz HTTP/1.1\r\n\r\n").await;
+        assert!(response.starts_with("HTTP/1.1 503"));
+
+        let response = get(addr, "GET /nope HTTP/1.1\r\n\r\n").await;
+        assert!(response.starts_with("HTTP/1.1 404"));
+
+        handle.abort();
+    }
+
+    #[tokio::test]
+    async fn test_metrics_json_and_prometheus() {
+        let bus = Arc::new(MessageBus::with_buffer_size(10));
+        bus.publish_outbound(OutboundMessage::new("telegram", "c1", "hi"))
+            .await
+            .unwrap();
+        let usage = Arc::new(UsageMetrics::new());
+        usage.set_ready(true);
+        usage.record_request();
+        let channels = Arc::new(ChannelManager::new(Arc::clone(&bus), Config::default()));
+        let metrics = GatewayMetrics::new(bus, usage).with_channels(channels);
+        let (addr, handle) = start_metrics_server("127.0.0.1:0", Arc::new(metrics))
+            .await
+            .unwrap();
+
+        let response = get(addr, "GET /metrics HTTP/1.1\r\n\r\n").await;
+        let body = response.split("\r\n\r\n").nth(1).unwrap();
+        let json: serde_json::Value = serde_json::from_str(body).unwrap();
+        assert_eq!(json["alive"], true);
+        assert_eq!(json["usage"]["requests"], 1);
+        assert_eq!(json["bus"]["outbound"], 1);
+        assert_eq!(json["bus"]["capacity"], 10);
+        assert_eq!(json["providers"], serde_json::json!([]));
+
+        let response = get(
+            addr,
+            "GET /metrics HTTP/1.1\r\nAccept: text/plain;version=0.0.4\r\n\r\n",
+        )
+        .await;
+        assert!(response.contains("Content-Type: text/plain"));
+        assert!(response.contains("zeptoclaw_up 1\n"));
+        assert!(response.contains("zeptoclaw_bus_queue_depth{queue=\"outbound\"} 1\n"));
+        assert!(response.contains("# TYPE zeptoclaw_requests_total counter\n"));
+
+        let response = get(addr, "GET /metrics?format=prometheus HTTP/1.1\r\n\r\n").await;
+        assert!(response.contains("zeptoclaw_requests_total 1\n"));
+
+        handle.abort();
+    }
+
+    #[test]
+    fn test_prometheus_labels_are_escaped() {
+        assert_eq!(labels(&[("channel", "a\"b\\c")]), r#"{channel="a\"b\\c"}"#);
+        assert_eq!(
+            labels(&[("hook", "before_tool"), ("action", "block")]),
+            r#"{hook="before_tool",action="block"}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn test_hook_stats_in_snapshot() {
+        use crate::hooks::{HookAction, HookRule, HooksConfig};
+
+        let engine = Arc::new(HookEngine::new(HooksConfig {
+            enabled: true,
+            before_tool: vec![HookRule {
+                action: HookAction::Block,
+                tools: vec!["shell".to_string()],
+                ..Default::default()
+            }],
+            ..Default::default()
+        }));
+        engine.before_tool("shell", &serde_json::json!({}), "cli", "c");
+
+        let metrics =
+            GatewayMetrics::new(Arc::new(MessageBus::new()), Arc::new(UsageMetrics::new()));
+        let json = serde_json::to_value(metrics.snapshot().await).unwrap();
+        assert!(json.get("hooks").is_none());
+
+        let snapshot = metrics.with_hooks(engine).snapshot().await;
+        let json = serde_json::to_value(&snapshot).unwrap();
+        assert_eq!(json["hooks"]["blocked"], 1);
+        assert_eq!(json["hooks"]["matched"]["before_tool"]["block"], 1);
+
+        let text = snapshot.to_prometheus();
+        assert!(text.contains(
+            "zeptoclaw_hook_rule_matches_total{hook=\"before_tool\",action=\"block\"} 1\n"
+        ));
+        assert!(text.contains("zeptoclaw_hook_blocked_total 1\n"));
+    }
+}
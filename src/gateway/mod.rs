@@ -5,13 +5,18 @@
 //! scenarios with proper isolation between requests.
 
 pub mod container_agent;
+mod container_pool;
 pub mod ipc;
+pub mod metrics;
+pub mod rate_limit;
 
 #[cfg(target_os = "macos")]
 pub use container_agent::is_apple_container_available;
 pub use container_agent::{
     generate_env_file_content, is_docker_available, is_docker_available_with_binary,
-    resolve_backend, ContainerAgentProxy, ResolvedBackend,
+    is_podman_available, resolve_backend, ContainerAgentProxy, DrainSummary, ResolvedBackend,
 };
 pub use ipc::{parse_marked_response, AgentRequest, AgentResponse, AgentResult};
 pub use ipc::{RESPONSE_END_MARKER, RESPONSE_START_MARKER};
+pub use metrics::{start_metrics_server, GatewayMetrics, MetricsSnapshot};
+pub use rate_limit::{ChatRateLimitConfig, ChatRateLimiter};
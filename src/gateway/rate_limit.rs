@@ -0,0 +1,267 @@
+//! Per-chat inbound rate limiting for the gateway.
+//!
+//! A token bucket is kept for every `channel:chat_id` pair so one noisy chat
+//! cannot monopolize the agent. Messages over the limit are answered with a
+//! short "please slow down" reply instead of being processed or queued.
+//! Senders listed in `exempt_users` are never throttled.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+use crate::bus::{InboundMessage, OutboundMessage};
+
+/// Number of tracked buckets above which idle (full) buckets are pruned.
+const PRUNE_THRESHOLD: usize = 4096;
+
+/// Configuration for per-chat inbound rate limiting (`gateway.rate_limit`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChatRateLimitConfig {
+    /// Whether rate limiting is enabled.
+    pub enabled: bool,
+    /// Sustained number of messages allowed per minute for each chat.
+    pub messages_per_minute: u32,
+    /// Maximum number of messages a chat can send in a burst.
+    pub burst: u32,
+    /// Sender IDs that are never throttled (admins, allowlisted users).
+    pub exempt_users: Vec<String>,
+    /// Reply sent when a message is throttled.
+    pub message: String,
+}
+
+impl Default for ChatRateLimitConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            messages_per_minute: 20,
+            burst: 5,
+            exempt_users: Vec::new(),
+            message:
+                "You're sending messages too quickly. Please slow down and try again in a moment."
+                    .to_string(),
+        }
+    }
+}
+
+/// Time source for the rate limiter, replaceable in tests.
+pub trait Clock: Send + Sync {
+    /// Current instant.
+    fn now(&self) -> Instant;
+}
+
+/// Clock backed by [`Instant::now`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// Token-bucket rate limiter keyed by `channel:chat_id`.
+pub struct ChatRateLimiter {
+    config: ChatRateLimitConfig,
+    clock: Arc<dyn Clock>,
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+impl ChatRateLimiter {
+    /// Create a limiter using the system clock.
+    pub fn new(config: ChatRateLimitConfig) -> Self {
+        Self::with_clock(config, Arc::new(SystemClock))
+    }
+
+    /// Create a limiter with a custom time source.
+    pub fn with_clock(config: ChatRateLimitConfig, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            config,
+            clock,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Build a limiter from config, or `None` when rate limiting is disabled.
+    pub fn from_config(config: &ChatRateLimitConfig) -> Option<Self> {
+        config.enabled.then(|| Self::new(config.clone()))
+    }
+
+    /// Returns true if the message may be processed, consuming one token from
+    /// its chat's bucket. Exempt senders always pass without consuming tokens.
+    pub fn check(&self, msg: &InboundMessage) -> bool {
+        if self.config.exempt_users.iter().any(|u| u == &msg.sender_id) {
+            return true;
+        }
+
+        let capacity = f64::from(self.config.burst.max(1));
+        let per_sec = f64::from(self.config.messages_per_minute) / 60.0;
+        let now = self.clock.now();
+        let key = format!("{}:{}", msg.channel, msg.chat_id);
+
+        let Ok(mut buckets) = self.buckets.lock() else {
+            // A poisoned lock should not take the gateway down; fail open.
+            return true;
+        };
+
+        if buckets.len() > PRUNE_THRESHOLD {
+            buckets.retain(|_, b| {
+                let elapsed = now.saturating_duration_since(b.last_refill).as_secs_f64();
+                b.tokens + elapsed * per_sec < capacity
+            });
+        }
+
+        let bucket = buckets.entry(key).or_insert(Bucket {
+            tokens: capacity,
+            last_refill: now,
+        });
+        let elapsed = now
+            .saturating_duration_since(bucket.last_refill)
+            .as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * per_sec).min(capacity);
+        bucket.last_refill = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// The polite reply sent back to a throttled chat.
+    pub fn throttled_reply(&self, msg: &InboundMessage) -> OutboundMessage {
+        OutboundMessage::reply_to(msg, &self.config.message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    /// Clock that only moves when advanced by the test.
+    struct ManualClock {
+        now: Mutex<Instant>,
+    }
+
+    impl ManualClock {
+        fn new() -> Self {
+            Self {
+                now: Mutex::new(Instant::now()),
+            }
+        }
+
+        fn advance(&self, by: Duration) {
+            *self.now.lock().unwrap() += by;
+        }
+    }
+
+    impl Clock for ManualClock {
+        fn now(&self) -> Instant {
+            *self.now.lock().unwrap()
+        }
+    }
+
+    fn limiter(clock: Arc<ManualClock>) -> ChatRateLimiter {
+        ChatRateLimiter::with_clock(
+            ChatRateLimitConfig {
+                enabled: true,
+                messages_per_minute: 60,
+                burst: 3,
+                exempt_users: vec!["admin".to_string()],
+                ..Default::default()
+            },
+            clock,
+        )
+    }
+
+    fn msg(sender: &str, chat: &str) -> InboundMessage {
+        InboundMessage::new("telegram", sender, chat, "hi")
+    }
+
+    #[test]
+    fn test_burst_beyond_limit_is_throttled() {
+        let limiter = limiter(Arc::new(ManualClock::new()));
+        let m = msg("user", "chat1");
+
+        assert!(limiter.check(&m));
+        assert!(limiter.check(&m));
+        assert!(limiter.check(&m));
+        assert!(!limiter.check(&m));
+        assert!(!limiter.check(&m));
+    }
+
+    #[test]
+    fn test_bucket_refills_over_time() {
+        let clock = Arc::new(ManualClock::new());
+        let limiter = limiter(Arc::clone(&clock));
+        let m = msg("user", "chat1");
+
+        for _ in 0..3 {
+            assert!(limiter.check(&m));
+        }
+        assert!(!limiter.check(&m));
+
+        // 60/min = one token per second.
+        clock.advance(Duration::from_millis(500));
+        assert!(!limiter.check(&m));
+        clock.advance(Duration::from_millis(500));
+        assert!(limiter.check(&m));
+        assert!(!limiter.check(&m));
+
+        // Refill is capped at the burst size.
+        clock.advance(Duration::from_secs(60));
+        for _ in 0..3 {
+            assert!(limiter.check(&m));
+        }
+        assert!(!limiter.check(&m));
+    }
+
+    #[test]
+    fn test_buckets_are_per_chat() {
+        let limiter = limiter(Arc::new(ManualClock::new()));
+        for _ in 0..3 {
+            assert!(limiter.check(&msg("user", "chat1")));
+        }
+        assert!(!limiter.check(&msg("user", "chat1")));
+        assert!(limiter.check(&msg("user", "chat2")));
+    }
+
+    #[test]
+    fn test_exempt_user_is_never_throttled() {
+        let limiter = limiter(Arc::new(ManualClock::new()));
+        for _ in 0..10 {
+            assert!(limiter.check(&msg("admin", "chat1")));
+        }
+        // Exempt traffic does not drain the chat's bucket for others.
+        assert!(limiter.check(&msg("user", "chat1")));
+    }
+
+    #[test]
+    fn test_throttled_reply_targets_chat() {
+        let limiter = limiter(Arc::new(ManualClock::new()));
+        let reply = limiter.throttled_reply(&msg("user", "chat9"));
+        assert_eq!(reply.channel, "telegram");
+        assert_eq!(reply.chat_id, "chat9");
+        assert!(reply.content.contains("slow down"));
+    }
+
+    #[test]
+    fn test_from_config_disabled_by_default() {
+        assert!(ChatRateLimiter::from_config(&ChatRateLimitConfig::default()).is_none());
+        let enabled = ChatRateLimitConfig {
+            enabled: true,
+            ..Default::default()
+        };
+        assert!(ChatRateLimiter::from_config(&enabled).is_some());
+    }
+}
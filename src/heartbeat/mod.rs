@@ -2,6 +2,8 @@
 
 mod service;
 mod template;
+mod timer;
 
-pub use service::{HeartbeatService, HEARTBEAT_PROMPT};
+pub use service::{HeartbeatEntry, HeartbeatSchedule, HeartbeatService, HEARTBEAT_PROMPT};
 pub use template::{ensure_heartbeat_file, HEARTBEAT_TEMPLATE};
+pub use timer::HeartbeatTimer;
@@ -1,104 +1,399 @@
 //! Heartbeat service implementation.
 
 use std::path::PathBuf;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
-use tokio::sync::RwLock;
+use chrono::{DateTime, Local, Utc};
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, warn};
 
 use crate::bus::{InboundMessage, MessageBus};
-use crate::error::Result;
+use crate::config::{HeartbeatCatchUp, HeartbeatConfig, ScheduleTimezone};
+use crate::cron::next_cron_time;
+use crate::error::{Result, ZeptoError};
+
+use super::HeartbeatTimer;
 
 /// Prompt sent to the agent when heartbeat is triggered.
 pub const HEARTBEAT_PROMPT: &str = r#"Read HEARTBEAT.md in your workspace (if it exists).
 Follow any actionable items listed there.
 If nothing needs attention, reply with: HEARTBEAT_OK"#;
 
+/// Longest single sleep while waiting for a fire time, so clock changes
+/// and missed ticks are noticed promptly.
+const MAX_SLEEP_STEP: Duration = Duration::from_secs(60);
+
+/// Channel used by the default, untargeted heartbeat.
+const HEARTBEAT_CHANNEL: &str = "heartbeat";
+
+/// Name of the default, untargeted heartbeat.
+const DEFAULT_HEARTBEAT_NAME: &str = "default";
+
+/// When heartbeat ticks fire.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HeartbeatSchedule {
+    /// Every fixed interval.
+    Interval(Duration),
+    /// At the times matched by a five-field cron expression.
+    Cron {
+        expr: String,
+        timezone: ScheduleTimezone,
+    },
+}
+
+impl HeartbeatSchedule {
+    /// Build the schedule from config: cron when `schedule` is set,
+    /// otherwise the fixed interval (minimum 30s).
+    ///
+    /// # Errors
+    /// Returns `ZeptoError::Config` for an invalid cron expression.
+    pub fn from_config(config: &HeartbeatConfig) -> Result<Self> {
+        match config.schedule.as_deref().map(str::trim) {
+            Some(expr) if !expr.is_empty() => {
+                if !crate::cron::is_valid_cron_expr(expr) {
+                    return Err(ZeptoError::Config(format!(
+                        "Invalid heartbeat schedule '{}': expected a five-field cron expression",
+                        expr
+                    )));
+                }
+                Ok(Self::Cron {
+                    expr: expr.to_string(),
+                    timezone: config.timezone,
+                })
+            }
+            _ => Ok(Self::Interval(Duration::from_secs(
+                config.interval_secs.max(30),
+            ))),
+        }
+    }
+
+    /// Next fire time strictly after `now`.
+    pub fn next_fire(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            Self::Interval(interval) => chrono::Duration::from_std(*interval).ok().map(|d| now + d),
+            Self::Cron { expr, timezone } => match timezone {
+                ScheduleTimezone::Utc => next_cron_time(expr, &now),
+                ScheduleTimezone::Local => {
+                    next_cron_time(expr, &now.with_timezone(&Local)).map(|t| t.with_timezone(&Utc))
+                }
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for HeartbeatSchedule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Interval(interval) => write!(f, "interval={}s", interval.as_secs()),
+            Self::Cron { expr, timezone } => write!(f, "cron='{}' ({:?})", expr, timezone),
+        }
+    }
+}
+
+/// One named heartbeat: what to send, where, and when.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeartbeatEntry {
+    /// Name used in logs and in the `heartbeat` message metadata.
+    pub name: String,
+    /// When this heartbeat fires.
+    pub schedule: HeartbeatSchedule,
+    /// Channel the prompt is published on.
+    pub channel: String,
+    /// Chat the prompt is published to.
+    pub chat_id: String,
+    /// Prompt sent to the agent.
+    pub prompt: String,
+}
+
+impl HeartbeatEntry {
+    /// Create an entry sending [`HEARTBEAT_PROMPT`] to `channel:chat_id`.
+    pub fn new(name: &str, schedule: HeartbeatSchedule, channel: &str, chat_id: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            schedule,
+            channel: channel.to_string(),
+            chat_id: chat_id.to_string(),
+            prompt: HEARTBEAT_PROMPT.to_string(),
+        }
+    }
+
+    /// Use a custom prompt (builder pattern).
+    pub fn with_prompt(mut self, prompt: &str) -> Self {
+        self.prompt = prompt.to_string();
+        self
+    }
+
+    /// Whether this entry sends the default prompt, which points the agent
+    /// at HEARTBEAT.md and so is skipped while that file has nothing to do.
+    fn uses_heartbeat_file(&self) -> bool {
+        self.prompt == HEARTBEAT_PROMPT
+    }
+}
+
 /// Background service that periodically enqueues heartbeat prompts.
+///
+/// Each [`HeartbeatEntry`] runs on its own timer, so a slow or failing
+/// heartbeat never delays the others.
 pub struct HeartbeatService {
     file_path: PathBuf,
-    interval: Duration,
+    entries: Vec<HeartbeatEntry>,
     bus: Arc<MessageBus>,
-    running: Arc<RwLock<bool>>,
-    chat_id: String,
+    /// Cancels the running timers; `None` while stopped.
+    cancel: Mutex<Option<CancellationToken>>,
+    /// Maximum random offset applied to each fire time.
+    jitter: Duration,
+    catch_up: HeartbeatCatchUp,
+    /// Seed for the jitter; each heartbeat offsets it by its index.
+    seed: u64,
 }
 
 impl HeartbeatService {
-    /// Create a new heartbeat service.
+    /// Create a service with a single heartbeat firing every `interval_secs`
+    /// (minimum 30) on the `heartbeat` channel.
     pub fn new(
         file_path: PathBuf,
         interval_secs: u64,
         bus: Arc<MessageBus>,
         chat_id: &str,
+    ) -> Self {
+        let entry = HeartbeatEntry::new(
+            DEFAULT_HEARTBEAT_NAME,
+            HeartbeatSchedule::Interval(Duration::from_secs(interval_secs.max(30))),
+            HEARTBEAT_CHANNEL,
+            chat_id,
+        );
+        Self::with_entries(file_path, bus, vec![entry])
+    }
+
+    /// Create a service running the given heartbeats.
+    pub fn with_entries(
+        file_path: PathBuf,
+        bus: Arc<MessageBus>,
+        entries: Vec<HeartbeatEntry>,
     ) -> Self {
         Self {
             file_path,
-            interval: Duration::from_secs(interval_secs.max(30)),
+            entries,
             bus,
-            running: Arc::new(RwLock::new(false)),
-            chat_id: chat_id.to_string(),
+            cancel: Mutex::new(None),
+            jitter: Duration::ZERO,
+            catch_up: HeartbeatCatchUp::default(),
+            seed: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_nanos() as u64)
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Create a service from config.
+    ///
+    /// Each enabled `heartbeat.targets` entry becomes its own heartbeat,
+    /// falling back to the top-level schedule, timezone and default prompt
+    /// for anything it leaves unset. Jitter and catch-up apply to all. Without targets, a single heartbeat
+    /// is sent to `chat_id` on the `heartbeat` channel.
+    ///
+    /// # Errors
+    /// Returns `ZeptoError::Config` for an invalid cron expression or a
+    /// target without a channel or chat.
+    pub fn from_config(
+        file_path: PathBuf,
+        config: &HeartbeatConfig,
+        bus: Arc<MessageBus>,
+        chat_id: &str,
+    ) -> Result<Self> {
+        let mut entries = Vec::new();
+        if config.targets.is_empty() {
+            entries.push(HeartbeatEntry::new(
+                DEFAULT_HEARTBEAT_NAME,
+                HeartbeatSchedule::from_config(config)?,
+                HEARTBEAT_CHANNEL,
+                chat_id,
+            ));
+        }
+        for target in config.targets.iter().filter(|t| t.enabled) {
+            if target.channel.trim().is_empty() || target.chat_id.trim().is_empty() {
+                return Err(ZeptoError::Config(format!(
+                    "Heartbeat target '{}' needs both a channel and a chat_id",
+                    target.name
+                )));
+            }
+            let schedule = HeartbeatSchedule::from_config(&HeartbeatConfig {
+                interval_secs: target.interval_secs.unwrap_or(config.interval_secs),
+                schedule: target.schedule.clone().or_else(|| {
+                    // A target with its own interval does not inherit the cron schedule
+                    target
+                        .interval_secs
+                        .is_none()
+                        .then(|| config.schedule.clone())
+                        .flatten()
+                }),
+                timezone: target.timezone.unwrap_or(config.timezone),
+                ..Default::default()
+            })?;
+            let mut entry =
+                HeartbeatEntry::new(&target.name, schedule, &target.channel, &target.chat_id);
+            if let Some(prompt) = target.prompt.as_deref() {
+                entry = entry.with_prompt(prompt);
+            }
+            entries.push(entry);
+        }
+        Ok(Self::with_entries(file_path, bus, entries)
+            .with_jitter(Duration::from_secs(config.jitter_secs))
+            .with_catch_up(config.catch_up))
+    }
+
+    /// Offset each fire time by a random amount of up to ± `jitter`
+    /// (builder pattern).
+    pub fn with_jitter(mut self, jitter: Duration) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Set how ticks missed during suspend are handled (builder pattern).
+    pub fn with_catch_up(mut self, catch_up: HeartbeatCatchUp) -> Self {
+        self.catch_up = catch_up;
+        self
+    }
+
+    /// Seed the jitter for reproducible fire times (builder pattern).
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Replace every heartbeat's schedule (builder pattern).
+    pub fn with_schedule(mut self, schedule: HeartbeatSchedule) -> Self {
+        for entry in &mut self.entries {
+            entry.schedule = schedule.clone();
         }
+        self
+    }
+
+    /// The heartbeats this service runs.
+    pub fn entries(&self) -> &[HeartbeatEntry] {
+        &self.entries
     }
 
-    /// Start heartbeat loop in the background.
+    /// Start one background timer per heartbeat.
     pub async fn start(&self) -> Result<()> {
-        {
-            let mut running = self.running.write().await;
-            if *running {
+        let cancel = {
+            let mut guard = self.cancel.lock().unwrap();
+            if guard.is_some() {
                 warn!("Heartbeat service already running");
                 return Ok(());
             }
-            *running = true;
-        }
-
-        let file_path = self.file_path.clone();
-        let interval_duration = self.interval;
-        let bus = Arc::clone(&self.bus);
-        let running = Arc::clone(&self.running);
-        let chat_id = self.chat_id.clone();
+            let token = CancellationToken::new();
+            *guard = Some(token.clone());
+            token
+        };
 
         info!(
-            "Heartbeat service started (interval={}s, file={:?})",
-            interval_duration.as_secs(),
-            file_path
+            "Heartbeat service started ({} heartbeat(s), file={:?})",
+            self.entries.len(),
+            self.file_path
         );
 
-        tokio::spawn(async move {
-            let mut ticker = tokio::time::interval(interval_duration);
-            ticker.tick().await;
+        let now = Utc::now();
+        for (index, entry) in self.entries.iter().enumerate() {
+            info!("Heartbeat '{}' scheduled ({})", entry.name, entry.schedule);
+            let timer = HeartbeatTimer::new(
+                entry.schedule.clone(),
+                self.jitter,
+                self.catch_up,
+                self.seed.wrapping_add(index as u64),
+                now,
+            );
+            tokio::spawn(Self::run_entry(
+                entry.clone(),
+                timer,
+                self.file_path.clone(),
+                Arc::clone(&self.bus),
+                cancel.child_token(),
+            ));
+        }
 
-            loop {
-                ticker.tick().await;
+        Ok(())
+    }
 
-                if !*running.read().await {
-                    info!("Heartbeat service stopped");
-                    break;
-                }
+    /// Timer loop for a single heartbeat; runs until cancelled.
+    async fn run_entry(
+        entry: HeartbeatEntry,
+        mut timer: HeartbeatTimer,
+        file_path: PathBuf,
+        bus: Arc<MessageBus>,
+        cancel: CancellationToken,
+    ) {
+        loop {
+            let Some(fire_at) = timer.next_fire() else {
+                warn!(
+                    "Heartbeat '{}' schedule ({}) has no upcoming run",
+                    entry.name, entry.schedule
+                );
+                return;
+            };
+            debug!("Next heartbeat '{}' at {}", entry.name, fire_at);
 
-                if let Err(e) = Self::tick(&file_path, &bus, &chat_id).await {
-                    error!("Heartbeat tick failed: {}", e);
+            tokio::select! {
+                _ = cancel.cancelled() => {
+                    debug!("Heartbeat '{}' stopped", entry.name);
+                    return;
                 }
+                _ = Self::sleep_until(fire_at) => {}
             }
-        });
 
-        Ok(())
+            if !timer.advance(Utc::now()) {
+                info!(
+                    "Heartbeat '{}' skipped a tick missed at {}",
+                    entry.name, fire_at
+                );
+                continue;
+            }
+
+            if let Err(e) = Self::tick(&entry, &file_path, &bus).await {
+                error!("Heartbeat '{}' tick failed: {}", entry.name, e);
+            }
+        }
     }
 
-    /// Stop heartbeat loop.
+    /// Sleep until the wall-clock time `target`, in short steps so clock
+    /// jumps and system suspend are noticed.
+    async fn sleep_until(target: DateTime<Utc>) {
+        while let Ok(remaining) = (target - Utc::now()).to_std() {
+            if remaining.is_zero() {
+                break;
+            }
+            tokio::time::sleep(remaining.min(MAX_SLEEP_STEP)).await;
+        }
+    }
+
+    /// Stop all heartbeat timers.
     pub async fn stop(&self) {
-        let mut running = self.running.write().await;
-        *running = false;
+        if let Some(token) = self.cancel.lock().unwrap().take() {
+            token.cancel();
+            info!("Heartbeat service stopped");
+        }
     }
 
-    /// Trigger heartbeat immediately.
+    /// Trigger every heartbeat immediately.
+    ///
+    /// All heartbeats are attempted; the first error, if any, is returned.
     pub async fn trigger_now(&self) -> Result<()> {
-        Self::tick(&self.file_path, &self.bus, &self.chat_id).await
+        let mut result = Ok(());
+        for entry in &self.entries {
+            if let Err(e) = Self::tick(entry, &self.file_path, &self.bus).await {
+                error!("Heartbeat '{}' trigger failed: {}", entry.name, e);
+                if result.is_ok() {
+                    result = Err(e);
+                }
+            }
+        }
+        result
     }
 
     /// Returns whether service is running.
     pub async fn is_running(&self) -> bool {
-        *self.running.read().await
+        self.cancel.lock().unwrap().is_some()
     }
 
     /// Whether heartbeat content is actionable.
@@ -116,25 +411,28 @@ impl HeartbeatService {
         true
     }
 
-    async fn tick(file_path: &PathBuf, bus: &MessageBus, chat_id: &str) -> Result<()> {
-        let content = match tokio::fs::read_to_string(file_path).await {
-            Ok(content) => content,
-            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
-                debug!("Heartbeat file missing at {:?}, skipping tick", file_path);
-                return Ok(());
-            }
-            Err(e) => {
-                warn!("Failed to read heartbeat file {:?}: {}", file_path, e);
+    async fn tick(entry: &HeartbeatEntry, file_path: &PathBuf, bus: &MessageBus) -> Result<()> {
+        if entry.uses_heartbeat_file() {
+            let content = match tokio::fs::read_to_string(file_path).await {
+                Ok(content) => content,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                    debug!("Heartbeat file missing at {:?}, skipping tick", file_path);
+                    return Ok(());
+                }
+                Err(e) => {
+                    warn!("Failed to read heartbeat file {:?}: {}", file_path, e);
+                    return Ok(());
+                }
+            };
+
+            if Self::is_empty(&content) {
+                debug!("Heartbeat file has no actionable content");
                 return Ok(());
             }
-        };
-
-        if Self::is_empty(&content) {
-            debug!("Heartbeat file has no actionable content");
-            return Ok(());
         }
 
-        let message = InboundMessage::new("heartbeat", "system", chat_id, HEARTBEAT_PROMPT);
+        let message = InboundMessage::new(&entry.channel, "system", &entry.chat_id, &entry.prompt)
+            .with_metadata("heartbeat", &entry.name);
         bus.publish_inbound(message).await?;
         Ok(())
     }
@@ -143,6 +441,7 @@ impl HeartbeatService {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::HeartbeatTarget;
 
     #[test]
     fn test_is_empty_true() {
@@ -151,10 +450,235 @@ mod tests {
         assert!(HeartbeatService::is_empty("<!-- comment -->\n\n- [ ]"));
     }
 
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_schedule_from_config_falls_back_to_interval() {
+        let mut config = HeartbeatConfig {
+            interval_secs: 10,
+            ..Default::default()
+        };
+        assert_eq!(
+            HeartbeatSchedule::from_config(&config).unwrap(),
+            HeartbeatSchedule::Interval(Duration::from_secs(30))
+        );
+
+        config.schedule = Some("  ".to_string());
+        assert!(matches!(
+            HeartbeatSchedule::from_config(&config).unwrap(),
+            HeartbeatSchedule::Interval(_)
+        ));
+    }
+
+    #[test]
+    fn test_schedule_from_config_cron() {
+        let config = HeartbeatConfig {
+            schedule: Some("0 9 * * 1-5".to_string()),
+            timezone: ScheduleTimezone::Utc,
+            ..Default::default()
+        };
+        let schedule = HeartbeatSchedule::from_config(&config).unwrap();
+        assert_eq!(
+            schedule,
+            HeartbeatSchedule::Cron {
+                expr: "0 9 * * 1-5".to_string(),
+                timezone: ScheduleTimezone::Utc,
+            }
+        );
+
+        let bad = HeartbeatConfig {
+            schedule: Some("9am weekdays".to_string()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            HeartbeatSchedule::from_config(&bad),
+            Err(ZeptoError::Config(_))
+        ));
+    }
+
+    #[test]
+    fn test_next_fire_weekdays_at_nine_utc() {
+        let schedule = HeartbeatSchedule::Cron {
+            expr: "0 9 * * 1-5".to_string(),
+            timezone: ScheduleTimezone::Utc,
+        };
+        // Friday after 9am -> Monday 9am
+        assert_eq!(
+            schedule.next_fire(utc("2026-03-06T09:00:00Z")),
+            Some(utc("2026-03-09T09:00:00Z"))
+        );
+        // Monday before 9am -> same day
+        assert_eq!(
+            schedule.next_fire(utc("2026-03-09T08:59:59Z")),
+            Some(utc("2026-03-09T09:00:00Z"))
+        );
+    }
+
+    #[test]
+    fn test_next_fire_interval_and_local() {
+        let now = utc("2026-03-06T09:00:00Z");
+        let interval = HeartbeatSchedule::Interval(Duration::from_secs(600));
+        assert_eq!(interval.next_fire(now), Some(utc("2026-03-06T09:10:00Z")));
+
+        let local = HeartbeatSchedule::Cron {
+            expr: "*/5 * * * *".to_string(),
+            timezone: ScheduleTimezone::Local,
+        };
+        let next = local.next_fire(now).unwrap();
+        assert!(next > now && next <= now + chrono::Duration::minutes(5));
+    }
+
     #[test]
     fn test_is_empty_false() {
         assert!(!HeartbeatService::is_empty("Check orders"));
         assert!(!HeartbeatService::is_empty("- [x] Done"));
         assert!(!HeartbeatService::is_empty("# Header\n- Send alert"));
     }
+
+    #[test]
+    fn test_from_config_targets_replace_default() {
+        let bus = Arc::new(MessageBus::new());
+        let config = HeartbeatConfig {
+            interval_secs: 600,
+            schedule: Some("0 * * * *".to_string()),
+            targets: vec![
+                HeartbeatTarget {
+                    name: "standup".to_string(),
+                    channel: "slack".to_string(),
+                    chat_id: "C1".to_string(),
+                    prompt: Some("Post the standup summary".to_string()),
+                    interval_secs: None,
+                    schedule: Some("0 9 * * 1-5".to_string()),
+                    timezone: Some(ScheduleTimezone::Utc),
+                    enabled: true,
+                },
+                HeartbeatTarget {
+                    name: "ops".to_string(),
+                    channel: "telegram".to_string(),
+                    chat_id: "42".to_string(),
+                    prompt: None,
+                    interval_secs: Some(120),
+                    schedule: None,
+                    timezone: None,
+                    enabled: true,
+                },
+                HeartbeatTarget {
+                    name: "off".to_string(),
+                    channel: "telegram".to_string(),
+                    chat_id: "7".to_string(),
+                    prompt: None,
+                    interval_secs: None,
+                    schedule: None,
+                    timezone: None,
+                    enabled: false,
+                },
+            ],
+            ..Default::default()
+        };
+
+        let service =
+            HeartbeatService::from_config(PathBuf::from("HEARTBEAT.md"), &config, bus, "x")
+                .unwrap();
+        let entries = service.entries();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].channel, "slack");
+        assert_eq!(entries[0].prompt, "Post the standup summary");
+        assert_eq!(
+            entries[0].schedule,
+            HeartbeatSchedule::Cron {
+                expr: "0 9 * * 1-5".to_string(),
+                timezone: ScheduleTimezone::Utc,
+            }
+        );
+        assert_eq!(entries[1].prompt, HEARTBEAT_PROMPT);
+        assert_eq!(
+            entries[1].schedule,
+            HeartbeatSchedule::Interval(Duration::from_secs(120))
+        );
+    }
+
+    #[test]
+    fn test_from_config_without_targets_keeps_default() {
+        let bus = Arc::new(MessageBus::new());
+        let service = HeartbeatService::from_config(
+            PathBuf::from("HEARTBEAT.md"),
+            &HeartbeatConfig::default(),
+            bus,
+            "heartbeat:system",
+        )
+        .unwrap();
+        assert_eq!(service.entries().len(), 1);
+        assert_eq!(service.entries()[0].channel, HEARTBEAT_CHANNEL);
+        assert_eq!(service.entries()[0].chat_id, "heartbeat:system");
+    }
+
+    #[tokio::test]
+    async fn test_entries_fire_independently() {
+        let bus = Arc::new(MessageBus::new());
+        let fast = HeartbeatEntry::new(
+            "fast",
+            HeartbeatSchedule::Interval(Duration::from_millis(20)),
+            "telegram",
+            "42",
+        )
+        .with_prompt("fast check");
+        let slow = HeartbeatEntry::new(
+            "slow",
+            HeartbeatSchedule::Interval(Duration::from_millis(90)),
+            "slack",
+            "C1",
+        )
+        .with_prompt("slow check");
+        // No upcoming run: this timer exits without affecting the others.
+        let never = HeartbeatEntry::new(
+            "never",
+            HeartbeatSchedule::Cron {
+                expr: "0 0 31 2 *".to_string(),
+                timezone: ScheduleTimezone::Utc,
+            },
+            "slack",
+            "C2",
+        )
+        .with_prompt("never");
+        let service = HeartbeatService::with_entries(
+            PathBuf::from("/nonexistent/HEARTBEAT.md"),
+            bus.clone(),
+            vec![fast, slow, never],
+        );
+        service.start().await.unwrap();
+        assert!(service.is_running().await);
+
+        let mut fast_count = 0;
+        let mut slow_count = 0;
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while fast_count < 4 || slow_count < 2 {
+                let msg = bus.consume_inbound().await.unwrap();
+                match msg.metadata.get("heartbeat").map(String::as_str) {
+                    Some("fast") => {
+                        assert_eq!(
+                            (msg.channel.as_str(), msg.chat_id.as_str()),
+                            ("telegram", "42")
+                        );
+                        assert_eq!(msg.content, "fast check");
+                        fast_count += 1;
+                    }
+                    Some("slow") => {
+                        assert_eq!(
+                            (msg.channel.as_str(), msg.chat_id.as_str()),
+                            ("slack", "C1")
+                        );
+                        slow_count += 1;
+                    }
+                    other => panic!("unexpected heartbeat {:?}", other),
+                }
+            }
+        })
+        .await
+        .expect("both heartbeats should fire");
+
+        service.stop().await;
+        assert!(!service.is_running().await);
+    }
 }
@@ -0,0 +1,272 @@
+//! Fire-time planning for a single heartbeat: jitter and missed-tick catch-up.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+use crate::config::HeartbeatCatchUp;
+
+use super::HeartbeatSchedule;
+
+/// How late a tick may fire before it counts as missed (the process was
+/// suspended or the clock jumped, rather than ordinary scheduling delay).
+const MISSED_TICK_GRACE: Duration = Duration::from_secs(60);
+
+/// Small seeded PRNG (SplitMix64) so jitter is reproducible in tests.
+#[derive(Debug, Clone)]
+struct JitterRng(u64);
+
+impl JitterRng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform offset in `[-max_ms, max_ms]`.
+    fn offset_ms(&mut self, max_ms: i64) -> i64 {
+        if max_ms <= 0 {
+            return 0;
+        }
+        let span = (max_ms as u64) * 2 + 1;
+        (self.next_u64() % span) as i64 - max_ms
+    }
+}
+
+/// Tracks when one heartbeat should next fire.
+///
+/// Each tick is planned from the un-jittered schedule, so jitter never
+/// accumulates into drift. A tick reached more than a minute late counts as
+/// missed and is handled by the [`HeartbeatCatchUp`] policy; either way the
+/// timer then moves past every slot already in the past.
+#[derive(Debug, Clone)]
+pub struct HeartbeatTimer {
+    schedule: HeartbeatSchedule,
+    jitter: Duration,
+    catch_up: HeartbeatCatchUp,
+    rng: JitterRng,
+    /// Scheduled (un-jittered) time of the next tick.
+    next_base: Option<DateTime<Utc>>,
+    /// Jittered time the next tick fires at.
+    next_fire: Option<DateTime<Utc>>,
+}
+
+impl HeartbeatTimer {
+    /// Plan the first tick after `now`. `seed` drives the jitter.
+    pub fn new(
+        schedule: HeartbeatSchedule,
+        jitter: Duration,
+        catch_up: HeartbeatCatchUp,
+        seed: u64,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let mut timer = Self {
+            schedule,
+            jitter,
+            catch_up,
+            rng: JitterRng(seed),
+            next_base: None,
+            next_fire: None,
+        };
+        let base = timer.schedule.next_fire(now);
+        timer.plan(base, now);
+        timer
+    }
+
+    /// When the next tick fires, or `None` if the schedule has no more runs.
+    pub fn next_fire(&self) -> Option<DateTime<Utc>> {
+        self.next_fire
+    }
+
+    /// Handle a wake-up at `now`.
+    ///
+    /// Returns whether a heartbeat should be sent. Once the planned fire time
+    /// has passed, the timer moves on to the next slot after `now`.
+    pub fn advance(&mut self, now: DateTime<Utc>) -> bool {
+        let (Some(base), Some(fire_at)) = (self.next_base, self.next_fire) else {
+            return false;
+        };
+        if now < fire_at {
+            return false;
+        }
+
+        let grace = chrono::Duration::from_std(MISSED_TICK_GRACE).unwrap_or_default();
+        let missed = now - fire_at > grace;
+
+        let next = self
+            .schedule
+            .next_fire(base)
+            .filter(|next| *next > now)
+            .or_else(|| self.schedule.next_fire(now));
+        self.plan(next, now);
+
+        !missed || self.catch_up == HeartbeatCatchUp::Once
+    }
+
+    /// Set the next tick to `base` plus a random jitter offset.
+    ///
+    /// The offset is capped at half the time until `base`, so a tick never
+    /// lands at or before `now`.
+    fn plan(&mut self, base: Option<DateTime<Utc>>, now: DateTime<Utc>) {
+        self.next_base = base;
+        self.next_fire = base.map(|base| {
+            let headroom_ms = (base - now).num_milliseconds() / 2;
+            let max_ms = (self.jitter.as_millis() as i64).min(headroom_ms);
+            base + chrono::Duration::milliseconds(self.rng.offset_ms(max_ms))
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ScheduleTimezone;
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    fn half_hourly() -> HeartbeatSchedule {
+        HeartbeatSchedule::Interval(Duration::from_secs(30 * 60))
+    }
+
+    #[test]
+    fn test_jittered_fire_times_stay_in_window() {
+        let start = utc("2026-03-06T09:00:00Z");
+        let jitter = chrono::Duration::seconds(90);
+        let mut timer = HeartbeatTimer::new(
+            half_hourly(),
+            Duration::from_secs(90),
+            HeartbeatCatchUp::Skip,
+            42,
+            start,
+        );
+
+        let mut offsets = Vec::new();
+        for n in 1..=50 {
+            let base = start + chrono::Duration::minutes(30 * n);
+            let fire_at = timer.next_fire().unwrap();
+            assert!(
+                fire_at >= base - jitter && fire_at <= base + jitter,
+                "tick {} fired at {}, outside {} ± 90s",
+                n,
+                fire_at,
+                base
+            );
+            offsets.push((fire_at - base).num_milliseconds());
+            assert!(timer.advance(fire_at));
+        }
+        // Randomized per fire, in both directions.
+        assert!(offsets.iter().any(|o| *o < 0));
+        assert!(offsets.iter().any(|o| *o > 0));
+    }
+
+    #[test]
+    fn test_same_seed_same_jitter() {
+        let start = utc("2026-03-06T09:00:00Z");
+        let plan = |seed| {
+            let mut timer = HeartbeatTimer::new(
+                half_hourly(),
+                Duration::from_secs(300),
+                HeartbeatCatchUp::Once,
+                seed,
+                start,
+            );
+            (0..5)
+                .map(|_| {
+                    let fire_at = timer.next_fire().unwrap();
+                    timer.advance(fire_at);
+                    fire_at
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(plan(7), plan(7));
+        assert_ne!(plan(7), plan(8));
+    }
+
+    #[test]
+    fn test_jitter_never_fires_before_now() {
+        let start = utc("2026-03-06T09:00:00Z");
+        let mut timer = HeartbeatTimer::new(
+            HeartbeatSchedule::Interval(Duration::from_secs(60)),
+            Duration::from_secs(3600),
+            HeartbeatCatchUp::Skip,
+            1,
+            start,
+        );
+        let mut now = start;
+        for _ in 0..20 {
+            let fire_at = timer.next_fire().unwrap();
+            assert!(fire_at > now);
+            now = fire_at;
+            timer.advance(now);
+        }
+    }
+
+    #[test]
+    fn test_long_gap_fires_once_without_burst() {
+        let start = utc("2026-03-06T09:00:00Z");
+        let mut timer = HeartbeatTimer::new(
+            half_hourly(),
+            Duration::ZERO,
+            HeartbeatCatchUp::Once,
+            0,
+            start,
+        );
+
+        // Suspended for ten hours: twenty ticks were missed.
+        let wake = start + chrono::Duration::hours(10) + chrono::Duration::minutes(5);
+        assert!(timer.advance(wake));
+        // The interval restarts from the wake-up.
+        let next = timer.next_fire().unwrap();
+        assert_eq!(next, utc("2026-03-06T19:35:00Z"));
+        // Nothing else is due at wake-up.
+        assert!(!timer.advance(wake));
+        assert!(!timer.advance(wake + chrono::Duration::minutes(1)));
+        assert!(timer.advance(next));
+    }
+
+    #[test]
+    fn test_long_gap_skip_policy_drops_missed_ticks() {
+        let start = utc("2026-03-06T09:00:00Z");
+        let mut timer = HeartbeatTimer::new(
+            half_hourly(),
+            Duration::ZERO,
+            HeartbeatCatchUp::Skip,
+            0,
+            start,
+        );
+
+        let wake = start + chrono::Duration::hours(10) + chrono::Duration::minutes(5);
+        assert!(!timer.advance(wake));
+        assert_eq!(timer.next_fire(), Some(utc("2026-03-06T19:35:00Z")));
+
+        // Slightly late (within the grace period) still fires, and the
+        // following tick keeps to the schedule.
+        let late = utc("2026-03-06T19:35:20Z");
+        assert!(timer.advance(late));
+        assert_eq!(timer.next_fire(), Some(utc("2026-03-06T20:05:00Z")));
+    }
+
+    #[test]
+    fn test_cron_gap_resumes_at_next_slot() {
+        let schedule = HeartbeatSchedule::Cron {
+            expr: "0 9 * * *".to_string(),
+            timezone: ScheduleTimezone::Utc,
+        };
+        let mut timer = HeartbeatTimer::new(
+            schedule,
+            Duration::ZERO,
+            HeartbeatCatchUp::Once,
+            0,
+            utc("2026-03-01T12:00:00Z"),
+        );
+        assert_eq!(timer.next_fire(), Some(utc("2026-03-02T09:00:00Z")));
+
+        // Asleep across four daily runs.
+        assert!(timer.advance(utc("2026-03-05T18:00:00Z")));
+        assert_eq!(timer.next_fire(), Some(utc("2026-03-06T09:00:00Z")));
+    }
+}
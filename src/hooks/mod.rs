@@ -2,9 +2,10 @@
 //!
 //! Config-driven hooks that fire at specific points in the agent loop:
 //!
-//! - `before_tool` — before tool execution (can log or block)
+//! - `before_tool` — before tool execution (can log, rewrite arguments, or block)
 //! - `after_tool` — after tool execution (can log)
-//! - `on_error` — when a tool fails (can log)
+//! - `on_error` — when a tool fails (can log, notify, or retry)
+//! - `on_turn_start` / `on_turn_end` — once per agent turn (can log or notify)
 //!
 //! # Configuration
 //!
@@ -14,13 +15,16 @@
 //!         "enabled": true,
 //!         "before_tool": [
 //!             { "action": "log", "tools": ["shell"], "level": "warn" },
-//!             { "action": "block", "tools": ["shell"], "channels": ["telegram"], "message": "Shell disabled on Telegram" }
+//!             { "action": "block", "tools": ["shell"], "channels": ["telegram"], "message": "Shell disabled on Telegram" },
+//!             { "action": "block", "tools": ["shell"], "arg_match": { "key": "command", "contains": "rm -rf" } },
+//!             { "action": "rewrite", "tools": ["shell"], "set": { "timeout": 60 }, "remove": ["env"] }
 //!         ],
 //!         "after_tool": [
 //!             { "action": "log", "tools": ["*"], "level": "info" }
 //!         ],
 //!         "on_error": [
-//!             { "action": "log", "level": "error" }
+//!             { "action": "log", "level": "error" },
+//!             { "action": "retry", "tools": ["web_fetch"], "max_retries": 2, "backoff_ms": 500 }
 //!         ]
 //!     }
 //! }
@@ -47,11 +51,15 @@
 //! assert!(matches!(result, HookResult::Block(_)));
 //! ```
 
-use std::sync::Arc;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use serde::{Deserialize, Serialize};
 
 use crate::bus::{MessageBus, OutboundMessage};
+use crate::utils::redact::RedactedValue;
 
 // ---------------------------------------------------------------------------
 // Hook action enum
@@ -67,6 +75,142 @@ pub enum HookAction {
     Block,
     /// Send a notification message via the message bus.
     Notify,
+    /// Re-run the failed tool call (on_error only).
+    Retry,
+    /// Mutate the tool arguments with `set` / `remove` (before_tool only).
+    Rewrite,
+}
+
+impl HookAction {
+    const ALL: [HookAction; 5] = [
+        HookAction::Log,
+        HookAction::Block,
+        HookAction::Notify,
+        HookAction::Retry,
+        HookAction::Rewrite,
+    ];
+
+    /// Config name of the action (`"log"`, `"block"`, ...).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HookAction::Log => "log",
+            HookAction::Block => "block",
+            HookAction::Notify => "notify",
+            HookAction::Retry => "retry",
+            HookAction::Rewrite => "rewrite",
+        }
+    }
+
+    fn index(&self) -> usize {
+        match self {
+            HookAction::Log => 0,
+            HookAction::Block => 1,
+            HookAction::Notify => 2,
+            HookAction::Retry => 3,
+            HookAction::Rewrite => 4,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Argument predicate
+// ---------------------------------------------------------------------------
+
+/// Predicate on a tool call's JSON arguments (before_tool only).
+///
+/// `key` is a dot-separated path into the arguments (`"command"`,
+/// `"options.path"`, `"files.0"`). The rule matches when the value at that
+/// path contains `contains`. Non-string values are compared via their JSON
+/// form; a missing key never matches.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArgMatch {
+    /// Dot-separated path to the argument.
+    pub key: String,
+    /// Substring the argument value must contain.
+    pub contains: String,
+}
+
+impl ArgMatch {
+    /// Check the predicate against tool arguments.
+    pub fn matches(&self, args: &serde_json::Value) -> bool {
+        let mut value = args;
+        for segment in self.key.split('.') {
+            let next = match value {
+                serde_json::Value::Object(map) => map.get(segment),
+                serde_json::Value::Array(items) => {
+                    segment.parse::<usize>().ok().and_then(|i| items.get(i))
+                }
+                _ => None,
+            };
+            match next {
+                Some(v) => value = v,
+                None => return false,
+            }
+        }
+
+        match value {
+            serde_json::Value::String(s) => s.contains(&self.contains),
+            other => other.to_string().contains(&self.contains),
+        }
+    }
+}
+
+/// Set the value at a dot-separated path, creating missing objects.
+///
+/// Array segments must index an existing element; otherwise nothing is set.
+fn set_path(target: &mut serde_json::Value, segments: &[&str], value: serde_json::Value) {
+    let Some((first, rest)) = segments.split_first() else {
+        *target = value;
+        return;
+    };
+    if !target.is_object() && !target.is_array() {
+        *target = serde_json::Value::Object(serde_json::Map::new());
+    }
+    let next = match target {
+        serde_json::Value::Array(items) => {
+            match first.parse::<usize>().ok().and_then(|i| items.get_mut(i)) {
+                Some(item) => item,
+                None => return,
+            }
+        }
+        serde_json::Value::Object(map) => map
+            .entry(first.to_string())
+            .or_insert(serde_json::Value::Null),
+        _ => return,
+    };
+    set_path(next, rest, value);
+}
+
+/// Remove the value at a dot-separated path. Missing paths are ignored.
+fn remove_path(target: &mut serde_json::Value, segments: &[&str]) {
+    let Some((last, parents)) = segments.split_last() else {
+        return;
+    };
+    let mut target = target;
+    for segment in parents {
+        let next = match target {
+            serde_json::Value::Object(map) => map.get_mut(*segment),
+            serde_json::Value::Array(items) => {
+                segment.parse::<usize>().ok().and_then(|i| items.get_mut(i))
+            }
+            _ => None,
+        };
+        match next {
+            Some(v) => target = v,
+            None => return,
+        }
+    }
+    match target {
+        serde_json::Value::Object(map) => {
+            map.remove(*last);
+        }
+        serde_json::Value::Array(items) => {
+            if let Some(i) = last.parse::<usize>().ok().filter(|i| *i < items.len()) {
+                items.remove(i);
+            }
+        }
+        _ => {}
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -75,8 +219,11 @@ pub enum HookAction {
 
 /// A single hook rule that matches tool calls and performs an action.
 ///
-/// Rules are evaluated in order. For `before_tool`, the first `Block` rule
-/// that matches wins. `Log` rules always execute (no short-circuit).
+/// Rules are evaluated in order. For `before_tool`, all matching `Rewrite`
+/// rules run first, each seeing the arguments left by the previous one;
+/// the remaining rules then match against the rewritten arguments and the
+/// first `Block` rule that matches wins. `Log` rules always execute (no
+/// short-circuit).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct HookRule {
@@ -96,6 +243,22 @@ pub struct HookRule {
     /// Optional target chat ID for `Notify` action.
     /// Falls back to current tool call chat_id when unset.
     pub chat_id: Option<String>,
+    /// Maximum number of re-runs for `Retry` action (default 1).
+    pub max_retries: Option<u32>,
+    /// Delay between attempts for `Retry` action, in milliseconds (default 0).
+    pub backoff_ms: Option<u64>,
+    /// Only match tool calls whose arguments satisfy this predicate
+    /// (before_tool only).
+    pub arg_match: Option<ArgMatch>,
+    /// Minimum seconds between `Notify` messages from this rule. Matches
+    /// within the cooldown are not notified. Other actions are unaffected.
+    pub cooldown_secs: Option<u64>,
+    /// Arguments to set for `Rewrite` action, keyed by dot-separated path.
+    /// Missing intermediate objects are created.
+    pub set: serde_json::Map<String, serde_json::Value>,
+    /// Dot-separated argument paths to delete for `Rewrite` action.
+    /// Applied before `set`.
+    pub remove: Vec<String>,
 }
 
 impl Default for HookRule {
@@ -108,6 +271,12 @@ impl Default for HookRule {
             message: None,
             channel: None,
             chat_id: None,
+            max_retries: None,
+            backoff_ms: None,
+            arg_match: None,
+            cooldown_secs: None,
+            set: serde_json::Map::new(),
+            remove: vec![],
         }
     }
 }
@@ -123,6 +292,22 @@ impl HookRule {
     pub fn matches_channel(&self, channel_name: &str) -> bool {
         self.channels.is_empty() || self.channels.iter().any(|c| c == "*" || c == channel_name)
     }
+
+    /// Check the optional argument predicate. Rules without one match any
+    /// arguments.
+    pub fn matches_args(&self, args: &serde_json::Value) -> bool {
+        self.arg_match.as_ref().is_none_or(|m| m.matches(args))
+    }
+
+    /// Apply this rule's `remove` then `set` mutations to `args`.
+    pub fn apply_rewrite(&self, args: &mut serde_json::Value) {
+        for path in &self.remove {
+            remove_path(args, &path.split('.').collect::<Vec<_>>());
+        }
+        for (path, value) in &self.set {
+            set_path(args, &path.split('.').collect::<Vec<_>>(), value.clone());
+        }
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -148,6 +333,10 @@ pub struct HooksConfig {
     pub after_tool: Vec<HookRule>,
     /// Rules evaluated when a tool returns an error.
     pub on_error: Vec<HookRule>,
+    /// Rules evaluated once when an agent turn begins. `tools` is ignored.
+    pub on_turn_start: Vec<HookRule>,
+    /// Rules evaluated once when an agent turn finishes. `tools` is ignored.
+    pub on_turn_end: Vec<HookRule>,
 }
 
 // ---------------------------------------------------------------------------
@@ -161,6 +350,61 @@ pub enum HookResult {
     Continue,
     /// Block the tool with the given message.
     Block(String),
+    /// Execute the tool with these rewritten arguments instead.
+    Rewrite(serde_json::Value),
+}
+
+/// Result of evaluating on_error hooks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorHookOutcome {
+    /// Report the error to the LLM as usual.
+    Continue,
+    /// Re-run the tool call, at most `max_retries` times per call, waiting
+    /// `backoff` before each attempt. The caller owns the attempt counter.
+    Retry {
+        /// Maximum number of re-runs for this tool call.
+        max_retries: u32,
+        /// Delay before each re-run.
+        backoff: std::time::Duration,
+    },
+}
+
+/// Snapshot of [`HookEngine`] counters since the engine was created.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct HookStats {
+    /// Matched rules per hook point and action, e.g.
+    /// `matched["before_tool"]["block"]`. Zero counts are omitted.
+    pub matched: BTreeMap<&'static str, BTreeMap<&'static str, u64>>,
+    /// Tool calls stopped by a `block` rule.
+    pub blocked: u64,
+    /// Tool calls whose arguments were changed by `rewrite` rules.
+    pub rewritten: u64,
+}
+
+/// Hook points, in [`HookCounters::matched`] order.
+const HOOK_POINTS: [&str; 5] = [
+    "before_tool",
+    "after_tool",
+    "on_error",
+    "on_turn_start",
+    "on_turn_end",
+];
+
+/// Lock-free counters behind [`HookEngine::stats`].
+struct HookCounters {
+    matched: [[AtomicU64; HookAction::ALL.len()]; HOOK_POINTS.len()],
+    blocked: AtomicU64,
+    rewritten: AtomicU64,
+}
+
+impl Default for HookCounters {
+    fn default() -> Self {
+        Self {
+            matched: std::array::from_fn(|_| std::array::from_fn(|_| AtomicU64::new(0))),
+            blocked: AtomicU64::new(0),
+            rewritten: AtomicU64::new(0),
+        }
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -172,16 +416,55 @@ pub enum HookResult {
 /// Created once per agent loop iteration and called at 3 points:
 /// 1. `before_tool` — before approval gate + tool execution
 /// 2. `after_tool` — after successful tool execution
-/// 3. `on_error` — after failed tool execution
+/// 3. `on_error` — after failed tool execution (may request a retry)
+///
+/// The engine is shared via `Arc` across concurrent tool calls; notify
+/// cooldowns are tracked behind an internal mutex and match counters
+/// (see [`stats`](Self::stats)) are atomics.
 pub struct HookEngine {
     config: HooksConfig,
     bus: Option<Arc<MessageBus>>,
+    /// Last time each rule sent a notification, keyed by (hook point, rule index).
+    last_notified: Mutex<HashMap<(&'static str, usize), Instant>>,
+    counters: HookCounters,
 }
 
 impl HookEngine {
     /// Create a new HookEngine from configuration.
     pub fn new(config: HooksConfig) -> Self {
-        Self { config, bus: None }
+        Self {
+            config,
+            bus: None,
+            last_notified: Mutex::new(HashMap::new()),
+            counters: HookCounters::default(),
+        }
+    }
+
+    /// Counts of matched rules, blocked and rewritten calls so far.
+    pub fn stats(&self) -> HookStats {
+        let mut matched = BTreeMap::new();
+        for (hook, counts) in HOOK_POINTS.iter().zip(&self.counters.matched) {
+            let by_action: BTreeMap<_, _> = HookAction::ALL
+                .iter()
+                .zip(counts)
+                .map(|(action, count)| (action.as_str(), count.load(Ordering::Relaxed)))
+                .filter(|(_, count)| *count > 0)
+                .collect();
+            if !by_action.is_empty() {
+                matched.insert(*hook, by_action);
+            }
+        }
+        HookStats {
+            matched,
+            blocked: self.counters.blocked.load(Ordering::Relaxed),
+            rewritten: self.counters.rewritten.load(Ordering::Relaxed),
+        }
+    }
+
+    fn record_match(&self, hook: &str, action: &HookAction) {
+        if let Some(point) = HOOK_POINTS.iter().position(|h| *h == hook) {
+            self.counters.matched[point][action.index()].fetch_add(1, Ordering::Relaxed);
+        }
     }
 
     /// Attach a message bus for `notify` actions.
@@ -215,6 +498,32 @@ impl HookEngine {
         Some((target_channel, target_chat_id))
     }
 
+    /// Returns true if the rule is outside its cooldown window, and if so
+    /// marks it as fired now.
+    fn take_cooldown(&self, hook: &'static str, index: usize, rule: &HookRule) -> bool {
+        let Some(secs) = rule.cooldown_secs.filter(|s| *s > 0) else {
+            return true;
+        };
+        let Ok(mut last) = self.last_notified.lock() else {
+            return true;
+        };
+        let now = Instant::now();
+        match last.get(&(hook, index)) {
+            Some(fired) if now.duration_since(*fired) < Duration::from_secs(secs) => {
+                tracing::debug!(
+                    hook = hook,
+                    rule = index,
+                    "Hook notify suppressed: rule is cooling down"
+                );
+                false
+            }
+            _ => {
+                last.insert((hook, index), now);
+                true
+            }
+        }
+    }
+
     fn emit_notify(
         &self,
         hook: &str,
@@ -268,12 +577,16 @@ impl HookEngine {
 
     /// Evaluate before_tool hooks. Returns Block if any matching rule blocks.
     ///
-    /// Rules are evaluated in order. `Log` rules execute without stopping.
-    /// The first `Block` rule that matches returns immediately.
+    /// A rule matches when its tool, channel, and (optional) argument
+    /// predicate all match. Matching `Rewrite` rules are applied first, in
+    /// order. The other rules are then evaluated in order against the
+    /// rewritten arguments. `Log` rules execute without stopping.
+    /// The first `Block` rule that matches returns immediately. Otherwise
+    /// `Rewrite` carries the new arguments if any rule changed them.
     pub fn before_tool(
         &self,
         tool_name: &str,
-        _args: &serde_json::Value,
+        args: &serde_json::Value,
         channel: &str,
         chat_id: &str,
     ) -> HookResult {
@@ -281,10 +594,36 @@ impl HookEngine {
             return HookResult::Continue;
         }
 
+        let mut rewritten = std::borrow::Cow::Borrowed(args);
         for rule in &self.config.before_tool {
-            if !rule.matches_tool(tool_name) || !rule.matches_channel(channel) {
+            if rule.action != HookAction::Rewrite
+                || !rule.matches_tool(tool_name)
+                || !rule.matches_channel(channel)
+                || !rule.matches_args(&rewritten)
+            {
+                continue;
+            }
+            self.record_match("before_tool", &rule.action);
+            rule.apply_rewrite(rewritten.to_mut());
+            tracing::info!(
+                hook = "before_tool",
+                tool = tool_name,
+                channel = channel,
+                "Hook: rewriting tool arguments"
+            );
+        }
+        let original = args;
+        let args: &serde_json::Value = &rewritten;
+
+        for (index, rule) in self.config.before_tool.iter().enumerate() {
+            if rule.action == HookAction::Rewrite
+                || !rule.matches_tool(tool_name)
+                || !rule.matches_channel(channel)
+                || !rule.matches_args(args)
+            {
                 continue;
             }
+            self.record_match("before_tool", &rule.action);
 
             match rule.action {
                 HookAction::Log => {
@@ -294,30 +633,35 @@ impl HookEngine {
                             hook = "before_tool",
                             tool = tool_name,
                             channel = channel,
+                            args = %RedactedValue::new(args),
                             "Hook: tool call"
                         ),
                         "warn" => tracing::warn!(
                             hook = "before_tool",
                             tool = tool_name,
                             channel = channel,
+                            args = %RedactedValue::new(args),
                             "Hook: tool call"
                         ),
                         "debug" => tracing::debug!(
                             hook = "before_tool",
                             tool = tool_name,
                             channel = channel,
+                            args = %RedactedValue::new(args),
                             "Hook: tool call"
                         ),
                         "trace" => tracing::trace!(
                             hook = "before_tool",
                             tool = tool_name,
                             channel = channel,
+                            args = %RedactedValue::new(args),
                             "Hook: tool call"
                         ),
                         _ => tracing::info!(
                             hook = "before_tool",
                             tool = tool_name,
                             channel = channel,
+                            args = %RedactedValue::new(args),
                             "Hook: tool call"
                         ),
                     }
@@ -333,6 +677,7 @@ impl HookEngine {
                         channel = channel,
                         "Hook: blocking tool"
                     );
+                    self.counters.blocked.fetch_add(1, Ordering::Relaxed);
                     return HookResult::Block(msg);
                 }
                 HookAction::Notify => {
@@ -342,12 +687,22 @@ impl HookEngine {
                             tool_name, channel, chat_id
                         )
                     });
-                    self.emit_notify("before_tool", tool_name, rule, channel, chat_id, message);
+                    if self.take_cooldown("before_tool", index, rule) {
+                        self.emit_notify("before_tool", tool_name, rule, channel, chat_id, message);
+                    }
                 }
+                HookAction::Retry => {}   // Retry only applies in on_error
+                HookAction::Rewrite => {} // Applied (and counted) above
             }
         }
 
-        HookResult::Continue
+        match rewritten {
+            std::borrow::Cow::Owned(args) if args != *original => {
+                self.counters.rewritten.fetch_add(1, Ordering::Relaxed);
+                HookResult::Rewrite(args)
+            }
+            _ => HookResult::Continue,
+        }
     }
 
     /// Evaluate after_tool hooks (logging only, no blocking).
@@ -363,10 +718,11 @@ impl HookEngine {
             return;
         }
 
-        for rule in &self.config.after_tool {
+        for (index, rule) in self.config.after_tool.iter().enumerate() {
             if !rule.matches_tool(tool_name) || !rule.matches_channel(channel) {
                 continue;
             }
+            self.record_match("after_tool", &rule.action);
 
             match rule.action {
                 HookAction::Log => {
@@ -396,22 +752,37 @@ impl HookEngine {
                             tool_name, ms, channel, chat_id
                         )
                     });
-                    self.emit_notify("after_tool", tool_name, rule, channel, chat_id, message);
+                    if self.take_cooldown("after_tool", index, rule) {
+                        self.emit_notify("after_tool", tool_name, rule, channel, chat_id, message);
+                    }
                 }
+                HookAction::Retry => {}   // Retry only applies in on_error
+                HookAction::Rewrite => {} // Rewrite only applies in before_tool
             }
         }
     }
 
-    /// Evaluate on_error hooks (logging only, no blocking).
-    pub fn on_error(&self, tool_name: &str, error: &str, channel: &str, chat_id: &str) {
+    /// Evaluate on_error hooks.
+    ///
+    /// `Log` and `Notify` rules always execute. The first matching `Retry`
+    /// rule determines the returned [`ErrorHookOutcome`].
+    pub fn on_error(
+        &self,
+        tool_name: &str,
+        error: &str,
+        channel: &str,
+        chat_id: &str,
+    ) -> ErrorHookOutcome {
+        let mut outcome = ErrorHookOutcome::Continue;
         if !self.config.enabled {
-            return;
+            return outcome;
         }
 
-        for rule in &self.config.on_error {
+        for (index, rule) in self.config.on_error.iter().enumerate() {
             if !rule.matches_tool(tool_name) || !rule.matches_channel(channel) {
                 continue;
             }
+            self.record_match("on_error", &rule.action);
 
             match rule.action {
                 HookAction::Log => {
@@ -437,7 +808,7 @@ impl HookEngine {
                         ),
                     }
                 }
-                HookAction::Block => {} // Block is a no-op in on_error
+                HookAction::Block | HookAction::Rewrite => {} // No-op in on_error
                 HookAction::Notify => {
                     let message = rule.message.clone().unwrap_or_else(|| {
                         format!(
@@ -445,8 +816,131 @@ impl HookEngine {
                             tool_name, error, channel, chat_id
                         )
                     });
-                    self.emit_notify("on_error", tool_name, rule, channel, chat_id, message);
+                    if self.take_cooldown("on_error", index, rule) {
+                        self.emit_notify("on_error", tool_name, rule, channel, chat_id, message);
+                    }
+                }
+                HookAction::Retry => {
+                    if outcome == ErrorHookOutcome::Continue {
+                        outcome = ErrorHookOutcome::Retry {
+                            max_retries: rule.max_retries.unwrap_or(1),
+                            backoff: std::time::Duration::from_millis(rule.backoff_ms.unwrap_or(0)),
+                        };
+                    }
+                }
+            }
+        }
+
+        outcome
+    }
+
+    /// Evaluate on_turn_start hooks (log/notify only).
+    pub fn on_turn_start(&self, channel: &str, chat_id: &str) {
+        if !self.config.enabled {
+            return;
+        }
+
+        for (index, rule) in self.config.on_turn_start.iter().enumerate() {
+            if !rule.matches_channel(channel) {
+                continue;
+            }
+            self.record_match("on_turn_start", &rule.action);
+
+            match rule.action {
+                HookAction::Log => match rule.level.as_deref().unwrap_or("info") {
+                    "warn" => tracing::warn!(
+                        hook = "on_turn_start",
+                        channel = channel,
+                        chat_id = chat_id,
+                        "Hook: turn started"
+                    ),
+                    "debug" => tracing::debug!(
+                        hook = "on_turn_start",
+                        channel = channel,
+                        chat_id = chat_id,
+                        "Hook: turn started"
+                    ),
+                    _ => tracing::info!(
+                        hook = "on_turn_start",
+                        channel = channel,
+                        chat_id = chat_id,
+                        "Hook: turn started"
+                    ),
+                },
+                HookAction::Notify => {
+                    let message = rule.message.clone().unwrap_or_else(|| {
+                        format!(
+                            "Hook notify (on_turn_start): turn started in {}:{}",
+                            channel, chat_id
+                        )
+                    });
+                    if self.take_cooldown("on_turn_start", index, rule) {
+                        self.emit_notify("on_turn_start", "-", rule, channel, chat_id, message);
+                    }
+                }
+                HookAction::Block | HookAction::Retry | HookAction::Rewrite => {} // No-op for turn hooks
+            }
+        }
+    }
+
+    /// Evaluate on_turn_end hooks (log/notify only).
+    pub fn on_turn_end(
+        &self,
+        channel: &str,
+        chat_id: &str,
+        elapsed: std::time::Duration,
+        tool_count: usize,
+    ) {
+        if !self.config.enabled {
+            return;
+        }
+
+        for (index, rule) in self.config.on_turn_end.iter().enumerate() {
+            if !rule.matches_channel(channel) {
+                continue;
+            }
+            self.record_match("on_turn_end", &rule.action);
+
+            let ms = elapsed.as_millis();
+            match rule.action {
+                HookAction::Log => match rule.level.as_deref().unwrap_or("info") {
+                    "warn" => tracing::warn!(
+                        hook = "on_turn_end",
+                        channel = channel,
+                        chat_id = chat_id,
+                        latency_ms = %ms,
+                        tool_count = tool_count,
+                        "Hook: turn completed"
+                    ),
+                    "debug" => tracing::debug!(
+                        hook = "on_turn_end",
+                        channel = channel,
+                        chat_id = chat_id,
+                        latency_ms = %ms,
+                        tool_count = tool_count,
+                        "Hook: turn completed"
+                    ),
+                    _ => tracing::info!(
+                        hook = "on_turn_end",
+                        channel = channel,
+                        chat_id = chat_id,
+                        latency_ms = %ms,
+                        tool_count = tool_count,
+                        "Hook: turn completed"
+                    ),
+                },
+                HookAction::Notify => {
+                    let message = rule.message.clone().unwrap_or_else(|| {
+                        format!(
+                            "Hook notify (on_turn_end): turn completed in {}ms with {} tool call(s) ({}:{})",
+                            ms, tool_count, channel, chat_id
+                        )
+                    });
+                    if self.take_cooldown("on_turn_end", index, rule) {
+                        self.emit_notify("on_turn_end", "-", rule, channel, chat_id, message);
+                    }
                 }
+                HookAction::Block | HookAction::Retry | HookAction::Rewrite => {} // No-op for turn hooks
             }
         }
     }
@@ -474,6 +968,8 @@ mod tests {
         assert!(config.before_tool.is_empty());
         assert!(config.after_tool.is_empty());
         assert!(config.on_error.is_empty());
+        assert!(config.on_turn_start.is_empty());
+        assert!(config.on_turn_end.is_empty());
     }
 
     #[test]
@@ -507,6 +1003,7 @@ mod tests {
                 ..Default::default()
             }],
             on_error: vec![],
+            ..Default::default()
         };
         let json = serde_json::to_string(&config).unwrap();
         let deserialized: HooksConfig = serde_json::from_str(&json).unwrap();
@@ -715,6 +1212,387 @@ mod tests {
         engine.on_error("shell", "command not found", "cli", "chat1");
     }
 
+    fn rm_rf_block_engine() -> HookEngine {
+        HookEngine::new(HooksConfig {
+            enabled: true,
+            before_tool: vec![HookRule {
+                action: HookAction::Block,
+                tools: vec!["shell".to_string()],
+                arg_match: Some(ArgMatch {
+                    key: "command".to_string(),
+                    contains: "rm -rf".to_string(),
+                }),
+                ..Default::default()
+            }],
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_before_tool_arg_match_blocks_matching_substring() {
+        let engine = rm_rf_block_engine();
+        let result = engine.before_tool(
+            "shell",
+            &serde_json::json!({"command": "cd /tmp && rm -rf build"}),
+            "cli",
+            "chat1",
+        );
+        assert!(matches!(result, HookResult::Block(_)));
+    }
+
+    #[test]
+    fn test_before_tool_arg_match_allows_when_absent() {
+        let engine = rm_rf_block_engine();
+        assert_eq!(
+            engine.before_tool(
+                "shell",
+                &serde_json::json!({"command": "ls -la"}),
+                "cli",
+                "c"
+            ),
+            HookResult::Continue
+        );
+        // Missing key never matches
+        assert_eq!(
+            engine.before_tool("shell", &serde_json::json!({"cwd": "rm -rf"}), "cli", "c"),
+            HookResult::Continue
+        );
+    }
+
+    #[test]
+    fn test_arg_match_nested_and_non_string_values() {
+        let nested = ArgMatch {
+            key: "options.paths.1".to_string(),
+            contains: "secret".to_string(),
+        };
+        let args = serde_json::json!({"options": {"paths": ["/tmp", "/etc/secret"]}});
+        assert!(nested.matches(&args));
+        assert!(!nested.matches(&serde_json::json!({"options": {"paths": ["/tmp"]}})));
+        assert!(!nested.matches(&serde_json::json!({"options": "flat"})));
+
+        let number = ArgMatch {
+            key: "count".to_string(),
+            contains: "42".to_string(),
+        };
+        assert!(number.matches(&serde_json::json!({"count": 1420})));
+
+        let object = ArgMatch {
+            key: "env".to_string(),
+            contains: "\"DEBUG\"".to_string(),
+        };
+        assert!(object.matches(&serde_json::json!({"env": {"DEBUG": "1"}})));
+    }
+
+    #[test]
+    fn test_stats_count_blocked_calls() {
+        let engine = HookEngine::new(HooksConfig {
+            enabled: true,
+            before_tool: vec![
+                HookRule {
+                    action: HookAction::Log,
+                    tools: vec!["*".to_string()],
+                    ..Default::default()
+                },
+                HookRule {
+                    action: HookAction::Block,
+                    tools: vec!["shell".to_string()],
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        });
+        assert_eq!(engine.stats(), HookStats::default());
+
+        let args = serde_json::json!({"command": "ls"});
+        for _ in 0..2 {
+            assert!(matches!(
+                engine.before_tool("shell", &args, "cli", "c"),
+                HookResult::Block(_)
+            ));
+        }
+        assert_eq!(
+            engine.before_tool("read_file", &args, "cli", "c"),
+            HookResult::Continue
+        );
+
+        let stats = engine.stats();
+        assert_eq!(stats.blocked, 2);
+        assert_eq!(stats.rewritten, 0);
+        assert_eq!(stats.matched["before_tool"]["log"], 3);
+        assert_eq!(stats.matched["before_tool"]["block"], 2);
+        assert!(!stats.matched.contains_key("after_tool"));
+    }
+
+    #[test]
+    fn test_stats_count_each_hook_point() {
+        let log_all = || {
+            vec![HookRule {
+                action: HookAction::Log,
+                tools: vec!["*".to_string()],
+                ..Default::default()
+            }]
+        };
+        let engine = HookEngine::new(HooksConfig {
+            enabled: true,
+            before_tool: vec![rewrite_rule(serde_json::json!({"timeout": 30}), &[])],
+            after_tool: log_all(),
+            on_error: log_all(),
+            on_turn_start: log_all(),
+            on_turn_end: log_all(),
+        });
+
+        engine.on_turn_start("cli", "c");
+        engine.before_tool("shell", &serde_json::json!({}), "cli", "c");
+        engine.after_tool("shell", "ok", Duration::from_millis(1), "cli", "c");
+        engine.on_error("shell", "boom", "cli", "c");
+        engine.on_turn_end("cli", "c", Duration::from_millis(2), 1);
+
+        let stats = engine.stats();
+        assert_eq!(stats.rewritten, 1);
+        assert_eq!(stats.matched["before_tool"]["rewrite"], 1);
+        for hook in ["after_tool", "on_error", "on_turn_start", "on_turn_end"] {
+            assert_eq!(stats.matched[hook]["log"], 1, "{}", hook);
+        }
+        let json = serde_json::to_value(&stats).unwrap();
+        assert_eq!(json["matched"]["on_error"]["log"], 1);
+    }
+
+    fn rewrite_rule(set: serde_json::Value, remove: &[&str]) -> HookRule {
+        HookRule {
+            action: HookAction::Rewrite,
+            tools: vec!["shell".to_string()],
+            set: set.as_object().cloned().unwrap_or_default(),
+            remove: remove.iter().map(|p| p.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_before_tool_rewrite_injects_timeout() {
+        let engine = HookEngine::new(HooksConfig {
+            enabled: true,
+            before_tool: vec![rewrite_rule(serde_json::json!({"timeout": 30}), &[])],
+            ..Default::default()
+        });
+
+        let result = engine.before_tool(
+            "shell",
+            &serde_json::json!({"command": "make test"}),
+            "cli",
+            "c",
+        );
+        assert_eq!(
+            result,
+            HookResult::Rewrite(serde_json::json!({"command": "make test", "timeout": 30}))
+        );
+
+        // Other tools and already-matching args are left alone
+        assert_eq!(
+            engine.before_tool("echo", &serde_json::json!({}), "cli", "c"),
+            HookResult::Continue
+        );
+        assert_eq!(
+            engine.before_tool("shell", &serde_json::json!({"timeout": 30}), "cli", "c"),
+            HookResult::Continue
+        );
+    }
+
+    #[test]
+    fn test_before_tool_rewrites_compose_in_order() {
+        let mut second = rewrite_rule(serde_json::json!({"timeout": 60}), &["env.TOKEN"]);
+        // Only matches once the first rule has set the working directory
+        second.arg_match = Some(ArgMatch {
+            key: "cwd".to_string(),
+            contains: "/srv".to_string(),
+        });
+        let engine = HookEngine::new(HooksConfig {
+            enabled: true,
+            before_tool: vec![
+                rewrite_rule(
+                    serde_json::json!({"timeout": 30, "cwd": "/srv/app", "options.shell": "sh"}),
+                    &[],
+                ),
+                second,
+            ],
+            ..Default::default()
+        });
+
+        let result = engine.before_tool(
+            "shell",
+            &serde_json::json!({"command": "ls", "env": {"TOKEN": "x", "LANG": "C"}}),
+            "cli",
+            "c",
+        );
+        assert_eq!(
+            result,
+            HookResult::Rewrite(serde_json::json!({
+                "command": "ls",
+                "cwd": "/srv/app",
+                "env": {"LANG": "C"},
+                "options": {"shell": "sh"},
+                "timeout": 60
+            }))
+        );
+    }
+
+    #[test]
+    fn test_before_tool_rewrite_runs_before_block() {
+        let engine = HookEngine::new(HooksConfig {
+            enabled: true,
+            before_tool: vec![
+                HookRule {
+                    action: HookAction::Block,
+                    tools: vec!["shell".to_string()],
+                    arg_match: Some(ArgMatch {
+                        key: "command".to_string(),
+                        contains: "--force".to_string(),
+                    }),
+                    ..Default::default()
+                },
+                // Listed after the block rule but applied first
+                rewrite_rule(serde_json::json!({"command": "git push"}), &[]),
+            ],
+            ..Default::default()
+        });
+
+        let result = engine.before_tool(
+            "shell",
+            &serde_json::json!({"command": "git push --force"}),
+            "cli",
+            "c",
+        );
+        assert_eq!(
+            result,
+            HookResult::Rewrite(serde_json::json!({"command": "git push"}))
+        );
+    }
+
+    #[test]
+    fn test_rewrite_paths_into_arrays_and_missing_keys() {
+        let rule = rewrite_rule(
+            serde_json::json!({"files.0": "a.txt", "files.5": "ignored"}),
+            &["files.1", "missing.key"],
+        );
+        let mut args = serde_json::json!({"files": ["x", "y", "z"]});
+        rule.apply_rewrite(&mut args);
+        assert_eq!(args, serde_json::json!({"files": ["a.txt", "z"]}));
+    }
+
+    #[test]
+    fn test_rewrite_rule_deserialize() {
+        let json = r#"{
+            "action": "rewrite",
+            "tools": ["shell"],
+            "set": { "timeout": 30 },
+            "remove": ["env"]
+        }"#;
+        let rule: HookRule = serde_json::from_str(json).unwrap();
+        assert_eq!(rule.action, HookAction::Rewrite);
+        assert_eq!(rule.set.get("timeout"), Some(&serde_json::json!(30)));
+        assert_eq!(rule.remove, vec!["env"]);
+    }
+
+    #[test]
+    fn test_arg_match_deserialize() {
+        let json = r#"{
+            "action": "block",
+            "tools": ["shell"],
+            "arg_match": { "key": "command", "contains": "rm -rf" }
+        }"#;
+        let rule: HookRule = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            rule.arg_match,
+            Some(ArgMatch {
+                key: "command".to_string(),
+                contains: "rm -rf".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_hook_engine_on_error_retry_outcome() {
+        let config = HooksConfig {
+            enabled: true,
+            on_error: vec![
+                HookRule {
+                    action: HookAction::Log,
+                    tools: vec!["*".to_string()],
+                    ..Default::default()
+                },
+                HookRule {
+                    action: HookAction::Retry,
+                    tools: vec!["web_fetch".to_string()],
+                    channels: vec!["telegram".to_string()],
+                    max_retries: Some(3),
+                    backoff_ms: Some(250),
+                    ..Default::default()
+                },
+                HookRule {
+                    action: HookAction::Retry,
+                    tools: vec!["*".to_string()],
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let engine = HookEngine::new(config);
+
+        // First matching retry rule wins
+        assert_eq!(
+            engine.on_error("web_fetch", "timeout", "telegram", "chat1"),
+            ErrorHookOutcome::Retry {
+                max_retries: 3,
+                backoff: std::time::Duration::from_millis(250),
+            }
+        );
+        // Channel mismatch falls through to the catch-all rule with defaults
+        assert_eq!(
+            engine.on_error("web_fetch", "timeout", "cli", "chat1"),
+            ErrorHookOutcome::Retry {
+                max_retries: 1,
+                backoff: std::time::Duration::ZERO,
+            }
+        );
+    }
+
+    #[test]
+    fn test_hook_engine_on_error_retry_requires_match() {
+        let config = HooksConfig {
+            enabled: true,
+            on_error: vec![HookRule {
+                action: HookAction::Retry,
+                tools: vec!["web_fetch".to_string()],
+                channels: vec!["telegram".to_string()],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let engine = HookEngine::new(config);
+        assert_eq!(
+            engine.on_error("shell", "boom", "telegram", "chat1"),
+            ErrorHookOutcome::Continue
+        );
+        assert_eq!(
+            engine.on_error("web_fetch", "boom", "discord", "chat1"),
+            ErrorHookOutcome::Continue
+        );
+
+        // Retry is ignored outside on_error
+        let engine = HookEngine::new(HooksConfig {
+            enabled: true,
+            before_tool: vec![HookRule {
+                action: HookAction::Retry,
+                tools: vec!["*".to_string()],
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+        assert_eq!(
+            engine.before_tool("shell", &serde_json::json!({}), "cli", "chat1"),
+            HookResult::Continue
+        );
+    }
+
     #[test]
     fn test_hook_engine_is_enabled() {
         let enabled = HookEngine::new(HooksConfig {
@@ -819,4 +1697,142 @@ mod tests {
         assert!(outbound.content.contains("permission denied"));
         assert!(outbound.content.contains("shell"));
     }
+
+    fn cooldown_notify_engine(bus: Arc<MessageBus>) -> HookEngine {
+        HookEngine::new(HooksConfig {
+            enabled: true,
+            before_tool: vec![HookRule {
+                action: HookAction::Block,
+                tools: vec!["shell".to_string()],
+                cooldown_secs: Some(60),
+                ..Default::default()
+            }],
+            after_tool: vec![HookRule {
+                action: HookAction::Notify,
+                tools: vec!["*".to_string()],
+                message: Some("tool ran".to_string()),
+                cooldown_secs: Some(60),
+                ..Default::default()
+            }],
+            ..Default::default()
+        })
+        .with_bus(bus)
+    }
+
+    #[tokio::test]
+    async fn test_notify_cooldown_suppresses_repeat_notifications() {
+        use tokio::time::{timeout, Duration};
+
+        let bus = Arc::new(MessageBus::new());
+        let engine = cooldown_notify_engine(Arc::clone(&bus));
+        let elapsed = std::time::Duration::from_millis(5);
+
+        engine.after_tool("echo", "ok", elapsed, "telegram", "chat1");
+        engine.after_tool("read_file", "ok", elapsed, "telegram", "chat1");
+
+        let first = timeout(Duration::from_millis(300), bus.consume_outbound())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(first.content, "tool ran");
+        assert!(
+            timeout(Duration::from_millis(100), bus.consume_outbound())
+                .await
+                .is_err(),
+            "second notification should be suppressed"
+        );
+
+        // Once the cooldown has elapsed the rule fires again.
+        engine.last_notified.lock().unwrap().insert(
+            ("after_tool", 0),
+            Instant::now()
+                .checked_sub(std::time::Duration::from_secs(61))
+                .unwrap(),
+        );
+        engine.after_tool("echo", "ok", elapsed, "telegram", "chat1");
+        let again = timeout(Duration::from_millis(300), bus.consume_outbound())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(again.content, "tool ran");
+    }
+
+    #[test]
+    fn test_cooldown_does_not_affect_block() {
+        let engine = cooldown_notify_engine(Arc::new(MessageBus::new()));
+        for _ in 0..3 {
+            assert!(matches!(
+                engine.before_tool("shell", &serde_json::json!({}), "cli", "chat1"),
+                HookResult::Block(_)
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_turn_hooks_notify_on_start_and_end() {
+        use tokio::time::{timeout, Duration};
+
+        let bus = Arc::new(MessageBus::new());
+        let engine = HookEngine::new(HooksConfig {
+            enabled: true,
+            on_turn_start: vec![
+                HookRule {
+                    action: HookAction::Notify,
+                    ..Default::default()
+                },
+                HookRule {
+                    action: HookAction::Block,
+                    ..Default::default()
+                },
+            ],
+            on_turn_end: vec![HookRule {
+                action: HookAction::Notify,
+                channels: vec!["telegram".to_string()],
+                ..Default::default()
+            }],
+            ..Default::default()
+        })
+        .with_bus(Arc::clone(&bus));
+
+        engine.on_turn_start("telegram", "chat5");
+        engine.on_turn_end(
+            "telegram",
+            "chat5",
+            std::time::Duration::from_millis(1200),
+            3,
+        );
+        // Channel filter still applies to turn hooks
+        engine.on_turn_end("discord", "chat5", std::time::Duration::from_millis(5), 0);
+
+        let start = timeout(Duration::from_millis(300), bus.consume_outbound())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(start.chat_id, "chat5");
+        assert!(start.content.contains("turn started"));
+
+        let end = timeout(Duration::from_millis(300), bus.consume_outbound())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(end.content.contains("1200ms"));
+        assert!(end.content.contains("3 tool call(s)"));
+
+        assert!(timeout(Duration::from_millis(100), bus.consume_outbound())
+            .await
+            .is_err());
+    }
+
+    #[test]
+    fn test_turn_hooks_deserialize() {
+        let json = r#"{
+            "enabled": true,
+            "on_turn_start": [{ "action": "log" }],
+            "on_turn_end": [{ "action": "notify", "chat_id": "ops" }]
+        }"#;
+        let config: HooksConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.on_turn_start.len(), 1);
+        assert_eq!(config.on_turn_end[0].action, HookAction::Notify);
+        assert_eq!(config.on_turn_end[0].chat_id.as_deref(), Some("ops"));
+    }
 }
@@ -56,7 +56,7 @@ pub use gateway::is_apple_container_available;
 pub use gateway::{
     generate_env_file_content, is_docker_available, is_docker_available_with_binary,
     parse_marked_response, resolve_backend, AgentRequest, AgentResponse, AgentResult,
-    ContainerAgentProxy, ResolvedBackend, RESPONSE_END_MARKER, RESPONSE_START_MARKER,
+    ContainerAgentProxy, DrainSummary, ResolvedBackend, RESPONSE_END_MARKER, RESPONSE_START_MARKER,
 };
 pub use health::{health_port, start_health_server, start_periodic_usage_flush, UsageMetrics};
 
@@ -70,6 +70,7 @@ pub use session::{Message, Role, Session, SessionManager, ToolCall};
 pub use tools::screenshot::WebScreenshotTool;
 pub use tools::{
     cron::CronTool, custom::CustomTool, delegate::DelegateTool, spawn::SpawnTool, BinaryPluginTool,
-    EchoTool, GoogleSheetsTool, MemoryGetTool, MemorySearchTool, MessageTool, R8rTool,
-    ReminderTool, Tool, ToolContext, ToolRegistry, WebFetchTool, WebSearchTool, WhatsAppTool,
+    EchoTool, GoogleSheetsTool, MemoryGetTool, MemorySearchTool, MemoryWriteTool, MessageTool,
+    R8rTool, ReminderTool, Tool, ToolContext, ToolRegistry, WebFetchTool, WebSearchTool,
+    WhatsAppTool,
 };
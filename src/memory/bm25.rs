@@ -0,0 +1,168 @@
+//! Corpus-level BM25 scoring for workspace memory chunks.
+//!
+//! Unlike `Bm25Searcher` (which maintains a persistent index over long-term
+//! memory entries), this scores a transient corpus — the chunks collected by
+//! a single workspace memory search — so document frequencies reflect the
+//! files actually being searched. Always compiled.
+
+use std::collections::{HashMap, HashSet};
+
+/// BM25 term-frequency saturation.
+const K1: f32 = 1.2;
+/// BM25 document-length normalization.
+const B: f32 = 0.75;
+
+/// Tokenize text into lowercase alphanumeric terms of 2+ characters.
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|ch: char| !ch.is_ascii_alphanumeric())
+        .filter(|term| term.len() >= 2)
+        .map(|term| term.to_string())
+        .collect()
+}
+
+/// Score every chunk against `query` with Okapi BM25.
+///
+/// Document frequency and average length are computed across `chunks`.
+/// Each score is divided by the best achievable score for the query
+/// (`sum(idf) * (K1 + 1)`), so results fall in `[0.0, 1.0]` and stay
+/// comparable against an absolute `min_score` threshold.
+pub fn score_corpus(chunks: &[&str], query: &str) -> Vec<f32> {
+    let docs: Vec<Vec<String>> = chunks.iter().map(|c| tokenize(c)).collect();
+    let docs: Vec<&[String]> = docs.iter().map(Vec::as_slice).collect();
+    score_tokenized(&docs, query)
+}
+
+/// Same as [`score_corpus`], for chunks already split with [`tokenize`].
+pub fn score_tokenized(docs: &[&[String]], query: &str) -> Vec<f32> {
+    let mut query_terms = tokenize(query);
+    query_terms.sort();
+    query_terms.dedup();
+    if query_terms.is_empty() || docs.is_empty() {
+        return vec![0.0; docs.len()];
+    }
+
+    let total_len: usize = docs.iter().map(|doc| doc.len()).sum();
+    let avg_len = (total_len as f32 / docs.len() as f32).max(1.0);
+    let n = docs.len() as f32;
+
+    let mut doc_freq: HashMap<&str, f32> = HashMap::new();
+    for doc in docs {
+        let unique: HashSet<&str> = doc.iter().map(String::as_str).collect();
+        for term in &query_terms {
+            if unique.contains(term.as_str()) {
+                *doc_freq.entry(term.as_str()).or_insert(0.0) += 1.0;
+            }
+        }
+    }
+
+    let idf: Vec<f32> = query_terms
+        .iter()
+        .map(|term| {
+            let df = doc_freq.get(term.as_str()).copied().unwrap_or(0.0);
+            ((n - df + 0.5) / (df + 0.5) + 1.0).ln()
+        })
+        .collect();
+    let max_possible: f32 = idf.iter().sum::<f32>() * (K1 + 1.0);
+    if max_possible <= 0.0 {
+        return vec![0.0; docs.len()];
+    }
+
+    docs.iter()
+        .map(|doc| {
+            if doc.is_empty() {
+                return 0.0;
+            }
+            let doc_len = doc.len() as f32;
+            let mut tf: HashMap<&str, f32> = HashMap::new();
+            for token in doc.iter() {
+                *tf.entry(token.as_str()).or_insert(0.0) += 1.0;
+            }
+
+            let score: f32 = query_terms
+                .iter()
+                .zip(&idf)
+                .map(|(term, idf)| {
+                    let tf = tf.get(term.as_str()).copied().unwrap_or(0.0);
+                    if tf == 0.0 {
+                        return 0.0;
+                    }
+                    idf * (tf * (K1 + 1.0)) / (tf + K1 * (1.0 - B + B * doc_len / avg_len))
+                })
+                .sum();
+
+            (score / max_possible).clamp(0.0, 1.0)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_score_corpus_empty_inputs() {
+        assert!(score_corpus(&[], "rust").is_empty());
+        assert_eq!(score_corpus(&["some text"], "  "), vec![0.0]);
+        assert_eq!(score_corpus(&[""], "rust"), vec![0.0]);
+    }
+
+    #[test]
+    fn test_score_tokenized_matches_score_corpus() {
+        let chunks = ["Deploy runs on Friday", "notes notes", "deploy notes"];
+        let tokens: Vec<Vec<String>> = chunks.iter().map(|c| tokenize(c)).collect();
+        let tokens: Vec<&[String]> = tokens.iter().map(Vec::as_slice).collect();
+        assert_eq!(
+            score_tokenized(&tokens, "deploy notes"),
+            score_corpus(&chunks, "deploy notes")
+        );
+    }
+
+    #[test]
+    fn test_score_corpus_no_match_is_zero() {
+        let scores = score_corpus(&["apples and pears"], "rust");
+        assert_eq!(scores, vec![0.0]);
+    }
+
+    #[test]
+    fn test_score_corpus_in_unit_range() {
+        let chunks = ["rust rust rust", "rust", "go python", "rust go"];
+        for score in score_corpus(&chunks, "rust go") {
+            assert!(
+                (0.0..=1.0).contains(&score),
+                "score out of range: {}",
+                score
+            );
+        }
+    }
+
+    #[test]
+    fn test_score_corpus_rare_term_outweighs_common_term() {
+        // "notes" appears everywhere (boilerplate); "deploy" is rare.
+        let chunks = [
+            "## Notes\nnotes notes",
+            "## Notes\nmore notes here",
+            "## Notes\nDeploy runs on Friday",
+            "## Notes\nnothing relevant",
+        ];
+        let scores = score_corpus(&chunks, "notes deploy");
+        let best = scores
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .map(|(i, _)| i)
+            .unwrap();
+        assert_eq!(best, 2, "scores: {:?}", scores);
+    }
+
+    #[test]
+    fn test_score_corpus_short_chunk_not_penalized() {
+        let chunks = [
+            "Preferred editor: helix",
+            "Long meeting recap with many words about planning, budgets, hiring, \
+             roadmaps, retrospectives and an editor mention buried at the end",
+        ];
+        let scores = score_corpus(&chunks, "editor");
+        assert!(scores[0] > scores[1], "scores: {:?}", scores);
+    }
+}
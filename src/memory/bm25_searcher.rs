@@ -138,6 +138,21 @@ impl MemorySearcher for Bm25Searcher {
         (score / max_possible).clamp(0.0, 1.0)
     }
 
+    /// Workspace chunks are not part of the persistent index, so rank them
+    /// with document frequencies from the searched corpus itself.
+    fn score_corpus(&self, chunks: &[&str], query: &str) -> Vec<f32> {
+        super::bm25::score_corpus(chunks, query)
+    }
+
+    fn score_tokenized_corpus(
+        &self,
+        _chunks: &[&str],
+        tokens: &[&[String]],
+        query: &str,
+    ) -> Vec<f32> {
+        super::bm25::score_tokenized(tokens, query)
+    }
+
     async fn index(&self, key: &str, text: &str) -> Result<()> {
         let tokens = Self::tokenize(text);
         let mut index = self.index.write().unwrap();
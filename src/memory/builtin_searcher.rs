@@ -13,6 +13,9 @@ use super::traits::MemorySearcher;
 /// - Tokenize query into terms (2+ char alphanumeric tokens)
 /// - Count term hits in chunk (case-insensitive)
 /// - Score = coverage * 0.7 + density * 0.3 + phrase_bonus (0.25 if full phrase matches)
+///
+/// Workspace memory chunks are ranked with corpus-level BM25 instead
+/// (see [`super::bm25::score_corpus`]), which weights rare terms higher.
 pub struct BuiltinSearcher;
 
 impl BuiltinSearcher {
@@ -69,6 +72,19 @@ impl MemorySearcher for BuiltinSearcher {
 
         (coverage * 0.7 + density * 0.3 + phrase_bonus).min(1.0)
     }
+
+    fn score_corpus(&self, chunks: &[&str], query: &str) -> Vec<f32> {
+        super::bm25::score_corpus(chunks, query)
+    }
+
+    fn score_tokenized_corpus(
+        &self,
+        _chunks: &[&str],
+        tokens: &[&[String]],
+        query: &str,
+    ) -> Vec<f32> {
+        super::bm25::score_tokenized(tokens, query)
+    }
 }
 
 #[cfg(test)]
@@ -60,24 +60,30 @@ mod tests {
 
     #[test]
     fn test_create_searcher_disabled_returns_builtin() {
-        let mut config = MemoryConfig::default();
-        config.backend = MemoryBackend::Disabled;
+        let config = MemoryConfig {
+            backend: MemoryBackend::Disabled,
+            ..Default::default()
+        };
         let searcher = create_searcher(&config);
         assert_eq!(searcher.name(), "builtin");
     }
 
     #[test]
     fn test_create_searcher_qmd_falls_back() {
-        let mut config = MemoryConfig::default();
-        config.backend = MemoryBackend::Qmd;
+        let config = MemoryConfig {
+            backend: MemoryBackend::Qmd,
+            ..Default::default()
+        };
         let searcher = create_searcher(&config);
         assert_eq!(searcher.name(), "builtin");
     }
 
     #[test]
     fn test_create_searcher_embedding_falls_back() {
-        let mut config = MemoryConfig::default();
-        config.backend = MemoryBackend::Embedding;
+        let config = MemoryConfig {
+            backend: MemoryBackend::Embedding,
+            ..Default::default()
+        };
         let searcher = create_searcher(&config);
         assert_eq!(searcher.name(), "builtin");
     }
@@ -85,16 +91,20 @@ mod tests {
     #[cfg(feature = "memory-bm25")]
     #[test]
     fn test_create_searcher_bm25() {
-        let mut config = MemoryConfig::default();
-        config.backend = MemoryBackend::Bm25;
+        let config = MemoryConfig {
+            backend: MemoryBackend::Bm25,
+            ..Default::default()
+        };
         let searcher = create_searcher(&config);
         assert_eq!(searcher.name(), "bm25");
     }
 
     #[test]
     fn test_create_searcher_hnsw_falls_back() {
-        let mut config = MemoryConfig::default();
-        config.backend = MemoryBackend::Hnsw;
+        let config = MemoryConfig {
+            backend: MemoryBackend::Hnsw,
+            ..Default::default()
+        };
         let searcher = create_searcher(&config);
         assert_eq!(searcher.name(), "builtin");
     }
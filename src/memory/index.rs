@@ -0,0 +1,200 @@
+//! In-memory cache of parsed workspace memory files.
+//!
+//! `search_workspace_memory` normally re-reads, re-chunks and re-tokenizes
+//! every markdown file on each call. A [`MemoryIndex`] keeps the parsed
+//! result per canonical path together with the file's mtime and size, and
+//! only re-parses a file when either changes.
+//!
+//! The cache is advisory: if a file cannot be stat'ed (or its mtime is not
+//! available on this platform) the entry is dropped and the file is read
+//! fresh, exactly as without an index.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use super::read_memory_file;
+
+/// One searchable chunk of a memory file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemoryChunk {
+    /// First line of the chunk (1-based, relative to the whole file).
+    pub start_line: usize,
+    /// Last line of the chunk (1-based).
+    pub end_line: usize,
+    /// Chunk text.
+    pub text: String,
+    /// Terms of `text` split by `bm25::tokenize`, ready for scoring.
+    pub tokens: Vec<String>,
+}
+
+/// A memory file split into frontmatter tags and body chunks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedMemoryFile {
+    /// Tags from the file's YAML frontmatter.
+    pub tags: Vec<String>,
+    /// Non-empty body chunks.
+    pub chunks: Vec<MemoryChunk>,
+}
+
+#[derive(Debug)]
+struct CachedFile {
+    modified: SystemTime,
+    len: u64,
+    chunk_params: (usize, usize),
+    parsed: Arc<ParsedMemoryFile>,
+}
+
+/// Cache of parsed memory files keyed by canonical path.
+///
+/// Construct once and pass it to every search (via
+/// `MemorySearchOptions::index`) to reuse parsing work across calls.
+#[derive(Debug, Default)]
+pub struct MemoryIndex {
+    files: Mutex<HashMap<PathBuf, CachedFile>>,
+}
+
+impl MemoryIndex {
+    /// Create an empty index.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of cached files.
+    pub fn len(&self) -> usize {
+        self.files.lock().unwrap().len()
+    }
+
+    /// Whether the index holds no cached files.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drop all cached entries.
+    pub fn clear(&self) {
+        self.files.lock().unwrap().clear();
+    }
+
+    /// Return the parsed file, re-parsing only if its mtime, size or the
+    /// chunking parameters changed since it was cached.
+    ///
+    /// Returns `None` if the file cannot be read.
+    pub(crate) fn load(
+        &self,
+        path: &Path,
+        chunk_lines: usize,
+        chunk_overlap: usize,
+    ) -> Option<Arc<ParsedMemoryFile>> {
+        let key = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        let stat = std::fs::metadata(path)
+            .and_then(|meta| Ok((meta.modified()?, meta.len())))
+            .ok();
+        let chunk_params = (chunk_lines, chunk_overlap);
+
+        let Some((modified, len)) = stat else {
+            self.files.lock().unwrap().remove(&key);
+            return read_memory_file(path, chunk_lines, chunk_overlap).map(Arc::new);
+        };
+
+        if let Some(cached) = self.files.lock().unwrap().get(&key) {
+            if cached.modified == modified
+                && cached.len == len
+                && cached.chunk_params == chunk_params
+            {
+                return Some(cached.parsed.clone());
+            }
+        }
+
+        let parsed = match read_memory_file(path, chunk_lines, chunk_overlap) {
+            Some(parsed) => Arc::new(parsed),
+            None => {
+                self.files.lock().unwrap().remove(&key);
+                return None;
+            }
+        };
+        self.files.lock().unwrap().insert(
+            key,
+            CachedFile {
+                modified,
+                len,
+                chunk_params,
+                parsed: parsed.clone(),
+            },
+        );
+        Some(parsed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::time::Duration;
+
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_load_caches_until_mtime_changes() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("MEMORY.md");
+        fs::write(&path, "first note\n").unwrap();
+
+        let index = MemoryIndex::new();
+        let first = index.load(&path, 18, 4).unwrap();
+        let again = index.load(&path, 18, 4).unwrap();
+        assert!(Arc::ptr_eq(&first, &again));
+        assert_eq!(index.len(), 1);
+
+        fs::write(&path, "second note\n").unwrap();
+        let file = fs::File::options().write(true).open(&path).unwrap();
+        file.set_modified(SystemTime::now() + Duration::from_secs(5))
+            .unwrap();
+        drop(file);
+
+        let updated = index.load(&path, 18, 4).unwrap();
+        assert!(!Arc::ptr_eq(&first, &updated));
+        assert_eq!(updated.chunks[0].text, "second note");
+        assert_eq!(updated.chunks[0].tokens, vec!["second", "note"]);
+    }
+
+    #[test]
+    fn test_load_reparses_when_chunk_params_change() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("MEMORY.md");
+        let content: String = (1..=30).map(|i| format!("line {}\n", i)).collect();
+        fs::write(&path, content).unwrap();
+
+        let index = MemoryIndex::new();
+        assert_eq!(index.load(&path, 18, 4).unwrap().chunks.len(), 2);
+        assert_eq!(index.load(&path, 40, 4).unwrap().chunks.len(), 1);
+        assert_eq!(index.len(), 1);
+    }
+
+    #[test]
+    fn test_load_missing_file_evicts_entry() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("MEMORY.md");
+        fs::write(&path, "note\n").unwrap();
+
+        let index = MemoryIndex::new();
+        assert!(index.load(&path, 18, 4).is_some());
+        assert!(!index.is_empty());
+
+        fs::remove_file(&path).unwrap();
+        assert!(index.load(&path, 18, 4).is_none());
+        assert!(index.is_empty());
+    }
+
+    #[test]
+    fn test_clear() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("MEMORY.md");
+        fs::write(&path, "note\n").unwrap();
+
+        let index = MemoryIndex::new();
+        index.load(&path, 18, 4);
+        index.clear();
+        assert!(index.is_empty());
+    }
+}
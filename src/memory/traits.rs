@@ -16,6 +16,27 @@ pub trait MemorySearcher: Send + Sync {
     /// Score a text chunk against a query. Returns 0.0..=1.0.
     fn score(&self, chunk: &str, query: &str) -> f32;
 
+    /// Score all chunks of a transient corpus against a query.
+    ///
+    /// Workspace memory search passes every collected chunk in one call so
+    /// scorers can use corpus statistics (e.g., BM25 document frequency).
+    /// Default implementation calls `score()` per chunk.
+    fn score_corpus(&self, chunks: &[&str], query: &str) -> Vec<f32> {
+        chunks.iter().map(|c| self.score(c, query)).collect()
+    }
+
+    /// Like `score_corpus()`, with each chunk's terms already split by
+    /// `bm25::tokenize` (e.g., cached in a `MemoryIndex`). `tokens[i]`
+    /// belongs to `chunks[i]`. Default implementation ignores the tokens.
+    fn score_tokenized_corpus(
+        &self,
+        chunks: &[&str],
+        _tokens: &[&[String]],
+        query: &str,
+    ) -> Vec<f32> {
+        self.score_corpus(chunks, query)
+    }
+
     /// Batch-score for backends that benefit from batching (e.g., embedding).
     /// Default implementation calls `score()` in a loop.
     async fn score_batch(&self, chunks: &[&str], query: &str) -> Vec<f32> {
@@ -71,6 +92,16 @@ mod tests {
         assert_eq!(scores, vec![0.7, 0.7, 0.7]);
     }
 
+    #[test]
+    fn test_default_score_corpus() {
+        let searcher = FixedScorer(0.4);
+        assert_eq!(searcher.score_corpus(&["a", "b"], "query"), vec![0.4, 0.4]);
+        assert_eq!(
+            searcher.score_tokenized_corpus(&["a"], &[&[]], "query"),
+            vec![0.4]
+        );
+    }
+
     #[tokio::test]
     async fn test_default_index_and_remove_are_noop() {
         let searcher = FixedScorer(0.0);
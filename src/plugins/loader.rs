@@ -14,7 +14,7 @@ use tracing::{info, warn};
 use crate::audit::{log_audit_event, AuditCategory, AuditSeverity};
 use crate::error::{Result, ZeptoError};
 
-use super::types::{BinaryPluginConfig, Plugin, PluginManifest};
+use super::types::{BinaryPluginConfig, Plugin, PluginManifest, WasmPluginConfig};
 
 /// Discover plugins across multiple directories.
 ///
@@ -144,6 +144,7 @@ pub fn load_plugin(dir: &Path) -> Result<Plugin> {
 /// - Plugin name must be 1-64 characters, alphanumeric and hyphens only
 /// - Version must be non-empty
 /// - At least one tool must be defined
+/// - `allowed_env` entries must be environment variable names
 /// - Tool names must be alphanumeric and underscores only
 /// - Command templates must not contain dangerous shell operators
 ///   (`&&`, `||`, `;`, `|`, backticks)
@@ -179,6 +180,19 @@ pub fn validate_manifest(manifest: &PluginManifest) -> Result<()> {
         )));
     }
 
+    // Validate allowed_env entries are plain environment variable names
+    let env_name_re = Regex::new(r"^[A-Za-z_][A-Za-z0-9_]*$").unwrap();
+    if let Some(bad) = manifest
+        .allowed_env
+        .iter()
+        .find(|name| !env_name_re.is_match(name))
+    {
+        return Err(ZeptoError::Config(format!(
+            "Plugin '{}' has invalid allowed_env entry '{}': must be an environment variable name",
+            manifest.name, bad
+        )));
+    }
+
     // Validate execution mode
     match manifest.execution.as_str() {
         "command" => {}
@@ -191,40 +205,50 @@ pub fn validate_manifest(manifest: &PluginManifest) -> Result<()> {
                 ))
             })?;
 
-            // Only jsonrpc protocol supported
-            if bin_cfg.protocol != "jsonrpc" {
-                return Err(ZeptoError::Config(format!(
-                    "Plugin '{}' has unsupported binary protocol '{}': only \"jsonrpc\" is supported",
-                    manifest.name, bin_cfg.protocol
-                )));
+            match bin_cfg.protocol.as_str() {
+                "jsonrpc" => {}
+                "grpc" => {
+                    let socket = bin_cfg.socket.as_deref().ok_or_else(|| {
+                        ZeptoError::Config(format!(
+                            "Plugin '{}' has binary protocol \"grpc\" but no socket path",
+                            manifest.name
+                        ))
+                    })?;
+                    validate_relative_path(&manifest.name, "socket", socket)?;
+                }
+                other => {
+                    return Err(ZeptoError::Config(format!(
+                        "Plugin '{}' has unsupported binary protocol '{}': must be \"jsonrpc\" or \"grpc\"",
+                        manifest.name, other
+                    )));
+                }
             }
 
-            // Path must be non-empty
-            if bin_cfg.path.trim().is_empty() {
-                return Err(ZeptoError::Config(format!(
-                    "Plugin '{}' has empty binary path",
+            validate_relative_path(&manifest.name, "binary", &bin_cfg.path)?;
+        }
+        "wasm" => {
+            let wasm_cfg = manifest.wasm.as_ref().ok_or_else(|| {
+                ZeptoError::Config(format!(
+                    "Plugin '{}' has execution \"wasm\" but no wasm config",
                     manifest.name
-                )));
-            }
+                ))
+            })?;
 
-            // Reject path traversal and absolute paths
-            if bin_cfg.path.contains("..") {
-                return Err(ZeptoError::SecurityViolation(format!(
-                    "Plugin '{}' binary path contains '..': path traversal not allowed",
-                    manifest.name
-                )));
-            }
+            validate_relative_path(&manifest.name, "wasm", &wasm_cfg.path)?;
 
-            if Path::new(&bin_cfg.path).is_absolute() {
-                return Err(ZeptoError::SecurityViolation(format!(
-                    "Plugin '{}' binary path must be relative, not absolute",
+            let is_wasm_file = Path::new(&wasm_cfg.path)
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("wasm"));
+            if !is_wasm_file {
+                return Err(ZeptoError::Config(format!(
+                    "Plugin '{}' wasm path must point to a .wasm module",
                     manifest.name
                 )));
             }
         }
         other => {
             return Err(ZeptoError::Config(format!(
-                "Plugin '{}' has unknown execution mode '{}': must be \"command\" or \"binary\"",
+                "Plugin '{}' has unknown execution mode '{}': must be \"command\", \"binary\" or \"wasm\"",
                 manifest.name, other
             )));
         }
@@ -241,7 +265,7 @@ pub fn validate_manifest(manifest: &PluginManifest) -> Result<()> {
         }
 
         // Only check command safety for command-mode plugins
-        if !manifest.is_binary() {
+        if manifest.execution == "command" {
             validate_command_safety(&tool.command, &tool.name, &manifest.name)?;
         }
     }
@@ -249,6 +273,34 @@ pub fn validate_manifest(manifest: &PluginManifest) -> Result<()> {
     Ok(())
 }
 
+/// Reject empty, traversing (`..`) or absolute plugin file paths.
+///
+/// `kind` names the path in error messages (e.g. "binary", "wasm").
+fn validate_relative_path(plugin_name: &str, kind: &str, path: &str) -> Result<()> {
+    if path.trim().is_empty() {
+        return Err(ZeptoError::Config(format!(
+            "Plugin '{}' has empty {} path",
+            plugin_name, kind
+        )));
+    }
+
+    if path.contains("..") {
+        return Err(ZeptoError::SecurityViolation(format!(
+            "Plugin '{}' {} path contains '..': path traversal not allowed",
+            plugin_name, kind
+        )));
+    }
+
+    if Path::new(path).is_absolute() {
+        return Err(ZeptoError::SecurityViolation(format!(
+            "Plugin '{}' {} path must be relative, not absolute",
+            plugin_name, kind
+        )));
+    }
+
+    Ok(())
+}
+
 /// Validate binary exists, is a file, is executable, and stays within plugin dir.
 ///
 /// Canonicalizes both paths and verifies the binary does not escape the plugin
@@ -265,18 +317,103 @@ pub fn validate_binary_path(
     binary_config: &BinaryPluginConfig,
 ) -> Result<PathBuf> {
     let binary_path = plugin_dir.join(&binary_config.path);
+    let canonical_bin = resolve_plugin_file(plugin_dir, &binary_path, "Binary")?;
+
+    // Check execute bit on Unix
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let perms = binary_path.metadata().map_err(|e| {
+            ZeptoError::Config(format!(
+                "Failed to read permissions for {}: {}",
+                binary_path.display(),
+                e
+            ))
+        })?;
+        if perms.permissions().mode() & 0o111 == 0 {
+            return Err(ZeptoError::Config(format!(
+                "Binary is not executable: {}",
+                binary_path.display()
+            )));
+        }
+    }
+
+    // SHA-256 integrity verification (when configured).
+    if let Some(ref expected_hash) = binary_config.sha256 {
+        verify_sha256(&canonical_bin, expected_hash, "Binary")?;
+    }
+
+    Ok(canonical_bin)
+}
 
-    if !binary_path.exists() {
+/// Absolute path of a gRPC plugin's unix socket.
+///
+/// The socket does not exist until the plugin starts, so only the plugin
+/// directory is canonicalized; the manifest path itself was already checked
+/// to be relative and free of `..` by [`validate_manifest`].
+pub fn grpc_socket_path(plugin_dir: &Path, binary_config: &BinaryPluginConfig) -> Result<PathBuf> {
+    let socket = binary_config
+        .socket
+        .as_deref()
+        .ok_or_else(|| ZeptoError::Config("gRPC binary plugin has no socket path".to_string()))?;
+    let canonical_dir = plugin_dir.canonicalize().map_err(|e| {
+        ZeptoError::Config(format!(
+            "Failed to canonicalize plugin dir {}: {}",
+            plugin_dir.display(),
+            e
+        ))
+    })?;
+    Ok(canonical_dir.join(socket))
+}
+
+/// Validate a WASM module exists, stays within plugin dir, and starts with
+/// the WebAssembly magic header (`\0asm`).
+///
+/// # Arguments
+/// * `plugin_dir` - The plugin's root directory
+/// * `wasm_config` - The WASM plugin configuration containing the relative path
+///
+/// # Returns
+/// The canonicalized absolute path to the module, or an error.
+pub fn validate_wasm_path(plugin_dir: &Path, wasm_config: &WasmPluginConfig) -> Result<PathBuf> {
+    let module_path = plugin_dir.join(&wasm_config.path);
+    let canonical_module = resolve_plugin_file(plugin_dir, &module_path, "WASM module")?;
+
+    let mut magic = [0u8; 4];
+    let has_magic = fs::File::open(&canonical_module)
+        .and_then(|mut file| std::io::Read::read_exact(&mut file, &mut magic))
+        .is_ok()
+        && magic == *b"\0asm";
+    if !has_magic {
+        return Err(ZeptoError::Config(format!(
+            "WASM module is not a valid WebAssembly binary: {}",
+            canonical_module.display()
+        )));
+    }
+
+    if let Some(ref expected_hash) = wasm_config.sha256 {
+        verify_sha256(&canonical_module, expected_hash, "WASM module")?;
+    }
+
+    Ok(canonical_module)
+}
+
+/// Check that `path` is an existing file that does not escape `plugin_dir`
+/// (e.g. via symlinks). `label` prefixes error messages.
+fn resolve_plugin_file(plugin_dir: &Path, path: &Path, label: &str) -> Result<PathBuf> {
+    if !path.exists() {
         return Err(ZeptoError::Config(format!(
-            "Binary not found: {}",
-            binary_path.display()
+            "{} not found: {}",
+            label,
+            path.display()
         )));
     }
 
-    if !binary_path.is_file() {
+    if !path.is_file() {
         return Err(ZeptoError::Config(format!(
-            "Binary path is not a file: {}",
-            binary_path.display()
+            "{} path is not a file: {}",
+            label,
+            path.display()
         )));
     }
 
@@ -288,74 +425,62 @@ pub fn validate_binary_path(
             e
         ))
     })?;
-    let canonical_bin = binary_path.canonicalize().map_err(|e| {
+    let canonical_path = path.canonicalize().map_err(|e| {
         ZeptoError::Config(format!(
-            "Failed to canonicalize binary path {}: {}",
-            binary_path.display(),
+            "Failed to canonicalize {} path {}: {}",
+            label.to_lowercase(),
+            path.display(),
             e
         ))
     })?;
 
-    if !canonical_bin.starts_with(&canonical_dir) {
+    if !canonical_path.starts_with(&canonical_dir) {
         return Err(ZeptoError::SecurityViolation(format!(
-            "Binary escapes plugin directory: {} is outside {}",
-            canonical_bin.display(),
+            "{} escapes plugin directory: {} is outside {}",
+            label,
+            canonical_path.display(),
             canonical_dir.display()
         )));
     }
 
-    // Check execute bit on Unix
-    #[cfg(unix)]
-    {
-        use std::os::unix::fs::PermissionsExt;
-        let perms = binary_path.metadata().map_err(|e| {
-            ZeptoError::Config(format!(
-                "Failed to read permissions for {}: {}",
-                binary_path.display(),
-                e
-            ))
-        })?;
-        if perms.permissions().mode() & 0o111 == 0 {
-            return Err(ZeptoError::Config(format!(
-                "Binary is not executable: {}",
-                binary_path.display()
-            )));
-        }
-    }
+    Ok(canonical_path)
+}
 
-    // SHA-256 integrity verification (when configured).
-    if let Some(ref expected_hash) = binary_config.sha256 {
-        let file_bytes = fs::read(&canonical_bin).map_err(|e| {
-            ZeptoError::Config(format!(
-                "Failed to read binary for SHA-256 check {}: {}",
-                canonical_bin.display(),
-                e
-            ))
-        })?;
-        let actual_hash = hex::encode(Sha256::digest(&file_bytes));
-        if !actual_hash.eq_ignore_ascii_case(expected_hash) {
-            log_audit_event(
-                AuditCategory::PluginIntegrity,
-                AuditSeverity::Critical,
-                "sha256_mismatch",
-                &format!(
-                    "Binary {} expected SHA-256 {} but got {}",
-                    canonical_bin.display(),
-                    expected_hash,
-                    actual_hash
-                ),
-                true,
-            );
-            return Err(ZeptoError::SecurityViolation(format!(
-                "Binary SHA-256 mismatch for {}: expected {} but got {}",
-                canonical_bin.display(),
+/// Compare a file's SHA-256 digest against `expected_hash` (hex).
+fn verify_sha256(path: &Path, expected_hash: &str, label: &str) -> Result<()> {
+    let file_bytes = fs::read(path).map_err(|e| {
+        ZeptoError::Config(format!(
+            "Failed to read {} for SHA-256 check {}: {}",
+            label.to_lowercase(),
+            path.display(),
+            e
+        ))
+    })?;
+    let actual_hash = hex::encode(Sha256::digest(&file_bytes));
+    if !actual_hash.eq_ignore_ascii_case(expected_hash) {
+        log_audit_event(
+            AuditCategory::PluginIntegrity,
+            AuditSeverity::Critical,
+            "sha256_mismatch",
+            &format!(
+                "{} {} expected SHA-256 {} but got {}",
+                label,
+                path.display(),
                 expected_hash,
                 actual_hash
-            )));
-        }
+            ),
+            true,
+        );
+        return Err(ZeptoError::SecurityViolation(format!(
+            "{} SHA-256 mismatch for {}: expected {} but got {}",
+            label,
+            path.display(),
+            expected_hash,
+            actual_hash
+        )));
     }
 
-    Ok(canonical_bin)
+    Ok(())
 }
 
 /// Check a command template for dangerous shell operators.
@@ -394,7 +519,7 @@ fn validate_command_safety(command: &str, tool_name: &str, plugin_name: &str) ->
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::plugins::types::PluginToolDef;
+    use crate::plugins::types::{PluginOutputFormat, PluginToolDef};
     use serde_json::json;
     use tempfile::TempDir;
 
@@ -417,9 +542,12 @@ mod tests {
                 working_dir: None,
                 timeout_secs: None,
                 env: None,
+                output_format: PluginOutputFormat::Text,
             }],
             execution: "command".to_string(),
             binary: None,
+            wasm: None,
+            allowed_env: Vec::new(),
         }
     }
 
@@ -710,6 +838,21 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("empty version"));
     }
 
+    #[test]
+    fn test_validate_manifest_allowed_env_names() {
+        let mut manifest = valid_manifest();
+        manifest.allowed_env = vec!["GITHUB_TOKEN".to_string(), "_PATH2".to_string()];
+        assert!(validate_manifest(&manifest).is_ok());
+
+        manifest.allowed_env = vec!["BAD-NAME".to_string()];
+        let err = validate_manifest(&manifest).unwrap_err().to_string();
+        assert!(
+            err.contains("invalid allowed_env entry 'BAD-NAME'"),
+            "{}",
+            err
+        );
+    }
+
     #[test]
     fn test_validate_manifest_no_tools() {
         let mut manifest = valid_manifest();
@@ -811,6 +954,7 @@ mod tests {
             working_dir: None,
             timeout_secs: None,
             env: None,
+            output_format: PluginOutputFormat::Text,
         });
         assert!(validate_manifest(&manifest).is_ok());
     }
@@ -826,6 +970,7 @@ mod tests {
             working_dir: None,
             timeout_secs: None,
             env: None,
+            output_format: PluginOutputFormat::Text,
         });
         let result = validate_manifest(&manifest);
         assert!(result.is_err());
@@ -847,14 +992,18 @@ mod tests {
                 working_dir: None,
                 timeout_secs: None,
                 env: None,
+                output_format: PluginOutputFormat::Text,
             }],
             execution: "binary".to_string(),
             binary: Some(BinaryPluginConfig {
                 path: "bin/plugin".to_string(),
                 protocol: "jsonrpc".to_string(),
+                socket: None,
                 timeout_secs: None,
                 sha256: None,
             }),
+            wasm: None,
+            allowed_env: Vec::new(),
         }
     }
 
@@ -876,7 +1025,7 @@ mod tests {
     #[test]
     fn test_validate_binary_unsupported_protocol() {
         let mut manifest = binary_manifest();
-        manifest.binary.as_mut().unwrap().protocol = "grpc".to_string();
+        manifest.binary.as_mut().unwrap().protocol = "thrift".to_string();
         let result = validate_manifest(&manifest);
         assert!(result.is_err());
         assert!(result
@@ -885,6 +1034,53 @@ mod tests {
             .contains("unsupported binary protocol"));
     }
 
+    #[test]
+    fn test_validate_grpc_protocol_requires_socket() {
+        let mut manifest = binary_manifest();
+        let bin = manifest.binary.as_mut().unwrap();
+        bin.protocol = "grpc".to_string();
+        let err = validate_manifest(&manifest).unwrap_err();
+        assert!(err.to_string().contains("no socket path"));
+
+        manifest.binary.as_mut().unwrap().socket = Some("run/plugin.sock".to_string());
+        assert!(validate_manifest(&manifest).is_ok());
+    }
+
+    #[test]
+    fn test_validate_grpc_socket_path_rules() {
+        for (socket, expected) in [
+            ("../outside.sock", "path traversal"),
+            ("/tmp/plugin.sock", "must be relative"),
+            ("   ", "empty socket path"),
+        ] {
+            let mut manifest = binary_manifest();
+            let bin = manifest.binary.as_mut().unwrap();
+            bin.protocol = "grpc".to_string();
+            bin.socket = Some(socket.to_string());
+            let err = validate_manifest(&manifest).unwrap_err();
+            assert!(
+                err.to_string().contains(expected),
+                "socket {:?}: {}",
+                socket,
+                err
+            );
+        }
+    }
+
+    #[test]
+    fn test_grpc_socket_path_is_inside_plugin_dir() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = binary_manifest().binary.unwrap();
+        config.protocol = "grpc".to_string();
+        config.socket = Some("plugin.sock".to_string());
+
+        let path = grpc_socket_path(tmp.path(), &config).unwrap();
+        assert_eq!(path, tmp.path().canonicalize().unwrap().join("plugin.sock"));
+
+        config.socket = None;
+        assert!(grpc_socket_path(tmp.path(), &config).is_err());
+    }
+
     #[test]
     fn test_validate_binary_empty_path() {
         let mut manifest = binary_manifest();
@@ -957,6 +1153,7 @@ mod tests {
         let config = BinaryPluginConfig {
             path: "bin/plugin".to_string(),
             protocol: "jsonrpc".to_string(),
+            socket: None,
             timeout_secs: None,
             sha256: None,
         };
@@ -973,6 +1170,7 @@ mod tests {
         let config = BinaryPluginConfig {
             path: "bin/missing".to_string(),
             protocol: "jsonrpc".to_string(),
+            socket: None,
             timeout_secs: None,
             sha256: None,
         };
@@ -994,6 +1192,7 @@ mod tests {
         let config = BinaryPluginConfig {
             path: "plugin".to_string(),
             protocol: "jsonrpc".to_string(),
+            socket: None,
             timeout_secs: None,
             sha256: None,
         };
@@ -1021,6 +1220,7 @@ mod tests {
         let config = BinaryPluginConfig {
             path: "plugin".to_string(),
             protocol: "jsonrpc".to_string(),
+            socket: None,
             timeout_secs: None,
             sha256: Some(expected),
         };
@@ -1041,6 +1241,7 @@ mod tests {
         let config = BinaryPluginConfig {
             path: "plugin".to_string(),
             protocol: "jsonrpc".to_string(),
+            socket: None,
             timeout_secs: None,
             sha256: Some(
                 "0000000000000000000000000000000000000000000000000000000000000000".to_string(),
@@ -1065,6 +1266,7 @@ mod tests {
         let config = BinaryPluginConfig {
             path: "plugin".to_string(),
             protocol: "jsonrpc".to_string(),
+            socket: None,
             timeout_secs: None,
             sha256: None,
         };
@@ -1087,6 +1289,7 @@ mod tests {
         let config = BinaryPluginConfig {
             path: "plugin".to_string(),
             protocol: "jsonrpc".to_string(),
+            socket: None,
             timeout_secs: None,
             sha256: Some(expected),
         };
@@ -1107,4 +1310,102 @@ mod tests {
         let config: BinaryPluginConfig = serde_json::from_str(json).expect("should parse");
         assert_eq!(config.sha256.as_deref(), Some("abc123"));
     }
+
+    // ---- wasm plugin validation tests ----
+
+    fn wasm_manifest() -> PluginManifest {
+        PluginManifest {
+            execution: "wasm".to_string(),
+            binary: None,
+            wasm: Some(WasmPluginConfig {
+                path: "plugin.wasm".to_string(),
+                timeout_secs: None,
+                sha256: None,
+            }),
+            ..binary_manifest()
+        }
+    }
+
+    fn wasm_config(path: &str, sha256: Option<String>) -> WasmPluginConfig {
+        WasmPluginConfig {
+            path: path.to_string(),
+            timeout_secs: None,
+            sha256,
+        }
+    }
+
+    #[test]
+    fn test_validate_wasm_mode_valid() {
+        assert!(validate_manifest(&wasm_manifest()).is_ok());
+    }
+
+    #[test]
+    fn test_validate_wasm_missing_config() {
+        let mut manifest = wasm_manifest();
+        manifest.wasm = None;
+        let err = validate_manifest(&manifest).unwrap_err().to_string();
+        assert!(err.contains("no wasm config"));
+    }
+
+    #[test]
+    fn test_validate_wasm_rejects_bad_paths() {
+        for (path, expected) in [
+            ("", "empty wasm path"),
+            ("../escape.wasm", "path traversal"),
+            ("/abs/plugin.wasm", "must be relative"),
+            ("plugin.so", ".wasm module"),
+        ] {
+            let mut manifest = wasm_manifest();
+            manifest.wasm.as_mut().unwrap().path = path.to_string();
+            let err = validate_manifest(&manifest).unwrap_err().to_string();
+            assert!(err.contains(expected), "{}: {}", path, err);
+        }
+    }
+
+    #[test]
+    fn test_validate_wasm_skips_command_safety() {
+        let mut manifest = wasm_manifest();
+        manifest.tools[0].command = "a && b".to_string();
+        assert!(validate_manifest(&manifest).is_ok());
+    }
+
+    #[test]
+    fn test_validate_wasm_path_valid_module() {
+        let tmp = TempDir::new().unwrap();
+        let content = b"\0asm\x01\0\0\0";
+        fs::write(tmp.path().join("plugin.wasm"), content).unwrap();
+
+        let canonical = validate_wasm_path(tmp.path(), &wasm_config("plugin.wasm", None)).unwrap();
+        assert!(canonical.ends_with("plugin.wasm"));
+
+        let expected = hex::encode(Sha256::digest(content));
+        let config = wasm_config("plugin.wasm", Some(expected));
+        assert!(validate_wasm_path(tmp.path(), &config).is_ok());
+    }
+
+    #[test]
+    fn test_validate_wasm_path_rejects_missing_and_non_wasm() {
+        let tmp = TempDir::new().unwrap();
+        let err = validate_wasm_path(tmp.path(), &wasm_config("missing.wasm", None))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("WASM module not found"));
+
+        fs::write(tmp.path().join("fake.wasm"), b"#!/bin/sh").unwrap();
+        let err = validate_wasm_path(tmp.path(), &wasm_config("fake.wasm", None))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("not a valid WebAssembly binary"));
+    }
+
+    #[test]
+    fn test_validate_wasm_path_sha256_mismatch() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(tmp.path().join("plugin.wasm"), b"\0asm\x01\0\0\0").unwrap();
+        let config = wasm_config("plugin.wasm", Some("00".repeat(32)));
+        let err = validate_wasm_path(tmp.path(), &config)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("SHA-256 mismatch"));
+    }
 }
@@ -6,11 +6,17 @@
 //! with parameter interpolation, making it easy to extend ZeptoClaw's tool
 //! set without writing Rust code.
 //!
+//! Plugins may instead set `"execution": "binary"` (an executable speaking
+//! JSON-RPC 2.0 over stdio, or gRPC over a unix socket with the
+//! `grpc-plugins` feature) or `"execution": "wasm"` (a WebAssembly module).
+//! WASM manifests and modules are validated, but no WASM runtime is compiled
+//! in yet, so their tools are skipped at registration.
+//!
 //! # Architecture
 //!
 //! - **types**: Core data structures (`PluginManifest`, `PluginToolDef`, `Plugin`, `PluginConfig`)
 //! - **loader**: Plugin discovery, loading, and manifest validation
-//! - **registry**: Plugin and tool registration with conflict detection
+//! - **registry**: Plugin and tool registration with conflict detection and hot reload
 //!
 //! # Plugin Directory Structure
 //!
@@ -73,6 +79,12 @@ mod loader;
 pub mod registry;
 pub mod types;
 
-pub use loader::{discover_plugins, load_plugin, validate_binary_path, validate_manifest};
-pub use registry::PluginRegistry;
-pub use types::{BinaryPluginConfig, Plugin, PluginConfig, PluginManifest, PluginToolDef};
+pub use loader::{
+    discover_plugins, grpc_socket_path, load_plugin, validate_binary_path, validate_manifest,
+    validate_wasm_path,
+};
+pub use registry::{PluginRegistry, PluginReloadSummary};
+pub use types::{
+    BinaryPluginConfig, Plugin, PluginConfig, PluginManifest, PluginOutputFormat, PluginToolDef,
+    WasmPluginConfig,
+};
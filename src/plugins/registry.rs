@@ -5,14 +5,37 @@
 //! name uniqueness across all registered plugins and provides lookup methods
 //! for both plugins and individual tool definitions.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
 
-use tracing::info;
+use tracing::{info, warn};
 
 use crate::error::{Result, ZeptoError};
 
+use super::loader::discover_plugins;
 use super::types::{Plugin, PluginToolDef};
 
+/// Outcome of [`PluginRegistry::reload`]. Each list holds plugin names, sorted.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PluginReloadSummary {
+    /// Plugins that were not registered before.
+    pub added: Vec<String>,
+    /// Plugins whose manifest or directory changed and were re-registered.
+    pub updated: Vec<String>,
+    /// Plugins whose directory or `plugin.json` no longer exists.
+    pub removed: Vec<String>,
+    /// New or updated plugins rejected because of a tool name conflict.
+    /// Updated plugins keep their previously registered version.
+    pub rejected: Vec<String>,
+}
+
+impl PluginReloadSummary {
+    /// Returns true if the reload changed the registry.
+    pub fn has_changes(&self) -> bool {
+        !(self.added.is_empty() && self.updated.is_empty() && self.removed.is_empty())
+    }
+}
+
 /// A registry that holds loaded plugins and indexes their tools.
 ///
 /// The registry maintains two mappings:
@@ -25,7 +48,9 @@ use super::types::{Plugin, PluginToolDef};
 /// # Example
 ///
 /// ```rust
-/// use zeptoclaw::plugins::{PluginRegistry, Plugin, PluginManifest, PluginToolDef};
+/// use zeptoclaw::plugins::{
+///     Plugin, PluginManifest, PluginOutputFormat, PluginRegistry, PluginToolDef,
+/// };
 /// use std::path::PathBuf;
 /// use serde_json::json;
 ///
@@ -44,9 +69,12 @@ use super::types::{Plugin, PluginToolDef};
 ///         working_dir: None,
 ///         timeout_secs: None,
 ///         env: None,
+///         output_format: PluginOutputFormat::Text,
 ///     }],
 ///     execution: "command".to_string(),
 ///     binary: None,
+///     wasm: None,
+///     allowed_env: Vec::new(),
 /// };
 ///
 /// let plugin = Plugin::new(manifest, PathBuf::from("/tmp/example"));
@@ -125,6 +153,87 @@ impl PluginRegistry {
         Ok(())
     }
 
+    /// Remove a plugin and its tool mappings.
+    ///
+    /// # Returns
+    /// The removed plugin, or `None` if no plugin with that name is registered.
+    pub fn unregister(&mut self, name: &str) -> Option<Plugin> {
+        let plugin = self.plugins.remove(name)?;
+        self.tool_to_plugin.retain(|_, pname| pname != name);
+        info!(plugin = %name, "Unregistered plugin");
+        Some(plugin)
+    }
+
+    /// Re-run plugin discovery and apply the difference to the registry.
+    ///
+    /// Plugins are matched by name and compared by manifest contents and
+    /// directory: unknown names are added, changed plugins are re-registered
+    /// (whether or not their version was bumped), and plugins whose
+    /// `plugin.json` is gone are removed. A registered plugin whose manifest
+    /// still exists but no longer loads (e.g. invalid JSON mid-edit) is kept
+    /// as-is, so a bad edit never wipes the working set. Tool name conflict
+    /// detection applies as in [`register`](Self::register).
+    ///
+    /// # Errors
+    /// Returns the discovery error (e.g. unreadable plugin directory) without
+    /// modifying the registry.
+    pub fn reload(&mut self, dirs: &[PathBuf]) -> Result<PluginReloadSummary> {
+        let discovered = discover_plugins(dirs)?;
+        let mut summary = PluginReloadSummary::default();
+
+        let discovered_names: HashSet<String> =
+            discovered.iter().map(|p| p.name().to_string()).collect();
+        let mut stale: Vec<String> = self
+            .plugins
+            .iter()
+            .filter(|(name, plugin)| {
+                !discovered_names.contains(*name) && !plugin.path.join("plugin.json").exists()
+            })
+            .map(|(name, _)| name.clone())
+            .collect();
+        stale.sort();
+        for name in stale {
+            self.unregister(&name);
+            summary.removed.push(name);
+        }
+
+        // Apply updates before additions so tools dropped by an update are
+        // free for a newly added plugin to claim.
+        let (updates, additions): (Vec<Plugin>, Vec<Plugin>) = discovered
+            .into_iter()
+            .filter(|plugin| {
+                self.plugins
+                    .get(plugin.name())
+                    .map(|current| {
+                        current.manifest != plugin.manifest || current.path != plugin.path
+                    })
+                    .unwrap_or(true)
+            })
+            .partition(|plugin| self.plugins.contains_key(plugin.name()));
+
+        for (plugin, is_update) in updates
+            .into_iter()
+            .map(|p| (p, true))
+            .chain(additions.into_iter().map(|p| (p, false)))
+        {
+            let name = plugin.name().to_string();
+            match self.register(plugin) {
+                Ok(()) if is_update => summary.updated.push(name),
+                Ok(()) => summary.added.push(name),
+                Err(e) => {
+                    warn!(plugin = %name, error = %e, "Plugin rejected during reload");
+                    summary.rejected.push(name);
+                }
+            }
+        }
+
+        summary.added.sort();
+        summary.added.dedup();
+        summary.updated.sort();
+        summary.rejected.sort();
+        Ok(summary)
+    }
+
     /// Get a plugin by name.
     ///
     /// # Arguments
@@ -212,7 +321,7 @@ impl Default for PluginRegistry {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::plugins::types::{PluginManifest, PluginToolDef};
+    use crate::plugins::types::{PluginManifest, PluginOutputFormat, PluginToolDef};
     use serde_json::json;
     use std::path::PathBuf;
 
@@ -232,6 +341,7 @@ mod tests {
                 working_dir: None,
                 timeout_secs: None,
                 env: None,
+                output_format: PluginOutputFormat::Text,
             })
             .collect();
 
@@ -243,6 +353,8 @@ mod tests {
             tools,
             execution: "command".to_string(),
             binary: None,
+            wasm: None,
+            allowed_env: Vec::new(),
         };
 
         Plugin::new(manifest, PathBuf::from(format!("/tmp/{}", name)))
@@ -459,4 +571,132 @@ mod tests {
         let (_, tool_b) = registry.get_tool_plugin("tool_b").unwrap();
         assert_eq!(tool_b.command, "command_b");
     }
+
+    // ---- reload tests ----
+
+    /// Write `<root>/<name>/plugin.json` with the given version and tools.
+    fn write_plugin(root: &std::path::Path, name: &str, version: &str, tools: &[&str]) {
+        let mut plugin = make_plugin(name, tools);
+        plugin.manifest.version = version.to_string();
+        let dir = root.join(name);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("plugin.json"),
+            serde_json::to_string(&plugin.manifest).unwrap(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_reload_adds_updates_and_removes() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dirs = vec![tmp.path().to_path_buf()];
+        write_plugin(tmp.path(), "alpha", "1.0.0", &["alpha_tool"]);
+        write_plugin(tmp.path(), "beta", "1.0.0", &["beta_tool"]);
+
+        let mut registry = PluginRegistry::new();
+        let summary = registry.reload(&dirs).unwrap();
+        assert_eq!(summary.added, vec!["alpha", "beta"]);
+        assert!(summary.has_changes());
+
+        // No changes on disk: nothing to do.
+        let summary = registry.reload(&dirs).unwrap();
+        assert_eq!(summary, PluginReloadSummary::default());
+        assert!(!summary.has_changes());
+
+        write_plugin(tmp.path(), "alpha", "1.1.0", &["alpha_tool", "alpha_extra"]);
+        std::fs::remove_dir_all(tmp.path().join("beta")).unwrap();
+        write_plugin(tmp.path(), "gamma", "0.1.0", &["gamma_tool"]);
+
+        let summary = registry.reload(&dirs).unwrap();
+        assert_eq!(summary.added, vec!["gamma"]);
+        assert_eq!(summary.updated, vec!["alpha"]);
+        assert_eq!(summary.removed, vec!["beta"]);
+        assert!(summary.rejected.is_empty());
+
+        assert_eq!(registry.plugin_count(), 2);
+        assert_eq!(registry.plugin_for_tool("alpha_extra"), Some("alpha"));
+        assert!(!registry.is_tool_from_plugin("beta_tool"));
+        assert_eq!(
+            registry.get_plugin("alpha").unwrap().manifest.version,
+            "1.1.0"
+        );
+    }
+
+    #[test]
+    fn test_reload_detects_changes_without_version_bump() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dirs = vec![tmp.path().to_path_buf()];
+        write_plugin(tmp.path(), "alpha", "1.0.0", &["alpha_tool"]);
+
+        let mut registry = PluginRegistry::new();
+        registry.reload(&dirs).unwrap();
+
+        write_plugin(tmp.path(), "alpha", "1.0.0", &["alpha_tool", "alpha_extra"]);
+        let summary = registry.reload(&dirs).unwrap();
+        assert_eq!(summary.updated, vec!["alpha"]);
+        assert_eq!(registry.plugin_for_tool("alpha_extra"), Some("alpha"));
+    }
+
+    #[test]
+    fn test_reload_keeps_plugin_with_invalid_manifest() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dirs = vec![tmp.path().to_path_buf()];
+        write_plugin(tmp.path(), "alpha", "1.0.0", &["alpha_tool"]);
+
+        let mut registry = PluginRegistry::new();
+        registry.reload(&dirs).unwrap();
+
+        std::fs::write(tmp.path().join("alpha/plugin.json"), "{ not json").unwrap();
+        let summary = registry.reload(&dirs).unwrap();
+        assert!(!summary.has_changes());
+        assert!(registry.is_tool_from_plugin("alpha_tool"));
+    }
+
+    #[test]
+    fn test_reload_rejects_conflicting_plugins() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dirs = vec![tmp.path().to_path_buf()];
+        write_plugin(tmp.path(), "alpha", "1.0.0", &["shared_tool"]);
+
+        let mut registry = PluginRegistry::new();
+        registry.reload(&dirs).unwrap();
+
+        // New plugin claiming an existing tool name is rejected.
+        write_plugin(tmp.path(), "beta", "1.0.0", &["shared_tool"]);
+        let summary = registry.reload(&dirs).unwrap();
+        assert_eq!(summary.rejected, vec!["beta"]);
+        assert!(summary.added.is_empty());
+        assert_eq!(registry.plugin_for_tool("shared_tool"), Some("alpha"));
+        assert!(registry.get_plugin("beta").is_none());
+    }
+
+    #[test]
+    fn test_reload_update_frees_tool_for_new_plugin() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dirs = vec![tmp.path().to_path_buf()];
+        write_plugin(tmp.path(), "alpha", "1.0.0", &["moved_tool"]);
+
+        let mut registry = PluginRegistry::new();
+        registry.reload(&dirs).unwrap();
+
+        write_plugin(tmp.path(), "alpha", "2.0.0", &["alpha_tool"]);
+        write_plugin(tmp.path(), "beta", "1.0.0", &["moved_tool"]);
+        let summary = registry.reload(&dirs).unwrap();
+        assert_eq!(summary.updated, vec!["alpha"]);
+        assert_eq!(summary.added, vec!["beta"]);
+        assert_eq!(registry.plugin_for_tool("moved_tool"), Some("beta"));
+    }
+
+    #[test]
+    fn test_unregister() {
+        let mut registry = PluginRegistry::new();
+        registry
+            .register(make_plugin("alpha", &["a_tool"]))
+            .unwrap();
+
+        assert!(registry.unregister("alpha").is_some());
+        assert!(registry.unregister("alpha").is_none());
+        assert_eq!(registry.tool_count(), 0);
+    }
 }
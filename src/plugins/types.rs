@@ -50,7 +50,7 @@ fn default_protocol() -> String {
 ///   ]
 /// }
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PluginManifest {
     /// Plugin name. Must be unique, alphanumeric characters and hyphens only,
     /// between 1 and 64 characters.
@@ -69,28 +69,46 @@ pub struct PluginManifest {
     /// List of tool definitions provided by this plugin.
     pub tools: Vec<PluginToolDef>,
 
-    /// Execution mode: "command" (default) or "binary" (JSON-RPC stdin/stdout).
+    /// Execution mode: "command" (default), "binary" (JSON-RPC stdin/stdout)
+    /// or "wasm" (sandboxed WebAssembly module).
     #[serde(default = "default_execution")]
     pub execution: String,
 
     /// Binary plugin configuration. Required when execution is "binary".
     #[serde(default)]
     pub binary: Option<BinaryPluginConfig>,
+
+    /// WASM plugin configuration. Required when execution is "wasm".
+    #[serde(default)]
+    pub wasm: Option<WasmPluginConfig>,
+
+    /// Host environment variables that command templates and tool `env`
+    /// values may reference as `${VAR}`. Any other `${...}` is an error.
+    #[serde(default)]
+    pub allowed_env: Vec<String>,
 }
 
 /// Configuration for binary plugin execution.
 ///
-/// Binary plugins are standalone executables that communicate via JSON-RPC 2.0
-/// over stdin/stdout. They are spawned on-demand per tool call.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Binary plugins are standalone executables. With the default `"jsonrpc"`
+/// protocol they speak JSON-RPC 2.0 over stdin/stdout and are spawned
+/// on-demand per tool call. With `"grpc"` they are spawned once, serve the
+/// `zeptoclaw.plugin.v1.Plugin` service (`proto/plugin.proto`) on a unix
+/// socket, and handle every tool call of the plugin.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct BinaryPluginConfig {
     /// Relative path to binary within plugin directory.
     pub path: String,
 
-    /// Protocol: only "jsonrpc" supported.
+    /// Protocol: "jsonrpc" (default) or "grpc".
     #[serde(default = "default_protocol")]
     pub protocol: String,
 
+    /// Unix socket the plugin listens on, relative to the plugin directory.
+    /// Required when protocol is "grpc".
+    #[serde(default)]
+    pub socket: Option<String>,
+
     /// Optional timeout override in seconds (default: 30).
     #[serde(default)]
     pub timeout_secs: Option<u64>,
@@ -101,12 +119,35 @@ pub struct BinaryPluginConfig {
     pub sha256: Option<String>,
 }
 
+/// Configuration for WASM plugin execution.
+///
+/// WASM plugins are WebAssembly modules run by the wasmi interpreter when
+/// ZeptoClaw is built with the `wasm-plugins` feature. A module exports
+/// `memory`, `alloc(len) -> ptr` and
+/// `call(name_ptr, name_len, args_ptr, args_len) -> (ptr << 32) | len`,
+/// which returns `{"output": ...}` or `{"error": ...}` as JSON. The module is
+/// instantiated per tool call with no host imports, so it has no filesystem
+/// or network access. See `tools::wasm_plugin` for the full ABI.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WasmPluginConfig {
+    /// Relative path to the `.wasm` module within plugin directory.
+    pub path: String,
+
+    /// Optional timeout override in seconds (default: 30).
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+
+    /// Optional SHA-256 hex digest for module integrity verification.
+    #[serde(default)]
+    pub sha256: Option<String>,
+}
+
 /// A tool definition within a plugin manifest.
 ///
-/// Each tool wraps a shell command template that is executed when the LLM
+/// Each tool wraps a command template that is executed when the LLM
 /// invokes the tool. Parameter interpolation uses `{{param_name}}` syntax
 /// within the command string.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PluginToolDef {
     /// Tool name as registered with the agent. Must be alphanumeric
     /// characters and underscores only.
@@ -119,7 +160,10 @@ pub struct PluginToolDef {
     /// JSON Schema describing the tool's parameters.
     pub parameters: Value,
 
-    /// Shell command template. Uses `{{param_name}}` for parameter interpolation.
+    /// Command template. Uses `{{param_name}}` for parameter interpolation.
+    /// Split into argv with shell-like quoting and run without a shell, so
+    /// each interpolated value stays a single argument. `${VAR}` is replaced
+    /// with a host environment variable listed in the manifest's `allowed_env`.
     /// Must not contain dangerous shell operators (&&, ||, ;, |, backticks).
     /// Empty for binary plugins.
     #[serde(default)]
@@ -134,8 +178,34 @@ pub struct PluginToolDef {
     pub timeout_secs: Option<u64>,
 
     /// Optional environment variables to set during command execution.
+    /// Values may reference `allowed_env` variables as `${VAR}`.
     #[serde(default)]
     pub env: Option<HashMap<String, String>>,
+
+    /// How a command tool's stdout becomes the tool result: "text" (default),
+    /// "json" or "lines". Also accepted as `output`.
+    #[serde(default, alias = "output")]
+    pub output_format: PluginOutputFormat,
+}
+
+/// Transformation applied to a command plugin tool's stdout.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PluginOutputFormat {
+    /// Raw stdout, unchanged.
+    #[default]
+    Text,
+    /// Stdout parsed as JSON; invalid JSON is an error.
+    Json,
+    /// JSON array of the trimmed, non-empty stdout lines.
+    Lines,
+}
+
+impl BinaryPluginConfig {
+    /// Returns true if the binary serves gRPC on a unix socket.
+    pub fn is_grpc(&self) -> bool {
+        self.protocol == "grpc"
+    }
 }
 
 impl PluginManifest {
@@ -143,6 +213,11 @@ impl PluginManifest {
     pub fn is_binary(&self) -> bool {
         self.execution == "binary"
     }
+
+    /// Returns true if this plugin uses WASM execution mode.
+    pub fn is_wasm(&self) -> bool {
+        self.execution == "wasm"
+    }
 }
 
 impl PluginToolDef {
@@ -270,9 +345,12 @@ mod tests {
                 working_dir: None,
                 timeout_secs: Some(15),
                 env: None,
+                output_format: PluginOutputFormat::Text,
             }],
             execution: "command".to_string(),
             binary: None,
+            wasm: None,
+            allowed_env: Vec::new(),
         };
 
         let json_str = serde_json::to_string(&manifest).unwrap();
@@ -333,9 +411,26 @@ mod tests {
         assert!(tool_def.working_dir.is_none());
         assert!(tool_def.timeout_secs.is_none());
         assert!(tool_def.env.is_none());
+        assert_eq!(tool_def.output_format, PluginOutputFormat::Text);
         assert_eq!(tool_def.effective_timeout(), 30);
     }
 
+    #[test]
+    fn test_plugin_tool_def_output_format() {
+        let base = r#""name": "t", "description": "d", "parameters": {}, "command": "ls""#;
+        for (field, expected) in [
+            (r#""output_format": "json""#, PluginOutputFormat::Json),
+            (r#""output_format": "lines""#, PluginOutputFormat::Lines),
+            (r#""output": "text""#, PluginOutputFormat::Text),
+        ] {
+            let def: PluginToolDef =
+                serde_json::from_str(&format!("{{{}, {}}}", base, field)).unwrap();
+            assert_eq!(def.output_format, expected);
+        }
+        let bad = format!(r#"{{{}, "output_format": "yaml"}}"#, base);
+        assert!(serde_json::from_str::<PluginToolDef>(&bad).is_err());
+    }
+
     #[test]
     fn test_plugin_tool_def_effective_timeout() {
         let tool = PluginToolDef {
@@ -346,6 +441,7 @@ mod tests {
             working_dir: None,
             timeout_secs: Some(60),
             env: None,
+            output_format: PluginOutputFormat::Text,
         };
         assert_eq!(tool.effective_timeout(), 60);
 
@@ -357,6 +453,7 @@ mod tests {
             working_dir: None,
             timeout_secs: None,
             env: None,
+            output_format: PluginOutputFormat::Text,
         };
         assert_eq!(tool_default.effective_timeout(), 30);
     }
@@ -375,6 +472,7 @@ mod tests {
             working_dir: Some("/tmp".to_string()),
             timeout_secs: Some(5),
             env: Some(env),
+            output_format: PluginOutputFormat::Text,
         };
 
         assert_eq!(tool.env.as_ref().unwrap().get("FOO").unwrap(), "bar");
@@ -458,9 +556,12 @@ mod tests {
                 working_dir: None,
                 timeout_secs: None,
                 env: None,
+                output_format: PluginOutputFormat::Text,
             }],
             execution: "command".to_string(),
             binary: None,
+            wasm: None,
+            allowed_env: Vec::new(),
         };
 
         let plugin = Plugin::new(manifest, PathBuf::from("/tmp/test-plugin"));
@@ -515,6 +616,7 @@ mod tests {
             working_dir: None,
             timeout_secs: None,
             env: None,
+            output_format: PluginOutputFormat::Text,
         };
 
         let params = &tool.parameters;
@@ -594,6 +696,25 @@ mod tests {
         assert!(bin.timeout_secs.is_none());
     }
 
+    #[test]
+    fn test_manifest_wasm_deserialization() {
+        let json = r#"{
+            "name": "wasm-plugin",
+            "version": "0.1.0",
+            "description": "WASM plugin",
+            "execution": "wasm",
+            "wasm": { "path": "plugin.wasm", "timeout_secs": 5 },
+            "tools": [{"name": "wasm_tool", "description": "t", "parameters": {}}]
+        }"#;
+        let manifest: PluginManifest = serde_json::from_str(json).unwrap();
+        assert!(manifest.is_wasm());
+        assert!(!manifest.is_binary());
+        let wasm = manifest.wasm.unwrap();
+        assert_eq!(wasm.path, "plugin.wasm");
+        assert_eq!(wasm.timeout_secs, Some(5));
+        assert!(wasm.sha256.is_none());
+    }
+
     #[test]
     fn test_manifest_is_binary() {
         let command_manifest = PluginManifest {
@@ -604,6 +725,8 @@ mod tests {
             tools: vec![],
             execution: "command".to_string(),
             binary: None,
+            wasm: None,
+            allowed_env: Vec::new(),
         };
         assert!(!command_manifest.is_binary());
 
@@ -617,9 +740,12 @@ mod tests {
             binary: Some(BinaryPluginConfig {
                 path: "plugin".to_string(),
                 protocol: "jsonrpc".to_string(),
+                socket: None,
                 timeout_secs: None,
                 sha256: None,
             }),
+            wasm: None,
+            allowed_env: Vec::new(),
         };
         assert!(binary_manifest.is_binary());
     }
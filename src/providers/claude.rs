@@ -32,10 +32,11 @@ use serde::{Deserialize, Serialize};
 use tracing::warn;
 
 use crate::error::{Result, ZeptoError};
-use crate::session::{Message, Role, ToolCall};
+use crate::session::{ContentPart, Message, Role, ToolCall};
 
 use super::{
-    parse_provider_error, ChatOptions, LLMProvider, LLMResponse, LLMToolCall, ToolDefinition, Usage,
+    parse_provider_error, ChatOptions, HttpRetryPolicy, LLMProvider, LLMResponse, LLMToolCall,
+    OutputFormat, ToolDefinition, Usage,
 };
 
 /// The Claude API endpoint URL.
@@ -51,6 +52,12 @@ const DEFAULT_MODEL: &str = match option_env!("ZEPTOCLAW_CLAUDE_DEFAULT_MODEL")
 /// The Anthropic API version header value.
 const ANTHROPIC_VERSION: &str = "2023-06-01";
 
+/// Tool Claude is forced to call when a JSON Schema output format is requested.
+const JSON_OUTPUT_TOOL: &str = "json_output";
+
+/// Anthropic accepts at most this many `cache_control` breakpoints per request.
+const MAX_CACHE_BREAKPOINTS: usize = 4;
+
 /// Claude/Anthropic LLM provider.
 ///
 /// Implements the `LLMProvider` trait for Anthropic's Claude API.
@@ -60,6 +67,8 @@ pub struct ClaudeProvider {
     credential: crate::auth::ResolvedCredential,
     /// HTTP client for making requests
     client: Client,
+    /// Retries of transient HTTP failures
+    http_retry: HttpRetryPolicy,
 }
 
 impl ClaudeProvider {
@@ -83,6 +92,7 @@ impl ClaudeProvider {
                 .timeout(std::time::Duration::from_secs(120))
                 .build()
                 .unwrap_or_else(|_| Client::new()),
+            http_retry: HttpRetryPolicy::default(),
         }
     }
 
@@ -96,6 +106,7 @@ impl ClaudeProvider {
                 .timeout(std::time::Duration::from_secs(120))
                 .build()
                 .unwrap_or_else(|_| Client::new()),
+            http_retry: HttpRetryPolicy::default(),
         }
     }
 
@@ -111,9 +122,16 @@ impl ClaudeProvider {
         Self {
             credential: crate::auth::ResolvedCredential::ApiKey(api_key.to_string()),
             client,
+            http_retry: HttpRetryPolicy::default(),
         }
     }
 
+    /// Retry transient HTTP failures (429, 5xx) according to `policy`.
+    pub fn with_http_retry(mut self, policy: HttpRetryPolicy) -> Self {
+        self.http_retry = policy;
+        self
+    }
+
     /// Build auth headers based on the resolved credential type.
     ///
     /// - API key: sends `x-api-key` header
@@ -157,42 +175,20 @@ impl LLMProvider for ClaudeProvider {
         options: ChatOptions,
     ) -> Result<LLMResponse> {
         let model = model.unwrap_or(DEFAULT_MODEL);
-
-        // Convert messages to Claude format, extracting system message
-        let (mut system, claude_messages) = convert_messages(messages)?;
-
-        // Append structured output instructions to system prompt if needed
-        if let Some(suffix) = options.output_format.to_claude_system_suffix() {
-            let base = system.unwrap_or_default();
-            system = Some(format!("{}{}", base, suffix));
-        }
-
-        // Build request
-        let request = ClaudeRequest {
-            model: model.to_string(),
-            max_tokens: options.max_tokens.unwrap_or(8192),
-            messages: claude_messages,
-            system,
-            tools: if tools.is_empty() {
-                None
-            } else {
-                Some(convert_tools(tools))
-            },
-            temperature: options.temperature,
-            top_p: options.top_p,
-            stop_sequences: options.stop,
-            stream: None,
-        };
+        let request = build_request(model, messages, tools, options, false)?;
+        let forced_output = request.tool_choice.is_some();
 
         // Send request
         let response = self
-            .client
-            .post(CLAUDE_API_URL)
-            .headers(self.auth_headers())
-            .header("anthropic-version", ANTHROPIC_VERSION)
-            .header("content-type", "application/json")
-            .json(&request)
-            .send()
+            .http_retry
+            .send("anthropic", || {
+                self.client
+                    .post(CLAUDE_API_URL)
+                    .headers(self.auth_headers())
+                    .header("anthropic-version", ANTHROPIC_VERSION)
+                    .header("content-type", "application/json")
+                    .json(&request)
+            })
             .await?;
 
         if !response.status().is_success() {
@@ -215,7 +211,11 @@ impl LLMProvider for ClaudeProvider {
         }
 
         let claude_response: ClaudeResponse = response.json().await?;
-        Ok(convert_response(claude_response))
+        let mut llm_response = convert_response(claude_response);
+        if forced_output {
+            take_forced_output(&mut llm_response);
+        }
+        Ok(llm_response)
     }
 
     async fn chat_stream(
@@ -229,38 +229,19 @@ impl LLMProvider for ClaudeProvider {
         use futures::StreamExt;
 
         let model = model.unwrap_or(DEFAULT_MODEL);
-        let (mut system, claude_messages) = convert_messages(messages)?;
-
-        // Append structured output instructions to system prompt if needed
-        if let Some(suffix) = options.output_format.to_claude_system_suffix() {
-            let base = system.unwrap_or_default();
-            system = Some(format!("{}{}", base, suffix));
-        }
-
-        let request = ClaudeRequest {
-            model: model.to_string(),
-            max_tokens: options.max_tokens.unwrap_or(8192),
-            messages: claude_messages,
-            system,
-            tools: if tools.is_empty() {
-                None
-            } else {
-                Some(convert_tools(tools))
-            },
-            temperature: options.temperature,
-            top_p: options.top_p,
-            stop_sequences: options.stop,
-            stream: Some(true),
-        };
+        let request = build_request(model, messages, tools, options, true)?;
+        let forced_output = request.tool_choice.is_some();
 
         let response = self
-            .client
-            .post(CLAUDE_API_URL)
-            .headers(self.auth_headers())
-            .header("anthropic-version", ANTHROPIC_VERSION)
-            .header("content-type", "application/json")
-            .json(&request)
-            .send()
+            .http_retry
+            .send("anthropic", || {
+                self.client
+                    .post(CLAUDE_API_URL)
+                    .headers(self.auth_headers())
+                    .header("anthropic-version", ANTHROPIC_VERSION)
+                    .header("content-type", "application/json")
+                    .json(&request)
+            })
             .await?;
 
         if !response.status().is_success() {
@@ -288,8 +269,10 @@ impl LLMProvider for ClaudeProvider {
             let mut current_tool_id: Option<String> = None;
             let mut current_tool_name: Option<String> = None;
             let mut current_tool_json = String::new();
+            let mut in_forced_output = false;
             let mut input_tokens: u32 = 0;
             let mut output_tokens: u32 = 0;
+            let mut served_model: Option<String> = None;
             let mut line_buffer = String::new();
 
             tokio::pin!(byte_stream);
@@ -342,11 +325,20 @@ impl LLMProvider for ClaudeProvider {
                                 if let Some(usage) = &msg.usage {
                                     input_tokens = usage.input_tokens.unwrap_or(0);
                                 }
+                                if msg.model.is_some() {
+                                    served_model = msg.model.clone();
+                                }
                             }
                         }
                         "content_block_start" => {
                             if let Some(block) = &sse.content_block {
-                                if block.block_type == "tool_use" {
+                                if block.block_type == "tool_use"
+                                    && forced_output
+                                    && block.name.as_deref() == Some(JSON_OUTPUT_TOOL)
+                                {
+                                    // Forced JSON output streams as plain text.
+                                    in_forced_output = true;
+                                } else if block.block_type == "tool_use" {
                                     current_tool_id = block.id.clone();
                                     current_tool_name = block.name.clone();
                                     current_tool_json.clear();
@@ -370,7 +362,18 @@ impl LLMProvider for ClaudeProvider {
                                     }
                                     Some("input_json_delta") => {
                                         if let Some(json_chunk) = &delta.partial_json {
-                                            current_tool_json.push_str(json_chunk);
+                                            if in_forced_output {
+                                                assembled_content.push_str(json_chunk);
+                                                if tx
+                                                    .send(StreamEvent::Delta(json_chunk.clone()))
+                                                    .await
+                                                    .is_err()
+                                                {
+                                                    return;
+                                                }
+                                            } else {
+                                                current_tool_json.push_str(json_chunk);
+                                            }
                                         }
                                     }
                                     _ => {}
@@ -378,6 +381,7 @@ impl LLMProvider for ClaudeProvider {
                             }
                         }
                         "content_block_stop" => {
+                            in_forced_output = false;
                             if let (Some(id), Some(name)) =
                                 (current_tool_id.take(), current_tool_name.take())
                             {
@@ -400,7 +404,8 @@ impl LLMProvider for ClaudeProvider {
                                     .send(StreamEvent::ToolCalls(std::mem::take(&mut tool_calls)))
                                     .await;
                             }
-                            let usage = super::Usage::new(input_tokens, output_tokens);
+                            let usage = super::Usage::new(input_tokens, output_tokens)
+                                .with_model(served_model.as_deref());
                             let _ = tx
                                 .send(StreamEvent::Done {
                                     content: assembled_content.clone(),
@@ -419,7 +424,8 @@ impl LLMProvider for ClaudeProvider {
                     .send(StreamEvent::ToolCalls(std::mem::take(&mut tool_calls)))
                     .await;
             }
-            let usage = super::Usage::new(input_tokens, output_tokens);
+            let usage =
+                super::Usage::new(input_tokens, output_tokens).with_model(served_model.as_deref());
             let _ = tx
                 .send(StreamEvent::Done {
                     content: assembled_content,
@@ -453,12 +459,16 @@ struct ClaudeRequest {
     max_tokens: u32,
     /// Conversation messages (excluding system)
     messages: Vec<ClaudeMessage>,
-    /// System prompt (separate from messages in Claude API)
+    /// System prompt (separate from messages in Claude API); a text block
+    /// when it carries a cache marker
     #[serde(skip_serializing_if = "Option::is_none")]
-    system: Option<String>,
+    system: Option<ClaudeContent>,
     /// Available tools
     #[serde(skip_serializing_if = "Option::is_none")]
     tools: Option<Vec<ClaudeTool>>,
+    /// Forces a specific tool (used for schema-constrained JSON output)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_choice: Option<serde_json::Value>,
     /// Temperature for sampling
     #[serde(skip_serializing_if = "Option::is_none")]
     temperature: Option<f32>,
@@ -488,7 +498,7 @@ struct ClaudeMessage {
 enum ClaudeContent {
     /// Simple text content
     Text(String),
-    /// Array of content blocks (for tool calls/results)
+    /// Array of content blocks (for tool calls/results and images)
     Blocks(Vec<ClaudeContentBlock>),
 }
 
@@ -498,24 +508,85 @@ enum ClaudeContent {
 enum ClaudeContentBlock {
     /// Text content
     #[serde(rename = "text")]
-    Text { text: String },
+    Text {
+        text: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cache_control: Option<CacheControl>,
+    },
+    /// Image input
+    #[serde(rename = "image")]
+    Image {
+        source: ClaudeImageSource,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cache_control: Option<CacheControl>,
+    },
     /// Tool use (assistant requesting to call a tool)
     #[serde(rename = "tool_use")]
     ToolUse {
         id: String,
         name: String,
         input: serde_json::Value,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cache_control: Option<CacheControl>,
     },
     /// Tool result (user providing result of tool execution)
     #[serde(rename = "tool_result")]
     ToolResult {
         tool_use_id: String,
-        content: String,
+        content: ClaudeContent,
         #[serde(skip_serializing_if = "Option::is_none")]
         is_error: Option<bool>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cache_control: Option<CacheControl>,
     },
 }
 
+impl ClaudeContentBlock {
+    /// Plain text block.
+    fn text(text: String) -> Self {
+        Self::Text {
+            text,
+            cache_control: None,
+        }
+    }
+
+    /// Set the block's prompt-cache marker.
+    fn set_cache_control(&mut self, cache: Option<CacheControl>) {
+        match self {
+            Self::Text { cache_control, .. }
+            | Self::Image { cache_control, .. }
+            | Self::ToolUse { cache_control, .. }
+            | Self::ToolResult { cache_control, .. } => *cache_control = cache,
+        }
+    }
+}
+
+/// Inline image data for an `image` block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct ClaudeImageSource {
+    /// Always "base64"
+    #[serde(rename = "type")]
+    kind: String,
+    media_type: String,
+    data: String,
+}
+
+/// Prompt-cache breakpoint: the request prefix up to and including the
+/// marked block is cached.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct CacheControl {
+    #[serde(rename = "type")]
+    kind: String,
+}
+
+impl CacheControl {
+    fn ephemeral() -> Self {
+        Self {
+            kind: "ephemeral".to_string(),
+        }
+    }
+}
+
 /// Claude tool definition.
 #[derive(Debug, Serialize)]
 struct ClaudeTool {
@@ -525,6 +596,9 @@ struct ClaudeTool {
     description: String,
     /// JSON Schema for tool parameters
     input_schema: serde_json::Value,
+    /// Prompt-cache marker, set on the last tool
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cache_control: Option<CacheControl>,
 }
 
 // ============================================================================
@@ -534,6 +608,9 @@ struct ClaudeTool {
 /// Claude API response body.
 #[derive(Debug, Deserialize)]
 struct ClaudeResponse {
+    /// Model that served the request
+    #[serde(default)]
+    model: Option<String>,
     /// Response content blocks
     content: Vec<ClaudeContentBlock>,
     /// Token usage
@@ -623,6 +700,8 @@ struct SseUsage {
 
 #[derive(Debug, Deserialize)]
 struct SseMessage {
+    #[serde(default)]
+    model: Option<String>,
     #[serde(default)]
     usage: Option<SseUsage>,
 }
@@ -638,21 +717,26 @@ struct SseMessage {
 ///
 /// # Arguments
 /// * `messages` - ZeptoClaw messages
+/// * `breakpoints` - Indices of messages whose last block gets a cache marker
 ///
 /// # Returns
 /// A tuple of (optional system message, Claude messages)
-fn convert_messages(messages: Vec<Message>) -> Result<(Option<String>, Vec<ClaudeMessage>)> {
+fn convert_messages(
+    messages: Vec<Message>,
+    breakpoints: &[usize],
+) -> Result<(Option<String>, Vec<ClaudeMessage>)> {
     let mut system: Option<String> = None;
     let mut claude_messages: Vec<ClaudeMessage> = Vec::new();
 
     // Group consecutive tool results together
     let mut pending_tool_results: Vec<ClaudeContentBlock> = Vec::new();
 
-    for msg in messages {
+    for (index, msg) in messages.into_iter().enumerate() {
+        let cache = breakpoints.contains(&index).then(CacheControl::ephemeral);
         match msg.role {
             Role::System => {
                 // Claude uses a separate system field
-                system = Some(msg.content);
+                system = Some(msg.flattened_content());
             }
             Role::User => {
                 // Flush any pending tool results first as a user message
@@ -664,9 +748,18 @@ fn convert_messages(messages: Vec<Message>) -> Result<(Option<String>, Vec<Claud
                 }
 
                 // Add user message
+                let content = if msg.has_images() {
+                    let mut blocks = content_blocks(msg.parts);
+                    if let Some(last) = blocks.last_mut() {
+                        last.set_cache_control(cache);
+                    }
+                    ClaudeContent::Blocks(blocks)
+                } else {
+                    text_content(msg.content, cache)
+                };
                 claude_messages.push(ClaudeMessage {
                     role: "user".to_string(),
-                    content: ClaudeContent::Text(msg.content),
+                    content,
                 });
             }
             Role::Assistant => {
@@ -678,13 +771,16 @@ fn convert_messages(messages: Vec<Message>) -> Result<(Option<String>, Vec<Claud
                     });
                 }
 
+                // Claude only accepts images from the user
+                let text = msg.flattened_content();
+
                 // Check if this message has tool calls
                 if let Some(tool_calls) = msg.tool_calls {
                     let mut blocks: Vec<ClaudeContentBlock> = Vec::new();
 
                     // Add text content if present
-                    if !msg.content.is_empty() {
-                        blocks.push(ClaudeContentBlock::Text { text: msg.content });
+                    if !text.is_empty() {
+                        blocks.push(ClaudeContentBlock::text(text));
                     }
 
                     // Add tool use blocks
@@ -697,8 +793,12 @@ fn convert_messages(messages: Vec<Message>) -> Result<(Option<String>, Vec<Claud
                             id: tc.id,
                             name: tc.name,
                             input,
+                            cache_control: None,
                         });
                     }
+                    if let Some(last) = blocks.last_mut() {
+                        last.set_cache_control(cache);
+                    }
 
                     claude_messages.push(ClaudeMessage {
                         role: "assistant".to_string(),
@@ -708,17 +808,24 @@ fn convert_messages(messages: Vec<Message>) -> Result<(Option<String>, Vec<Claud
                     // Simple text message
                     claude_messages.push(ClaudeMessage {
                         role: "assistant".to_string(),
-                        content: ClaudeContent::Text(msg.content),
+                        content: text_content(text, cache),
                     });
                 }
             }
             Role::Tool => {
                 // Tool results are sent as user messages with tool_result blocks
+                let has_images = msg.has_images();
                 if let Some(tool_call_id) = msg.tool_call_id {
+                    let content = if has_images {
+                        ClaudeContent::Blocks(content_blocks(msg.parts))
+                    } else {
+                        ClaudeContent::Text(msg.content)
+                    };
                     pending_tool_results.push(ClaudeContentBlock::ToolResult {
                         tool_use_id: tool_call_id,
-                        content: msg.content,
+                        content,
                         is_error: None,
+                        cache_control: cache,
                     });
                 }
             }
@@ -736,7 +843,125 @@ fn convert_messages(messages: Vec<Message>) -> Result<(Option<String>, Vec<Claud
     Ok((system, claude_messages))
 }
 
+/// Text content, as a single block when it carries a cache marker.
+fn text_content(text: String, cache: Option<CacheControl>) -> ClaudeContent {
+    match cache {
+        Some(cache) => ClaudeContent::Blocks(vec![ClaudeContentBlock::Text {
+            text,
+            cache_control: Some(cache),
+        }]),
+        None => ClaudeContent::Text(text),
+    }
+}
+
+/// Text and image blocks for multimodal message parts.
+fn content_blocks(parts: Vec<ContentPart>) -> Vec<ClaudeContentBlock> {
+    parts
+        .into_iter()
+        .map(|part| match part {
+            ContentPart::Text(text) => ClaudeContentBlock::text(text),
+            ContentPart::Image { media_type, base64 } => ClaudeContentBlock::Image {
+                source: ClaudeImageSource {
+                    kind: "base64".to_string(),
+                    media_type,
+                    data: base64,
+                },
+                cache_control: None,
+            },
+        })
+        .collect()
+}
+
 /// Convert ZeptoClaw tool definitions to Claude API format.
+/// Build a Claude request from the provider-neutral inputs.
+///
+/// Claude has no `response_format` parameter. A JSON Schema output format is
+/// enforced by forcing a call to a tool whose input schema is the requested
+/// schema; the tool input becomes the response text. This only applies when
+/// no other tools are offered, since forcing a tool would stop the model from
+/// calling them. Plain JSON mode, and schemas alongside real tools, fall back
+/// to best-effort instructions in the system prompt.
+///
+/// With `cache_system_prompt`, the last tool definition and the system
+/// prompt carry `cache_control` markers. Message breakpoints fill the
+/// remaining slots, keeping the latest ones.
+fn build_request(
+    model: &str,
+    messages: Vec<Message>,
+    tools: Vec<ToolDefinition>,
+    options: ChatOptions,
+    stream: bool,
+) -> Result<ClaudeRequest> {
+    let cache_prefix = options.cache_system_prompt;
+    let reserved = if cache_prefix { 2 } else { 0 };
+    let mut breakpoints = options.cache_breakpoints.clone();
+    breakpoints.sort_unstable();
+    let skip = breakpoints
+        .len()
+        .saturating_sub(MAX_CACHE_BREAKPOINTS - reserved);
+    let (mut system, claude_messages) = convert_messages(messages, &breakpoints[skip..])?;
+
+    let forced_tool = match &options.output_format {
+        OutputFormat::JsonSchema { name, schema, .. } if tools.is_empty() => Some(ClaudeTool {
+            name: JSON_OUTPUT_TOOL.to_string(),
+            description: format!("Respond with the final `{}` result as JSON.", name),
+            input_schema: schema.clone(),
+            cache_control: None,
+        }),
+        _ => None,
+    };
+
+    if forced_tool.is_none() {
+        if let Some(suffix) = options.output_format.to_claude_system_suffix() {
+            let base = system.unwrap_or_default();
+            system = Some(format!("{}{}", base, suffix));
+        }
+    }
+
+    let (mut tools, tool_choice) = match forced_tool {
+        Some(tool) => (
+            Some(vec![tool]),
+            Some(serde_json::json!({ "type": "tool", "name": JSON_OUTPUT_TOOL })),
+        ),
+        None if tools.is_empty() => (None, None),
+        None => (Some(convert_tools(tools)), None),
+    };
+
+    let system = system.map(|text| match cache_prefix {
+        true => text_content(text, Some(CacheControl::ephemeral())),
+        false => ClaudeContent::Text(text),
+    });
+    if cache_prefix {
+        if let Some(last) = tools.as_mut().and_then(|tools| tools.last_mut()) {
+            last.cache_control = Some(CacheControl::ephemeral());
+        }
+    }
+
+    Ok(ClaudeRequest {
+        model: model.to_string(),
+        max_tokens: options.max_tokens.unwrap_or(8192),
+        messages: claude_messages,
+        system,
+        tools,
+        tool_choice,
+        temperature: options.temperature,
+        top_p: options.top_p,
+        stop_sequences: options.stop,
+        stream: stream.then_some(true),
+    })
+}
+
+/// Move the forced JSON output tool call into the response text.
+fn take_forced_output(response: &mut LLMResponse) {
+    if let Some(index) = response
+        .tool_calls
+        .iter()
+        .position(|call| call.name == JSON_OUTPUT_TOOL)
+    {
+        response.content = response.tool_calls.remove(index).arguments;
+    }
+}
+
 fn convert_tools(tools: Vec<ToolDefinition>) -> Vec<ClaudeTool> {
     tools
         .into_iter()
@@ -744,6 +969,7 @@ fn convert_tools(tools: Vec<ToolDefinition>) -> Vec<ClaudeTool> {
             name: t.name,
             description: t.description,
             input_schema: t.parameters,
+            cache_control: None,
         })
         .collect()
 }
@@ -755,24 +981,27 @@ fn convert_response(response: ClaudeResponse) -> LLMResponse {
 
     for block in response.content {
         match block {
-            ClaudeContentBlock::Text { text } => {
+            ClaudeContentBlock::Text { text, .. } => {
                 if !content.is_empty() {
                     content.push('\n');
                 }
                 content.push_str(&text);
             }
-            ClaudeContentBlock::ToolUse { id, name, input } => {
+            ClaudeContentBlock::ToolUse {
+                id, name, input, ..
+            } => {
                 // Convert input Value back to JSON string
                 let arguments = serde_json::to_string(&input).unwrap_or_else(|_| "{}".to_string());
                 tool_calls.push(LLMToolCall::new(&id, &name, &arguments));
             }
-            ClaudeContentBlock::ToolResult { .. } => {
-                // Tool results shouldn't appear in responses, but handle gracefully
+            ClaudeContentBlock::ToolResult { .. } | ClaudeContentBlock::Image { .. } => {
+                // Tool results and images shouldn't appear in responses, but handle gracefully
             }
         }
     }
 
-    let usage = Usage::new(response.usage.input_tokens, response.usage.output_tokens);
+    let usage = Usage::new(response.usage.input_tokens, response.usage.output_tokens)
+        .with_model(response.model.as_deref());
 
     LLMResponse {
         content,
@@ -817,7 +1046,7 @@ mod tests {
     fn test_message_conversion_simple() {
         let messages = vec![Message::user("Hello"), Message::assistant("Hi there!")];
 
-        let (system, claude_messages) = convert_messages(messages).unwrap();
+        let (system, claude_messages) = convert_messages(messages, &[]).unwrap();
 
         assert!(system.is_none());
         assert_eq!(claude_messages.len(), 2);
@@ -833,7 +1062,7 @@ mod tests {
             Message::assistant("Hi there!"),
         ];
 
-        let (system, claude_messages) = convert_messages(messages).unwrap();
+        let (system, claude_messages) = convert_messages(messages, &[]).unwrap();
 
         assert_eq!(system, Some("You are a helpful assistant".to_string()));
         assert_eq!(claude_messages.len(), 2);
@@ -851,7 +1080,7 @@ mod tests {
             Message::assistant("I found 100 results about Rust."),
         ];
 
-        let (system, claude_messages) = convert_messages(messages).unwrap();
+        let (system, claude_messages) = convert_messages(messages, &[]).unwrap();
 
         assert!(system.is_none());
         assert_eq!(claude_messages.len(), 4);
@@ -895,7 +1124,7 @@ mod tests {
             Message::assistant("Both completed."),
         ];
 
-        let (_, claude_messages) = convert_messages(messages).unwrap();
+        let (_, claude_messages) = convert_messages(messages, &[]).unwrap();
 
         assert_eq!(claude_messages.len(), 4);
 
@@ -908,6 +1137,56 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_message_conversion_mixed_text_and_image() {
+        let messages = vec![Message::user("What is this?").with_image("image/png", "iVBORw0KGgo=")];
+        let (_, claude_messages) = convert_messages(messages, &[0]).unwrap();
+
+        let value = serde_json::to_value(&claude_messages[0]).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "role": "user",
+                "content": [
+                    {"type": "text", "text": "What is this?"},
+                    {
+                        "type": "image",
+                        "source": {"type": "base64", "media_type": "image/png", "data": "iVBORw0KGgo="},
+                        "cache_control": {"type": "ephemeral"}
+                    }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn test_message_conversion_image_tool_result_and_assistant() {
+        let tc = ToolCall::new("call_1", "screenshot", "{}");
+        let messages = vec![
+            Message::user("Take a screenshot"),
+            Message::assistant_with_tools("", vec![tc]),
+            Message::tool_result("call_1", "Captured").with_image("image/jpeg", "AAAA"),
+            Message::assistant("Done").with_image("image/png", "AAAA"),
+        ];
+        let (_, claude_messages) = convert_messages(messages, &[]).unwrap();
+
+        let tool_result = serde_json::to_value(&claude_messages[2].content).unwrap();
+        assert_eq!(tool_result[0]["type"], "tool_result");
+        assert_eq!(tool_result[0]["content"][0]["text"], "Captured");
+        assert_eq!(tool_result[0]["content"][1]["type"], "image");
+        assert_eq!(
+            tool_result[0]["content"][1]["source"]["media_type"],
+            "image/jpeg"
+        );
+
+        // Assistant images are flattened to a note
+        let assistant = serde_json::to_value(&claude_messages[3].content).unwrap();
+        assert_eq!(
+            assistant,
+            "Done\n[image: image/png, 3 bytes not shown to this model]"
+        );
+    }
+
     #[test]
     fn test_convert_tools() {
         let tools = vec![
@@ -945,9 +1224,8 @@ mod tests {
     #[test]
     fn test_convert_response_text_only() {
         let response = ClaudeResponse {
-            content: vec![ClaudeContentBlock::Text {
-                text: "Hello, world!".to_string(),
-            }],
+            model: None,
+            content: vec![ClaudeContentBlock::text("Hello, world!".to_string())],
             usage: ClaudeUsage {
                 input_tokens: 10,
                 output_tokens: 5,
@@ -970,14 +1248,14 @@ mod tests {
     #[test]
     fn test_convert_response_with_tool_calls() {
         let response = ClaudeResponse {
+            model: None,
             content: vec![
-                ClaudeContentBlock::Text {
-                    text: "Let me search for that.".to_string(),
-                },
+                ClaudeContentBlock::text("Let me search for that.".to_string()),
                 ClaudeContentBlock::ToolUse {
                     id: "toolu_01".to_string(),
                     name: "web_search".to_string(),
                     input: serde_json::json!({"query": "rust programming"}),
+                    cache_control: None,
                 },
             ],
             usage: ClaudeUsage {
@@ -1002,13 +1280,10 @@ mod tests {
     #[test]
     fn test_convert_response_multiple_text_blocks() {
         let response = ClaudeResponse {
+            model: None,
             content: vec![
-                ClaudeContentBlock::Text {
-                    text: "First part.".to_string(),
-                },
-                ClaudeContentBlock::Text {
-                    text: "Second part.".to_string(),
-                },
+                ClaudeContentBlock::text("First part.".to_string()),
+                ClaudeContentBlock::text("Second part.".to_string()),
             ],
             usage: ClaudeUsage {
                 input_tokens: 10,
@@ -1031,8 +1306,9 @@ mod tests {
                 role: "user".to_string(),
                 content: ClaudeContent::Text("Hello".to_string()),
             }],
-            system: Some("You are helpful.".to_string()),
+            system: Some(ClaudeContent::Text("You are helpful.".to_string())),
             tools: None,
+            tool_choice: None,
             temperature: Some(0.7),
             top_p: None,
             stop_sequences: None,
@@ -1058,6 +1334,7 @@ mod tests {
             messages: vec![],
             system: None,
             tools: None,
+            tool_choice: None,
             temperature: None,
             top_p: None,
             stop_sequences: None,
@@ -1077,9 +1354,7 @@ mod tests {
     #[test]
     fn test_content_block_serialization() {
         // Text block
-        let text_block = ClaudeContentBlock::Text {
-            text: "Hello".to_string(),
-        };
+        let text_block = ClaudeContentBlock::text("Hello".to_string());
         let json = serde_json::to_string(&text_block).unwrap();
         assert!(json.contains(r#""type":"text""#));
         assert!(json.contains(r#""text":"Hello""#));
@@ -1089,6 +1364,7 @@ mod tests {
             id: "call_1".to_string(),
             name: "search".to_string(),
             input: serde_json::json!({"q": "test"}),
+            cache_control: None,
         };
         let json = serde_json::to_string(&tool_use).unwrap();
         assert!(json.contains(r#""type":"tool_use""#));
@@ -1098,8 +1374,9 @@ mod tests {
         // Tool result block
         let tool_result = ClaudeContentBlock::ToolResult {
             tool_use_id: "call_1".to_string(),
-            content: "Result".to_string(),
+            content: ClaudeContent::Text("Result".to_string()),
             is_error: None,
+            cache_control: None,
         };
         let json = serde_json::to_string(&tool_result).unwrap();
         assert!(json.contains(r#""type":"tool_result""#));
@@ -1109,7 +1386,7 @@ mod tests {
     #[test]
     fn test_empty_messages() {
         let messages: Vec<Message> = vec![];
-        let (system, claude_messages) = convert_messages(messages).unwrap();
+        let (system, claude_messages) = convert_messages(messages, &[]).unwrap();
 
         assert!(system.is_none());
         assert!(claude_messages.is_empty());
@@ -1118,7 +1395,7 @@ mod tests {
     #[test]
     fn test_only_system_message() {
         let messages = vec![Message::system("You are helpful.")];
-        let (system, claude_messages) = convert_messages(messages).unwrap();
+        let (system, claude_messages) = convert_messages(messages, &[]).unwrap();
 
         assert_eq!(system, Some("You are helpful.".to_string()));
         assert!(claude_messages.is_empty());
@@ -1142,6 +1419,7 @@ mod tests {
             messages: vec![],
             system: None,
             tools: None,
+            tool_choice: None,
             temperature: None,
             top_p: None,
             stop_sequences: None,
@@ -1159,6 +1437,7 @@ mod tests {
             messages: vec![],
             system: None,
             tools: None,
+            tool_choice: None,
             temperature: None,
             top_p: None,
             stop_sequences: None,
@@ -1204,4 +1483,200 @@ mod tests {
             "web_search"
         );
     }
+
+    fn system_text(request: &ClaudeRequest) -> Option<&str> {
+        match request.system.as_ref()? {
+            ClaudeContent::Text(text) => Some(text),
+            ClaudeContent::Blocks(_) => None,
+        }
+    }
+
+    fn person_schema() -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": { "name": { "type": "string" } },
+            "required": ["name"]
+        })
+    }
+
+    #[test]
+    fn test_build_request_forces_tool_for_json_schema() {
+        let options = ChatOptions::new()
+            .with_output_format(OutputFormat::json_schema("person", person_schema()));
+        let request = build_request(
+            "claude-sonnet-4-5-20250929",
+            vec![Message::system("Extract."), Message::user("Alice")],
+            vec![],
+            options,
+            false,
+        )
+        .unwrap();
+
+        let tools = request.tools.as_ref().unwrap();
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0].name, JSON_OUTPUT_TOOL);
+        assert_eq!(tools[0].input_schema, person_schema());
+        assert_eq!(
+            request.tool_choice,
+            Some(serde_json::json!({ "type": "tool", "name": JSON_OUTPUT_TOOL }))
+        );
+        // The schema is enforced by the tool, so no prompt instructions are added.
+        assert_eq!(system_text(&request), Some("Extract."));
+    }
+
+    #[test]
+    fn test_build_request_json_schema_with_tools_uses_prompt() {
+        let options = ChatOptions::new()
+            .with_output_format(OutputFormat::json_schema("person", person_schema()));
+        let tools = vec![ToolDefinition::new(
+            "web_search",
+            "Search",
+            serde_json::json!({"type": "object"}),
+        )];
+        let request = build_request(
+            "claude-sonnet-4-5-20250929",
+            vec![Message::user("Alice")],
+            tools,
+            options,
+            true,
+        )
+        .unwrap();
+
+        assert!(request.tool_choice.is_none());
+        assert_eq!(request.tools.as_ref().unwrap()[0].name, "web_search");
+        assert!(system_text(&request)
+            .unwrap()
+            .contains("valid JSON matching this schema"));
+        assert_eq!(request.stream, Some(true));
+    }
+
+    #[test]
+    fn test_build_request_json_mode_is_prompt_only() {
+        let options = ChatOptions::new().with_output_format(OutputFormat::json());
+        let request = build_request(
+            "claude-sonnet-4-5-20250929",
+            vec![Message::user("Hi")],
+            vec![],
+            options,
+            false,
+        )
+        .unwrap();
+
+        assert!(request.tools.is_none());
+        assert!(request.tool_choice.is_none());
+        assert!(system_text(&request).unwrap().contains("valid JSON only"));
+        let json = serde_json::to_string(&request).unwrap();
+        assert!(!json.contains("tool_choice"));
+    }
+
+    #[test]
+    fn test_take_forced_output_moves_json_into_content() {
+        let response = ClaudeResponse {
+            model: None,
+            content: vec![ClaudeContentBlock::ToolUse {
+                id: "toolu_1".to_string(),
+                name: JSON_OUTPUT_TOOL.to_string(),
+                input: serde_json::json!({"name": "Alice"}),
+                cache_control: None,
+            }],
+            stop_reason: Some("tool_use".to_string()),
+            usage: ClaudeUsage {
+                input_tokens: 10,
+                output_tokens: 5,
+            },
+        };
+        let mut llm_response = convert_response(response);
+        take_forced_output(&mut llm_response);
+
+        assert!(!llm_response.has_tool_calls());
+        assert_eq!(llm_response.content, r#"{"name":"Alice"}"#);
+    }
+
+    fn cache_request(options: ChatOptions) -> serde_json::Value {
+        let tools = vec![
+            ToolDefinition::new("read_file", "Read", serde_json::json!({"type": "object"})),
+            ToolDefinition::new("shell", "Run", serde_json::json!({"type": "object"})),
+        ];
+        let messages = vec![
+            Message::system("You are helpful."),
+            Message::user("List files"),
+            Message::assistant_with_tools(
+                "",
+                vec![ToolCall::new("call_1", "shell", r#"{"command":"ls"}"#)],
+            ),
+            Message::tool_result("call_1", "a.txt"),
+            Message::user("Thanks"),
+        ];
+        let request = build_request(
+            "claude-sonnet-4-5-20250929",
+            messages,
+            tools,
+            options,
+            false,
+        )
+        .unwrap();
+        serde_json::to_value(&request).unwrap()
+    }
+
+    #[test]
+    fn test_build_request_caches_system_and_tools() {
+        let json = cache_request(ChatOptions::new().with_cache_system_prompt(true));
+        let ephemeral = serde_json::json!({"type": "ephemeral"});
+
+        assert_eq!(
+            json["system"],
+            serde_json::json!([{
+                "type": "text",
+                "text": "You are helpful.",
+                "cache_control": {"type": "ephemeral"}
+            }])
+        );
+        assert!(json["tools"][0].get("cache_control").is_none());
+        assert_eq!(json["tools"][1]["cache_control"], ephemeral);
+        // Messages are untouched without explicit breakpoints.
+        assert!(!json["messages"].to_string().contains("cache_control"));
+    }
+
+    #[test]
+    fn test_build_request_without_caching_has_no_markers() {
+        let json = cache_request(ChatOptions::new());
+        assert_eq!(json["system"], "You are helpful.");
+        assert!(!json.to_string().contains("cache_control"));
+    }
+
+    #[test]
+    fn test_build_request_message_breakpoints() {
+        // Index 2 is the assistant tool call, 3 the tool result, 4 the user text.
+        let options = ChatOptions::new()
+            .with_cache_breakpoint(2)
+            .with_cache_breakpoint(3)
+            .with_cache_breakpoint(4);
+        let json = cache_request(options);
+        let messages = json["messages"].as_array().unwrap();
+        let ephemeral = serde_json::json!({"type": "ephemeral"});
+
+        assert_eq!(messages[0]["content"], "List files");
+        assert_eq!(messages[1]["content"][0]["type"], "tool_use");
+        assert_eq!(messages[1]["content"][0]["cache_control"], ephemeral);
+        assert_eq!(messages[2]["content"][0]["type"], "tool_result");
+        assert_eq!(messages[2]["content"][0]["cache_control"], ephemeral);
+        assert_eq!(messages[3]["content"][0]["text"], "Thanks");
+        assert_eq!(messages[3]["content"][0]["cache_control"], ephemeral);
+    }
+
+    #[test]
+    fn test_build_request_caps_breakpoints() {
+        let mut options = ChatOptions::new().with_cache_system_prompt(true);
+        for index in 1..5 {
+            options = options.with_cache_breakpoint(index);
+        }
+        let json = cache_request(options);
+        let markers = json.to_string().matches("cache_control").count();
+        assert_eq!(markers, MAX_CACHE_BREAKPOINTS);
+        // The earliest message breakpoints are dropped.
+        assert_eq!(json["messages"][0]["content"], "List files");
+        assert!(json["messages"][3]["content"][0]
+            .get("cache_control")
+            .is_some());
+    }
 }
@@ -196,6 +196,12 @@ impl LLMProvider for FallbackProvider {
         self.primary.default_model()
     }
 
+    fn health_snapshot(&self) -> Vec<super::ProviderHealthStatus> {
+        let mut health = self.primary.health_snapshot();
+        health.extend(self.fallback.health_snapshot());
+        health
+    }
+
     async fn chat(
         &self,
         messages: Vec<Message>,
@@ -227,12 +233,7 @@ impl LLMProvider for FallbackProvider {
             }
             Err(primary_err) => {
                 // Don't fallback for auth/billing/invalid request errors
-                let should_fallback = match &primary_err {
-                    crate::error::ZeptoError::ProviderTyped(pe) => pe.should_fallback(),
-                    _ => true, // Legacy errors always fallback
-                };
-
-                if should_fallback {
+                if super::should_fallback(&primary_err) {
                     self.circuit_breaker.record_failure();
                     warn!(
                         primary = self.primary.name(),
@@ -288,12 +289,7 @@ impl LLMProvider for FallbackProvider {
             }
             Err(primary_err) => {
                 // Don't fallback for auth/billing/invalid request errors
-                let should_fallback = match &primary_err {
-                    crate::error::ZeptoError::ProviderTyped(pe) => pe.should_fallback(),
-                    _ => true, // Legacy errors always fallback
-                };
-
-                if should_fallback {
+                if super::should_fallback(&primary_err) {
                     self.circuit_breaker.record_failure();
                     warn!(
                         primary = self.primary.name(),
@@ -981,4 +977,160 @@ mod tests {
             "primary should be probed once in HalfOpen state"
         );
     }
+
+    // ====================================================================
+    // Streaming tests (parity with chat)
+    // ====================================================================
+
+    /// Drain a stream receiver and return the content of its `Done` event.
+    async fn stream_done_content(
+        mut rx: tokio::sync::mpsc::Receiver<StreamEvent>,
+    ) -> Option<String> {
+        while let Some(event) = rx.recv().await {
+            if let StreamEvent::Done { content, .. } = event {
+                return Some(content);
+            }
+        }
+        None
+    }
+
+    #[tokio::test]
+    async fn test_fallback_stream_uses_primary_when_available() {
+        let provider = FallbackProvider::new(
+            Box::new(SuccessProvider { name: "primary" }),
+            Box::new(SuccessProvider { name: "fallback" }),
+        );
+
+        let rx = provider
+            .chat_stream(vec![], vec![], None, ChatOptions::default())
+            .await
+            .expect("primary stream should succeed");
+
+        assert_eq!(
+            stream_done_content(rx).await.as_deref(),
+            Some("success from primary")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fallback_stream_uses_secondary_on_primary_failure() {
+        let provider = FallbackProvider::new(
+            Box::new(FailProvider { name: "primary" }),
+            Box::new(SuccessProvider { name: "fallback" }),
+        );
+
+        let rx = provider
+            .chat_stream(vec![], vec![], None, ChatOptions::default())
+            .await
+            .expect("fallback stream should succeed");
+
+        assert_eq!(
+            stream_done_content(rx).await.as_deref(),
+            Some("success from fallback")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fallback_stream_auth_error_does_not_trigger_fallback() {
+        use crate::error::ProviderError;
+
+        let provider = FallbackProvider::new(
+            Box::new(TypedFailProvider {
+                name: "primary",
+                error: || ZeptoError::ProviderTyped(ProviderError::Auth("invalid key".into())),
+            }),
+            Box::new(SuccessProvider { name: "fallback" }),
+        );
+
+        let result = provider
+            .chat_stream(vec![], vec![], None, ChatOptions::default())
+            .await;
+
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Authentication error"));
+    }
+
+    #[tokio::test]
+    async fn test_fallback_stream_rate_limit_triggers_fallback() {
+        use crate::error::ProviderError;
+
+        let provider = FallbackProvider::new(
+            Box::new(TypedFailProvider {
+                name: "primary",
+                error: || {
+                    ZeptoError::ProviderTyped(ProviderError::RateLimit("quota exceeded".into()))
+                },
+            }),
+            Box::new(SuccessProvider { name: "fallback" }),
+        );
+
+        let rx = provider
+            .chat_stream(vec![], vec![], None, ChatOptions::default())
+            .await
+            .expect("rate limit should fall back");
+
+        assert_eq!(
+            stream_done_content(rx).await.as_deref(),
+            Some("success from fallback")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fallback_stream_returns_error_when_both_fail() {
+        let provider = FallbackProvider::new(
+            Box::new(FailProvider { name: "primary" }),
+            Box::new(FailProvider { name: "fallback" }),
+        );
+
+        let result = provider
+            .chat_stream(vec![], vec![], None, ChatOptions::default())
+            .await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_fallback_stream_skips_primary_when_circuit_open() {
+        let primary_calls = Arc::new(AtomicU32::new(0));
+        let fallback_calls = Arc::new(AtomicU32::new(0));
+
+        let provider = FallbackProvider::new(
+            Box::new(CountingFailProvider {
+                name: "primary",
+                call_count: Arc::clone(&primary_calls),
+            }),
+            Box::new(CountingProvider {
+                name: "fallback",
+                call_count: Arc::clone(&fallback_calls),
+            }),
+        );
+
+        // Streaming failures count towards the same circuit breaker.
+        for _ in 0..3 {
+            let _ = provider
+                .chat_stream(vec![], vec![], None, ChatOptions::default())
+                .await;
+        }
+        assert_eq!(primary_calls.load(Ordering::SeqCst), 3);
+        assert_eq!(provider.circuit_breaker.state(), CircuitState::Open);
+
+        let rx = provider
+            .chat_stream(vec![], vec![], None, ChatOptions::default())
+            .await
+            .expect("fallback stream should succeed");
+
+        assert_eq!(
+            stream_done_content(rx).await.as_deref(),
+            Some("success from fallback")
+        );
+        assert_eq!(
+            primary_calls.load(Ordering::SeqCst),
+            3,
+            "primary should be skipped when circuit is open"
+        );
+        assert_eq!(fallback_calls.load(Ordering::SeqCst), 4);
+    }
 }
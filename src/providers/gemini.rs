@@ -0,0 +1,1193 @@
+//! Gemini Provider Implementation
+//!
+//! This module implements the `LLMProvider` trait for Google's Generative
+//! Language API (`generateContent` / `streamGenerateContent`), handling
+//! message conversion, function calling, and response parsing.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use zeptoclaw::providers::{gemini::GeminiProvider, ChatOptions, LLMProvider};
+//! use zeptoclaw::session::Message;
+//!
+//! async fn example() {
+//!     let provider = GeminiProvider::new("your-api-key");
+//!
+//!     let messages = vec![
+//!         Message::system("You are a helpful assistant."),
+//!         Message::user("Hello!"),
+//!     ];
+//!
+//!     let response = provider
+//!         .chat(messages, vec![], None, ChatOptions::default())
+//!         .await
+//!         .unwrap();
+//!
+//!     println!("Gemini: {}", response.content);
+//! }
+//! ```
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info};
+
+use crate::error::{ProviderError, Result, ZeptoError};
+use crate::session::{Message, Role};
+
+use super::{
+    parse_provider_error, ChatOptions, HttpRetryPolicy, LLMProvider, LLMResponse, LLMToolCall,
+    OutputFormat, ToolDefinition, Usage,
+};
+
+/// The Generative Language API endpoint URL.
+const GEMINI_API_URL: &str = "https://generativelanguage.googleapis.com/v1beta";
+
+/// The default Gemini model to use.
+/// Can be overridden at compile time with `ZEPTOCLAW_GEMINI_DEFAULT_MODEL` env var.
+const DEFAULT_MODEL: &str = match option_env!("ZEPTOCLAW_GEMINI_DEFAULT_MODEL") {
+    Some(v) => v,
+    None => "gemini-2.5-flash",
+};
+
+/// JSON Schema keywords the Gemini API rejects in function declarations.
+const UNSUPPORTED_SCHEMA_KEYS: &[&str] = &["$schema", "additionalProperties"];
+
+// ============================================================================
+// Gemini API Request Types
+// ============================================================================
+
+/// Gemini `generateContent` request body.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiRequest {
+    /// Conversation turns
+    contents: Vec<GeminiContent>,
+    /// System prompt, sent separately from the turns
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system_instruction: Option<GeminiContent>,
+    /// Available function declarations
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tools: Option<Vec<GeminiTool>>,
+    /// Sampling and output options
+    #[serde(skip_serializing_if = "Option::is_none")]
+    generation_config: Option<GeminiGenerationConfig>,
+}
+
+/// A single turn: a role and its parts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct GeminiContent {
+    /// "user" or "model" (absent for the system instruction)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    role: Option<String>,
+    /// Text, function call, or function response parts
+    #[serde(default)]
+    parts: Vec<GeminiPart>,
+}
+
+/// One part of a turn. Exactly one of the payload fields is set.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiPart {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    text: Option<String>,
+    /// Set on reasoning summaries, which are not part of the answer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    thought: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    function_call: Option<GeminiFunctionCall>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    function_response: Option<GeminiFunctionResponse>,
+}
+
+/// A function call requested by the model.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct GeminiFunctionCall {
+    name: String,
+    #[serde(default)]
+    args: serde_json::Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+}
+
+/// The result of a function call, sent back to the model.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct GeminiFunctionResponse {
+    name: String,
+    response: serde_json::Value,
+}
+
+/// Tool wrapper holding function declarations.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiTool {
+    function_declarations: Vec<GeminiFunctionDeclaration>,
+}
+
+/// A function the model may call.
+#[derive(Debug, Serialize)]
+struct GeminiFunctionDeclaration {
+    name: String,
+    description: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parameters: Option<serde_json::Value>,
+}
+
+/// Generation options.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiGenerationConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_output_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stop_sequences: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_mime_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_schema: Option<serde_json::Value>,
+}
+
+impl GeminiGenerationConfig {
+    fn is_empty(&self) -> bool {
+        self.max_output_tokens.is_none()
+            && self.temperature.is_none()
+            && self.top_p.is_none()
+            && self.stop_sequences.is_none()
+            && self.response_mime_type.is_none()
+            && self.response_schema.is_none()
+    }
+}
+
+// ============================================================================
+// Gemini API Response Types
+// ============================================================================
+
+/// Gemini `generateContent` response (also each streamed chunk).
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiResponse {
+    #[serde(default)]
+    candidates: Vec<GeminiCandidate>,
+    #[serde(default)]
+    usage_metadata: Option<GeminiUsageMetadata>,
+    #[serde(default)]
+    prompt_feedback: Option<GeminiPromptFeedback>,
+    /// Model that served the request
+    #[serde(default)]
+    model_version: Option<String>,
+}
+
+/// A generated candidate.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiCandidate {
+    #[serde(default)]
+    content: Option<GeminiContent>,
+}
+
+/// Token accounting.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiUsageMetadata {
+    #[serde(default)]
+    prompt_token_count: u32,
+    #[serde(default)]
+    candidates_token_count: u32,
+}
+
+/// Present when the prompt itself was rejected.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiPromptFeedback {
+    #[serde(default)]
+    block_reason: Option<String>,
+}
+
+/// Gemini API error envelope.
+#[derive(Debug, Deserialize)]
+struct GeminiErrorResponse {
+    error: GeminiErrorBody,
+}
+
+/// Gemini API error details.
+#[derive(Debug, Deserialize)]
+struct GeminiErrorBody {
+    #[serde(default)]
+    message: String,
+    #[serde(default)]
+    status: String,
+}
+
+// ============================================================================
+// Gemini Provider
+// ============================================================================
+
+/// Gemini LLM provider.
+///
+/// Implements the `LLMProvider` trait for Google's Generative Language API.
+/// Handles message format conversion, function calling, and response parsing.
+pub struct GeminiProvider {
+    /// API key for authentication
+    api_key: String,
+    /// API base URL
+    api_base: String,
+    /// HTTP client for making requests
+    client: Client,
+    /// Retries of transient HTTP failures
+    http_retry: HttpRetryPolicy,
+}
+
+impl GeminiProvider {
+    /// Create a new Gemini provider with the given API key.
+    ///
+    /// Uses the default Generative Language API endpoint.
+    ///
+    /// # Arguments
+    /// * `api_key` - Google AI Studio API key
+    ///
+    /// # Example
+    /// ```
+    /// use zeptoclaw::providers::gemini::GeminiProvider;
+    /// use zeptoclaw::providers::LLMProvider;
+    ///
+    /// let provider = GeminiProvider::new("AIza-xxx");
+    /// assert_eq!(provider.name(), "gemini");
+    /// ```
+    pub fn new(api_key: &str) -> Self {
+        Self::with_base_url(api_key, GEMINI_API_URL)
+    }
+
+    /// Create a new Gemini provider with a custom base URL.
+    ///
+    /// # Arguments
+    /// * `api_key` - API key
+    /// * `api_base` - Base URL up to the API version (trailing slash will be removed)
+    ///
+    /// # Example
+    /// ```
+    /// use zeptoclaw::providers::gemini::GeminiProvider;
+    ///
+    /// let provider = GeminiProvider::with_base_url(
+    ///     "AIza-xxx",
+    ///     "https://generativelanguage.googleapis.com/v1beta/",
+    /// );
+    /// ```
+    pub fn with_base_url(api_key: &str, api_base: &str) -> Self {
+        Self {
+            api_key: api_key.to_string(),
+            api_base: api_base.trim_end_matches('/').to_string(),
+            client: Client::builder()
+                .timeout(std::time::Duration::from_secs(120))
+                .build()
+                .unwrap_or_else(|_| Client::new()),
+            http_retry: HttpRetryPolicy::default(),
+        }
+    }
+
+    /// Retry transient HTTP failures (429, 5xx) according to `policy`.
+    pub fn with_http_retry(mut self, policy: HttpRetryPolicy) -> Self {
+        self.http_retry = policy;
+        self
+    }
+
+    /// Build the URL for a model method such as `generateContent`.
+    fn endpoint(&self, model: &str, method: &str) -> String {
+        let model = model.strip_prefix("models/").unwrap_or(model);
+        format!("{}/models/{}:{}", self.api_base, model, method)
+    }
+}
+
+// ============================================================================
+// Conversion Functions
+// ============================================================================
+
+/// Build a Gemini request from the provider-neutral inputs.
+///
+/// System messages become `systemInstruction`, assistant turns use the
+/// `model` role, and tool results are sent as `functionResponse` parts
+/// named after the call they answer.
+fn build_request(
+    messages: &[Message],
+    tools: &[ToolDefinition],
+    options: &ChatOptions,
+) -> GeminiRequest {
+    let mut system_parts: Vec<String> = Vec::new();
+    let mut call_names: HashMap<&str, &str> = HashMap::new();
+    let mut contents: Vec<GeminiContent> = Vec::new();
+
+    for msg in messages {
+        // Images are not sent to Gemini; each becomes a placeholder note.
+        let text = msg.flattened_content();
+        match msg.role {
+            Role::System => {
+                if !text.is_empty() {
+                    system_parts.push(text);
+                }
+            }
+            Role::User => push_part(&mut contents, "user", text_part(&text)),
+            Role::Assistant => {
+                if !text.is_empty() {
+                    push_part(&mut contents, "model", text_part(&text));
+                }
+                for call in msg.tool_calls.iter().flatten() {
+                    call_names.insert(&call.id, &call.name);
+                    let args = serde_json::from_str(&call.arguments)
+                        .unwrap_or_else(|_| serde_json::json!({}));
+                    let part = GeminiPart {
+                        function_call: Some(GeminiFunctionCall {
+                            name: call.name.clone(),
+                            args,
+                            id: None,
+                        }),
+                        ..Default::default()
+                    };
+                    push_part(&mut contents, "model", part);
+                }
+            }
+            Role::Tool => {
+                let call_id = msg.tool_call_id.as_deref().unwrap_or_default();
+                let name = call_names.get(call_id).copied().unwrap_or(call_id);
+                let part = GeminiPart {
+                    function_response: Some(GeminiFunctionResponse {
+                        name: name.to_string(),
+                        response: serde_json::json!({ "content": text }),
+                    }),
+                    ..Default::default()
+                };
+                push_part(&mut contents, "user", part);
+            }
+        }
+    }
+
+    let system_instruction = if system_parts.is_empty() {
+        None
+    } else {
+        Some(GeminiContent {
+            role: None,
+            parts: vec![text_part(&system_parts.join("\n\n"))],
+        })
+    };
+
+    let tools = if tools.is_empty() {
+        None
+    } else {
+        Some(vec![GeminiTool {
+            function_declarations: tools
+                .iter()
+                .map(|tool| GeminiFunctionDeclaration {
+                    name: tool.name.clone(),
+                    description: tool.description.clone(),
+                    parameters: function_parameters(&tool.parameters),
+                })
+                .collect(),
+        }])
+    };
+
+    let mut generation_config = GeminiGenerationConfig {
+        max_output_tokens: options.max_tokens,
+        temperature: options.temperature,
+        top_p: options.top_p,
+        stop_sequences: options.stop.clone(),
+        ..Default::default()
+    };
+    match &options.output_format {
+        OutputFormat::Text => {}
+        OutputFormat::Json => {
+            generation_config.response_mime_type = Some("application/json".to_string());
+        }
+        OutputFormat::JsonSchema { schema, .. } => {
+            generation_config.response_mime_type = Some("application/json".to_string());
+            generation_config.response_schema = Some(sanitize_schema(schema));
+        }
+    }
+
+    GeminiRequest {
+        contents,
+        system_instruction,
+        tools,
+        generation_config: (!generation_config.is_empty()).then_some(generation_config),
+    }
+}
+
+fn text_part(text: &str) -> GeminiPart {
+    GeminiPart {
+        text: Some(text.to_string()),
+        ..Default::default()
+    }
+}
+
+/// Append a part, merging into the previous turn when the role repeats.
+///
+/// Gemini expects user and model turns to alternate, so consecutive tool
+/// results (or text followed by function calls) share one turn.
+fn push_part(contents: &mut Vec<GeminiContent>, role: &str, part: GeminiPart) {
+    if let Some(last) = contents.last_mut() {
+        if last.role.as_deref() == Some(role) {
+            last.parts.push(part);
+            return;
+        }
+    }
+    contents.push(GeminiContent {
+        role: Some(role.to_string()),
+        parts: vec![part],
+    });
+}
+
+/// Convert a tool's JSON Schema into a Gemini function `parameters` value.
+///
+/// Returns `None` for parameterless tools, since Gemini rejects object
+/// schemas with no properties.
+fn function_parameters(schema: &serde_json::Value) -> Option<serde_json::Value> {
+    let has_properties = schema
+        .get("properties")
+        .and_then(|p| p.as_object())
+        .is_some_and(|p| !p.is_empty());
+    has_properties.then(|| sanitize_schema(schema))
+}
+
+/// Strip JSON Schema keywords that the Gemini API does not accept.
+fn sanitize_schema(schema: &serde_json::Value) -> serde_json::Value {
+    match schema {
+        serde_json::Value::Object(map) => serde_json::Value::Object(
+            map.iter()
+                .filter(|(key, _)| !UNSUPPORTED_SCHEMA_KEYS.contains(&key.as_str()))
+                .map(|(key, value)| (key.clone(), sanitize_schema(value)))
+                .collect(),
+        ),
+        serde_json::Value::Array(items) => {
+            serde_json::Value::Array(items.iter().map(sanitize_schema).collect())
+        }
+        other => other.clone(),
+    }
+}
+
+/// Convert a Gemini function call into a tool call.
+///
+/// Gemini does not always assign call ids, so one is generated when missing.
+fn convert_function_call(call: GeminiFunctionCall) -> LLMToolCall {
+    let id = call
+        .id
+        .filter(|id| !id.is_empty())
+        .unwrap_or_else(|| format!("call_{}", uuid::Uuid::new_v4().simple()));
+    let arguments = if call.args.is_null() {
+        "{}".to_string()
+    } else {
+        call.args.to_string()
+    };
+    LLMToolCall::new(&id, &call.name, &arguments)
+}
+
+fn convert_usage(usage: GeminiUsageMetadata, model_version: Option<&str>) -> Usage {
+    Usage::new(usage.prompt_token_count, usage.candidates_token_count).with_model(model_version)
+}
+
+/// Convert a Gemini response into an `LLMResponse`.
+///
+/// Only the first candidate is used. A prompt rejected by safety filters
+/// (no candidates plus a block reason) is reported as an invalid request.
+fn convert_response(response: GeminiResponse) -> Result<LLMResponse> {
+    if response.candidates.is_empty() {
+        if let Some(reason) = response
+            .prompt_feedback
+            .and_then(|feedback| feedback.block_reason)
+        {
+            return Err(ZeptoError::from(ProviderError::InvalidRequest(format!(
+                "Gemini blocked the prompt: {}",
+                reason
+            ))));
+        }
+    }
+
+    let mut content = String::new();
+    let mut tool_calls = Vec::new();
+    let parts = response
+        .candidates
+        .into_iter()
+        .next()
+        .and_then(|candidate| candidate.content)
+        .map(|content| content.parts)
+        .unwrap_or_default();
+
+    for part in parts {
+        if let Some(call) = part.function_call {
+            tool_calls.push(convert_function_call(call));
+        } else if let Some(text) = part.text {
+            if part.thought != Some(true) {
+                content.push_str(&text);
+            }
+        }
+    }
+
+    let mut llm_response = LLMResponse::with_tools(&content, tool_calls);
+    if let Some(usage) = response.usage_metadata {
+        llm_response =
+            llm_response.with_usage(convert_usage(usage, response.model_version.as_deref()));
+    }
+    Ok(llm_response)
+}
+
+/// Fold one streamed chunk into the running state.
+///
+/// Returns the text deltas to forward. Usage metadata is cumulative in
+/// Gemini streams, so the latest value replaces the previous one.
+fn apply_stream_chunk(
+    chunk: GeminiResponse,
+    assembled_content: &mut String,
+    tool_calls: &mut Vec<LLMToolCall>,
+    usage: &mut Option<Usage>,
+) -> Vec<String> {
+    let mut deltas = Vec::new();
+
+    if let Some(meta) = chunk.usage_metadata {
+        *usage = Some(convert_usage(meta, chunk.model_version.as_deref()));
+    }
+
+    let parts = chunk
+        .candidates
+        .into_iter()
+        .next()
+        .and_then(|candidate| candidate.content)
+        .map(|content| content.parts)
+        .unwrap_or_default();
+
+    for part in parts {
+        if let Some(call) = part.function_call {
+            tool_calls.push(convert_function_call(call));
+        } else if let Some(text) = part.text {
+            if part.thought != Some(true) && !text.is_empty() {
+                assembled_content.push_str(&text);
+                deltas.push(text);
+            }
+        }
+    }
+
+    deltas
+}
+
+/// Map a failed Gemini HTTP response onto a typed provider error.
+///
+/// Gemini reports a bad API key as 400 `INVALID_ARGUMENT` and a missing
+/// billing account as 400 `FAILED_PRECONDITION`, so the error status and
+/// message are checked before falling back to [`parse_provider_error`].
+fn parse_gemini_error(status: u16, error_text: &str) -> ProviderError {
+    let (api_status, message) = match serde_json::from_str::<GeminiErrorResponse>(error_text) {
+        Ok(parsed) => (parsed.error.status, parsed.error.message),
+        Err(_) => (String::new(), error_text.to_string()),
+    };
+    let body = if api_status.is_empty() {
+        format!("Gemini API error: {}", message)
+    } else {
+        format!("Gemini API error: {} - {}", api_status, message)
+    };
+    let lower = message.to_lowercase();
+
+    match (status, api_status.as_str()) {
+        (401 | 403, _) | (_, "UNAUTHENTICATED" | "PERMISSION_DENIED") => ProviderError::Auth(body),
+        (400, _) if lower.contains("api key") => ProviderError::Auth(body),
+        (400, "FAILED_PRECONDITION") if lower.contains("billing") => ProviderError::Billing(body),
+        (_, "RESOURCE_EXHAUSTED") => ProviderError::RateLimit(body),
+        _ => parse_provider_error(status, &body),
+    }
+}
+
+// ============================================================================
+// LLMProvider Implementation
+// ============================================================================
+
+#[async_trait]
+impl LLMProvider for GeminiProvider {
+    async fn chat(
+        &self,
+        messages: Vec<Message>,
+        tools: Vec<ToolDefinition>,
+        model: Option<&str>,
+        options: ChatOptions,
+    ) -> Result<LLMResponse> {
+        let model = model.unwrap_or(DEFAULT_MODEL);
+        let request = build_request(&messages, &tools, &options);
+        debug!("Gemini request to model {}", model);
+
+        let response = self
+            .http_retry
+            .send("gemini", || {
+                self.client
+                    .post(self.endpoint(model, "generateContent"))
+                    .header("x-goog-api-key", &self.api_key)
+                    .header("Content-Type", "application/json")
+                    .json(&request)
+            })
+            .await
+            .map_err(|e| ZeptoError::Provider(format!("Gemini request failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(ZeptoError::from(parse_gemini_error(status, &error_text)));
+        }
+
+        let gemini_response: GeminiResponse = response
+            .json()
+            .await
+            .map_err(|e| ZeptoError::Provider(format!("Failed to parse Gemini response: {}", e)))?;
+
+        info!("Gemini response received");
+        convert_response(gemini_response)
+    }
+
+    async fn chat_stream(
+        &self,
+        messages: Vec<Message>,
+        tools: Vec<ToolDefinition>,
+        model: Option<&str>,
+        options: ChatOptions,
+    ) -> Result<tokio::sync::mpsc::Receiver<super::StreamEvent>> {
+        use super::StreamEvent;
+        use futures::StreamExt;
+
+        let model = model.unwrap_or(DEFAULT_MODEL);
+        let request = build_request(&messages, &tools, &options);
+        debug!("Gemini streaming request to model {}", model);
+
+        let response = self
+            .http_retry
+            .send("gemini", || {
+                self.client
+                    .post(self.endpoint(model, "streamGenerateContent"))
+                    .query(&[("alt", "sse")])
+                    .header("x-goog-api-key", &self.api_key)
+                    .header("Content-Type", "application/json")
+                    .json(&request)
+            })
+            .await
+            .map_err(|e| ZeptoError::Provider(format!("Gemini request failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(ZeptoError::from(parse_gemini_error(status, &error_text)));
+        }
+
+        let (tx, rx) = tokio::sync::mpsc::channel::<StreamEvent>(32);
+        let byte_stream = response.bytes_stream();
+
+        tokio::spawn(async move {
+            let mut assembled_content = String::new();
+            let mut tool_calls: Vec<LLMToolCall> = Vec::new();
+            let mut usage: Option<Usage> = None;
+            let mut line_buffer = String::new();
+
+            tokio::pin!(byte_stream);
+
+            while let Some(chunk_result) = byte_stream.next().await {
+                let chunk = match chunk_result {
+                    Ok(bytes) => bytes,
+                    Err(e) => {
+                        let _ = tx
+                            .send(StreamEvent::Error(ZeptoError::Provider(format!(
+                                "Stream read error: {}",
+                                e
+                            ))))
+                            .await;
+                        return;
+                    }
+                };
+
+                line_buffer.push_str(&String::from_utf8_lossy(&chunk));
+
+                while let Some(newline_pos) = line_buffer.find('\n') {
+                    let line = line_buffer[..newline_pos].trim().to_string();
+                    line_buffer = line_buffer[newline_pos + 1..].to_string();
+
+                    let Some(data) = line
+                        .strip_prefix("data: ")
+                        .or_else(|| line.strip_prefix("data:"))
+                    else {
+                        continue;
+                    };
+
+                    let stream_chunk: GeminiResponse = match serde_json::from_str(data) {
+                        Ok(v) => v,
+                        Err(_) => continue,
+                    };
+
+                    let deltas = apply_stream_chunk(
+                        stream_chunk,
+                        &mut assembled_content,
+                        &mut tool_calls,
+                        &mut usage,
+                    );
+
+                    for delta in deltas {
+                        if tx.send(StreamEvent::Delta(delta)).await.is_err() {
+                            return;
+                        }
+                    }
+                }
+            }
+
+            if !tool_calls.is_empty() {
+                let _ = tx.send(StreamEvent::ToolCalls(tool_calls)).await;
+            }
+
+            let _ = tx
+                .send(StreamEvent::Done {
+                    content: assembled_content,
+                    usage,
+                })
+                .await;
+        });
+
+        Ok(rx)
+    }
+
+    fn default_model(&self) -> &str {
+        DEFAULT_MODEL
+    }
+
+    fn name(&self) -> &str {
+        "gemini"
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::mock_http::{self, provider_error, request_json, MockResponse};
+    use crate::providers::StreamEvent;
+    use crate::session::ToolCall;
+    use serde_json::json;
+
+    /// Serve one canned response and return the `/v1beta` base URL.
+    async fn mock_server(response: MockResponse) -> (String, tokio::task::JoinHandle<Vec<String>>) {
+        let (addr, requests) = mock_http::serve(vec![response]).await;
+        (format!("{}/v1beta", addr), requests)
+    }
+
+    #[test]
+    fn test_gemini_provider_name_and_default_model() {
+        let provider = GeminiProvider::new("key");
+        assert_eq!(provider.name(), "gemini");
+        assert_eq!(provider.default_model(), DEFAULT_MODEL);
+    }
+
+    #[test]
+    fn test_endpoint_strips_models_prefix_and_trailing_slash() {
+        let provider = GeminiProvider::with_base_url("key", "https://example.com/v1beta/");
+        assert_eq!(
+            provider.endpoint("models/gemini-2.5-pro", "generateContent"),
+            "https://example.com/v1beta/models/gemini-2.5-pro:generateContent"
+        );
+    }
+
+    #[test]
+    fn test_build_request_maps_roles_and_system_instruction() {
+        let messages = vec![
+            Message::system("Be brief."),
+            Message::user("Hi"),
+            Message::assistant("Hello!"),
+            Message::user("Bye"),
+        ];
+        let request = build_request(&messages, &[], &ChatOptions::default());
+        let value = serde_json::to_value(&request).unwrap();
+
+        assert_eq!(value["systemInstruction"]["parts"][0]["text"], "Be brief.");
+        assert!(value["systemInstruction"].get("role").is_none());
+        let contents = value["contents"].as_array().unwrap();
+        assert_eq!(contents.len(), 3);
+        assert_eq!(contents[0]["role"], "user");
+        assert_eq!(contents[1]["role"], "model");
+        assert_eq!(contents[1]["parts"][0]["text"], "Hello!");
+        assert!(value.get("tools").is_none());
+        assert!(value.get("generationConfig").is_none());
+    }
+
+    #[test]
+    fn test_build_request_flattens_images() {
+        let messages = vec![Message::user("Look").with_image("image/png", "AAAA")];
+        let request = build_request(&messages, &[], &ChatOptions::default());
+        let value = serde_json::to_value(&request).unwrap();
+        assert_eq!(
+            value["contents"][0]["parts"][0]["text"],
+            "Look\n[image: image/png, 3 bytes not shown to this model]"
+        );
+    }
+
+    #[test]
+    fn test_build_request_maps_tool_calls_and_results() {
+        let messages = vec![
+            Message::user("Weather in Paris and Rome?"),
+            Message::assistant_with_tools(
+                "",
+                vec![
+                    ToolCall::new("call_1", "weather", r#"{"city":"Paris"}"#),
+                    ToolCall::new("call_2", "weather", r#"{"city":"Rome"}"#),
+                ],
+            ),
+            Message::tool_result("call_1", "Sunny"),
+            Message::tool_result("call_2", "Rain"),
+        ];
+        let request = build_request(&messages, &[], &ChatOptions::default());
+        let value = serde_json::to_value(&request).unwrap();
+        let contents = value["contents"].as_array().unwrap();
+
+        assert_eq!(contents.len(), 3);
+        assert_eq!(contents[1]["role"], "model");
+        let calls = contents[1]["parts"].as_array().unwrap();
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0]["functionCall"]["name"], "weather");
+        assert_eq!(calls[0]["functionCall"]["args"]["city"], "Paris");
+
+        assert_eq!(contents[2]["role"], "user");
+        let results = contents[2]["parts"].as_array().unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0]["functionResponse"]["name"], "weather");
+        assert_eq!(
+            results[1]["functionResponse"]["response"]["content"],
+            "Rain"
+        );
+    }
+
+    #[test]
+    fn test_build_request_maps_tools_and_options() {
+        let tools = vec![
+            ToolDefinition::new(
+                "search",
+                "Search the web",
+                json!({
+                    "$schema": "http://json-schema.org/draft-07/schema#",
+                    "type": "object",
+                    "properties": {
+                        "query": { "type": "string" },
+                        "filters": { "type": "object", "additionalProperties": false }
+                    },
+                    "required": ["query"],
+                    "additionalProperties": false
+                }),
+            ),
+            ToolDefinition::new(
+                "now",
+                "Current time",
+                json!({"type": "object", "properties": {}}),
+            ),
+        ];
+        let options = ChatOptions::new()
+            .with_max_tokens(256)
+            .with_temperature(0.2)
+            .with_stop(vec!["END".to_string()])
+            .with_output_format(OutputFormat::Json);
+        let request = build_request(&[Message::user("q")], &tools, &options);
+        let value = serde_json::to_value(&request).unwrap();
+
+        let decls = value["tools"][0]["functionDeclarations"]
+            .as_array()
+            .unwrap();
+        assert_eq!(decls.len(), 2);
+        assert_eq!(decls[0]["name"], "search");
+        let params = &decls[0]["parameters"];
+        assert!(params.get("$schema").is_none());
+        assert!(params.get("additionalProperties").is_none());
+        assert!(params["properties"]["filters"]
+            .get("additionalProperties")
+            .is_none());
+        assert_eq!(params["required"][0], "query");
+        assert!(decls[1].get("parameters").is_none());
+
+        let config = &value["generationConfig"];
+        assert_eq!(config["maxOutputTokens"], 256);
+        assert_eq!(config["stopSequences"][0], "END");
+        assert_eq!(config["responseMimeType"], "application/json");
+        assert!((config["temperature"].as_f64().unwrap() - 0.2).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_convert_response_text_tool_calls_and_usage() {
+        let response: GeminiResponse = serde_json::from_value(json!({
+            "candidates": [{
+                "content": {
+                    "role": "model",
+                    "parts": [
+                        { "text": "thinking...", "thought": true },
+                        { "text": "Let me check." },
+                        { "functionCall": { "name": "weather", "args": { "city": "Paris" } } }
+                    ]
+                },
+                "finishReason": "STOP"
+            }],
+            "usageMetadata": {
+                "promptTokenCount": 12,
+                "candidatesTokenCount": 5,
+                "totalTokenCount": 17
+            }
+        }))
+        .unwrap();
+
+        let result = convert_response(response).unwrap();
+        assert_eq!(result.content, "Let me check.");
+        assert_eq!(result.tool_calls.len(), 1);
+        assert_eq!(result.tool_calls[0].name, "weather");
+        assert!(result.tool_calls[0].id.starts_with("call_"));
+        let args: serde_json::Value =
+            serde_json::from_str(&result.tool_calls[0].arguments).unwrap();
+        assert_eq!(args["city"], "Paris");
+        let usage = result.usage.unwrap();
+        assert_eq!(usage.prompt_tokens, 12);
+        assert_eq!(usage.completion_tokens, 5);
+        assert_eq!(usage.total_tokens, 17);
+    }
+
+    #[test]
+    fn test_convert_response_blocked_prompt() {
+        let response: GeminiResponse =
+            serde_json::from_value(json!({ "promptFeedback": { "blockReason": "SAFETY" } }))
+                .unwrap();
+        let err = match convert_response(response) {
+            Ok(_) => panic!("blocked prompt should fail"),
+            Err(e) => provider_error(e),
+        };
+        assert!(matches!(err, ProviderError::InvalidRequest(ref m) if m.contains("SAFETY")));
+    }
+
+    #[test]
+    fn test_apply_stream_chunk_accumulates() {
+        let mut content = String::new();
+        let mut calls = Vec::new();
+        let mut usage = None;
+
+        let first: GeminiResponse = serde_json::from_value(json!({
+            "candidates": [{ "content": { "parts": [{ "text": "Hel" }] } }],
+            "usageMetadata": { "promptTokenCount": 3, "candidatesTokenCount": 1 }
+        }))
+        .unwrap();
+        let second: GeminiResponse = serde_json::from_value(json!({
+            "candidates": [{ "content": { "parts": [
+                { "text": "lo" },
+                { "functionCall": { "name": "ping", "args": {}, "id": "fc-1" } }
+            ] } }],
+            "usageMetadata": { "promptTokenCount": 3, "candidatesTokenCount": 4 }
+        }))
+        .unwrap();
+
+        assert_eq!(
+            apply_stream_chunk(first, &mut content, &mut calls, &mut usage),
+            vec!["Hel"]
+        );
+        assert_eq!(
+            apply_stream_chunk(second, &mut content, &mut calls, &mut usage),
+            vec!["lo"]
+        );
+        assert_eq!(content, "Hello");
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].id, "fc-1");
+        assert_eq!(usage.unwrap().completion_tokens, 4);
+    }
+
+    #[test]
+    fn test_parse_gemini_error_mapping() {
+        let bad_key = r#"{"error":{"code":400,"message":"API key not valid. Please pass a valid API key.","status":"INVALID_ARGUMENT"}}"#;
+        assert!(matches!(
+            parse_gemini_error(400, bad_key),
+            ProviderError::Auth(_)
+        ));
+
+        let denied =
+            r#"{"error":{"code":403,"message":"Permission denied","status":"PERMISSION_DENIED"}}"#;
+        assert!(matches!(
+            parse_gemini_error(403, denied),
+            ProviderError::Auth(_)
+        ));
+
+        let billing = r#"{"error":{"code":400,"message":"Billing account is not enabled for this project.","status":"FAILED_PRECONDITION"}}"#;
+        assert!(matches!(
+            parse_gemini_error(400, billing),
+            ProviderError::Billing(_)
+        ));
+
+        let quota =
+            r#"{"error":{"code":429,"message":"Quota exceeded","status":"RESOURCE_EXHAUSTED"}}"#;
+        let err = parse_gemini_error(429, quota);
+        assert!(matches!(err, ProviderError::RateLimit(_)));
+        assert!(err.should_fallback());
+
+        let unavailable =
+            r#"{"error":{"code":503,"message":"The model is overloaded.","status":"UNAVAILABLE"}}"#;
+        assert!(matches!(
+            parse_gemini_error(503, unavailable),
+            ProviderError::ServerError(_)
+        ));
+
+        let invalid = r#"{"error":{"code":400,"message":"Invalid JSON payload","status":"INVALID_ARGUMENT"}}"#;
+        assert!(matches!(
+            parse_gemini_error(400, invalid),
+            ProviderError::InvalidRequest(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_chat_against_mock_server() {
+        let body = json!({
+            "candidates": [{ "content": { "role": "model", "parts": [{ "text": "Hi there" }] } }],
+            "usageMetadata": { "promptTokenCount": 4, "candidatesTokenCount": 2 }
+        })
+        .to_string();
+        let (base, request) = mock_server(MockResponse::json(200, body)).await;
+        let provider = GeminiProvider::with_base_url("test-key", &base);
+
+        let response = provider
+            .chat(
+                vec![Message::system("sys"), Message::user("Hello")],
+                vec![],
+                Some("gemini-test"),
+                ChatOptions::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.content, "Hi there");
+        assert_eq!(response.usage.unwrap().total_tokens, 6);
+
+        let raw = request.await.unwrap().remove(0);
+        assert!(raw.starts_with("POST /v1beta/models/gemini-test:generateContent "));
+        assert!(raw.to_lowercase().contains("x-goog-api-key: test-key"));
+        let sent = request_json(&raw);
+        assert_eq!(sent["systemInstruction"]["parts"][0]["text"], "sys");
+        assert_eq!(sent["contents"][0]["parts"][0]["text"], "Hello");
+    }
+
+    #[tokio::test]
+    async fn test_chat_retries_transient_server_errors() {
+        let body = json!({
+            "candidates": [{ "content": { "role": "model", "parts": [{ "text": "Recovered" }] } }]
+        })
+        .to_string();
+        let unavailable = r#"{"error":{"code":503,"message":"overloaded"}}"#;
+        let (addr, requests) = mock_http::serve(vec![
+            MockResponse::json(503, unavailable),
+            MockResponse::json(503, unavailable),
+            MockResponse::json(200, body),
+        ])
+        .await;
+        let policy = HttpRetryPolicy::from_config(&crate::config::HttpRetryConfig {
+            base_delay_ms: 1,
+            jitter: false,
+            ..Default::default()
+        });
+        let provider = GeminiProvider::with_base_url("test-key", &format!("{}/v1beta", addr))
+            .with_http_retry(policy);
+
+        let response = provider
+            .chat(
+                vec![Message::user("Hi")],
+                vec![],
+                None,
+                ChatOptions::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.content, "Recovered");
+        assert_eq!(requests.await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_chat_maps_http_errors() {
+        let body = r#"{"error":{"code":429,"message":"Resource has been exhausted","status":"RESOURCE_EXHAUSTED"}}"#;
+        let (base, _request) = mock_server(MockResponse::json(429, body)).await;
+        let provider = GeminiProvider::with_base_url("test-key", &base);
+
+        let err = match provider
+            .chat(
+                vec![Message::user("Hi")],
+                vec![],
+                None,
+                ChatOptions::default(),
+            )
+            .await
+        {
+            Ok(_) => panic!("429 should fail"),
+            Err(e) => provider_error(e),
+        };
+        assert!(matches!(err, ProviderError::RateLimit(ref m) if m.contains("exhausted")));
+    }
+
+    #[tokio::test]
+    async fn test_chat_stream_against_mock_server() {
+        let sse = [
+            json!({ "candidates": [{ "content": { "role": "model", "parts": [{ "text": "Hel" }] } }] }),
+            json!({ "candidates": [{ "content": { "role": "model", "parts": [{ "text": "lo" }] } }] }),
+            json!({
+                "candidates": [{ "content": { "role": "model", "parts": [
+                    { "functionCall": { "name": "ping", "args": { "n": 1 } } }
+                ] } }],
+                "usageMetadata": { "promptTokenCount": 5, "candidatesTokenCount": 3 }
+            }),
+        ]
+        .iter()
+        .map(|chunk| format!("data: {}\r\n\r\n", chunk))
+        .collect::<String>();
+        let (base, request) = mock_server(MockResponse::stream("text/event-stream", sse)).await;
+        let provider = GeminiProvider::with_base_url("test-key", &base);
+
+        let mut rx = provider
+            .chat_stream(
+                vec![Message::user("Hi")],
+                vec![],
+                Some("gemini-test"),
+                ChatOptions::default(),
+            )
+            .await
+            .unwrap();
+
+        let mut deltas = Vec::new();
+        let mut tool_calls = Vec::new();
+        let mut done = None;
+        while let Some(event) = rx.recv().await {
+            match event {
+                StreamEvent::Delta(text) => deltas.push(text),
+                StreamEvent::ToolCalls(calls) => tool_calls = calls,
+                StreamEvent::Done { content, usage } => done = Some((content, usage)),
+                StreamEvent::Error(e) => panic!("unexpected stream error: {}", e),
+            }
+        }
+
+        assert_eq!(deltas, vec!["Hel", "lo"]);
+        assert_eq!(tool_calls.len(), 1);
+        assert_eq!(tool_calls[0].name, "ping");
+        let (content, usage) = done.expect("stream should finish");
+        assert_eq!(content, "Hello");
+        assert_eq!(usage.unwrap().total_tokens, 8);
+
+        let raw = request.await.unwrap().remove(0);
+        assert!(raw.starts_with("POST /v1beta/models/gemini-test:streamGenerateContent?alt=sse "));
+    }
+
+    #[tokio::test]
+    async fn test_chat_stream_maps_http_errors() {
+        let body =
+            r#"{"error":{"code":400,"message":"API key not valid.","status":"INVALID_ARGUMENT"}}"#;
+        let (base, _request) = mock_server(MockResponse::json(400, body)).await;
+        let provider = GeminiProvider::with_base_url("bad-key", &base);
+
+        let err = match provider
+            .chat_stream(
+                vec![Message::user("Hi")],
+                vec![],
+                None,
+                ChatOptions::default(),
+            )
+            .await
+        {
+            Ok(_) => panic!("bad key should fail"),
+            Err(e) => provider_error(e),
+        };
+        assert!(matches!(err, ProviderError::Auth(_)));
+        assert!(!err.should_fallback());
+    }
+}
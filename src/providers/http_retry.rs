@@ -0,0 +1,273 @@
+//! Retry with exponential backoff inside a provider's HTTP client.
+//!
+//! [`HttpRetryPolicy::send`] re-sends a request when the response status is
+//! 429, 500, 502, 503 or 504, or when the connection could not be made. A
+//! `Retry-After` header (seconds or HTTP date) on the response replaces the
+//! computed backoff. Every other response, including auth and billing
+//! failures, is returned on the first attempt for the provider to turn into
+//! a [`ProviderError`](crate::error::ProviderError).
+//!
+//! This works below fallback and rotation: a single provider survives a
+//! brief blip, and only persistent failures reach the layers above. It is
+//! the retry layer only while [`RetryProvider`](super::RetryProvider) is off;
+//! with `providers.retry` enabled the runtime builds providers with
+//! [`HttpRetryPolicy::disabled`] so attempts do not multiply.
+
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::{RequestBuilder, Response, StatusCode};
+use tracing::warn;
+
+use crate::config::HttpRetryConfig;
+
+use super::retry::compute_delay;
+
+/// Statuses worth retrying.
+const RETRYABLE_STATUSES: &[StatusCode] = &[
+    StatusCode::TOO_MANY_REQUESTS,
+    StatusCode::INTERNAL_SERVER_ERROR,
+    StatusCode::BAD_GATEWAY,
+    StatusCode::SERVICE_UNAVAILABLE,
+    StatusCode::GATEWAY_TIMEOUT,
+];
+
+/// When and how long to wait before re-sending a failed provider request.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpRetryPolicy {
+    max_retries: u32,
+    base_delay_ms: u64,
+    max_delay_ms: u64,
+    jitter: bool,
+}
+
+impl Default for HttpRetryPolicy {
+    /// No retries; providers built directly keep single-attempt behavior.
+    fn default() -> Self {
+        Self::disabled()
+    }
+}
+
+impl HttpRetryPolicy {
+    /// A policy that sends every request exactly once.
+    pub fn disabled() -> Self {
+        Self {
+            max_retries: 0,
+            base_delay_ms: 0,
+            max_delay_ms: 0,
+            jitter: false,
+        }
+    }
+
+    /// Build a policy from provider configuration.
+    pub fn from_config(config: &HttpRetryConfig) -> Self {
+        if !config.enabled {
+            return Self::disabled();
+        }
+        Self {
+            max_retries: config.max_retries,
+            base_delay_ms: config.base_delay_ms,
+            max_delay_ms: config.max_delay_ms,
+            jitter: config.jitter,
+        }
+    }
+
+    /// Send the request produced by `build`, retrying transient failures.
+    ///
+    /// `build` is called once per attempt. The last response is returned
+    /// even when its status is an error, so callers keep their own error
+    /// parsing.
+    pub async fn send<F>(&self, provider: &str, build: F) -> reqwest::Result<Response>
+    where
+        F: Fn() -> RequestBuilder,
+    {
+        let mut attempt = 0;
+        loop {
+            let result = build().send().await;
+            if attempt >= self.max_retries {
+                return result;
+            }
+
+            let (delay, reason) = match &result {
+                Ok(response) if RETRYABLE_STATUSES.contains(&response.status()) => {
+                    let delay = match retry_after(response.headers()) {
+                        Some(wait) if wait > Duration::from_millis(self.max_delay_ms) => {
+                            warn!(
+                                provider = provider,
+                                retry_after_secs = wait.as_secs(),
+                                "Retry-After exceeds max_delay_ms; not retrying"
+                            );
+                            return result;
+                        }
+                        Some(wait) => wait,
+                        None => self.backoff(attempt),
+                    };
+                    (delay, response.status().to_string())
+                }
+                Err(e) if e.is_connect() => (self.backoff(attempt), e.to_string()),
+                _ => return result,
+            };
+
+            attempt += 1;
+            warn!(
+                provider = provider,
+                attempt = attempt,
+                max_retries = self.max_retries,
+                delay_ms = delay.as_millis() as u64,
+                reason = %reason,
+                "Retrying provider request after transient failure"
+            );
+            drop(result);
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    /// Backoff before retry number `attempt + 1`.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let jitter_ms = if self.jitter {
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.subsec_nanos() as u64 % self.base_delay_ms.max(1))
+                .unwrap_or(0)
+        } else {
+            0
+        };
+        Duration::from_millis(compute_delay(
+            attempt,
+            self.base_delay_ms,
+            self.max_delay_ms,
+            jitter_ms,
+        ))
+    }
+}
+
+/// Parse a `Retry-After` header given as delay seconds or an HTTP date.
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let at = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let wait = at.signed_duration_since(chrono::Utc::now());
+    Some(wait.to_std().unwrap_or(Duration::ZERO))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::mock_http::{self, MockResponse};
+
+    fn fast_policy(max_retries: u32) -> HttpRetryPolicy {
+        HttpRetryPolicy::from_config(&HttpRetryConfig {
+            enabled: true,
+            max_retries,
+            base_delay_ms: 1,
+            max_delay_ms: 2_000,
+            jitter: false,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_retries_503_then_succeeds() {
+        let (base, requests) = mock_http::serve(vec![
+            MockResponse::json(503, r#"{"error":"busy"}"#),
+            MockResponse::json(503, r#"{"error":"busy"}"#),
+            MockResponse::json(200, r#"{"ok":true}"#),
+        ])
+        .await;
+        let client = reqwest::Client::new();
+
+        let response = fast_policy(2)
+            .send("test", || client.post(format!("{}/chat", base)).body("{}"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.text().await.unwrap(), r#"{"ok":true}"#);
+        assert_eq!(requests.await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_retries() {
+        let (base, requests) = mock_http::serve(vec![
+            MockResponse::json(502, "{}"),
+            MockResponse::json(502, "{}"),
+        ])
+        .await;
+        let client = reqwest::Client::new();
+
+        let response = fast_policy(1)
+            .send("test", || client.get(format!("{}/chat", base)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_GATEWAY);
+        assert_eq!(requests.await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_client_errors_are_not_retried() {
+        for status in [400, 401, 402, 403, 404] {
+            let (base, requests) = mock_http::serve(vec![MockResponse::json(status, "{}")]).await;
+            let client = reqwest::Client::new();
+            let response = fast_policy(3)
+                .send("test", || client.get(format!("{}/chat", base)))
+                .await
+                .unwrap();
+            assert_eq!(response.status().as_u16(), status);
+            assert_eq!(requests.await.unwrap().len(), 1);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_honors_retry_after() {
+        let (base, requests) = mock_http::serve(vec![
+            MockResponse::json(429, "{}").with_header("Retry-After", "1"),
+            MockResponse::json(200, "{}"),
+        ])
+        .await;
+        let client = reqwest::Client::new();
+
+        let start = std::time::Instant::now();
+        let response = fast_policy(2)
+            .send("test", || client.get(format!("{}/chat", base)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(start.elapsed() >= Duration::from_secs(1));
+        assert_eq!(requests.await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_long_retry_after_returns_response() {
+        let (base, requests) = mock_http::serve(vec![
+            MockResponse::json(429, "{}").with_header("Retry-After", "120")
+        ])
+        .await;
+        let client = reqwest::Client::new();
+        let response = fast_policy(2)
+            .send("test", || client.get(format!("{}/chat", base)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(requests.await.unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_retry_after_parsing_and_disabled_policy() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(retry_after(&headers), None);
+        headers.insert(RETRY_AFTER, "7".parse().unwrap());
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(7)));
+        headers.insert(
+            RETRY_AFTER,
+            "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap(),
+        );
+        assert_eq!(retry_after(&headers), Some(Duration::ZERO));
+
+        let disabled = HttpRetryPolicy::from_config(&HttpRetryConfig {
+            enabled: false,
+            ..Default::default()
+        });
+        assert_eq!(disabled, HttpRetryPolicy::disabled());
+        assert_eq!(HttpRetryPolicy::default(), HttpRetryPolicy::disabled());
+    }
+}
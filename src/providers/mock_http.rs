@@ -0,0 +1,121 @@
+//! Minimal canned HTTP server for provider and web tool tests.
+//!
+//! Serves a fixed list of responses, one per connection, on a local port and
+//! hands back the raw requests so tests can inspect what was sent.
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+
+use crate::error::{ProviderError, ZeptoError};
+
+/// A canned HTTP response.
+pub(crate) struct MockResponse {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: String,
+    /// Extra `name: value` response headers
+    pub headers: Vec<(&'static str, String)>,
+}
+
+impl MockResponse {
+    pub fn json(status: u16, body: impl Into<String>) -> Self {
+        Self {
+            status,
+            content_type: "application/json",
+            body: body.into(),
+            headers: Vec::new(),
+        }
+    }
+
+    pub fn stream(content_type: &'static str, body: impl Into<String>) -> Self {
+        Self {
+            status: 200,
+            content_type,
+            body: body.into(),
+            headers: Vec::new(),
+        }
+    }
+
+    /// Add a response header.
+    pub fn with_header(mut self, name: &'static str, value: impl Into<String>) -> Self {
+        self.headers.push((name, value.into()));
+        self
+    }
+}
+
+/// Serve `responses` in order, one per connection.
+///
+/// Returns `http://<addr>` and a handle resolving to the raw requests.
+pub(crate) async fn serve(responses: Vec<MockResponse>) -> (String, JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let handle = tokio::spawn(async move {
+        let mut requests = Vec::new();
+        for response in responses {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            requests.push(read_request(&mut socket).await);
+            let headers: String = response
+                .headers
+                .iter()
+                .map(|(name, value)| format!("{}: {}\r\n", name, value))
+                .collect();
+            let raw = format!(
+                "HTTP/1.1 {} Mock\r\nContent-Type: {}\r\nContent-Length: {}\r\n{}Connection: close\r\n\r\n{}",
+                response.status,
+                response.content_type,
+                response.body.len(),
+                headers,
+                response.body
+            );
+            socket.write_all(raw.as_bytes()).await.unwrap();
+            socket.shutdown().await.ok();
+        }
+        requests
+    });
+
+    (format!("http://{}", addr), handle)
+}
+
+/// Read one request: headers plus a `Content-Length` body.
+async fn read_request(socket: &mut tokio::net::TcpStream) -> String {
+    let mut request = Vec::new();
+    let mut buf = [0u8; 4096];
+    loop {
+        let n = socket.read(&mut buf).await.unwrap();
+        if n == 0 {
+            break;
+        }
+        request.extend_from_slice(&buf[..n]);
+        let text = String::from_utf8_lossy(&request);
+        if let Some(header_end) = text.find("\r\n\r\n") {
+            let content_length = text[..header_end]
+                .lines()
+                .find_map(|line| {
+                    let (name, value) = line.split_once(':')?;
+                    name.eq_ignore_ascii_case("content-length")
+                        .then(|| value.trim().parse::<usize>().ok())?
+                })
+                .unwrap_or(0);
+            if request.len() >= header_end + 4 + content_length {
+                break;
+            }
+        }
+    }
+    String::from_utf8_lossy(&request).to_string()
+}
+
+/// Parse the JSON body of a raw request.
+pub(crate) fn request_json(raw: &str) -> serde_json::Value {
+    let (_, body) = raw.split_once("\r\n\r\n").unwrap();
+    serde_json::from_str(body).unwrap()
+}
+
+/// Unwrap a typed provider error.
+pub(crate) fn provider_error(err: ZeptoError) -> ProviderError {
+    match err {
+        ZeptoError::ProviderTyped(pe) => pe,
+        other => panic!("expected typed provider error, got {:?}", other),
+    }
+}
@@ -23,7 +23,13 @@
 
 pub mod claude;
 pub mod fallback;
+pub mod gemini;
+pub mod http_retry;
+#[cfg(test)]
+pub(crate) mod mock_http;
+pub mod ollama;
 pub mod openai;
+pub mod prompt_tools;
 mod registry;
 pub mod retry;
 pub mod rotation;
@@ -43,21 +49,25 @@ pub const RUNTIME_SUPPORTED_PROVIDERS: &[&str] = &[
     "nvidia",
 ];
 
-use crate::error::ProviderError;
+use crate::error::{ProviderError, ZeptoError};
 
 pub use claude::ClaudeProvider;
 pub use fallback::FallbackProvider;
+pub use gemini::GeminiProvider;
+pub use http_retry::HttpRetryPolicy;
+pub use ollama::OllamaProvider;
 pub use openai::OpenAIProvider;
 pub use registry::{
     configured_provider_names, configured_unsupported_provider_names, provider_config_by_name,
-    resolve_runtime_provider, resolve_runtime_providers, ProviderSpec, RuntimeProviderSelection,
-    PROVIDER_REGISTRY,
+    provider_config_slot_mut, resolve_runtime_provider, resolve_runtime_providers, ProviderSpec,
+    RuntimeProviderSelection, PROVIDER_REGISTRY,
 };
 pub use retry::RetryProvider;
-pub use rotation::{RotationProvider, RotationStrategy};
+pub use rotation::{ProviderHealthStatus, RotationProvider, RotationStrategy};
 pub use structured::{validate_json_response, OutputFormat};
 pub use types::{
-    ChatOptions, LLMProvider, LLMResponse, LLMToolCall, StreamEvent, ToolDefinition, Usage,
+    ChatOptions, LLMProvider, LLMResponse, LLMToolCall, StreamEvent, ToolCallMode, ToolDefinition,
+    Usage,
 };
 
 /// Parse an HTTP status code and response body into a structured [`ProviderError`].
@@ -76,6 +86,19 @@ pub fn parse_provider_error(status: u16, body: &str) -> ProviderError {
     }
 }
 
+/// Decide whether a failed request should move on to another provider.
+///
+/// Shared by [`FallbackProvider`] and [`RotationProvider`] (for both `chat` and
+/// `chat_stream`) so the composites agree on which failures are recoverable.
+/// Typed errors defer to [`ProviderError::should_fallback`]; legacy string
+/// errors always fail over for backward compatibility.
+pub(crate) fn should_fallback(err: &ZeptoError) -> bool {
+    match err {
+        ZeptoError::ProviderTyped(pe) => pe.should_fallback(),
+        _ => true,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -147,4 +170,25 @@ mod tests {
         assert!(matches!(err, ProviderError::Unknown(_)));
         assert!(err.to_string().contains("HTTP 418"));
     }
+
+    #[test]
+    fn test_should_fallback_typed_errors() {
+        assert!(should_fallback(&ZeptoError::ProviderTyped(
+            ProviderError::RateLimit("slow down".into())
+        )));
+        assert!(should_fallback(&ZeptoError::ProviderTyped(
+            ProviderError::ServerError("boom".into())
+        )));
+        assert!(!should_fallback(&ZeptoError::ProviderTyped(
+            ProviderError::Auth("bad key".into())
+        )));
+        assert!(!should_fallback(&ZeptoError::ProviderTyped(
+            ProviderError::Billing("no funds".into())
+        )));
+    }
+
+    #[test]
+    fn test_should_fallback_legacy_errors() {
+        assert!(should_fallback(&ZeptoError::Provider("legacy".into())));
+    }
 }
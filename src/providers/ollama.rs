@@ -0,0 +1,1010 @@
+//! Ollama Provider Implementation
+//!
+//! This module implements the `LLMProvider` trait for a local Ollama server's
+//! native chat API (`/api/chat`), so agents can run fully offline.
+//!
+//! Models without native tool support are detected from Ollama's error reply
+//! and switched to prompt-based tool calling (see [`super::prompt_tools`]).
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use zeptoclaw::providers::{ollama::OllamaProvider, ChatOptions, LLMProvider};
+//! use zeptoclaw::session::Message;
+//!
+//! async fn example() {
+//!     let provider = OllamaProvider::new();
+//!
+//!     let response = provider
+//!         .chat(vec![Message::user("Hello!")], vec![], Some("llama3.2"), ChatOptions::default())
+//!         .await
+//!         .unwrap();
+//!
+//!     println!("Ollama: {}", response.content);
+//! }
+//! ```
+
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use reqwest::{Client, StatusCode};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info};
+
+use crate::error::{ProviderError, Result, ZeptoError};
+use crate::session::{Message, Role};
+
+use super::{
+    parse_provider_error, prompt_tools, ChatOptions, HttpRetryPolicy, LLMProvider, LLMResponse,
+    LLMToolCall, OutputFormat, ToolCallMode, ToolDefinition, Usage,
+};
+
+/// The default local Ollama server URL.
+const OLLAMA_API_URL: &str = "http://localhost:11434";
+
+/// The default Ollama model to use.
+/// Can be overridden at compile time with `ZEPTOCLAW_OLLAMA_DEFAULT_MODEL` env var.
+const DEFAULT_MODEL: &str = match option_env!("ZEPTOCLAW_OLLAMA_DEFAULT_MODEL") {
+    Some(v) => v,
+    None => "llama3.2",
+};
+
+// ============================================================================
+// Ollama API Request Types
+// ============================================================================
+
+/// Ollama `/api/chat` request body.
+#[derive(Debug, Serialize)]
+struct OllamaRequest {
+    model: String,
+    messages: Vec<OllamaMessage>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tools: Vec<OllamaTool>,
+    stream: bool,
+    /// `"json"` or a JSON Schema for structured output
+    #[serde(skip_serializing_if = "Option::is_none")]
+    format: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    options: Option<OllamaOptions>,
+}
+
+/// A chat message in Ollama's format.
+#[derive(Debug, Serialize)]
+struct OllamaMessage {
+    role: &'static str,
+    content: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tool_calls: Vec<OllamaToolCall>,
+    /// Name of the tool a `tool` message answers
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_name: Option<String>,
+}
+
+/// A tool call, as sent in history and returned by the model.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct OllamaToolCall {
+    function: OllamaFunctionCall,
+}
+
+/// Function name and arguments. Ollama uses a JSON object, not a string.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct OllamaFunctionCall {
+    name: String,
+    #[serde(default)]
+    arguments: serde_json::Value,
+}
+
+/// Tool definition in Ollama's format.
+#[derive(Debug, Serialize)]
+struct OllamaTool {
+    r#type: &'static str,
+    function: OllamaFunction,
+}
+
+/// Function definition.
+#[derive(Debug, Serialize)]
+struct OllamaFunction {
+    name: String,
+    description: String,
+    parameters: serde_json::Value,
+}
+
+/// Model options (Ollama's name for sampling parameters).
+#[derive(Debug, Default, Serialize)]
+struct OllamaOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    num_predict: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stop: Option<Vec<String>>,
+}
+
+// ============================================================================
+// Ollama API Response Types
+// ============================================================================
+
+/// Ollama `/api/chat` response (also each streamed NDJSON line).
+#[derive(Debug, Deserialize)]
+struct OllamaResponse {
+    /// Model that served the request
+    #[serde(default)]
+    model: Option<String>,
+    #[serde(default)]
+    message: Option<OllamaResponseMessage>,
+    #[serde(default)]
+    done: bool,
+    #[serde(default)]
+    prompt_eval_count: Option<u32>,
+    #[serde(default)]
+    eval_count: Option<u32>,
+    /// Set when the server fails mid-stream
+    #[serde(default)]
+    error: Option<String>,
+}
+
+/// Assistant message in a response.
+#[derive(Debug, Deserialize)]
+struct OllamaResponseMessage {
+    #[serde(default)]
+    content: String,
+    #[serde(default)]
+    tool_calls: Vec<OllamaToolCall>,
+}
+
+/// Ollama error body.
+#[derive(Debug, Deserialize)]
+struct OllamaErrorResponse {
+    error: String,
+}
+
+// ============================================================================
+// Ollama Provider
+// ============================================================================
+
+/// Ollama LLM provider.
+///
+/// Talks to Ollama's native `/api/chat` endpoint. No API key is needed for a
+/// local server; one is sent as a bearer token when configured (for servers
+/// behind an authenticating proxy).
+pub struct OllamaProvider {
+    /// Optional bearer token
+    api_key: Option<String>,
+    /// Server base URL
+    api_base: String,
+    /// HTTP client for making requests
+    client: Client,
+    /// Retries of transient HTTP failures
+    http_retry: HttpRetryPolicy,
+    /// Always use prompt-based tool calling
+    prompted_tools: bool,
+    /// Models found to lack native tool support
+    prompted_models: Mutex<HashSet<String>>,
+}
+
+impl OllamaProvider {
+    /// Create a provider for the local Ollama server at `http://localhost:11434`.
+    ///
+    /// # Example
+    /// ```
+    /// use zeptoclaw::providers::ollama::OllamaProvider;
+    /// use zeptoclaw::providers::LLMProvider;
+    ///
+    /// let provider = OllamaProvider::new();
+    /// assert_eq!(provider.name(), "ollama");
+    /// ```
+    pub fn new() -> Self {
+        Self::with_base_url(OLLAMA_API_URL)
+    }
+
+    /// Create a provider for an Ollama server at a custom URL.
+    ///
+    /// # Arguments
+    /// * `api_base` - Server URL (trailing slash will be removed)
+    pub fn with_base_url(api_base: &str) -> Self {
+        Self {
+            api_key: None,
+            api_base: api_base.trim_end_matches('/').to_string(),
+            client: Client::builder()
+                .timeout(std::time::Duration::from_secs(300))
+                .build()
+                .unwrap_or_else(|_| Client::new()),
+            http_retry: HttpRetryPolicy::default(),
+            prompted_tools: false,
+            prompted_models: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Send an API key as a bearer token. Empty keys are ignored.
+    pub fn with_api_key(mut self, api_key: &str) -> Self {
+        self.api_key = (!api_key.is_empty()).then(|| api_key.to_string());
+        self
+    }
+
+    /// Retry transient HTTP failures (429, 5xx) according to `policy`.
+    pub fn with_http_retry(mut self, policy: HttpRetryPolicy) -> Self {
+        self.http_retry = policy;
+        self
+    }
+
+    /// Always describe tools in the prompt instead of using native tool calls.
+    ///
+    /// Without this, prompting is used only for models whose first native
+    /// tool request is rejected.
+    pub fn with_prompted_tools(mut self, enabled: bool) -> Self {
+        self.prompted_tools = enabled;
+        self
+    }
+
+    fn uses_prompted_tools(&self, model: &str, options: &ChatOptions) -> bool {
+        match options.tool_call_mode {
+            ToolCallMode::Native => return false,
+            ToolCallMode::Prompted => return true,
+            ToolCallMode::Auto => {}
+        }
+        self.prompted_tools
+            || self
+                .prompted_models
+                .lock()
+                .map(|models| models.contains(model))
+                .unwrap_or(false)
+    }
+
+    fn remember_prompted_model(&self, model: &str) {
+        if let Ok(mut models) = self.prompted_models.lock() {
+            models.insert(model.to_string());
+        }
+    }
+
+    /// POST a request to `/api/chat`, returning the raw response on success.
+    async fn send(
+        &self,
+        request: &OllamaRequest,
+    ) -> std::result::Result<reqwest::Response, SendError> {
+        let response = self
+            .http_retry
+            .send("ollama", || {
+                let builder = self
+                    .client
+                    .post(format!("{}/api/chat", self.api_base))
+                    .json(request);
+                match &self.api_key {
+                    Some(key) => builder.header("Authorization", format!("Bearer {}", key)),
+                    None => builder,
+                }
+            })
+            .await
+            .map_err(|e| SendError::Fatal(map_request_error(&self.api_base, e)))?;
+
+        if response.status().is_success() {
+            return Ok(response);
+        }
+
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        let message = serde_json::from_str::<OllamaErrorResponse>(&error_text)
+            .map(|e| e.error)
+            .unwrap_or(error_text);
+
+        if status == StatusCode::BAD_REQUEST && prompt_tools::is_tools_unsupported_error(&message) {
+            return Err(SendError::ToolsUnsupported);
+        }
+
+        Err(SendError::Fatal(ZeptoError::from(parse_provider_error(
+            status.as_u16(),
+            &format!("Ollama API error: {}", message),
+        ))))
+    }
+
+    /// Run a non-streaming chat with tools described in the prompt.
+    async fn chat_prompted(
+        &self,
+        messages: &[Message],
+        tools: &[ToolDefinition],
+        model: &str,
+        options: &ChatOptions,
+    ) -> Result<LLMResponse> {
+        prompt_tools::chat_prompted(messages, tools, |prompted| async move {
+            let request = build_request(model, &prompted, &[], options, false);
+            debug!("Ollama prompted-tools request to model {}", model);
+
+            let response = self.send(&request).await.map_err(SendError::into_error)?;
+            let ollama_response: OllamaResponse = response.json().await.map_err(|e| {
+                ZeptoError::Provider(format!("Failed to parse Ollama response: {}", e))
+            })?;
+            convert_response(ollama_response)
+        })
+        .await
+    }
+}
+
+impl Default for OllamaProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Outcome of a failed `/api/chat` request.
+enum SendError {
+    /// The model rejected the `tools` field; retry with prompted tools.
+    ToolsUnsupported,
+    /// Any other failure.
+    Fatal(ZeptoError),
+}
+
+impl SendError {
+    fn into_error(self) -> ZeptoError {
+        match self {
+            SendError::ToolsUnsupported => ZeptoError::from(ProviderError::InvalidRequest(
+                "Ollama API error: model does not support tools".to_string(),
+            )),
+            SendError::Fatal(err) => err,
+        }
+    }
+}
+
+// ============================================================================
+// Conversion Functions
+// ============================================================================
+
+/// Build an Ollama request from the provider-neutral inputs.
+fn build_request(
+    model: &str,
+    messages: &[Message],
+    tools: &[ToolDefinition],
+    options: &ChatOptions,
+    stream: bool,
+) -> OllamaRequest {
+    let mut call_names = std::collections::HashMap::new();
+    let messages = messages
+        .iter()
+        .map(|msg| match msg.role {
+            // Images are flattened to placeholder notes
+            Role::System => text_message("system", &msg.flattened_content()),
+            Role::User => text_message("user", &msg.flattened_content()),
+            Role::Assistant => {
+                let tool_calls = msg
+                    .tool_calls
+                    .iter()
+                    .flatten()
+                    .map(|call| {
+                        call_names.insert(call.id.clone(), call.name.clone());
+                        OllamaToolCall {
+                            function: OllamaFunctionCall {
+                                name: call.name.clone(),
+                                arguments: serde_json::from_str(&call.arguments)
+                                    .unwrap_or_else(|_| serde_json::json!({})),
+                            },
+                        }
+                    })
+                    .collect();
+                OllamaMessage {
+                    tool_calls,
+                    ..text_message("assistant", &msg.flattened_content())
+                }
+            }
+            Role::Tool => OllamaMessage {
+                tool_name: msg
+                    .tool_call_id
+                    .as_ref()
+                    .and_then(|id| call_names.get(id).cloned()),
+                ..text_message("tool", &msg.flattened_content())
+            },
+        })
+        .collect();
+
+    let tools = tools
+        .iter()
+        .map(|tool| OllamaTool {
+            r#type: "function",
+            function: OllamaFunction {
+                name: tool.name.clone(),
+                description: tool.description.clone(),
+                parameters: tool.parameters.clone(),
+            },
+        })
+        .collect();
+
+    let format = match &options.output_format {
+        OutputFormat::Text => None,
+        OutputFormat::Json => Some(serde_json::Value::String("json".to_string())),
+        OutputFormat::JsonSchema { schema, .. } => Some(schema.clone()),
+    };
+
+    let model_options = OllamaOptions {
+        num_predict: options.max_tokens,
+        temperature: options.temperature,
+        top_p: options.top_p,
+        stop: options.stop.clone(),
+    };
+    let has_options = model_options.num_predict.is_some()
+        || model_options.temperature.is_some()
+        || model_options.top_p.is_some()
+        || model_options.stop.is_some();
+
+    OllamaRequest {
+        model: model.to_string(),
+        messages,
+        tools,
+        stream,
+        format,
+        options: has_options.then_some(model_options),
+    }
+}
+
+fn text_message(role: &'static str, content: &str) -> OllamaMessage {
+    OllamaMessage {
+        role,
+        content: content.to_string(),
+        tool_calls: Vec::new(),
+        tool_name: None,
+    }
+}
+
+fn convert_tool_call(call: OllamaToolCall) -> LLMToolCall {
+    let id = format!("call_{}", uuid::Uuid::new_v4().simple());
+    let arguments = if call.function.arguments.is_null() {
+        "{}".to_string()
+    } else {
+        call.function.arguments.to_string()
+    };
+    LLMToolCall::new(&id, &call.function.name, &arguments)
+}
+
+fn convert_usage(response: &OllamaResponse) -> Option<Usage> {
+    match (response.prompt_eval_count, response.eval_count) {
+        (None, None) => None,
+        (prompt, completion) => Some(
+            Usage::new(prompt.unwrap_or(0), completion.unwrap_or(0))
+                .with_model(response.model.as_deref()),
+        ),
+    }
+}
+
+/// Convert a complete Ollama response into an `LLMResponse`.
+fn convert_response(response: OllamaResponse) -> Result<LLMResponse> {
+    if let Some(error) = response.error {
+        return Err(ZeptoError::Provider(format!("Ollama API error: {}", error)));
+    }
+
+    let usage = convert_usage(&response);
+    let (content, tool_calls) = match response.message {
+        Some(message) => (
+            message.content,
+            message
+                .tool_calls
+                .into_iter()
+                .map(convert_tool_call)
+                .collect(),
+        ),
+        None => (String::new(), Vec::new()),
+    };
+
+    let mut llm_response = LLMResponse::with_tools(&content, tool_calls);
+    if let Some(usage) = usage {
+        llm_response = llm_response.with_usage(usage);
+    }
+    Ok(llm_response)
+}
+
+/// Map a transport error, treating an unreachable server as a server error
+/// so rotation and fallback move on to the next provider.
+fn map_request_error(api_base: &str, err: reqwest::Error) -> ZeptoError {
+    if err.is_connect() {
+        ZeptoError::from(ProviderError::ServerError(format!(
+            "Ollama server at {} is not reachable: {}",
+            api_base, err
+        )))
+    } else if err.is_timeout() {
+        ZeptoError::from(ProviderError::Timeout(format!(
+            "Ollama request timed out: {}",
+            err
+        )))
+    } else {
+        ZeptoError::Provider(format!("Ollama request failed: {}", err))
+    }
+}
+
+// ============================================================================
+// LLMProvider Implementation
+// ============================================================================
+
+#[async_trait]
+impl LLMProvider for OllamaProvider {
+    async fn chat(
+        &self,
+        messages: Vec<Message>,
+        tools: Vec<ToolDefinition>,
+        model: Option<&str>,
+        options: ChatOptions,
+    ) -> Result<LLMResponse> {
+        let model = model.unwrap_or(DEFAULT_MODEL);
+        if !tools.is_empty() && self.uses_prompted_tools(model, &options) {
+            return self.chat_prompted(&messages, &tools, model, &options).await;
+        }
+
+        let request = build_request(model, &messages, &tools, &options, false);
+        debug!("Ollama request to model {}", model);
+
+        let response = match self.send(&request).await {
+            Ok(response) => response,
+            Err(SendError::ToolsUnsupported)
+                if !tools.is_empty() && options.tool_call_mode == ToolCallMode::Auto =>
+            {
+                info!(
+                    "Ollama model '{}' does not support tools; switching to prompted tool calls",
+                    model
+                );
+                self.remember_prompted_model(model);
+                return self.chat_prompted(&messages, &tools, model, &options).await;
+            }
+            Err(err) => return Err(err.into_error()),
+        };
+
+        let ollama_response: OllamaResponse = response
+            .json()
+            .await
+            .map_err(|e| ZeptoError::Provider(format!("Failed to parse Ollama response: {}", e)))?;
+
+        info!("Ollama response received");
+        convert_response(ollama_response)
+    }
+
+    async fn chat_stream(
+        &self,
+        messages: Vec<Message>,
+        tools: Vec<ToolDefinition>,
+        model: Option<&str>,
+        options: ChatOptions,
+    ) -> Result<tokio::sync::mpsc::Receiver<super::StreamEvent>> {
+        use super::StreamEvent;
+        use futures::StreamExt;
+
+        let model = model.unwrap_or(DEFAULT_MODEL);
+
+        // Prompted tool calls are parsed from the full reply, so they are not streamed.
+        if !tools.is_empty() && self.uses_prompted_tools(model, &options) {
+            let response = self
+                .chat_prompted(&messages, &tools, model, &options)
+                .await?;
+            return Ok(prompt_tools::replay_response(response));
+        }
+
+        let request = build_request(model, &messages, &tools, &options, true);
+        debug!("Ollama streaming request to model {}", model);
+
+        let response = match self.send(&request).await {
+            Ok(response) => response,
+            Err(SendError::ToolsUnsupported)
+                if !tools.is_empty() && options.tool_call_mode == ToolCallMode::Auto =>
+            {
+                info!(
+                    "Ollama model '{}' does not support tools; switching to prompted tool calls",
+                    model
+                );
+                self.remember_prompted_model(model);
+                let response = self
+                    .chat_prompted(&messages, &tools, model, &options)
+                    .await?;
+                return Ok(prompt_tools::replay_response(response));
+            }
+            Err(err) => return Err(err.into_error()),
+        };
+
+        let (tx, rx) = tokio::sync::mpsc::channel::<StreamEvent>(32);
+        let byte_stream = response.bytes_stream();
+        tokio::spawn(async move {
+            let mut assembled_content = String::new();
+            let mut tool_calls: Vec<LLMToolCall> = Vec::new();
+            let mut usage: Option<Usage> = None;
+            let mut line_buffer = String::new();
+
+            tokio::pin!(byte_stream);
+
+            while let Some(chunk_result) = byte_stream.next().await {
+                let chunk = match chunk_result {
+                    Ok(bytes) => bytes,
+                    Err(e) => {
+                        let _ = tx
+                            .send(StreamEvent::Error(ZeptoError::Provider(format!(
+                                "Stream read error: {}",
+                                e
+                            ))))
+                            .await;
+                        return;
+                    }
+                };
+
+                line_buffer.push_str(&String::from_utf8_lossy(&chunk));
+
+                while let Some(newline_pos) = line_buffer.find('\n') {
+                    let line = line_buffer[..newline_pos].trim().to_string();
+                    line_buffer = line_buffer[newline_pos + 1..].to_string();
+                    if line.is_empty() {
+                        continue;
+                    }
+
+                    let stream_chunk: OllamaResponse = match serde_json::from_str(&line) {
+                        Ok(v) => v,
+                        Err(_) => continue,
+                    };
+
+                    if let Some(error) = stream_chunk.error {
+                        let _ = tx
+                            .send(StreamEvent::Error(ZeptoError::Provider(format!(
+                                "Ollama API error: {}",
+                                error
+                            ))))
+                            .await;
+                        return;
+                    }
+
+                    if stream_chunk.done {
+                        usage = convert_usage(&stream_chunk);
+                    }
+
+                    if let Some(message) = stream_chunk.message {
+                        tool_calls.extend(message.tool_calls.into_iter().map(convert_tool_call));
+                        if !message.content.is_empty() {
+                            assembled_content.push_str(&message.content);
+                            if tx.send(StreamEvent::Delta(message.content)).await.is_err() {
+                                return;
+                            }
+                        }
+                    }
+                }
+            }
+
+            if !tool_calls.is_empty() {
+                let _ = tx.send(StreamEvent::ToolCalls(tool_calls)).await;
+            }
+
+            let _ = tx
+                .send(StreamEvent::Done {
+                    content: assembled_content,
+                    usage,
+                })
+                .await;
+        });
+
+        Ok(rx)
+    }
+
+    fn default_model(&self) -> &str {
+        DEFAULT_MODEL
+    }
+
+    fn name(&self) -> &str {
+        "ollama"
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::mock_http::{self, provider_error, request_json, MockResponse};
+    use crate::providers::StreamEvent;
+    use crate::session::ToolCall;
+    use serde_json::json;
+
+    fn weather_tool() -> ToolDefinition {
+        ToolDefinition::new(
+            "weather",
+            "Get the weather",
+            json!({"type": "object", "properties": {"city": {"type": "string"}}}),
+        )
+    }
+
+    #[test]
+    fn test_ollama_provider_defaults() {
+        let provider = OllamaProvider::new();
+        assert_eq!(provider.name(), "ollama");
+        assert_eq!(provider.default_model(), DEFAULT_MODEL);
+        assert_eq!(provider.api_base, OLLAMA_API_URL);
+        assert!(provider.with_api_key("").api_key.is_none());
+    }
+
+    #[test]
+    fn test_build_request_maps_messages_tools_and_options() {
+        let messages = vec![
+            Message::system("Be brief."),
+            Message::user("Weather in Paris?"),
+            Message::assistant_with_tools(
+                "",
+                vec![ToolCall::new("c1", "weather", r#"{"city":"Paris"}"#)],
+            ),
+            Message::tool_result("c1", "Sunny"),
+        ];
+        let options = ChatOptions::new()
+            .with_max_tokens(64)
+            .with_output_format(OutputFormat::Json);
+        let request = build_request("llama3.2", &messages, &[weather_tool()], &options, true);
+        let value = serde_json::to_value(&request).unwrap();
+
+        assert_eq!(value["model"], "llama3.2");
+        assert_eq!(value["stream"], true);
+        assert_eq!(value["messages"][0]["role"], "system");
+        assert_eq!(
+            value["messages"][2]["tool_calls"][0]["function"]["arguments"]["city"],
+            "Paris"
+        );
+        assert_eq!(value["messages"][3]["role"], "tool");
+        assert_eq!(value["messages"][3]["tool_name"], "weather");
+        assert_eq!(value["tools"][0]["type"], "function");
+        assert_eq!(value["tools"][0]["function"]["name"], "weather");
+        assert_eq!(value["options"]["num_predict"], 64);
+        assert_eq!(value["format"], "json");
+    }
+
+    #[test]
+    fn test_build_request_omits_empty_fields() {
+        let request = build_request(
+            "llama3.2",
+            &[Message::user("Hi")],
+            &[],
+            &ChatOptions::default(),
+            false,
+        );
+        let value = serde_json::to_value(&request).unwrap();
+        assert!(value.get("tools").is_none());
+        assert!(value.get("options").is_none());
+        assert!(value.get("format").is_none());
+        assert!(value["messages"][0].get("tool_calls").is_none());
+    }
+
+    #[test]
+    fn test_convert_response_tool_calls_and_usage() {
+        let response: OllamaResponse = serde_json::from_value(json!({
+            "message": {
+                "role": "assistant",
+                "content": "",
+                "tool_calls": [{ "function": { "name": "weather", "arguments": { "city": "Rome" } } }]
+            },
+            "done": true,
+            "prompt_eval_count": 20,
+            "eval_count": 7
+        }))
+        .unwrap();
+
+        let result = convert_response(response).unwrap();
+        assert_eq!(result.tool_calls.len(), 1);
+        assert_eq!(result.tool_calls[0].name, "weather");
+        assert!(result.tool_calls[0].id.starts_with("call_"));
+        assert_eq!(result.tool_calls[0].arguments, r#"{"city":"Rome"}"#);
+        assert_eq!(result.usage.unwrap().total_tokens, 27);
+    }
+
+    #[test]
+    fn test_is_tools_unsupported_error() {
+        assert!(prompt_tools::is_tools_unsupported_error(
+            "registry.ollama.ai/library/gemma2:latest does not support tools"
+        ));
+        assert!(!prompt_tools::is_tools_unsupported_error(
+            "model 'x' not found"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_chat_against_mock_server() {
+        let body = json!({
+            "message": { "role": "assistant", "content": "Hello!" },
+            "done": true,
+            "prompt_eval_count": 3,
+            "eval_count": 2
+        });
+        let (base, requests) =
+            mock_http::serve(vec![MockResponse::json(200, body.to_string())]).await;
+        let provider = OllamaProvider::with_base_url(&base);
+
+        let response = provider
+            .chat(
+                vec![Message::user("Hi")],
+                vec![],
+                None,
+                ChatOptions::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.content, "Hello!");
+        assert_eq!(response.usage.unwrap().total_tokens, 5);
+
+        let raw = requests.await.unwrap().remove(0);
+        assert!(raw.starts_with("POST /api/chat "));
+        assert!(!raw.to_lowercase().contains("authorization:"));
+        assert_eq!(request_json(&raw)["stream"], false);
+    }
+
+    #[tokio::test]
+    async fn test_chat_falls_back_to_prompted_tools() {
+        let unsupported =
+            json!({ "error": "registry.ollama.ai/library/gemma2:latest does not support tools" });
+        let prompted_reply = |city: &str| {
+            json!({
+                "message": {
+                    "role": "assistant",
+                    "content": format!(
+                        "```json\n{{\"tool\": \"weather\", \"arguments\": {{\"city\": \"{}\"}}}}\n```",
+                        city
+                    )
+                },
+                "done": true
+            })
+            .to_string()
+        };
+        let (base, requests) = mock_http::serve(vec![
+            MockResponse::json(400, unsupported.to_string()),
+            MockResponse::json(200, prompted_reply("Paris")),
+            MockResponse::json(200, prompted_reply("Rome")),
+        ])
+        .await;
+        let provider = OllamaProvider::with_base_url(&base);
+
+        for city in ["Paris", "Rome"] {
+            let response = provider
+                .chat(
+                    vec![Message::user("Weather?")],
+                    vec![weather_tool()],
+                    Some("gemma2"),
+                    ChatOptions::default(),
+                )
+                .await
+                .unwrap();
+            assert!(response.content.is_empty());
+            assert_eq!(response.tool_calls.len(), 1);
+            let args: serde_json::Value =
+                serde_json::from_str(&response.tool_calls[0].arguments).unwrap();
+            assert_eq!(args["city"], city);
+        }
+
+        let requests = requests.await.unwrap();
+        assert!(request_json(&requests[0]).get("tools").is_some());
+        // The model is remembered, so later requests go straight to prompting.
+        for raw in &requests[1..] {
+            let sent = request_json(raw);
+            assert!(sent.get("tools").is_none());
+            assert_eq!(sent["messages"][0]["role"], "system");
+            assert!(sent["messages"][0]["content"]
+                .as_str()
+                .unwrap()
+                .contains("You can call tools."));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_chat_maps_http_errors() {
+        let body = json!({ "error": "model 'nope' not found, try pulling it first" });
+        let (base, _requests) =
+            mock_http::serve(vec![MockResponse::json(404, body.to_string())]).await;
+        let provider = OllamaProvider::with_base_url(&base).with_api_key("secret");
+
+        let err = match provider
+            .chat(
+                vec![Message::user("Hi")],
+                vec![],
+                Some("nope"),
+                ChatOptions::default(),
+            )
+            .await
+        {
+            Ok(_) => panic!("404 should fail"),
+            Err(e) => provider_error(e),
+        };
+        assert!(matches!(err, ProviderError::ModelNotFound(ref m) if m.contains("not found")));
+    }
+
+    #[tokio::test]
+    async fn test_connection_refused_is_retryable_server_error() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+        let provider = OllamaProvider::with_base_url(&format!("http://{}", addr));
+
+        let err = match provider
+            .chat(
+                vec![Message::user("Hi")],
+                vec![],
+                None,
+                ChatOptions::default(),
+            )
+            .await
+        {
+            Ok(_) => panic!("closed port should fail"),
+            Err(e) => provider_error(e),
+        };
+        assert!(matches!(err, ProviderError::ServerError(ref m) if m.contains("not reachable")));
+        assert!(err.should_fallback());
+    }
+
+    #[tokio::test]
+    async fn test_chat_stream_against_mock_server() {
+        let lines = [
+            json!({ "message": { "role": "assistant", "content": "Hel" }, "done": false }),
+            json!({ "message": { "role": "assistant", "content": "lo" }, "done": false }),
+            json!({
+                "message": {
+                    "role": "assistant",
+                    "content": "",
+                    "tool_calls": [{ "function": { "name": "weather", "arguments": { "city": "Oslo" } } }]
+                },
+                "done": false
+            }),
+            json!({ "message": { "role": "assistant", "content": "" }, "done": true, "prompt_eval_count": 4, "eval_count": 6 }),
+        ]
+        .iter()
+        .map(|line| format!("{}\n", line))
+        .collect::<String>();
+        let (base, requests) =
+            mock_http::serve(vec![MockResponse::stream("application/x-ndjson", lines)]).await;
+        let provider = OllamaProvider::with_base_url(&base);
+
+        let mut rx = provider
+            .chat_stream(
+                vec![Message::user("Hi")],
+                vec![weather_tool()],
+                None,
+                ChatOptions::default(),
+            )
+            .await
+            .unwrap();
+
+        let mut deltas = Vec::new();
+        let mut tool_calls = Vec::new();
+        let mut done = None;
+        while let Some(event) = rx.recv().await {
+            match event {
+                StreamEvent::Delta(text) => deltas.push(text),
+                StreamEvent::ToolCalls(calls) => tool_calls = calls,
+                StreamEvent::Done { content, usage } => done = Some((content, usage)),
+                StreamEvent::Error(e) => panic!("unexpected stream error: {}", e),
+            }
+        }
+
+        assert_eq!(deltas, vec!["Hel", "lo"]);
+        assert_eq!(tool_calls.len(), 1);
+        assert_eq!(tool_calls[0].name, "weather");
+        let (content, usage) = done.expect("stream should finish");
+        assert_eq!(content, "Hello");
+        assert_eq!(usage.unwrap().total_tokens, 10);
+        assert_eq!(request_json(&requests.await.unwrap()[0])["stream"], true);
+    }
+
+    #[tokio::test]
+    async fn test_chat_stream_prompted_tools_replays_response() {
+        let body = json!({
+            "message": {
+                "role": "assistant",
+                "content": "Checking.\n```json\n{\"tool\": \"weather\", \"arguments\": {\"city\": \"Oslo\"}}\n```"
+            },
+            "done": true
+        });
+        let (base, requests) =
+            mock_http::serve(vec![MockResponse::json(200, body.to_string())]).await;
+        let provider = OllamaProvider::with_base_url(&base).with_prompted_tools(true);
+
+        let mut rx = provider
+            .chat_stream(
+                vec![Message::user("Weather?")],
+                vec![weather_tool()],
+                None,
+                ChatOptions::default(),
+            )
+            .await
+            .unwrap();
+
+        let mut events = Vec::new();
+        while let Some(event) = rx.recv().await {
+            events.push(event);
+        }
+        assert!(matches!(&events[0], StreamEvent::Delta(text) if text == "Checking."));
+        assert!(matches!(&events[1], StreamEvent::ToolCalls(calls) if calls[0].name == "weather"));
+        assert!(matches!(&events[2], StreamEvent::Done { content, .. } if content == "Checking."));
+        assert_eq!(request_json(&requests.await.unwrap()[0])["stream"], false);
+    }
+}
@@ -29,15 +29,16 @@
 use async_trait::async_trait;
 use reqwest::{Client, StatusCode};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Mutex;
 use tracing::{debug, info};
 
 use crate::error::{Result, ZeptoError};
-use crate::session::{Message, Role};
+use crate::session::{ContentPart, Message, Role};
 
 use super::{
-    parse_provider_error, ChatOptions, LLMProvider, LLMResponse, LLMToolCall, ToolDefinition, Usage,
+    parse_provider_error, prompt_tools, ChatOptions, HttpRetryPolicy, LLMProvider, LLMResponse,
+    LLMToolCall, ToolCallMode, ToolDefinition, Usage,
 };
 
 /// The OpenAI API endpoint URL.
@@ -82,6 +83,9 @@ struct OpenAIRequest {
     /// Whether to stream the response using SSE
     #[serde(skip_serializing_if = "Option::is_none")]
     stream: Option<bool>,
+    /// Streaming options; used to request a final usage chunk
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream_options: Option<serde_json::Value>,
     /// Response format (e.g., json_object, json_schema)
     #[serde(skip_serializing_if = "Option::is_none")]
     response_format: Option<serde_json::Value>,
@@ -94,7 +98,7 @@ struct OpenAIMessage {
     role: String,
     /// Message content (can be null for assistant with tool_calls)
     #[serde(skip_serializing_if = "Option::is_none")]
-    content: Option<String>,
+    content: Option<OpenAIContent>,
     /// Tool calls made by the assistant
     #[serde(skip_serializing_if = "Option::is_none")]
     tool_calls: Option<Vec<OpenAIToolCallRequest>>,
@@ -103,6 +107,32 @@ struct OpenAIMessage {
     tool_call_id: Option<String>,
 }
 
+/// OpenAI message content - plain text or an array of parts.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+enum OpenAIContent {
+    /// Simple text content
+    Text(String),
+    /// Text and image parts (user messages only)
+    Parts(Vec<OpenAIContentPart>),
+}
+
+/// A part of multimodal message content.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum OpenAIContentPart {
+    /// Text part
+    Text { text: String },
+    /// Image part, as a `data:` URL
+    ImageUrl { image_url: OpenAIImageUrl },
+}
+
+/// Image reference within an `image_url` part.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct OpenAIImageUrl {
+    url: String,
+}
+
 /// A tool call in a request (assistant requesting tool execution).
 #[derive(Debug, Clone, Serialize)]
 struct OpenAIToolCallRequest {
@@ -150,6 +180,9 @@ struct OpenAIFunctionDef {
 /// OpenAI API response body.
 #[derive(Debug, Deserialize)]
 struct OpenAIResponse {
+    /// Model that served the request
+    #[serde(default)]
+    model: Option<String>,
     /// Response choices
     choices: Vec<OpenAIChoice>,
     /// Token usage
@@ -195,6 +228,9 @@ struct OpenAIUsage {
 /// OpenAI streaming chunk response body.
 #[derive(Debug, Deserialize)]
 struct OpenAIStreamChunk {
+    /// Model that served the request
+    #[serde(default)]
+    model: Option<String>,
     /// Delta choices for this chunk
     #[serde(default)]
     choices: Vec<OpenAIStreamChoice>,
@@ -288,8 +324,12 @@ pub struct OpenAIProvider {
     api_base: String,
     /// HTTP client for making requests
     client: Client,
+    /// Retries of transient HTTP failures
+    http_retry: HttpRetryPolicy,
     /// Preferred token field by model to avoid repeated fallback retries
     model_token_fields: Mutex<HashMap<String, MaxTokenField>>,
+    /// Models that rejected native tools and now get prompted tool calls
+    prompted_models: Mutex<HashSet<String>>,
 }
 
 impl OpenAIProvider {
@@ -316,7 +356,9 @@ impl OpenAIProvider {
                 .timeout(std::time::Duration::from_secs(120))
                 .build()
                 .unwrap_or_else(|_| Client::new()),
+            http_retry: HttpRetryPolicy::default(),
             model_token_fields: Mutex::new(HashMap::new()),
+            prompted_models: Mutex::new(HashSet::new()),
         }
     }
 
@@ -342,7 +384,9 @@ impl OpenAIProvider {
                 .timeout(std::time::Duration::from_secs(120))
                 .build()
                 .unwrap_or_else(|_| Client::new()),
+            http_retry: HttpRetryPolicy::default(),
             model_token_fields: Mutex::new(HashMap::new()),
+            prompted_models: Mutex::new(HashSet::new()),
         }
     }
 
@@ -360,10 +404,18 @@ impl OpenAIProvider {
             api_key: api_key.to_string(),
             api_base: api_base.trim_end_matches('/').to_string(),
             client,
+            http_retry: HttpRetryPolicy::default(),
             model_token_fields: Mutex::new(HashMap::new()),
+            prompted_models: Mutex::new(HashSet::new()),
         }
     }
 
+    /// Retry transient HTTP failures (429, 5xx) according to `policy`.
+    pub fn with_http_retry(mut self, policy: HttpRetryPolicy) -> Self {
+        self.http_retry = policy;
+        self
+    }
+
     /// Get the preferred token field for a model, defaulting to `max_tokens`.
     fn token_field_for_model(&self, model: &str) -> MaxTokenField {
         self.model_token_fields
@@ -379,6 +431,59 @@ impl OpenAIProvider {
             fields.insert(model.to_string(), token_field);
         }
     }
+
+    /// Whether tools for `model` should be described in the prompt.
+    fn uses_prompted_tools(&self, model: &str, options: &ChatOptions) -> bool {
+        match options.tool_call_mode {
+            ToolCallMode::Native => false,
+            ToolCallMode::Prompted => true,
+            ToolCallMode::Auto => self
+                .prompted_models
+                .lock()
+                .map(|models| models.contains(model))
+                .unwrap_or(false),
+        }
+    }
+
+    /// Whether a failed native request should be retried with prompted tools.
+    fn should_fall_back_to_prompted(
+        &self,
+        model: &str,
+        tools: &[ToolDefinition],
+        options: &ChatOptions,
+        status: StatusCode,
+        error_text: &str,
+    ) -> bool {
+        if tools.is_empty()
+            || options.tool_call_mode != ToolCallMode::Auto
+            || status != StatusCode::BAD_REQUEST
+            || !prompt_tools::is_tools_unsupported_error(error_text)
+        {
+            return false;
+        }
+        info!(
+            "OpenAI-compatible model '{}' does not support tools; switching to prompted tool calls",
+            model
+        );
+        if let Ok(mut models) = self.prompted_models.lock() {
+            models.insert(model.to_string());
+        }
+        true
+    }
+
+    /// Run a chat with tools described in the prompt.
+    async fn chat_prompted(
+        &self,
+        messages: &[Message],
+        tools: &[ToolDefinition],
+        model: &str,
+        options: &ChatOptions,
+    ) -> Result<LLMResponse> {
+        prompt_tools::chat_prompted(messages, tools, |prompted| {
+            self.chat(prompted, Vec::new(), Some(model), options.clone())
+        })
+        .await
+    }
 }
 
 // ============================================================================
@@ -398,6 +503,19 @@ fn convert_messages(messages: Vec<Message>) -> Vec<OpenAIMessage> {
             }
             .to_string();
 
+            // Only user messages accept image parts; other roles get a
+            // placeholder note per image.
+            let content = if msg.role == Role::User && msg.has_images() {
+                Some(OpenAIContent::Parts(content_parts(msg.parts)))
+            } else {
+                let text = msg.flattened_content();
+                if text.is_empty() && msg.tool_calls.is_some() {
+                    None
+                } else {
+                    Some(OpenAIContent::Text(text))
+                }
+            };
+
             let tool_calls = msg.tool_calls.map(|tcs| {
                 tcs.into_iter()
                     .map(|tc| OpenAIToolCallRequest {
@@ -413,11 +531,7 @@ fn convert_messages(messages: Vec<Message>) -> Vec<OpenAIMessage> {
 
             OpenAIMessage {
                 role,
-                content: if msg.content.is_empty() && tool_calls.is_some() {
-                    None
-                } else {
-                    Some(msg.content)
-                },
+                content,
                 tool_calls,
                 tool_call_id: msg.tool_call_id,
             }
@@ -425,6 +539,21 @@ fn convert_messages(messages: Vec<Message>) -> Vec<OpenAIMessage> {
         .collect()
 }
 
+/// Text and `image_url` parts for multimodal message parts.
+fn content_parts(parts: Vec<ContentPart>) -> Vec<OpenAIContentPart> {
+    parts
+        .into_iter()
+        .map(|part| match part {
+            ContentPart::Text(text) => OpenAIContentPart::Text { text },
+            ContentPart::Image { media_type, base64 } => OpenAIContentPart::ImageUrl {
+                image_url: OpenAIImageUrl {
+                    url: format!("data:{};base64,{}", media_type, base64),
+                },
+            },
+        })
+        .collect()
+}
+
 /// Convert ZeptoClaw tool definitions to OpenAI API format.
 fn convert_tools(tools: Vec<ToolDefinition>) -> Vec<OpenAITool> {
     tools
@@ -476,8 +605,10 @@ fn convert_response(response: OpenAIResponse) -> LLMResponse {
     };
 
     if let Some(usage) = response.usage {
-        llm_response =
-            llm_response.with_usage(Usage::new(usage.prompt_tokens, usage.completion_tokens));
+        llm_response = llm_response.with_usage(
+            Usage::new(usage.prompt_tokens, usage.completion_tokens)
+                .with_model(response.model.as_deref()),
+        );
     }
 
     llm_response
@@ -510,6 +641,7 @@ fn build_request(
         top_p: options.top_p,
         stop: options.stop.clone(),
         stream: None,
+        stream_options: None,
         response_format: options.output_format.to_openai_response_format(),
     }
 }
@@ -521,10 +653,10 @@ fn apply_stream_chunk(
     usage: &mut Option<Usage>,
 ) -> Vec<String> {
     if let Some(chunk_usage) = chunk.usage {
-        *usage = Some(Usage::new(
-            chunk_usage.prompt_tokens,
-            chunk_usage.completion_tokens,
-        ));
+        *usage = Some(
+            Usage::new(chunk_usage.prompt_tokens, chunk_usage.completion_tokens)
+                .with_model(chunk.model.as_deref()),
+        );
     }
 
     let mut deltas = Vec::new();
@@ -606,6 +738,10 @@ impl LLMProvider for OpenAIProvider {
         options: ChatOptions,
     ) -> Result<LLMResponse> {
         let model = model.unwrap_or(DEFAULT_MODEL);
+        if !tools.is_empty() && self.uses_prompted_tools(model, &options) {
+            return self.chat_prompted(&messages, &tools, model, &options).await;
+        }
+
         let mut token_field = self.token_field_for_model(model);
         let mut retried_for_token_field = token_field == MaxTokenField::MaxCompletionTokens;
 
@@ -614,12 +750,14 @@ impl LLMProvider for OpenAIProvider {
             debug!("OpenAI request to model {} with {:?}", model, token_field);
 
             let response = self
-                .client
-                .post(format!("{}/chat/completions", self.api_base))
-                .header("Authorization", format!("Bearer {}", self.api_key))
-                .header("Content-Type", "application/json")
-                .json(&request)
-                .send()
+                .http_retry
+                .send("openai", || {
+                    self.client
+                        .post(format!("{}/chat/completions", self.api_base))
+                        .header("Authorization", format!("Bearer {}", self.api_key))
+                        .header("Content-Type", "application/json")
+                        .json(&request)
+                })
                 .await
                 .map_err(|e| ZeptoError::Provider(format!("OpenAI request failed: {}", e)))?;
 
@@ -652,6 +790,10 @@ impl LLMProvider for OpenAIProvider {
                 continue;
             }
 
+            if self.should_fall_back_to_prompted(model, &tools, &options, status, &error_text) {
+                return self.chat_prompted(&messages, &tools, model, &options).await;
+            }
+
             // Build a human-readable body for the typed error
             let body = if let Ok(error_response) =
                 serde_json::from_str::<OpenAIErrorResponse>(&error_text)
@@ -682,12 +824,22 @@ impl LLMProvider for OpenAIProvider {
         use futures::StreamExt;
 
         let model = model.unwrap_or(DEFAULT_MODEL);
+
+        // Prompted tool calls are parsed from the full reply, so they are not streamed.
+        if !tools.is_empty() && self.uses_prompted_tools(model, &options) {
+            let response = self
+                .chat_prompted(&messages, &tools, model, &options)
+                .await?;
+            return Ok(prompt_tools::replay_response(response));
+        }
+
         let mut token_field = self.token_field_for_model(model);
         let mut retried_for_token_field = token_field == MaxTokenField::MaxCompletionTokens;
 
         loop {
             let mut request = build_request(model, &messages, &tools, &options, token_field);
             request.stream = Some(true);
+            request.stream_options = Some(serde_json::json!({ "include_usage": true }));
 
             debug!(
                 "OpenAI streaming request to model {} with {:?}",
@@ -695,12 +847,14 @@ impl LLMProvider for OpenAIProvider {
             );
 
             let response = self
-                .client
-                .post(format!("{}/chat/completions", self.api_base))
-                .header("Authorization", format!("Bearer {}", self.api_key))
-                .header("Content-Type", "application/json")
-                .json(&request)
-                .send()
+                .http_retry
+                .send("openai", || {
+                    self.client
+                        .post(format!("{}/chat/completions", self.api_base))
+                        .header("Authorization", format!("Bearer {}", self.api_key))
+                        .header("Content-Type", "application/json")
+                        .json(&request)
+                })
                 .await
                 .map_err(|e| ZeptoError::Provider(format!("OpenAI request failed: {}", e)))?;
 
@@ -815,6 +969,13 @@ impl LLMProvider for OpenAIProvider {
                 continue;
             }
 
+            if self.should_fall_back_to_prompted(model, &tools, &options, status, &error_text) {
+                let response = self
+                    .chat_prompted(&messages, &tools, model, &options)
+                    .await?;
+                return Ok(prompt_tools::replay_response(response));
+            }
+
             let body = if let Ok(error_response) =
                 serde_json::from_str::<OpenAIErrorResponse>(&error_text)
             {
@@ -849,6 +1010,7 @@ impl LLMProvider for OpenAIProvider {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::providers::OutputFormat;
     use crate::session::{Message, ToolCall};
 
     #[test]
@@ -902,11 +1064,20 @@ mod tests {
 
         assert_eq!(converted.len(), 3);
         assert_eq!(converted[0].role, "system");
-        assert_eq!(converted[0].content, Some("You are helpful".to_string()));
+        assert_eq!(
+            converted[0].content,
+            Some(OpenAIContent::Text("You are helpful".to_string()))
+        );
         assert_eq!(converted[1].role, "user");
-        assert_eq!(converted[1].content, Some("Hello".to_string()));
+        assert_eq!(
+            converted[1].content,
+            Some(OpenAIContent::Text("Hello".to_string()))
+        );
         assert_eq!(converted[2].role, "assistant");
-        assert_eq!(converted[2].content, Some("Hi there!".to_string()));
+        assert_eq!(
+            converted[2].content,
+            Some(OpenAIContent::Text("Hi there!".to_string()))
+        );
     }
 
     #[test]
@@ -932,7 +1103,10 @@ mod tests {
         // Second message: tool result
         assert_eq!(converted[1].role, "tool");
         assert_eq!(converted[1].tool_call_id, Some("call_1".to_string()));
-        assert_eq!(converted[1].content, Some("Found results".to_string()));
+        assert_eq!(
+            converted[1].content,
+            Some(OpenAIContent::Text("Found results".to_string()))
+        );
     }
 
     #[test]
@@ -949,6 +1123,35 @@ mod tests {
         assert!(converted[0].tool_calls.is_some());
     }
 
+    #[test]
+    fn test_convert_messages_mixed_text_and_image() {
+        let messages = vec![
+            Message::user("What is this?").with_image("image/png", "iVBORw0KGgo="),
+            Message::tool_result("call_1", "Captured").with_image("image/jpeg", "AAAA"),
+        ];
+        let converted = convert_messages(messages);
+
+        let value = serde_json::to_value(&converted[0]).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "role": "user",
+                "content": [
+                    {"type": "text", "text": "What is this?"},
+                    {"type": "image_url", "image_url": {"url": "data:image/png;base64,iVBORw0KGgo="}}
+                ]
+            })
+        );
+
+        // Tool messages only take text
+        assert_eq!(
+            converted[1].content,
+            Some(OpenAIContent::Text(
+                "Captured\n[image: image/jpeg, 3 bytes not shown to this model]".to_string()
+            ))
+        );
+    }
+
     #[test]
     fn test_convert_tools() {
         let tools = vec![ToolDefinition::new(
@@ -972,8 +1175,10 @@ mod tests {
     #[test]
     fn test_convert_response_text_only() {
         let response = OpenAIResponse {
+            model: Some("gpt-4o-2024-08-06".to_string()),
             choices: vec![OpenAIChoice {
                 message: OpenAIResponseMessage {
+                    reasoning: None,
                     content: Some("Hello!".to_string()),
                     tool_calls: None,
                 },
@@ -992,14 +1197,17 @@ mod tests {
         let usage = converted.usage.unwrap();
         assert_eq!(usage.prompt_tokens, 10);
         assert_eq!(usage.completion_tokens, 5);
+        assert_eq!(usage.model.as_deref(), Some("gpt-4o-2024-08-06"));
         assert_eq!(usage.total_tokens, 15);
     }
 
     #[test]
     fn test_convert_response_with_tool_calls() {
         let response = OpenAIResponse {
+            model: None,
             choices: vec![OpenAIChoice {
                 message: OpenAIResponseMessage {
+                    reasoning: None,
                     content: Some("".to_string()),
                     tool_calls: Some(vec![OpenAIToolCallResponse {
                         id: "call_123".to_string(),
@@ -1024,6 +1232,7 @@ mod tests {
     #[test]
     fn test_convert_response_empty_choices() {
         let response = OpenAIResponse {
+            model: None,
             choices: vec![],
             usage: None,
         };
@@ -1036,8 +1245,10 @@ mod tests {
     #[test]
     fn test_convert_response_null_content() {
         let response = OpenAIResponse {
+            model: None,
             choices: vec![OpenAIChoice {
                 message: OpenAIResponseMessage {
+                    reasoning: None,
                     content: None,
                     tool_calls: Some(vec![OpenAIToolCallResponse {
                         id: "call_1".to_string(),
@@ -1063,7 +1274,7 @@ mod tests {
             model: "gpt-5.1".to_string(),
             messages: vec![OpenAIMessage {
                 role: "user".to_string(),
-                content: Some("Hello".to_string()),
+                content: Some(OpenAIContent::Text("Hello".to_string())),
                 tool_calls: None,
                 tool_call_id: None,
             }],
@@ -1074,6 +1285,7 @@ mod tests {
             top_p: None,
             stop: None,
             stream: None,
+            stream_options: None,
             response_format: None,
         };
 
@@ -1109,6 +1321,7 @@ mod tests {
             top_p: None,
             stop: None,
             stream: None,
+            stream_options: None,
             response_format: None,
         };
 
@@ -1123,7 +1336,7 @@ mod tests {
     fn test_openai_message_with_tool_call_id() {
         let msg = OpenAIMessage {
             role: "tool".to_string(),
-            content: Some("Tool result".to_string()),
+            content: Some(OpenAIContent::Text("Tool result".to_string())),
             tool_calls: None,
             tool_call_id: Some("call_123".to_string()),
         };
@@ -1170,6 +1383,35 @@ mod tests {
         assert_eq!(request.max_completion_tokens, None);
     }
 
+    #[test]
+    fn test_build_request_sets_response_format() {
+        let messages = vec![Message::user("Hello")];
+        let build = |format: OutputFormat| {
+            let options = ChatOptions::new().with_output_format(format);
+            let request = build_request(
+                "gpt-5.1",
+                &messages,
+                &[],
+                &options,
+                MaxTokenField::MaxTokens,
+            );
+            serde_json::to_value(&request).unwrap()
+        };
+
+        assert!(build(OutputFormat::Text).get("response_format").is_none());
+        assert_eq!(
+            build(OutputFormat::json())["response_format"],
+            serde_json::json!({"type": "json_object"})
+        );
+
+        let schema = serde_json::json!({"type": "object", "required": ["name"]});
+        let value = build(OutputFormat::json_schema("person", schema.clone()));
+        assert_eq!(value["response_format"]["type"], "json_schema");
+        assert_eq!(value["response_format"]["json_schema"]["name"], "person");
+        assert_eq!(value["response_format"]["json_schema"]["schema"], schema);
+        assert_eq!(value["response_format"]["json_schema"]["strict"], true);
+    }
+
     #[test]
     fn test_build_request_with_max_completion_tokens_field() {
         let messages = vec![Message::user("Hello")];
@@ -1213,6 +1455,7 @@ mod tests {
     #[test]
     fn test_apply_stream_chunk_collects_text_and_usage() {
         let chunk = OpenAIStreamChunk {
+            model: None,
             choices: vec![OpenAIStreamChoice {
                 delta: OpenAIStreamDelta {
                     content: Some("Hello".to_string()),
@@ -1351,6 +1594,7 @@ mod tests {
         let mut usage = None;
 
         let first = OpenAIStreamChunk {
+            model: None,
             choices: vec![OpenAIStreamChoice {
                 delta: OpenAIStreamDelta {
                     content: None,
@@ -1368,6 +1612,7 @@ mod tests {
         };
 
         let second = OpenAIStreamChunk {
+            model: None,
             choices: vec![OpenAIStreamChoice {
                 delta: OpenAIStreamDelta {
                     content: None,
@@ -1393,4 +1638,95 @@ mod tests {
         assert_eq!(tool_calls[0].name, "search");
         assert_eq!(tool_calls[0].arguments, r#"{"q":"rust"}"#);
     }
+
+    fn weather_tool() -> ToolDefinition {
+        ToolDefinition::new(
+            "weather",
+            "Get the weather",
+            serde_json::json!({"type": "object", "properties": {"city": {"type": "string"}}}),
+        )
+    }
+
+    fn tools_unsupported_body() -> String {
+        serde_json::json!({
+            "error": {
+                "message": "Tool calling is not supported with this model",
+                "type": "invalid_request_error"
+            }
+        })
+        .to_string()
+    }
+
+    #[tokio::test]
+    async fn test_auto_mode_falls_back_to_prompted_tools() {
+        use crate::providers::mock_http::{self, request_json, MockResponse};
+
+        let prompted_reply = serde_json::json!({
+            "choices": [{
+                "message": {
+                    "role": "assistant",
+                    "content": "```json\n{\"tool\": \"weather\", \"arguments\": {\"city\": \"Lima\"}}\n```"
+                },
+                "finish_reason": "stop"
+            }]
+        })
+        .to_string();
+        let (base, requests) = mock_http::serve(vec![
+            MockResponse::json(400, tools_unsupported_body()),
+            MockResponse::json(200, prompted_reply.clone()),
+            MockResponse::json(200, prompted_reply),
+        ])
+        .await;
+        let provider = OpenAIProvider::with_base_url("test-key", &base);
+
+        for _ in 0..2 {
+            let response = provider
+                .chat(
+                    vec![Message::user("Weather?")],
+                    vec![weather_tool()],
+                    Some("llama-3.1-8b"),
+                    ChatOptions::default(),
+                )
+                .await
+                .unwrap();
+            assert!(response.content.is_empty());
+            assert_eq!(response.tool_calls.len(), 1);
+            assert_eq!(response.tool_calls[0].name, "weather");
+            assert_eq!(response.tool_calls[0].arguments, r#"{"city":"Lima"}"#);
+        }
+
+        let requests = requests.await.unwrap();
+        assert_eq!(requests.len(), 3);
+        assert!(request_json(&requests[0]).get("tools").is_some());
+        // The model is remembered, so the second call goes straight to prompting.
+        for raw in &requests[1..] {
+            let body = request_json(raw);
+            assert!(body.get("tools").is_none());
+            assert!(body["messages"][0]["content"]
+                .as_str()
+                .unwrap()
+                .contains("- weather: Get the weather"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_native_mode_does_not_fall_back() {
+        use crate::providers::mock_http::{self, MockResponse};
+
+        let (base, requests) =
+            mock_http::serve(vec![MockResponse::json(400, tools_unsupported_body())]).await;
+        let provider = OpenAIProvider::with_base_url("test-key", &base);
+
+        let err = provider
+            .chat(
+                vec![Message::user("Weather?")],
+                vec![weather_tool()],
+                Some("llama-3.1-8b"),
+                ChatOptions::new().with_tool_call_mode(ToolCallMode::Native),
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Tool calling is not supported"));
+        assert_eq!(requests.await.unwrap().len(), 1);
+    }
 }
@@ -0,0 +1,373 @@
+//! Prompt-based tool calling.
+//!
+//! Some models have no native tool-calling support. For them the tool
+//! schemas are described in the system prompt and the model is asked to
+//! reply with a fenced JSON block, which is parsed back into
+//! [`LLMToolCall`]s. Prior tool calls and results in the history are
+//! rewritten as plain text so the model sees the same convention.
+//!
+//! Providers pick this path through [`ToolCallMode`](super::ToolCallMode):
+//! `Prompted` always calls [`chat_prompted`], and `Auto` calls it after a
+//! native request fails with an error matched by
+//! [`is_tools_unsupported_error`].
+
+use std::collections::HashMap;
+use std::future::Future;
+
+use crate::error::Result;
+use crate::session::{Message, Role};
+
+use super::{LLMResponse, LLMToolCall, StreamEvent, ToolDefinition};
+
+/// Phrases providers use when a model rejects the `tools` field.
+const TOOLS_UNSUPPORTED_PHRASES: &[&str] = &[
+    "does not support tool",
+    "does not support function calling",
+    "tool calling is not supported",
+    "tool use is not supported",
+    "tools are not supported",
+    "tools is not supported",
+    "function calling is not supported",
+    "support tool use",
+];
+
+/// Build the system prompt section describing the available tools.
+pub fn tool_instructions(tools: &[ToolDefinition]) -> String {
+    let mut prompt = String::from(
+        "You can call tools. To call one, reply with only a fenced JSON block \
+         and no other text:\n\
+         ```json\n{\"tool\": \"<tool name>\", \"arguments\": {<arguments>}}\n```\n\
+         Use one block per call. After the results come back, answer normally.\n\n\
+         Available tools:",
+    );
+    for tool in tools {
+        prompt.push_str(&format!(
+            "\n- {}: {}\n  parameters: {}",
+            tool.name, tool.description, tool.parameters
+        ));
+    }
+    prompt
+}
+
+/// Rewrite a conversation for a model without native tool calling.
+///
+/// The tool instructions are appended to the leading system message (or
+/// added as one), assistant tool calls become fenced JSON blocks, and tool
+/// results become user messages naming the tool they came from.
+pub fn inject_tools(messages: &[Message], tools: &[ToolDefinition]) -> Vec<Message> {
+    let instructions = tool_instructions(tools);
+    let mut call_names: HashMap<&str, &str> = HashMap::new();
+    let mut out = Vec::with_capacity(messages.len() + 1);
+
+    match messages.first() {
+        Some(first) if first.role == Role::System => {}
+        _ => out.push(Message::system(&instructions)),
+    }
+
+    for (index, msg) in messages.iter().enumerate() {
+        match msg.role {
+            Role::System if index == 0 => {
+                out.push(Message::system(&format!(
+                    "{}\n\n{}",
+                    msg.content, instructions
+                )));
+            }
+            Role::Assistant if msg.has_tool_calls() => {
+                let mut content = msg.content.clone();
+                for call in msg.tool_calls.iter().flatten() {
+                    call_names.insert(&call.id, &call.name);
+                    let arguments: serde_json::Value = serde_json::from_str(&call.arguments)
+                        .unwrap_or_else(|_| serde_json::json!({}));
+                    let block = serde_json::json!({ "tool": call.name, "arguments": arguments });
+                    if !content.is_empty() {
+                        content.push_str("\n\n");
+                    }
+                    content.push_str(&format!("```json\n{}\n```", block));
+                }
+                out.push(Message::assistant(&content));
+            }
+            Role::Tool => {
+                let call_id = msg.tool_call_id.as_deref().unwrap_or_default();
+                let name = call_names.get(call_id).copied().unwrap_or(call_id);
+                out.push(Message::user(&format!(
+                    "Result of tool `{}`:\n{}",
+                    name,
+                    msg.flattened_content()
+                )));
+            }
+            _ => out.push(msg.clone()),
+        }
+    }
+
+    out
+}
+
+/// Extract prompted tool calls from a model reply.
+///
+/// Looks for fenced code blocks (or a reply that is a bare JSON object)
+/// holding `{"tool": ..., "arguments": {...}}` for one of the offered tools.
+/// Matching blocks are removed from the text; everything else, including
+/// JSON that names an unknown tool, is left as prose.
+pub fn parse_tool_calls(text: &str, tools: &[ToolDefinition]) -> (String, Vec<LLMToolCall>) {
+    let mut calls = Vec::new();
+
+    if let Ok(value) = serde_json::from_str::<serde_json::Value>(text.trim()) {
+        if collect_calls(&value, tools, &mut calls) {
+            return (String::new(), calls);
+        }
+    }
+
+    let mut remaining = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("```") {
+        let after_fence = &rest[start + 3..];
+        let Some(end) = after_fence.find("```") else {
+            break;
+        };
+        let block = &after_fence[..end];
+        // Skip an optional language tag on the opening fence line.
+        let body = match block.split_once('\n') {
+            Some((tag, body)) if !tag.trim_start().starts_with('{') => body,
+            _ => block,
+        };
+
+        let is_call = serde_json::from_str::<serde_json::Value>(body.trim())
+            .map(|value| collect_calls(&value, tools, &mut calls))
+            .unwrap_or(false);
+        if is_call {
+            remaining.push_str(&rest[..start]);
+        } else {
+            remaining.push_str(&rest[..start + 3 + end + 3]);
+        }
+        rest = &after_fence[end + 3..];
+    }
+    remaining.push_str(rest);
+
+    if calls.is_empty() {
+        (text.to_string(), calls)
+    } else {
+        (remaining.trim().to_string(), calls)
+    }
+}
+
+/// Run one chat round with tools described in the prompt.
+///
+/// `chat` sends the rewritten conversation without native tools; fenced
+/// tool calls in its reply are moved into `tool_calls`.
+pub async fn chat_prompted<F, Fut>(
+    messages: &[Message],
+    tools: &[ToolDefinition],
+    chat: F,
+) -> Result<LLMResponse>
+where
+    F: FnOnce(Vec<Message>) -> Fut,
+    Fut: Future<Output = Result<LLMResponse>>,
+{
+    let mut response = chat(inject_tools(messages, tools)).await?;
+    let (content, calls) = parse_tool_calls(&response.content, tools);
+    response.content = content;
+    response.tool_calls.extend(calls);
+    Ok(response)
+}
+
+/// Whether a provider error says the model cannot take a `tools` field.
+pub fn is_tools_unsupported_error(message: &str) -> bool {
+    let message = message.to_lowercase();
+    TOOLS_UNSUPPORTED_PHRASES
+        .iter()
+        .any(|phrase| message.contains(phrase))
+}
+
+/// Emit a complete response as stream events.
+///
+/// Prompted tool calls are parsed from the full reply, so streaming
+/// providers answer prompted requests with a replayed response.
+pub fn replay_response(response: LLMResponse) -> tokio::sync::mpsc::Receiver<StreamEvent> {
+    let (tx, rx) = tokio::sync::mpsc::channel::<StreamEvent>(4);
+    tokio::spawn(async move {
+        if !response.content.is_empty() {
+            let _ = tx.send(StreamEvent::Delta(response.content.clone())).await;
+        }
+        if !response.tool_calls.is_empty() {
+            let _ = tx.send(StreamEvent::ToolCalls(response.tool_calls)).await;
+        }
+        let _ = tx
+            .send(StreamEvent::Done {
+                content: response.content,
+                usage: response.usage,
+            })
+            .await;
+    });
+    rx
+}
+
+/// Push every valid call in `value` (an object or array of objects).
+///
+/// Returns `false` without pushing anything if any entry is not a call to a
+/// known tool, so partially matching blocks stay in the prose.
+fn collect_calls(
+    value: &serde_json::Value,
+    tools: &[ToolDefinition],
+    calls: &mut Vec<LLMToolCall>,
+) -> bool {
+    let entries = match value {
+        serde_json::Value::Array(items) if !items.is_empty() => items.iter().collect(),
+        serde_json::Value::Object(_) => vec![value],
+        _ => return false,
+    };
+
+    let mut parsed = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let Some(name) = entry
+            .get("tool")
+            .or_else(|| entry.get("name"))
+            .and_then(|n| n.as_str())
+        else {
+            return false;
+        };
+        if !tools.iter().any(|tool| tool.name == name) {
+            return false;
+        }
+        let arguments = match entry.get("arguments") {
+            Some(args @ serde_json::Value::Object(_)) => args.to_string(),
+            None | Some(serde_json::Value::Null) => "{}".to_string(),
+            Some(_) => return false,
+        };
+        let id = format!("call_{}", uuid::Uuid::new_v4().simple());
+        parsed.push(LLMToolCall::new(&id, name, &arguments));
+    }
+
+    calls.extend(parsed);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::ToolCall;
+    use serde_json::json;
+
+    fn tools() -> Vec<ToolDefinition> {
+        vec![ToolDefinition::new(
+            "weather",
+            "Get the weather",
+            json!({"type": "object", "properties": {"city": {"type": "string"}}}),
+        )]
+    }
+
+    #[test]
+    fn test_parse_fenced_tool_call() {
+        let text = "Let me check.\n```json\n{\"tool\": \"weather\", \"arguments\": {\"city\": \"Paris\"}}\n```";
+        let (content, calls) = parse_tool_calls(text, &tools());
+        assert_eq!(content, "Let me check.");
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].name, "weather");
+        assert!(calls[0].id.starts_with("call_"));
+        let args: serde_json::Value = serde_json::from_str(&calls[0].arguments).unwrap();
+        assert_eq!(args["city"], "Paris");
+    }
+
+    #[test]
+    fn test_parse_bare_json_and_arrays() {
+        let (content, calls) = parse_tool_calls(
+            r#"{"name": "weather", "arguments": {"city": "Rome"}}"#,
+            &tools(),
+        );
+        assert!(content.is_empty());
+        assert_eq!(calls.len(), 1);
+
+        let text = "```\n[{\"tool\": \"weather\", \"arguments\": {\"city\": \"A\"}}, {\"tool\": \"weather\"}]\n```";
+        let (_, calls) = parse_tool_calls(text, &tools());
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[1].arguments, "{}");
+    }
+
+    #[test]
+    fn test_parse_ignores_prose_and_unknown_tools() {
+        let prose = "Here is an example config:\n```json\n{\"city\": \"Paris\"}\n```\nDone.";
+        let (content, calls) = parse_tool_calls(prose, &tools());
+        assert!(calls.is_empty());
+        assert_eq!(content, prose);
+
+        let unknown = "```json\n{\"tool\": \"rm_rf\", \"arguments\": {}}\n```";
+        let (content, calls) = parse_tool_calls(unknown, &tools());
+        assert!(calls.is_empty());
+        assert_eq!(content, unknown);
+
+        let (content, calls) = parse_tool_calls("The weather is sunny.", &tools());
+        assert!(calls.is_empty());
+        assert_eq!(content, "The weather is sunny.");
+    }
+
+    #[test]
+    fn test_inject_tools_rewrites_history() {
+        let messages = vec![
+            Message::system("Be brief."),
+            Message::user("Weather?"),
+            Message::assistant_with_tools(
+                "",
+                vec![ToolCall::new("c1", "weather", r#"{"city":"Paris"}"#)],
+            ),
+            Message::tool_result("c1", "Sunny"),
+        ];
+        let out = inject_tools(&messages, &tools());
+
+        assert_eq!(out.len(), 4);
+        assert!(out[0]
+            .content
+            .starts_with("Be brief.\n\nYou can call tools."));
+        assert!(out[0].content.contains("- weather: Get the weather"));
+        assert!(!out[2].has_tool_calls());
+        assert!(out[2].content.contains(r#""tool":"weather""#));
+        assert_eq!(out[3].role, Role::User);
+        assert_eq!(out[3].content, "Result of tool `weather`:\nSunny");
+    }
+
+    #[test]
+    fn test_inject_tools_adds_system_message() {
+        let out = inject_tools(&[Message::user("Hi")], &tools());
+        assert_eq!(out.len(), 2);
+        assert_eq!(out[0].role, Role::System);
+        assert_eq!(out[1].content, "Hi");
+    }
+
+    #[tokio::test]
+    async fn test_chat_prompted_injects_and_parses() {
+        let response = chat_prompted(
+            &[Message::user("Weather in Oslo?")],
+            &tools(),
+            |sent| async move {
+                assert_eq!(sent[0].role, Role::System);
+                assert!(sent[0].content.contains("- weather: Get the weather"));
+                Ok(LLMResponse::text(
+                    "```json\n{\"tool\": \"weather\", \"arguments\": {\"city\": \"Oslo\"}}\n```",
+                ))
+            },
+        )
+        .await
+        .unwrap();
+
+        assert!(response.content.is_empty());
+        assert_eq!(response.tool_calls.len(), 1);
+        assert_eq!(response.tool_calls[0].name, "weather");
+    }
+
+    #[test]
+    fn test_is_tools_unsupported_error() {
+        assert!(is_tools_unsupported_error(
+            "registry.ollama.ai/library/gemma:2b does not support tools"
+        ));
+        assert!(is_tools_unsupported_error(
+            r#"{"error":{"message":"Tool calling is not supported with this model"}}"#
+        ));
+        assert!(is_tools_unsupported_error(
+            "No endpoints found that support tool use."
+        ));
+        assert!(!is_tools_unsupported_error(
+            "Invalid value for 'temperature'"
+        ));
+        assert!(!is_tools_unsupported_error(
+            "Unsupported parameter: 'max_tokens'"
+        ));
+    }
+}
@@ -4,7 +4,7 @@
 //! to runtime provider selection.
 
 use crate::auth::{AuthMethod, ResolvedCredential};
-use crate::config::{Config, ProviderConfig};
+use crate::config::{Config, HttpRetryConfig, ProviderConfig};
 
 /// Metadata describing an LLM provider.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -17,8 +17,10 @@ pub struct ProviderSpec {
     pub runtime_supported: bool,
     /// Default API base URL (None = native OpenAI endpoint).
     pub default_base_url: Option<&'static str>,
-    /// The underlying backend ("anthropic" or "openai") for routing.
+    /// The underlying backend ("anthropic", "openai", "gemini" or "ollama") for routing.
     pub backend: &'static str,
+    /// Whether an API key is required (local servers can run without one).
+    pub requires_api_key: bool,
 }
 
 /// Runtime-ready provider selection.
@@ -30,10 +32,12 @@ pub struct RuntimeProviderSelection {
     pub api_key: String,
     /// Optional provider base URL.
     pub api_base: Option<String>,
-    /// The underlying backend type ("anthropic" or "openai").
+    /// The underlying backend type ("anthropic", "openai", "gemini" or "ollama").
     pub backend: &'static str,
     /// Resolved credential (OAuth token or API key).
     pub credential: ResolvedCredential,
+    /// In-client retry settings for transient HTTP failures.
+    pub http_retry: HttpRetryConfig,
 }
 
 /// Provider registry in priority order.
@@ -46,6 +50,7 @@ pub const PROVIDER_REGISTRY: &[ProviderSpec] = &[
         runtime_supported: true,
         default_base_url: None,
         backend: "anthropic",
+        requires_api_key: true,
     },
     ProviderSpec {
         name: "openai",
@@ -53,6 +58,7 @@ pub const PROVIDER_REGISTRY: &[ProviderSpec] = &[
         runtime_supported: true,
         default_base_url: None,
         backend: "openai",
+        requires_api_key: true,
     },
     ProviderSpec {
         name: "openrouter",
@@ -60,6 +66,7 @@ pub const PROVIDER_REGISTRY: &[ProviderSpec] = &[
         runtime_supported: true,
         default_base_url: Some("https://openrouter.ai/api/v1"),
         backend: "openai",
+        requires_api_key: true,
     },
     ProviderSpec {
         name: "groq",
@@ -67,6 +74,7 @@ pub const PROVIDER_REGISTRY: &[ProviderSpec] = &[
         runtime_supported: true,
         default_base_url: Some("https://api.groq.com/openai/v1"),
         backend: "openai",
+        requires_api_key: true,
     },
     ProviderSpec {
         name: "zhipu",
@@ -74,6 +82,7 @@ pub const PROVIDER_REGISTRY: &[ProviderSpec] = &[
         runtime_supported: true,
         default_base_url: Some("https://open.bigmodel.cn/api/paas/v4"),
         backend: "openai",
+        requires_api_key: true,
     },
     ProviderSpec {
         name: "vllm",
@@ -81,20 +90,23 @@ pub const PROVIDER_REGISTRY: &[ProviderSpec] = &[
         runtime_supported: true,
         default_base_url: Some("http://localhost:8000/v1"),
         backend: "openai",
+        requires_api_key: true,
     },
     ProviderSpec {
         name: "gemini",
         model_keywords: &["gemini"],
         runtime_supported: true,
-        default_base_url: Some("https://generativelanguage.googleapis.com/v1beta/openai"),
-        backend: "openai",
+        default_base_url: Some("https://generativelanguage.googleapis.com/v1beta"),
+        backend: "gemini",
+        requires_api_key: true,
     },
     ProviderSpec {
         name: "ollama",
         model_keywords: &["ollama", "llama", "mistral", "phi", "qwen"],
         runtime_supported: true,
-        default_base_url: Some("http://localhost:11434/v1"),
-        backend: "openai",
+        default_base_url: Some("http://localhost:11434"),
+        backend: "ollama",
+        requires_api_key: false,
     },
     ProviderSpec {
         name: "nvidia",
@@ -102,6 +114,7 @@ pub const PROVIDER_REGISTRY: &[ProviderSpec] = &[
         runtime_supported: true,
         default_base_url: Some("https://integrate.api.nvidia.com/v1"),
         backend: "openai",
+        requires_api_key: true,
     },
 ];
 
@@ -120,19 +133,46 @@ pub fn provider_config_by_name<'a>(config: &'a Config, name: &str) -> Option<&'a
     }
 }
 
+/// Mutable access to a provider's config slot by provider id.
+///
+/// Returns `None` for unknown provider ids.
+pub fn provider_config_slot_mut<'a>(
+    config: &'a mut Config,
+    name: &str,
+) -> Option<&'a mut Option<ProviderConfig>> {
+    match name {
+        "anthropic" => Some(&mut config.providers.anthropic),
+        "openai" => Some(&mut config.providers.openai),
+        "openrouter" => Some(&mut config.providers.openrouter),
+        "groq" => Some(&mut config.providers.groq),
+        "zhipu" => Some(&mut config.providers.zhipu),
+        "vllm" => Some(&mut config.providers.vllm),
+        "gemini" => Some(&mut config.providers.gemini),
+        "ollama" => Some(&mut config.providers.ollama),
+        "nvidia" => Some(&mut config.providers.nvidia),
+        _ => None,
+    }
+}
+
 fn configured_api_key(provider: Option<&ProviderConfig>) -> Option<&str> {
     provider
         .and_then(|p| p.api_key.as_deref())
         .and_then(|k| if k.is_empty() { None } else { Some(k) })
 }
 
+/// Whether a provider has enough config to be used.
+///
+/// Providers that need no API key count as configured once their section exists.
+fn is_configured(spec: &ProviderSpec, provider: Option<&ProviderConfig>) -> bool {
+    configured_api_key(provider).is_some() || (!spec.requires_api_key && provider.is_some())
+}
+
 /// Returns all configured provider ids in registry order.
 pub fn configured_provider_names(config: &Config) -> Vec<&'static str> {
     PROVIDER_REGISTRY
         .iter()
-        .filter_map(|spec| {
-            configured_api_key(provider_config_by_name(config, spec.name)).map(|_| spec.name)
-        })
+        .filter(|spec| is_configured(spec, provider_config_by_name(config, spec.name)))
+        .map(|spec| spec.name)
         .collect()
 }
 
@@ -184,6 +224,9 @@ pub fn resolve_runtime_providers(config: &Config) -> Vec<RuntimeProviderSelectio
         let (credential, api_key_str) =
             match resolve_credential(spec.name, &auth_method, provider, token_store.as_ref()) {
                 Some(pair) => pair,
+                None if is_configured(spec, provider) => {
+                    (ResolvedCredential::ApiKey(String::new()), String::new())
+                }
                 None => continue, // No credential available for this provider
             };
 
@@ -202,6 +245,7 @@ pub fn resolve_runtime_providers(config: &Config) -> Vec<RuntimeProviderSelectio
             api_base,
             backend: spec.backend,
             credential,
+            http_retry: provider.map(|p| p.http_retry.clone()).unwrap_or_default(),
         });
     }
 
@@ -281,6 +325,41 @@ mod tests {
         assert_eq!(names, vec!["anthropic", "openai"]);
     }
 
+    #[test]
+    fn test_runtime_selection_carries_http_retry_config() {
+        let config: Config = serde_json::from_str(
+            r#"{"providers": {
+                "anthropic": {"api_key": "sk-ant", "http_retry": {"enabled": false}},
+                "openai": {"api_key": "sk-openai"}
+            }}"#,
+        )
+        .unwrap();
+
+        let selections = resolve_runtime_providers(&config);
+        assert_eq!(selections[0].name, "anthropic");
+        assert!(!selections[0].http_retry.enabled);
+        assert_eq!(selections[0].http_retry.max_retries, 2);
+        assert_eq!(selections[1].http_retry, HttpRetryConfig::default());
+        assert!(selections[1].http_retry.enabled);
+    }
+
+    #[test]
+    fn test_provider_config_slot_mut_covers_registry() {
+        let mut config = Config::default();
+        for spec in PROVIDER_REGISTRY {
+            let slot = provider_config_slot_mut(&mut config, spec.name).unwrap();
+            slot.get_or_insert_with(Default::default).api_key = Some(format!("key-{}", spec.name));
+        }
+        assert!(provider_config_slot_mut(&mut config, "unknown").is_none());
+
+        let names = configured_provider_names(&config);
+        assert_eq!(names.len(), PROVIDER_REGISTRY.len());
+        assert_eq!(
+            provider_config_by_name(&config, "gemini").and_then(|p| p.api_key.as_deref()),
+            Some("key-gemini")
+        );
+    }
+
     #[test]
     fn test_configured_unsupported_provider_names_empty_when_all_supported() {
         let mut config = Config::default();
@@ -391,11 +470,25 @@ mod tests {
 
         let selected = resolve_runtime_provider(&config).expect("provider should resolve");
         assert_eq!(selected.name, "ollama");
-        assert_eq!(selected.backend, "openai");
-        assert_eq!(
-            selected.api_base.as_deref(),
-            Some("http://localhost:11434/v1")
-        );
+        assert_eq!(selected.backend, "ollama");
+        assert_eq!(selected.api_base.as_deref(), Some("http://localhost:11434"));
+    }
+
+    #[test]
+    fn test_ollama_resolves_without_api_key() {
+        let mut config = Config::default();
+        config.providers.ollama = Some(ProviderConfig::default());
+
+        let selected = resolve_runtime_provider(&config).expect("provider should resolve");
+        assert_eq!(selected.name, "ollama");
+        assert!(selected.api_key.is_empty());
+        assert_eq!(configured_provider_names(&config), vec!["ollama"]);
+
+        // Providers that need a key still require one.
+        let mut config = Config::default();
+        config.providers.groq = Some(ProviderConfig::default());
+        assert!(resolve_runtime_provider(&config).is_none());
+        assert!(configured_provider_names(&config).is_empty());
     }
 
     #[test]
@@ -408,7 +501,7 @@ mod tests {
 
         let selected = resolve_runtime_provider(&config).expect("provider should resolve");
         assert_eq!(selected.name, "gemini");
-        assert_eq!(selected.backend, "openai");
+        assert_eq!(selected.backend, "gemini");
         assert!(selected
             .api_base
             .as_deref()
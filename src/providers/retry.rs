@@ -214,6 +214,10 @@ impl LLMProvider for RetryProvider {
         self.inner.default_model()
     }
 
+    fn health_snapshot(&self) -> Vec<super::ProviderHealthStatus> {
+        self.inner.health_snapshot()
+    }
+
     async fn chat(
         &self,
         messages: Vec<Message>,
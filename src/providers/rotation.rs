@@ -3,7 +3,7 @@
 //! This module provides a [`RotationProvider`] that manages N LLM providers
 //! with health-aware selection. Unlike [`FallbackProvider`] which chains exactly
 //! two providers, `RotationProvider` supports 3+ providers with configurable
-//! rotation strategies (Priority or RoundRobin).
+//! rotation strategies (Priority, RoundRobin or Weighted).
 //!
 //! # Example
 //!
@@ -22,7 +22,7 @@
 //! ```
 
 use std::fmt;
-use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use async_trait::async_trait;
@@ -47,6 +47,9 @@ pub enum RotationStrategy {
     Priority,
     /// Round-robin across healthy providers.
     RoundRobin,
+    /// Split traffic across healthy providers in proportion to a weight per
+    /// provider (same order as the providers), e.g. `[7, 2, 1]` for ~70/20/10.
+    Weighted(Vec<u32>),
 }
 
 // ============================================================================
@@ -63,6 +66,30 @@ struct ProviderHealth {
     failure_threshold: u32,
     /// Seconds before retrying an unhealthy provider.
     cooldown_secs: u64,
+    /// Set while a half-open probe request is running against this provider.
+    probe_in_flight: AtomicBool,
+}
+
+/// Whether a provider may take a request right now.
+enum Admission<'a> {
+    /// Provider is healthy (closed circuit).
+    Admitted,
+    /// Cooldown elapsed and this caller won the single half-open probe slot.
+    Probe(ProbeGuard<'a>),
+    /// Still cooling down, or another request is already probing it.
+    Rejected,
+}
+
+/// Releases the half-open probe slot when the probe request finishes
+/// (or is dropped mid-flight).
+struct ProbeGuard<'a> {
+    health: &'a ProviderHealth,
+}
+
+impl Drop for ProbeGuard<'_> {
+    fn drop(&mut self) {
+        self.health.probe_in_flight.store(false, Ordering::Release);
+    }
 }
 
 impl ProviderHealth {
@@ -73,18 +100,17 @@ impl ProviderHealth {
             last_failure_epoch: AtomicU64::new(0),
             failure_threshold,
             cooldown_secs,
+            probe_in_flight: AtomicBool::new(false),
         }
     }
 
-    /// Returns `true` if this provider is considered healthy (below failure threshold
-    /// or cooldown has elapsed and it should be probed).
-    fn is_healthy(&self) -> bool {
-        let failures = self.failure_count.load(Ordering::Relaxed);
-        if failures < self.failure_threshold {
-            return true;
-        }
+    /// Returns `true` if the failure threshold has been reached.
+    fn is_tripped(&self) -> bool {
+        self.failure_count.load(Ordering::Relaxed) >= self.failure_threshold
+    }
 
-        // Check if cooldown has elapsed (half-open equivalent).
+    /// Returns `true` if the cooldown since the last failure has elapsed.
+    fn cooldown_elapsed(&self) -> bool {
         let last_failure = self.last_failure_epoch.load(Ordering::Relaxed);
         let now = SystemTime::now()
             .duration_since(UNIX_EPOCH)
@@ -93,6 +119,39 @@ impl ProviderHealth {
         now.saturating_sub(last_failure) >= self.cooldown_secs
     }
 
+    /// Returns `true` if this provider is considered healthy (below failure threshold,
+    /// or cooldown has elapsed and no probe request is already in flight).
+    ///
+    /// Read-only; use [`admit`](Self::admit) to actually claim the probe slot.
+    fn is_healthy(&self) -> bool {
+        if !self.is_tripped() {
+            return true;
+        }
+        self.cooldown_elapsed() && !self.probe_in_flight.load(Ordering::Acquire)
+    }
+
+    /// Decide whether a request may be sent to this provider.
+    ///
+    /// After the cooldown only one caller is let through as a half-open probe;
+    /// concurrent callers are rejected until that probe finishes.
+    fn admit(&self) -> Admission<'_> {
+        if !self.is_tripped() {
+            return Admission::Admitted;
+        }
+        if !self.cooldown_elapsed() {
+            return Admission::Rejected;
+        }
+        match self.probe_in_flight.compare_exchange(
+            false,
+            true,
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        ) {
+            Ok(_) => Admission::Probe(ProbeGuard { health: self }),
+            Err(_) => Admission::Rejected,
+        }
+    }
+
     /// Record a successful request -- resets the failure counter.
     fn record_success(&self) {
         let prev = self.failure_count.swap(0, Ordering::Relaxed);
@@ -105,7 +164,8 @@ impl ProviderHealth {
     }
 
     /// Record a failed request -- increments the failure counter and updates
-    /// the last-failure timestamp.
+    /// the last-failure timestamp (so a failed half-open probe restarts the
+    /// cooldown).
     fn record_failure(&self) {
         let prev = self.failure_count.fetch_add(1, Ordering::Relaxed);
         let now = SystemTime::now()
@@ -129,27 +189,51 @@ impl fmt::Debug for ProviderHealth {
             .field("failure_count", &self.failure_count.load(Ordering::Relaxed))
             .field("failure_threshold", &self.failure_threshold)
             .field("cooldown_secs", &self.cooldown_secs)
+            .field(
+                "probe_in_flight",
+                &self.probe_in_flight.load(Ordering::Relaxed),
+            )
             .field("is_healthy", &self.is_healthy())
             .finish()
     }
 }
 
+/// Point-in-time health of one provider in a [`RotationProvider`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProviderHealthStatus {
+    /// Provider name.
+    pub name: String,
+    /// Consecutive failure count.
+    pub failure_count: u32,
+    /// Timestamp (epoch secs) of last failure, 0 if it never failed.
+    pub last_failure_epoch: u64,
+    /// Whether the provider is currently eligible for selection.
+    pub healthy: bool,
+}
+
 // ============================================================================
 // RotationProvider
 // ============================================================================
 
 /// A provider that rotates across multiple LLM providers with health tracking.
 ///
-/// Supports two strategies:
+/// Supports three strategies:
 /// - **Priority**: iterate providers in order, skip unhealthy ones, use first healthy.
 /// - **RoundRobin**: advance index atomically, skip unhealthy, wrap around.
+/// - **Weighted**: advance a counter modulo the total weight of the healthy
+///   providers and pick the provider whose weight range contains it.
+///
+/// Once an unhealthy provider's cooldown elapses it is half-open: a single
+/// probe request is let through while concurrent requests go to other
+/// providers. A successful probe resets its health; a failed probe restarts
+/// the cooldown.
 ///
 /// When ALL providers are unhealthy, falls back to the one that has been
 /// unhealthy the longest (most likely to have recovered).
 pub struct RotationProvider {
     providers: Vec<(Box<dyn LLMProvider>, ProviderHealth)>,
     strategy: RotationStrategy,
-    /// Atomic counter for round-robin (wraps around).
+    /// Atomic counter for round-robin and weighted selection (wraps around).
     round_robin_index: AtomicU32,
     /// Pre-computed composite name.
     composite_name: String,
@@ -170,12 +254,13 @@ impl RotationProvider {
     ///
     /// # Arguments
     /// * `providers` - The LLM providers to rotate across (order matters for Priority).
-    /// * `strategy` - Rotation strategy (Priority or RoundRobin).
+    /// * `strategy` - Rotation strategy (Priority, RoundRobin or Weighted).
     /// * `failure_threshold` - Consecutive failures before marking a provider unhealthy.
     /// * `cooldown_secs` - Seconds to wait before retrying an unhealthy provider.
     ///
     /// # Panics
-    /// Panics if `providers` is empty.
+    /// Panics if `providers` is empty, or if a `Weighted` strategy does not
+    /// carry exactly one weight per provider or has no non-zero weight.
     pub fn new(
         providers: Vec<Box<dyn LLMProvider>>,
         strategy: RotationStrategy,
@@ -186,6 +271,20 @@ impl RotationProvider {
             !providers.is_empty(),
             "RotationProvider requires at least one provider"
         );
+        if let RotationStrategy::Weighted(weights) = &strategy {
+            assert_eq!(
+                weights.len(),
+                providers.len(),
+                "RotationProvider weighted strategy requires one weight per provider \
+                 ({} weights for {} providers)",
+                weights.len(),
+                providers.len()
+            );
+            assert!(
+                weights.iter().any(|&w| w > 0),
+                "RotationProvider weighted strategy requires at least one non-zero weight"
+            );
+        }
 
         let names: Vec<&str> = providers.iter().map(|p| p.name()).collect();
         let composite_name = format!("rotation({})", names.join(", "));
@@ -212,7 +311,7 @@ impl RotationProvider {
     fn select_provider_index(&self) -> usize {
         let len = self.providers.len();
 
-        match self.strategy {
+        match &self.strategy {
             RotationStrategy::Priority => {
                 // Try providers in order, skip unhealthy ones.
                 for i in 0..len {
@@ -235,9 +334,54 @@ impl RotationProvider {
                 // All unhealthy: use the one with the oldest last_failure.
                 self.oldest_unhealthy_index()
             }
+            RotationStrategy::Weighted(weights) => {
+                let healthy: Vec<usize> = (0..len)
+                    .filter(|&i| self.providers[i].1.is_healthy())
+                    .collect();
+                let total: u64 = healthy.iter().map(|&i| u64::from(weights[i])).sum();
+                if total == 0 {
+                    // Only zero-weight providers are healthy: treat them as
+                    // standbys. All unhealthy: oldest last_failure.
+                    return healthy
+                        .first()
+                        .copied()
+                        .unwrap_or_else(|| self.oldest_unhealthy_index());
+                }
+
+                let mut slot =
+                    u64::from(self.round_robin_index.fetch_add(1, Ordering::Relaxed)) % total;
+                for &i in &healthy {
+                    let weight = u64::from(weights[i]);
+                    if slot < weight {
+                        return i;
+                    }
+                    slot -= weight;
+                }
+                unreachable!("weighted slot is always below the healthy weight total")
+            }
         }
     }
 
+    /// Returns `true` if at least one provider is currently healthy.
+    fn any_healthy(&self) -> bool {
+        self.providers.iter().any(|(_, h)| h.is_healthy())
+    }
+
+    /// Snapshot the health of every provider, in configured order.
+    ///
+    /// Only reads the health counters; it never changes selection state.
+    pub fn health_snapshot(&self) -> Vec<ProviderHealthStatus> {
+        self.providers
+            .iter()
+            .map(|(provider, health)| ProviderHealthStatus {
+                name: provider.name().to_string(),
+                failure_count: health.failure_count.load(Ordering::Relaxed),
+                last_failure_epoch: health.last_failure_epoch.load(Ordering::Relaxed),
+                healthy: health.is_healthy(),
+            })
+            .collect()
+    }
+
     /// Find the provider with the oldest last_failure_epoch (most likely to have recovered).
     fn oldest_unhealthy_index(&self) -> usize {
         self.providers
@@ -247,14 +391,6 @@ impl RotationProvider {
             .map(|(i, _)| i)
             .unwrap_or(0)
     }
-
-    /// Determine whether an error should trigger rotation to the next provider.
-    fn should_rotate(err: &crate::error::ZeptoError) -> bool {
-        match err {
-            crate::error::ZeptoError::ProviderTyped(pe) => pe.should_fallback(),
-            _ => true, // Legacy errors always rotate
-        }
-    }
 }
 
 #[async_trait]
@@ -267,6 +403,10 @@ impl LLMProvider for RotationProvider {
         self.providers[0].0.default_model()
     }
 
+    fn health_snapshot(&self) -> Vec<ProviderHealthStatus> {
+        RotationProvider::health_snapshot(self)
+    }
+
     async fn chat(
         &self,
         messages: Vec<Message>,
@@ -283,10 +423,14 @@ impl LLMProvider for RotationProvider {
             let i = (start_index + offset) % len;
             let (provider, health) = &self.providers[i];
 
-            // Skip unhealthy providers (except the start index which was already selected).
-            if offset > 0 && !health.is_healthy() {
-                continue;
-            }
+            let _probe = match health.admit() {
+                Admission::Admitted => None,
+                Admission::Probe(guard) => Some(guard),
+                // Cooling down or already being probed: skip it, unless it is
+                // the last-resort pick because no provider is healthy.
+                Admission::Rejected if offset > 0 || self.any_healthy() => continue,
+                Admission::Rejected => None,
+            };
 
             match provider
                 .chat(messages.clone(), tools.clone(), model, options.clone())
@@ -297,7 +441,7 @@ impl LLMProvider for RotationProvider {
                     return Ok(response);
                 }
                 Err(err) => {
-                    if Self::should_rotate(&err) {
+                    if super::should_fallback(&err) {
                         health.record_failure();
                         warn!(
                             provider = provider.name(),
@@ -340,9 +484,14 @@ impl LLMProvider for RotationProvider {
             let i = (start_index + offset) % len;
             let (provider, health) = &self.providers[i];
 
-            if offset > 0 && !health.is_healthy() {
-                continue;
-            }
+            let _probe = match health.admit() {
+                Admission::Admitted => None,
+                Admission::Probe(guard) => Some(guard),
+                // Cooling down or already being probed: skip it, unless it is
+                // the last-resort pick because no provider is healthy.
+                Admission::Rejected if offset > 0 || self.any_healthy() => continue,
+                Admission::Rejected => None,
+            };
 
             match provider
                 .chat_stream(messages.clone(), tools.clone(), model, options.clone())
@@ -353,7 +502,7 @@ impl LLMProvider for RotationProvider {
                     return Ok(receiver);
                 }
                 Err(err) => {
-                    if Self::should_rotate(&err) {
+                    if super::should_fallback(&err) {
                         health.record_failure();
                         warn!(
                             provider = provider.name(),
@@ -679,6 +828,128 @@ mod tests {
         assert_eq!(calls_c.load(Ordering::SeqCst), 1);
     }
 
+    fn counting_providers(counts: &[Arc<AtomicU32>]) -> Vec<Box<dyn LLMProvider>> {
+        const NAMES: [&str; 3] = ["alpha", "beta", "gamma"];
+        counts
+            .iter()
+            .zip(NAMES)
+            .map(|(count, name)| {
+                Box::new(CountingProvider {
+                    name,
+                    call_count: Arc::clone(count),
+                }) as Box<dyn LLMProvider>
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_rotation_weighted_splits_traffic() {
+        let counts: Vec<Arc<AtomicU32>> = (0..3).map(|_| Arc::new(AtomicU32::new(0))).collect();
+        let provider = RotationProvider::new(
+            counting_providers(&counts),
+            RotationStrategy::Weighted(vec![7, 2, 1]),
+            3,
+            30,
+        );
+
+        for _ in 0..100 {
+            provider
+                .chat(vec![], vec![], None, ChatOptions::default())
+                .await
+                .expect("should succeed");
+        }
+
+        let calls: Vec<u32> = counts.iter().map(|c| c.load(Ordering::SeqCst)).collect();
+        assert_eq!(calls, vec![70, 20, 10]);
+    }
+
+    #[tokio::test]
+    async fn test_rotation_weighted_skips_unhealthy() {
+        let counts: Vec<Arc<AtomicU32>> = (0..3).map(|_| Arc::new(AtomicU32::new(0))).collect();
+        let provider = RotationProvider::new(
+            counting_providers(&counts),
+            RotationStrategy::Weighted(vec![7, 2, 1]),
+            1,
+            30,
+        );
+        // Trip alpha (threshold 1): its share is redistributed 2:1.
+        provider.providers[0].1.record_failure();
+
+        for _ in 0..30 {
+            provider
+                .chat(vec![], vec![], None, ChatOptions::default())
+                .await
+                .expect("should succeed");
+        }
+
+        let calls: Vec<u32> = counts.iter().map(|c| c.load(Ordering::SeqCst)).collect();
+        assert_eq!(calls, vec![0, 20, 10]);
+    }
+
+    #[test]
+    fn test_rotation_weighted_all_unhealthy_uses_oldest() {
+        let counts: Vec<Arc<AtomicU32>> = (0..3).map(|_| Arc::new(AtomicU32::new(0))).collect();
+        let provider = RotationProvider::new(
+            counting_providers(&counts),
+            RotationStrategy::Weighted(vec![7, 2, 1]),
+            1,
+            30,
+        );
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        for (i, ago) in [10, 100, 50].into_iter().enumerate() {
+            let health = &provider.providers[i].1;
+            health.record_failure();
+            health
+                .last_failure_epoch
+                .store(now - ago, Ordering::Relaxed);
+        }
+
+        assert_eq!(provider.select_provider_index(), 1);
+    }
+
+    #[test]
+    fn test_rotation_weighted_zero_weight_is_standby() {
+        let counts: Vec<Arc<AtomicU32>> = (0..2).map(|_| Arc::new(AtomicU32::new(0))).collect();
+        let provider = RotationProvider::new(
+            counting_providers(&counts),
+            RotationStrategy::Weighted(vec![1, 0]),
+            1,
+            30,
+        );
+        assert_eq!(provider.select_provider_index(), 0);
+        assert_eq!(provider.select_provider_index(), 0);
+
+        provider.providers[0].1.record_failure();
+        assert_eq!(provider.select_provider_index(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "requires one weight per provider (2 weights for 3 providers)")]
+    fn test_rotation_weighted_length_mismatch_panics() {
+        let counts: Vec<Arc<AtomicU32>> = (0..3).map(|_| Arc::new(AtomicU32::new(0))).collect();
+        let _ = RotationProvider::new(
+            counting_providers(&counts),
+            RotationStrategy::Weighted(vec![1, 1]),
+            3,
+            30,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one non-zero weight")]
+    fn test_rotation_weighted_all_zero_panics() {
+        let counts: Vec<Arc<AtomicU32>> = (0..2).map(|_| Arc::new(AtomicU32::new(0))).collect();
+        let _ = RotationProvider::new(
+            counting_providers(&counts),
+            RotationStrategy::Weighted(vec![0, 0]),
+            3,
+            30,
+        );
+    }
+
     #[tokio::test]
     async fn test_rotation_records_failure() {
         let provider = RotationProvider::new(
@@ -906,6 +1177,122 @@ mod tests {
         assert_eq!(response.content, "success from solo");
     }
 
+    #[tokio::test]
+    async fn test_rotation_health_snapshot() {
+        let provider = RotationProvider::new(
+            vec![
+                Box::new(FailProvider { name: "alpha" }),
+                Box::new(SuccessProvider { name: "beta" }),
+            ],
+            RotationStrategy::Priority,
+            1,
+            30,
+        );
+
+        let snapshot = provider.health_snapshot();
+        assert_eq!(snapshot.len(), 2);
+        assert!(snapshot.iter().all(|s| s.healthy && s.failure_count == 0));
+
+        // alpha fails once (threshold 1) and beta answers.
+        provider
+            .chat(vec![], vec![], None, ChatOptions::default())
+            .await
+            .expect("beta should succeed");
+
+        let snapshot = provider.health_snapshot();
+        assert_eq!(snapshot[0].name, "alpha");
+        assert_eq!(snapshot[0].failure_count, 1);
+        assert!(snapshot[0].last_failure_epoch > 0);
+        assert!(!snapshot[0].healthy);
+        assert_eq!(snapshot[1].name, "beta");
+        assert!(snapshot[1].healthy);
+        assert_eq!(snapshot[1].last_failure_epoch, 0);
+
+        let json = serde_json::to_value(&snapshot[0]).unwrap();
+        assert_eq!(json["name"], "alpha");
+        assert_eq!(json["healthy"], false);
+        assert_eq!(json["failure_count"], 1);
+    }
+
+    /// A provider that counts calls and answers after a short delay.
+    struct SlowCountingProvider {
+        name: &'static str,
+        call_count: Arc<AtomicU32>,
+    }
+
+    impl fmt::Debug for SlowCountingProvider {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("SlowCountingProvider")
+                .field("name", &self.name)
+                .finish()
+        }
+    }
+
+    #[async_trait]
+    impl LLMProvider for SlowCountingProvider {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn default_model(&self) -> &str {
+            "slow-model-v1"
+        }
+
+        async fn chat(
+            &self,
+            _messages: Vec<Message>,
+            _tools: Vec<ToolDefinition>,
+            _model: Option<&str>,
+            _options: ChatOptions,
+        ) -> Result<LLMResponse> {
+            self.call_count.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+            Ok(LLMResponse::text(&format!("success from {}", self.name)))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rotation_half_open_allows_one_concurrent_probe() {
+        let calls_a = Arc::new(AtomicU32::new(0));
+        let calls_b = Arc::new(AtomicU32::new(0));
+        let provider = RotationProvider::new(
+            vec![
+                Box::new(SlowCountingProvider {
+                    name: "alpha",
+                    call_count: Arc::clone(&calls_a),
+                }),
+                Box::new(CountingProvider {
+                    name: "beta",
+                    call_count: Arc::clone(&calls_b),
+                }),
+            ],
+            RotationStrategy::Priority,
+            1,
+            30,
+        );
+        // alpha tripped long ago: cooldown has elapsed, it is half-open.
+        provider.providers[0].1.record_failure();
+        provider.providers[0]
+            .1
+            .last_failure_epoch
+            .store(0, Ordering::Relaxed);
+
+        let (first, second) = tokio::join!(
+            provider.chat(vec![], vec![], None, ChatOptions::default()),
+            provider.chat(vec![], vec![], None, ChatOptions::default()),
+        );
+
+        assert_eq!(first.unwrap().content, "success from alpha");
+        assert_eq!(second.unwrap().content, "success from beta");
+        assert_eq!(calls_a.load(Ordering::SeqCst), 1, "only one probe");
+        assert_eq!(calls_b.load(Ordering::SeqCst), 1);
+
+        // The probe succeeded, so alpha is fully healthy again.
+        let snapshot = provider.health_snapshot();
+        assert!(snapshot[0].healthy);
+        assert_eq!(snapshot[0].failure_count, 0);
+    }
+
     #[test]
     fn test_rotation_config_defaults() {
         use crate::config::RotationConfig;
@@ -926,6 +1313,9 @@ mod tests {
 
         let parsed: RotationStrategy = serde_json::from_str("\"priority\"").unwrap();
         assert_eq!(parsed, RotationStrategy::Priority);
+
+        let parsed: RotationStrategy = serde_json::from_str(r#"{"weighted":[7,2,1]}"#).unwrap();
+        assert_eq!(parsed, RotationStrategy::Weighted(vec![7, 2, 1]));
     }
 
     #[tokio::test]
@@ -999,6 +1389,60 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_rotation_stream_rate_limit_triggers_rotation() {
+        let provider = RotationProvider::new(
+            vec![
+                Box::new(TypedFailProvider {
+                    name: "alpha",
+                    error: || {
+                        ZeptoError::ProviderTyped(ProviderError::RateLimit("quota exceeded".into()))
+                    },
+                }),
+                Box::new(SuccessProvider { name: "beta" }),
+            ],
+            RotationStrategy::Priority,
+            3,
+            30,
+        );
+
+        let mut rx = provider
+            .chat_stream(vec![], vec![], None, ChatOptions::default())
+            .await
+            .expect("rate limit should rotate");
+
+        match rx.recv().await {
+            Some(StreamEvent::Done { content, .. }) => assert_eq!(content, "success from beta"),
+            other => panic!("expected Done event, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rotation_stream_auth_error_no_rotation() {
+        let provider = RotationProvider::new(
+            vec![
+                Box::new(TypedFailProvider {
+                    name: "alpha",
+                    error: || ZeptoError::ProviderTyped(ProviderError::Auth("invalid key".into())),
+                }),
+                Box::new(SuccessProvider { name: "beta" }),
+            ],
+            RotationStrategy::Priority,
+            3,
+            30,
+        );
+
+        let result = provider
+            .chat_stream(vec![], vec![], None, ChatOptions::default())
+            .await;
+
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Authentication error"));
+    }
+
     #[test]
     fn test_provider_health_starts_healthy() {
         let health = ProviderHealth::new(3, 30);
@@ -1037,6 +1481,35 @@ mod tests {
         assert!(health.is_healthy());
     }
 
+    #[test]
+    fn test_provider_health_half_open_allows_single_probe() {
+        let health = ProviderHealth::new(1, 30);
+        health.record_failure();
+        assert!(matches!(health.admit(), Admission::Rejected));
+
+        // Cooldown elapsed: exactly one caller gets the probe slot.
+        health.last_failure_epoch.store(0, Ordering::Relaxed);
+        let probe = health.admit();
+        assert!(matches!(probe, Admission::Probe(_)));
+        assert!(matches!(health.admit(), Admission::Rejected));
+        assert!(!health.is_healthy());
+
+        // Failed probe: slot released, cooldown restarted.
+        health.record_failure();
+        drop(probe);
+        assert!(!health.probe_in_flight.load(Ordering::Relaxed));
+        assert!(matches!(health.admit(), Admission::Rejected));
+
+        // Successful probe: fully closed again.
+        health.last_failure_epoch.store(0, Ordering::Relaxed);
+        let probe = health.admit();
+        assert!(matches!(probe, Admission::Probe(_)));
+        health.record_success();
+        drop(probe);
+        assert!(matches!(health.admit(), Admission::Admitted));
+        assert!(matches!(health.admit(), Admission::Admitted));
+    }
+
     #[test]
     fn test_provider_health_success_resets() {
         let health = ProviderHealth::new(3, 30);
@@ -11,8 +11,18 @@
 //!
 //! Each provider handles structured output differently:
 //!
-//! - **OpenAI**: Uses the native `response_format` parameter.
-//! - **Claude**: Appends JSON instructions to the system prompt.
+//! - **OpenAI** (and OpenAI-compatible endpoints): Uses the native
+//!   `response_format` parameter.
+//! - **Claude**: `JsonSchema` forces a call to a tool whose input schema is
+//!   the requested schema, when no other tools are offered. `Json`, and
+//!   schemas alongside other tools, append JSON instructions to the system
+//!   prompt.
+//! - **Gemini**: Sets `responseMimeType` (and `responseSchema` for schemas).
+//! - **Ollama**: Sets `format` to `"json"` or the schema.
+//!
+//! Prompt instructions are best-effort: the model is asked for JSON but
+//! nothing enforces it, so callers should check the reply with
+//! [`validate_json_response`].
 //!
 //! # Example
 //!
@@ -55,12 +65,13 @@ pub enum OutputFormat {
     /// JSON mode — instructs the LLM to return valid JSON.
     ///
     /// For OpenAI: sets `response_format: { "type": "json_object" }`
-    /// For Claude: adds JSON instruction to system prompt
+    /// For Claude: adds JSON instruction to system prompt (best-effort)
     Json,
     /// JSON Schema mode — instructs the LLM to return JSON matching a schema.
     ///
     /// For OpenAI: sets `response_format: { "type": "json_schema", "json_schema": {...} }`
-    /// For Claude: adds schema instruction to system prompt
+    /// For Claude: forces a tool call with the schema as its input schema, or adds
+    /// schema instructions to the system prompt when other tools are offered
     JsonSchema {
         /// Name for the schema (used by OpenAI's `json_schema` response format).
         name: String,
@@ -213,7 +224,8 @@ impl OutputFormat {
     /// Generate a system prompt suffix for Claude to request JSON output.
     ///
     /// Claude does not have a native `response_format` parameter, so we
-    /// append instructions to the system prompt instead.
+    /// append instructions to the system prompt when a schema cannot be
+    /// enforced through tool forcing.
     ///
     /// # Returns
     /// - `None` for `Text`
@@ -133,6 +133,29 @@ pub trait LLMProvider: Send + Sync {
             .await;
         Ok(rx)
     }
+
+    /// Health of the providers behind this one, for monitoring.
+    ///
+    /// Only [`RotationProvider`](super::RotationProvider) tracks health;
+    /// wrapping providers pass it through and others report nothing.
+    fn health_snapshot(&self) -> Vec<super::ProviderHealthStatus> {
+        Vec::new()
+    }
+}
+
+/// How tool definitions are offered to the model.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ToolCallMode {
+    /// Send tools through the provider's native `tools` field.
+    Native,
+    /// Describe tools in the system prompt and parse fenced JSON calls
+    /// from the reply (see [`prompt_tools`](super::prompt_tools)).
+    Prompted,
+    /// Try native tool calling and switch to prompted when the model
+    /// rejects the `tools` field.
+    #[default]
+    Auto,
 }
 
 /// Options for chat completion requests.
@@ -150,6 +173,13 @@ pub struct ChatOptions {
     pub stop: Option<Vec<String>>,
     /// Output format (text, JSON, or JSON schema)
     pub output_format: OutputFormat,
+    /// Mark the system prompt and tool definitions as cacheable
+    /// (Anthropic prompt caching; ignored by other providers)
+    pub cache_system_prompt: bool,
+    /// Indices into the request messages to end a cached prefix at
+    pub cache_breakpoints: Vec<usize>,
+    /// How tools are offered to the model
+    pub tool_call_mode: ToolCallMode,
 }
 
 impl ChatOptions {
@@ -254,6 +284,57 @@ impl ChatOptions {
         self.output_format = output_format;
         self
     }
+
+    /// Cache the system prompt and tool definitions across requests.
+    ///
+    /// Providers without prompt caching ignore this.
+    ///
+    /// # Example
+    /// ```
+    /// use zeptoclaw::providers::ChatOptions;
+    ///
+    /// let options = ChatOptions::new().with_cache_system_prompt(true);
+    /// assert!(options.cache_system_prompt);
+    /// ```
+    pub fn with_cache_system_prompt(mut self, enabled: bool) -> Self {
+        self.cache_system_prompt = enabled;
+        self
+    }
+
+    /// Cache the conversation up to and including message `index`.
+    ///
+    /// Providers without prompt caching ignore this.
+    ///
+    /// # Example
+    /// ```
+    /// use zeptoclaw::providers::ChatOptions;
+    ///
+    /// let options = ChatOptions::new().with_cache_breakpoint(3);
+    /// assert_eq!(options.cache_breakpoints, vec![3]);
+    /// ```
+    pub fn with_cache_breakpoint(mut self, index: usize) -> Self {
+        if !self.cache_breakpoints.contains(&index) {
+            self.cache_breakpoints.push(index);
+        }
+        self
+    }
+
+    /// Set how tools are offered to the model.
+    ///
+    /// Providers that do not implement prompted tool calls always use
+    /// their native tool support.
+    ///
+    /// # Example
+    /// ```
+    /// use zeptoclaw::providers::{ChatOptions, ToolCallMode};
+    ///
+    /// let options = ChatOptions::new().with_tool_call_mode(ToolCallMode::Prompted);
+    /// assert_eq!(options.tool_call_mode, ToolCallMode::Prompted);
+    /// ```
+    pub fn with_tool_call_mode(mut self, mode: ToolCallMode) -> Self {
+        self.tool_call_mode = mode;
+        self
+    }
 }
 
 /// Response from an LLM chat completion request.
@@ -404,6 +485,9 @@ pub struct Usage {
     pub completion_tokens: u32,
     /// Total tokens used (prompt + completion)
     pub total_tokens: u32,
+    /// Model that served the request, as reported by the provider
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
 }
 
 impl Usage {
@@ -425,8 +509,23 @@ impl Usage {
             prompt_tokens,
             completion_tokens,
             total_tokens: prompt_tokens + completion_tokens,
+            model: None,
         }
     }
+
+    /// Attach the model the provider reported; an empty name is ignored.
+    ///
+    /// # Example
+    /// ```
+    /// use zeptoclaw::providers::Usage;
+    ///
+    /// let usage = Usage::new(100, 50).with_model(Some("gpt-4o-2024-08-06"));
+    /// assert_eq!(usage.model.as_deref(), Some("gpt-4o-2024-08-06"));
+    /// ```
+    pub fn with_model(mut self, model: Option<&str>) -> Self {
+        self.model = model.filter(|m| !m.is_empty()).map(str::to_string);
+        self
+    }
 }
 
 #[cfg(test)]
@@ -508,6 +607,7 @@ mod tests {
         assert!(options.temperature.is_none());
         assert!(options.top_p.is_none());
         assert!(options.stop.is_none());
+        assert_eq!(options.tool_call_mode, ToolCallMode::Auto);
     }
 
     #[test]
@@ -6,8 +6,14 @@ use async_trait::async_trait;
 use std::process::Stdio;
 use std::time::Duration;
 use tokio::process::Command;
+use tokio::sync::mpsc;
 
-use super::types::{CommandOutput, ContainerConfig, ContainerRuntime, RuntimeError, RuntimeResult};
+use crate::security::EgressPolicy;
+
+use super::stream::spawn_streaming;
+use super::types::{
+    CommandOutput, ContainerConfig, ContainerRuntime, OutputChunk, RuntimeError, RuntimeResult,
+};
 
 /// Docker runtime that executes commands in isolated containers
 #[derive(Debug, Clone)]
@@ -22,6 +28,8 @@ pub struct DockerRuntime {
     network: String,
     /// Extra volume mounts from config (host:container or host:container:ro format)
     extra_mounts: Vec<String>,
+    /// Hosts, IPs or CIDRs reachable from the container (empty = no filtering)
+    network_allowlist: Vec<String>,
 }
 
 impl DockerRuntime {
@@ -33,6 +41,7 @@ impl DockerRuntime {
             cpu_limit: Some("1.0".to_string()),
             network: "none".to_string(),
             extra_mounts: Vec::new(),
+            network_allowlist: Vec::new(),
         }
     }
 
@@ -60,43 +69,42 @@ impl DockerRuntime {
         self
     }
 
+    /// Restrict egress to these hosts, IPs or CIDRs (see [`EgressPolicy`])
+    pub fn with_network_allowlist(mut self, allowlist: Vec<String>) -> Self {
+        self.network_allowlist = allowlist;
+        self
+    }
+
+    /// Validate the configured allowlist against the network mode.
+    pub(crate) fn egress_policy(
+        &self,
+        extra: &[String],
+    ) -> crate::error::Result<Option<EgressPolicy>> {
+        let mut allowlist = self.network_allowlist.clone();
+        allowlist.extend(extra.iter().cloned());
+        EgressPolicy::new(&self.network, &allowlist)
+    }
+
     /// Disable resource limits
     pub fn without_limits(mut self) -> Self {
         self.memory_limit = None;
         self.cpu_limit = None;
         self
     }
-}
-
-impl Default for DockerRuntime {
-    fn default() -> Self {
-        Self::new("alpine:latest")
-    }
-}
 
-#[async_trait]
-impl ContainerRuntime for DockerRuntime {
-    fn name(&self) -> &str {
-        "docker"
+    /// Build the `docker run` command for `command`.
+    fn command(&self, command: &str, config: &ContainerConfig) -> RuntimeResult<Command> {
+        let mut cmd = Command::new("docker");
+        cmd.args(self.run_args(command, config)?);
+        Ok(cmd)
     }
 
-    async fn is_available(&self) -> bool {
-        // Check if docker is installed and running
-        Command::new("docker")
-            .args(["info"])
-            .stdout(Stdio::null())
-            .stderr(Stdio::null())
-            .status()
-            .await
-            .map(|s| s.success())
-            .unwrap_or(false)
-    }
+    /// Build the `docker run` arguments for `command`.
+    fn run_args(&self, command: &str, config: &ContainerConfig) -> RuntimeResult<Vec<String>> {
+        let policy = self
+            .egress_policy(&config.network_allowlist)
+            .map_err(|e| RuntimeError::StartFailed(e.to_string()))?;
 
-    async fn execute(
-        &self,
-        command: &str,
-        config: &ContainerConfig,
-    ) -> RuntimeResult<CommandOutput> {
         let mut args = vec![
             "run".to_string(),
             "--rm".to_string(),
@@ -147,16 +155,52 @@ impl ContainerRuntime for DockerRuntime {
             args.push(format!("{}={}", key, value));
         }
 
+        // Restrict egress to the allowlist
+        let mut shell = vec!["sh".to_string(), "-c".to_string(), command.to_string()];
+        if let Some(ref policy) = policy {
+            args.extend(policy.run_args());
+            shell = policy.wrap_command(shell);
+        }
+
         // Add image and command
         args.push(self.image.clone());
-        args.push("sh".to_string());
-        args.push("-c".to_string());
-        args.push(command.to_string());
+        args.extend(shell);
 
-        let mut cmd = Command::new("docker");
-        cmd.args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped());
+        Ok(args)
+    }
+}
+
+impl Default for DockerRuntime {
+    fn default() -> Self {
+        Self::new("alpine:latest")
+    }
+}
+
+#[async_trait]
+impl ContainerRuntime for DockerRuntime {
+    fn name(&self) -> &str {
+        "docker"
+    }
+
+    async fn is_available(&self) -> bool {
+        // Check if docker is installed and running
+        Command::new("docker")
+            .args(["info"])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .await
+            .map(|s| s.success())
+            .unwrap_or(false)
+    }
+
+    async fn execute(
+        &self,
+        command: &str,
+        config: &ContainerConfig,
+    ) -> RuntimeResult<CommandOutput> {
+        let mut cmd = self.command(command, config)?;
+        cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
 
         // Execute with timeout
         let output = tokio::time::timeout(Duration::from_secs(config.timeout_secs), cmd.output())
@@ -170,6 +214,18 @@ impl ContainerRuntime for DockerRuntime {
             output.status.code(),
         ))
     }
+
+    async fn execute_streaming(
+        &self,
+        command: &str,
+        config: &ContainerConfig,
+    ) -> RuntimeResult<mpsc::Receiver<OutputChunk>> {
+        spawn_streaming(
+            self.command(command, config)?,
+            config.timeout_secs,
+            |status, _| Ok(status.code()),
+        )
+    }
 }
 
 #[cfg(test)]
@@ -202,6 +258,52 @@ mod tests {
         assert!(runtime.cpu_limit.is_none());
     }
 
+    #[test]
+    fn test_docker_run_args_without_allowlist() {
+        let runtime = DockerRuntime::new("alpine:latest");
+        let args = runtime
+            .run_args("echo hi", &ContainerConfig::new())
+            .unwrap();
+        assert!(!args.contains(&"--cap-add".to_string()));
+        assert_eq!(
+            &args[args.len() - 4..],
+            ["alpine:latest", "sh", "-c", "echo hi"]
+        );
+    }
+
+    #[test]
+    fn test_docker_run_args_with_allowlist() {
+        let runtime = DockerRuntime::new("alpine:latest")
+            .with_network("bridge")
+            .with_network_allowlist(vec!["api.example.com:443".to_string()]);
+        let config = ContainerConfig::new().with_allowed_host("10.0.0.5");
+        let args = runtime.run_args("echo hi", &config).unwrap();
+
+        let cap = args.iter().position(|a| a == "--cap-add").unwrap();
+        assert_eq!(args[cap + 1], "NET_ADMIN");
+        let image = args.iter().position(|a| a == "alpine:latest").unwrap();
+        assert!(cap < image);
+        assert_eq!(args[image + 1..image + 3], ["sh", "-c"]);
+        let script = &args[image + 3];
+        assert!(script.contains("-d api.example.com -p tcp --dport 443 -j ACCEPT"));
+        assert!(script.contains("-d 10.0.0.5 -j ACCEPT"));
+        assert_eq!(args[image + 4], "zeptoclaw-egress");
+        assert_eq!(args[image + 5..], ["sh", "-c", "echo hi"]);
+    }
+
+    #[test]
+    fn test_docker_run_args_rejects_invalid_allowlist() {
+        let runtime = DockerRuntime::new("alpine:latest")
+            .with_network_allowlist(vec!["api.example.com".to_string()]);
+        // Default network is "none"
+        let result = runtime.run_args("echo hi", &ContainerConfig::new());
+        assert!(matches!(result, Err(RuntimeError::StartFailed(_))));
+
+        let runtime = runtime.with_network("bridge");
+        let config = ContainerConfig::new().with_allowed_host("$(reboot)");
+        assert!(runtime.run_args("echo hi", &config).is_err());
+    }
+
     #[test]
     fn test_docker_runtime_default() {
         let runtime = DockerRuntime::default();
@@ -7,6 +7,7 @@ use crate::security::validate_extra_mounts;
 
 use super::docker::DockerRuntime;
 use super::native::NativeRuntime;
+use super::podman::PodmanRuntime;
 use super::types::{ContainerRuntime, RuntimeError, RuntimeResult};
 
 #[cfg(target_os = "macos")]
@@ -15,7 +16,9 @@ use super::apple::AppleContainerRuntime;
 /// Create a container runtime from configuration
 pub async fn create_runtime(config: &RuntimeConfig) -> RuntimeResult<Arc<dyn ContainerRuntime>> {
     match config.runtime_type {
-        RuntimeType::Native => Ok(Arc::new(NativeRuntime::new())),
+        RuntimeType::Native => Ok(Arc::new(
+            NativeRuntime::new().with_limits(config.native.limits),
+        )),
         RuntimeType::Docker => {
             let extra_mounts =
                 validate_extra_mounts(&config.docker.extra_mounts, &config.mount_allowlist_path)
@@ -23,7 +26,11 @@ pub async fn create_runtime(config: &RuntimeConfig) -> RuntimeResult<Arc<dyn Con
 
             let runtime = DockerRuntime::new(&config.docker.image)
                 .with_network(&config.docker.network)
-                .with_extra_mounts(extra_mounts);
+                .with_extra_mounts(extra_mounts)
+                .with_network_allowlist(config.docker.network_allowlist.clone());
+            runtime
+                .egress_policy(&[])
+                .map_err(|e| RuntimeError::NotAvailable(e.to_string()))?;
 
             let runtime = if let Some(ref mem) = config.docker.memory_limit {
                 runtime.with_memory_limit(mem)
@@ -45,6 +52,35 @@ pub async fn create_runtime(config: &RuntimeConfig) -> RuntimeResult<Arc<dyn Con
 
             Ok(Arc::new(runtime))
         }
+        RuntimeType::Podman => {
+            let extra_mounts =
+                validate_extra_mounts(&config.podman.extra_mounts, &config.mount_allowlist_path)
+                    .map_err(|e| RuntimeError::NotAvailable(e.to_string()))?;
+
+            let runtime = PodmanRuntime::new(&config.podman.image)
+                .with_network(&config.podman.network)
+                .with_extra_mounts(extra_mounts);
+
+            let runtime = if let Some(ref mem) = config.podman.memory_limit {
+                runtime.with_memory_limit(mem)
+            } else {
+                runtime
+            };
+
+            let runtime = if let Some(ref cpu) = config.podman.cpu_limit {
+                runtime.with_cpu_limit(cpu)
+            } else {
+                runtime
+            };
+
+            if !runtime.is_available().await {
+                return Err(RuntimeError::NotAvailable(
+                    "Podman is not installed or not working".to_string(),
+                ));
+            }
+
+            Ok(Arc::new(runtime))
+        }
         RuntimeType::AppleContainer => {
             if !config.apple.allow_experimental {
                 return Err(RuntimeError::NotAvailable(
@@ -93,6 +129,12 @@ pub async fn available_runtimes() -> Vec<&'static str> {
         available.push("docker");
     }
 
+    // Check Podman
+    let podman = PodmanRuntime::default();
+    if podman.is_available().await {
+        available.push("podman");
+    }
+
     // Check Apple Container (macOS only)
     #[cfg(target_os = "macos")]
     {
@@ -124,8 +166,10 @@ mod tests {
 
     #[tokio::test]
     async fn test_create_apple_container_blocked_by_default() {
-        let mut config = RuntimeConfig::default();
-        config.runtime_type = RuntimeType::AppleContainer;
+        let config = RuntimeConfig {
+            runtime_type: RuntimeType::AppleContainer,
+            ..Default::default()
+        };
         // allow_experimental defaults to false
         assert!(!config.apple.allow_experimental);
 
@@ -152,9 +196,11 @@ mod tests {
 
     #[tokio::test]
     async fn test_create_docker_runtime_with_extra_mounts_requires_allowlist() {
-        let mut config = RuntimeConfig::default();
-        config.runtime_type = RuntimeType::Docker;
-        config.mount_allowlist_path = "/nonexistent/allowlist.json".to_string();
+        let mut config = RuntimeConfig {
+            runtime_type: RuntimeType::Docker,
+            mount_allowlist_path: "/nonexistent/allowlist.json".to_string(),
+            ..Default::default()
+        };
         config
             .docker
             .extra_mounts
@@ -165,4 +211,30 @@ mod tests {
         let err_text = result.err().map(|err| err.to_string()).unwrap_or_default();
         assert!(err_text.contains("allowlist"));
     }
+
+    #[tokio::test]
+    async fn test_create_podman_runtime_with_extra_mounts_requires_allowlist() {
+        let mut config = RuntimeConfig {
+            runtime_type: RuntimeType::Podman,
+            mount_allowlist_path: "/nonexistent/allowlist.json".to_string(),
+            ..Default::default()
+        };
+        config
+            .podman
+            .extra_mounts
+            .push("/tmp:/workspace/tmp".to_string());
+
+        let result = create_runtime(&config).await;
+        assert!(result.is_err());
+        let err_text = result.err().map(|err| err.to_string()).unwrap_or_default();
+        assert!(err_text.contains("allowlist"));
+    }
+
+    #[test]
+    fn test_podman_runtime_type_deserialize() {
+        let config: RuntimeConfig =
+            serde_json::from_str(r#"{"runtime_type": "podman"}"#).expect("should parse");
+        assert_eq!(config.runtime_type, RuntimeType::Podman);
+        assert_eq!(config.podman.network, "none");
+    }
 }
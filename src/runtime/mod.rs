@@ -4,6 +4,7 @@
 //! It supports multiple runtimes:
 //! - Native: Direct execution (no isolation, uses application-level security)
 //! - Docker: Docker container isolation (Linux, macOS, Windows)
+//! - Podman: Podman container isolation (daemonless, rootless by default)
 //! - Apple Container: Apple's native container technology (macOS only)
 
 #[cfg(target_os = "macos")]
@@ -11,6 +12,8 @@ pub mod apple;
 pub mod docker;
 pub mod factory;
 pub mod native;
+pub mod podman;
+mod stream;
 pub mod types;
 
 #[cfg(target_os = "macos")]
@@ -18,4 +21,8 @@ pub use apple::AppleContainerRuntime;
 pub use docker::DockerRuntime;
 pub use factory::{available_runtimes, create_runtime};
 pub use native::NativeRuntime;
-pub use types::{CommandOutput, ContainerConfig, ContainerRuntime, RuntimeError, RuntimeResult};
+pub use podman::PodmanRuntime;
+pub use types::{
+    CommandOutput, ContainerConfig, ContainerRuntime, OutputChunk, ResourceLimits, RuntimeError,
+    RuntimeResult,
+};
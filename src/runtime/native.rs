@@ -2,25 +2,166 @@
 //!
 //! Executes commands directly on the host system without container isolation.
 //! This is the fallback when no container runtime is configured.
+//!
+//! On Unix, optional [`ResourceLimits`] are applied to the child with
+//! `setrlimit` before it execs. This is best-effort: limits are inherited by
+//! everything the command spawns but are not a sandbox, and they are ignored
+//! on platforms without `setrlimit`.
 
 use async_trait::async_trait;
 use std::process::Stdio;
 use std::time::Duration;
 use tokio::process::Command;
+use tokio::sync::mpsc;
 
-use super::types::{CommandOutput, ContainerConfig, ContainerRuntime, RuntimeError, RuntimeResult};
+use super::stream::spawn_streaming;
+use super::types::{
+    CommandOutput, ContainerConfig, ContainerRuntime, OutputChunk, ResourceLimits, RuntimeError,
+    RuntimeResult,
+};
 
 /// Native runtime that executes commands directly on the host
 #[derive(Debug, Clone, Default)]
-pub struct NativeRuntime;
+pub struct NativeRuntime {
+    /// Limits applied when the per-command config leaves them unset
+    limits: ResourceLimits,
+}
 
 impl NativeRuntime {
     /// Create a new native runtime
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Set default resource limits for every command
+    pub fn with_limits(mut self, limits: ResourceLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Build the `sh -c` command and the limits applied to it.
+    fn command(&self, command: &str, config: &ContainerConfig) -> (Command, ResourceLimits) {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg(command);
+
+        // Set working directory if specified
+        if let Some(ref workdir) = config.workdir {
+            cmd.current_dir(workdir);
+        }
+
+        // Set environment variables
+        for (key, value) in &config.env {
+            cmd.env(key, value);
+        }
+
+        let limits = config.limits.or(self.limits);
+        apply_limits(&mut cmd, limits);
+        (cmd, limits)
+    }
+}
+
+/// Install `limits` on the child process just before it execs.
+#[cfg(unix)]
+fn apply_limits(cmd: &mut Command, limits: ResourceLimits) {
+    if limits.is_empty() {
+        return;
+    }
+    // SAFETY: the closure only calls setrlimit, which is async-signal-safe,
+    // and captures nothing but plain integers.
+    unsafe {
+        cmd.pre_exec(move || {
+            // The resource type differs between libcs, so use a macro rather than a helper fn.
+            macro_rules! set {
+                ($resource:expr, $soft:expr, $hard:expr) => {{
+                    let limit = libc::rlimit {
+                        rlim_cur: $soft as libc::rlim_t,
+                        rlim_max: $hard as libc::rlim_t,
+                    };
+                    if libc::setrlimit($resource, &limit) != 0 {
+                        return Err(std::io::Error::last_os_error());
+                    }
+                }};
+            }
+            if let Some(bytes) = limits.memory_bytes {
+                set!(libc::RLIMIT_AS, bytes, bytes);
+            }
+            if let Some(secs) = limits.cpu_secs {
+                // Hard limit one second later so SIGXCPU arrives before SIGKILL
+                set!(libc::RLIMIT_CPU, secs, secs.saturating_add(1));
+            }
+            if let Some(bytes) = limits.file_size_bytes {
+                set!(libc::RLIMIT_FSIZE, bytes, bytes);
+            }
+            if let Some(count) = limits.open_files {
+                set!(libc::RLIMIT_NOFILE, count, count);
+            }
+            Ok(())
+        });
     }
 }
 
+#[cfg(not(unix))]
+fn apply_limits(_cmd: &mut Command, _limits: ResourceLimits) {}
+
+/// Identify which limit, if any, stopped a failed command.
+///
+/// Best-effort: a signal only counts when a configured limit explains it
+/// (SIGXCPU for the CPU limit, SIGXFSZ for the file-size limit). Memory,
+/// file-size and open-file limits are also recognised by the usual error
+/// messages. Other failures are ordinary command failures.
+fn limit_exceeded(
+    limits: &ResourceLimits,
+    status: &std::process::ExitStatus,
+    stderr: &str,
+) -> Option<String> {
+    if status.success() {
+        return None;
+    }
+
+    #[cfg(unix)]
+    {
+        // Killed by a signal, either directly or as the shell's last child (exit 128 + n)
+        let signal = std::os::unix::process::ExitStatusExt::signal(status).or_else(|| {
+            status
+                .code()
+                .filter(|code| *code > 128)
+                .map(|code| code - 128)
+        });
+        if let (Some(secs), Some(libc::SIGXCPU)) = (limits.cpu_secs, signal) {
+            return Some(format!("CPU time limit of {}s", secs));
+        }
+        if let (Some(bytes), Some(libc::SIGXFSZ)) = (limits.file_size_bytes, signal) {
+            return Some(format!("file size limit of {} bytes", bytes));
+        }
+    }
+
+    let stderr = stderr.to_ascii_lowercase();
+    if let Some(bytes) = limits.memory_bytes {
+        let out_of_memory = [
+            "cannot allocate memory",
+            "memory exhausted",
+            "out of memory",
+            "memoryerror",
+        ]
+        .iter()
+        .any(|needle| stderr.contains(needle));
+        if out_of_memory {
+            return Some(format!("memory limit of {} bytes", bytes));
+        }
+    }
+    if let Some(bytes) = limits.file_size_bytes {
+        if stderr.contains("file too large") {
+            return Some(format!("file size limit of {} bytes", bytes));
+        }
+    }
+    if let Some(count) = limits.open_files {
+        if stderr.contains("too many open files") {
+            return Some(format!("open file limit of {}", count));
+        }
+    }
+    None
+}
+
 #[async_trait]
 impl ContainerRuntime for NativeRuntime {
     fn name(&self) -> &str {
@@ -37,18 +178,7 @@ impl ContainerRuntime for NativeRuntime {
         command: &str,
         config: &ContainerConfig,
     ) -> RuntimeResult<CommandOutput> {
-        let mut cmd = Command::new("sh");
-        cmd.arg("-c").arg(command);
-
-        // Set working directory if specified
-        if let Some(ref workdir) = config.workdir {
-            cmd.current_dir(workdir);
-        }
-
-        // Set environment variables
-        for (key, value) in &config.env {
-            cmd.env(key, value);
-        }
+        let (mut cmd, limits) = self.command(command, config);
 
         // Capture output
         cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
@@ -59,11 +189,29 @@ impl ContainerRuntime for NativeRuntime {
             .map_err(|_| RuntimeError::Timeout(config.timeout_secs))?
             .map_err(|e| RuntimeError::ExecutionFailed(e.to_string()))?;
 
-        Ok(CommandOutput::new(
+        let mut result = CommandOutput::new(
             String::from_utf8_lossy(&output.stdout).to_string(),
             String::from_utf8_lossy(&output.stderr).to_string(),
             output.status.code(),
-        ))
+        );
+        result.limit_exceeded = limit_exceeded(&limits, &output.status, &result.stderr);
+        Ok(result)
+    }
+
+    async fn execute_streaming(
+        &self,
+        command: &str,
+        config: &ContainerConfig,
+    ) -> RuntimeResult<mpsc::Receiver<OutputChunk>> {
+        let (cmd, limits) = self.command(command, config);
+        spawn_streaming(
+            cmd,
+            config.timeout_secs,
+            move |status, stderr| match limit_exceeded(&limits, &status, stderr) {
+                Some(limit) => Err(RuntimeError::ResourceLimitExceeded(limit)),
+                None => Ok(status.code()),
+            },
+        )
     }
 }
 
@@ -142,4 +290,116 @@ mod tests {
         let result = runtime.execute("sleep 10", &config).await;
         assert!(matches!(result, Err(RuntimeError::Timeout(1))));
     }
+
+    #[tokio::test]
+    async fn test_native_runtime_streaming_matches_buffered() {
+        let runtime = NativeRuntime::new();
+        let config = ContainerConfig::new();
+        let command = "echo one; echo warn >&2; echo two; exit 3";
+
+        let buffered = runtime.execute(command, &config).await.unwrap();
+        let rx = runtime.execute_streaming(command, &config).await.unwrap();
+        let streamed = CommandOutput::collect(rx).await.unwrap();
+        assert_eq!(streamed.stdout, buffered.stdout);
+        assert_eq!(streamed.stderr, buffered.stderr);
+        assert_eq!(streamed.exit_code, Some(3));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_native_runtime_streaming_reports_limits() {
+        let runtime = NativeRuntime::new();
+        let dir = tempfile::tempdir().unwrap();
+        let config = ContainerConfig::new()
+            .with_workdir(dir.path().to_path_buf())
+            .with_limits(ResourceLimits {
+                file_size_bytes: Some(1024),
+                ..Default::default()
+            });
+
+        let rx = runtime
+            .execute_streaming("echo start; head -c 4096 /dev/zero > big.bin", &config)
+            .await
+            .unwrap();
+        let output = CommandOutput::collect(rx).await.unwrap();
+        assert_eq!(output.stdout, "start\n");
+        assert!(output.limit_exceeded.unwrap().contains("file size"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_native_runtime_memory_limit_terminates() {
+        let runtime = NativeRuntime::new();
+        let config = ContainerConfig::new().with_limits(ResourceLimits {
+            memory_bytes: Some(64 * 1024 * 1024),
+            ..Default::default()
+        });
+
+        // Fine within the limit
+        let output = runtime.execute("echo ok", &config).await.unwrap();
+        assert_eq!(output.stdout.trim(), "ok");
+
+        // Doubling a string past 64 MB exceeds it; awk reports the failure
+        let output = runtime
+            .execute(
+                "echo before; awk 'BEGIN { s = \"x\"; for (i = 0; i < 40; i++) s = s s; print length(s) }'",
+                &config,
+            )
+            .await
+            .unwrap();
+        assert!(output.stdout.starts_with("before\n"), "{:?}", output);
+        let limit = output.limit_exceeded.clone().expect("memory limit");
+        assert!(limit.contains("memory"), "{}", limit);
+        assert!(output
+            .format()
+            .contains("[Resource limit exceeded: memory limit"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_native_runtime_file_size_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        let runtime = NativeRuntime::new().with_limits(ResourceLimits {
+            file_size_bytes: Some(1024),
+            ..Default::default()
+        });
+        let config = ContainerConfig::new().with_workdir(dir.path().to_path_buf());
+
+        let output = runtime
+            .execute("head -c 4096 /dev/zero > big.bin", &config)
+            .await
+            .unwrap();
+        assert!(output.limit_exceeded.unwrap().contains("file size"));
+    }
+
+    #[tokio::test]
+    async fn test_native_runtime_failure_without_limits_is_output() {
+        let runtime = NativeRuntime::new();
+        let config = ContainerConfig::new();
+
+        let output = runtime
+            .execute("echo 'out of memory' >&2; exit 3", &config)
+            .await
+            .unwrap();
+        assert_eq!(output.exit_code, Some(3));
+        assert_eq!(output.limit_exceeded, None);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_native_runtime_unrelated_signal_is_not_a_limit() {
+        let runtime = NativeRuntime::new().with_limits(ResourceLimits {
+            memory_bytes: Some(512 * 1024 * 1024),
+            cpu_secs: Some(60),
+            file_size_bytes: Some(1024 * 1024),
+            ..Default::default()
+        });
+
+        let output = runtime
+            .execute("echo partial; kill -TERM $$", &ContainerConfig::new())
+            .await
+            .unwrap();
+        assert_eq!(output.stdout, "partial\n");
+        assert_eq!(output.limit_exceeded, None);
+    }
 }
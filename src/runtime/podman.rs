@@ -0,0 +1,263 @@
+//! Podman runtime implementation
+//!
+//! Executes commands inside Podman containers. Podman's CLI is largely
+//! Docker-compatible and runs rootless by default, so this mirrors
+//! [`DockerRuntime`](super::DockerRuntime) with a few Podman-specific
+//! differences in how arguments are built.
+
+use async_trait::async_trait;
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::process::Command;
+
+use super::types::{CommandOutput, ContainerConfig, ContainerRuntime, RuntimeError, RuntimeResult};
+
+/// Returns `true` if a network mode means "use Podman's default network".
+///
+/// Rootless Podman has no `bridge` network to attach to (it uses
+/// slirp4netns/pasta), so Docker's `bridge` default is mapped to omitting
+/// `--network` entirely.
+pub(crate) fn is_podman_default_network(network: &str) -> bool {
+    let network = network.trim();
+    network.is_empty() || network == "bridge" || network == "default"
+}
+
+/// Podman runtime that executes commands in isolated containers
+#[derive(Debug, Clone)]
+pub struct PodmanRuntime {
+    /// Container image to use
+    image: String,
+    /// Memory limit (e.g., "512m")
+    memory_limit: Option<String>,
+    /// CPU limit (e.g., "1.0")
+    cpu_limit: Option<String>,
+    /// Network mode
+    network: String,
+    /// Extra volume mounts from config (host:container or host:container:ro format)
+    extra_mounts: Vec<String>,
+}
+
+impl PodmanRuntime {
+    /// Create a new Podman runtime with the specified image
+    pub fn new(image: &str) -> Self {
+        Self {
+            image: image.to_string(),
+            memory_limit: Some("512m".to_string()),
+            cpu_limit: Some("1.0".to_string()),
+            network: "none".to_string(),
+            extra_mounts: Vec::new(),
+        }
+    }
+
+    /// Set memory limit
+    pub fn with_memory_limit(mut self, limit: &str) -> Self {
+        self.memory_limit = Some(limit.to_string());
+        self
+    }
+
+    /// Set CPU limit
+    pub fn with_cpu_limit(mut self, limit: &str) -> Self {
+        self.cpu_limit = Some(limit.to_string());
+        self
+    }
+
+    /// Set network mode
+    pub fn with_network(mut self, network: &str) -> Self {
+        self.network = network.to_string();
+        self
+    }
+
+    /// Add extra volume mounts (host:container or host:container:ro format)
+    pub fn with_extra_mounts(mut self, mounts: Vec<String>) -> Self {
+        self.extra_mounts = mounts;
+        self
+    }
+
+    /// Disable resource limits
+    pub fn without_limits(mut self) -> Self {
+        self.memory_limit = None;
+        self.cpu_limit = None;
+        self
+    }
+
+    /// Build the `podman run` arguments for a command.
+    fn build_args(&self, command: &str, config: &ContainerConfig) -> Vec<String> {
+        let mut args = vec!["run".to_string(), "--rm".to_string()];
+
+        if !is_podman_default_network(&self.network) {
+            args.push("--network".to_string());
+            args.push(self.network.clone());
+        }
+
+        // Add resource limits
+        if let Some(ref mem) = self.memory_limit {
+            args.push("--memory".to_string());
+            args.push(mem.clone());
+        }
+        if let Some(ref cpu) = self.cpu_limit {
+            args.push("--cpus".to_string());
+            args.push(cpu.clone());
+        }
+
+        // Add working directory
+        if let Some(ref workdir) = config.workdir {
+            args.push("-w".to_string());
+            args.push(workdir.to_string_lossy().to_string());
+        }
+
+        // Add volume mounts from ContainerConfig
+        for (host, container, readonly) in &config.mounts {
+            let mount_spec = if *readonly {
+                format!(
+                    "{}:{}:ro",
+                    host.to_string_lossy(),
+                    container.to_string_lossy()
+                )
+            } else {
+                format!("{}:{}", host.to_string_lossy(), container.to_string_lossy())
+            };
+            args.push("-v".to_string());
+            args.push(mount_spec);
+        }
+
+        // Add extra mounts from runtime config
+        for mount in &self.extra_mounts {
+            args.push("-v".to_string());
+            args.push(mount.clone());
+        }
+
+        // Add environment variables
+        for (key, value) in &config.env {
+            args.push("-e".to_string());
+            args.push(format!("{}={}", key, value));
+        }
+
+        // Add image and command
+        args.push(self.image.clone());
+        args.push("sh".to_string());
+        args.push("-c".to_string());
+        args.push(command.to_string());
+        args
+    }
+}
+
+impl Default for PodmanRuntime {
+    fn default() -> Self {
+        Self::new("alpine:latest")
+    }
+}
+
+#[async_trait]
+impl ContainerRuntime for PodmanRuntime {
+    fn name(&self) -> &str {
+        "podman"
+    }
+
+    async fn is_available(&self) -> bool {
+        // Check if podman is installed and can reach its storage/service
+        Command::new("podman")
+            .args(["info"])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .await
+            .map(|s| s.success())
+            .unwrap_or(false)
+    }
+
+    async fn execute(
+        &self,
+        command: &str,
+        config: &ContainerConfig,
+    ) -> RuntimeResult<CommandOutput> {
+        let mut cmd = Command::new("podman");
+        cmd.args(self.build_args(command, config))
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+
+        // Execute with timeout
+        let output = tokio::time::timeout(Duration::from_secs(config.timeout_secs), cmd.output())
+            .await
+            .map_err(|_| RuntimeError::Timeout(config.timeout_secs))?
+            .map_err(|e| RuntimeError::ExecutionFailed(e.to_string()))?;
+
+        Ok(CommandOutput::new(
+            String::from_utf8_lossy(&output.stdout).to_string(),
+            String::from_utf8_lossy(&output.stderr).to_string(),
+            output.status.code(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_podman_runtime_creation() {
+        let runtime = PodmanRuntime::new("ubuntu:22.04");
+        assert_eq!(runtime.image, "ubuntu:22.04");
+        assert_eq!(runtime.name(), "podman");
+    }
+
+    #[test]
+    fn test_podman_runtime_default() {
+        let runtime = PodmanRuntime::default();
+        assert_eq!(runtime.image, "alpine:latest");
+        assert_eq!(runtime.memory_limit, Some("512m".to_string()));
+        assert_eq!(runtime.cpu_limit, Some("1.0".to_string()));
+        assert_eq!(runtime.network, "none");
+    }
+
+    #[test]
+    fn test_podman_build_args() {
+        let runtime = PodmanRuntime::new("alpine:latest")
+            .with_memory_limit("1g")
+            .with_extra_mounts(vec!["/data:/data:ro".to_string()]);
+        let config = ContainerConfig::new()
+            .with_workdir(PathBuf::from("/work"))
+            .with_env("FOO", "bar");
+
+        let args = runtime.build_args("echo hi", &config);
+        assert_eq!(args[..4], ["run", "--rm", "--network", "none"]);
+        assert!(args.windows(2).any(|w| w == ["--memory", "1g"]));
+        assert!(args.windows(2).any(|w| w == ["-w", "/work"]));
+        assert!(args.windows(2).any(|w| w == ["-v", "/data:/data:ro"]));
+        assert!(args.windows(2).any(|w| w == ["-e", "FOO=bar"]));
+        assert_eq!(
+            args[args.len() - 4..],
+            ["alpine:latest", "sh", "-c", "echo hi"]
+        );
+    }
+
+    #[test]
+    fn test_podman_bridge_network_uses_podman_default() {
+        for network in ["bridge", "default", ""] {
+            let runtime = PodmanRuntime::new("alpine:latest").with_network(network);
+            let args = runtime.build_args("true", &ContainerConfig::new());
+            assert!(
+                !args.iter().any(|a| a == "--network"),
+                "network {:?}",
+                network
+            );
+        }
+
+        let runtime = PodmanRuntime::new("alpine:latest").with_network("host");
+        let args = runtime.build_args("true", &ContainerConfig::new());
+        assert!(args.windows(2).any(|w| w == ["--network", "host"]));
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Podman"]
+    async fn test_podman_runtime_echo() {
+        let runtime = PodmanRuntime::new("alpine:latest");
+        let output = runtime
+            .execute("echo hello", &ContainerConfig::new())
+            .await
+            .unwrap();
+        assert!(output.success());
+        assert_eq!(output.stdout.trim(), "hello");
+    }
+}
@@ -0,0 +1,162 @@
+//! Incremental output streaming for process-backed runtimes.
+
+use std::process::{ExitStatus, Stdio};
+use std::time::Duration;
+
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::process::Command;
+use tokio::sync::mpsc;
+
+use super::types::{OutputChunk, RuntimeError, RuntimeResult, STREAM_BUFFER};
+
+/// Bytes of trailing stderr kept for the exit handler.
+const STDERR_TAIL_BYTES: usize = 8 * 1024;
+
+/// Spawn `cmd` and stream its stdout/stderr lines over a channel.
+///
+/// The command is killed on timeout or as soon as the receiver is dropped,
+/// even if it is not producing output.
+/// `finish` turns the exit status and the tail of stderr into the final
+/// [`OutputChunk::Exit`] result.
+pub(crate) fn spawn_streaming<F>(
+    mut cmd: Command,
+    timeout_secs: u64,
+    finish: F,
+) -> RuntimeResult<mpsc::Receiver<OutputChunk>>
+where
+    F: FnOnce(ExitStatus, &str) -> RuntimeResult<Option<i32>> + Send + 'static,
+{
+    cmd.stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    let mut child = cmd
+        .spawn()
+        .map_err(|e| RuntimeError::ExecutionFailed(e.to_string()))?;
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+
+    let (tx, rx) = mpsc::channel(STREAM_BUFFER);
+    tokio::spawn(async move {
+        let mut stderr_tail = String::new();
+        let run = async {
+            let forward_stdout = forward_lines(stdout, &tx, OutputChunk::Stdout, None);
+            let forward_stderr =
+                forward_lines(stderr, &tx, OutputChunk::Stderr, Some(&mut stderr_tail));
+            let (out_open, err_open) = tokio::join!(forward_stdout, forward_stderr);
+            if !(out_open && err_open) {
+                return None;
+            }
+            Some(child.wait().await)
+        };
+
+        // Watch the receiver too: a silent command would otherwise only
+        // notice it is gone on its next line of output.
+        let outcome = tokio::select! {
+            outcome = tokio::time::timeout(Duration::from_secs(timeout_secs), run) => Some(outcome),
+            _ = tx.closed() => None,
+        };
+        let result = match outcome {
+            // Receiver dropped
+            None | Some(Ok(None)) => {
+                let _ = child.kill().await;
+                return;
+            }
+            Some(Ok(Some(Ok(status)))) => finish(status, &stderr_tail),
+            Some(Ok(Some(Err(e)))) => Err(RuntimeError::ExecutionFailed(e.to_string())),
+            Some(Err(_)) => {
+                let _ = child.kill().await;
+                Err(RuntimeError::Timeout(timeout_secs))
+            }
+        };
+        let _ = tx.send(OutputChunk::Exit(result)).await;
+    });
+
+    Ok(rx)
+}
+
+/// Forward each line of `reader` to `tx`.
+///
+/// Returns `false` if the receiver has gone away.
+async fn forward_lines<R: AsyncRead + Unpin>(
+    reader: Option<R>,
+    tx: &mpsc::Sender<OutputChunk>,
+    chunk: fn(String) -> OutputChunk,
+    mut tail: Option<&mut String>,
+) -> bool {
+    let Some(reader) = reader else {
+        return true;
+    };
+    let mut reader = BufReader::new(reader);
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        match reader.read_until(b'\n', &mut buf).await {
+            Ok(0) | Err(_) => return true,
+            Ok(_) => {}
+        }
+        let line = String::from_utf8_lossy(&buf);
+        let line = line.trim_end_matches('\n').trim_end_matches('\r');
+        if let Some(tail) = tail.as_deref_mut() {
+            tail.push_str(line);
+            tail.push('\n');
+            if tail.len() > STDERR_TAIL_BYTES {
+                let mut cut = tail.len() - STDERR_TAIL_BYTES;
+                while !tail.is_char_boundary(cut) {
+                    cut += 1;
+                }
+                tail.drain(..cut);
+            }
+        }
+        if tx.send(chunk(line.to_string())).await.is_err() {
+            return false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::CommandOutput;
+
+    fn sh(command: &str) -> Command {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg(command);
+        cmd
+    }
+
+    #[tokio::test]
+    async fn test_streams_lines_before_exit() {
+        let mut rx = spawn_streaming(sh("echo first; sleep 2; echo second"), 10, |status, _| {
+            Ok(status.code())
+        })
+        .unwrap();
+
+        let first = tokio::time::timeout(Duration::from_millis(1500), rx.recv())
+            .await
+            .expect("first line should arrive before the command finishes");
+        assert!(matches!(first, Some(OutputChunk::Stdout(ref line)) if line == "first"));
+
+        let rest = CommandOutput::collect(rx).await.unwrap();
+        assert_eq!(rest.stdout, "second\n");
+        assert_eq!(rest.exit_code, Some(0));
+    }
+
+    #[tokio::test]
+    async fn test_stderr_tail_and_timeout() {
+        let rx = spawn_streaming(sh("echo oops >&2; exit 4"), 10, |status, tail| {
+            assert_eq!(tail, "oops\n");
+            Ok(status.code())
+        })
+        .unwrap();
+        let output = CommandOutput::collect(rx).await.unwrap();
+        assert_eq!(output.stderr, "oops\n");
+        assert_eq!(output.exit_code, Some(4));
+
+        let rx = spawn_streaming(sh("sleep 10"), 1, |status, _| Ok(status.code())).unwrap();
+        assert!(matches!(
+            CommandOutput::collect(rx).await,
+            Err(RuntimeError::Timeout(1))
+        ));
+    }
+}
@@ -1,8 +1,10 @@
 //! Runtime type definitions
 
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use thiserror::Error;
+use tokio::sync::mpsc;
 
 /// Errors that can occur during runtime operations
 #[derive(Error, Debug)]
@@ -23,6 +25,10 @@ pub enum RuntimeError {
     #[error("Command timed out after {0} seconds")]
     Timeout(u64),
 
+    /// Command was stopped by a resource limit
+    #[error("Resource limit exceeded: {0}")]
+    ResourceLimitExceeded(String),
+
     /// IO error
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
@@ -40,6 +46,8 @@ pub struct CommandOutput {
     pub stderr: String,
     /// Exit code (None if killed by signal)
     pub exit_code: Option<i32>,
+    /// Resource limit that stopped the command, if one was recognised
+    pub limit_exceeded: Option<String>,
 }
 
 impl CommandOutput {
@@ -49,7 +57,52 @@ impl CommandOutput {
             stdout,
             stderr,
             exit_code,
+            limit_exceeded: None,
+        }
+    }
+
+    /// Collect a streamed command into buffered output.
+    ///
+    /// # Errors
+    /// Returns the error carried by the final [`OutputChunk::Exit`], or
+    /// `ExecutionFailed` if the stream ends without one.
+    pub async fn collect(mut rx: mpsc::Receiver<OutputChunk>) -> RuntimeResult<Self> {
+        let mut output = Self::new(String::new(), String::new(), None);
+        while let Some(chunk) = rx.recv().await {
+            match chunk {
+                OutputChunk::Stdout(line) => {
+                    output.stdout.push_str(&line);
+                    output.stdout.push('\n');
+                }
+                OutputChunk::Stderr(line) => {
+                    output.stderr.push_str(&line);
+                    output.stderr.push('\n');
+                }
+                OutputChunk::Exit(result) => {
+                    output.finish(result)?;
+                    return Ok(output);
+                }
+            }
         }
+        Err(RuntimeError::ExecutionFailed(
+            "output stream ended without an exit status".to_string(),
+        ))
+    }
+
+    /// Record the result carried by [`OutputChunk::Exit`].
+    ///
+    /// A `ResourceLimitExceeded` result is kept in `limit_exceeded` rather
+    /// than returned, so the output produced before the limit hit survives.
+    ///
+    /// # Errors
+    /// Returns any other runtime error unchanged.
+    pub fn finish(&mut self, result: RuntimeResult<Option<i32>>) -> RuntimeResult<()> {
+        match result {
+            Ok(code) => self.exit_code = code,
+            Err(RuntimeError::ResourceLimitExceeded(limit)) => self.limit_exceeded = Some(limit),
+            Err(e) => return Err(e),
+        }
+        Ok(())
     }
 
     /// Check if the command succeeded (exit code 0)
@@ -78,10 +131,63 @@ impl CommandOutput {
             }
         }
 
+        if let Some(ref limit) = self.limit_exceeded {
+            result.push_str(&format!("\n[Resource limit exceeded: {}]", limit));
+        }
+
         result
     }
 }
 
+/// One event from a streamed command.
+#[derive(Debug)]
+pub enum OutputChunk {
+    /// A line written to stdout, without its trailing newline
+    Stdout(String),
+    /// A line written to stderr, without its trailing newline
+    Stderr(String),
+    /// Final event: the exit code (None if killed by signal) or the error
+    /// that ended the command
+    Exit(RuntimeResult<Option<i32>>),
+}
+
+/// Buffer size of the channel returned by [`ContainerRuntime::execute_streaming`].
+pub const STREAM_BUFFER: usize = 64;
+
+/// Resource ceilings for a command (`None` = unlimited).
+///
+/// Applied by the native runtime on Unix via `setrlimit`; best-effort and a
+/// no-op on other platforms.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ResourceLimits {
+    /// Maximum virtual address space in bytes (`RLIMIT_AS`).
+    pub memory_bytes: Option<u64>,
+    /// Maximum CPU time in seconds (`RLIMIT_CPU`).
+    pub cpu_secs: Option<u64>,
+    /// Maximum size of any file written, in bytes (`RLIMIT_FSIZE`).
+    pub file_size_bytes: Option<u64>,
+    /// Maximum number of open file descriptors (`RLIMIT_NOFILE`).
+    pub open_files: Option<u64>,
+}
+
+impl ResourceLimits {
+    /// Returns `true` if no limit is set.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Fill unset limits from `defaults`.
+    pub fn or(self, defaults: ResourceLimits) -> Self {
+        Self {
+            memory_bytes: self.memory_bytes.or(defaults.memory_bytes),
+            cpu_secs: self.cpu_secs.or(defaults.cpu_secs),
+            file_size_bytes: self.file_size_bytes.or(defaults.file_size_bytes),
+            open_files: self.open_files.or(defaults.open_files),
+        }
+    }
+}
+
 /// Configuration for a container execution
 #[derive(Debug, Clone, Default)]
 pub struct ContainerConfig {
@@ -93,6 +199,10 @@ pub struct ContainerConfig {
     pub env: Vec<(String, String)>,
     /// Command timeout in seconds
     pub timeout_secs: u64,
+    /// Resource limits (native runtime only)
+    pub limits: ResourceLimits,
+    /// Extra hosts, IPs or CIDRs the command may reach (Docker runtime only)
+    pub network_allowlist: Vec<String>,
 }
 
 impl ContainerConfig {
@@ -127,6 +237,18 @@ impl ContainerConfig {
         self.timeout_secs = secs;
         self
     }
+
+    /// Set resource limits
+    pub fn with_limits(mut self, limits: ResourceLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Allow network egress to a host, IP or CIDR (optionally `:port`)
+    pub fn with_allowed_host(mut self, entry: &str) -> Self {
+        self.network_allowlist.push(entry.to_string());
+        self
+    }
 }
 
 /// Trait for container runtimes
@@ -144,6 +266,29 @@ pub trait ContainerRuntime: Send + Sync {
         command: &str,
         config: &ContainerConfig,
     ) -> RuntimeResult<CommandOutput>;
+
+    /// Execute a command, yielding output lines as they arrive.
+    ///
+    /// The receiver ends with exactly one [`OutputChunk::Exit`]. Dropping it
+    /// early stops the command. The default implementation runs
+    /// [`execute`](Self::execute) and replays the buffered output.
+    async fn execute_streaming(
+        &self,
+        command: &str,
+        config: &ContainerConfig,
+    ) -> RuntimeResult<mpsc::Receiver<OutputChunk>> {
+        let output = self.execute(command, config).await?;
+        let lines = output.stdout.lines().count() + output.stderr.lines().count();
+        let (tx, rx) = mpsc::channel(lines + 1);
+        for line in output.stdout.lines() {
+            let _ = tx.try_send(OutputChunk::Stdout(line.to_string()));
+        }
+        for line in output.stderr.lines() {
+            let _ = tx.try_send(OutputChunk::Stderr(line.to_string()));
+        }
+        let _ = tx.try_send(OutputChunk::Exit(Ok(output.exit_code)));
+        Ok(rx)
+    }
 }
 
 #[cfg(test)]
@@ -210,4 +355,23 @@ mod tests {
         let err = RuntimeError::Timeout(30);
         assert_eq!(err.to_string(), "Command timed out after 30 seconds");
     }
+
+    #[test]
+    fn test_resource_limits_or() {
+        let limits = ResourceLimits {
+            memory_bytes: Some(1024),
+            ..Default::default()
+        };
+        assert!(!limits.is_empty());
+        assert!(ResourceLimits::default().is_empty());
+
+        let merged = limits.or(ResourceLimits {
+            memory_bytes: Some(1),
+            cpu_secs: Some(10),
+            ..Default::default()
+        });
+        assert_eq!(merged.memory_bytes, Some(1024));
+        assert_eq!(merged.cpu_secs, Some(10));
+        assert_eq!(merged.open_files, None);
+    }
 }
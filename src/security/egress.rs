@@ -0,0 +1,331 @@
+//! Container network egress allowlist.
+//!
+//! Docker's `--network` flag is all-or-nothing: `none` blocks everything and
+//! any other network allows everything. When a network allowlist is set, the
+//! container is started with `NET_ADMIN` and its command is wrapped in a
+//! small shell prelude that:
+//!
+//! 1. sets the default `OUTPUT` policy to `DROP` (IPv4, and IPv6 when the
+//!    container has IPv6 addresses),
+//! 2. accepts loopback, established connections, and DNS to the container's
+//!    configured nameservers,
+//! 3. accepts each allowlisted host, address or CIDR (optionally one TCP port),
+//! 4. drops `NET_ADMIN`/`NET_RAW` from the bounding, inheritable and ambient
+//!    sets with `setpriv` and execs the original command, so it cannot undo
+//!    the rules.
+//!
+//! The image must provide `iptables` (and `ip6tables` if IPv6 is enabled)
+//! and `setpriv` (util-linux). The prelude runs under `set -e`, so if any of
+//! them is missing, or a hostname does not resolve, the container exits
+//! before the command runs: the policy fails closed. Hostnames are resolved
+//! once, when the rules are installed. With an empty allowlist nothing is
+//! changed.
+
+use std::net::{IpAddr, Ipv4Addr};
+
+use crate::error::{Result, ZeptoError};
+
+/// Capability the container needs to install its egress rules.
+pub const EGRESS_CAPABILITY: &str = "NET_ADMIN";
+
+/// `$0` for the wrapped command, shown in `ps` inside the container.
+const EGRESS_ARGV0: &str = "zeptoclaw-egress";
+
+/// Rules installed before any allowlist entry.
+const EGRESS_PRELUDE: &str = r#"set -e
+iptables -P OUTPUT DROP
+iptables -A OUTPUT -o lo -j ACCEPT
+iptables -A OUTPUT -m conntrack --ctstate ESTABLISHED,RELATED -j ACCEPT
+for ns in $(awk '/^nameserver/ && $2 !~ /:/ {print $2}' /etc/resolv.conf); do
+  iptables -A OUTPUT -d "$ns" -p udp --dport 53 -j ACCEPT
+  iptables -A OUTPUT -d "$ns" -p tcp --dport 53 -j ACCEPT
+done
+if [ -s /proc/net/if_inet6 ]; then
+  ip6tables -P OUTPUT DROP
+  ip6tables -A OUTPUT -o lo -j ACCEPT
+  ip6tables -A OUTPUT -m conntrack --ctstate ESTABLISHED,RELATED -j ACCEPT
+fi
+"#;
+
+/// Drops the capabilities and runs the original command.
+const EGRESS_EXEC: &str = r#"exec setpriv --bounding-set -net_admin,-net_raw --inh-caps -net_admin,-net_raw --ambient-caps -net_admin,-net_raw -- "$@""#;
+
+/// Where an allowlist entry points.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum EgressTarget {
+    /// Hostname, resolved by iptables when the rule is added
+    Host(String),
+    /// IPv4 address or CIDR
+    V4(String),
+    /// IPv6 address or CIDR
+    V6(String),
+}
+
+/// One parsed allowlist entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EgressRule {
+    target: EgressTarget,
+    /// Restrict to this TCP port; `None` allows every port and protocol
+    port: Option<u16>,
+}
+
+impl EgressRule {
+    /// Parse `host`, `host:port`, `1.2.3.4`, `10.0.0.0/8`, `2001:db8::/32`
+    /// or `[2001:db8::1]:443`.
+    ///
+    /// # Errors
+    /// Returns `ZeptoError::Config` for anything else, including wildcards.
+    pub fn parse(entry: &str) -> Result<Self> {
+        let invalid = |reason: &str| {
+            ZeptoError::Config(format!(
+                "Invalid network allowlist entry '{}': {}",
+                entry, reason
+            ))
+        };
+        let entry = entry.trim();
+        if entry.is_empty() {
+            return Err(invalid("empty entry"));
+        }
+
+        // Split off an optional port: "[v6]:port", "host:port" or "v4:port".
+        let (addr, port) = if let Some(rest) = entry.strip_prefix('[') {
+            let (addr, rest) = rest.split_once(']').ok_or_else(|| invalid("missing ']'"))?;
+            match rest {
+                "" => (addr, None),
+                _ => (
+                    addr,
+                    Some(rest.strip_prefix(':').ok_or_else(|| invalid("bad port"))?),
+                ),
+            }
+        } else if entry.matches(':').count() == 1 {
+            let (addr, port) = entry.split_once(':').unwrap_or((entry, ""));
+            (addr, Some(port))
+        } else {
+            (entry, None)
+        };
+        let port = match port {
+            Some(port) => match port.parse::<u16>() {
+                Ok(port) if port > 0 => Some(port),
+                _ => return Err(invalid("port must be 1-65535")),
+            },
+            None => None,
+        };
+
+        let (ip, prefix) = match addr.split_once('/') {
+            Some((ip, prefix)) => (ip, Some(prefix)),
+            None => (addr, None),
+        };
+        let target = match ip.parse::<IpAddr>() {
+            Ok(ip) => {
+                let max_prefix = if ip.is_ipv4() { 32 } else { 128 };
+                if let Some(prefix) = prefix {
+                    match prefix.parse::<u8>() {
+                        Ok(bits) if bits <= max_prefix => {}
+                        _ => return Err(invalid("bad CIDR prefix")),
+                    }
+                }
+                if ip.is_unspecified() || ip == IpAddr::V4(Ipv4Addr::BROADCAST) {
+                    return Err(invalid("unspecified address"));
+                }
+                if prefix == Some("0") {
+                    return Err(invalid("/0 would allow everything"));
+                }
+                match ip {
+                    IpAddr::V4(_) => EgressTarget::V4(addr.to_string()),
+                    IpAddr::V6(_) => EgressTarget::V6(addr.to_string()),
+                }
+            }
+            Err(_) if prefix.is_none() && is_hostname(addr) => {
+                EgressTarget::Host(addr.to_ascii_lowercase())
+            }
+            Err(_) => return Err(invalid("expected a hostname, IP address or CIDR")),
+        };
+
+        Ok(Self { target, port })
+    }
+
+    /// The iptables command accepting this destination.
+    fn iptables_rule(&self) -> String {
+        let (binary, dest) = match &self.target {
+            EgressTarget::Host(host) => ("iptables", host),
+            EgressTarget::V4(addr) => ("iptables", addr),
+            EgressTarget::V6(addr) => ("ip6tables", addr),
+        };
+        match self.port {
+            Some(port) => format!(
+                "{} -A OUTPUT -d {} -p tcp --dport {} -j ACCEPT",
+                binary, dest, port
+            ),
+            None => format!("{} -A OUTPUT -d {} -j ACCEPT", binary, dest),
+        }
+    }
+}
+
+/// RFC 1123 hostname: dot-separated labels of letters, digits and hyphens.
+fn is_hostname(host: &str) -> bool {
+    host.len() <= 253
+        && host.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        })
+}
+
+/// A validated egress allowlist for one container.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EgressPolicy {
+    rules: Vec<EgressRule>,
+}
+
+impl EgressPolicy {
+    /// Validate `allowlist` for a container on `network`.
+    ///
+    /// Returns `Ok(None)` for an empty allowlist, meaning no change.
+    ///
+    /// # Errors
+    /// Returns `ZeptoError::Config` for an invalid entry, or when the
+    /// network is `none` (nothing to allow) or `host` (the rules would
+    /// apply to the host).
+    pub fn new(network: &str, allowlist: &[String]) -> Result<Option<Self>> {
+        if allowlist.is_empty() {
+            return Ok(None);
+        }
+        match network.trim() {
+            "none" => {
+                return Err(ZeptoError::Config(
+                    "network_allowlist requires a network other than 'none' (e.g. 'bridge')"
+                        .to_string(),
+                ))
+            }
+            "host" => {
+                return Err(ZeptoError::Config(
+                    "network_allowlist cannot be used with the 'host' network".to_string(),
+                ))
+            }
+            _ => {}
+        }
+        let rules = allowlist
+            .iter()
+            .map(|entry| EgressRule::parse(entry))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Some(Self { rules }))
+    }
+
+    /// Extra container `run` arguments the policy needs.
+    pub fn run_args(&self) -> Vec<String> {
+        vec!["--cap-add".to_string(), EGRESS_CAPABILITY.to_string()]
+    }
+
+    /// Shell script installing the rules, then exec-ing `"$@"`.
+    pub fn script(&self) -> String {
+        let mut script = EGRESS_PRELUDE.to_string();
+        for rule in &self.rules {
+            script.push_str(&rule.iptables_rule());
+            script.push('\n');
+        }
+        script.push_str(EGRESS_EXEC);
+        script
+    }
+
+    /// Wrap a container command so it runs behind the rules.
+    pub fn wrap_command(&self, command: Vec<String>) -> Vec<String> {
+        let mut wrapped = vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            self.script(),
+            EGRESS_ARGV0.to_string(),
+        ];
+        wrapped.extend(command);
+        wrapped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list(entries: &[&str]) -> Vec<String> {
+        entries.iter().map(|e| e.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_entries() {
+        let rule = EgressRule::parse("API.OpenAI.com").unwrap();
+        assert_eq!(rule.target, EgressTarget::Host("api.openai.com".into()));
+        assert_eq!(rule.port, None);
+
+        let rule = EgressRule::parse("api.anthropic.com:443").unwrap();
+        assert_eq!(rule.port, Some(443));
+
+        let rule = EgressRule::parse("10.0.0.0/8").unwrap();
+        assert_eq!(rule.target, EgressTarget::V4("10.0.0.0/8".into()));
+
+        let rule = EgressRule::parse("[2001:db8::1]:8443").unwrap();
+        assert_eq!(rule.target, EgressTarget::V6("2001:db8::1".into()));
+        assert_eq!(rule.port, Some(8443));
+
+        let rule = EgressRule::parse("2001:db8::/32").unwrap();
+        assert_eq!(rule.target, EgressTarget::V6("2001:db8::/32".into()));
+    }
+
+    #[test]
+    fn test_parse_rejects_unsafe_entries() {
+        for entry in [
+            "",
+            "*.example.com",
+            "example.com; rm -rf /",
+            "$(curl evil)",
+            "example.com:0",
+            "example.com:99999",
+            "10.0.0.0/33",
+            "0.0.0.0/0",
+            "0.0.0.0",
+            "::/0",
+            "-bad.example.com",
+            "[::1",
+        ] {
+            assert!(EgressRule::parse(entry).is_err(), "accepted {:?}", entry);
+        }
+    }
+
+    #[test]
+    fn test_empty_allowlist_is_no_policy() {
+        assert_eq!(EgressPolicy::new("none", &[]).unwrap(), None);
+    }
+
+    #[test]
+    fn test_policy_rejects_none_and_host_networks() {
+        let allow = list(&["example.com"]);
+        assert!(EgressPolicy::new("none", &allow).is_err());
+        assert!(EgressPolicy::new("host", &allow).is_err());
+        assert!(EgressPolicy::new("bridge", &allow).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_script_and_wrapped_command() {
+        let policy = EgressPolicy::new("bridge", &list(&["example.com:443", "10.1.2.3"]))
+            .unwrap()
+            .unwrap();
+        assert_eq!(policy.run_args(), vec!["--cap-add", "NET_ADMIN"]);
+
+        let script = policy.script();
+        assert!(script.starts_with("set -e\niptables -P OUTPUT DROP\n"));
+        assert!(script.contains("iptables -A OUTPUT -d example.com -p tcp --dport 443 -j ACCEPT\n"));
+        assert!(script.contains("iptables -A OUTPUT -d 10.1.2.3 -j ACCEPT\n"));
+        assert!(script.ends_with(
+            r#"exec setpriv --bounding-set -net_admin,-net_raw --inh-caps -net_admin,-net_raw --ambient-caps -net_admin,-net_raw -- "$@""#
+        ));
+
+        let wrapped = policy.wrap_command(list(&["sh", "-c", "curl https://example.com"]));
+        assert_eq!(wrapped[0], "sh");
+        assert_eq!(wrapped[1], "-c");
+        assert_eq!(wrapped[2], script);
+        assert_eq!(wrapped[3], "zeptoclaw-egress");
+        assert_eq!(
+            &wrapped[4..],
+            &list(&["sh", "-c", "curl https://example.com"])[..]
+        );
+    }
+}
@@ -41,6 +41,7 @@ const FORMAT_VERSION: &str = "1";
 ///
 /// Wraps a 256-bit key used with XChaCha20-Poly1305. The key can be derived
 /// from a passphrase via Argon2id or provided directly as raw bytes.
+#[derive(Clone)]
 pub struct SecretEncryption {
     key: [u8; 32],
 }
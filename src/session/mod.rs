@@ -2,7 +2,7 @@
 //!
 //! This module provides session management for ZeptoClaw, including:
 //! - In-memory session storage with async access
-//! - File-based persistence for sessions
+//! - Pluggable persistence backends via the `SessionStore` trait
 //! - Session creation, retrieval, and deletion
 //!
 //! # Example
@@ -27,10 +27,21 @@
 //! ```
 
 pub mod history;
+#[cfg(feature = "sqlite-sessions")]
+pub mod sqlite;
+pub mod store;
 pub mod types;
 
 pub use history::ConversationHistory;
-pub use types::{Message, Role, Session, ToolCall};
+#[cfg(feature = "sqlite-sessions")]
+pub use sqlite::SqliteSessionStore;
+pub use store::{
+    FileSessionStore, MemorySessionStore, SessionBackend, SessionConfig, SessionStore,
+};
+pub use types::{
+    ContentPart, Message, Role, Session, ToolCall, DEFAULT_CHARS_PER_TOKEN, IMAGE_TOKEN_ESTIMATE,
+    MESSAGE_TOKEN_OVERHEAD,
+};
 
 use crate::config::Config;
 use crate::error::Result;
@@ -38,12 +49,13 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::RwLock;
+use tracing::info;
 
 /// Session manager for storing and retrieving conversation sessions.
 ///
-/// The `SessionManager` provides both in-memory caching and optional
-/// file-based persistence for sessions. Sessions are identified by
-/// unique keys (e.g., "telegram:chat123").
+/// The `SessionManager` provides in-memory caching on top of an optional
+/// [`SessionStore`] backend. Sessions are identified by unique keys
+/// (e.g., "telegram:chat123").
 ///
 /// # Thread Safety
 ///
@@ -53,13 +65,16 @@ use tokio::sync::RwLock;
 /// # Persistence
 ///
 /// When created with `new()`, sessions are persisted to disk in the
-/// `~/.zeptoclaw/sessions/` directory. Use `new_memory()` for testing
-/// or when persistence is not needed.
+/// `~/.zeptoclaw/sessions/` directory. Use `from_config()` to honour the
+/// configured `session.backend`, `with_store()` for a custom backend, and
+/// `new_memory()` for testing or when persistence is not needed.
 pub struct SessionManager {
     /// In-memory cache of sessions
     sessions: Arc<RwLock<HashMap<String, Session>>>,
-    /// Optional path for file-based persistence
-    storage_path: Option<PathBuf>,
+    /// Optional persistence backend
+    store: Option<Arc<dyn SessionStore>>,
+    /// Age after which unpinned sessions are removed by `prune_expired`
+    ttl: Option<chrono::Duration>,
 }
 
 impl SessionManager {
@@ -79,14 +94,42 @@ impl SessionManager {
     /// let manager = SessionManager::new().unwrap();
     /// ```
     pub fn new() -> Result<Self> {
-        let storage_path = Config::dir().join("sessions");
-        std::fs::create_dir_all(&storage_path)?;
-        Ok(Self {
-            sessions: Arc::new(RwLock::new(HashMap::new())),
-            storage_path: Some(storage_path),
+        Self::with_path(Config::dir().join("sessions"))
+    }
+
+    /// Create a session manager using the backend selected by
+    /// `session.backend` in the config.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the backend cannot be initialized.
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let manager = match config.session.backend {
+            SessionBackend::Filesystem => Self::new()?,
+            SessionBackend::Memory => Self::with_store(Arc::new(MemorySessionStore::new())),
+            SessionBackend::Sqlite => Self::sqlite()?,
+        };
+        Ok(match config.session.ttl_secs {
+            0 => manager,
+            secs => manager.with_ttl(std::time::Duration::from_secs(secs)),
         })
     }
 
+    /// Open `~/.zeptoclaw/sessions.db`, importing file sessions on first use.
+    #[cfg(feature = "sqlite-sessions")]
+    fn sqlite() -> Result<Self> {
+        let store = SqliteSessionStore::open(Config::dir().join("sessions.db"))?;
+        store.import_file_sessions(&Config::dir().join("sessions"))?;
+        Ok(Self::with_store(Arc::new(store)))
+    }
+
+    #[cfg(not(feature = "sqlite-sessions"))]
+    fn sqlite() -> Result<Self> {
+        Err(crate::error::ZeptoError::Config(
+            "session.backend = \"sqlite\" requires building with --features sqlite-sessions".into(),
+        ))
+    }
+
     /// Create an in-memory session manager without persistence.
     ///
     /// This is useful for testing or temporary sessions that don't
@@ -101,7 +144,8 @@ impl SessionManager {
     pub fn new_memory() -> Self {
         Self {
             sessions: Arc::new(RwLock::new(HashMap::new())),
-            storage_path: None,
+            store: None,
+            ttl: None,
         }
     }
 
@@ -122,17 +166,44 @@ impl SessionManager {
     /// let manager = SessionManager::with_path(PathBuf::from("/tmp/sessions")).unwrap();
     /// ```
     pub fn with_path(path: PathBuf) -> Result<Self> {
-        std::fs::create_dir_all(&path)?;
-        Ok(Self {
+        Ok(Self::with_store(Arc::new(FileSessionStore::new(path)?)))
+    }
+
+    /// Create a session manager backed by a custom [`SessionStore`].
+    ///
+    /// # Example
+    /// ```
+    /// use std::sync::Arc;
+    /// use zeptoclaw::session::{MemorySessionStore, SessionManager};
+    ///
+    /// let manager = SessionManager::with_store(Arc::new(MemorySessionStore::new()));
+    /// ```
+    pub fn with_store(store: Arc<dyn SessionStore>) -> Self {
+        Self {
             sessions: Arc::new(RwLock::new(HashMap::new())),
-            storage_path: Some(path),
-        })
+            store: Some(store),
+            ttl: None,
+        }
+    }
+
+    /// Expire sessions that have not been updated for `ttl`.
+    ///
+    /// Expiry only happens when [`prune_expired`](Self::prune_expired) is
+    /// called; the gateway does so on a timer.
+    pub fn with_ttl(mut self, ttl: std::time::Duration) -> Self {
+        self.ttl = chrono::Duration::from_std(ttl).ok();
+        self
+    }
+
+    /// The configured session TTL, if any.
+    pub fn ttl(&self) -> Option<std::time::Duration> {
+        self.ttl.and_then(|ttl| ttl.to_std().ok())
     }
 
     /// Get an existing session or create a new one.
     ///
     /// If the session exists in memory, it is returned immediately.
-    /// If persistence is enabled and the session exists on disk, it
+    /// If persistence is enabled and the session exists in the store, it
     /// is loaded into memory. Otherwise, a new empty session is created.
     ///
     /// # Arguments
@@ -140,7 +211,7 @@ impl SessionManager {
     ///
     /// # Errors
     ///
-    /// Returns an error if loading from disk fails.
+    /// Returns an error if loading from the store fails.
     ///
     /// # Example
     /// ```
@@ -154,33 +225,14 @@ impl SessionManager {
     /// }
     /// ```
     pub async fn get_or_create(&self, key: &str) -> Result<Session> {
-        // Check in-memory cache first
-        {
-            let sessions = self.sessions.read().await;
-            if let Some(session) = sessions.get(key) {
-                return Ok(session.clone());
-            }
-        }
-
-        // Try loading from disk if persistence is enabled
-        if let Some(ref storage_path) = self.storage_path {
-            let file_path = storage_path.join(format!("{}.json", Self::sanitize_key(key)));
-            if file_path.exists() {
-                let content = tokio::fs::read_to_string(&file_path).await?;
-                let session: Session = serde_json::from_str(&content)?;
-
-                // Cache it in memory
-                let mut sessions = self.sessions.write().await;
-                sessions.insert(key.to_string(), session.clone());
-                return Ok(session);
-            }
+        if let Some(session) = self.get(key).await? {
+            return Ok(session);
         }
 
         // Create new session
         let session = Session::new(key);
         let mut sessions = self.sessions.write().await;
-        sessions.insert(key.to_string(), session.clone());
-        Ok(session)
+        Ok(sessions.entry(key.to_string()).or_insert(session).clone())
     }
 
     /// Get a session by key without creating it.
@@ -194,7 +246,7 @@ impl SessionManager {
     ///
     /// # Errors
     ///
-    /// Returns an error if loading from disk fails.
+    /// Returns an error if loading from the store fails.
     pub async fn get(&self, key: &str) -> Result<Option<Session>> {
         // Check in-memory cache first
         {
@@ -204,13 +256,9 @@ impl SessionManager {
             }
         }
 
-        // Try loading from disk if persistence is enabled
-        if let Some(ref storage_path) = self.storage_path {
-            let file_path = storage_path.join(format!("{}.json", Self::sanitize_key(key)));
-            if file_path.exists() {
-                let content = tokio::fs::read_to_string(&file_path).await?;
-                let session: Session = serde_json::from_str(&content)?;
-
+        // Try loading from the store if persistence is enabled
+        if let Some(ref store) = self.store {
+            if let Some(session) = store.load(key).await? {
                 // Cache it in memory
                 let mut sessions = self.sessions.write().await;
                 sessions.insert(key.to_string(), session.clone());
@@ -221,14 +269,14 @@ impl SessionManager {
         Ok(None)
     }
 
-    /// Save a session to both memory and disk (if persistence is enabled).
+    /// Save a session to memory and the store (if persistence is enabled).
     ///
     /// # Arguments
     /// * `session` - The session to save
     ///
     /// # Errors
     ///
-    /// Returns an error if writing to disk fails.
+    /// Returns an error if writing to the store fails.
     ///
     /// # Example
     /// ```
@@ -249,24 +297,21 @@ impl SessionManager {
             sessions.insert(session.key.clone(), session.clone());
         }
 
-        // Write to disk if persistence is enabled
-        if let Some(ref storage_path) = self.storage_path {
-            let file_path = storage_path.join(format!("{}.json", Self::sanitize_key(&session.key)));
-            let content = serde_json::to_string_pretty(session)?;
-            tokio::fs::write(&file_path, content).await?;
+        if let Some(ref store) = self.store {
+            store.save(session).await?;
         }
 
         Ok(())
     }
 
-    /// Delete a session from both memory and disk.
+    /// Delete a session from memory and the store.
     ///
     /// # Arguments
     /// * `key` - Unique session identifier
     ///
     /// # Errors
     ///
-    /// Returns an error if deleting from disk fails.
+    /// Returns an error if deleting from the store fails.
     ///
     /// # Example
     /// ```
@@ -286,12 +331,8 @@ impl SessionManager {
             sessions.remove(key);
         }
 
-        // Remove from disk if persistence is enabled
-        if let Some(ref storage_path) = self.storage_path {
-            let file_path = storage_path.join(format!("{}.json", Self::sanitize_key(key)));
-            if file_path.exists() {
-                tokio::fs::remove_file(&file_path).await?;
-            }
+        if let Some(ref store) = self.store {
+            store.delete(key).await?;
         }
 
         Ok(())
@@ -299,12 +340,12 @@ impl SessionManager {
 
     /// List all session keys.
     ///
-    /// Returns session keys from both memory and disk (if persistence is enabled).
-    /// Duplicate keys are not included.
+    /// Returns session keys from both memory and the store (if persistence
+    /// is enabled). Duplicate keys are not included.
     ///
     /// # Errors
     ///
-    /// Returns an error if reading the storage directory fails.
+    /// Returns an error if listing the store fails.
     ///
     /// # Example
     /// ```
@@ -321,34 +362,17 @@ impl SessionManager {
     /// }
     /// ```
     pub async fn list(&self) -> Result<Vec<String>> {
-        let mut keys = Vec::new();
-
-        // Get keys from memory
-        {
+        let mut keys: Vec<String> = {
             let sessions = self.sessions.read().await;
-            keys.extend(sessions.keys().cloned());
-        }
+            sessions.keys().cloned().collect()
+        };
 
-        // Get keys from disk if persistence is enabled
-        // We read each session file to get the actual key (not the sanitized filename)
-        if let Some(ref storage_path) = self.storage_path {
-            let mut dir_entries = tokio::fs::read_dir(storage_path).await?;
-            while let Some(entry) = dir_entries.next_entry().await? {
-                let path = entry.path();
-                if path.extension().map(|e| e == "json").unwrap_or(false) {
-                    // Read the session file to get the actual key
-                    if let Ok(content) = tokio::fs::read_to_string(&path).await {
-                        if let Ok(session) = serde_json::from_str::<Session>(&content) {
-                            if !keys.contains(&session.key) {
-                                keys.push(session.key);
-                            }
-                        }
-                    }
-                }
-            }
+        if let Some(ref store) = self.store {
+            keys.extend(store.list().await?);
         }
 
         keys.sort();
+        keys.dedup();
         Ok(keys)
     }
 
@@ -359,7 +383,7 @@ impl SessionManager {
     ///
     /// # Returns
     ///
-    /// `true` if the session exists in memory or on disk.
+    /// `true` if the session exists in memory or in the store.
     pub async fn exists(&self, key: &str) -> bool {
         // Check memory
         {
@@ -369,16 +393,82 @@ impl SessionManager {
             }
         }
 
-        // Check disk
-        if let Some(ref storage_path) = self.storage_path {
-            let file_path = storage_path.join(format!("{}.json", Self::sanitize_key(key)));
-            return file_path.exists();
+        match self.store {
+            Some(ref store) => store.exists(key).await.unwrap_or(false),
+            None => false,
         }
+    }
+
+    /// Delete every unpinned session that has not been updated within
+    /// `max_age`.
+    ///
+    /// Stale sessions are removed from both the cache and the store.
+    /// Sessions with `keep` set are never removed.
+    /// Returns the keys that were removed, sorted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store fails to collect stale sessions.
+    pub async fn gc(&self, max_age: chrono::Duration) -> Result<Vec<String>> {
+        let cutoff = chrono::Utc::now() - max_age;
+
+        let mut removed: Vec<String> = {
+            let mut sessions = self.sessions.write().await;
+            let stale: Vec<String> = sessions
+                .values()
+                .filter(|s| s.updated_at < cutoff && !s.keep)
+                .map(|s| s.key.clone())
+                .collect();
+            for key in &stale {
+                sessions.remove(key);
+            }
+            stale
+        };
+
+        if let Some(ref store) = self.store {
+            removed.extend(store.gc(cutoff).await?);
+        }
+
+        removed.sort();
+        removed.dedup();
+        Ok(removed)
+    }
+
+    /// Delete sessions older than the configured TTL.
+    ///
+    /// Does nothing when no TTL is set. Returns the removed keys, sorted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store fails to collect stale sessions.
+    pub async fn prune_expired(&self) -> Result<Vec<String>> {
+        let Some(ttl) = self.ttl else {
+            return Ok(Vec::new());
+        };
+        let removed = self.gc(ttl).await?;
+        if !removed.is_empty() {
+            info!(count = removed.len(), "Pruned expired sessions");
+        }
+        Ok(removed)
+    }
 
-        false
+    /// Pin or unpin a session so TTL expiry and `gc` skip it.
+    ///
+    /// Returns `false` if no session exists for `key`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if loading or saving the session fails.
+    pub async fn set_keep(&self, key: &str, keep: bool) -> Result<bool> {
+        let Some(mut session) = self.get(key).await? else {
+            return Ok(false);
+        };
+        session.keep = keep;
+        self.save(&session).await?;
+        Ok(true)
     }
 
-    /// Clear all sessions from memory (does not affect disk).
+    /// Clear all sessions from memory (does not affect the store).
     ///
     /// Use this to free memory while keeping persisted sessions.
     pub async fn clear_cache(&self) {
@@ -391,73 +481,14 @@ impl SessionManager {
         let sessions = self.sessions.read().await;
         sessions.len()
     }
-
-    /// Sanitize a session key for use as a filename.
-    ///
-    /// Uses percent-encoding to ensure the mapping is bijective (one-to-one).
-    /// This prevents collisions where different keys would map to the same filename.
-    ///
-    /// For example:
-    /// - "telegram:chat123" → "telegram%3Achat123"
-    /// - "discord/server" → "discord%2Fserver"
-    ///
-    /// This is reversible via `unsanitize_key`, ensuring keys round-trip correctly.
-    fn sanitize_key(key: &str) -> String {
-        // Characters that are problematic in filenames across platforms
-        // We percent-encode them to make the mapping reversible
-        let mut result = String::with_capacity(key.len() * 3);
-        for c in key.chars() {
-            match c {
-                '/' => result.push_str("%2F"),
-                '\\' => result.push_str("%5C"),
-                ':' => result.push_str("%3A"),
-                '*' => result.push_str("%2A"),
-                '?' => result.push_str("%3F"),
-                '"' => result.push_str("%22"),
-                '<' => result.push_str("%3C"),
-                '>' => result.push_str("%3E"),
-                '|' => result.push_str("%7C"),
-                '%' => result.push_str("%25"), // Escape % itself to make it reversible
-                c => result.push(c),
-            }
-        }
-        result
-    }
-
-    /// Reverse the sanitization to recover the original key.
-    ///
-    /// This is the inverse of `sanitize_key`.
-    #[allow(dead_code)]
-    fn unsanitize_key(sanitized: &str) -> String {
-        let mut result = String::with_capacity(sanitized.len());
-        let mut chars = sanitized.chars().peekable();
-
-        while let Some(c) = chars.next() {
-            if c == '%' {
-                // Try to read two hex digits
-                let hex: String = chars.by_ref().take(2).collect();
-                if hex.len() == 2 {
-                    if let Ok(byte) = u8::from_str_radix(&hex, 16) {
-                        result.push(byte as char);
-                        continue;
-                    }
-                }
-                // If parsing failed, just keep the % and the hex chars
-                result.push('%');
-                result.push_str(&hex);
-            } else {
-                result.push(c);
-            }
-        }
-        result
-    }
 }
 
 impl Clone for SessionManager {
     fn clone(&self) -> Self {
         Self {
             sessions: Arc::clone(&self.sessions),
-            storage_path: self.storage_path.clone(),
+            store: self.store.clone(),
+            ttl: self.ttl,
         }
     }
 }
@@ -640,71 +671,6 @@ mod tests {
         assert!(keys.contains(&"gamma".to_string()));
     }
 
-    #[test]
-    fn test_sanitize_key() {
-        // Simple keys pass through unchanged
-        assert_eq!(SessionManager::sanitize_key("simple"), "simple");
-        // Special characters are percent-encoded
-        assert_eq!(
-            SessionManager::sanitize_key("telegram:chat123"),
-            "telegram%3Achat123"
-        );
-        assert_eq!(
-            SessionManager::sanitize_key("path/to/session"),
-            "path%2Fto%2Fsession"
-        );
-        assert_eq!(
-            SessionManager::sanitize_key("a:b/c\\d*e?f\"g<h>i|j"),
-            "a%3Ab%2Fc%5Cd%2Ae%3Ff%22g%3Ch%3Ei%7Cj"
-        );
-        // Percent itself is escaped to make encoding reversible
-        assert_eq!(SessionManager::sanitize_key("100%done"), "100%25done");
-    }
-
-    #[test]
-    fn test_unsanitize_key() {
-        // Round-trip: sanitize then unsanitize should return original
-        let keys = [
-            "simple",
-            "telegram:chat123",
-            "path/to/session",
-            "a:b/c\\d*e?f\"g<h>i|j",
-            "100%done",
-            "multi%percent%%test",
-        ];
-        for key in &keys {
-            let sanitized = SessionManager::sanitize_key(key);
-            let unsanitized = SessionManager::unsanitize_key(&sanitized);
-            assert_eq!(
-                unsanitized, *key,
-                "Key '{}' should round-trip through sanitize/unsanitize",
-                key
-            );
-        }
-    }
-
-    #[test]
-    fn test_sanitize_key_no_collisions() {
-        // Keys that would collide with the old underscore-replacement approach
-        // should now produce different sanitized values
-        let key1 = "a:b";
-        let key2 = "a/b";
-        let key3 = "a_b"; // This one has an actual underscore
-
-        let sanitized1 = SessionManager::sanitize_key(key1);
-        let sanitized2 = SessionManager::sanitize_key(key2);
-        let sanitized3 = SessionManager::sanitize_key(key3);
-
-        assert_ne!(sanitized1, sanitized2, "a:b and a/b should not collide");
-        assert_ne!(sanitized1, sanitized3, "a:b and a_b should not collide");
-        assert_ne!(sanitized2, sanitized3, "a/b and a_b should not collide");
-
-        // Verify the actual values
-        assert_eq!(sanitized1, "a%3Ab");
-        assert_eq!(sanitized2, "a%2Fb");
-        assert_eq!(sanitized3, "a_b");
-    }
-
     #[tokio::test]
     async fn test_list_returns_original_keys_with_special_chars() {
         // Regression test: list() should return original keys, not sanitized filenames
@@ -790,6 +756,119 @@ mod tests {
         assert_eq!(loaded.messages[4].role, Role::Assistant);
     }
 
+    #[tokio::test]
+    async fn test_gc_removes_stale_sessions_from_cache_and_store() {
+        let temp_dir = TempDir::new().unwrap();
+        let stores: Vec<Arc<dyn SessionStore>> = vec![
+            Arc::new(FileSessionStore::new(temp_dir.path().to_path_buf()).unwrap()),
+            Arc::new(MemorySessionStore::new()),
+        ];
+
+        for store in stores {
+            let manager = SessionManager::with_store(store);
+            let mut stale = manager.get_or_create("stale").await.unwrap();
+            stale.updated_at = chrono::Utc::now() - chrono::Duration::days(10);
+            manager.save(&stale).await.unwrap();
+            let fresh = manager.get_or_create("fresh").await.unwrap();
+            manager.save(&fresh).await.unwrap();
+
+            let removed = manager.gc(chrono::Duration::days(1)).await.unwrap();
+            assert_eq!(removed, vec!["stale"]);
+            assert!(!manager.exists("stale").await);
+            assert_eq!(manager.list().await.unwrap(), vec!["fresh"]);
+
+            manager.clear_cache().await;
+            assert!(manager.get("stale").await.unwrap().is_none());
+            assert!(manager.get("fresh").await.unwrap().is_some());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_prune_expired_removes_old_sessions_and_skips_pinned() {
+        let manager = SessionManager::with_store(Arc::new(MemorySessionStore::new()))
+            .with_ttl(std::time::Duration::from_secs(3600));
+        let old = chrono::Utc::now() - chrono::Duration::hours(2);
+
+        let mut stale = manager.get_or_create("stale").await.unwrap();
+        stale.updated_at = old;
+        manager.save(&stale).await.unwrap();
+        let mut pinned = manager.get_or_create("pinned").await.unwrap();
+        pinned.updated_at = old;
+        manager.save(&pinned).await.unwrap();
+        assert!(manager.set_keep("pinned", true).await.unwrap());
+        let mut recent = manager.get_or_create("recent").await.unwrap();
+        recent.add_message(Message::user("still here"));
+        manager.save(&recent).await.unwrap();
+
+        assert_eq!(manager.prune_expired().await.unwrap(), vec!["stale"]);
+        assert_eq!(manager.list().await.unwrap(), vec!["pinned", "recent"]);
+
+        manager.clear_cache().await;
+        assert!(manager.get("pinned").await.unwrap().unwrap().keep);
+        assert!(!manager.set_keep("stale", true).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_prune_expired_without_ttl_is_noop() {
+        let manager = SessionManager::new_memory();
+        let mut stale = manager.get_or_create("stale").await.unwrap();
+        stale.updated_at = chrono::Utc::now() - chrono::Duration::days(365);
+        manager.save(&stale).await.unwrap();
+
+        assert!(manager.ttl().is_none());
+        assert!(manager.prune_expired().await.unwrap().is_empty());
+        assert!(manager.exists("stale").await);
+    }
+
+    #[test]
+    fn test_from_config_applies_ttl() {
+        let mut config = Config::default();
+        config.session.backend = SessionBackend::Memory;
+        assert!(SessionManager::from_config(&config)
+            .unwrap()
+            .ttl()
+            .is_none());
+
+        config.session.ttl_secs = 600;
+        assert_eq!(
+            SessionManager::from_config(&config).unwrap().ttl(),
+            Some(std::time::Duration::from_secs(600))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_from_config_memory_backend() {
+        let config = Config {
+            session: SessionConfig {
+                backend: SessionBackend::Memory,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let manager = SessionManager::from_config(&config).unwrap();
+        let session = manager.get_or_create("cfg").await.unwrap();
+        manager.save(&session).await.unwrap();
+
+        // Survives a cache clear because the store keeps it
+        manager.clear_cache().await;
+        assert!(manager.exists("cfg").await);
+    }
+
+    #[cfg(not(feature = "sqlite-sessions"))]
+    #[test]
+    fn test_from_config_sqlite_backend_requires_feature() {
+        let config = Config {
+            session: SessionConfig {
+                backend: SessionBackend::Sqlite,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let err = SessionManager::from_config(&config).err().unwrap();
+        assert!(matches!(err, crate::error::ZeptoError::Config(_)));
+        assert!(err.to_string().contains("sqlite-sessions"));
+    }
+
     #[tokio::test]
     async fn test_session_default() {
         let manager = SessionManager::default();
@@ -0,0 +1,465 @@
+//! SQLite session storage backend (feature `sqlite-sessions`).
+//!
+//! [`SqliteSessionStore`] keeps every session in a single database file
+//! instead of one JSON file per session. Each row holds the session key, the
+//! serialized session and its timestamps; `updated_at` is indexed so garbage
+//! collection does not scan the whole table.
+//!
+//! The database runs in WAL mode, so other processes (e.g. `zeptoclaw
+//! history`) can read while the gateway writes. Within the process, writes
+//! go through one connection and reads through a small pool of read-only
+//! connections, so loading a session never waits behind a save. Every query
+//! runs on the blocking thread pool.
+//!
+//! On first open, sessions from the file backend's directory are imported
+//! once (see [`SqliteSessionStore::import_file_sessions`]).
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use async_trait::async_trait;
+use chrono::{DateTime, SecondsFormat, Utc};
+use rusqlite::{params, Connection, OpenFlags, OptionalExtension};
+use tracing::{info, warn};
+
+use super::store::SessionStore;
+use super::types::Session;
+use crate::error::{Result, ZeptoError};
+
+/// Marker row recording that file sessions were imported.
+const FILE_IMPORT_MARKER: &str = "file_sessions_imported";
+
+/// Number of read-only connections kept open alongside the writer.
+const READ_CONNECTIONS: usize = 4;
+
+const BUSY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS sessions (
+    key TEXT PRIMARY KEY NOT NULL,
+    data TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_sessions_updated_at ON sessions (updated_at);
+CREATE TABLE IF NOT EXISTS store_meta (
+    name TEXT PRIMARY KEY NOT NULL,
+    value TEXT NOT NULL
+);
+";
+
+fn sql_err(e: rusqlite::Error) -> ZeptoError {
+    ZeptoError::Session(format!("SQLite session store: {}", e))
+}
+
+/// Timestamps are stored as fixed-width UTC RFC 3339 so they sort as text.
+fn timestamp(ts: &DateTime<Utc>) -> String {
+    ts.to_rfc3339_opts(SecondsFormat::Micros, true)
+}
+
+fn lock_conn(conn: &Mutex<Connection>) -> Result<MutexGuard<'_, Connection>> {
+    conn.lock()
+        .map_err(|_| ZeptoError::Session("SQLite session store lock poisoned".into()))
+}
+
+/// Fixed set of read-only connections.
+///
+/// A read takes the first idle connection, starting from a rotating index,
+/// and only waits when every connection is busy.
+struct ReadPool {
+    conns: Vec<Mutex<Connection>>,
+    next: AtomicUsize,
+}
+
+impl ReadPool {
+    fn open(path: &Path, size: usize) -> Result<Self> {
+        let conns = (0..size)
+            .map(|_| {
+                let conn = Connection::open_with_flags(
+                    path,
+                    OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+                )
+                .map_err(sql_err)?;
+                conn.busy_timeout(BUSY_TIMEOUT).map_err(sql_err)?;
+                Ok(Mutex::new(conn))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            conns,
+            next: AtomicUsize::new(0),
+        })
+    }
+
+    fn get(&self) -> Result<MutexGuard<'_, Connection>> {
+        let start = self.next.fetch_add(1, Ordering::Relaxed) % self.conns.len();
+        for i in 0..self.conns.len() {
+            if let Ok(conn) = self.conns[(start + i) % self.conns.len()].try_lock() {
+                return Ok(conn);
+            }
+        }
+        lock_conn(&self.conns[start])
+    }
+}
+
+/// Stores sessions in a SQLite database file.
+pub struct SqliteSessionStore {
+    path: PathBuf,
+    writer: Arc<Mutex<Connection>>,
+    readers: Arc<ReadPool>,
+}
+
+impl SqliteSessionStore {
+    /// Open (creating if needed) the database at `path`.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let conn = Connection::open(&path).map_err(sql_err)?;
+        conn.pragma_update(None, "journal_mode", "WAL")
+            .map_err(sql_err)?;
+        conn.pragma_update(None, "synchronous", "NORMAL")
+            .map_err(sql_err)?;
+        conn.busy_timeout(BUSY_TIMEOUT).map_err(sql_err)?;
+        conn.execute_batch(SCHEMA).map_err(sql_err)?;
+        // Readers open after the writer so the schema and WAL mode exist.
+        let readers = ReadPool::open(&path, READ_CONNECTIONS)?;
+        Ok(Self {
+            path,
+            writer: Arc::new(Mutex::new(conn)),
+            readers: Arc::new(readers),
+        })
+    }
+
+    /// Path of the database file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Import sessions saved by the file backend in `dir`, once.
+    ///
+    /// Sessions already in the database are kept; unparseable files are
+    /// skipped. The import is recorded in the database, so later calls
+    /// return `Ok(0)` without reading `dir`. Returns the number of sessions
+    /// imported.
+    pub fn import_file_sessions(&self, dir: &Path) -> Result<usize> {
+        let mut conn = self.lock()?;
+        let done: Option<String> = conn
+            .query_row(
+                "SELECT value FROM store_meta WHERE name = ?1",
+                params![FILE_IMPORT_MARKER],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(sql_err)?;
+        if done.is_some() {
+            return Ok(0);
+        }
+
+        let mut sessions = Vec::new();
+        if dir.is_dir() {
+            for entry in std::fs::read_dir(dir)? {
+                let path = entry?.path();
+                if path.extension().is_none_or(|e| e != "json") {
+                    continue;
+                }
+                let parsed = std::fs::read_to_string(&path)
+                    .ok()
+                    .and_then(|content| serde_json::from_str::<Session>(&content).ok());
+                match parsed {
+                    Some(session) => sessions.push(session),
+                    None => warn!(path = %path.display(), "Skipping unreadable session file"),
+                }
+            }
+        }
+
+        let tx = conn.transaction().map_err(sql_err)?;
+        let mut imported = 0;
+        for session in &sessions {
+            imported += tx
+                .execute(
+                    "INSERT OR IGNORE INTO sessions (key, data, created_at, updated_at)
+                     VALUES (?1, ?2, ?3, ?4)",
+                    params![
+                        session.key,
+                        serde_json::to_string(session)?,
+                        timestamp(&session.created_at),
+                        timestamp(&session.updated_at),
+                    ],
+                )
+                .map_err(sql_err)?;
+        }
+        tx.execute(
+            "INSERT OR REPLACE INTO store_meta (name, value) VALUES (?1, ?2)",
+            params![FILE_IMPORT_MARKER, timestamp(&Utc::now())],
+        )
+        .map_err(sql_err)?;
+        tx.commit().map_err(sql_err)?;
+
+        if imported > 0 {
+            info!(count = imported, from = %dir.display(), "Imported file sessions into SQLite");
+        }
+        Ok(imported)
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, Connection>> {
+        lock_conn(&self.writer)
+    }
+
+    /// Run `f` with the writer connection on the blocking thread pool.
+    async fn with_writer<T, F>(&self, f: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut Connection) -> Result<T> + Send + 'static,
+    {
+        let writer = Arc::clone(&self.writer);
+        run_blocking(move || f(&mut *lock_conn(&writer)?)).await
+    }
+
+    /// Run `f` with a pooled read-only connection on the blocking thread pool.
+    async fn with_reader<T, F>(&self, f: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Connection) -> Result<T> + Send + 'static,
+    {
+        let readers = Arc::clone(&self.readers);
+        run_blocking(move || f(&*readers.get()?)).await
+    }
+}
+
+async fn run_blocking<T, F>(f: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T> + Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| ZeptoError::Session(format!("SQLite session task failed: {}", e)))?
+}
+
+#[async_trait]
+impl SessionStore for SqliteSessionStore {
+    async fn load(&self, key: &str) -> Result<Option<Session>> {
+        let key = key.to_string();
+        let data: Option<String> = self
+            .with_reader(move |conn| {
+                conn.query_row(
+                    "SELECT data FROM sessions WHERE key = ?1",
+                    params![key],
+                    |row| row.get(0),
+                )
+                .optional()
+                .map_err(sql_err)
+            })
+            .await?;
+        data.map(|d| serde_json::from_str(&d).map_err(Into::into))
+            .transpose()
+    }
+
+    async fn save(&self, session: &Session) -> Result<()> {
+        let data = serde_json::to_string(session)?;
+        let key = session.key.clone();
+        let created_at = timestamp(&session.created_at);
+        let updated_at = timestamp(&session.updated_at);
+        self.with_writer(move |conn| {
+            conn.execute(
+                "INSERT INTO sessions (key, data, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4)
+                 ON CONFLICT (key) DO UPDATE SET
+                     data = excluded.data,
+                     created_at = excluded.created_at,
+                     updated_at = excluded.updated_at",
+                params![key, data, created_at, updated_at],
+            )
+            .map_err(sql_err)?;
+            Ok(())
+        })
+        .await
+    }
+
+    async fn delete(&self, key: &str) -> Result<bool> {
+        let key = key.to_string();
+        self.with_writer(move |conn| {
+            let removed = conn
+                .execute("DELETE FROM sessions WHERE key = ?1", params![key])
+                .map_err(sql_err)?;
+            Ok(removed > 0)
+        })
+        .await
+    }
+
+    async fn list(&self) -> Result<Vec<String>> {
+        self.with_reader(|conn| {
+            let mut stmt = conn
+                .prepare("SELECT key FROM sessions ORDER BY key")
+                .map_err(sql_err)?;
+            let keys = stmt
+                .query_map([], |row| row.get(0))
+                .map_err(sql_err)?
+                .collect::<rusqlite::Result<Vec<String>>>()
+                .map_err(sql_err)?;
+            Ok(keys)
+        })
+        .await
+    }
+
+    async fn exists(&self, key: &str) -> Result<bool> {
+        let key = key.to_string();
+        self.with_reader(move |conn| {
+            conn.query_row(
+                "SELECT 1 FROM sessions WHERE key = ?1",
+                params![key],
+                |_| Ok(()),
+            )
+            .optional()
+            .map(|row| row.is_some())
+            .map_err(sql_err)
+        })
+        .await
+    }
+
+    async fn gc(&self, cutoff: DateTime<Utc>) -> Result<Vec<String>> {
+        let cutoff = timestamp(&cutoff);
+        self.with_writer(move |conn| {
+            let tx = conn.transaction().map_err(sql_err)?;
+            let removed = {
+                let mut stmt = tx
+                    .prepare(
+                        "SELECT key FROM sessions
+                         WHERE updated_at < ?1 AND NOT coalesce(json_extract(data, '$.keep'), 0)
+                         ORDER BY key",
+                    )
+                    .map_err(sql_err)?;
+                let keys = stmt
+                    .query_map(params![cutoff], |row| row.get(0))
+                    .map_err(sql_err)?
+                    .collect::<rusqlite::Result<Vec<String>>>()
+                    .map_err(sql_err)?;
+                keys
+            };
+            tx.execute(
+                "DELETE FROM sessions
+                 WHERE updated_at < ?1 AND NOT coalesce(json_extract(data, '$.keep'), 0)",
+                params![cutoff],
+            )
+            .map_err(sql_err)?;
+            tx.commit().map_err(sql_err)?;
+            Ok(removed)
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::store::tests::run_store_suite;
+    use crate::session::{FileSessionStore, Message};
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_sqlite_store_suite() {
+        let dir = TempDir::new().unwrap();
+        let store = SqliteSessionStore::open(dir.path().join("sessions.db")).unwrap();
+        run_store_suite(&store).await;
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_store_persists_across_reopen() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("sessions.db");
+        {
+            let store = SqliteSessionStore::open(&path).unwrap();
+            let mut session = Session::new("telegram:1");
+            session.add_message(Message::user("Hello"));
+            store.save(&session).await.unwrap();
+        }
+
+        let store = SqliteSessionStore::open(&path).unwrap();
+        let loaded = store.load("telegram:1").await.unwrap().unwrap();
+        assert_eq!(loaded.messages[0].content, "Hello");
+        let mode: String = store
+            .lock()
+            .unwrap()
+            .query_row("PRAGMA journal_mode", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(mode, "wal");
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_store_imports_file_sessions_once() {
+        let dir = TempDir::new().unwrap();
+        let files = FileSessionStore::new(dir.path().join("sessions")).unwrap();
+        let mut old = Session::new("discord:42");
+        old.add_message(Message::user("from disk"));
+        files.save(&old).await.unwrap();
+        std::fs::write(dir.path().join("sessions/broken.json"), "{oops").unwrap();
+
+        let store = SqliteSessionStore::open(dir.path().join("sessions.db")).unwrap();
+        // A session already in the database wins over the file copy.
+        let mut newer = Session::new("slack:7");
+        newer.add_message(Message::user("in sqlite"));
+        store.save(&newer).await.unwrap();
+        let mut stale_copy = Session::new("slack:7");
+        stale_copy.add_message(Message::user("stale file copy"));
+        files.save(&stale_copy).await.unwrap();
+
+        let sessions_dir = dir.path().join("sessions");
+        assert_eq!(store.import_file_sessions(&sessions_dir).unwrap(), 1);
+        assert_eq!(
+            store.list().await.unwrap(),
+            vec!["discord:42".to_string(), "slack:7".to_string()]
+        );
+        let slack = store.load("slack:7").await.unwrap().unwrap();
+        assert_eq!(slack.messages[0].content, "in sqlite");
+
+        // Second run is a no-op even if the files changed.
+        files.save(&Session::new("later")).await.unwrap();
+        assert_eq!(store.import_file_sessions(&sessions_dir).unwrap(), 0);
+        assert!(!store.exists("later").await.unwrap());
+    }
+
+    #[test]
+    fn test_sqlite_store_reads_do_not_wait_for_writer() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let dir = TempDir::new().unwrap();
+        let store = SqliteSessionStore::open(dir.path().join("sessions.db")).unwrap();
+        rt.block_on(store.save(&Session::new("telegram:1")))
+            .unwrap();
+
+        // Hold the writer while reading; loads must use the read pool.
+        let _writer = store.lock().unwrap();
+        let result = rt
+            .block_on(async {
+                tokio::time::timeout(std::time::Duration::from_secs(5), async {
+                    let loaded = store.load("telegram:1").await.unwrap();
+                    (loaded.is_some(), store.list().await.unwrap().len())
+                })
+                .await
+            })
+            .expect("reads blocked behind the writer");
+        assert_eq!(result, (true, 1));
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_store_concurrent_saves() {
+        let dir = TempDir::new().unwrap();
+        let store = Arc::new(SqliteSessionStore::open(dir.path().join("sessions.db")).unwrap());
+
+        let handles: Vec<_> = (0..16)
+            .map(|i| {
+                let store = Arc::clone(&store);
+                tokio::spawn(async move {
+                    let mut session = Session::new(&format!("chat:{:02}", i));
+                    session.add_message(Message::user("hi"));
+                    store.save(&session).await.unwrap();
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.await.unwrap();
+        }
+        assert_eq!(store.list().await.unwrap().len(), 16);
+    }
+}
@@ -0,0 +1,459 @@
+//! Pluggable session storage backends.
+//!
+//! [`SessionManager`](super::SessionManager) keeps an in-memory cache of
+//! sessions and delegates persistence to a [`SessionStore`]. Two backends are
+//! provided:
+//!
+//! - [`FileSessionStore`] - one JSON file per session (the default)
+//! - [`MemorySessionStore`] - process-local storage, useful for tests and
+//!   ephemeral deployments
+//!
+//! With the `sqlite-sessions` feature, `SqliteSessionStore` (in
+//! `session::sqlite`) keeps all sessions in one database file.
+//!
+//! The backend is selected with `session.backend` (or `sessions.backend`) in
+//! the config file.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+use super::types::Session;
+use crate::error::Result;
+
+/// Which storage backend the session manager persists to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SessionBackend {
+    /// JSON files under `~/.zeptoclaw/sessions/`.
+    #[default]
+    #[serde(alias = "file")]
+    Filesystem,
+    /// Process-local storage; sessions are lost on restart.
+    Memory,
+    /// SQLite database at `~/.zeptoclaw/sessions.db` (requires the
+    /// `sqlite-sessions` feature).
+    Sqlite,
+}
+
+/// Session storage configuration (`session` section).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionConfig {
+    /// Storage backend for conversation sessions.
+    pub backend: SessionBackend,
+    /// Delete sessions not updated for this many seconds (0 = keep forever).
+    pub ttl_secs: u64,
+}
+
+/// Persistence backend for conversation sessions.
+#[async_trait]
+pub trait SessionStore: Send + Sync {
+    /// Load a session by key, returning `None` if it is not stored.
+    async fn load(&self, key: &str) -> Result<Option<Session>>;
+
+    /// Insert or replace a session.
+    async fn save(&self, session: &Session) -> Result<()>;
+
+    /// Delete a session. Returns true if it existed.
+    async fn delete(&self, key: &str) -> Result<bool>;
+
+    /// List the keys of all stored sessions, sorted.
+    async fn list(&self) -> Result<Vec<String>>;
+
+    /// Returns true if a session with this key is stored.
+    async fn exists(&self, key: &str) -> Result<bool>;
+
+    /// Delete every unpinned session last updated before `cutoff`.
+    ///
+    /// Returns the keys of the removed sessions, sorted.
+    async fn gc(&self, cutoff: DateTime<Utc>) -> Result<Vec<String>>;
+}
+
+/// Stores each session as a pretty-printed JSON file named after its key.
+pub struct FileSessionStore {
+    dir: PathBuf,
+}
+
+impl FileSessionStore {
+    /// Create a store rooted at `dir`, creating the directory if needed.
+    pub fn new(dir: PathBuf) -> Result<Self> {
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    /// Directory the session files live in.
+    pub fn dir(&self) -> &PathBuf {
+        &self.dir
+    }
+
+    fn path_for(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", Self::sanitize_key(key)))
+    }
+
+    /// Read every parseable session file in the directory.
+    ///
+    /// Files that cannot be read or parsed are skipped.
+    async fn read_all(&self) -> Result<Vec<Session>> {
+        let mut sessions = Vec::new();
+        let mut dir_entries = tokio::fs::read_dir(&self.dir).await?;
+        while let Some(entry) = dir_entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().map(|e| e == "json").unwrap_or(false) {
+                // Read the session file to get the actual key (not the sanitized filename)
+                if let Ok(content) = tokio::fs::read_to_string(&path).await {
+                    if let Ok(session) = serde_json::from_str::<Session>(&content) {
+                        sessions.push(session);
+                    }
+                }
+            }
+        }
+        Ok(sessions)
+    }
+
+    /// Sanitize a session key for use as a filename.
+    ///
+    /// Uses percent-encoding to ensure the mapping is bijective (one-to-one).
+    /// This prevents collisions where different keys would map to the same filename.
+    ///
+    /// For example:
+    /// - "telegram:chat123" → "telegram%3Achat123"
+    /// - "discord/server" → "discord%2Fserver"
+    ///
+    /// This is reversible via `unsanitize_key`, ensuring keys round-trip correctly.
+    fn sanitize_key(key: &str) -> String {
+        // Characters that are problematic in filenames across platforms
+        // We percent-encode them to make the mapping reversible
+        let mut result = String::with_capacity(key.len() * 3);
+        for c in key.chars() {
+            match c {
+                '/' => result.push_str("%2F"),
+                '\\' => result.push_str("%5C"),
+                ':' => result.push_str("%3A"),
+                '*' => result.push_str("%2A"),
+                '?' => result.push_str("%3F"),
+                '"' => result.push_str("%22"),
+                '<' => result.push_str("%3C"),
+                '>' => result.push_str("%3E"),
+                '|' => result.push_str("%7C"),
+                '%' => result.push_str("%25"), // Escape % itself to make it reversible
+                c => result.push(c),
+            }
+        }
+        result
+    }
+
+    /// Reverse the sanitization to recover the original key.
+    ///
+    /// This is the inverse of `sanitize_key`.
+    #[allow(dead_code)]
+    fn unsanitize_key(sanitized: &str) -> String {
+        let mut result = String::with_capacity(sanitized.len());
+        let mut chars = sanitized.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            if c == '%' {
+                // Try to read two hex digits
+                let hex: String = chars.by_ref().take(2).collect();
+                if hex.len() == 2 {
+                    if let Ok(byte) = u8::from_str_radix(&hex, 16) {
+                        result.push(byte as char);
+                        continue;
+                    }
+                }
+                // If parsing failed, just keep the % and the hex chars
+                result.push('%');
+                result.push_str(&hex);
+            } else {
+                result.push(c);
+            }
+        }
+        result
+    }
+}
+
+#[async_trait]
+impl SessionStore for FileSessionStore {
+    async fn load(&self, key: &str) -> Result<Option<Session>> {
+        let file_path = self.path_for(key);
+        if !file_path.exists() {
+            return Ok(None);
+        }
+        let content = tokio::fs::read_to_string(&file_path).await?;
+        Ok(Some(serde_json::from_str(&content)?))
+    }
+
+    async fn save(&self, session: &Session) -> Result<()> {
+        let content = serde_json::to_string_pretty(session)?;
+        tokio::fs::write(self.path_for(&session.key), content).await?;
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> Result<bool> {
+        let file_path = self.path_for(key);
+        if !file_path.exists() {
+            return Ok(false);
+        }
+        tokio::fs::remove_file(&file_path).await?;
+        Ok(true)
+    }
+
+    async fn list(&self) -> Result<Vec<String>> {
+        let mut keys: Vec<String> = self.read_all().await?.into_iter().map(|s| s.key).collect();
+        keys.sort();
+        keys.dedup();
+        Ok(keys)
+    }
+
+    async fn exists(&self, key: &str) -> Result<bool> {
+        Ok(self.path_for(key).exists())
+    }
+
+    async fn gc(&self, cutoff: DateTime<Utc>) -> Result<Vec<String>> {
+        let mut removed = Vec::new();
+        for session in self.read_all().await? {
+            if session.updated_at < cutoff && !session.keep {
+                tokio::fs::remove_file(self.path_for(&session.key)).await?;
+                removed.push(session.key);
+            }
+        }
+        removed.sort();
+        Ok(removed)
+    }
+}
+
+/// Keeps sessions in a process-local map.
+#[derive(Default)]
+pub struct MemorySessionStore {
+    sessions: RwLock<HashMap<String, Session>>,
+}
+
+impl MemorySessionStore {
+    /// Create an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl SessionStore for MemorySessionStore {
+    async fn load(&self, key: &str) -> Result<Option<Session>> {
+        Ok(self.sessions.read().await.get(key).cloned())
+    }
+
+    async fn save(&self, session: &Session) -> Result<()> {
+        self.sessions
+            .write()
+            .await
+            .insert(session.key.clone(), session.clone());
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> Result<bool> {
+        Ok(self.sessions.write().await.remove(key).is_some())
+    }
+
+    async fn list(&self) -> Result<Vec<String>> {
+        let mut keys: Vec<String> = self.sessions.read().await.keys().cloned().collect();
+        keys.sort();
+        Ok(keys)
+    }
+
+    async fn exists(&self, key: &str) -> Result<bool> {
+        Ok(self.sessions.read().await.contains_key(key))
+    }
+
+    async fn gc(&self, cutoff: DateTime<Utc>) -> Result<Vec<String>> {
+        let mut sessions = self.sessions.write().await;
+        let mut removed: Vec<String> = sessions
+            .values()
+            .filter(|s| s.updated_at < cutoff && !s.keep)
+            .map(|s| s.key.clone())
+            .collect();
+        for key in &removed {
+            sessions.remove(key);
+        }
+        removed.sort();
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::session::Message;
+    use chrono::Duration;
+    use tempfile::TempDir;
+
+    /// Behavioral suite every backend must pass.
+    pub(crate) async fn run_store_suite(store: &dyn SessionStore) {
+        // Empty store
+        assert!(store.load("missing").await.unwrap().is_none());
+        assert!(!store.exists("missing").await.unwrap());
+        assert!(store.list().await.unwrap().is_empty());
+        assert!(!store.delete("missing").await.unwrap());
+
+        // Save and load round-trip, including keys with special characters
+        let mut session = Session::new("telegram:chat/1");
+        session.add_message(Message::user("Hello"));
+        session.add_message(Message::assistant("Hi"));
+        store.save(&session).await.unwrap();
+
+        let loaded = store.load("telegram:chat/1").await.unwrap().unwrap();
+        assert_eq!(loaded.key, "telegram:chat/1");
+        assert_eq!(loaded.messages.len(), 2);
+        assert!(store.exists("telegram:chat/1").await.unwrap());
+
+        // Save replaces
+        session.add_message(Message::user("Again"));
+        store.save(&session).await.unwrap();
+        let loaded = store.load("telegram:chat/1").await.unwrap().unwrap();
+        assert_eq!(loaded.messages.len(), 3);
+
+        // List returns original keys, sorted, without duplicates
+        store.save(&Session::new("alpha")).await.unwrap();
+        store.save(&Session::new("100%done")).await.unwrap();
+        assert_eq!(
+            store.list().await.unwrap(),
+            vec!["100%done", "alpha", "telegram:chat/1"]
+        );
+
+        // Delete
+        assert!(store.delete("alpha").await.unwrap());
+        assert!(!store.exists("alpha").await.unwrap());
+        assert!(!store.delete("alpha").await.unwrap());
+
+        // GC removes only sessions updated before the cutoff
+        let mut stale = Session::new("stale");
+        stale.updated_at = Utc::now() - Duration::days(30);
+        store.save(&stale).await.unwrap();
+
+        let mut pinned = Session::new("pinned");
+        pinned.updated_at = Utc::now() - Duration::days(30);
+        pinned.keep = true;
+        store.save(&pinned).await.unwrap();
+
+        let removed = store.gc(Utc::now() - Duration::days(7)).await.unwrap();
+        assert_eq!(removed, vec!["stale"]);
+        assert!(store.load("pinned").await.unwrap().unwrap().keep);
+        assert!(store.delete("pinned").await.unwrap());
+        assert!(!store.exists("stale").await.unwrap());
+        assert_eq!(
+            store.list().await.unwrap(),
+            vec!["100%done", "telegram:chat/1"]
+        );
+        assert!(store
+            .gc(Utc::now() - Duration::days(7))
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_file_store_suite() {
+        let dir = TempDir::new().unwrap();
+        let store = FileSessionStore::new(dir.path().to_path_buf()).unwrap();
+        run_store_suite(&store).await;
+    }
+
+    #[tokio::test]
+    async fn test_memory_store_suite() {
+        run_store_suite(&MemorySessionStore::new()).await;
+    }
+
+    #[tokio::test]
+    async fn test_file_store_skips_unparseable_files() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("broken.json"), "{not json").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "ignored").unwrap();
+        let store = FileSessionStore::new(dir.path().to_path_buf()).unwrap();
+        store.save(&Session::new("ok")).await.unwrap();
+
+        assert_eq!(store.list().await.unwrap(), vec!["ok"]);
+        assert!(store.gc(Utc::now()).await.unwrap().contains(&"ok".into()));
+    }
+
+    #[test]
+    fn test_session_config_backend_parsing() {
+        let config: SessionConfig = serde_json::from_str(r#"{"backend": "memory"}"#).unwrap();
+        assert_eq!(config.backend, SessionBackend::Memory);
+        let config: SessionConfig = serde_json::from_str("{}").unwrap();
+        assert_eq!(config.backend, SessionBackend::Filesystem);
+        let config: SessionConfig = serde_json::from_str(r#"{"backend": "file"}"#).unwrap();
+        assert_eq!(config.backend, SessionBackend::Filesystem);
+        let config: SessionConfig = serde_json::from_str(r#"{"backend": "sqlite"}"#).unwrap();
+        assert_eq!(config.backend, SessionBackend::Sqlite);
+        assert!(serde_json::from_str::<SessionConfig>(r#"{"backend": "bogus"}"#).is_err());
+        let config: SessionConfig = serde_json::from_str(r#"{"ttl_secs": 86400}"#).unwrap();
+        assert_eq!(config.ttl_secs, 86400);
+    }
+
+    #[test]
+    fn test_sanitize_key() {
+        // Simple keys pass through unchanged
+        assert_eq!(FileSessionStore::sanitize_key("simple"), "simple");
+        // Special characters are percent-encoded
+        assert_eq!(
+            FileSessionStore::sanitize_key("telegram:chat123"),
+            "telegram%3Achat123"
+        );
+        assert_eq!(
+            FileSessionStore::sanitize_key("path/to/session"),
+            "path%2Fto%2Fsession"
+        );
+        assert_eq!(
+            FileSessionStore::sanitize_key("a:b/c\\d*e?f\"g<h>i|j"),
+            "a%3Ab%2Fc%5Cd%2Ae%3Ff%22g%3Ch%3Ei%7Cj"
+        );
+        // Percent itself is escaped to make encoding reversible
+        assert_eq!(FileSessionStore::sanitize_key("100%done"), "100%25done");
+    }
+
+    #[test]
+    fn test_unsanitize_key() {
+        // Round-trip: sanitize then unsanitize should return original
+        let keys = [
+            "simple",
+            "telegram:chat123",
+            "path/to/session",
+            "a:b/c\\d*e?f\"g<h>i|j",
+            "100%done",
+            "multi%percent%%test",
+        ];
+        for key in &keys {
+            let sanitized = FileSessionStore::sanitize_key(key);
+            let unsanitized = FileSessionStore::unsanitize_key(&sanitized);
+            assert_eq!(
+                unsanitized, *key,
+                "Key '{}' should round-trip through sanitize/unsanitize",
+                key
+            );
+        }
+    }
+
+    #[test]
+    fn test_sanitize_key_no_collisions() {
+        // Keys that would collide with the old underscore-replacement approach
+        // should now produce different sanitized values
+        let key1 = "a:b";
+        let key2 = "a/b";
+        let key3 = "a_b"; // This one has an actual underscore
+
+        let sanitized1 = FileSessionStore::sanitize_key(key1);
+        let sanitized2 = FileSessionStore::sanitize_key(key2);
+        let sanitized3 = FileSessionStore::sanitize_key(key3);
+
+        assert_ne!(sanitized1, sanitized2, "a:b and a/b should not collide");
+        assert_ne!(sanitized1, sanitized3, "a:b and a_b should not collide");
+        assert_ne!(sanitized2, sanitized3, "a/b and a_b should not collide");
+
+        // Verify the actual values
+        assert_eq!(sanitized1, "a%3Ab");
+        assert_eq!(sanitized2, "a%2Fb");
+        assert_eq!(sanitized3, "a_b");
+    }
+}
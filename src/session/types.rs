@@ -6,6 +6,18 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+/// Default characters-per-token ratio for [`Message::estimated_tokens`].
+///
+/// Roughly right for English text with GPT- and Claude-style BPE tokenizers;
+/// code and non-Latin scripts usually need a smaller ratio.
+pub const DEFAULT_CHARS_PER_TOKEN: f64 = 4.0;
+
+/// Tokens added per message for role and framing overhead.
+pub const MESSAGE_TOKEN_OVERHEAD: usize = 4;
+
+/// Rough token cost of one image part (a ~1000x1000 px image on Claude).
+pub const IMAGE_TOKEN_ESTIMATE: usize = 1600;
+
 /// A conversation session containing messages and metadata.
 ///
 /// Sessions are identified by a unique key and store the full conversation
@@ -22,6 +34,9 @@ pub struct Session {
     pub created_at: DateTime<Utc>,
     /// When this session was last modified
     pub updated_at: DateTime<Utc>,
+    /// Pinned sessions are never removed by TTL expiry or `gc`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub keep: bool,
 }
 
 impl Session {
@@ -45,6 +60,7 @@ impl Session {
             summary: None,
             created_at: now,
             updated_at: now,
+            keep: false,
         }
     }
 
@@ -117,6 +133,32 @@ impl Session {
     pub fn messages_by_role(&self, role: Role) -> Vec<&Message> {
         self.messages.iter().filter(|m| m.role == role).collect()
     }
+
+    /// Estimate how many tokens the session's messages use, at
+    /// [`DEFAULT_CHARS_PER_TOKEN`].
+    ///
+    /// See [`Message::estimated_tokens`] for the approximation used.
+    ///
+    /// # Example
+    /// ```
+    /// use zeptoclaw::session::{Message, Session};
+    ///
+    /// let mut session = Session::new("test");
+    /// session.add_message(Message::user("12345678"));
+    /// session.add_message(Message::assistant(""));
+    /// assert_eq!(session.estimated_tokens(), (2 + 4) + 4);
+    /// ```
+    pub fn estimated_tokens(&self) -> usize {
+        self.estimated_tokens_with(DEFAULT_CHARS_PER_TOKEN)
+    }
+
+    /// Estimate the session's token usage with a custom chars-per-token ratio.
+    pub fn estimated_tokens_with(&self, chars_per_token: f64) -> usize {
+        self.messages
+            .iter()
+            .map(|m| m.estimated_tokens_with(chars_per_token))
+            .sum()
+    }
 }
 
 /// A single message in a conversation.
@@ -134,6 +176,39 @@ pub struct Message {
     /// ID of the tool call this message is responding to (for tool results)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tool_call_id: Option<String>,
+    /// Multimodal content, in order. When non-empty, providers send these
+    /// parts instead of `content`, which holds the text parts joined.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parts: Vec<ContentPart>,
+}
+
+/// One part of a multimodal message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContentPart {
+    /// Plain text
+    Text(String),
+    /// Base64-encoded image
+    Image {
+        /// MIME type, e.g. `image/png`
+        media_type: String,
+        /// Image bytes, base64-encoded (no `data:` prefix)
+        base64: String,
+    },
+}
+
+impl ContentPart {
+    /// Text shown in place of this part by providers that cannot take images.
+    pub fn to_text(&self) -> String {
+        match self {
+            Self::Text(text) => text.clone(),
+            Self::Image { media_type, base64 } => format!(
+                "[image: {}, {} bytes not shown to this model]",
+                media_type,
+                base64.len() / 4 * 3
+            ),
+        }
+    }
 }
 
 impl Message {
@@ -155,6 +230,7 @@ impl Message {
             content: content.to_string(),
             tool_calls: None,
             tool_call_id: None,
+            parts: Vec::new(),
         }
     }
 
@@ -176,6 +252,7 @@ impl Message {
             content: content.to_string(),
             tool_calls: None,
             tool_call_id: None,
+            parts: Vec::new(),
         }
     }
 
@@ -199,6 +276,7 @@ impl Message {
             content: content.to_string(),
             tool_calls: None,
             tool_call_id: None,
+            parts: Vec::new(),
         }
     }
 
@@ -224,6 +302,7 @@ impl Message {
             content: content.to_string(),
             tool_calls: None,
             tool_call_id: Some(tool_call_id.to_string()),
+            parts: Vec::new(),
         }
     }
 
@@ -247,9 +326,83 @@ impl Message {
             content: content.to_string(),
             tool_calls: Some(tool_calls),
             tool_call_id: None,
+            parts: Vec::new(),
         }
     }
 
+    /// Create a user message from text and image parts.
+    ///
+    /// # Example
+    /// ```
+    /// use zeptoclaw::session::{ContentPart, Message};
+    ///
+    /// let msg = Message::user_with_parts(vec![
+    ///     ContentPart::Text("What is on screen?".to_string()),
+    ///     ContentPart::Image { media_type: "image/png".to_string(), base64: "iVBORw0KGgo=".to_string() },
+    /// ]);
+    /// assert_eq!(msg.content, "What is on screen?");
+    /// assert!(msg.has_images());
+    /// ```
+    pub fn user_with_parts(parts: Vec<ContentPart>) -> Self {
+        let mut msg = Self::user("");
+        msg.parts = parts;
+        msg.content = msg.text_parts();
+        msg
+    }
+
+    /// Append an image, keeping any existing text as the first part.
+    ///
+    /// # Example
+    /// ```
+    /// use zeptoclaw::session::Message;
+    ///
+    /// let msg = Message::tool_result("call_1", "Screenshot taken").with_image("image/png", "iVBORw0KGgo=");
+    /// assert_eq!(msg.parts.len(), 2);
+    /// assert_eq!(msg.content, "Screenshot taken");
+    /// ```
+    pub fn with_image(mut self, media_type: &str, base64: &str) -> Self {
+        if self.parts.is_empty() && !self.content.is_empty() {
+            self.parts.push(ContentPart::Text(self.content.clone()));
+        }
+        self.parts.push(ContentPart::Image {
+            media_type: media_type.to_string(),
+            base64: base64.to_string(),
+        });
+        self
+    }
+
+    /// Check if this message carries any image parts.
+    pub fn has_images(&self) -> bool {
+        self.parts
+            .iter()
+            .any(|part| matches!(part, ContentPart::Image { .. }))
+    }
+
+    /// The message as plain text, with each image replaced by a placeholder
+    /// note. For providers without image input.
+    pub fn flattened_content(&self) -> String {
+        if !self.has_images() {
+            return self.content.clone();
+        }
+        self.parts
+            .iter()
+            .map(ContentPart::to_text)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Text parts joined with newlines.
+    fn text_parts(&self) -> String {
+        self.parts
+            .iter()
+            .filter_map(|part| match part {
+                ContentPart::Text(text) => Some(text.as_str()),
+                ContentPart::Image { .. } => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
     /// Check if this message has tool calls.
     pub fn has_tool_calls(&self) -> bool {
         self.tool_calls
@@ -262,6 +415,49 @@ impl Message {
     pub fn is_tool_result(&self) -> bool {
         self.role == Role::Tool && self.tool_call_id.is_some()
     }
+
+    /// Estimate how many tokens this message uses, at [`DEFAULT_CHARS_PER_TOKEN`].
+    ///
+    /// This is a cheap approximation, not a tokenizer: the characters in the
+    /// content, tool call names/arguments/ids and tool result id are divided
+    /// by the ratio (rounded up), plus [`MESSAGE_TOKEN_OVERHEAD`] and
+    /// [`IMAGE_TOKEN_ESTIMATE`] per image. Expect it to be off by 10-30%
+    /// against a provider's real count.
+    pub fn estimated_tokens(&self) -> usize {
+        self.estimated_tokens_with(DEFAULT_CHARS_PER_TOKEN)
+    }
+
+    /// Estimate this message's token usage with a custom chars-per-token
+    /// ratio. Non-positive ratios fall back to [`DEFAULT_CHARS_PER_TOKEN`].
+    pub fn estimated_tokens_with(&self, chars_per_token: f64) -> usize {
+        let ratio = if chars_per_token > 0.0 {
+            chars_per_token
+        } else {
+            DEFAULT_CHARS_PER_TOKEN
+        };
+        let tool_call_chars: usize = self
+            .tool_calls
+            .iter()
+            .flatten()
+            .map(|tc| {
+                tc.id.chars().count() + tc.name.chars().count() + tc.arguments.chars().count()
+            })
+            .sum();
+        let chars = self.content.chars().count()
+            + tool_call_chars
+            + self
+                .tool_call_id
+                .as_deref()
+                .map_or(0, |id| id.chars().count());
+        let images = self
+            .parts
+            .iter()
+            .filter(|part| matches!(part, ContentPart::Image { .. }))
+            .count();
+        (chars as f64 / ratio).ceil() as usize
+            + MESSAGE_TOKEN_OVERHEAD
+            + images * IMAGE_TOKEN_ESTIMATE
+    }
 }
 
 /// The role of a message sender in a conversation.
@@ -493,4 +689,99 @@ mod tests {
         assert!(!json.contains("tool_calls"));
         assert!(!json.contains("tool_call_id"));
     }
+
+    #[test]
+    fn test_estimated_tokens_empty_content() {
+        let msg = Message::assistant("");
+        assert_eq!(msg.estimated_tokens(), MESSAGE_TOKEN_OVERHEAD);
+        assert_eq!(Session::new("empty").estimated_tokens(), 0);
+    }
+
+    #[test]
+    fn test_estimated_tokens_rounds_up_and_uses_ratio() {
+        let msg = Message::user("123456789"); // 9 chars
+        assert_eq!(msg.estimated_tokens(), 3 + MESSAGE_TOKEN_OVERHEAD);
+        assert_eq!(msg.estimated_tokens_with(3.0), 3 + MESSAGE_TOKEN_OVERHEAD);
+        assert_eq!(msg.estimated_tokens_with(1.0), 9 + MESSAGE_TOKEN_OVERHEAD);
+        // Invalid ratios fall back to the default.
+        assert_eq!(msg.estimated_tokens_with(0.0), msg.estimated_tokens());
+        // Characters, not bytes.
+        assert_eq!(
+            Message::user("héllo wörld").estimated_tokens(),
+            3 + MESSAGE_TOKEN_OVERHEAD
+        );
+    }
+
+    #[test]
+    fn test_estimated_tokens_counts_tool_calls() {
+        let call = ToolCall::new("call_1", "read_file", r#"{"path":"a.txt"}"#);
+        let msg = Message::assistant_with_tools("", vec![call]);
+        // "call_1" (6) + "read_file" (9) + args (16) = 31 chars -> 8 tokens
+        assert_eq!(msg.estimated_tokens(), 8 + MESSAGE_TOKEN_OVERHEAD);
+
+        let result = Message::tool_result("call_1", "contents");
+        // "contents" (8) + "call_1" (6) = 14 chars -> 4 tokens
+        assert_eq!(result.estimated_tokens(), 4 + MESSAGE_TOKEN_OVERHEAD);
+    }
+
+    #[test]
+    fn test_session_estimated_tokens_sums_messages() {
+        let mut session = Session::new("multi");
+        session.add_message(Message::system("You are helpful."));
+        session.add_message(Message::user("Hello there"));
+        session.add_message(Message::assistant("Hi!"));
+
+        let expected: usize = session.messages.iter().map(Message::estimated_tokens).sum();
+        assert_eq!(session.estimated_tokens(), expected);
+        assert_eq!(session.estimated_tokens(), (4 + 4) + (3 + 4) + (1 + 4));
+        assert!(session.estimated_tokens_with(2.0) > session.estimated_tokens());
+    }
+
+    #[test]
+    fn test_message_with_image_parts_round_trip() {
+        let msg = Message::user("Describe this").with_image("image/png", "AAAA");
+        assert_eq!(msg.content, "Describe this");
+        assert_eq!(
+            msg.parts,
+            vec![
+                ContentPart::Text("Describe this".to_string()),
+                ContentPart::Image {
+                    media_type: "image/png".to_string(),
+                    base64: "AAAA".to_string(),
+                },
+            ]
+        );
+
+        let json = serde_json::to_string(&msg).unwrap();
+        assert!(json.contains(r#"{"image":{"media_type":"image/png","base64":"AAAA"}}"#));
+        let parsed: Message = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.parts, msg.parts);
+
+        // Messages saved before parts existed still load.
+        let old: Message = serde_json::from_str(r#"{"role":"user","content":"hi"}"#).unwrap();
+        assert!(old.parts.is_empty());
+        assert!(!serde_json::to_string(&old).unwrap().contains("parts"));
+    }
+
+    #[test]
+    fn test_flattened_content_and_image_tokens() {
+        let msg = Message::user_with_parts(vec![
+            ContentPart::Text("Before".to_string()),
+            ContentPart::Image {
+                media_type: "image/jpeg".to_string(),
+                base64: "AAAAAAAA".to_string(),
+            },
+            ContentPart::Text("After".to_string()),
+        ]);
+        assert_eq!(msg.content, "Before\nAfter");
+        assert_eq!(
+            msg.flattened_content(),
+            "Before\n[image: image/jpeg, 6 bytes not shown to this model]\nAfter"
+        );
+        assert_eq!(
+            msg.estimated_tokens(),
+            Message::user("Before\nAfter").estimated_tokens() + IMAGE_TOKEN_ESTIMATE
+        );
+        assert_eq!(Message::user("plain").flattened_content(), "plain");
+    }
 }
@@ -171,50 +171,7 @@ impl SkillsLoader {
     }
 
     fn parse_skill_file(&self, path: &Path, fallback_name: &str, source: &str) -> Option<Skill> {
-        let raw = std::fs::read_to_string(path).ok()?;
-        let (metadata, body) = self.parse_frontmatter(&raw);
-
-        let name = if metadata.name.trim().is_empty() {
-            fallback_name.to_string()
-        } else {
-            metadata.name.clone()
-        };
-        let description = if metadata.description.trim().is_empty() {
-            format!("Skill '{}'", name)
-        } else {
-            metadata.description.clone()
-        };
-
-        // Replace {baseDir} with the skill's parent directory (OpenClaw compat).
-        let base_dir = path
-            .parent()
-            .map(|p| p.to_string_lossy().to_string())
-            .unwrap_or_default();
-        let body = body.replace("{baseDir}", &base_dir);
-
-        Some(Skill {
-            name,
-            description,
-            path: path.to_string_lossy().to_string(),
-            source: source.to_string(),
-            metadata,
-            content: body,
-        })
-    }
-
-    fn parse_frontmatter(&self, content: &str) -> (SkillMetadata, String) {
-        let re = Regex::new(r"(?s)^---\n(.*?)\n---\n?").ok();
-        if let Some(re) = re {
-            if let Some(captures) = re.captures(content) {
-                if let (Some(frontmatter), Some(full)) = (captures.get(1), captures.get(0)) {
-                    let metadata = parse_frontmatter_metadata(frontmatter.as_str());
-                    let body = content[full.end()..].trim().to_string();
-                    return (metadata, body);
-                }
-            }
-        }
-
-        (SkillMetadata::default(), content.to_string())
+        parse_skill_file(path, fallback_name, source)
     }
 
     fn get_zeptometa(&self, skill: &Skill) -> ZeptoMetadata {
@@ -240,6 +197,55 @@ impl SkillsLoader {
     }
 }
 
+/// Read a `SKILL.md` file, falling back to `fallback_name` when the
+/// frontmatter has no name.
+pub(super) fn parse_skill_file(path: &Path, fallback_name: &str, source: &str) -> Option<Skill> {
+    let raw = std::fs::read_to_string(path).ok()?;
+    let (metadata, body) = split_frontmatter(&raw);
+
+    let name = if metadata.name.trim().is_empty() {
+        fallback_name.to_string()
+    } else {
+        metadata.name.clone()
+    };
+    let description = if metadata.description.trim().is_empty() {
+        format!("Skill '{}'", name)
+    } else {
+        metadata.description.clone()
+    };
+
+    // Replace {baseDir} with the skill's parent directory (OpenClaw compat).
+    let base_dir = path
+        .parent()
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let body = body.replace("{baseDir}", &base_dir);
+
+    Some(Skill {
+        name,
+        description,
+        path: path.to_string_lossy().to_string(),
+        source: source.to_string(),
+        metadata,
+        content: body,
+    })
+}
+
+fn split_frontmatter(content: &str) -> (SkillMetadata, String) {
+    let re = Regex::new(r"(?s)^---\n(.*?)\n---\n?").ok();
+    if let Some(re) = re {
+        if let Some(captures) = re.captures(content) {
+            if let (Some(frontmatter), Some(full)) = (captures.get(1), captures.get(0)) {
+                let metadata = parse_frontmatter_metadata(frontmatter.as_str());
+                let body = content[full.end()..].trim().to_string();
+                return (metadata, body);
+            }
+        }
+    }
+
+    (SkillMetadata::default(), content.to_string())
+}
+
 fn default_builtin_skills_dir() -> PathBuf {
     std::env::current_exe()
         .ok()
@@ -317,6 +323,7 @@ fn parse_frontmatter_metadata(frontmatter: &str) -> SkillMetadata {
                         metadata.homepage = Some(parsed);
                     }
                 }
+                "triggers" => metadata.triggers = parse_trigger_list(value),
                 "metadata" => {
                     let parsed = unquote(value);
                     if !parsed.is_empty() {
@@ -333,6 +340,18 @@ fn parse_frontmatter_metadata(frontmatter: &str) -> SkillMetadata {
     metadata
 }
 
+/// Parse `a, b` or `["a", "b"]` into a list of trigger keywords.
+fn parse_trigger_list(value: &str) -> Vec<String> {
+    if let Ok(list) = serde_json::from_str::<Vec<String>>(value) {
+        return list;
+    }
+    value
+        .split(',')
+        .map(unquote)
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
 fn unquote(input: &str) -> String {
     input
         .trim()
@@ -384,7 +403,6 @@ mod tests {
 
     #[test]
     fn test_parse_frontmatter() {
-        let loader = SkillsLoader::with_defaults();
         let content = r#"---
 name: weather
 description: Weather helper
@@ -395,7 +413,7 @@ metadata: {"zeptoclaw":{"emoji":"🌤️","requires":{"bins":["curl"]}}}
 Use wttr.in.
 "#;
 
-        let (meta, body) = loader.parse_frontmatter(content);
+        let (meta, body) = split_frontmatter(content);
         assert_eq!(meta.name, "weather");
         assert_eq!(meta.description, "Weather helper");
         assert!(body.contains("# Weather"));
@@ -403,9 +421,8 @@ Use wttr.in.
 
     #[test]
     fn test_parse_frontmatter_without_frontmatter() {
-        let loader = SkillsLoader::with_defaults();
         let content = "# Just markdown";
-        let (meta, body) = loader.parse_frontmatter(content);
+        let (meta, body) = split_frontmatter(content);
         assert!(meta.name.is_empty());
         assert_eq!(body, content);
     }
@@ -535,7 +552,7 @@ Use wttr.in.
         // Skill restricted to current platform — should pass
         std::fs::write(
             ws.join("platform/SKILL.md"),
-            &format!(
+            format!(
                 "---\nname: platform\ndescription: Platform-specific\nmetadata: {{\"openclaw\":{{\"os\":[\"{}\"]}}}}\n---\nBody.",
                 current_os()
             ),
@@ -560,7 +577,7 @@ Use wttr.in.
         };
         std::fs::write(
             ws.join("wrong_os/SKILL.md"),
-            &format!(
+            format!(
                 "---\nname: wrong_os\ndescription: Wrong platform\nmetadata: {{\"openclaw\":{{\"os\":[\"{}\"]}}}}\n---\nBody.",
                 wrong_os
             ),
@@ -1,7 +1,9 @@
 //! Skills system - markdown skill discovery and loading.
 
 mod loader;
+mod registry;
 mod types;
 
 pub use loader::SkillsLoader;
+pub use registry::{discover_skills, load_skill_dir, validate_skill, SkillRegistry};
 pub use types::{InstallOption, Skill, SkillInfo, SkillMetadata, SkillRequirements, ZeptoMetadata};
@@ -0,0 +1,465 @@
+//! Keyword-triggered skill registry.
+//!
+//! Skills live in one subdirectory each, defined either by a `SKILL.md` with
+//! a `triggers:` frontmatter key or by a `skill.json`:
+//!
+//! ```json
+//! {
+//!   "name": "weather",
+//!   "description": "Weather lookups",
+//!   "triggers": ["weather", "forecast"],
+//!   "instructions": "Use wttr.in via web_fetch for forecasts."
+//! }
+//! ```
+//!
+//! [`SkillRegistry::match_skills`] returns the skills whose triggers appear
+//! in a user message so their instructions can be added to the system prompt.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+use serde::Deserialize;
+use tracing::{info, warn};
+
+use crate::error::{Result, ZeptoError};
+
+use super::loader::parse_skill_file;
+use super::types::{Skill, SkillMetadata};
+
+/// Markdown skill definition file.
+const SKILL_MARKDOWN_FILE: &str = "SKILL.md";
+/// JSON skill definition file.
+const SKILL_JSON_FILE: &str = "skill.json";
+
+/// Contents of a `skill.json` file.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SkillManifest {
+    name: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    triggers: Vec<String>,
+    instructions: String,
+}
+
+/// Discover skills across multiple directories.
+///
+/// Scans each directory for subdirectories containing a `SKILL.md` or
+/// `skill.json` (the markdown file wins when both exist). Invalid skills are
+/// logged and skipped; when two directories define the same skill name, the
+/// one from the earlier directory is kept.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::path::PathBuf;
+/// use zeptoclaw::skills::discover_skills;
+///
+/// let dirs = vec![PathBuf::from("/home/user/.zeptoclaw/skills")];
+/// for skill in discover_skills(&dirs).unwrap() {
+///     println!("{}: {:?}", skill.name, skill.metadata.triggers);
+/// }
+/// ```
+pub fn discover_skills(dirs: &[PathBuf]) -> Result<Vec<Skill>> {
+    let mut skills = Vec::new();
+    let mut seen = HashSet::new();
+
+    for dir in dirs {
+        if !dir.exists() {
+            info!(dir = %dir.display(), "Skill directory does not exist, skipping");
+            continue;
+        }
+
+        if !dir.is_dir() {
+            warn!(path = %dir.display(), "Skill path is not a directory, skipping");
+            continue;
+        }
+
+        let entries = fs::read_dir(dir).map_err(|e| {
+            ZeptoError::Config(format!(
+                "Failed to read skill directory {}: {}",
+                dir.display(),
+                e
+            ))
+        })?;
+
+        let mut skill_dirs: Vec<PathBuf> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir())
+            .collect();
+        skill_dirs.sort();
+
+        for skill_dir in skill_dirs {
+            if !skill_dir.join(SKILL_MARKDOWN_FILE).is_file()
+                && !skill_dir.join(SKILL_JSON_FILE).is_file()
+            {
+                continue;
+            }
+
+            match load_skill_dir(&skill_dir) {
+                Ok(skill) => {
+                    if !seen.insert(skill.name.to_ascii_lowercase()) {
+                        info!(skill = %skill.name, dir = %skill_dir.display(), "Skill already defined, skipping");
+                        continue;
+                    }
+                    info!(
+                        skill = %skill.name,
+                        triggers = skill.metadata.triggers.len(),
+                        "Discovered skill"
+                    );
+                    skills.push(skill);
+                }
+                Err(e) => {
+                    warn!(
+                        dir = %skill_dir.display(),
+                        error = %e,
+                        "Failed to load skill, skipping"
+                    );
+                }
+            }
+        }
+    }
+
+    Ok(skills)
+}
+
+/// Load and validate the skill defined in `dir`.
+///
+/// # Errors
+/// - `ZeptoError::Config` if the directory has no skill file, the file cannot
+///   be read, or validation fails (see [`validate_skill`])
+/// - `ZeptoError::Json` if `skill.json` is malformed
+pub fn load_skill_dir(dir: &Path) -> Result<Skill> {
+    let fallback_name = dir
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let markdown_path = dir.join(SKILL_MARKDOWN_FILE);
+    let json_path = dir.join(SKILL_JSON_FILE);
+
+    let mut skill = if markdown_path.is_file() {
+        parse_skill_file(&markdown_path, &fallback_name, "workspace").ok_or_else(|| {
+            ZeptoError::Config(format!("Failed to read {}", markdown_path.display()))
+        })?
+    } else if json_path.is_file() {
+        let content = fs::read_to_string(&json_path).map_err(|e| {
+            ZeptoError::Config(format!("Failed to read {}: {}", json_path.display(), e))
+        })?;
+        let manifest: SkillManifest = serde_json::from_str(&content)?;
+        let description = if manifest.description.trim().is_empty() {
+            format!("Skill '{}'", manifest.name)
+        } else {
+            manifest.description
+        };
+        Skill {
+            name: manifest.name.clone(),
+            description: description.clone(),
+            path: json_path.to_string_lossy().to_string(),
+            source: "workspace".to_string(),
+            metadata: SkillMetadata {
+                name: manifest.name,
+                description,
+                triggers: manifest.triggers,
+                ..Default::default()
+            },
+            content: manifest.instructions.trim().to_string(),
+        }
+    } else {
+        return Err(ZeptoError::Config(format!(
+            "No {} or {} found in {}",
+            SKILL_MARKDOWN_FILE,
+            SKILL_JSON_FILE,
+            dir.display()
+        )));
+    };
+
+    validate_skill(&skill)?;
+    skill.metadata.triggers = skill
+        .metadata
+        .triggers
+        .iter()
+        .map(|t| t.trim().to_lowercase())
+        .collect();
+    Ok(skill)
+}
+
+/// Validate a skill definition.
+///
+/// Performs the following checks:
+/// - Name must be 1-64 characters: alphanumerics, hyphens and underscores,
+///   starting with an alphanumeric
+/// - Instructions must be non-empty
+/// - Triggers must not be blank
+///
+/// # Returns
+/// `Ok(())` if valid, or `ZeptoError::Config` describing the violation.
+pub fn validate_skill(skill: &Skill) -> Result<()> {
+    let name_re = Regex::new(r"^[a-zA-Z0-9][a-zA-Z0-9_\-]{0,63}$").unwrap();
+    if !name_re.is_match(&skill.name) {
+        return Err(ZeptoError::Config(format!(
+            "Invalid skill name '{}': must be 1-64 alphanumeric characters, hyphens and underscores, starting with alphanumeric",
+            skill.name
+        )));
+    }
+
+    if skill.content.trim().is_empty() {
+        return Err(ZeptoError::Config(format!(
+            "Skill '{}' has no instructions",
+            skill.name
+        )));
+    }
+
+    if skill.metadata.triggers.iter().any(|t| t.trim().is_empty()) {
+        return Err(ZeptoError::Config(format!(
+            "Skill '{}' has an empty trigger keyword",
+            skill.name
+        )));
+    }
+
+    Ok(())
+}
+
+/// Skills indexed for keyword matching against user messages.
+#[derive(Debug, Clone, Default)]
+pub struct SkillRegistry {
+    skills: Vec<Skill>,
+}
+
+impl SkillRegistry {
+    /// Create a registry from already loaded skills.
+    pub fn new(skills: Vec<Skill>) -> Self {
+        Self { skills }
+    }
+
+    /// Discover skills from `dirs` (see [`discover_skills`]).
+    pub fn discover(dirs: &[PathBuf]) -> Result<Self> {
+        discover_skills(dirs).map(Self::new)
+    }
+
+    /// Drop skills whose names are listed in `names` (case-insensitive).
+    pub fn without(mut self, names: &[String]) -> Self {
+        let names: HashSet<String> = names.iter().map(|n| n.to_ascii_lowercase()).collect();
+        self.skills
+            .retain(|skill| !names.contains(&skill.name.to_ascii_lowercase()));
+        self
+    }
+
+    /// All registered skills.
+    pub fn skills(&self) -> &[Skill] {
+        &self.skills
+    }
+
+    /// Look up a skill by name.
+    pub fn get(&self, name: &str) -> Option<&Skill> {
+        self.skills.iter().find(|skill| skill.name == name)
+    }
+
+    /// Number of registered skills.
+    pub fn len(&self) -> usize {
+        self.skills.len()
+    }
+
+    /// Whether the registry holds no skills.
+    pub fn is_empty(&self) -> bool {
+        self.skills.is_empty()
+    }
+
+    /// Skills with at least one trigger in `user_message`.
+    ///
+    /// Triggers match case-insensitively on word boundaries, so `"log"` does
+    /// not match "blog". Skills matching more triggers come first, then by
+    /// name. Skills without triggers never match.
+    pub fn match_skills(&self, user_message: &str) -> Vec<&Skill> {
+        let message = user_message.to_lowercase();
+        let mut matched: Vec<(usize, &Skill)> = self
+            .skills
+            .iter()
+            .filter_map(|skill| {
+                let hits = skill
+                    .metadata
+                    .triggers
+                    .iter()
+                    .filter(|trigger| contains_word(&message, trigger))
+                    .count();
+                (hits > 0).then_some((hits, skill))
+            })
+            .collect();
+        matched.sort_by(|(a_hits, a), (b_hits, b)| b_hits.cmp(a_hits).then(a.name.cmp(&b.name)));
+        matched.into_iter().map(|(_, skill)| skill).collect()
+    }
+
+    /// Instructions of the skills matching `user_message`, formatted for the
+    /// system prompt. Empty when nothing matches.
+    pub fn build_matched_context(&self, user_message: &str) -> String {
+        self.match_skills(user_message)
+            .into_iter()
+            .map(|skill| format!("### {} Skill\n\n{}", skill.name, skill.content))
+            .collect::<Vec<_>>()
+            .join("\n\n---\n\n")
+    }
+}
+
+/// Whether `needle` occurs in `haystack` with no word character on either side.
+fn contains_word(haystack: &str, needle: &str) -> bool {
+    if needle.is_empty() {
+        return false;
+    }
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    haystack.match_indices(needle).any(|(start, _)| {
+        let end = start + needle.len();
+        let before = haystack[..start].chars().next_back();
+        let after = haystack[end..].chars().next();
+        !before.is_some_and(is_word) && !after.is_some_and(is_word)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn write_skill(root: &Path, dir: &str, file: &str, content: &str) {
+        let skill_dir = root.join(dir);
+        fs::create_dir_all(&skill_dir).unwrap();
+        fs::write(skill_dir.join(file), content).unwrap();
+    }
+
+    fn sample_registry() -> (tempfile::TempDir, SkillRegistry) {
+        let temp = tempdir().unwrap();
+        write_skill(
+            temp.path(),
+            "weather",
+            SKILL_MARKDOWN_FILE,
+            "---\nname: weather\ndescription: Weather helper\ntriggers: weather, forecast\n---\nUse wttr.in.",
+        );
+        write_skill(
+            temp.path(),
+            "deploy",
+            SKILL_JSON_FILE,
+            r#"{"name": "deploy", "triggers": ["Deploy", "release notes"], "instructions": "Run the deploy checklist."}"#,
+        );
+        write_skill(
+            temp.path(),
+            "logs",
+            SKILL_JSON_FILE,
+            r#"{"name": "logs", "triggers": ["log"], "instructions": "Tail the log."}"#,
+        );
+        let registry = SkillRegistry::discover(&[temp.path().to_path_buf()]).unwrap();
+        (temp, registry)
+    }
+
+    #[test]
+    fn test_discovers_markdown_and_json_skills() {
+        let (_temp, registry) = sample_registry();
+        assert_eq!(registry.len(), 3);
+        let deploy = registry.get("deploy").unwrap();
+        assert_eq!(deploy.content, "Run the deploy checklist.");
+        assert_eq!(deploy.metadata.triggers, vec!["deploy", "release notes"]);
+        assert_eq!(
+            registry.get("weather").unwrap().metadata.triggers,
+            vec!["weather", "forecast"]
+        );
+    }
+
+    #[test]
+    fn test_match_skills_by_keyword() {
+        let (_temp, registry) = sample_registry();
+
+        let names = |msg: &str| -> Vec<String> {
+            registry
+                .match_skills(msg)
+                .into_iter()
+                .map(|s| s.name.clone())
+                .collect()
+        };
+        assert_eq!(names("What's the WEATHER forecast?"), vec!["weather"]);
+        assert_eq!(
+            names("Write release notes, then check the weather"),
+            vec!["deploy", "weather"]
+        );
+        // Word boundaries: "blog" must not trigger "log".
+        assert!(names("Post to my blog").is_empty());
+        assert_eq!(names("check the log."), vec!["logs"]);
+        assert!(names("hello").is_empty());
+
+        let context = registry.build_matched_context("deploy now");
+        assert!(context.contains("### deploy Skill"));
+        assert!(context.contains("Run the deploy checklist."));
+        assert!(registry.build_matched_context("hello").is_empty());
+    }
+
+    #[test]
+    fn test_invalid_skill_files_are_skipped() {
+        let temp = tempdir().unwrap();
+        write_skill(
+            temp.path(),
+            "good",
+            SKILL_JSON_FILE,
+            r#"{"name": "good", "triggers": ["ok"], "instructions": "Fine."}"#,
+        );
+        write_skill(temp.path(), "malformed", SKILL_JSON_FILE, "{not json");
+        write_skill(
+            temp.path(),
+            "bad-name",
+            SKILL_JSON_FILE,
+            r#"{"name": "bad name!", "instructions": "x"}"#,
+        );
+        write_skill(
+            temp.path(),
+            "empty",
+            SKILL_JSON_FILE,
+            r#"{"name": "empty", "triggers": ["x"], "instructions": "  "}"#,
+        );
+        write_skill(
+            temp.path(),
+            "blank-trigger",
+            SKILL_JSON_FILE,
+            r#"{"name": "blank-trigger", "triggers": [" "], "instructions": "x"}"#,
+        );
+        write_skill(
+            temp.path(),
+            "unknown-field",
+            SKILL_JSON_FILE,
+            r#"{"name": "unknown-field", "instructions": "x", "prompt": "y"}"#,
+        );
+        fs::create_dir_all(temp.path().join("no-skill-file")).unwrap();
+
+        let skills = discover_skills(&[temp.path().to_path_buf()]).unwrap();
+        let names: Vec<_> = skills.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["good"]);
+        assert!(load_skill_dir(&temp.path().join("malformed")).is_err());
+        assert!(load_skill_dir(&temp.path().join("no-skill-file")).is_err());
+    }
+
+    #[test]
+    fn test_earlier_directory_wins_and_missing_dirs_skipped() {
+        let first = tempdir().unwrap();
+        let second = tempdir().unwrap();
+        write_skill(
+            first.path(),
+            "notes",
+            SKILL_JSON_FILE,
+            r#"{"name": "notes", "triggers": ["note"], "instructions": "First."}"#,
+        );
+        write_skill(
+            second.path(),
+            "notes",
+            SKILL_JSON_FILE,
+            r#"{"name": "notes", "triggers": ["note"], "instructions": "Second."}"#,
+        );
+
+        let registry = SkillRegistry::discover(&[
+            first.path().join("missing"),
+            first.path().to_path_buf(),
+            second.path().to_path_buf(),
+        ])
+        .unwrap();
+        assert_eq!(registry.len(), 1);
+        assert_eq!(registry.get("notes").unwrap().content, "First.");
+        assert!(registry.without(&["NOTES".to_string()]).is_empty());
+    }
+}
@@ -42,6 +42,8 @@ pub struct SkillMetadata {
     pub version: Option<String>,
     /// Optional homepage URL.
     pub homepage: Option<String>,
+    /// Keywords that make the skill relevant to a user message.
+    pub triggers: Vec<String>,
     /// ZeptoClaw metadata payload.
     pub metadata: Option<serde_json::Value>,
 }
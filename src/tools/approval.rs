@@ -10,18 +10,28 @@
 //! - `AlwaysAllow` - All tools execute without approval (default)
 //! - `AlwaysRequire` - Every tool invocation requires approval
 //! - `RequireForTools` - Only named tools require approval
-//! - `RequireForDangerous` - Tools tagged as "dangerous" require approval
+//! - `RequireForDangerous` - Tools tagged as "dangerous" require approval;
+//!   opt-in [`DangerRule`]s relax this to calls whose arguments look dangerous
+//!
+//! Outside the interactive CLI, [`AsyncApprovalBroker`] asks for approval
+//! over the message bus: it sends the prompt to the chat the tool call came
+//! from and waits for a "yes"/"no" reply.
 //!
 //! # Configuration
 //!
-//! The approval system is configured via `ApprovalConfig` in `config.json`:
+//! The approval system is configured via `ApprovalConfig` in `config.json`.
+//! `per_chat` overrides the policy for individual chat IDs:
 //!
 //! ```json
 //! {
 //!     "approval": {
 //!         "enabled": true,
 //!         "policy": "require_for_dangerous",
-//!         "dangerous_tools": ["shell", "write_file", "edit_file"]
+//!         "dangerous_tools": ["shell", "write_file", "edit_file", "http_request"],
+//!         "per_chat": {
+//!             "123456789": "always_allow",
+//!             "-100987654": "always_require"
+//!         }
 //!     }
 //! }
 //! ```
@@ -38,9 +48,20 @@
 //! assert!(!gate.requires_approval("shell"));
 //! ```
 
+use std::collections::{HashMap, VecDeque};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use tokio::sync::oneshot;
+use tracing::{debug, warn};
+
+use crate::bus::{InboundMessage, MessageBus, MessagePriority, OutboundMessage};
+use crate::error::{Result, ZeptoError};
+use crate::security::validate_path_in_workspace;
 
 // ---------------------------------------------------------------------------
 // Approval policy (runtime enum, not serialized directly)
@@ -62,6 +83,142 @@ pub enum ApprovalPolicy {
     RequireForDangerous,
 }
 
+// ---------------------------------------------------------------------------
+// Argument-based danger rules
+// ---------------------------------------------------------------------------
+
+/// How a [`DangerRule`] matches an argument value.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DangerMatch {
+    /// The argument contains this substring.
+    Contains(String),
+    /// The argument matches this regular expression.
+    Regex(String),
+}
+
+/// Marks calls to a dangerous tool as needing approval based on an argument.
+///
+/// Under `RequireForDangerous`, a tool in `dangerous_tools` that has rules
+/// only needs approval when at least one of its rules matches; tools without
+/// rules always need approval. Rules are therefore a relaxation: a call that
+/// no rule catches runs without a prompt. In `config.json`:
+///
+/// ```json
+/// { "tool": "shell", "arg": "command", "regex": "\\b(rm|mkfs|dd)\\b" }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DangerRule {
+    /// Tool the rule applies to.
+    pub tool: String,
+    /// Name of the (string) argument to inspect.
+    pub arg: String,
+    /// How the argument is matched.
+    #[serde(flatten)]
+    pub matcher: DangerMatch,
+}
+
+impl DangerRule {
+    /// Rule matching `arg` of `tool` against a regular expression.
+    pub fn regex(tool: &str, arg: &str, pattern: &str) -> Self {
+        Self {
+            tool: tool.to_string(),
+            arg: arg.to_string(),
+            matcher: DangerMatch::Regex(pattern.to_string()),
+        }
+    }
+
+    /// Rule matching `arg` of `tool` containing `needle`.
+    pub fn contains(tool: &str, arg: &str, needle: &str) -> Self {
+        Self {
+            tool: tool.to_string(),
+            arg: arg.to_string(),
+            matcher: DangerMatch::Contains(needle.to_string()),
+        }
+    }
+}
+
+/// A [`DangerRule`] with its regex compiled.
+#[derive(Debug)]
+struct CompiledDangerRule {
+    arg: String,
+    /// `None` for substring rules and for regexes that failed to compile.
+    regex: Option<regex::Regex>,
+    needle: Option<String>,
+}
+
+impl CompiledDangerRule {
+    fn compile(rule: &DangerRule) -> Self {
+        let (regex, needle) = match &rule.matcher {
+            DangerMatch::Contains(needle) => (None, Some(needle.clone())),
+            DangerMatch::Regex(pattern) => match regex::Regex::new(pattern) {
+                Ok(regex) => (Some(regex), None),
+                Err(e) => {
+                    // Fail closed: a broken rule makes every call to the tool
+                    // need approval rather than none.
+                    warn!(tool = %rule.tool, pattern = %pattern, error = %e, "Invalid danger rule regex; treating it as always matching");
+                    (None, None)
+                }
+            },
+        };
+        Self {
+            arg: rule.arg.clone(),
+            regex,
+            needle,
+        }
+    }
+
+    fn matches(&self, args: &Value) -> bool {
+        let Some(value) = args.get(&self.arg).and_then(Value::as_str) else {
+            return false;
+        };
+        match (&self.regex, &self.needle) {
+            (Some(regex), _) => regex.is_match(value),
+            (None, Some(needle)) => value.contains(needle.as_str()),
+            (None, None) => true,
+        }
+    }
+}
+
+/// Named set of [`DangerRule`]s selectable by string in `config.json`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DangerRulePreset {
+    /// [`ApprovalGate::default_danger_rules`].
+    Builtin,
+}
+
+/// The `danger_rules` setting: either a preset name or explicit rules.
+///
+/// ```json
+/// "danger_rules": "builtin"
+/// "danger_rules": [{ "tool": "shell", "arg": "command", "contains": "deploy" }]
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum DangerRulesConfig {
+    /// A named rule set.
+    Preset(DangerRulePreset),
+    /// Explicit rules.
+    Rules(Vec<DangerRule>),
+}
+
+impl Default for DangerRulesConfig {
+    fn default() -> Self {
+        Self::Rules(Vec::new())
+    }
+}
+
+impl DangerRulesConfig {
+    /// The rules this setting expands to.
+    pub fn rules(&self) -> Vec<DangerRule> {
+        match self {
+            Self::Preset(DangerRulePreset::Builtin) => ApprovalGate::default_danger_rules(),
+            Self::Rules(rules) => rules.clone(),
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Serde-friendly policy config enum
 // ---------------------------------------------------------------------------
@@ -97,8 +254,9 @@ pub enum ApprovalPolicyConfig {
 /// - `enabled`: `false`
 /// - `policy`: `AlwaysAllow`
 /// - `require_for`: empty
-/// - `dangerous_tools`: `["shell", "write_file", "edit_file"]`
+/// - `dangerous_tools`: `["shell", "write_file", "edit_file", "http_request"]`
 /// - `auto_approve_timeout_secs`: `0` (disabled)
+/// - `danger_rules`: `[]` (every call to a dangerous tool needs approval)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct ApprovalConfig {
@@ -119,6 +277,24 @@ pub struct ApprovalConfig {
     /// If greater than zero, auto-approve after this many seconds without
     /// a response. `0` means no auto-approve (wait indefinitely).
     pub auto_approve_timeout_secs: u64,
+
+    /// Optional JSONL file recording every approval decision. Relative paths
+    /// are resolved against the workspace; the file must live inside it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audit_log: Option<String>,
+
+    /// Argument rules that narrow which calls to `dangerous_tools` need
+    /// approval. Empty by default, so checks are purely name-based. Set to
+    /// `"builtin"` for [`ApprovalGate::default_danger_rules`]; note that any
+    /// rules let calls they don't match run without approval.
+    pub danger_rules: DangerRulesConfig,
+
+    /// Policy overrides keyed by chat ID, checked before `policy`.
+    ///
+    /// Use this to relax approval for trusted chats or tighten it for
+    /// untrusted ones. `require_for` and `dangerous_tools` apply to overrides
+    /// just as they do to the global policy.
+    pub per_chat: HashMap<String, ApprovalPolicyConfig>,
 }
 
 impl Default for ApprovalConfig {
@@ -129,6 +305,9 @@ impl Default for ApprovalConfig {
             require_for: Vec::new(),
             dangerous_tools: ApprovalGate::default_dangerous_tools(),
             auto_approve_timeout_secs: 0,
+            audit_log: None,
+            danger_rules: DangerRulesConfig::default(),
+            per_chat: HashMap::new(),
         }
     }
 }
@@ -223,65 +402,150 @@ pub struct ApprovalGate {
     enabled: bool,
     /// The resolved runtime policy.
     policy: ApprovalPolicy,
+    /// Resolved per-chat policy overrides, keyed by chat ID.
+    per_chat: HashMap<String, ApprovalPolicy>,
+    /// Tools considered dangerous under `RequireForDangerous`.
+    dangerous_tools: Vec<String>,
+    /// Compiled danger rules, keyed by tool name.
+    danger_rules: HashMap<String, Vec<CompiledDangerRule>>,
     /// Auto-approve timeout in seconds (0 = disabled).
     auto_approve_timeout_secs: u64,
+    /// Where decisions are recorded, if configured.
+    audit_log: Option<ApprovalAuditLog>,
 }
 
 impl ApprovalGate {
     /// Create a new `ApprovalGate` from the given configuration.
     ///
     /// The `ApprovalPolicyConfig` is resolved into a runtime
-    /// `ApprovalPolicy`, incorporating the `require_for` and
-    /// `dangerous_tools` lists as needed.
+    /// `ApprovalPolicy`, incorporating the `require_for` list as needed, and
+    /// the danger rules are compiled.
     pub fn new(config: ApprovalConfig) -> Self {
-        let policy = match config.policy {
-            ApprovalPolicyConfig::AlwaysAllow => ApprovalPolicy::AlwaysAllow,
-            ApprovalPolicyConfig::AlwaysRequire => ApprovalPolicy::AlwaysRequire,
-            ApprovalPolicyConfig::RequireForTools => {
-                ApprovalPolicy::RequireForTools(config.require_for)
-            }
-            ApprovalPolicyConfig::RequireForDangerous => ApprovalPolicy::RequireForDangerous,
-        };
-
-        // When policy is RequireForDangerous we need the dangerous list at
-        // check time, so we store it inside RequireForTools-like matching.
-        // However, to keep the enum clean we handle it in `requires_approval`
-        // by referencing `dangerous_tools` from config. We therefore store
-        // the dangerous list separately.
-        //
-        // Actually, for simplicity we convert RequireForDangerous into
-        // RequireForTools at construction time so the check is uniform.
-        let policy = if policy == ApprovalPolicy::RequireForDangerous {
-            ApprovalPolicy::RequireForTools(config.dangerous_tools)
-        } else {
-            policy
-        };
+        let policy = Self::resolve_policy(&config.policy, &config);
+        let per_chat = config
+            .per_chat
+            .iter()
+            .map(|(chat_id, policy)| (chat_id.clone(), Self::resolve_policy(policy, &config)))
+            .collect();
+
+        let mut danger_rules: HashMap<String, Vec<CompiledDangerRule>> = HashMap::new();
+        for rule in &config.danger_rules.rules() {
+            danger_rules
+                .entry(rule.tool.clone())
+                .or_default()
+                .push(CompiledDangerRule::compile(rule));
+        }
 
         Self {
             enabled: config.enabled,
             policy,
+            per_chat,
+            dangerous_tools: config.dangerous_tools,
+            danger_rules,
             auto_approve_timeout_secs: config.auto_approve_timeout_secs,
+            audit_log: None,
+        }
+    }
+
+    /// Record every decision passed to
+    /// [`record_decision`](Self::record_decision) in a JSONL file.
+    ///
+    /// `path` may be relative to `workspace` and must resolve inside it.
+    pub fn with_audit_log(mut self, path: impl AsRef<Path>, workspace: &Path) -> Result<Self> {
+        self.audit_log = Some(ApprovalAuditLog::open(path.as_ref(), workspace)?);
+        Ok(self)
+    }
+
+    /// Return the audit log, if one is configured.
+    pub fn audit_log(&self) -> Option<&ApprovalAuditLog> {
+        self.audit_log.as_ref()
+    }
+
+    /// Record an approval decision in the audit log (no-op without one).
+    ///
+    /// `auto_approved` marks a timed-out request that was approved because
+    /// `auto_approve_timeout_secs` elapsed. Write failures are logged, not
+    /// returned, so auditing never blocks a decision.
+    pub fn record_decision(
+        &self,
+        tool_name: &str,
+        args: &Value,
+        chat_id: Option<&str>,
+        decision: &ApprovalResponse,
+        auto_approved: bool,
+    ) {
+        if let Some(log) = &self.audit_log {
+            let entry = ApprovalAuditEntry::new(tool_name, args, chat_id, decision, auto_approved);
+            if let Err(e) = log.append(&entry) {
+                warn!(path = %log.path().display(), error = %e, "Failed to write approval audit entry");
+            }
+        }
+    }
+
+    /// Resolve a configured policy into its runtime form.
+    fn resolve_policy(policy: &ApprovalPolicyConfig, config: &ApprovalConfig) -> ApprovalPolicy {
+        match policy {
+            ApprovalPolicyConfig::AlwaysAllow => ApprovalPolicy::AlwaysAllow,
+            ApprovalPolicyConfig::AlwaysRequire => ApprovalPolicy::AlwaysRequire,
+            ApprovalPolicyConfig::RequireForTools => {
+                ApprovalPolicy::RequireForTools(config.require_for.clone())
+            }
+            ApprovalPolicyConfig::RequireForDangerous => ApprovalPolicy::RequireForDangerous,
         }
     }
 
     /// Check whether a tool with the given name requires user approval.
     ///
     /// Returns `false` if the approval system is disabled or if the policy
-    /// does not require approval for this tool.
+    /// does not require approval for this tool. Per-chat overrides are not
+    /// consulted; see [`requires_approval_for_chat`](Self::requires_approval_for_chat).
     pub fn requires_approval(&self, tool_name: &str) -> bool {
+        self.check(tool_name, None, None)
+    }
+
+    /// Check whether a tool invoked from `chat_id` requires user approval.
+    ///
+    /// A per-chat override for `chat_id` takes precedence over the global
+    /// policy. Returns `false` if the approval system is disabled. Without
+    /// arguments, dangerous tools always require approval; see
+    /// [`requires_approval_for_call`](Self::requires_approval_for_call).
+    pub fn requires_approval_for_chat(&self, tool_name: &str, chat_id: &str) -> bool {
+        self.check(tool_name, None, Some(chat_id))
+    }
+
+    /// Check whether a specific call requires approval under the global
+    /// policy, applying danger rules to `args`.
+    pub fn requires_approval_with_args(&self, tool_name: &str, args: &Value) -> bool {
+        self.check(tool_name, Some(args), None)
+    }
+
+    /// Check whether a specific call from `chat_id` requires approval,
+    /// applying per-chat overrides and danger rules.
+    pub fn requires_approval_for_call(&self, tool_name: &str, args: &Value, chat_id: &str) -> bool {
+        self.check(tool_name, Some(args), Some(chat_id))
+    }
+
+    fn check(&self, tool_name: &str, args: Option<&Value>, chat_id: Option<&str>) -> bool {
         if !self.enabled {
             return false;
         }
+        let policy = chat_id
+            .and_then(|chat_id| self.per_chat.get(chat_id))
+            .unwrap_or(&self.policy);
 
-        match &self.policy {
+        match policy {
             ApprovalPolicy::AlwaysAllow => false,
             ApprovalPolicy::AlwaysRequire => true,
             ApprovalPolicy::RequireForTools(tools) => tools.iter().any(|t| t == tool_name),
-            // RequireForDangerous is converted to RequireForTools at
-            // construction time, but we handle it here for completeness.
-            ApprovalPolicy::RequireForDangerous => Self::default_dangerous_tools()
-                .iter()
-                .any(|t| t == tool_name),
+            ApprovalPolicy::RequireForDangerous => {
+                if !self.dangerous_tools.iter().any(|t| t == tool_name) {
+                    return false;
+                }
+                match (args, self.danger_rules.get(tool_name)) {
+                    (Some(args), Some(rules)) => rules.iter().any(|rule| rule.matches(args)),
+                    _ => true,
+                }
+            }
         }
     }
 
@@ -323,6 +587,37 @@ impl ApprovalGate {
             "shell".to_string(),
             "write_file".to_string(),
             "edit_file".to_string(),
+            "http_request".to_string(),
+        ]
+    }
+
+    /// Return the built-in danger rules, enabled with
+    /// `"danger_rules": "builtin"`.
+    ///
+    /// `shell` needs approval for destructive or privileged commands and
+    /// piping downloads into a shell; `write_file`/`edit_file` for system
+    /// paths, credential directories and `..` traversal. This is a denylist,
+    /// so it relaxes the default of approving every call: commands it does not
+    /// recognise (e.g. `find / -delete`, `python -c ...`) run without a prompt.
+    pub fn default_danger_rules() -> Vec<DangerRule> {
+        let system_paths = concat!(
+            r"^(/etc|/usr|/bin|/sbin|/lib\w*|/boot|/dev|/sys|/proc|/var|/root)(/|$)",
+            r"|(^|/)\.(ssh|gnupg|aws)(/|$)",
+            r"|(^|/)\.\.(/|$)",
+        );
+        vec![
+            DangerRule::regex(
+                "shell",
+                "command",
+                r"\b(rm|rmdir|mkfs(\.\w+)?|dd|shred|wipefs|fdisk|parted|shutdown|reboot|halt|poweroff|sudo|su|doas|chmod|chown|kill|killall|pkill|crontab)\b",
+            ),
+            DangerRule::regex(
+                "shell",
+                "command",
+                r"\|\s*(ba|z|da)?sh\b|>\s*/dev/(sd|hd|nvme|mmcblk|disk)",
+            ),
+            DangerRule::regex("write_file", "path", system_paths),
+            DangerRule::regex("edit_file", "path", system_paths),
         ]
     }
 
@@ -337,6 +632,335 @@ impl ApprovalGate {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Approval audit log
+// ---------------------------------------------------------------------------
+
+/// One line of the approval audit log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApprovalAuditEntry {
+    /// When the decision was reached.
+    pub timestamp: DateTime<Utc>,
+    /// Tool the decision applies to.
+    pub tool_name: String,
+    /// SHA-256 (hex) of the JSON-serialized arguments, so the log can be
+    /// matched against a call without storing its contents.
+    pub arguments_sha256: String,
+    /// `approved`, `denied` or `timed_out`.
+    pub decision: String,
+    /// Denial reason, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// Whether a timed-out request was auto-approved and the tool ran.
+    #[serde(default)]
+    pub auto_approved: bool,
+    /// Chat the tool call came from, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chat_id: Option<String>,
+}
+
+impl ApprovalAuditEntry {
+    /// Build an entry for `decision`, timestamped now.
+    pub fn new(
+        tool_name: &str,
+        args: &Value,
+        chat_id: Option<&str>,
+        decision: &ApprovalResponse,
+        auto_approved: bool,
+    ) -> Self {
+        use sha2::{Digest, Sha256};
+
+        let (decision, reason) = match decision {
+            ApprovalResponse::Approved => ("approved", None),
+            ApprovalResponse::Denied(reason) => ("denied", Some(reason.clone())),
+            ApprovalResponse::TimedOut => ("timed_out", None),
+        };
+        Self {
+            timestamp: Utc::now(),
+            tool_name: tool_name.to_string(),
+            arguments_sha256: hex::encode(Sha256::digest(args.to_string().as_bytes())),
+            decision: decision.to_string(),
+            reason,
+            auto_approved,
+            chat_id: chat_id.map(str::to_string),
+        }
+    }
+}
+
+/// Append-only JSONL log of approval decisions.
+///
+/// Each entry is written as a single line and flushed to disk with
+/// `sync_data` before [`append`](Self::append) returns.
+#[derive(Debug)]
+pub struct ApprovalAuditLog {
+    path: PathBuf,
+    file: Mutex<std::fs::File>,
+}
+
+impl ApprovalAuditLog {
+    /// Open (creating if needed) the log at `path`, which must resolve
+    /// inside `workspace`.
+    pub fn open(path: &Path, workspace: &Path) -> Result<Self> {
+        let safe =
+            validate_path_in_workspace(&path.to_string_lossy(), &workspace.to_string_lossy())?;
+        let path = safe.into_path_buf();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| {
+                ZeptoError::Config(format!(
+                    "Failed to open approval audit log {}: {}",
+                    path.display(),
+                    e
+                ))
+            })?;
+        Ok(Self {
+            path,
+            file: Mutex::new(file),
+        })
+    }
+
+    /// Path of the log file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append one entry and sync it to disk.
+    pub fn append(&self, entry: &ApprovalAuditEntry) -> Result<()> {
+        let mut line = serde_json::to_string(entry)?;
+        line.push('\n');
+        let mut file = self
+            .file
+            .lock()
+            .map_err(|_| ZeptoError::Tool("approval audit log lock poisoned".into()))?;
+        file.write_all(line.as_bytes())?;
+        file.sync_data()?;
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Async approval broker (remote channels)
+// ---------------------------------------------------------------------------
+
+/// Replies that approve a pending request (first word, case-insensitive).
+const APPROVE_REPLIES: &[&str] = &["yes", "y", "approve", "approved", "ok"];
+
+/// Replies that deny a pending request (first word, case-insensitive).
+const DENY_REPLIES: &[&str] = &["no", "n", "deny", "denied", "reject"];
+
+/// Parse a chat reply into an approval verdict.
+///
+/// The first word decides (`yes`/`approve` → `true`, `no`/`deny` → `false`);
+/// the rest of the message is returned as-is. Returns `None` for messages
+/// that are not approval replies.
+fn parse_approval_reply(text: &str) -> Option<(bool, &str)> {
+    let text = text.trim();
+    let (verb, rest) = text.split_once(char::is_whitespace).unwrap_or((text, ""));
+    let verb = verb.trim_end_matches(['.', '!', ',']).to_ascii_lowercase();
+    if APPROVE_REPLIES.contains(&verb.as_str()) {
+        Some((true, rest.trim()))
+    } else if DENY_REPLIES.contains(&verb.as_str()) {
+        Some((false, rest.trim()))
+    } else {
+        None
+    }
+}
+
+/// An approval prompt waiting for a reply.
+struct PendingApproval {
+    /// Short reference shown in the prompt, used to target a reply.
+    reference: String,
+    /// Completes the waiting `request_approval` call.
+    tx: oneshot::Sender<ApprovalResponse>,
+}
+
+/// Collects approval decisions from remote chat channels.
+///
+/// [`request_approval`](Self::request_approval) publishes the prompt as an
+/// [`OutboundMessage`] to the originating chat and waits for a reply. The
+/// inbound consumer (the agent loop) must offer every inbound message to
+/// [`try_resolve`](Self::try_resolve) first, so replies are routed here
+/// instead of starting a new agent turn.
+///
+/// A bare "yes"/"no" answers the oldest pending request in that chat;
+/// "yes <ref>" answers a specific one. Any sender in the chat may answer,
+/// so channel allowlists decide who can approve.
+pub struct AsyncApprovalBroker {
+    bus: Arc<MessageBus>,
+    /// How long to wait for a reply; `None` waits indefinitely.
+    timeout: Option<std::time::Duration>,
+    /// Pending requests per `(channel, chat_id)`, oldest first.
+    pending: Mutex<HashMap<(String, String), VecDeque<PendingApproval>>>,
+}
+
+impl AsyncApprovalBroker {
+    /// Create a broker publishing on `bus`.
+    ///
+    /// Replies are awaited for `auto_approve_timeout_secs`; `0` waits
+    /// indefinitely.
+    pub fn new(bus: Arc<MessageBus>, config: &ApprovalConfig) -> Self {
+        let timeout = (config.auto_approve_timeout_secs > 0)
+            .then(|| std::time::Duration::from_secs(config.auto_approve_timeout_secs));
+        Self {
+            bus,
+            timeout,
+            pending: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Override how long to wait for a reply (`None` waits indefinitely).
+    pub fn with_timeout(mut self, timeout: Option<std::time::Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Number of requests currently waiting for a reply.
+    pub fn pending_count(&self) -> usize {
+        self.pending
+            .lock()
+            .map(|pending| pending.values().map(VecDeque::len).sum())
+            .unwrap_or(0)
+    }
+
+    /// Send `prompt` to the chat and wait for the user's decision.
+    ///
+    /// Returns [`ApprovalResponse::TimedOut`] if no reply arrives in time and
+    /// [`ApprovalResponse::Denied`] if the prompt cannot be delivered.
+    pub async fn request_approval(
+        &self,
+        channel: &str,
+        chat_id: &str,
+        prompt: &str,
+    ) -> ApprovalResponse {
+        let reference = uuid::Uuid::new_v4().simple().to_string()[..6].to_string();
+        let key = (channel.to_string(), chat_id.to_string());
+        let (tx, rx) = oneshot::channel();
+
+        // Register before publishing so an immediate reply is not missed.
+        if let Ok(mut pending) = self.pending.lock() {
+            pending
+                .entry(key.clone())
+                .or_default()
+                .push_back(PendingApproval {
+                    reference: reference.clone(),
+                    tx,
+                });
+        }
+
+        let content = format!(
+            "{}\n(ref: {} — reply \"yes {}\" or \"no {}\" if several requests are open)",
+            prompt, reference, reference, reference
+        );
+        if let Err(e) = self
+            .bus
+            .publish_outbound(
+                OutboundMessage::new(channel, chat_id, &content)
+                    .with_priority(MessagePriority::High),
+            )
+            .await
+        {
+            self.remove(&key, &reference);
+            warn!(channel, chat_id, error = %e, "Failed to send approval request");
+            return ApprovalResponse::Denied(format!("approval request not delivered: {}", e));
+        }
+
+        let outcome = match self.timeout {
+            Some(timeout) => tokio::time::timeout(timeout, rx).await.ok(),
+            None => Some(rx.await),
+        };
+        match outcome {
+            Some(Ok(response)) => response,
+            Some(Err(_)) => ApprovalResponse::Denied("approval request cancelled".to_string()),
+            None => {
+                self.remove(&key, &reference);
+                debug!(channel, chat_id, reference = %reference, "Approval request timed out");
+                ApprovalResponse::TimedOut
+            }
+        }
+    }
+
+    /// Whether `msg` is a yes/no reply in a chat with a pending request,
+    /// i.e. one [`try_resolve`](Self::try_resolve) would consume.
+    pub fn is_reply(&self, msg: &InboundMessage) -> bool {
+        if parse_approval_reply(&msg.content).is_none() {
+            return false;
+        }
+        let key = (msg.channel.clone(), msg.chat_id.clone());
+        self.pending
+            .lock()
+            .map(|pending| pending.contains_key(&key))
+            .unwrap_or(false)
+    }
+
+    /// Route an inbound message to a pending request, if it answers one.
+    ///
+    /// Returns `true` if the message was consumed as an approval reply and
+    /// must not be processed further.
+    pub fn try_resolve(&self, msg: &InboundMessage) -> bool {
+        let Some((approved, rest)) = parse_approval_reply(&msg.content) else {
+            return false;
+        };
+        let Ok(mut pending) = self.pending.lock() else {
+            return false;
+        };
+        let key = (msg.channel.clone(), msg.chat_id.clone());
+        let Some(queue) = pending.get_mut(&key) else {
+            return false;
+        };
+
+        // "yes <ref> ..." targets a specific request; otherwise the oldest.
+        let (first, after) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+        let (index, rest) = match queue
+            .iter()
+            .position(|p| p.reference.eq_ignore_ascii_case(first))
+        {
+            Some(index) => (index, after.trim()),
+            None => (0, rest),
+        };
+        let Some(entry) = queue.remove(index) else {
+            return false;
+        };
+        let response = if approved {
+            ApprovalResponse::Approved
+        } else if rest.is_empty() {
+            ApprovalResponse::Denied("denied by user".to_string())
+        } else {
+            ApprovalResponse::Denied(rest.to_string())
+        };
+        if queue.is_empty() {
+            pending.remove(&key);
+        }
+
+        debug!(
+            channel = %msg.channel,
+            chat_id = %msg.chat_id,
+            reference = %entry.reference,
+            decision = ?response,
+            "Approval reply received"
+        );
+        // The requester may have timed out in the meantime; the reply is
+        // still consumed so it does not start an agent turn.
+        let _ = entry.tx.send(response);
+        true
+    }
+
+    fn remove(&self, key: &(String, String), reference: &str) {
+        if let Ok(mut pending) = self.pending.lock() {
+            if let Some(queue) = pending.get_mut(key) {
+                queue.retain(|p| p.reference != reference);
+                if queue.is_empty() {
+                    pending.remove(key);
+                }
+            }
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -500,7 +1124,7 @@ mod tests {
         assert!(config.require_for.is_empty());
         assert_eq!(
             config.dangerous_tools,
-            vec!["shell", "write_file", "edit_file"]
+            vec!["shell", "write_file", "edit_file", "http_request"]
         );
         assert_eq!(config.auto_approve_timeout_secs, 0);
     }
@@ -634,6 +1258,11 @@ mod tests {
                 "edit_file".to_string(),
             ],
             auto_approve_timeout_secs: 30,
+            audit_log: Some("logs/approvals.jsonl".to_string()),
+            danger_rules: DangerRulesConfig::Rules(vec![DangerRule::contains(
+                "shell", "command", "rm ",
+            )]),
+            per_chat: HashMap::from([("A".to_string(), ApprovalPolicyConfig::AlwaysRequire)]),
         };
 
         let json_str = serde_json::to_string(&config).expect("serialize");
@@ -647,6 +1276,9 @@ mod tests {
             deserialized.auto_approve_timeout_secs,
             config.auto_approve_timeout_secs
         );
+        assert_eq!(deserialized.per_chat, config.per_chat);
+        assert_eq!(deserialized.audit_log, config.audit_log);
+        assert_eq!(deserialized.danger_rules, config.danger_rules);
     }
 
     #[test]
@@ -675,10 +1307,11 @@ mod tests {
     #[test]
     fn test_default_dangerous_tools_list() {
         let defaults = ApprovalGate::default_dangerous_tools();
-        assert_eq!(defaults.len(), 3);
+        assert_eq!(defaults.len(), 4);
         assert!(defaults.contains(&"shell".to_string()));
         assert!(defaults.contains(&"write_file".to_string()));
         assert!(defaults.contains(&"edit_file".to_string()));
+        assert!(defaults.contains(&"http_request".to_string()));
     }
 
     #[test]
@@ -731,4 +1364,481 @@ mod tests {
         let gate = ApprovalGate::new(config);
         assert_eq!(*gate.policy(), ApprovalPolicy::AlwaysAllow);
     }
+
+    // ---- AsyncApprovalBroker -------------------------------------------
+
+    fn broker(timeout_ms: u64) -> (Arc<MessageBus>, Arc<AsyncApprovalBroker>) {
+        let bus = Arc::new(MessageBus::new());
+        let broker = AsyncApprovalBroker::new(bus.clone(), &ApprovalConfig::default())
+            .with_timeout(Some(std::time::Duration::from_millis(timeout_ms)));
+        (bus, Arc::new(broker))
+    }
+
+    /// Start a request and return it with the prompt that was published.
+    async fn start_request(
+        bus: &MessageBus,
+        broker: &Arc<AsyncApprovalBroker>,
+        chat_id: &str,
+    ) -> (tokio::task::JoinHandle<ApprovalResponse>, OutboundMessage) {
+        let broker = Arc::clone(broker);
+        let chat = chat_id.to_string();
+        let handle = tokio::spawn(async move {
+            broker
+                .request_approval("telegram", &chat, "Approve execution? (yes/no)")
+                .await
+        });
+        let prompt = bus.consume_outbound().await.unwrap();
+        (handle, prompt)
+    }
+
+    fn reply(chat_id: &str, text: &str) -> InboundMessage {
+        InboundMessage::new("telegram", "user1", chat_id, text)
+    }
+
+    #[tokio::test]
+    async fn test_broker_publishes_prompt_and_approves() {
+        let (bus, broker) = broker(5_000);
+        let (handle, prompt) = start_request(&bus, &broker, "chat1").await;
+        assert_eq!(prompt.channel, "telegram");
+        assert_eq!(prompt.chat_id, "chat1");
+        assert!(prompt.content.starts_with("Approve execution? (yes/no)"));
+        assert_eq!(broker.pending_count(), 1);
+
+        assert!(!broker.is_reply(&reply("chat1", "what is this?")));
+        assert!(!broker.try_resolve(&reply("chat1", "what is this?")));
+        assert!(!broker.is_reply(&reply("other", "yes")));
+        assert!(!broker.try_resolve(&reply("other", "yes")));
+        assert!(broker.is_reply(&reply("chat1", "Yes!")));
+        assert!(broker.try_resolve(&reply("chat1", "Yes!")));
+        assert_eq!(handle.await.unwrap(), ApprovalResponse::Approved);
+        assert_eq!(broker.pending_count(), 0);
+
+        // Nothing pending any more: a later "yes" is an ordinary message.
+        assert!(!broker.is_reply(&reply("chat1", "yes")));
+        assert!(!broker.try_resolve(&reply("chat1", "yes")));
+    }
+
+    #[tokio::test]
+    async fn test_broker_denial_reason_and_reference() {
+        let (bus, broker) = broker(5_000);
+        let (first, _) = start_request(&bus, &broker, "chat1").await;
+        let (second, prompt) = start_request(&bus, &broker, "chat1").await;
+        let reference = prompt
+            .content
+            .split("(ref: ")
+            .nth(1)
+            .unwrap()
+            .split_whitespace()
+            .next()
+            .unwrap()
+            .to_string();
+
+        // A reference targets the second request even though the first is older.
+        assert!(broker.try_resolve(&reply("chat1", &format!("deny {} too risky", reference))));
+        assert_eq!(
+            second.await.unwrap(),
+            ApprovalResponse::Denied("too risky".to_string())
+        );
+
+        // Bare reply answers the oldest; text without a reference is the reason.
+        assert!(broker.try_resolve(&reply("chat1", "no not now")));
+        assert_eq!(
+            first.await.unwrap(),
+            ApprovalResponse::Denied("not now".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_broker_default_denial_reason() {
+        let (bus, broker) = broker(5_000);
+        let (handle, _) = start_request(&bus, &broker, "chat1").await;
+        assert!(broker.try_resolve(&reply("chat1", "no")));
+        assert_eq!(
+            handle.await.unwrap(),
+            ApprovalResponse::Denied("denied by user".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_broker_times_out() {
+        let (bus, broker) = broker(50);
+        let (handle, _) = start_request(&bus, &broker, "chat1").await;
+        assert_eq!(handle.await.unwrap(), ApprovalResponse::TimedOut);
+        assert_eq!(broker.pending_count(), 0);
+        assert!(!broker.try_resolve(&reply("chat1", "yes")));
+    }
+
+    #[test]
+    fn test_broker_timeout_from_config() {
+        let bus = Arc::new(MessageBus::new());
+        let config = ApprovalConfig {
+            auto_approve_timeout_secs: 30,
+            ..Default::default()
+        };
+        let broker = AsyncApprovalBroker::new(bus.clone(), &config);
+        assert_eq!(broker.timeout, Some(std::time::Duration::from_secs(30)));
+        let broker = AsyncApprovalBroker::new(bus, &ApprovalConfig::default());
+        assert_eq!(broker.timeout, None);
+    }
+
+    #[test]
+    fn test_parse_approval_reply() {
+        assert_eq!(parse_approval_reply("  approve "), Some((true, "")));
+        assert_eq!(parse_approval_reply("OK, go"), Some((true, "go")));
+        assert_eq!(
+            parse_approval_reply("Deny  because"),
+            Some((false, "because"))
+        );
+        assert_eq!(parse_approval_reply("yesterday"), None);
+        assert_eq!(parse_approval_reply(""), None);
+    }
+
+    // ---- Per-chat overrides --------------------------------------------
+
+    #[test]
+    fn test_per_chat_override_takes_precedence() {
+        let config = ApprovalConfig {
+            enabled: true,
+            policy: ApprovalPolicyConfig::AlwaysAllow,
+            per_chat: HashMap::from([
+                ("A".to_string(), ApprovalPolicyConfig::RequireForDangerous),
+                ("trusted".to_string(), ApprovalPolicyConfig::AlwaysAllow),
+            ]),
+            ..Default::default()
+        };
+        let gate = ApprovalGate::new(config);
+
+        assert!(gate.requires_approval_for_chat("shell", "A"));
+        assert!(!gate.requires_approval_for_chat("echo", "A"));
+        assert!(!gate.requires_approval_for_chat("shell", "B"));
+        assert!(!gate.requires_approval_for_chat("shell", "trusted"));
+        assert!(!gate.requires_approval("shell"));
+    }
+
+    #[test]
+    fn test_per_chat_override_can_relax_global_policy() {
+        let config = ApprovalConfig {
+            enabled: true,
+            policy: ApprovalPolicyConfig::RequireForTools,
+            require_for: vec!["shell".to_string()],
+            per_chat: HashMap::from([("owner".to_string(), ApprovalPolicyConfig::AlwaysAllow)]),
+            ..Default::default()
+        };
+        let gate = ApprovalGate::new(config);
+
+        assert!(!gate.requires_approval_for_chat("shell", "owner"));
+        assert!(gate.requires_approval_for_chat("shell", "stranger"));
+        assert!(gate.requires_approval("shell"));
+    }
+
+    #[test]
+    fn test_per_chat_override_respects_master_switch() {
+        let config = ApprovalConfig {
+            enabled: false,
+            per_chat: HashMap::from([("A".to_string(), ApprovalPolicyConfig::AlwaysRequire)]),
+            ..Default::default()
+        };
+        let gate = ApprovalGate::new(config);
+        assert!(!gate.requires_approval_for_chat("shell", "A"));
+    }
+
+    #[test]
+    fn test_per_chat_config_deserialize() {
+        let config: ApprovalConfig = serde_json::from_value(json!({
+            "enabled": true,
+            "per_chat": {"A": "always_require"}
+        }))
+        .unwrap();
+        assert_eq!(
+            config.per_chat.get("A"),
+            Some(&ApprovalPolicyConfig::AlwaysRequire)
+        );
+        assert!(ApprovalConfig::default().per_chat.is_empty());
+    }
+
+    // ---- Danger rules --------------------------------------------------
+
+    fn dangerous_gate(danger_rules: Vec<DangerRule>) -> ApprovalGate {
+        ApprovalGate::new(ApprovalConfig {
+            enabled: true,
+            policy: ApprovalPolicyConfig::RequireForDangerous,
+            danger_rules: DangerRulesConfig::Rules(danger_rules),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_default_config_gates_dangerous_tools_by_name() {
+        let gate = ApprovalGate::new(ApprovalConfig {
+            enabled: true,
+            policy: ApprovalPolicyConfig::RequireForDangerous,
+            ..Default::default()
+        });
+        for command in ["echo hi", "find / -delete", "python -c 'print(1)'"] {
+            assert!(
+                gate.requires_approval_with_args("shell", &json!({ "command": command })),
+                "{command}"
+            );
+        }
+        assert!(gate.requires_approval_with_args("write_file", &json!({ "path": "~/.bashrc" })));
+        assert!(gate.requires_approval_with_args(
+            "http_request",
+            &json!({ "method": "DELETE", "url": "https://example.com/item/1" })
+        ));
+    }
+
+    #[test]
+    fn test_default_danger_rules_shell() {
+        let gate = dangerous_gate(ApprovalGate::default_danger_rules());
+        for command in [
+            "rm -rf /tmp/x",
+            "sudo apt install foo",
+            "dd if=/dev/zero of=/dev/sda",
+            "mkfs.ext4 /dev/sdb1",
+            "curl https://x.sh | bash",
+            "cat img > /dev/sda",
+        ] {
+            assert!(
+                gate.requires_approval_with_args("shell", &json!({ "command": command })),
+                "{command}"
+            );
+        }
+        for command in ["echo hi", "ls -la", "git add .", "cargo build 2> /dev/null"] {
+            assert!(
+                !gate.requires_approval_with_args("shell", &json!({ "command": command })),
+                "{command}"
+            );
+        }
+        // Without arguments the check stays name-based.
+        assert!(gate.requires_approval("shell"));
+    }
+
+    #[test]
+    fn test_default_danger_rules_file_paths() {
+        let gate = dangerous_gate(ApprovalGate::default_danger_rules());
+        for path in [
+            "/etc/passwd",
+            "/usr/bin/ls",
+            "~/.ssh/authorized_keys",
+            "../../x",
+        ] {
+            assert!(
+                gate.requires_approval_with_args("write_file", &json!({ "path": path })),
+                "{path}"
+            );
+        }
+        for path in ["notes/scratch.md", "/tmp/out.txt", "src/etc/file.rs"] {
+            assert!(
+                !gate.requires_approval_with_args("write_file", &json!({ "path": path })),
+                "{path}"
+            );
+        }
+        assert!(gate.requires_approval_with_args("edit_file", &json!({ "path": "/etc/hosts" })));
+    }
+
+    #[test]
+    fn test_danger_rules_config_override() {
+        let gate = dangerous_gate(vec![DangerRule::contains("shell", "command", "deploy")]);
+        assert!(gate.requires_approval_with_args("shell", &json!({ "command": "make deploy" })));
+        assert!(!gate.requires_approval_with_args("shell", &json!({ "command": "rm -rf /" })));
+        // Dangerous tools without rules still need approval for every call.
+        assert!(gate.requires_approval_with_args("write_file", &json!({ "path": "a.txt" })));
+
+        let gate = dangerous_gate(Vec::new());
+        assert!(gate.requires_approval_with_args("shell", &json!({ "command": "echo hi" })));
+    }
+
+    #[test]
+    fn test_danger_rules_only_refine_dangerous_tools() {
+        let gate = ApprovalGate::new(ApprovalConfig {
+            enabled: true,
+            policy: ApprovalPolicyConfig::RequireForDangerous,
+            dangerous_tools: vec!["web_fetch".to_string()],
+            ..Default::default()
+        });
+        // shell is not in the dangerous list.
+        assert!(!gate.requires_approval_with_args("shell", &json!({ "command": "rm -rf /" })));
+
+        let gate = ApprovalGate::new(ApprovalConfig {
+            enabled: true,
+            policy: ApprovalPolicyConfig::AlwaysRequire,
+            ..Default::default()
+        });
+        assert!(gate.requires_approval_with_args("shell", &json!({ "command": "echo hi" })));
+    }
+
+    #[test]
+    fn test_danger_rule_invalid_regex_fails_closed() {
+        let gate = dangerous_gate(vec![DangerRule::regex("shell", "command", "(unclosed")]);
+        assert!(gate.requires_approval_with_args("shell", &json!({ "command": "echo hi" })));
+        // A missing or non-string argument never matches.
+        assert!(!gate.requires_approval_with_args("shell", &json!({ "command": 5 })));
+    }
+
+    #[test]
+    fn test_danger_rules_per_chat() {
+        let gate = ApprovalGate::new(ApprovalConfig {
+            enabled: true,
+            policy: ApprovalPolicyConfig::AlwaysRequire,
+            per_chat: HashMap::from([(
+                "owner".to_string(),
+                ApprovalPolicyConfig::RequireForDangerous,
+            )]),
+            danger_rules: DangerRulesConfig::Preset(DangerRulePreset::Builtin),
+            ..Default::default()
+        });
+        let safe = json!({ "command": "ls" });
+        assert!(!gate.requires_approval_for_call("shell", &safe, "owner"));
+        assert!(gate.requires_approval_for_call("shell", &safe, "guest"));
+    }
+
+    #[test]
+    fn test_danger_rule_deserialize() {
+        let rule: DangerRule = serde_json::from_value(json!({
+            "tool": "shell",
+            "arg": "command",
+            "regex": "\\b(rm|mkfs|dd)\\b"
+        }))
+        .unwrap();
+        assert_eq!(
+            rule,
+            DangerRule::regex("shell", "command", r"\b(rm|mkfs|dd)\b")
+        );
+
+        let config: ApprovalConfig = serde_json::from_value(json!({
+            "danger_rules": [{"tool": "shell", "arg": "command", "contains": "rm"}]
+        }))
+        .unwrap();
+        assert_eq!(
+            config.danger_rules.rules(),
+            vec![DangerRule::contains("shell", "command", "rm")]
+        );
+        assert!(ApprovalConfig::default().danger_rules.rules().is_empty());
+
+        let config: ApprovalConfig =
+            serde_json::from_value(json!({ "danger_rules": "builtin" })).unwrap();
+        assert_eq!(
+            config.danger_rules,
+            DangerRulesConfig::Preset(DangerRulePreset::Builtin)
+        );
+        assert_eq!(
+            config.danger_rules.rules(),
+            ApprovalGate::default_danger_rules()
+        );
+        assert_eq!(
+            serde_json::to_value(&config.danger_rules).unwrap(),
+            json!("builtin")
+        );
+    }
+
+    // ---- Audit log -----------------------------------------------------
+
+    fn enabled_config() -> ApprovalConfig {
+        ApprovalConfig {
+            enabled: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_audit_log_records_denied_decision() {
+        let workspace = tempfile::tempdir().unwrap();
+        let gate = ApprovalGate::new(enabled_config())
+            .with_audit_log("logs/approvals.jsonl", workspace.path())
+            .unwrap();
+        let args = json!({ "command": "rm -rf /tmp/x" });
+
+        gate.record_decision(
+            "shell",
+            &args,
+            Some("chat-42"),
+            &ApprovalResponse::Denied("not now".to_string()),
+            false,
+        );
+
+        let path = workspace.path().join("logs/approvals.jsonl");
+        assert_eq!(gate.audit_log().unwrap().path(), path.as_path());
+        let content = std::fs::read_to_string(&path).unwrap();
+        let line: Value = serde_json::from_str(content.trim_end()).unwrap();
+        assert_eq!(line["tool_name"], "shell");
+        assert_eq!(line["decision"], "denied");
+        assert_eq!(line["reason"], "not now");
+        assert_eq!(line["chat_id"], "chat-42");
+        assert_eq!(line["auto_approved"], false);
+        let hash = line["arguments_sha256"].as_str().unwrap();
+        assert_eq!(hash.len(), 64);
+        assert!(hash.chars().all(|c| c.is_ascii_hexdigit()));
+        assert!(!content.contains("rm -rf"));
+        assert!(line["timestamp"]
+            .as_str()
+            .unwrap()
+            .parse::<DateTime<Utc>>()
+            .is_ok());
+    }
+
+    #[test]
+    fn test_audit_log_appends_lines() {
+        let workspace = tempfile::tempdir().unwrap();
+        let gate = ApprovalGate::new(enabled_config())
+            .with_audit_log("approvals.jsonl", workspace.path())
+            .unwrap();
+        let args = json!({ "path": "a.txt" });
+        gate.record_decision(
+            "write_file",
+            &args,
+            None,
+            &ApprovalResponse::Approved,
+            false,
+        );
+        gate.record_decision("write_file", &args, None, &ApprovalResponse::TimedOut, true);
+
+        // Reopening appends rather than truncating.
+        let reopened = ApprovalGate::new(enabled_config())
+            .with_audit_log("approvals.jsonl", workspace.path())
+            .unwrap();
+        reopened.record_decision("shell", &args, None, &ApprovalResponse::Approved, false);
+
+        let content = std::fs::read_to_string(workspace.path().join("approvals.jsonl")).unwrap();
+        let entries: Vec<ApprovalAuditEntry> = content
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].decision, "approved");
+        assert_eq!(entries[1].decision, "timed_out");
+        assert!(entries[1].auto_approved);
+        assert_eq!(entries[0].arguments_sha256, entries[1].arguments_sha256);
+        assert!(entries[0].chat_id.is_none());
+        assert_eq!(entries[2].tool_name, "shell");
+    }
+
+    #[test]
+    fn test_audit_log_rejects_path_outside_workspace() {
+        let workspace = tempfile::tempdir().unwrap();
+        let outside = tempfile::tempdir().unwrap();
+        let err = match ApprovalGate::new(enabled_config())
+            .with_audit_log(outside.path().join("approvals.jsonl"), workspace.path())
+        {
+            Ok(_) => panic!("audit log outside the workspace was accepted"),
+            Err(e) => e,
+        };
+        assert!(matches!(err, ZeptoError::SecurityViolation(_)));
+
+        let err =
+            ApprovalAuditLog::open(Path::new("../approvals.jsonl"), workspace.path()).unwrap_err();
+        assert!(matches!(err, ZeptoError::SecurityViolation(_)));
+    }
+
+    #[test]
+    fn test_record_decision_without_audit_log_is_noop() {
+        let gate = ApprovalGate::new(enabled_config());
+        assert!(gate.audit_log().is_none());
+        gate.record_decision(
+            "shell",
+            &json!({}),
+            None,
+            &ApprovalResponse::Approved,
+            false,
+        );
+    }
 }
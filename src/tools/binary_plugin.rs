@@ -15,7 +15,7 @@ use tracing::warn;
 
 use crate::error::{Result, ZeptoError};
 use crate::plugins::types::PluginToolDef;
-use crate::tools::types::{Tool, ToolContext};
+use crate::tools::types::{Tool, ToolContext, ToolSource};
 
 // ---- JSON-RPC 2.0 types (local, not coupled to MCP) ----
 
@@ -119,6 +119,10 @@ impl Tool for BinaryPluginTool {
         self.description()
     }
 
+    fn source(&self) -> ToolSource {
+        ToolSource::Plugin
+    }
+
     fn parameters(&self) -> Value {
         self.def.parameters.clone()
     }
@@ -127,6 +131,8 @@ impl Tool for BinaryPluginTool {
         use tokio::io::AsyncWriteExt;
         use tokio::process::Command;
 
+        self.validate_args(&args)?;
+
         // Build JSON-RPC request
         let request = PluginJsonRpcRequest {
             jsonrpc: "2.0".to_string(),
@@ -273,6 +279,7 @@ impl Tool for BinaryPluginTool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::plugins::types::PluginOutputFormat;
     use serde_json::json;
 
     // ---- JSON-RPC serialization tests ----
@@ -346,6 +353,7 @@ mod tests {
             working_dir: None,
             timeout_secs: None,
             env: None,
+            output_format: PluginOutputFormat::Text,
         }
     }
 
@@ -358,6 +366,7 @@ mod tests {
             30,
         );
         assert_eq!(tool.name(), "my_tool");
+        assert_eq!(tool.source(), ToolSource::Plugin);
     }
 
     #[test]
@@ -416,6 +425,25 @@ echo '{"jsonrpc":"2.0","result":{"output":"hello world"},"id":1}'"#,
         assert_eq!(result.unwrap(), "hello world");
     }
 
+    #[tokio::test]
+    async fn test_execute_rejects_args_violating_schema() {
+        // The binary does not exist: validation must fail before spawning.
+        let tool = BinaryPluginTool::new(
+            test_tool_def(),
+            "test-plugin",
+            PathBuf::from("/nonexistent/plugin"),
+            30,
+        );
+        let ctx = ToolContext::new();
+        let err = tool.execute(json!({"x": 42}), &ctx).await.unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Invalid arguments for tool 'my_tool': 'x' must be of type string"),
+            "unexpected error: {}",
+            err
+        );
+    }
+
     #[cfg(unix)]
     #[tokio::test]
     async fn test_execute_error_response() {
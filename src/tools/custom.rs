@@ -13,7 +13,7 @@ use crate::config::CustomToolDef;
 use crate::error::{Result, ZeptoError};
 use crate::security::ShellSecurityConfig;
 
-use super::types::{Tool, ToolContext};
+use super::types::{Tool, ToolContext, ToolSource};
 
 /// Maximum output bytes to capture from custom tool stdout (50KB).
 const MAX_OUTPUT_BYTES: usize = 50_000;
@@ -67,6 +67,10 @@ impl Tool for CustomTool {
         self.description()
     }
 
+    fn source(&self) -> ToolSource {
+        ToolSource::Custom
+    }
+
     fn parameters(&self) -> Value {
         match &self.def.parameters {
             None => json!({
@@ -194,11 +198,7 @@ mod tests {
     use crate::tools::types::ToolContext;
 
     fn test_ctx() -> ToolContext {
-        ToolContext {
-            workspace: Some(std::env::temp_dir().to_string_lossy().to_string()),
-            channel: None,
-            chat_id: None,
-        }
+        ToolContext::new().with_workspace(&std::env::temp_dir().to_string_lossy())
     }
 
     fn simple_def(name: &str, command: &str) -> CustomToolDef {
@@ -248,6 +248,7 @@ mod tests {
     fn test_tool_name() {
         let tool = CustomTool::new(simple_def("cpu_temp", "echo 42"));
         assert_eq!(tool.name(), "cpu_temp");
+        assert_eq!(tool.source(), ToolSource::Custom);
     }
 
     #[test]
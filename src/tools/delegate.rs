@@ -18,11 +18,14 @@ use crate::providers::{ChatOptions, LLMProvider, LLMResponse, ToolDefinition};
 use crate::runtime::NativeRuntime;
 use crate::session::{Message, SessionManager};
 use crate::tools::filesystem::{EditFileTool, ListDirTool, ReadFileTool, WriteFileTool};
-use crate::tools::memory::{MemoryGetTool, MemorySearchTool};
+use crate::tools::grep::GrepTool;
+use crate::tools::memory::{MemoryGetTool, MemorySearchTool, MemoryWriteTool};
 use crate::tools::message::MessageTool;
 use crate::tools::shell::ShellTool;
+use crate::tools::tree::TreeTool;
 use crate::tools::web::WebFetchTool;
 use crate::tools::EchoTool;
+use crate::utils::url_policy::UrlPolicy;
 
 use super::{Tool, ToolContext};
 
@@ -63,9 +66,18 @@ impl DelegateTool {
             Box::new(WriteFileTool),
             Box::new(ListDirTool),
             Box::new(EditFileTool),
+            Box::new(GrepTool),
+            Box::new(TreeTool),
             Box::new(ShellTool::with_runtime(Arc::new(NativeRuntime::new()))),
-            Box::new(WebFetchTool::new()),
-            Box::new(MessageTool::new(self.bus.clone())),
+            Box::new(
+                WebFetchTool::new()
+                    .with_render(&self.config.tools.web.fetch)
+                    .with_url_policy(UrlPolicy::from_config(&self.config.tools.url_policy)),
+            ),
+            Box::new(
+                MessageTool::new(self.bus.clone())
+                    .with_channels(crate::channels::enabled_channel_names(&self.config)),
+            ),
         ];
 
         // Add memory tools if enabled
@@ -74,6 +86,7 @@ impl DelegateTool {
             _ => {
                 all_tools.push(Box::new(MemorySearchTool::new(self.config.memory.clone())));
                 all_tools.push(Box::new(MemoryGetTool::new(self.config.memory.clone())));
+                all_tools.push(Box::new(MemoryWriteTool::new(self.config.memory.clone())));
             }
         }
 
@@ -243,6 +256,10 @@ impl LLMProvider for ProviderRef {
         self.0.default_model()
     }
 
+    fn health_snapshot(&self) -> Vec<crate::providers::ProviderHealthStatus> {
+        self.0.health_snapshot()
+    }
+
     async fn chat(
         &self,
         messages: Vec<Message>,
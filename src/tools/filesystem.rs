@@ -11,6 +11,7 @@ use std::path::Path;
 use crate::error::{Result, ZeptoError};
 use crate::security::validate_path_in_workspace;
 
+use super::patch::Patch;
 use super::{Tool, ToolContext};
 
 /// Resolve and validate a path relative to the workspace.
@@ -28,6 +29,38 @@ fn resolve_path(path: &str, ctx: &ToolContext) -> Result<String> {
     Ok(safe_path.as_path().to_string_lossy().to_string())
 }
 
+/// Replace `path` with `content` by writing a sibling temp file and renaming
+/// it over the target, so readers never see a half-written file. An existing
+/// file keeps its permissions.
+async fn write_atomic(path: &str, content: &str) -> Result<()> {
+    let target = Path::new(path);
+    let file_name = target
+        .file_name()
+        .ok_or_else(|| ZeptoError::Tool(format!("Invalid file path '{}'", path)))?;
+    let temp = target.with_file_name(format!(
+        ".{}.{}.tmp",
+        file_name.to_string_lossy(),
+        uuid::Uuid::new_v4().simple()
+    ));
+
+    let result = async {
+        tokio::fs::write(&temp, content).await?;
+        if let Ok(meta) = tokio::fs::metadata(target).await {
+            tokio::fs::set_permissions(&temp, meta.permissions()).await?;
+        }
+        tokio::fs::rename(&temp, target).await
+    }
+    .await;
+    if let Err(e) = result {
+        let _ = tokio::fs::remove_file(&temp).await;
+        return Err(ZeptoError::Tool(format!(
+            "Failed to write file '{}': {}",
+            path, e
+        )));
+    }
+    Ok(())
+}
+
 /// Tool for reading file contents.
 ///
 /// Reads the entire contents of a file and returns it as a string.
@@ -64,6 +97,10 @@ impl Tool for ReadFileTool {
         "Read file"
     }
 
+    fn is_parallel_safe(&self) -> bool {
+        true
+    }
+
     fn parameters(&self) -> Value {
         json!({
             "type": "object",
@@ -145,6 +182,10 @@ impl Tool for WriteFileTool {
         })
     }
 
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<String> {
         let path = args
             .get("path")
@@ -158,6 +199,23 @@ impl Tool for WriteFileTool {
 
         let full_path = resolve_path(path, ctx)?;
 
+        if ctx.dry_run {
+            let existing = tokio::fs::metadata(&full_path).await.ok();
+            return Ok(match existing {
+                Some(meta) => format!(
+                    "[DRY RUN] Would overwrite {} ({} bytes) with {} bytes",
+                    full_path,
+                    meta.len(),
+                    content.len()
+                ),
+                None => format!(
+                    "[DRY RUN] Would create {} with {} bytes",
+                    full_path,
+                    content.len()
+                ),
+            });
+        }
+
         // Create parent directories if they don't exist
         if let Some(parent) = Path::new(&full_path).parent() {
             if !parent.as_os_str().is_empty() {
@@ -214,6 +272,10 @@ impl Tool for ListDirTool {
         "List directory"
     }
 
+    fn is_parallel_safe(&self) -> bool {
+        true
+    }
+
     fn parameters(&self) -> Value {
         json!({
             "type": "object",
@@ -268,10 +330,17 @@ impl Tool for ListDirTool {
 /// Searches for a specific string in the file and replaces it with new content.
 /// This is useful for making targeted edits without rewriting the entire file.
 ///
+/// With `mode: "diff"` the tool applies a unified diff instead. Every hunk
+/// must match before anything is written, so a stale diff leaves the file
+/// untouched. A diff from `/dev/null` creates the file. Both modes write via
+/// a temp file and rename.
+///
 /// # Parameters
 /// - `path`: The path to the file to edit (required)
-/// - `old_text`: The text to search for and replace (required)
-/// - `new_text`: The text to replace it with (required)
+/// - `mode`: `"replace"` (default) or `"diff"`
+/// - `old_text`: The text to search for and replace (replace mode)
+/// - `new_text`: The text to replace it with (replace mode)
+/// - `diff`: Unified diff for the file (diff mode)
 ///
 /// # Example
 /// ```rust
@@ -298,7 +367,8 @@ impl Tool for EditFileTool {
     }
 
     fn description(&self) -> &str {
-        "Edit a file by replacing specified text with new content"
+        "Edit a file by replacing specified text with new content, \
+         or apply a unified diff with mode 'diff'"
     }
 
     fn compact_description(&self) -> &str {
@@ -313,25 +383,57 @@ impl Tool for EditFileTool {
                     "type": "string",
                     "description": "The path to the file to edit"
                 },
+                "mode": {
+                    "type": "string",
+                    "enum": ["replace", "diff"],
+                    "description": "'replace' (default) swaps old_text for new_text; 'diff' applies a unified diff"
+                },
                 "old_text": {
                     "type": "string",
-                    "description": "The text to search for and replace"
+                    "description": "The text to search for and replace (replace mode)"
                 },
                 "new_text": {
                     "type": "string",
-                    "description": "The text to replace it with"
+                    "description": "The text to replace it with (replace mode)"
+                },
+                "diff": {
+                    "type": "string",
+                    "description": "Unified diff with @@ hunks for this file (diff mode). Use --- /dev/null to create a file"
                 }
             },
-            "required": ["path", "old_text", "new_text"]
+            "required": ["path"]
         })
     }
 
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<String> {
         let path = args
             .get("path")
             .and_then(|v| v.as_str())
             .ok_or_else(|| ZeptoError::Tool("Missing 'path' argument".into()))?;
 
+        let mode =
+            args.get("mode")
+                .and_then(|v| v.as_str())
+                .unwrap_or(if args.get("diff").is_some() {
+                    "diff"
+                } else {
+                    "replace"
+                });
+        match mode {
+            "replace" => {}
+            "diff" => return self.apply_diff(path, &args, ctx).await,
+            other => {
+                return Err(ZeptoError::Tool(format!(
+                    "Unknown mode '{}'; expected 'replace' or 'diff'",
+                    other
+                )))
+            }
+        }
+
         let old_text = args
             .get("old_text")
             .and_then(|v| v.as_str())
@@ -364,15 +466,20 @@ impl Tool for EditFileTool {
 
         // Replace the text
         let new_content = content.replace(old_text, new_text);
+        let replacements = content.matches(old_text).count();
 
-        // Write back
-        tokio::fs::write(&full_path, &new_content)
-            .await
-            .map_err(|e| {
-                ZeptoError::Tool(format!("Failed to write file '{}': {}", full_path, e))
-            })?;
+        if ctx.dry_run {
+            return Ok(format!(
+                "[DRY RUN] Would replace {} occurrence(s) in {} ({} -> {} bytes)",
+                replacements,
+                full_path,
+                content.len(),
+                new_content.len()
+            ));
+        }
+
+        write_atomic(&full_path, &new_content).await?;
 
-        let replacements = content.matches(old_text).count();
         Ok(format!(
             "Successfully replaced {} occurrence(s) in {}",
             replacements, full_path
@@ -380,6 +487,53 @@ impl Tool for EditFileTool {
     }
 }
 
+impl EditFileTool {
+    /// Diff mode: apply every hunk in memory, then write once.
+    async fn apply_diff(&self, path: &str, args: &Value, ctx: &ToolContext) -> Result<String> {
+        let diff = args
+            .get("diff")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| ZeptoError::Tool("Missing 'diff' argument".into()))?;
+        let patch = Patch::parse(diff)?;
+
+        let full_path = resolve_path(path, ctx)?;
+        let original = match tokio::fs::read_to_string(&full_path).await {
+            Ok(content) if patch.creates && !content.is_empty() => {
+                return Err(ZeptoError::Tool(format!(
+                    "Diff creates '{}' but the file already exists",
+                    full_path
+                )));
+            }
+            Ok(content) => content,
+            Err(e) if patch.creates && e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => {
+                return Err(ZeptoError::Tool(format!(
+                    "Failed to read file '{}': {}",
+                    full_path, e
+                )));
+            }
+        };
+
+        let applied = patch.apply(&original)?;
+        let summary = format!(
+            "{} hunk(s) to {} (+{} -{} lines)",
+            applied.hunks, full_path, applied.added, applied.removed
+        );
+        if ctx.dry_run {
+            return Ok(format!("[DRY RUN] Would apply {}", summary));
+        }
+
+        if let Some(parent) = Path::new(&full_path).parent() {
+            tokio::fs::create_dir_all(parent).await.map_err(|e| {
+                ZeptoError::Tool(format!("Failed to create parent directories: {}", e))
+            })?;
+        }
+        write_atomic(&full_path, &applied.content).await?;
+
+        Ok(format!("Successfully applied {}", summary))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -501,6 +655,41 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_write_file_tool_dry_run() {
+        let dir = tempdir().unwrap();
+        // Use canonical path to avoid macOS /var -> /private/var mismatch
+        let canonical = dir.path().canonicalize().unwrap();
+
+        let tool = WriteFileTool;
+        let ctx = ToolContext::new()
+            .with_workspace(canonical.to_str().unwrap())
+            .with_dry_run(true);
+
+        let result = tool
+            .execute(json!({"path": "plan/new.txt", "content": "12345"}), &ctx)
+            .await
+            .unwrap();
+        let target = canonical.join("plan/new.txt");
+        assert!(result.starts_with("[DRY RUN]"), "{}", result);
+        assert!(result.contains(target.to_str().unwrap()), "{}", result);
+        assert!(result.contains("5 bytes"), "{}", result);
+        assert!(!target.exists());
+        assert!(!canonical.join("plan").exists());
+
+        fs::write(canonical.join("existing.txt"), "old").unwrap();
+        let result = tool
+            .execute(json!({"path": "existing.txt", "content": "newer"}), &ctx)
+            .await
+            .unwrap();
+        assert!(result.contains("overwrite"), "{}", result);
+        assert!(result.contains("(3 bytes) with 5 bytes"), "{}", result);
+        assert_eq!(
+            fs::read_to_string(canonical.join("existing.txt")).unwrap(),
+            "old"
+        );
+    }
+
     #[tokio::test]
     async fn test_write_file_tool_missing_content() {
         let tool = WriteFileTool;
@@ -592,6 +781,37 @@ mod tests {
         assert_eq!(fs::read_to_string(&file_path).unwrap(), "Hello Rust");
     }
 
+    #[tokio::test]
+    async fn test_edit_file_tool_dry_run() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("edit_test.txt");
+        fs::write(&file_path, "Hello World").unwrap();
+
+        let tool = EditFileTool;
+        let ctx = ToolContext::new()
+            .with_workspace(dir.path().to_str().unwrap())
+            .with_dry_run(true);
+
+        let result = tool
+            .execute(
+                json!({
+                    "path": "edit_test.txt",
+                    "old_text": "World",
+                    "new_text": "Rust"
+                }),
+                &ctx,
+            )
+            .await
+            .unwrap();
+        assert!(
+            result.contains("[DRY RUN] Would replace 1 occurrence(s)"),
+            "{}",
+            result
+        );
+        assert!(result.contains("(11 -> 10 bytes)"), "{}", result);
+        assert_eq!(fs::read_to_string(&file_path).unwrap(), "Hello World");
+    }
+
     #[tokio::test]
     async fn test_edit_file_tool_multiple_occurrences() {
         let dir = tempdir().unwrap();
@@ -673,6 +893,124 @@ mod tests {
             .contains("Missing 'new_text'"));
     }
 
+    #[tokio::test]
+    async fn test_edit_file_diff_mode_applies_hunks() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("lib.rs");
+        fs::write(
+            &file_path,
+            "fn one() {}\n\nfn two() {}\n\nfn three() {}\n\nfn four() {}\n",
+        )
+        .unwrap();
+        let ctx = ToolContext::new().with_workspace(dir.path().to_str().unwrap());
+
+        let diff = "--- a/lib.rs\n+++ b/lib.rs\n\
+                    @@ -1,3 +1,3 @@\n-fn one() {}\n+fn uno() {}\n \n fn two() {}\n\
+                    @@ -5,3 +5,4 @@\n fn three() {}\n \n-fn four() {}\n+fn quatro() {}\n+fn cinco() {}\n";
+        let result = EditFileTool
+            .execute(
+                json!({"path": "lib.rs", "mode": "diff", "diff": diff}),
+                &ctx,
+            )
+            .await
+            .unwrap();
+        assert!(
+            result.contains("Successfully applied 2 hunk(s)") && result.contains("(+3 -2 lines)"),
+            "{}",
+            result
+        );
+        assert_eq!(
+            fs::read_to_string(&file_path).unwrap(),
+            "fn uno() {}\n\nfn two() {}\n\nfn three() {}\n\nfn quatro() {}\nfn cinco() {}\n"
+        );
+        // No temp files are left behind.
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_edit_file_diff_mode_rejects_mismatch_without_writing() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("notes.txt");
+        fs::write(&file_path, "alpha\nbeta\ngamma\n").unwrap();
+        let ctx = ToolContext::new().with_workspace(dir.path().to_str().unwrap());
+
+        // The first hunk applies, the second does not: nothing is written.
+        let diff = "@@ -1,2 +1,2 @@\n-alpha\n+ALPHA\n beta\n@@ -3 +3 @@\n-delta\n+DELTA\n";
+        let err = EditFileTool
+            .execute(
+                json!({"path": "notes.txt", "mode": "diff", "diff": diff}),
+                &ctx,
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Hunk 2"), "{}", err);
+        assert_eq!(
+            fs::read_to_string(&file_path).unwrap(),
+            "alpha\nbeta\ngamma\n"
+        );
+
+        let err = EditFileTool
+            .execute(json!({"path": "notes.txt", "mode": "diff"}), &ctx)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Missing 'diff'"));
+        let err = EditFileTool
+            .execute(json!({"path": "notes.txt", "mode": "patch"}), &ctx)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Unknown mode 'patch'"));
+    }
+
+    #[tokio::test]
+    async fn test_edit_file_diff_mode_creates_file() {
+        let dir = tempdir().unwrap();
+        let canonical = dir.path().canonicalize().unwrap();
+        let ctx = ToolContext::new().with_workspace(canonical.to_str().unwrap());
+        let diff = "--- /dev/null\n+++ b/docs/new.md\n@@ -0,0 +1,2 @@\n+# Title\n+body\n";
+
+        // `mode` may be omitted when `diff` is given.
+        let result = EditFileTool
+            .execute(json!({"path": "docs/new.md", "diff": diff}), &ctx)
+            .await
+            .unwrap();
+        assert!(result.contains("(+2 -0 lines)"), "{}", result);
+        assert_eq!(
+            fs::read_to_string(canonical.join("docs/new.md")).unwrap(),
+            "# Title\nbody\n"
+        );
+
+        // Creating over an existing file is refused.
+        let err = EditFileTool
+            .execute(json!({"path": "docs/new.md", "diff": diff}), &ctx)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("already exists"));
+    }
+
+    #[tokio::test]
+    async fn test_edit_file_diff_mode_dry_run() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("a.txt");
+        fs::write(&file_path, "old\n").unwrap();
+        let ctx = ToolContext::new()
+            .with_workspace(dir.path().to_str().unwrap())
+            .with_dry_run(true);
+
+        let result = EditFileTool
+            .execute(
+                json!({"path": "a.txt", "diff": "@@ -1 +1 @@\n-old\n+new\n"}),
+                &ctx,
+            )
+            .await
+            .unwrap();
+        assert!(
+            result.starts_with("[DRY RUN] Would apply 1 hunk(s)"),
+            "{}",
+            result
+        );
+        assert_eq!(fs::read_to_string(&file_path).unwrap(), "old\n");
+    }
+
     #[test]
     fn test_resolve_path_rejects_without_workspace() {
         let ctx = ToolContext::new();
@@ -0,0 +1,565 @@
+//! Workspace content search tool.
+//!
+//! Searches file contents under the workspace with a regular expression and
+//! returns `path:line:content` entries, so the agent can find code without
+//! reading whole files.
+
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use regex::{Regex, RegexBuilder};
+use serde_json::{json, Value};
+
+use crate::error::{Result, ZeptoError};
+use crate::security::validate_path_in_workspace;
+
+use super::{Tool, ToolContext};
+
+/// Matches returned when `max_results` is not given.
+const DEFAULT_MAX_RESULTS: usize = 100;
+
+/// Upper bound for `max_results`.
+const MAX_RESULTS_LIMIT: usize = 1000;
+
+/// Total output size, in bytes, before the result list is cut off.
+const MAX_OUTPUT_BYTES: usize = 32 * 1024;
+
+/// Characters of a matching line kept in the output.
+const MAX_LINE_CHARS: usize = 300;
+
+/// Files larger than this are skipped.
+const MAX_FILE_BYTES: u64 = 1024 * 1024;
+
+/// Bytes inspected for a NUL byte when detecting binary files.
+const BINARY_SNIFF_BYTES: usize = 8 * 1024;
+
+/// Directory depth below the search root.
+const MAX_DIR_DEPTH: usize = 20;
+
+/// Directories never descended into.
+pub(super) const SKIP_DIRS: &[&str] = &[".git", "node_modules", "target"];
+
+/// Tool for searching file contents in the workspace.
+///
+/// # Parameters
+/// - `pattern`: Regular expression to search for (required)
+/// - `path`: File, directory or glob to search, relative to the workspace
+///   (optional, default: the whole workspace). A glob without `/` such as
+///   `*.rs` matches file names at any depth.
+/// - `case_insensitive`: Ignore case (optional, default: false)
+/// - `max_results`: Maximum matching lines to return (optional, default: 100)
+///
+/// Symlinks, binary files, files over 1 MiB and `.git`, `node_modules` and
+/// `target` directories are skipped.
+///
+/// # Example
+/// ```rust
+/// use zeptoclaw::tools::{Tool, ToolContext};
+/// use zeptoclaw::tools::GrepTool;
+/// use serde_json::json;
+///
+/// # tokio_test::block_on(async {
+/// let dir = tempfile::tempdir().unwrap();
+/// std::fs::write(dir.path().join("notes.txt"), "alpha\nbeta\n").unwrap();
+/// let ctx = ToolContext::new().with_workspace(dir.path().to_str().unwrap());
+///
+/// let result = GrepTool.execute(json!({"pattern": "^b"}), &ctx).await.unwrap();
+/// assert_eq!(result, "notes.txt:2:beta");
+/// # });
+/// ```
+pub struct GrepTool;
+
+#[async_trait]
+impl Tool for GrepTool {
+    fn name(&self) -> &str {
+        "grep"
+    }
+
+    fn description(&self) -> &str {
+        "Search file contents in the workspace with a regular expression. \
+         Returns matching lines as path:line:content."
+    }
+
+    fn compact_description(&self) -> &str {
+        "Search files"
+    }
+
+    fn is_parallel_safe(&self) -> bool {
+        true
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "pattern": {
+                    "type": "string",
+                    "description": "Regular expression to search for"
+                },
+                "path": {
+                    "type": "string",
+                    "description": "File, directory or glob to search (e.g. 'src', 'src/**/*.rs', '*.md'). Defaults to the whole workspace"
+                },
+                "case_insensitive": {
+                    "type": "boolean",
+                    "description": "Ignore case when matching (default: false)"
+                },
+                "max_results": {
+                    "type": "integer",
+                    "description": "Maximum number of matching lines to return (default: 100, max: 1000)",
+                    "minimum": 1,
+                    "maximum": MAX_RESULTS_LIMIT
+                }
+            },
+            "required": ["pattern"]
+        })
+    }
+
+    async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<String> {
+        let pattern = args
+            .get("pattern")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| ZeptoError::Tool("Missing 'pattern' argument".into()))?;
+        let case_insensitive = args
+            .get("case_insensitive")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let max_results = args
+            .get("max_results")
+            .and_then(|v| v.as_u64())
+            .map(|n| (n as usize).clamp(1, MAX_RESULTS_LIMIT))
+            .unwrap_or(DEFAULT_MAX_RESULTS);
+        let path = args
+            .get("path")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .unwrap_or(".");
+
+        let workspace = ctx.workspace.as_ref().ok_or_else(|| {
+            ZeptoError::SecurityViolation(
+                "Workspace not configured; filesystem tools require a workspace for safety"
+                    .to_string(),
+            )
+        })?;
+
+        let regex = RegexBuilder::new(pattern)
+            .case_insensitive(case_insensitive)
+            .build()
+            .map_err(|e| ZeptoError::Tool(format!("Invalid pattern '{}': {}", pattern, e)))?;
+
+        let search = Search::new(path, workspace, regex, max_results)?;
+        tokio::task::spawn_blocking(move || search.run())
+            .await
+            .map_err(|e| ZeptoError::Tool(format!("Search task failed: {}", e)))
+    }
+}
+
+/// One resolved search request.
+struct Search {
+    /// Canonical workspace root; output paths are relative to it
+    workspace: PathBuf,
+    /// File or directory to walk
+    root: PathBuf,
+    /// Filter on workspace-relative paths, from a glob
+    filter: Option<PathFilter>,
+    regex: Regex,
+    max_results: usize,
+}
+
+/// Compiled `path` glob.
+struct PathFilter {
+    regex: Regex,
+    /// Match the file name only (glob has no `/`)
+    name_only: bool,
+}
+
+impl PathFilter {
+    fn matches(&self, relative: &str) -> bool {
+        let target = if self.name_only {
+            relative.rsplit('/').next().unwrap_or(relative)
+        } else {
+            relative
+        };
+        self.regex.is_match(target)
+    }
+}
+
+impl Search {
+    /// Resolve `path` against the workspace. The literal part of a glob
+    /// (everything before the first wildcard component) must stay inside
+    /// the workspace.
+    fn new(path: &str, workspace: &str, regex: Regex, max_results: usize) -> Result<Self> {
+        let (base, filter) = if has_glob_meta(path) {
+            let components: Vec<&str> = path.split('/').collect();
+            let literal = components
+                .iter()
+                .take_while(|c| !has_glob_meta(c))
+                .copied()
+                .collect::<Vec<_>>()
+                .join("/");
+            let base = if literal.is_empty() { "." } else { &literal };
+            // Reject escapes in the wildcard part too, e.g. "*/../../etc".
+            validate_path_in_workspace(
+                path.replace(['*', '?', '[', ']'], "x").as_str(),
+                workspace,
+            )?;
+            let glob = path
+                .strip_prefix(workspace)
+                .map(|rest| rest.trim_start_matches('/'))
+                .unwrap_or(path)
+                .trim_start_matches("./");
+            let filter = PathFilter {
+                regex: glob_to_regex(glob)?,
+                name_only: !glob.contains('/'),
+            };
+            (base.to_string(), Some(filter))
+        } else {
+            (path.to_string(), None)
+        };
+
+        let root = validate_path_in_workspace(&base, workspace)?.into_path_buf();
+        let workspace = Path::new(workspace)
+            .canonicalize()
+            .unwrap_or_else(|_| PathBuf::from(workspace));
+        if !root.exists() {
+            return Err(ZeptoError::Tool(format!("Path '{}' does not exist", base)));
+        }
+
+        Ok(Self {
+            workspace,
+            root,
+            filter,
+            regex,
+            max_results,
+        })
+    }
+
+    /// Walk the files and collect matches.
+    fn run(&self) -> String {
+        let mut files = Vec::new();
+        if self.root.is_file() {
+            files.push(self.root.clone());
+        } else {
+            collect_files(&self.root, &mut files, 0);
+        }
+
+        let mut output = String::new();
+        let mut count = 0;
+        for file in files {
+            let relative = file
+                .strip_prefix(&self.workspace)
+                .unwrap_or(&file)
+                .to_string_lossy()
+                .replace('\\', "/");
+            if let Some(filter) = &self.filter {
+                if !filter.matches(&relative) {
+                    continue;
+                }
+            }
+            let Some(text) = read_text(&file) else {
+                continue;
+            };
+            for (index, line) in text.lines().enumerate() {
+                if !self.regex.is_match(line) {
+                    continue;
+                }
+                let entry = format!("{}:{}:{}", relative, index + 1, truncate_line(line));
+                if output.len() + entry.len() + 1 > MAX_OUTPUT_BYTES {
+                    output.push_str(&format!(
+                        "\n[Output truncated at {} bytes after {} matches; narrow the pattern or path]",
+                        MAX_OUTPUT_BYTES, count
+                    ));
+                    return output;
+                }
+                if !output.is_empty() {
+                    output.push('\n');
+                }
+                output.push_str(&entry);
+                count += 1;
+                if count >= self.max_results {
+                    output.push_str(&format!(
+                        "\n[Stopped after {} matches; raise max_results or narrow the search]",
+                        count
+                    ));
+                    return output;
+                }
+            }
+        }
+
+        if output.is_empty() {
+            "No matches found".to_string()
+        } else {
+            output
+        }
+    }
+}
+
+/// Regular files under `dir`, sorted by path. Symlinks are not followed.
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>, depth: usize) {
+    if depth > MAX_DIR_DEPTH {
+        return;
+    }
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut entries: Vec<_> = entries.filter_map(|entry| entry.ok()).collect();
+    entries.sort_by_key(|entry| entry.file_name());
+
+    for entry in entries {
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_symlink() {
+            continue;
+        }
+        let path = entry.path();
+        if file_type.is_dir() {
+            let name = entry.file_name();
+            if !SKIP_DIRS.iter().any(|skip| name == *skip) {
+                collect_files(&path, files, depth + 1);
+            }
+        } else if file_type.is_file() {
+            files.push(path);
+        }
+    }
+}
+
+/// File contents as text, or `None` for large, unreadable or binary files.
+fn read_text(path: &Path) -> Option<String> {
+    let file = fs::File::open(path).ok()?;
+    if file.metadata().ok()?.len() > MAX_FILE_BYTES {
+        return None;
+    }
+    let mut bytes = Vec::new();
+    file.take(MAX_FILE_BYTES).read_to_end(&mut bytes).ok()?;
+    let sniff = &bytes[..bytes.len().min(BINARY_SNIFF_BYTES)];
+    if sniff.contains(&0) {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+fn truncate_line(line: &str) -> String {
+    let line = line.trim_end_matches('\r');
+    match line.char_indices().nth(MAX_LINE_CHARS) {
+        Some((cut, _)) => format!("{}...", &line[..cut]),
+        None => line.to_string(),
+    }
+}
+
+fn has_glob_meta(s: &str) -> bool {
+    s.contains(['*', '?', '['])
+}
+
+/// Translate a glob into an anchored regex: `**` spans directories, `*` and
+/// `?` stay within one path component, `[...]` is a character class.
+pub(super) fn glob_to_regex(glob: &str) -> Result<Regex> {
+    let mut out = String::from("^");
+    let mut chars = glob.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                // "**/" also matches zero directories
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    out.push_str("(?:.*/)?");
+                } else {
+                    out.push_str(".*");
+                }
+            }
+            '*' => out.push_str("[^/]*"),
+            '?' => out.push_str("[^/]"),
+            '[' => {
+                let mut class = String::new();
+                for c in chars.by_ref() {
+                    if c == ']' {
+                        break;
+                    }
+                    class.push(c);
+                }
+                let class = match class.strip_prefix('!') {
+                    Some(rest) => format!("^{}", rest),
+                    None => class,
+                };
+                out.push('[');
+                out.push_str(&class.replace('\\', "\\\\"));
+                out.push(']');
+            }
+            c => out.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    out.push('$');
+    Regex::new(&out).map_err(|e| ZeptoError::Tool(format!("Invalid path glob '{}': {}", glob, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn workspace() -> (tempfile::TempDir, ToolContext) {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src/nested")).unwrap();
+        fs::create_dir_all(root.join(".git")).unwrap();
+        fs::write(root.join("README.md"), "# Demo\nTODO: write docs\n").unwrap();
+        fs::write(
+            root.join("src/main.rs"),
+            "fn main() {\n    // todo: parse args\n    run();\n}\n",
+        )
+        .unwrap();
+        fs::write(root.join("src/nested/lib.rs"), "pub fn run() {}\n").unwrap();
+        fs::write(root.join(".git/config"), "TODO in git\n").unwrap();
+        fs::write(root.join("image.bin"), b"TODO\0\x01\x02").unwrap();
+        let ctx = ToolContext::new().with_workspace(root.canonicalize().unwrap().to_str().unwrap());
+        (dir, ctx)
+    }
+
+    #[tokio::test]
+    async fn test_grep_regex_matching() {
+        let (_dir, ctx) = workspace();
+
+        let result = GrepTool
+            .execute(json!({"pattern": r"fn \w+\("}), &ctx)
+            .await
+            .unwrap();
+        assert_eq!(
+            result,
+            "src/main.rs:1:fn main() {\nsrc/nested/lib.rs:1:pub fn run() {}"
+        );
+
+        // Binary files and .git are skipped; case folding is opt-in.
+        let result = GrepTool
+            .execute(json!({"pattern": "TODO"}), &ctx)
+            .await
+            .unwrap();
+        assert_eq!(result, "README.md:2:TODO: write docs");
+        let result = GrepTool
+            .execute(json!({"pattern": "TODO", "case_insensitive": true}), &ctx)
+            .await
+            .unwrap();
+        assert_eq!(
+            result,
+            "README.md:2:TODO: write docs\nsrc/main.rs:2:    // todo: parse args"
+        );
+
+        let result = GrepTool
+            .execute(json!({"pattern": "zzz"}), &ctx)
+            .await
+            .unwrap();
+        assert_eq!(result, "No matches found");
+
+        let err = GrepTool
+            .execute(json!({"pattern": "("}), &ctx)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Invalid pattern"));
+    }
+
+    #[tokio::test]
+    async fn test_grep_path_globs() {
+        let (_dir, ctx) = workspace();
+        let search = |path: &str| GrepTool.execute(json!({"pattern": "run", "path": path}), &ctx);
+
+        assert_eq!(
+            search("*.rs").await.unwrap(),
+            "src/main.rs:3:    run();\nsrc/nested/lib.rs:1:pub fn run() {}"
+        );
+        assert_eq!(
+            search("src/*.rs").await.unwrap(),
+            "src/main.rs:3:    run();"
+        );
+        assert_eq!(
+            search("src/**/lib.rs").await.unwrap(),
+            "src/nested/lib.rs:1:pub fn run() {}"
+        );
+        assert_eq!(
+            search("src/nested").await.unwrap(),
+            "src/nested/lib.rs:1:pub fn run() {}"
+        );
+        assert_eq!(
+            search("src/main.rs").await.unwrap(),
+            "src/main.rs:3:    run();"
+        );
+        assert!(search("missing").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_grep_result_caps() {
+        let dir = tempdir().unwrap();
+        let padding = "x".repeat(40);
+        let lines: String = (0..2000)
+            .map(|i| format!("match {} {}\n", i, padding))
+            .collect();
+        fs::write(dir.path().join("many.txt"), &lines).unwrap();
+        fs::write(dir.path().join("long.txt"), "match ".repeat(10_000)).unwrap();
+        let ctx = ToolContext::new().with_workspace(dir.path().to_str().unwrap());
+
+        let result = GrepTool
+            .execute(
+                json!({"pattern": r"match [0-2] ", "path": "many.txt", "max_results": 2}),
+                &ctx,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            result,
+            format!(
+                "many.txt:1:match 0 {0}\nmany.txt:2:match 1 {0}\n\
+                 [Stopped after 2 matches; raise max_results or narrow the search]",
+                padding
+            )
+        );
+
+        let result = GrepTool
+            .execute(json!({"pattern": "match", "max_results": 1000}), &ctx)
+            .await
+            .unwrap();
+        assert!(result.len() <= MAX_OUTPUT_BYTES + 200);
+        assert!(result.ends_with("narrow the pattern or path]"));
+        // Long lines are shortened.
+        let first = result.lines().next().unwrap();
+        assert!(first.starts_with("long.txt:1:match match"));
+        assert!(first.ends_with("..."));
+        assert!(first.len() < MAX_LINE_CHARS + 20);
+    }
+
+    #[tokio::test]
+    async fn test_grep_refuses_to_escape_workspace() {
+        let (_dir, ctx) = workspace();
+        for path in ["../", "../**/*.rs", "/etc", "/etc/*.conf", "src/../../*"] {
+            let result = GrepTool
+                .execute(json!({"pattern": "root", "path": path}), &ctx)
+                .await;
+            assert!(
+                matches!(result, Err(ZeptoError::SecurityViolation(_))),
+                "{} was not rejected: {:?}",
+                path,
+                result
+            );
+        }
+
+        let result = GrepTool
+            .execute(json!({"pattern": "x"}), &ToolContext::new())
+            .await;
+        assert!(matches!(result, Err(ZeptoError::SecurityViolation(_))));
+    }
+
+    #[test]
+    fn test_glob_to_regex() {
+        let re = glob_to_regex("src/**/*.rs").unwrap();
+        assert!(re.is_match("src/main.rs"));
+        assert!(re.is_match("src/a/b/c.rs"));
+        assert!(!re.is_match("tests/main.rs"));
+        assert!(!re.is_match("src/main.rsx"));
+
+        let re = glob_to_regex("file?.[ch]").unwrap();
+        assert!(re.is_match("file1.c"));
+        assert!(!re.is_match("file12.c"));
+        assert!(!re.is_match("file1.rs"));
+    }
+}
@@ -0,0 +1,546 @@
+//! gRPC binary plugin transport for ZeptoClaw.
+//!
+//! Binary plugins with `"protocol": "grpc"` are spawned once per plugin and
+//! serve the `zeptoclaw.plugin.v1.Plugin` service (see `proto/plugin.proto`)
+//! on the unix socket named in their manifest. The socket's absolute path is
+//! passed to the binary in `ZEPTOCLAW_PLUGIN_SOCKET`. All tools of a plugin
+//! share one [`GrpcPluginProcess`]; it is restarted on the next call if the
+//! binary exits or the connection becomes unavailable, and killed when the
+//! last tool holding it is dropped.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use hyper_util::rt::TokioIo;
+use serde_json::Value;
+use tokio::net::UnixStream;
+use tokio::process::{Child, Command};
+use tokio::sync::Mutex;
+use tonic::codegen::http::uri::PathAndQuery;
+use tonic::transport::{Channel, Endpoint, Uri};
+use tonic_prost::ProstCodec;
+use tracing::{info, warn};
+
+use crate::error::{Result, ZeptoError};
+use crate::plugins::types::PluginToolDef;
+use crate::tools::types::{Tool, ToolContext, ToolSource};
+
+/// Path of the `Execute` RPC.
+const EXECUTE_PATH: &str = "/zeptoclaw.plugin.v1.Plugin/Execute";
+
+/// Environment variable carrying the socket path to the plugin binary.
+pub const PLUGIN_SOCKET_ENV: &str = "ZEPTOCLAW_PLUGIN_SOCKET";
+
+/// How long a freshly spawned plugin has to start serving on its socket.
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Interval between socket connection attempts during startup.
+const STARTUP_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+// ---- Protocol messages (mirror proto/plugin.proto) ----
+
+/// `zeptoclaw.plugin.v1.ExecuteRequest`
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ExecuteRequest {
+    /// Tool name from plugin.json.
+    #[prost(string, tag = "1")]
+    pub tool_name: String,
+    /// Tool arguments as a JSON object.
+    #[prost(string, tag = "2")]
+    pub args_json: String,
+}
+
+/// `zeptoclaw.plugin.v1.ExecuteResponse`
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ExecuteResponse {
+    /// Tool result as JSON.
+    #[prost(string, tag = "1")]
+    pub result_json: String,
+}
+
+// ---- GrpcPluginProcess ----
+
+struct RunningPlugin {
+    /// Held so the process is killed when the connection is dropped.
+    child: Child,
+    channel: Channel,
+}
+
+/// A long-lived gRPC plugin binary and its connection.
+pub struct GrpcPluginProcess {
+    plugin_name: String,
+    binary_path: PathBuf,
+    socket_path: PathBuf,
+    env: HashMap<String, String>,
+    running: Mutex<Option<RunningPlugin>>,
+}
+
+impl GrpcPluginProcess {
+    /// Create a handle for a plugin binary serving on `socket_path`.
+    ///
+    /// The binary is not started until the first tool call.
+    ///
+    /// # Arguments
+    /// * `plugin_name` - Name of the plugin
+    /// * `binary_path` - Absolute, validated path to the binary
+    /// * `socket_path` - Absolute path of the unix socket the binary serves on
+    pub fn new(plugin_name: impl Into<String>, binary_path: PathBuf, socket_path: PathBuf) -> Self {
+        Self {
+            plugin_name: plugin_name.into(),
+            binary_path,
+            socket_path,
+            env: HashMap::new(),
+            running: Mutex::new(None),
+        }
+    }
+
+    /// Set extra environment variables for the plugin process.
+    pub fn with_env(mut self, env: HashMap<String, String>) -> Self {
+        self.env = env;
+        self
+    }
+
+    /// A connected channel, starting or restarting the binary if needed.
+    async fn channel(&self) -> Result<Channel> {
+        let mut running = self.running.lock().await;
+        if let Some(plugin) = running.as_mut() {
+            match plugin.child.try_wait() {
+                Ok(None) => return Ok(plugin.channel.clone()),
+                Ok(Some(status)) => warn!(
+                    plugin = %self.plugin_name,
+                    status = %status,
+                    "gRPC plugin exited; restarting"
+                ),
+                Err(e) => warn!(
+                    plugin = %self.plugin_name,
+                    error = %e,
+                    "Failed to poll gRPC plugin; restarting"
+                ),
+            }
+        }
+        *running = None;
+
+        let plugin = self.start().await?;
+        let channel = plugin.channel.clone();
+        *running = Some(plugin);
+        Ok(channel)
+    }
+
+    /// Drop the connection and kill the process so the next call restarts it.
+    async fn reset(&self) {
+        *self.running.lock().await = None;
+    }
+
+    async fn start(&self) -> Result<RunningPlugin> {
+        // A socket left behind by an earlier run would make the bind fail.
+        if self.socket_path.exists() {
+            let _ = std::fs::remove_file(&self.socket_path);
+        }
+        if let Some(parent) = self.socket_path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                ZeptoError::Tool(format!(
+                    "Failed to create socket directory for gRPC plugin '{}': {}",
+                    self.plugin_name, e
+                ))
+            })?;
+        }
+
+        let mut cmd = Command::new(&self.binary_path);
+        cmd.env(PLUGIN_SOCKET_ENV, &self.socket_path)
+            .envs(&self.env)
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::null())
+            .kill_on_drop(true);
+        if let Some(dir) = self.binary_path.parent() {
+            cmd.current_dir(dir);
+        }
+
+        let mut child = cmd.spawn().map_err(|e| {
+            ZeptoError::Tool(format!(
+                "Failed to spawn gRPC plugin '{}' ({}): {}",
+                self.plugin_name,
+                self.binary_path.display(),
+                e
+            ))
+        })?;
+
+        let deadline = Instant::now() + STARTUP_TIMEOUT;
+        loop {
+            if let Ok(Some(status)) = child.try_wait() {
+                return Err(ZeptoError::Tool(format!(
+                    "gRPC plugin '{}' exited with {} before serving on {}",
+                    self.plugin_name,
+                    status,
+                    self.socket_path.display()
+                )));
+            }
+            if self.socket_path.exists() {
+                if let Ok(channel) = connect(&self.socket_path).await {
+                    info!(
+                        plugin = %self.plugin_name,
+                        socket = %self.socket_path.display(),
+                        "Connected to gRPC plugin"
+                    );
+                    return Ok(RunningPlugin { child, channel });
+                }
+            }
+            if Instant::now() >= deadline {
+                return Err(ZeptoError::Tool(format!(
+                    "gRPC plugin '{}' did not serve on {} within {}s",
+                    self.plugin_name,
+                    self.socket_path.display(),
+                    STARTUP_TIMEOUT.as_secs()
+                )));
+            }
+            tokio::time::sleep(STARTUP_POLL_INTERVAL).await;
+        }
+    }
+}
+
+impl std::fmt::Debug for GrpcPluginProcess {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GrpcPluginProcess")
+            .field("plugin", &self.plugin_name)
+            .field("binary", &self.binary_path)
+            .field("socket", &self.socket_path)
+            .finish()
+    }
+}
+
+/// Open an HTTP/2 channel over the unix socket at `socket_path`.
+async fn connect(socket_path: &Path) -> std::result::Result<Channel, tonic::transport::Error> {
+    let path = socket_path.to_path_buf();
+    // The URI is required by the HTTP/2 client but ignored by the connector.
+    Endpoint::from_static("http://plugin.local")
+        .connect_with_connector(tower::service_fn(move |_: Uri| {
+            let path = path.clone();
+            async move { UnixStream::connect(path).await.map(TokioIo::new) }
+        }))
+        .await
+}
+
+// ---- GrpcPluginTool ----
+
+/// A tool adapter that calls a gRPC binary plugin's `Execute` RPC.
+pub struct GrpcPluginTool {
+    def: PluginToolDef,
+    plugin_name: String,
+    process: Arc<GrpcPluginProcess>,
+    timeout: Duration,
+}
+
+impl GrpcPluginTool {
+    /// Create a new gRPC plugin tool.
+    ///
+    /// # Arguments
+    /// * `def` - The tool definition from the plugin manifest
+    /// * `plugin_name` - Name of the parent plugin
+    /// * `process` - The plugin process, shared by all tools of the plugin
+    /// * `timeout_secs` - Per-call timeout in seconds
+    pub fn new(
+        def: PluginToolDef,
+        plugin_name: impl Into<String>,
+        process: Arc<GrpcPluginProcess>,
+        timeout_secs: u64,
+    ) -> Self {
+        Self {
+            def,
+            plugin_name: plugin_name.into(),
+            process,
+            timeout: Duration::from_secs(timeout_secs),
+        }
+    }
+
+    /// Convert `result_json` into the text returned to the agent.
+    fn decode_result(&self, result_json: &str) -> Result<String> {
+        if result_json.trim().is_empty() {
+            return Ok(String::new());
+        }
+        match serde_json::from_str::<Value>(result_json) {
+            Ok(Value::String(text)) => Ok(text),
+            Ok(other) => Ok(other.to_string()),
+            Err(e) => Err(ZeptoError::Tool(format!(
+                "gRPC plugin '{}' returned invalid result_json: {} (raw: {})",
+                self.plugin_name,
+                e,
+                &result_json[..result_json.len().min(200)]
+            ))),
+        }
+    }
+}
+
+impl std::fmt::Debug for GrpcPluginTool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GrpcPluginTool")
+            .field("name", &self.def.name)
+            .field("plugin", &self.plugin_name)
+            .field("process", &self.process)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl Tool for GrpcPluginTool {
+    fn name(&self) -> &str {
+        &self.def.name
+    }
+
+    fn description(&self) -> &str {
+        &self.def.description
+    }
+
+    fn compact_description(&self) -> &str {
+        self.description()
+    }
+
+    fn source(&self) -> ToolSource {
+        ToolSource::Plugin
+    }
+
+    fn parameters(&self) -> Value {
+        self.def.parameters.clone()
+    }
+
+    async fn execute(&self, args: Value, _ctx: &ToolContext) -> Result<String> {
+        self.validate_args(&args)?;
+
+        let request = ExecuteRequest {
+            tool_name: self.def.name.clone(),
+            args_json: args.to_string(),
+        };
+
+        let channel = self.process.channel().await?;
+        let mut client = tonic::client::Grpc::new(channel);
+        let call = async {
+            client
+                .ready()
+                .await
+                .map_err(|e| tonic::Status::unavailable(e.to_string()))?;
+            client
+                .unary(
+                    tonic::Request::new(request),
+                    PathAndQuery::from_static(EXECUTE_PATH),
+                    ProstCodec::<ExecuteRequest, ExecuteResponse>::default(),
+                )
+                .await
+        };
+
+        let response = match tokio::time::timeout(self.timeout, call).await {
+            Ok(Ok(response)) => response.into_inner(),
+            Ok(Err(status)) => {
+                if status.code() == tonic::Code::Unavailable {
+                    self.process.reset().await;
+                }
+                warn!(
+                    plugin = %self.plugin_name,
+                    code = ?status.code(),
+                    "gRPC plugin returned error"
+                );
+                return Err(ZeptoError::Tool(format!(
+                    "gRPC plugin '{}' error ({:?}): {}",
+                    self.plugin_name,
+                    status.code(),
+                    status.message()
+                )));
+            }
+            Err(_) => {
+                return Err(ZeptoError::Tool(format!(
+                    "gRPC plugin '{}' timed out after {}s",
+                    self.plugin_name,
+                    self.timeout.as_secs()
+                )));
+            }
+        };
+
+        self.decode_result(&response.result_json)
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::plugins::types::PluginOutputFormat;
+    use serde_json::json;
+    use tokio::net::UnixListener;
+    use tokio_stream::wrappers::UnixListenerStream;
+    use tonic::codegen::{http, BoxFuture, Context, Poll, Service};
+
+    /// In-process stand-in for a plugin's gRPC server.
+    #[derive(Clone)]
+    struct TestPluginServer;
+
+    impl Service<http::Request<tonic::body::Body>> for TestPluginServer {
+        type Response = http::Response<tonic::body::Body>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<std::result::Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, req: http::Request<tonic::body::Body>) -> Self::Future {
+            Box::pin(async move {
+                assert_eq!(req.uri().path(), EXECUTE_PATH);
+                let execute = tower::service_fn(|req: tonic::Request<ExecuteRequest>| async move {
+                    let req = req.into_inner();
+                    let result_json = match req.tool_name.as_str() {
+                        "echo" => req.args_json,
+                        "text" => json!("plain text").to_string(),
+                        _ => return Err(tonic::Status::invalid_argument("bad input")),
+                    };
+                    Ok(tonic::Response::new(ExecuteResponse { result_json }))
+                });
+                let mut grpc = tonic::server::Grpc::new(ProstCodec::<
+                    ExecuteResponse,
+                    ExecuteRequest,
+                >::default());
+                Ok(grpc.unary(execute, req).await)
+            })
+        }
+    }
+
+    impl tonic::server::NamedService for TestPluginServer {
+        const NAME: &'static str = "zeptoclaw.plugin.v1.Plugin";
+    }
+
+    /// Serve the test plugin on `socket` once it is safe to bind.
+    fn serve_after_delay(socket: PathBuf) {
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            let listener = UnixListener::bind(&socket).unwrap();
+            tonic::transport::Server::builder()
+                .serve_with_incoming(TestPluginServer, UnixListenerStream::new(listener))
+                .await
+                .unwrap();
+        });
+    }
+
+    fn create_test_script(dir: &Path, content: &str) -> PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+
+        let script_path = dir.join("plugin.sh");
+        std::fs::write(&script_path, format!("#!/bin/sh\n{}", content)).unwrap();
+        std::fs::set_permissions(&script_path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        script_path
+    }
+
+    fn tool_def(name: &str) -> PluginToolDef {
+        PluginToolDef {
+            name: name.to_string(),
+            description: "test tool".to_string(),
+            parameters: json!({"type": "object", "properties": {"q": {"type": "string"}}}),
+            command: String::new(),
+            working_dir: None,
+            timeout_secs: None,
+            env: None,
+            output_format: PluginOutputFormat::Text,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_round_trip_shares_process() {
+        let dir = tempfile::TempDir::new().unwrap();
+        // Stands in for the plugin binary: records its environment and stays up.
+        let script = create_test_script(
+            dir.path(),
+            "echo \"$ZEPTOCLAW_PLUGIN_SOCKET\" > socket_env.txt\nexec sleep 30",
+        );
+        let socket = dir.path().join("run").join("plugin.sock");
+        let process = Arc::new(GrpcPluginProcess::new(
+            "test-plugin",
+            script,
+            socket.clone(),
+        ));
+        serve_after_delay(socket.clone());
+
+        let echo = GrpcPluginTool::new(tool_def("echo"), "test-plugin", process.clone(), 10);
+        let result = echo
+            .execute(json!({"q": "hello"}), &ToolContext::new())
+            .await
+            .unwrap();
+        assert_eq!(result, r#"{"q":"hello"}"#);
+
+        let text = GrpcPluginTool::new(tool_def("text"), "test-plugin", process, 10);
+        let result = text.execute(json!({}), &ToolContext::new()).await.unwrap();
+        assert_eq!(result, "plain text");
+
+        let env = std::fs::read_to_string(dir.path().join("socket_env.txt")).unwrap();
+        assert_eq!(env.trim(), socket.display().to_string());
+    }
+
+    #[tokio::test]
+    async fn test_status_error_becomes_tool_error() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let script = create_test_script(dir.path(), "exec sleep 30");
+        let socket = dir.path().join("plugin.sock");
+        let process = Arc::new(GrpcPluginProcess::new(
+            "test-plugin",
+            script,
+            socket.clone(),
+        ));
+        serve_after_delay(socket);
+
+        let tool = GrpcPluginTool::new(tool_def("broken"), "test-plugin", process, 10);
+        let err = tool
+            .execute(json!({}), &ToolContext::new())
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("InvalidArgument"), "err was: {}", err);
+        assert!(err.contains("bad input"), "err was: {}", err);
+    }
+
+    #[tokio::test]
+    async fn test_binary_exiting_before_serving_is_an_error() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let script = create_test_script(dir.path(), "exit 3");
+        let process = Arc::new(GrpcPluginProcess::new(
+            "test-plugin",
+            script,
+            dir.path().join("plugin.sock"),
+        ));
+
+        let tool = GrpcPluginTool::new(tool_def("echo"), "test-plugin", process, 10);
+        let err = tool
+            .execute(json!({}), &ToolContext::new())
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("before serving"), "err was: {}", err);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_args_rejected_before_spawning() {
+        let process = Arc::new(GrpcPluginProcess::new(
+            "test-plugin",
+            PathBuf::from("/nonexistent/plugin"),
+            PathBuf::from("/nonexistent/plugin.sock"),
+        ));
+        let tool = GrpcPluginTool::new(tool_def("echo"), "test-plugin", process, 10);
+        let err = tool
+            .execute(json!({"q": 42}), &ToolContext::new())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Invalid arguments"));
+    }
+
+    #[test]
+    fn test_decode_result() {
+        let process = Arc::new(GrpcPluginProcess::new(
+            "p",
+            PathBuf::from("/bin/true"),
+            PathBuf::from("/tmp/p.sock"),
+        ));
+        let tool = GrpcPluginTool::new(tool_def("echo"), "p", process, 10);
+        assert_eq!(tool.decode_result(r#""hi""#).unwrap(), "hi");
+        assert_eq!(tool.decode_result(r#"{"a": 1}"#).unwrap(), r#"{"a":1}"#);
+        assert_eq!(tool.decode_result("  ").unwrap(), "");
+        assert!(tool.decode_result("not json").is_err());
+    }
+}
@@ -0,0 +1,561 @@
+//! Generic HTTP request tool for API integrations.
+//!
+//! Unlike `web_fetch`, which only GETs pages and extracts text, this tool
+//! sends arbitrary methods, headers and bodies and returns the raw response.
+//! Every URL, including each redirect hop, goes through the shared
+//! `tools.url_policy` (see [`url_policy`](crate::utils::url_policy)),
+//! narrowed by the host allowlist/denylist from `tools.http`. Redirects are
+//! followed by hand so each hop is also resolved and pinned like the first
+//! URL.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE};
+use reqwest::{Client, Method, Response, StatusCode, Url};
+use serde_json::{json, Value};
+
+use crate::config::HttpToolConfig;
+use crate::error::{Result, ZeptoError};
+
+use crate::utils::url_policy::{validate_url, UrlPolicy};
+
+use super::web::{
+    read_body_limited, redirect_target, resolve_and_check_host, MAX_REDIRECTS, WEB_USER_AGENT,
+};
+use super::{Tool, ToolContext};
+
+/// Request headers the caller may not set.
+const RESERVED_HEADERS: &[&str] = &["host", "content-length", "transfer-encoding", "connection"];
+
+/// Request headers dropped when a redirect leaves the original origin.
+const CREDENTIAL_HEADERS: &[&str] = &["authorization", "cookie", "proxy-authorization"];
+
+/// Tool for sending HTTP requests with full method, header and body control.
+///
+/// # Parameters
+/// - `url`: http/https URL (required)
+/// - `method`: GET, POST, PUT, DELETE or PATCH (optional, default: GET)
+/// - `headers`: Object of request headers (optional)
+/// - `json`: JSON request body; sets `Content-Type: application/json` (optional)
+/// - `body`: Raw string request body (optional, exclusive with `json`)
+/// - `timeout_secs`: Request timeout, capped at `tools.http.timeout_secs` (optional)
+///
+/// Returns a JSON object with `status`, `headers`, `body`, `truncated` and
+/// `final_url`. Bodies over `tools.http.max_response_bytes` are truncated.
+pub struct HttpTool {
+    /// Shared URL policy narrowed by the `tools.http` host lists.
+    url_policy: UrlPolicy,
+    allowed_hosts: Vec<String>,
+    denied_hosts: Vec<String>,
+    timeout: Duration,
+    max_response_bytes: usize,
+}
+
+impl HttpTool {
+    /// Create an HTTP tool from `tools.http` configuration.
+    pub fn new(config: &HttpToolConfig) -> Self {
+        Self {
+            url_policy: UrlPolicy::default()
+                .with_host_rules(&config.allowed_hosts, &config.denied_hosts),
+            allowed_hosts: config.allowed_hosts.clone(),
+            denied_hosts: config.denied_hosts.clone(),
+            timeout: Duration::from_secs(config.timeout_secs.max(1)),
+            max_response_bytes: config.max_response_bytes.max(1),
+        }
+    }
+
+    /// Apply `tools.url_policy`, narrowed by the `tools.http` host lists.
+    pub fn with_url_policy(mut self, url_policy: UrlPolicy) -> Self {
+        self.url_policy = url_policy.with_host_rules(&self.allowed_hosts, &self.denied_hosts);
+        self
+    }
+
+    /// Client for a single hop: redirects are not followed (see
+    /// [`send`](Self::send)) and, when given, `host` is pinned to an
+    /// already-validated address. A builder failure is an error rather than
+    /// a fallback to a default client, which would follow redirects itself.
+    fn client(
+        &self,
+        timeout: Duration,
+        pinned: Option<(String, std::net::SocketAddr)>,
+    ) -> Result<Client> {
+        let mut builder = Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .timeout(timeout);
+        if let Some((host, addr)) = pinned {
+            builder = builder.resolve(&host, addr);
+        }
+        builder
+            .build()
+            .map_err(|e| ZeptoError::Tool(format!("Failed to build HTTP client: {}", e)))
+    }
+
+    /// Send the request for `url`, following up to [`MAX_REDIRECTS`]
+    /// redirects. `url` must already have passed the URL policy; every
+    /// redirect target is checked against it here. Each hop resolves its
+    /// host, rejects private addresses, and pins the connection to the
+    /// checked address, so neither a redirect nor DNS rebinding can reach a
+    /// private network.
+    async fn send(
+        &self,
+        mut url: Url,
+        args: &Value,
+        timeout: Duration,
+        ctx: &ToolContext,
+    ) -> Result<Response> {
+        let mut args = args.clone();
+        for _ in 0..=MAX_REDIRECTS {
+            let pinned = resolve_and_check_host(&url).await?;
+            let client = self.client(timeout, pinned)?;
+            let request = self.build_request(&client, url.clone(), &args)?;
+            let response = tokio::select! {
+                res = client.execute(request) => res.map_err(|e| {
+                    ZeptoError::Tool(format!("HTTP request failed: {}", e))
+                })?,
+                _ = ctx.cancelled() => {
+                    return Err(ZeptoError::Tool("HTTP request cancelled".to_string()));
+                }
+            };
+            let Some(next) = redirect_target(&url, &response)? else {
+                return Ok(response);
+            };
+            self.url_policy.check(&next)?;
+            args = redirect_args(args, response.status(), &url, &next);
+            url = next;
+        }
+        Err(ZeptoError::Tool(format!(
+            "HTTP request failed: more than {} redirects",
+            MAX_REDIRECTS
+        )))
+    }
+
+    /// Validate `args` and build the request, without sending it.
+    fn build_request(&self, client: &Client, url: Url, args: &Value) -> Result<reqwest::Request> {
+        let method = args
+            .get("method")
+            .and_then(|v| v.as_str())
+            .unwrap_or("GET")
+            .to_ascii_uppercase();
+        let method = match method.as_str() {
+            "GET" => Method::GET,
+            "POST" => Method::POST,
+            "PUT" => Method::PUT,
+            "DELETE" => Method::DELETE,
+            "PATCH" => Method::PATCH,
+            other => {
+                return Err(ZeptoError::Tool(format!(
+                    "Unsupported method '{}': use GET, POST, PUT, DELETE or PATCH",
+                    other
+                )))
+            }
+        };
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            reqwest::header::USER_AGENT,
+            HeaderValue::from_static(WEB_USER_AGENT),
+        );
+        match args.get("headers") {
+            None | Some(Value::Null) => {}
+            Some(Value::Object(map)) => {
+                for (name, value) in map {
+                    if RESERVED_HEADERS.contains(&name.to_ascii_lowercase().as_str()) {
+                        return Err(ZeptoError::Tool(format!("Header '{}' cannot be set", name)));
+                    }
+                    let value = match value {
+                        Value::String(s) => s.clone(),
+                        other => other.to_string(),
+                    };
+                    let name = HeaderName::from_bytes(name.as_bytes())
+                        .map_err(|_| ZeptoError::Tool(format!("Invalid header name '{}'", name)))?;
+                    let value = HeaderValue::from_str(&value).map_err(|_| {
+                        ZeptoError::Tool(format!("Invalid value for header '{}'", name))
+                    })?;
+                    headers.insert(name, value);
+                }
+            }
+            Some(_) => {
+                return Err(ZeptoError::Tool(
+                    "'headers' must be an object of header names to values".to_string(),
+                ))
+            }
+        }
+
+        let mut request = client.request(method, url);
+        match (args.get("json"), args.get("body")) {
+            (Some(json), Some(_)) if !json.is_null() => {
+                return Err(ZeptoError::Tool(
+                    "Pass either 'json' or 'body', not both".to_string(),
+                ))
+            }
+            (Some(json), _) if !json.is_null() => {
+                if !headers.contains_key(CONTENT_TYPE) {
+                    headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+                }
+                request = request.body(json.to_string());
+            }
+            (_, Some(Value::String(body))) => request = request.body(body.clone()),
+            (_, Some(Value::Null)) | (_, None) => {}
+            (_, Some(_)) => {
+                return Err(ZeptoError::Tool(
+                    "'body' must be a string; use 'json' for structured data".to_string(),
+                ))
+            }
+        }
+
+        request
+            .headers(headers)
+            .build()
+            .map_err(|e| ZeptoError::Tool(format!("Invalid request: {}", e)))
+    }
+}
+
+#[async_trait]
+impl Tool for HttpTool {
+    fn name(&self) -> &str {
+        "http_request"
+    }
+
+    fn description(&self) -> &str {
+        "Send an HTTP request (GET, POST, PUT, DELETE, PATCH) with custom headers and a JSON \
+         or raw body. Returns status, response headers and body."
+    }
+
+    fn compact_description(&self) -> &str {
+        "HTTP request"
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "url": {
+                    "type": "string",
+                    "description": "http/https URL to request"
+                },
+                "method": {
+                    "type": "string",
+                    "enum": ["GET", "POST", "PUT", "DELETE", "PATCH"],
+                    "description": "HTTP method (default: GET)"
+                },
+                "headers": {
+                    "type": "object",
+                    "description": "Request headers, e.g. {\"Authorization\": \"Bearer ...\"}",
+                    "additionalProperties": { "type": "string" }
+                },
+                "json": {
+                    "description": "JSON request body; sets Content-Type: application/json"
+                },
+                "body": {
+                    "type": "string",
+                    "description": "Raw request body (use instead of 'json')"
+                },
+                "timeout_secs": {
+                    "type": "integer",
+                    "description": "Request timeout in seconds",
+                    "minimum": 1
+                }
+            },
+            "required": ["url"]
+        })
+    }
+
+    async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<String> {
+        let url = args
+            .get("url")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .ok_or_else(|| ZeptoError::Tool("Missing 'url' parameter".to_string()))?;
+        let parsed = validate_url(url, &self.url_policy)?;
+
+        let timeout = args
+            .get("timeout_secs")
+            .and_then(|v| v.as_u64())
+            .map(|secs| Duration::from_secs(secs.max(1)).min(self.timeout))
+            .unwrap_or(self.timeout);
+
+        let response = self.send(parsed, &args, timeout, ctx).await?;
+
+        let status = response.status();
+        let final_url = response.url().to_string();
+        let headers: BTreeMap<String, String> = response
+            .headers()
+            .iter()
+            .map(|(name, value)| {
+                (
+                    name.to_string(),
+                    String::from_utf8_lossy(value.as_bytes()).into_owned(),
+                )
+            })
+            .collect();
+
+        // Read one byte past the cap to know whether the body was cut.
+        let mut body = tokio::select! {
+            res = read_body_limited(response, self.max_response_bytes + 1) => res?,
+            _ = ctx.cancelled() => {
+                return Err(ZeptoError::Tool("HTTP request cancelled".to_string()));
+            }
+        };
+        let truncated = body.len() > self.max_response_bytes;
+        if truncated {
+            let mut end = self.max_response_bytes;
+            while !body.is_char_boundary(end) {
+                end -= 1;
+            }
+            body.truncate(end);
+        }
+
+        Ok(json!({
+            "status": status.as_u16(),
+            "final_url": final_url,
+            "headers": headers,
+            "truncated": truncated,
+            "body": body,
+        })
+        .to_string())
+    }
+}
+
+/// Arguments for the request that follows a `status` redirect from `from` to
+/// `to`, matching what clients usually do: 301/302/303 turn a request with a
+/// body into a bodiless GET, and credentials are not sent to another origin.
+fn redirect_args(mut args: Value, status: StatusCode, from: &Url, to: &Url) -> Value {
+    let Some(map) = args.as_object_mut() else {
+        return args;
+    };
+    let method = map
+        .get("method")
+        .and_then(|v| v.as_str())
+        .unwrap_or("GET")
+        .to_ascii_uppercase();
+    let mut dropped: Vec<&str> = Vec::new();
+    if matches!(status.as_u16(), 301..=303) && method != "GET" {
+        map.insert("method".to_string(), json!("GET"));
+        map.remove("json");
+        map.remove("body");
+        dropped.push("content-type");
+    }
+    if from.origin() != to.origin() {
+        dropped.extend_from_slice(CREDENTIAL_HEADERS);
+    }
+    if let Some(Value::Object(headers)) = map.get_mut("headers") {
+        headers.retain(|name, _| !dropped.contains(&name.to_ascii_lowercase().as_str()));
+    }
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::UrlPolicyConfig;
+
+    fn tool() -> HttpTool {
+        HttpTool::new(&HttpToolConfig::default())
+    }
+
+    fn check(tool: &HttpTool, url: &str) -> Result<()> {
+        tool.url_policy.check(&Url::parse(url).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_ssrf_guard_rejects_link_local_and_metadata() {
+        let tool = tool();
+        for url in [
+            "http://169.254.169.254/latest/meta-data/",
+            "http://169.254.10.1/",
+            "http://[fe80::1]/",
+            "http://localhost:8080/admin",
+            "http://127.0.0.1/",
+            "http://10.0.0.5/",
+            "http://metadata.google.internal/computeMetadata/v1/",
+        ] {
+            let result = tool.execute(json!({"url": url}), &ToolContext::new()).await;
+            assert!(
+                matches!(result, Err(ZeptoError::SecurityViolation(_))),
+                "{} was not blocked: {:?}",
+                url,
+                result
+            );
+        }
+
+        let err = tool
+            .execute(json!({"url": "file:///etc/passwd"}), &ToolContext::new())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Only http/https"));
+    }
+
+    /// This machine's hostname, if it resolves to a private or loopback
+    /// address (as it usually does through /etc/hosts).
+    #[cfg(unix)]
+    async fn privately_resolving_hostname() -> Option<String> {
+        let mut buf = [0u8; 256];
+        // SAFETY: the buffer is valid for its full length.
+        if unsafe { libc::gethostname(buf.as_mut_ptr().cast(), buf.len()) } != 0 {
+            return None;
+        }
+        let end = buf.iter().position(|&b| b == 0)?;
+        let name = String::from_utf8(buf[..end].to_vec()).ok()?;
+        let private = tokio::net::lookup_host((name.as_str(), 80))
+            .await
+            .ok()?
+            .any(|addr| crate::utils::url_policy::is_private_or_local_ip(addr.ip()));
+        private.then_some(name)
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_redirect_to_privately_resolving_name_is_blocked() {
+        use crate::providers::mock_http::{serve, MockResponse};
+
+        // Only meaningful where the hostname resolves to a private address.
+        let Some(hostname) = privately_resolving_hostname().await else {
+            return;
+        };
+        // The name passes the by-name URL policy; only the per-hop DNS
+        // check can catch it.
+        let target = format!("http://{}:8080/secret", hostname);
+        assert!(check(&tool(), &target).is_ok());
+
+        let (base, requests) = serve(vec![
+            MockResponse::json(302, "").with_header("Location", target.clone())
+        ])
+        .await;
+        let err = tool()
+            .send(
+                Url::parse(&format!("{}/start", base)).unwrap(),
+                &json!({}),
+                Duration::from_secs(5),
+                &ToolContext::new(),
+            )
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, ZeptoError::SecurityViolation(ref msg) if msg.contains("private")),
+            "{:?}",
+            err
+        );
+        assert_eq!(requests.await.unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_redirect_args_downgrade_method_and_drop_credentials() {
+        let from = Url::parse("https://api.example.com/a").unwrap();
+        let same = Url::parse("https://api.example.com/b").unwrap();
+        let other = Url::parse("https://cdn.example.net/b").unwrap();
+        let args = json!({
+            "method": "POST",
+            "headers": {"Authorization": "Bearer abc", "Content-Type": "text/plain", "X-Id": "1"},
+            "body": "payload"
+        });
+
+        let kept = redirect_args(args.clone(), StatusCode::TEMPORARY_REDIRECT, &from, &same);
+        assert_eq!(kept, args);
+
+        let moved = redirect_args(args, StatusCode::SEE_OTHER, &from, &other);
+        assert_eq!(moved["method"], "GET");
+        assert!(moved.get("body").is_none());
+        assert_eq!(moved["headers"], json!({"X-Id": "1"}));
+    }
+
+    #[test]
+    fn test_host_allowlist_and_denylist() {
+        let tool = HttpTool::new(&HttpToolConfig {
+            allowed_hosts: vec!["example.com".to_string(), "*.github.com".to_string()],
+            denied_hosts: vec!["private.example.com".to_string()],
+            ..Default::default()
+        });
+        assert!(check(&tool, "https://example.com/v1").is_ok());
+        assert!(check(&tool, "https://api.example.com/v1").is_ok());
+        assert!(check(&tool, "https://api.github.com/repos").is_ok());
+        assert!(check(&tool, "https://notexample.com/").is_err());
+        assert!(check(&tool, "https://other.org/").is_err());
+        assert!(check(&tool, "https://private.example.com/").is_err());
+        assert!(check(&tool, "https://a.private.example.com/").is_err());
+
+        // Empty allowlist permits any public host.
+        assert!(check(&self::tool(), "https://other.org/").is_ok());
+    }
+
+    #[test]
+    fn test_shared_url_policy_applies() {
+        let tool = self::tool().with_url_policy(UrlPolicy::from_config(&UrlPolicyConfig {
+            denied_hosts: vec!["tracker.example".to_string()],
+            denied_ports: vec![8080],
+            ..Default::default()
+        }));
+        let err = check(&tool, "https://tracker.example/").unwrap_err();
+        assert!(err.to_string().contains("denied_hosts"));
+        assert!(check(&tool, "http://example.com:8080/").is_err());
+        assert!(check(&tool, "https://example.com/").is_ok());
+        // The tools.http denylist still applies on top.
+        assert!(check(&tool, "https://metadata.google.internal/").is_err());
+    }
+
+    #[test]
+    fn test_header_and_body_passthrough() {
+        let tool = tool();
+        let client = Client::new();
+        let url = Url::parse("https://api.example.com/items").unwrap();
+
+        let request = tool
+            .build_request(
+                &client,
+                url.clone(),
+                &json!({
+                    "method": "post",
+                    "headers": {"Authorization": "Bearer abc", "X-Request-Id": 42},
+                    "json": {"name": "widget"}
+                }),
+            )
+            .unwrap();
+        assert_eq!(request.method(), Method::POST);
+        assert_eq!(request.headers()["authorization"], "Bearer abc");
+        assert_eq!(request.headers()["x-request-id"], "42");
+        assert_eq!(request.headers()["content-type"], "application/json");
+        assert_eq!(
+            request.body().and_then(|b| b.as_bytes()),
+            Some(br#"{"name":"widget"}"#.as_slice())
+        );
+
+        let request = tool
+            .build_request(
+                &client,
+                url.clone(),
+                &json!({
+                    "method": "PUT",
+                    "headers": {"Content-Type": "text/csv"},
+                    "body": "a,b\n1,2"
+                }),
+            )
+            .unwrap();
+        assert_eq!(request.headers()["content-type"], "text/csv");
+        assert_eq!(
+            request.body().and_then(|b| b.as_bytes()),
+            Some(b"a,b\n1,2".as_slice())
+        );
+    }
+
+    #[test]
+    fn test_build_request_rejects_bad_input() {
+        let tool = tool();
+        let client = Client::new();
+        let url = Url::parse("https://api.example.com/").unwrap();
+        for args in [
+            json!({"method": "TRACE"}),
+            json!({"headers": {"Host": "evil.com"}}),
+            json!({"headers": "Authorization: x"}),
+            json!({"json": {}, "body": "x"}),
+            json!({"body": {"a": 1}}),
+        ] {
+            assert!(
+                tool.build_request(&client, url.clone(), &args).is_err(),
+                "accepted {}",
+                args
+            );
+        }
+    }
+}
@@ -0,0 +1,171 @@
+//! Tool discovery tool.
+//!
+//! Lets the agent answer "what can you do?" from the live tool registry
+//! instead of guessing. Built-in, plugin, and custom tools are all listed,
+//! minus anything the active template blocks.
+
+use std::sync::Weak;
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+use crate::agent::AgentLoop;
+use crate::config::templates::AgentTemplate;
+use crate::error::{Result, ZeptoError};
+
+use super::{Tool, ToolContext};
+
+/// Tool that lists the agent's currently registered tools.
+pub struct ListToolsTool {
+    agent: Weak<AgentLoop>,
+    template: Option<AgentTemplate>,
+}
+
+impl ListToolsTool {
+    /// Create a new list-tools tool for the given agent.
+    pub fn new(agent: Weak<AgentLoop>) -> Self {
+        Self {
+            agent,
+            template: None,
+        }
+    }
+
+    /// Hide tools the template does not permit from the listing.
+    pub fn with_template(mut self, template: Option<AgentTemplate>) -> Self {
+        self.template = template;
+        self
+    }
+}
+
+#[async_trait]
+impl Tool for ListToolsTool {
+    fn name(&self) -> &str {
+        "list_tools"
+    }
+
+    fn description(&self) -> &str {
+        "List the tools currently available to you (name and short description) as JSON. \
+Use this to answer questions about your capabilities."
+    }
+
+    fn compact_description(&self) -> &str {
+        "List available tools"
+    }
+
+    fn is_parallel_safe(&self) -> bool {
+        true
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {},
+            "required": []
+        })
+    }
+
+    async fn execute(&self, _args: Value, _ctx: &ToolContext) -> Result<String> {
+        let agent = self
+            .agent
+            .upgrade()
+            .ok_or_else(|| ZeptoError::Tool("Agent is no longer available".into()))?;
+
+        let mut definitions = agent.tool_definitions(true).await;
+        definitions.retain(|def| {
+            self.template
+                .as_ref()
+                .is_none_or(|tpl| tpl.allows_tool(&def.name))
+        });
+        definitions.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let tools: Vec<Value> = definitions
+            .into_iter()
+            .map(|def| json!({ "name": def.name, "description": def.description }))
+            .collect();
+
+        Ok(json!({ "count": tools.len(), "tools": tools }).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    use crate::bus::MessageBus;
+    use crate::config::templates::TemplateRegistry;
+    use crate::config::Config;
+    use crate::session::SessionManager;
+    use crate::tools::filesystem::ReadFileTool;
+    use crate::tools::shell::ShellTool;
+    use crate::tools::EchoTool;
+
+    async fn make_agent() -> Arc<AgentLoop> {
+        let agent = Arc::new(AgentLoop::new(
+            Config::default(),
+            SessionManager::new_memory(),
+            Arc::new(MessageBus::new()),
+        ));
+        agent.register_tool(Box::new(EchoTool)).await;
+        agent.register_tool(Box::new(ReadFileTool)).await;
+        agent.register_tool(Box::new(ShellTool::new())).await;
+        agent
+    }
+
+    fn listed_names(output: &str) -> Vec<String> {
+        let parsed: Value = serde_json::from_str(output).unwrap();
+        parsed["tools"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|t| t["name"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_list_tools_name_and_schema() {
+        let tool = ListToolsTool::new(Weak::new());
+        assert_eq!(tool.name(), "list_tools");
+        assert_eq!(tool.parameters()["type"], "object");
+        assert!(tool.compact_description().len() < tool.description().len());
+    }
+
+    #[tokio::test]
+    async fn test_list_tools_returns_registered_tools() {
+        let agent = make_agent().await;
+        let tool = ListToolsTool::new(Arc::downgrade(&agent));
+
+        let output = tool.execute(json!({}), &ToolContext::new()).await.unwrap();
+        let parsed: Value = serde_json::from_str(&output).unwrap();
+
+        assert_eq!(parsed["count"], 3);
+        assert_eq!(listed_names(&output), vec!["echo", "read_file", "shell"]);
+        assert_eq!(parsed["tools"][0]["description"], "Echo message");
+    }
+
+    #[tokio::test]
+    async fn test_list_tools_excludes_blocked_tools() {
+        let agent = make_agent().await;
+        let mut template = TemplateRegistry::new().get("coder").unwrap().clone();
+        template.blocked_tools = Some(vec!["shell".to_string()]);
+        let tool = ListToolsTool::new(Arc::downgrade(&agent)).with_template(Some(template));
+
+        let output = tool.execute(json!({}), &ToolContext::new()).await.unwrap();
+        let names = listed_names(&output);
+
+        assert!(!names.contains(&"shell".to_string()));
+        assert!(names.contains(&"echo".to_string()));
+        assert!(names.contains(&"read_file".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_list_tools_agent_dropped() {
+        let tool = ListToolsTool::new(Weak::new());
+        let result = tool.execute(json!({}), &ToolContext::new()).await;
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("no longer available"));
+    }
+}
@@ -4,7 +4,7 @@ use async_trait::async_trait;
 use std::sync::Arc;
 
 use super::client::McpClient;
-use crate::tools::{Tool, ToolContext};
+use crate::tools::{Tool, ToolContext, ToolSource};
 
 /// Wraps a single MCP tool as a ZeptoClaw `Tool` implementation.
 pub struct McpToolWrapper {
@@ -65,6 +65,10 @@ impl Tool for McpToolWrapper {
         self.description()
     }
 
+    fn source(&self) -> ToolSource {
+        ToolSource::Mcp
+    }
+
     fn parameters(&self) -> serde_json::Value {
         self.input_schema.clone()
     }
@@ -160,6 +164,7 @@ mod tests {
         let wrapper = McpToolWrapper::new("server", "tool", "desc", json!({}), client);
         // The Tool trait's name() should return the same as tool_name()
         assert_eq!(Tool::name(&wrapper), wrapper.tool_name());
+        assert_eq!(wrapper.source(), ToolSource::Mcp);
     }
 
     #[test]
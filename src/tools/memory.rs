@@ -3,6 +3,7 @@
 //! Provides:
 //! - `memory_search`: search memory markdown files in the workspace.
 //! - `memory_get`: read a memory file with optional line window.
+//! - `memory_write`: append a dated entry to a memory markdown file.
 
 use std::path::Path;
 use std::sync::Arc;
@@ -14,28 +15,36 @@ use crate::config::{MemoryCitationsMode, MemoryConfig};
 use crate::error::{Result, ZeptoError};
 use crate::memory::builtin_searcher::BuiltinSearcher;
 use crate::memory::traits::MemorySearcher;
-use crate::memory::{read_workspace_memory, search_workspace_memory};
+use crate::memory::{
+    append_workspace_memory, read_workspace_memory, search_workspace_memory, MemoryIndex,
+    MemorySearchOptions,
+};
 
 use super::{Tool, ToolContext};
 
 /// Tool for searching workspace memory files.
+///
+/// Parsed memory files are cached in a [`MemoryIndex`] for the lifetime of
+/// the tool and re-parsed only when they change on disk.
 pub struct MemorySearchTool {
     config: MemoryConfig,
     searcher: Arc<dyn MemorySearcher>,
+    index: Arc<MemoryIndex>,
 }
 
 impl MemorySearchTool {
     /// Create a new memory search tool.
     pub fn new(config: MemoryConfig) -> Self {
-        Self {
-            config,
-            searcher: Arc::new(BuiltinSearcher),
-        }
+        Self::with_searcher(config, Arc::new(BuiltinSearcher))
     }
 
     /// Create a new memory search tool with a custom searcher.
     pub fn with_searcher(config: MemoryConfig, searcher: Arc<dyn MemorySearcher>) -> Self {
-        Self { config, searcher }
+        Self {
+            config,
+            searcher,
+            index: Arc::new(MemoryIndex::new()),
+        }
     }
 }
 
@@ -51,6 +60,18 @@ impl MemoryGetTool {
     }
 }
 
+/// Tool for appending entries to workspace memory files.
+pub struct MemoryWriteTool {
+    config: MemoryConfig,
+}
+
+impl MemoryWriteTool {
+    /// Create a new memory write tool.
+    pub fn new(config: MemoryConfig) -> Self {
+        Self { config }
+    }
+}
+
 #[async_trait]
 impl Tool for MemorySearchTool {
     fn name(&self) -> &str {
@@ -65,6 +86,10 @@ impl Tool for MemorySearchTool {
         "Search memory"
     }
 
+    fn is_parallel_safe(&self) -> bool {
+        true
+    }
+
     fn parameters(&self) -> Value {
         json!({
             "type": "object",
@@ -84,6 +109,11 @@ impl Tool for MemorySearchTool {
                 "include_citations": {
                     "type": "boolean",
                     "description": "Override citation behavior for this call"
+                },
+                "tags": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Only search files whose frontmatter has at least one of these tags"
                 }
             },
             "required": ["query"]
@@ -112,15 +142,29 @@ impl Tool for MemorySearchTool {
             .map(|v| v as f32);
 
         let include_citations = resolve_citations(&args, ctx, &self.config.citations);
+        let tags = args
+            .get("tags")
+            .and_then(Value::as_array)
+            .map(|tags| {
+                tags.iter()
+                    .filter_map(Value::as_str)
+                    .map(str::to_string)
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
 
         let results = search_workspace_memory(
             Path::new(workspace),
             query,
             &self.config,
             self.searcher.clone(),
-            max_results,
-            min_score,
-            include_citations,
+            MemorySearchOptions {
+                max_results,
+                min_score,
+                include_citations,
+                tags,
+                index: Some(self.index.clone()),
+            },
         )
         .await?;
 
@@ -134,13 +178,19 @@ impl Tool for MemorySearchTool {
             query
         );
         for (index, item) in results.iter().enumerate() {
+            let tags = if item.tags.is_empty() {
+                String::new()
+            } else {
+                format!(", tags: {}", item.tags.join(", "))
+            };
             output.push_str(&format!(
-                "{}. {} (score {:.3}, lines {}-{})\n{}\n\n",
+                "{}. {} (score {:.3}, lines {}-{}{})\n{}\n\n",
                 index + 1,
                 item.path,
                 item.score,
                 item.start_line,
                 item.end_line,
+                tags,
                 item.snippet.trim()
             ));
         }
@@ -163,6 +213,10 @@ impl Tool for MemoryGetTool {
         "Read memory"
     }
 
+    fn is_parallel_safe(&self) -> bool {
+        true
+    }
+
     fn parameters(&self) -> Value {
         json!({
             "type": "object",
@@ -219,6 +273,65 @@ impl Tool for MemoryGetTool {
     }
 }
 
+#[async_trait]
+impl Tool for MemoryWriteTool {
+    fn name(&self) -> &str {
+        "memory_write"
+    }
+
+    fn description(&self) -> &str {
+        "Append a dated note to a workspace memory markdown file (e.g. MEMORY.md or memory/*.md) so it persists between sessions."
+    }
+
+    fn compact_description(&self) -> &str {
+        "Write memory"
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Workspace-relative markdown file path (default: MEMORY.md)"
+                },
+                "content": {
+                    "type": "string",
+                    "description": "Note to append"
+                }
+            },
+            "required": ["content"]
+        })
+    }
+
+    async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<String> {
+        let content = args
+            .get("content")
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .ok_or_else(|| ZeptoError::Tool("Missing 'content' parameter".to_string()))?;
+        let path = args
+            .get("path")
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .unwrap_or("MEMORY.md");
+
+        let workspace = ctx.workspace.as_deref().ok_or_else(|| {
+            ZeptoError::Tool("Memory tools require a workspace context".to_string())
+        })?;
+
+        let result =
+            append_workspace_memory(Path::new(workspace), path, content, &self.config).await?;
+
+        Ok(format!(
+            "Memory written: {}\nLines: {}-{} of {}",
+            result.path, result.start_line, result.end_line, result.total_lines
+        ))
+    }
+}
+
 fn resolve_citations(args: &Value, ctx: &ToolContext, mode: &MemoryCitationsMode) -> bool {
     if let Some(explicit) = args.get("include_citations").and_then(Value::as_bool) {
         return explicit;
@@ -299,6 +412,62 @@ mod tests {
         assert!(result.contains("Lines: 2-3"));
     }
 
+    #[tokio::test]
+    async fn test_memory_write_tool_defaults_to_memory_md() {
+        let dir = tempdir().unwrap();
+
+        let tool = MemoryWriteTool::new(MemoryConfig::default());
+        assert_eq!(tool.name(), "memory_write");
+        let ctx = ToolContext::new().with_workspace(dir.path().to_str().unwrap());
+        let result = tool
+            .execute(json!({"content": "Deploys happen on Fridays"}), &ctx)
+            .await
+            .unwrap();
+
+        assert!(result.contains("Memory written: MEMORY.md"));
+        assert!(result.contains("Lines: 1-3"));
+        let written = fs::read_to_string(dir.path().join("MEMORY.md")).unwrap();
+        assert!(written.contains("Deploys happen on Fridays"));
+    }
+
+    #[tokio::test]
+    async fn test_memory_write_tool_requires_content() {
+        let dir = tempdir().unwrap();
+        let tool = MemoryWriteTool::new(MemoryConfig::default());
+        let ctx = ToolContext::new().with_workspace(dir.path().to_str().unwrap());
+        assert!(tool
+            .execute(json!({"path": "memory/notes.md"}), &ctx)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_memory_search_tool_filters_by_tags() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("memory")).unwrap();
+        fs::write(
+            dir.path().join("memory/work.md"),
+            "---\ntags: [work, Projects]\n---\nStandup moved to 10am\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("memory/home.md"),
+            "---\ntags: [personal]\n---\nDentist standup-desk delivery\n",
+        )
+        .unwrap();
+
+        let tool = MemorySearchTool::new(MemoryConfig::default());
+        let ctx = ToolContext::new().with_workspace(dir.path().to_str().unwrap());
+        let result = tool
+            .execute(json!({"query": "standup", "tags": ["work"]}), &ctx)
+            .await
+            .unwrap();
+
+        assert!(result.contains("memory/work.md"));
+        assert!(result.contains("tags: work, projects"));
+        assert!(!result.contains("memory/home.md"));
+    }
+
     #[tokio::test]
     async fn test_memory_search_requires_query() {
         let tool = MemorySearchTool::new(MemoryConfig::default());
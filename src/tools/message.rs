@@ -5,6 +5,10 @@
 //! - `react`: Add emoji reaction (Discord only)
 //! - `rich_message`: Send Slack Block Kit message (Slack only)
 //! - `inline_keyboard`: Send inline keyboard buttons (Telegram only)
+//!
+//! Because the agent can reach any chat on an active channel without being
+//! asked, deployments that want a human in the loop should list `message`
+//! in `approval.dangerous_tools`.
 
 use std::sync::Arc;
 
@@ -33,12 +37,24 @@ const ALLOWED_CHANNELS: &[&str] = &[
 /// keyboards (Telegram).
 pub struct MessageTool {
     bus: Arc<MessageBus>,
+    /// Channels currently running; `None` accepts any allowed channel.
+    active_channels: Option<Vec<String>>,
 }
 
 impl MessageTool {
     /// Create a new message tool.
     pub fn new(bus: Arc<MessageBus>) -> Self {
-        Self { bus }
+        Self {
+            bus,
+            active_channels: None,
+        }
+    }
+
+    /// Only deliver to these channels, so messages are not queued for a
+    /// channel that nothing will send on.
+    pub fn with_channels(mut self, channels: Vec<String>) -> Self {
+        self.active_channels = Some(channels);
+        self
     }
 }
 
@@ -124,6 +140,19 @@ impl Tool for MessageTool {
                 ALLOWED_CHANNELS.join(", ")
             )));
         }
+        if let Some(active) = &self.active_channels {
+            if !active.iter().any(|c| c.eq_ignore_ascii_case(&channel)) {
+                return Err(ZeptoError::Tool(format!(
+                    "Channel '{}' is not enabled. Enabled channels: {}",
+                    channel,
+                    if active.is_empty() {
+                        "none".to_string()
+                    } else {
+                        active.join(", ")
+                    }
+                )));
+            }
+        }
 
         // Determine action — default to "send" when absent.
         let action = args
@@ -355,6 +384,34 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_message_tool_restricted_to_enabled_channels() {
+        let bus = Arc::new(MessageBus::new());
+        let tool = MessageTool::new(bus.clone()).with_channels(vec!["slack".to_string()]);
+
+        let err = tool
+            .execute(
+                json!({"content": "Digest", "channel": "telegram", "chat_id": "42"}),
+                &ToolContext::new(),
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not enabled"));
+
+        let result = tool
+            .execute(
+                json!({"content": "Daily digest", "channel": "slack", "chat_id": "C123"}),
+                &ToolContext::new(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result, "Message sent to slack:C123");
+        let outbound = bus.consume_outbound().await.expect("outbound message");
+        assert_eq!(outbound.channel, "slack");
+        assert_eq!(outbound.chat_id, "C123");
+        assert_eq!(outbound.content, "Daily digest");
+    }
+
     // ====================================================================
     // WhatsApp channel tests
     // ====================================================================
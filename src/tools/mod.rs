@@ -18,15 +18,20 @@
 //! - `WriteFileTool`: Write content to a file
 //! - `ListDirTool`: List directory contents
 //! - `EditFileTool`: Edit a file by replacing text
+//! - `GrepTool`: Search workspace file contents by regex
+//! - `TreeTool`: Show a depth-limited workspace directory tree
 //! - `ShellTool`: Execute shell commands
 //! - `WebSearchTool`: Search the web via Brave Search API
 //! - `WebFetchTool`: Fetch URL content and extract text
+//! - `HttpTool`: Send HTTP requests with custom method, headers and body
 //! - `MessageTool`: Send proactive outbound chat messages
 //! - `MemorySearchTool`: Search workspace markdown memory files
 //! - `MemoryGetTool`: Read memory files with line windows
+//! - `MemoryWriteTool`: Append dated entries to memory files
 //! - `WhatsAppTool`: Send WhatsApp Cloud API messages
 //! - `GoogleSheetsTool`: Read and write Google Sheets ranges
 //! - `R8rTool`: Execute r8r workflows for deterministic automation
+//! - `ListToolsTool`: List the agent's available tools
 //!
 //! # Example
 //!
@@ -59,36 +64,51 @@ pub mod cron;
 pub mod custom;
 pub mod delegate;
 pub mod filesystem;
+pub mod grep;
+#[cfg(feature = "grpc-plugins")]
+pub mod grpc_plugin;
 pub mod gsheets;
+pub mod http;
+pub mod list_tools;
 pub mod longterm_memory;
 pub mod mcp;
 pub mod memory;
 pub mod message;
+mod patch;
 pub mod plugin;
 pub mod r8r;
 mod registry;
 pub mod reminder;
+mod render;
+pub mod schema;
 #[cfg(feature = "screenshot")]
 pub mod screenshot;
 pub mod shell;
 pub mod spawn;
+pub mod tree;
 mod types;
+#[cfg(feature = "wasm-plugins")]
+pub mod wasm_plugin;
 pub mod web;
 pub mod whatsapp;
 
 pub use binary_plugin::BinaryPluginTool;
 pub use custom::CustomTool;
 pub use delegate::DelegateTool;
+pub use grep::GrepTool;
 pub use gsheets::GoogleSheetsTool;
+pub use http::HttpTool;
+pub use list_tools::ListToolsTool;
 pub use longterm_memory::LongTermMemoryTool;
-pub use memory::{MemoryGetTool, MemorySearchTool};
+pub use memory::{MemoryGetTool, MemorySearchTool, MemoryWriteTool};
 pub use message::MessageTool;
 pub use r8r::R8rTool;
-pub use registry::ToolRegistry;
+pub use registry::{ToolDescription, ToolRegistry};
 pub use reminder::ReminderTool;
 #[cfg(feature = "screenshot")]
 pub use screenshot::WebScreenshotTool;
-pub use types::{Tool, ToolContext};
+pub use tree::TreeTool;
+pub use types::{Tool, ToolContext, ToolSource};
 pub use web::{is_blocked_host, resolve_and_check_host, WebFetchTool, WebSearchTool};
 pub use whatsapp::WhatsAppTool;
 
@@ -131,6 +151,10 @@ impl Tool for EchoTool {
         "Echo message"
     }
 
+    fn is_parallel_safe(&self) -> bool {
+        true
+    }
+
     fn parameters(&self) -> Value {
         serde_json::json!({
             "type": "object",
@@ -0,0 +1,310 @@
+//! Unified diff parsing and application for `edit_file` diff mode.
+//!
+//! Only single-file diffs are supported. File headers (`---`/`+++`) are
+//! optional and their paths are ignored; the tool's `path` argument decides
+//! the target. Hunks must match the file exactly, but may sit at a different
+//! line than their header says, as with `patch`.
+
+use crate::error::{Result, ZeptoError};
+
+/// One `@@` hunk.
+#[derive(Debug, Default)]
+struct Hunk {
+    /// 1-based start line in the original file, from the header
+    old_start: usize,
+    /// Context and removed lines, in order
+    old: Vec<String>,
+    /// Context and added lines, in order
+    new: Vec<String>,
+    added: usize,
+    removed: usize,
+    /// The new side ends with `\ No newline at end of file`
+    new_no_newline: bool,
+}
+
+/// A parsed single-file unified diff.
+#[derive(Debug)]
+pub(crate) struct Patch {
+    hunks: Vec<Hunk>,
+    /// The diff creates the file (`--- /dev/null` or `@@ -0,0 ...`)
+    pub(crate) creates: bool,
+}
+
+/// Outcome of applying a patch.
+#[derive(Debug, PartialEq)]
+pub(crate) struct Applied {
+    pub(crate) content: String,
+    pub(crate) hunks: usize,
+    pub(crate) added: usize,
+    pub(crate) removed: usize,
+}
+
+impl Patch {
+    /// Parse a unified diff.
+    pub(crate) fn parse(diff: &str) -> Result<Self> {
+        let mut hunks: Vec<Hunk> = Vec::new();
+        let mut creates = false;
+        let mut seen_header = false;
+        let mut lines = diff.lines().peekable();
+
+        while let Some(line) = lines.next() {
+            if let Some(old_path) = line.strip_prefix("--- ") {
+                if seen_header || !hunks.is_empty() {
+                    return Err(invalid("the diff must change a single file"));
+                }
+                seen_header = true;
+                creates = old_path.split('\t').next().map(str::trim) == Some("/dev/null");
+                continue;
+            }
+            if let Some(new_path) = line.strip_prefix("+++ ") {
+                if new_path.split('\t').next().map(str::trim) == Some("/dev/null") {
+                    return Err(invalid("deleting files is not supported"));
+                }
+                continue;
+            }
+            if !line.starts_with("@@") {
+                // `diff --git`, `index` and other preamble lines.
+                if hunks.is_empty() {
+                    continue;
+                }
+                return Err(invalid(&format!("unexpected line '{}'", line)));
+            }
+
+            let (old_start, old_len, new_len) = parse_header(line)?;
+            if old_start == 0 && old_len == 0 {
+                creates = true;
+            }
+            let mut hunk = Hunk {
+                old_start,
+                ..Hunk::default()
+            };
+            let mut last_new_side = false;
+            while hunk.old.len() < old_len || hunk.new.len() < new_len {
+                let Some(body) = lines.next() else {
+                    return Err(invalid(&format!(
+                        "hunk '{}' ends early; expected {} old and {} new lines",
+                        line, old_len, new_len
+                    )));
+                };
+                // Some editors strip the single space of an empty context line.
+                let (marker, text) = match body.chars().next() {
+                    Some(c) => (c, &body[c.len_utf8()..]),
+                    None => (' ', ""),
+                };
+                match marker {
+                    ' ' => {
+                        hunk.old.push(text.to_string());
+                        hunk.new.push(text.to_string());
+                        last_new_side = true;
+                    }
+                    '-' => {
+                        hunk.old.push(text.to_string());
+                        hunk.removed += 1;
+                        last_new_side = false;
+                    }
+                    '+' => {
+                        hunk.new.push(text.to_string());
+                        hunk.added += 1;
+                        last_new_side = true;
+                    }
+                    '\\' => {}
+                    _ => {
+                        return Err(invalid(&format!(
+                            "unexpected line '{}' in hunk '{}'",
+                            body, line
+                        )))
+                    }
+                }
+                if hunk.old.len() > old_len || hunk.new.len() > new_len {
+                    return Err(invalid(&format!(
+                        "hunk '{}' has more lines than its header says",
+                        line
+                    )));
+                }
+            }
+            if lines.peek().is_some_and(|next| next.starts_with('\\')) {
+                lines.next();
+                hunk.new_no_newline = last_new_side;
+            }
+            hunks.push(hunk);
+        }
+
+        if hunks.is_empty() {
+            return Err(invalid("no hunks found"));
+        }
+        if creates && (hunks.len() > 1 || !hunks[0].old.is_empty()) {
+            return Err(invalid(
+                "a diff from /dev/null must be a single all-addition hunk",
+            ));
+        }
+        Ok(Self { hunks, creates })
+    }
+
+    /// Apply the hunks to `original`, all or nothing.
+    pub(crate) fn apply(&self, original: &str) -> Result<Applied> {
+        let crlf = original.contains("\r\n");
+        let mut trailing_newline = original.is_empty() || original.ends_with('\n');
+        let mut lines: Vec<String> = original
+            .lines()
+            .map(|l| l.trim_end_matches('\r').to_string())
+            .collect();
+
+        // Hunks are applied in order; `offset` tracks how earlier hunks
+        // shifted line numbers, `floor` keeps hunks from overlapping.
+        let mut offset: isize = 0;
+        let mut floor = 0;
+        let (mut added, mut removed) = (0, 0);
+        for (index, hunk) in self.hunks.iter().enumerate() {
+            // A pure insertion's start line is the line it follows.
+            let start = if hunk.old.is_empty() {
+                hunk.old_start
+            } else {
+                hunk.old_start.saturating_sub(1)
+            };
+            let expected = (start as isize + offset).max(0) as usize;
+            let at = find_hunk(&lines, &hunk.old, expected, floor).ok_or_else(|| {
+                ZeptoError::Tool(format!(
+                    "Hunk {} (@@ -{}) does not match the file; re-read it and regenerate the diff. No changes were written",
+                    index + 1,
+                    hunk.old_start
+                ))
+            })?;
+            let reaches_end = at + hunk.old.len() == lines.len();
+            lines.splice(at..at + hunk.old.len(), hunk.new.iter().cloned());
+            if reaches_end {
+                trailing_newline = !hunk.new_no_newline;
+            }
+            offset += hunk.new.len() as isize - hunk.old.len() as isize;
+            floor = at + hunk.new.len();
+            added += hunk.added;
+            removed += hunk.removed;
+        }
+
+        let newline = if crlf { "\r\n" } else { "\n" };
+        let mut content = lines.join(newline);
+        if trailing_newline && !lines.is_empty() {
+            content.push_str(newline);
+        }
+        Ok(Applied {
+            content,
+            hunks: self.hunks.len(),
+            added,
+            removed,
+        })
+    }
+}
+
+/// Where `old` matches `lines`: at `expected` if possible, otherwise the
+/// nearest match at or after `floor`.
+fn find_hunk(lines: &[String], old: &[String], expected: usize, floor: usize) -> Option<usize> {
+    let fits = |at: usize| at + old.len() <= lines.len() && lines[at..at + old.len()] == *old;
+    if old.is_empty() {
+        return Some(expected.clamp(floor, lines.len()));
+    }
+    if expected >= floor && fits(expected) {
+        return Some(expected);
+    }
+    (floor..=lines.len().saturating_sub(old.len()))
+        .filter(|&at| fits(at))
+        .min_by_key(|&at| at.abs_diff(expected))
+}
+
+/// Parse `@@ -start[,len] +start[,len] @@` into (old_start, old_len, new_len).
+fn parse_header(line: &str) -> Result<(usize, usize, usize)> {
+    let bad = || invalid(&format!("malformed hunk header '{}'", line));
+    let mut parts = line
+        .strip_prefix("@@ ")
+        .and_then(|rest| rest.split(" @@").next())
+        .ok_or_else(bad)?
+        .split_whitespace();
+    let range = |part: Option<&str>, sign: char| -> Option<(usize, usize)> {
+        let part = part?.strip_prefix(sign)?;
+        match part.split_once(',') {
+            Some((start, len)) => Some((start.parse().ok()?, len.parse().ok()?)),
+            None => Some((part.parse().ok()?, 1)),
+        }
+    };
+    let (old_start, old_len) = range(parts.next(), '-').ok_or_else(bad)?;
+    let (_, new_len) = range(parts.next(), '+').ok_or_else(bad)?;
+    Ok((old_start, old_len, new_len))
+}
+
+fn invalid(reason: &str) -> ZeptoError {
+    ZeptoError::Tool(format!("Invalid diff: {}", reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_multiple_hunks_with_offsets() {
+        let original = "a\nb\nc\nd\ne\nf\ng\nh\n";
+        // The second header is off by one line; the hunk is still found.
+        let diff = "--- a/f.txt\n+++ b/f.txt\n\
+                    @@ -1,3 +1,4 @@\n a\n-b\n+B\n+B2\n c\n\
+                    @@ -7,2 +8,1 @@\n f\n-g\n";
+        let applied = Patch::parse(diff).unwrap().apply(original).unwrap();
+        assert_eq!(applied.content, "a\nB\nB2\nc\nd\ne\nf\nh\n");
+        assert_eq!((applied.hunks, applied.added, applied.removed), (2, 2, 2));
+    }
+
+    #[test]
+    fn test_apply_keeps_line_endings() {
+        let diff = "@@ -1,2 +1,2 @@\n one\n-two\n+TWO\n";
+        let patch = Patch::parse(diff).unwrap();
+        assert_eq!(
+            patch.apply("one\r\ntwo\r\n").unwrap().content,
+            "one\r\nTWO\r\n"
+        );
+        assert_eq!(patch.apply("one\ntwo").unwrap().content, "one\nTWO\n");
+
+        let diff = "@@ -1,2 +1,2 @@\n one\n-two\n+TWO\n\\ No newline at end of file\n";
+        let patch = Patch::parse(diff).unwrap();
+        assert_eq!(patch.apply("one\ntwo\n").unwrap().content, "one\nTWO");
+    }
+
+    #[test]
+    fn test_pure_insertion_follows_its_start_line() {
+        let diff = "@@ -2,0 +3,1 @@\n+inserted\n";
+        let applied = Patch::parse(diff).unwrap().apply("a\nb\nc\n").unwrap();
+        assert_eq!(applied.content, "a\nb\ninserted\nc\n");
+        assert_eq!((applied.added, applied.removed), (1, 0));
+    }
+
+    #[test]
+    fn test_context_mismatch_is_rejected() {
+        let diff = "@@ -1,2 +1,2 @@\n one\n-two\n+TWO\n";
+        let err = Patch::parse(diff)
+            .unwrap()
+            .apply("one\nthree\n")
+            .unwrap_err();
+        assert!(err.to_string().contains("Hunk 1 (@@ -1) does not match"));
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_diffs() {
+        for (diff, reason) in [
+            ("just text\n", "no hunks found"),
+            ("@@ -1 +1 @@\n-a\n", "ends early"),
+            ("@@ -x +1 @@\n", "malformed hunk header"),
+            ("@@ -1 +1 @@\n-a\n?b\n", "unexpected line"),
+            (
+                "--- a/x\n+++ b/x\n@@ -1 +1 @@\n-a\n+b\n--- a/y\n+++ b/y\n",
+                "single file",
+            ),
+            ("--- a/x\n+++ /dev/null\n@@ -1 +0,0 @@\n-a\n", "deleting"),
+        ] {
+            let err = Patch::parse(diff).unwrap_err().to_string();
+            assert!(err.contains(reason), "{:?}: {}", diff, err);
+        }
+    }
+
+    #[test]
+    fn test_creation_diff() {
+        let patch = Patch::parse("--- /dev/null\n+++ b/new.txt\n@@ -0,0 +1,2 @@\n+hello\n+world\n")
+            .unwrap();
+        assert!(patch.creates);
+        assert_eq!(patch.apply("").unwrap().content, "hello\nworld\n");
+    }
+}
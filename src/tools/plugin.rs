@@ -9,16 +9,23 @@
 //! Each `PluginTool` instance holds a single tool definition from a plugin
 //! manifest. When the LLM invokes the tool, `execute()`:
 //!
-//! 1. Parses the JSON arguments
-//! 2. Interpolates `{{param_name}}` placeholders in the command template
-//! 3. Executes the resulting shell command via `tokio::process::Command`
-//! 4. Returns stdout (or stderr on failure) as the tool result
+//! 1. Validates the JSON arguments against the tool's `parameters` schema
+//! 2. Splits the command template into words and fills `{{param_name}}`
+//!    placeholders in each word, plus `${VAR}` references to host
+//!    environment variables listed in the manifest's `allowed_env` (see
+//!    [`render_command_with_env`]); `env` values are interpolated the same way
+//! 3. Executes the resulting argv directly via `tokio::process::Command`
+//!    (no shell), killing and reaping it if it exceeds `timeout_secs`
+//!    (default 30s). In dry-run mode the argv is returned instead
+//! 4. Returns stdout (or stderr on failure) as the tool result, shaped by
+//!    the tool's `output_format`: raw text, validated JSON, or a JSON array
+//!    of non-empty lines (see [`format_output`])
 //!
 //! # Example
 //!
 //! ```rust,ignore
 //! use zeptoclaw::tools::plugin::PluginTool;
-//! use zeptoclaw::plugins::PluginToolDef;
+//! use zeptoclaw::plugins::{PluginOutputFormat, PluginToolDef};
 //! use serde_json::json;
 //!
 //! let def = PluginToolDef {
@@ -29,6 +36,7 @@
 //!     working_dir: None,
 //!     timeout_secs: Some(10),
 //!     env: None,
+//!     output_format: PluginOutputFormat::Text,
 //! };
 //!
 //! let tool = PluginTool::new(def, "git-tools");
@@ -36,30 +44,216 @@
 
 use async_trait::async_trait;
 use serde_json::Value;
+use std::process::Stdio;
 use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::process::Child;
 
 use crate::error::{Result, ZeptoError};
-use crate::plugins::PluginToolDef;
+use crate::plugins::{PluginOutputFormat, PluginToolDef};
 
-use super::types::{Tool, ToolContext};
+use super::types::{Tool, ToolContext, ToolSource};
 
-/// Shell-escape a string by wrapping it in single quotes.
+/// Render a command template into an argv list.
 ///
-/// Any embedded single quotes are escaped as `'\''` (end quote, escaped
-/// quote, restart quote). This prevents command injection via `$(...)`,
-/// backticks, `&&`, `;`, pipes, and all other shell metacharacters.
-fn shell_escape(value: &str) -> String {
-    let mut escaped = String::with_capacity(value.len() + 2);
-    escaped.push('\'');
-    for ch in value.chars() {
-        if ch == '\'' {
-            escaped.push_str("'\\''");
-        } else {
-            escaped.push(ch);
+/// The template is first split into words with POSIX-shell-like quoting
+/// (whitespace separates words; `'...'`, `"..."` and `\` group or escape
+/// characters), and only then are `{{param_name}}` placeholders replaced
+/// inside each word. An argument value therefore always stays within the
+/// word it was placed in, whatever spaces, quotes or shell metacharacters
+/// it contains. Placeholders without a matching argument are left as-is.
+///
+/// No shell expansion (variables, globs, `~`) is performed.
+pub fn render_command(template: &str, args: &Value) -> Vec<String> {
+    split_words(template)
+        .into_iter()
+        .map(|word| substitute_placeholders(&word, args))
+        .collect()
+}
+
+/// Render a command template like [`render_command`], also replacing
+/// `${VAR}` with the host environment variable `VAR`.
+///
+/// Only variables named in `allowed_env` are resolved; any other `${...}`,
+/// or an allowed variable that is not set, is an error. Argument and
+/// environment values are inserted in the same pass, so neither is ever
+/// expanded again.
+pub fn render_command_with_env(
+    template: &str,
+    args: &Value,
+    allowed_env: &[String],
+) -> std::result::Result<Vec<String>, String> {
+    split_words(template)
+        .into_iter()
+        .map(|word| {
+            expand_env(&word, allowed_env, |text| {
+                substitute_placeholders(text, args)
+            })
+        })
+        .collect()
+}
+
+/// Replace `${VAR}` references in `text` with allowed host environment
+/// variables, as for [`render_command_with_env`].
+pub fn interpolate_env(text: &str, allowed_env: &[String]) -> std::result::Result<String, String> {
+    expand_env(text, allowed_env, str::to_string)
+}
+
+/// Resolve `${VAR}` references in `text`, passing the text between them
+/// through `literal`.
+fn expand_env(
+    text: &str,
+    allowed_env: &[String],
+    mut literal: impl FnMut(&str) -> String,
+) -> std::result::Result<String, String> {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("${") {
+        let Some(len) = rest[start + 2..].find('}') else {
+            break;
+        };
+        let name = &rest[start + 2..start + 2 + len];
+        if !allowed_env.iter().any(|allowed| allowed == name) {
+            return Err(format!(
+                "${{{}}} is not listed in the plugin's allowed_env",
+                name
+            ));
         }
+        let value = std::env::var(name)
+            .map_err(|_| format!("environment variable '{}' is not set", name))?;
+        result.push_str(&literal(&rest[..start]));
+        result.push_str(&value);
+        rest = &rest[start + len + 3..];
+    }
+    result.push_str(&literal(rest));
+    Ok(result)
+}
+
+/// Split a command template into words, honouring quotes and backslashes.
+///
+/// An unterminated quote runs to the end of the template.
+fn split_words(template: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut chars = template.chars();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            c if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            '\'' => {
+                in_word = true;
+                for c in chars.by_ref() {
+                    if c == '\'' {
+                        break;
+                    }
+                    current.push(c);
+                }
+            }
+            '"' => {
+                in_word = true;
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' => match chars.next() {
+                            Some(next @ ('"' | '\\' | '$' | '`')) => current.push(next),
+                            Some(next) => {
+                                current.push('\\');
+                                current.push(next);
+                            }
+                            None => current.push('\\'),
+                        },
+                        c => current.push(c),
+                    }
+                }
+            }
+            '\\' => {
+                in_word = true;
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+            }
+            c => {
+                in_word = true;
+                current.push(c);
+            }
+        }
+    }
+    if in_word {
+        words.push(current);
+    }
+    words
+}
+
+/// Replace `{{param_name}}` placeholders in a single word, in one pass so
+/// placeholder-like text inside a value is never expanded again.
+fn substitute_placeholders(word: &str, args: &Value) -> String {
+    let mut result = String::with_capacity(word.len());
+    let mut rest = word;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        let key = &rest[start + 2..start + 2 + len];
+        result.push_str(&rest[..start]);
+        match args.get(key) {
+            Some(Value::String(s)) => result.push_str(s),
+            Some(other) => result.push_str(&other.to_string()),
+            None => result.push_str(&rest[start..start + len + 4]),
+        }
+        rest = &rest[start + len + 4..];
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Shape a successful command's stdout according to `format`.
+///
+/// `Text` returns stdout unchanged. `Json` parses it and returns the compact
+/// serialization, failing if stdout is not valid JSON. `Lines` returns a JSON
+/// array of the trimmed, non-empty lines.
+pub fn format_output(tool_name: &str, format: PluginOutputFormat, stdout: &str) -> Result<String> {
+    match format {
+        PluginOutputFormat::Text => Ok(stdout.to_string()),
+        PluginOutputFormat::Json => serde_json::from_str::<Value>(stdout)
+            .map(|value| value.to_string())
+            .map_err(|e| {
+                ZeptoError::Tool(format!(
+                    "Plugin tool '{}' output is not valid JSON: {}",
+                    tool_name, e
+                ))
+            }),
+        PluginOutputFormat::Lines => {
+            let lines: Vec<&str> = stdout
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .collect();
+            Ok(Value::from(lines).to_string())
+        }
+    }
+}
+
+/// Read a child's pipe to the end, returning whatever was collected.
+async fn read_pipe<R: AsyncRead + Unpin>(pipe: Option<R>) -> Vec<u8> {
+    let mut buf = Vec::new();
+    if let Some(mut pipe) = pipe {
+        let _ = pipe.read_to_end(&mut buf).await;
+    }
+    buf
+}
+
+/// Kill a child that overran its budget and wait for it so it is not left
+/// behind as a zombie.
+async fn kill_and_reap(child: &mut Child) {
+    if let Err(e) = child.kill().await {
+        tracing::warn!(error = %e, "Failed to kill plugin tool process");
     }
-    escaped.push('\'');
-    escaped
 }
 
 /// Adapter that wraps a `PluginToolDef` and implements the `Tool` trait.
@@ -68,6 +262,8 @@ pub struct PluginTool {
     def: PluginToolDef,
     /// Name of the plugin that provides this tool (for logging).
     plugin_name: String,
+    /// Host environment variables that may be referenced as `${VAR}`.
+    allowed_env: Vec<String>,
 }
 
 impl PluginTool {
@@ -76,28 +272,15 @@ impl PluginTool {
         Self {
             def,
             plugin_name: plugin_name.to_string(),
+            allowed_env: Vec::new(),
         }
     }
 
-    /// Interpolate `{{param_name}}` placeholders in a command template.
-    ///
-    /// All parameter values are shell-escaped to prevent command injection.
-    /// Values are wrapped in single quotes with any embedded single quotes
-    /// escaped as `'\''`.
-    fn interpolate(command: &str, args: &Value) -> String {
-        let mut result = command.to_string();
-        if let Some(obj) = args.as_object() {
-            for (key, value) in obj {
-                let placeholder = format!("{{{{{}}}}}", key);
-                let raw = match value {
-                    Value::String(s) => s.clone(),
-                    other => other.to_string(),
-                };
-                let replacement = shell_escape(&raw);
-                result = result.replace(&placeholder, &replacement);
-            }
-        }
-        result
+    /// Allow `${VAR}` references to these host environment variables
+    /// (the manifest's `allowed_env`).
+    pub fn with_allowed_env(mut self, allowed_env: Vec<String>) -> Self {
+        self.allowed_env = allowed_env;
+        self
     }
 }
 
@@ -115,24 +298,49 @@ impl Tool for PluginTool {
         self.description()
     }
 
+    fn source(&self) -> ToolSource {
+        ToolSource::Plugin
+    }
+
     fn parameters(&self) -> Value {
         self.def.parameters.clone()
     }
 
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<String> {
-        let command = Self::interpolate(&self.def.command, &args);
+        self.validate_args(&args)?;
+
+        let argv = render_command_with_env(&self.def.command, &args, &self.allowed_env)
+            .map_err(|e| ZeptoError::Tool(format!("Plugin tool '{}': {}", self.def.name, e)))?;
+        let Some((program, program_args)) = argv.split_first() else {
+            return Err(ZeptoError::Tool(format!(
+                "Plugin tool '{}' has an empty command",
+                self.def.name
+            )));
+        };
         let timeout = Duration::from_secs(self.def.effective_timeout());
 
+        if ctx.dry_run {
+            return Ok(format!(
+                "[DRY RUN] Would run plugin tool '{}': {}",
+                self.def.name,
+                Value::from(argv.clone())
+            ));
+        }
+
         tracing::debug!(
             plugin = %self.plugin_name,
             tool = %self.def.name,
-            command = %command,
+            argv = ?argv,
             "Executing plugin tool"
         );
 
-        // Build the command
-        let mut cmd = tokio::process::Command::new("sh");
-        cmd.arg("-c").arg(&command);
+        // Build the command directly from argv; no shell is involved
+        let mut cmd = tokio::process::Command::new(program);
+        cmd.args(program_args);
 
         // Apply working directory: tool def > workspace from context
         if let Some(ref wd) = self.def.working_dir {
@@ -144,29 +352,75 @@ impl Tool for PluginTool {
         // Apply environment variables from tool definition
         if let Some(ref env_vars) = self.def.env {
             for (key, value) in env_vars {
+                let value = interpolate_env(value, &self.allowed_env).map_err(|e| {
+                    ZeptoError::Tool(format!("Plugin tool '{}': {}", self.def.name, e))
+                })?;
                 cmd.env(key, value);
             }
         }
 
-        // Execute with timeout
-        let output = tokio::time::timeout(timeout, cmd.output())
-            .await
-            .map_err(|_| {
-                ZeptoError::Tool(format!(
-                    "Plugin tool '{}' timed out after {}s",
-                    self.def.name,
-                    timeout.as_secs()
-                ))
-            })?
-            .map_err(|e| {
+        cmd.stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            // Safety net if this future is dropped mid-run
+            .kill_on_drop(true);
+
+        let mut child = cmd.spawn().map_err(|e| {
+            ZeptoError::Tool(format!(
+                "Failed to execute plugin tool '{}': {}",
+                self.def.name, e
+            ))
+        })?;
+        let stdout = child.stdout.take();
+        let stderr = child.stderr.take();
+
+        // Execute with timeout, bailing out early if the turn is cancelled.
+        // Pipes are drained concurrently so a chatty command cannot block.
+        let run = async {
+            let (status, stdout, stderr) =
+                tokio::join!(child.wait(), read_pipe(stdout), read_pipe(stderr));
+            status.map(|status| (status, stdout, stderr))
+        };
+        let outcome = tokio::select! {
+            res = tokio::time::timeout(timeout, run) => Some(res),
+            _ = ctx.cancelled() => None,
+        };
+
+        let (status, stdout, stderr) = match outcome {
+            Some(Ok(res)) => res.map_err(|e| {
                 ZeptoError::Tool(format!(
                     "Failed to execute plugin tool '{}': {}",
                     self.def.name, e
                 ))
-            })?;
+            })?,
+            Some(Err(_)) => {
+                kill_and_reap(&mut child).await;
+                return Err(ZeptoError::Tool(format!(
+                    "plugin tool '{}' timed out after {}s",
+                    self.def.name,
+                    timeout.as_secs()
+                )));
+            }
+            None => {
+                kill_and_reap(&mut child).await;
+                return Err(ZeptoError::Tool(format!(
+                    "Plugin tool '{}' cancelled",
+                    self.def.name
+                )));
+            }
+        };
+        let output = std::process::Output {
+            status,
+            stdout,
+            stderr,
+        };
 
         if output.status.success() {
-            Ok(String::from_utf8_lossy(&output.stdout).to_string())
+            format_output(
+                &self.def.name,
+                self.def.output_format,
+                &String::from_utf8_lossy(&output.stdout),
+            )
         } else {
             let stderr = String::from_utf8_lossy(&output.stderr);
             let stdout = String::from_utf8_lossy(&output.stdout);
@@ -200,74 +454,94 @@ mod tests {
             working_dir: None,
             timeout_secs: Some(5),
             env: None,
+            output_format: PluginOutputFormat::Text,
         }
     }
 
     #[test]
-    fn test_shell_escape_basic() {
-        assert_eq!(shell_escape("hello"), "'hello'");
+    fn test_render_command_basic() {
+        let args = json!({"message": "hello"});
+        assert_eq!(
+            render_command("echo {{message}}", &args),
+            vec!["echo", "hello"]
+        );
     }
 
     #[test]
-    fn test_shell_escape_with_single_quote() {
-        assert_eq!(shell_escape("it's"), "'it'\\''s'");
+    fn test_render_command_multiple() {
+        let cmd = "git -C {{path}} log --oneline -{{count}}";
+        let args = json!({"path": "/tmp/repo", "count": 5});
+        assert_eq!(
+            render_command(cmd, &args),
+            vec!["git", "-C", "/tmp/repo", "log", "--oneline", "-5"]
+        );
     }
 
     #[test]
-    fn test_shell_escape_injection_attempt() {
-        // $(rm -rf /) should become a literal string, not executed
-        assert_eq!(shell_escape("$(rm -rf /)"), "'$(rm -rf /)'");
-        assert_eq!(shell_escape("`whoami`"), "'`whoami`'");
-        assert_eq!(shell_escape("foo; rm -rf /"), "'foo; rm -rf /'");
-        assert_eq!(shell_escape("foo && evil"), "'foo && evil'");
-        assert_eq!(shell_escape("foo | evil"), "'foo | evil'");
+    fn test_render_command_no_match() {
+        let args = json!({"unused": "val"});
+        assert_eq!(render_command("echo hello", &args), vec!["echo", "hello"]);
     }
 
     #[test]
-    fn test_interpolate_basic() {
-        let cmd = "echo {{message}}";
-        let args = json!({"message": "hello"});
-        assert_eq!(PluginTool::interpolate(cmd, &args), "echo 'hello'");
+    fn test_render_command_missing_param() {
+        assert_eq!(
+            render_command("echo {{missing}}", &json!({})),
+            vec!["echo", "{{missing}}"]
+        );
     }
 
     #[test]
-    fn test_interpolate_multiple() {
-        let cmd = "git -C {{path}} log --oneline -{{count}}";
-        let args = json!({"path": "/tmp/repo", "count": 5});
+    fn test_render_command_value_with_spaces_and_quotes_is_one_arg() {
+        let args = json!({"msg": "it's a \"quoted\" value; rm -rf ~"});
         assert_eq!(
-            PluginTool::interpolate(cmd, &args),
-            "git -C '/tmp/repo' log --oneline -'5'"
+            render_command("echo {{msg}}", &args),
+            vec!["echo", "it's a \"quoted\" value; rm -rf ~"]
+        );
+        assert_eq!(
+            render_command("grep -n {{msg}} log.txt", &args),
+            vec!["grep", "-n", "it's a \"quoted\" value; rm -rf ~", "log.txt"]
         );
     }
 
     #[test]
-    fn test_interpolate_no_match() {
-        let cmd = "echo hello";
-        let args = json!({"unused": "val"});
-        assert_eq!(PluginTool::interpolate(cmd, &args), "echo hello");
+    fn test_render_command_prevents_command_injection() {
+        let args = json!({"input": "$(cat /etc/passwd)", "other": "`whoami`"});
+        assert_eq!(
+            render_command("echo {{input}} {{other}}", &args),
+            vec!["echo", "$(cat /etc/passwd)", "`whoami`"]
+        );
     }
 
     #[test]
-    fn test_interpolate_missing_param() {
-        let cmd = "echo {{missing}}";
-        let args = json!({});
-        assert_eq!(PluginTool::interpolate(cmd, &args), "echo {{missing}}");
+    fn test_render_command_values_are_not_reexpanded() {
+        let args = json!({"a": "{{b}}", "b": "secret"});
+        assert_eq!(render_command("echo {{a}}", &args), vec!["echo", "{{b}}"]);
     }
 
     #[test]
-    fn test_interpolate_prevents_command_injection() {
-        let cmd = "echo {{input}}";
-        let args = json!({"input": "$(cat /etc/passwd)"});
-        let result = PluginTool::interpolate(cmd, &args);
-        // The $() should be inside single quotes, making it a literal string
-        assert_eq!(result, "echo '$(cat /etc/passwd)'");
-        assert!(!result.contains("$(cat /etc/passwd)'") || result.starts_with("echo '"));
+    fn test_render_command_template_quoting() {
+        let args = json!({"name": "a b"});
+        assert_eq!(
+            render_command(r#"printf '%s\n' "Hello, {{name}}!" x\ y ''"#, &args),
+            vec!["printf", "%s\\n", "Hello, a b!", "x y", ""]
+        );
+        assert_eq!(
+            render_command(r#"echo "say \"hi\" \n""#, &json!({})),
+            vec!["echo", "say \"hi\" \\n"]
+        );
+        assert_eq!(
+            render_command("  echo   'unterminated value", &json!({})),
+            vec!["echo", "unterminated value"]
+        );
+        assert!(render_command("   ", &json!({})).is_empty());
     }
 
     #[test]
     fn test_tool_name() {
         let tool = PluginTool::new(test_def("echo"), "test-plugin");
         assert_eq!(tool.name(), "test_tool");
+        assert_eq!(tool.source(), ToolSource::Plugin);
     }
 
     #[test]
@@ -288,8 +562,6 @@ mod tests {
 
     #[tokio::test]
     async fn test_execute_with_interpolation() {
-        // Note: {{msg}} is replaced with shell-escaped value 'greetings'
-        // The command becomes: echo 'greetings'
         let def = test_def("echo {{msg}}");
         let tool = PluginTool::new(def, "test-plugin");
         let ctx = ToolContext::new();
@@ -320,6 +592,83 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_execute_passes_value_as_single_argument() {
+        // printf repeats its format once per argument, so a value that was
+        // split into several words would print several lines.
+        let def = test_def("printf '[%s]\\n' {{msg}}");
+        let tool = PluginTool::new(def, "test-plugin");
+        let ctx = ToolContext::new();
+        let result = tool
+            .execute(json!({"msg": "two words \"and\" 'quotes'; rm -rf ~"}), &ctx)
+            .await
+            .unwrap();
+        assert_eq!(result, "[two words \"and\" 'quotes'; rm -rf ~]\n");
+    }
+
+    #[tokio::test]
+    async fn test_execute_rejects_args_violating_schema() {
+        let dir = tempfile::tempdir().unwrap();
+        let marker = dir.path().join("ran-3");
+        let mut def = test_def("touch ran-{{count}}");
+        def.working_dir = Some(dir.path().to_string_lossy().into_owned());
+        def.parameters = json!({
+            "type": "object",
+            "properties": {"count": {"type": "integer"}},
+            "required": ["count"]
+        });
+        let tool = PluginTool::new(def, "test-plugin");
+        let ctx = ToolContext::new();
+
+        let err = tool
+            .execute(json!({"count": "3; rm -rf /"}), &ctx)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ZeptoError::Tool(_)));
+        assert_eq!(
+            err.to_string(),
+            "Tool error: Invalid arguments for tool 'test_tool': \
+             'count' must be of type integer, got string"
+        );
+        let err = tool.execute(json!({}), &ctx).await.unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Missing required parameter 'count'"));
+
+        assert!(std::fs::read_dir(dir.path()).unwrap().next().is_none());
+
+        tool.execute(json!({"count": 3}), &ctx).await.unwrap();
+        assert!(marker.exists());
+    }
+
+    #[tokio::test]
+    async fn test_execute_timeout_kills_and_reaps_child() {
+        let dir = tempfile::tempdir().unwrap();
+        let pid_file = dir.path().join("pid");
+        let mut def = test_def(&format!(
+            "sh -c 'echo $$ > {} && exec sleep 10'",
+            pid_file.display()
+        ));
+        def.timeout_secs = Some(1);
+        let tool = PluginTool::new(def, "test-plugin");
+        let ctx = ToolContext::new();
+
+        let started = std::time::Instant::now();
+        let err = tool.execute(json!({}), &ctx).await.unwrap_err();
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert_eq!(
+            err.to_string(),
+            "Tool error: plugin tool 'test_tool' timed out after 1s"
+        );
+
+        // The process was waited on, so no zombie entry remains.
+        let pid = std::fs::read_to_string(&pid_file).unwrap();
+        let proc_dir = std::path::Path::new("/proc").join(pid.trim());
+        if std::path::Path::new("/proc/self").exists() {
+            assert!(!proc_dir.exists(), "child {} was not reaped", pid.trim());
+        }
+    }
+
     #[tokio::test]
     async fn test_execute_failure() {
         let def = test_def("false");
@@ -329,6 +678,21 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_execute_stops_when_cancelled() {
+        let def = test_def("sleep 5");
+        let tool = PluginTool::new(def, "test-plugin");
+        let token = tokio_util::sync::CancellationToken::new();
+        let ctx = ToolContext::new().with_cancellation(token.clone());
+        token.cancel();
+
+        let start = std::time::Instant::now();
+        let result = tool.execute(json!({}), &ctx).await;
+        let err = result.unwrap_err().to_string();
+        assert!(err.contains("cancelled"), "unexpected error: {}", err);
+        assert!(start.elapsed() < Duration::from_secs(4));
+    }
+
     #[tokio::test]
     async fn test_execute_with_env() {
         let mut env = HashMap::new();
@@ -337,10 +701,11 @@ mod tests {
             name: "env_tool".to_string(),
             description: "Tests env".to_string(),
             parameters: json!({}),
-            command: "echo $MY_VAR".to_string(),
+            command: "printenv MY_VAR".to_string(),
             working_dir: None,
             timeout_secs: Some(5),
             env: Some(env),
+            output_format: PluginOutputFormat::Text,
         };
         let tool = PluginTool::new(def, "test-plugin");
         let ctx = ToolContext::new();
@@ -348,4 +713,181 @@ mod tests {
         assert!(result.is_ok());
         assert_eq!(result.unwrap().trim(), "test_value");
     }
+
+    fn format_def(command: &str, output_format: PluginOutputFormat) -> PluginToolDef {
+        PluginToolDef {
+            output_format,
+            ..test_def(command)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_output_format_text() {
+        let tool = PluginTool::new(
+            format_def("printf '  a\n\nb  \n'", PluginOutputFormat::Text),
+            "test-plugin",
+        );
+        let result = tool.execute(json!({}), &ToolContext::new()).await.unwrap();
+        assert_eq!(result, "  a\n\nb  \n");
+    }
+
+    #[tokio::test]
+    async fn test_execute_output_format_json() {
+        let tool = PluginTool::new(
+            format_def(
+                r#"printf '{ "status": "ok", "count": 2 }\n'"#,
+                PluginOutputFormat::Json,
+            ),
+            "test-plugin",
+        );
+        let result = tool.execute(json!({}), &ToolContext::new()).await.unwrap();
+        let value: Value = serde_json::from_str(&result).unwrap();
+        assert_eq!(value, json!({"status": "ok", "count": 2}));
+    }
+
+    #[tokio::test]
+    async fn test_execute_output_format_json_rejects_malformed() {
+        let tool = PluginTool::new(
+            format_def("printf '{not json'", PluginOutputFormat::Json),
+            "test-plugin",
+        );
+        let err = tool
+            .execute(json!({}), &ToolContext::new())
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("not valid JSON"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_execute_output_format_lines() {
+        let tool = PluginTool::new(
+            format_def(
+                "printf '  first \n\n second\n   \nthird'",
+                PluginOutputFormat::Lines,
+            ),
+            "test-plugin",
+        );
+        let result = tool.execute(json!({}), &ToolContext::new()).await.unwrap();
+        let value: Value = serde_json::from_str(&result).unwrap();
+        assert_eq!(value, json!(["first", "second", "third"]));
+    }
+
+    #[test]
+    fn test_format_output_lines_empty() {
+        assert_eq!(
+            format_output("t", PluginOutputFormat::Lines, "\n  \n").unwrap(),
+            "[]"
+        );
+    }
+
+    fn allowed(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_render_command_with_env_resolves_allowed_vars() {
+        std::env::set_var("ZEPTO_PLUGIN_TEST_HOST", "example.com");
+        let argv = render_command_with_env(
+            "curl https://${ZEPTO_PLUGIN_TEST_HOST}/{{path}}",
+            &json!({"path": "status"}),
+            &allowed(&["ZEPTO_PLUGIN_TEST_HOST"]),
+        )
+        .unwrap();
+        assert_eq!(argv, vec!["curl", "https://example.com/status"]);
+    }
+
+    #[test]
+    fn test_render_command_with_env_requires_allowlist() {
+        std::env::set_var("ZEPTO_PLUGIN_TEST_SECRET", "hunter2");
+        let err = render_command_with_env("echo ${ZEPTO_PLUGIN_TEST_SECRET}", &json!({}), &[])
+            .unwrap_err();
+        assert!(
+            err.contains("not listed in the plugin's allowed_env"),
+            "{}",
+            err
+        );
+
+        let err = render_command_with_env(
+            "echo ${ZEPTO_PLUGIN_TEST_UNSET}",
+            &json!({}),
+            &allowed(&["ZEPTO_PLUGIN_TEST_UNSET"]),
+        )
+        .unwrap_err();
+        assert!(
+            err.contains("'ZEPTO_PLUGIN_TEST_UNSET' is not set"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_render_command_with_env_does_not_expand_values() {
+        std::env::set_var("ZEPTO_PLUGIN_TEST_BRACES", "{{msg}}");
+        let allowed_env = allowed(&["ZEPTO_PLUGIN_TEST_BRACES", "ZEPTO_PLUGIN_TEST_HOST"]);
+        // An argument value that looks like an env reference stays literal
+        let argv = render_command_with_env(
+            "echo {{msg}}",
+            &json!({"msg": "${ZEPTO_PLUGIN_TEST_HOST}"}),
+            &allowed_env,
+        )
+        .unwrap();
+        assert_eq!(argv, vec!["echo", "${ZEPTO_PLUGIN_TEST_HOST}"]);
+        // An env value that looks like a placeholder stays literal
+        let argv = render_command_with_env(
+            "echo ${ZEPTO_PLUGIN_TEST_BRACES}",
+            &json!({"msg": "injected"}),
+            &allowed_env,
+        )
+        .unwrap();
+        assert_eq!(argv, vec!["echo", "{{msg}}"]);
+    }
+
+    #[tokio::test]
+    async fn test_execute_interpolates_env_values() {
+        std::env::set_var("ZEPTO_PLUGIN_TEST_TOKEN", "tok-123");
+        let mut env = HashMap::new();
+        env.insert(
+            "API_TOKEN".to_string(),
+            "Bearer ${ZEPTO_PLUGIN_TEST_TOKEN}".to_string(),
+        );
+        let def = PluginToolDef {
+            env: Some(env),
+            ..test_def("printenv API_TOKEN")
+        };
+
+        let tool = PluginTool::new(def.clone(), "test-plugin")
+            .with_allowed_env(allowed(&["ZEPTO_PLUGIN_TEST_TOKEN"]));
+        let result = tool.execute(json!({}), &ToolContext::new()).await.unwrap();
+        assert_eq!(result.trim(), "Bearer tok-123");
+
+        let err = PluginTool::new(def, "test-plugin")
+            .execute(json!({}), &ToolContext::new())
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("ZEPTO_PLUGIN_TEST_TOKEN"), "{}", err);
+        assert!(err.contains("allowed_env"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_execute_dry_run_returns_argv() {
+        let dir = tempfile::tempdir().unwrap();
+        let marker = dir.path().join("ran");
+        let tool = PluginTool::new(test_def("touch {{path}}"), "test-plugin");
+        let ctx = ToolContext::new().with_dry_run(true);
+
+        let result = tool
+            .execute(json!({"path": marker.to_str().unwrap()}), &ctx)
+            .await
+            .unwrap();
+        assert_eq!(
+            result,
+            format!(
+                "[DRY RUN] Would run plugin tool 'test_tool': {}",
+                json!(["touch", marker.to_str().unwrap()])
+            )
+        );
+        assert!(!marker.exists());
+    }
 }
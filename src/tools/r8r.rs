@@ -773,8 +773,7 @@ mod tests {
             .await;
 
         // If r8r is running and workflow exists, should succeed
-        if result.is_ok() {
-            let output = result.unwrap();
+        if let Ok(output) = result {
             assert!(output.contains("completed") || output.contains("Execution ID"));
         }
     }
@@ -6,13 +6,30 @@
 use std::collections::HashMap;
 use std::time::Instant;
 
+use serde::Serialize;
 use serde_json::Value;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 use crate::error::{Result, ZeptoError};
 use crate::providers::ToolDefinition;
 
-use super::{Tool, ToolContext};
+use super::{Tool, ToolContext, ToolSource};
+
+/// Summary of a registered tool, as returned by
+/// [`ToolRegistry::describe_all`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ToolDescription {
+    /// Tool name.
+    pub name: String,
+    /// Full description sent to the LLM.
+    pub description: String,
+    /// Compact description.
+    pub compact_description: String,
+    /// Where the tool comes from.
+    pub source: ToolSource,
+    /// JSON schema of the tool's parameters.
+    pub parameters: Value,
+}
 
 /// A registry that holds and manages tools.
 ///
@@ -76,6 +93,26 @@ impl ToolRegistry {
         self.tools.insert(name, tool);
     }
 
+    /// Remove a tool from the registry.
+    ///
+    /// # Returns
+    /// The removed tool, or `None` if no tool with that name is registered.
+    ///
+    /// # Example
+    /// ```
+    /// use zeptoclaw::tools::{ToolRegistry, EchoTool};
+    ///
+    /// let mut registry = ToolRegistry::new();
+    /// registry.register(Box::new(EchoTool));
+    /// assert!(registry.unregister("echo").is_some());
+    /// assert!(!registry.has("echo"));
+    /// ```
+    pub fn unregister(&mut self, name: &str) -> Option<Box<dyn Tool>> {
+        let tool = self.tools.remove(name)?;
+        info!(tool = %name, "Unregistered tool");
+        Some(tool)
+    }
+
     /// Get a tool by name.
     ///
     /// # Arguments
@@ -99,6 +136,26 @@ impl ToolRegistry {
         self.tools.get(name).map(|t| t.as_ref())
     }
 
+    /// Whether the named tool may run concurrently with other parallel-safe
+    /// calls. Unknown tools are not.
+    pub fn is_parallel_safe(&self, name: &str) -> bool {
+        self.get(name).is_some_and(|tool| tool.is_parallel_safe())
+    }
+
+    /// Whether the named tool may actually run in dry-run mode: it honors
+    /// [`ToolContext::dry_run`](super::ToolContext::dry_run) itself or is
+    /// read-only. Unknown tools may not.
+    pub fn runs_in_dry_run(&self, name: &str) -> bool {
+        self.get(name)
+            .is_some_and(|tool| tool.supports_dry_run() || tool.is_parallel_safe())
+    }
+
+    /// How long results of the named tool may be cached. Unknown tools are
+    /// never cached.
+    pub fn cache_ttl(&self, name: &str) -> Option<std::time::Duration> {
+        self.get(name).and_then(|tool| tool.cache_ttl())
+    }
+
     /// Execute a tool by name with default context.
     ///
     /// # Arguments
@@ -135,9 +192,12 @@ impl ToolRegistry {
     /// * `args` - The JSON arguments for the tool
     /// * `ctx` - The execution context
     ///
+    /// Arguments are checked with [`Tool::validate_args`] first; a tool
+    /// whose arguments are rejected is not executed.
+    ///
     /// # Returns
-    /// The tool's output as a string, or an error if the tool is not found
-    /// or execution fails.
+    /// The tool's output as a string, or an error if the tool is not found,
+    /// its arguments are invalid, or execution fails.
     ///
     /// # Example
     /// ```
@@ -164,6 +224,11 @@ impl ToolRegistry {
             .get(name)
             .ok_or_else(|| ZeptoError::NotFound(format!("Tool not found: {}", name)))?;
 
+        if let Err(e) = tool.validate_args(&args) {
+            warn!(tool = name, error = %e, "Tool arguments rejected");
+            return Err(e);
+        }
+
         let start = Instant::now();
 
         match tool.execute(args, ctx).await {
@@ -273,6 +338,35 @@ impl ToolRegistry {
             .collect()
     }
 
+    /// Describe every registered tool, sorted by name.
+    ///
+    /// # Example
+    /// ```
+    /// use zeptoclaw::tools::{ToolRegistry, ToolSource, EchoTool};
+    ///
+    /// let mut registry = ToolRegistry::new();
+    /// registry.register(Box::new(EchoTool));
+    ///
+    /// let described = registry.describe_all();
+    /// assert_eq!(described[0].name, "echo");
+    /// assert_eq!(described[0].source, ToolSource::Builtin);
+    /// ```
+    pub fn describe_all(&self) -> Vec<ToolDescription> {
+        let mut described: Vec<ToolDescription> = self
+            .tools
+            .values()
+            .map(|t| ToolDescription {
+                name: t.name().to_string(),
+                description: t.description().to_string(),
+                compact_description: t.compact_description().to_string(),
+                source: t.source(),
+                parameters: t.parameters(),
+            })
+            .collect();
+        described.sort_by(|a, b| a.name.cmp(&b.name));
+        described
+    }
+
     /// Get the names of all registered tools.
     ///
     /// # Returns
@@ -362,7 +456,7 @@ impl Default for ToolRegistry {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::tools::EchoTool;
+    use crate::tools::{schema, EchoTool};
     use serde_json::json;
 
     #[test]
@@ -401,6 +495,40 @@ mod tests {
         assert!(missing.is_none());
     }
 
+    #[test]
+    fn test_registry_is_parallel_safe() {
+        use crate::tools::filesystem::{ReadFileTool, WriteFileTool};
+        use crate::tools::shell::ShellTool;
+
+        let mut registry = ToolRegistry::new();
+        registry.register(Box::new(ReadFileTool));
+        registry.register(Box::new(WriteFileTool));
+        registry.register(Box::new(ShellTool::new()));
+
+        assert!(registry.is_parallel_safe("read_file"));
+        assert!(!registry.is_parallel_safe("write_file"));
+        assert!(!registry.is_parallel_safe("shell"));
+        assert!(!registry.is_parallel_safe("nonexistent"));
+    }
+
+    #[test]
+    fn test_registry_runs_in_dry_run() {
+        use crate::tools::filesystem::{ReadFileTool, WriteFileTool};
+        use crate::tools::shell::ShellTool;
+
+        let mut registry = ToolRegistry::new();
+        registry.register(Box::new(ReadFileTool));
+        registry.register(Box::new(WriteFileTool));
+        registry.register(Box::new(ShellTool::new()));
+        registry.register(Box::new(RemoteTool));
+
+        assert!(registry.runs_in_dry_run("read_file"));
+        assert!(registry.runs_in_dry_run("write_file"));
+        assert!(registry.runs_in_dry_run("shell"));
+        assert!(!registry.runs_in_dry_run("remote_lookup"));
+        assert!(!registry.runs_in_dry_run("nonexistent"));
+    }
+
     #[tokio::test]
     async fn test_registry_register_and_execute() {
         let mut registry = ToolRegistry::new();
@@ -471,9 +599,16 @@ mod tests {
         let mut registry = ToolRegistry::new();
         registry.register(Box::new(EchoTool));
 
-        // Execute without message argument - should return default
-        let result = registry.execute("echo", json!({})).await;
-        assert!(result.is_ok());
+        // The registry rejects the call before the tool runs
+        let err = registry.execute("echo", json!({})).await.unwrap_err();
+        assert!(matches!(err, ZeptoError::Tool(_)));
+        assert_eq!(
+            err.to_string(),
+            "Tool error: Invalid arguments for tool 'echo': Missing required parameter 'message'"
+        );
+
+        // Called directly, the tool still falls back to its default
+        let result = EchoTool.execute(json!({}), &ToolContext::default()).await;
         assert_eq!(result.unwrap(), "(no message)");
     }
 
@@ -482,10 +617,66 @@ mod tests {
         let mut registry = ToolRegistry::new();
         registry.register(Box::new(EchoTool));
 
-        // Execute with null message - should return default
-        let result = registry.execute("echo", json!({"message": null})).await;
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), "(no message)");
+        let err = registry
+            .execute("echo", json!({"message": null}))
+            .await
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("'message' must be of type string, got null"));
+    }
+
+    #[tokio::test]
+    async fn test_registry_uses_tool_validate_args_override() {
+        struct StrictTool;
+
+        #[async_trait::async_trait]
+        impl Tool for StrictTool {
+            fn name(&self) -> &str {
+                "strict"
+            }
+            fn description(&self) -> &str {
+                "Rejects empty paths"
+            }
+            fn parameters(&self) -> Value {
+                json!({
+                    "type": "object",
+                    "properties": {"path": {"type": "string"}},
+                    "required": ["path"]
+                })
+            }
+            fn validate_args(&self, args: &Value) -> Result<()> {
+                // Schema checks first, then the tool's own rule
+                schema::validate_args(&self.parameters(), args).map_err(ZeptoError::Tool)?;
+                if args["path"].as_str() == Some("") {
+                    return Err(ZeptoError::Tool("path must not be empty".into()));
+                }
+                Ok(())
+            }
+            async fn execute(&self, _args: Value, _ctx: &ToolContext) -> Result<String> {
+                Ok("ran".into())
+            }
+        }
+
+        let mut registry = ToolRegistry::new();
+        registry.register(Box::new(StrictTool));
+
+        let err = registry.execute("strict", json!({})).await.unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Missing required parameter 'path'"));
+        let err = registry
+            .execute("strict", json!({"path": ""}))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("path must not be empty"));
+        assert_eq!(
+            registry
+                .execute("strict", json!({"path": "a.txt"}))
+                .await
+                .unwrap(),
+            "ran"
+        );
     }
 
     #[test]
@@ -536,4 +727,51 @@ mod tests {
         assert_eq!(defs.len(), 1);
         assert_eq!(defs[0].description, "Echo message");
     }
+
+    struct RemoteTool;
+
+    #[async_trait::async_trait]
+    impl Tool for RemoteTool {
+        fn name(&self) -> &str {
+            "remote_lookup"
+        }
+
+        fn description(&self) -> &str {
+            "Look something up on a remote server"
+        }
+
+        fn compact_description(&self) -> &str {
+            "Remote lookup"
+        }
+
+        fn parameters(&self) -> Value {
+            json!({"type": "object", "properties": {"q": {"type": "string"}}})
+        }
+
+        fn source(&self) -> ToolSource {
+            ToolSource::Mcp
+        }
+
+        async fn execute(&self, _args: Value, _ctx: &ToolContext) -> Result<String> {
+            Ok(String::new())
+        }
+    }
+
+    #[test]
+    fn test_describe_all_sorted_with_sources() {
+        let mut registry = ToolRegistry::new();
+        registry.register(Box::new(RemoteTool));
+        registry.register(Box::new(EchoTool));
+
+        let described = registry.describe_all();
+        let names: Vec<_> = described.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, vec!["echo", "remote_lookup"]);
+        assert_eq!(described[0].source, ToolSource::Builtin);
+        assert_eq!(described[1].source, ToolSource::Mcp);
+        assert_eq!(described[1].compact_description, "Remote lookup");
+        assert_eq!(described[1].parameters["properties"]["q"]["type"], "string");
+
+        let json = serde_json::to_value(&described[1]).unwrap();
+        assert_eq!(json["source"], "mcp");
+    }
 }
@@ -0,0 +1,247 @@
+//! JavaScript rendering backends for `web_fetch` with `render: true`.
+//!
+//! Two backends are supported, in order of preference:
+//!
+//! 1. An external rendering service (`tools.web.fetch.render_endpoint`).
+//!    The page URL is POSTed as `{"url": "..."}` and the response body is the
+//!    rendered HTML, as served by browserless' `/content` endpoint. An
+//!    `X-Final-Url` response header, if present, reports the URL after
+//!    redirects.
+//! 2. A local headless Chromium, when `tools.web.fetch.render_chromium` is
+//!    set, the crate is built with the `screenshot` feature and a
+//!    Chrome/Chromium binary is installed. The browser's own requests bypass
+//!    the URL policy and private-address checks, hence the explicit opt-in.
+//!
+//! With neither available, [`PageRenderer::render`] fails and the caller
+//! falls back to a plain HTTP fetch.
+
+use std::time::Duration;
+
+use reqwest::{Client, Url};
+use serde_json::json;
+
+use crate::config::WebFetchConfig;
+use crate::error::{Result, ZeptoError};
+
+use super::web::{read_body_limited, WEB_USER_AGENT};
+
+/// Response header carrying the final URL from a rendering service.
+const FINAL_URL_HEADER: &str = "x-final-url";
+
+/// HTML of a page after its scripts have run.
+#[derive(Debug, Clone)]
+pub(crate) struct RenderedPage {
+    /// URL after redirects and client-side navigation
+    pub final_url: String,
+    /// Serialized post-JavaScript DOM
+    pub html: String,
+}
+
+#[derive(Debug, Clone)]
+enum RenderBackend {
+    /// External rendering service
+    Service { client: Client, endpoint: String },
+    /// Local headless Chromium
+    #[cfg(feature = "screenshot")]
+    Chromium { no_sandbox: bool },
+    /// Nothing configured or compiled in
+    Unavailable,
+}
+
+/// Renders JavaScript-heavy pages through the best available backend.
+#[derive(Debug, Clone)]
+pub(crate) struct PageRenderer {
+    backend: RenderBackend,
+    timeout: Duration,
+}
+
+impl Default for PageRenderer {
+    fn default() -> Self {
+        Self::from_config(&WebFetchConfig::default())
+    }
+}
+
+impl PageRenderer {
+    /// Use `render_endpoint` if set, otherwise local Chromium when opted in
+    /// with `render_chromium` and compiled in.
+    pub(crate) fn from_config(config: &WebFetchConfig) -> Self {
+        let timeout = Duration::from_secs(config.render_timeout_secs.max(1));
+        let endpoint = config
+            .render_endpoint
+            .as_deref()
+            .map(str::trim)
+            .filter(|e| !e.is_empty());
+        let backend = match endpoint {
+            Some(endpoint) => RenderBackend::Service {
+                client: Client::new(),
+                endpoint: endpoint.to_string(),
+            },
+            #[cfg(feature = "screenshot")]
+            None if config.render_chromium => RenderBackend::Chromium {
+                no_sandbox: config.render_chromium_no_sandbox,
+            },
+            None => RenderBackend::Unavailable,
+        };
+        Self { backend, timeout }
+    }
+
+    /// Render `url` and return its post-JavaScript HTML.
+    pub(crate) async fn render(&self, url: &Url, max_bytes: usize) -> Result<RenderedPage> {
+        let render = async {
+            match &self.backend {
+                RenderBackend::Service { client, endpoint } => {
+                    render_with_service(client, endpoint, url, max_bytes).await
+                }
+                #[cfg(feature = "screenshot")]
+                RenderBackend::Chromium { no_sandbox } => {
+                    render_with_chromium(url, *no_sandbox).await
+                }
+                RenderBackend::Unavailable => Err(ZeptoError::Tool(
+                    "No rendering backend: set tools.web.fetch.render_endpoint, or \
+                     tools.web.fetch.render_chromium in a build with the `screenshot` feature"
+                        .to_string(),
+                )),
+            }
+        };
+        tokio::time::timeout(self.timeout, render)
+            .await
+            .map_err(|_| {
+                ZeptoError::Tool(format!(
+                    "Rendering timed out after {}s",
+                    self.timeout.as_secs()
+                ))
+            })?
+    }
+}
+
+async fn render_with_service(
+    client: &Client,
+    endpoint: &str,
+    url: &Url,
+    max_bytes: usize,
+) -> Result<RenderedPage> {
+    let response = client
+        .post(endpoint)
+        .header("User-Agent", WEB_USER_AGENT)
+        .json(&json!({ "url": url.as_str() }))
+        .send()
+        .await
+        .map_err(|e| ZeptoError::Tool(format!("Render service request failed: {}", e)))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        return Err(ZeptoError::Tool(format!(
+            "Render service returned HTTP {}",
+            status
+        )));
+    }
+    let final_url = response
+        .headers()
+        .get(FINAL_URL_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string)
+        .unwrap_or_else(|| url.to_string());
+    let html = read_body_limited(response, max_bytes).await?;
+    Ok(RenderedPage { final_url, html })
+}
+
+#[cfg(feature = "screenshot")]
+async fn render_with_chromium(url: &Url, no_sandbox: bool) -> Result<RenderedPage> {
+    use chromiumoxide::browser::{Browser, BrowserConfig};
+    use futures::StreamExt;
+
+    let mut builder = BrowserConfig::builder()
+        .arg("--disable-gpu")
+        .arg("--disable-dev-shm-usage");
+    if no_sandbox {
+        builder = builder.no_sandbox();
+    }
+    let config = builder
+        .build()
+        .map_err(|e| ZeptoError::Tool(format!("Failed to configure browser: {}", e)))?;
+    let (browser, mut handler) = Browser::launch(config)
+        .await
+        .map_err(|e| ZeptoError::Tool(format!("Failed to launch browser: {}", e)))?;
+    let handler_handle = tokio::spawn(async move { while handler.next().await.is_some() {} });
+
+    let result = async {
+        let page = browser
+            .new_page(url.as_str())
+            .await
+            .map_err(|e| ZeptoError::Tool(format!("Failed to open page: {}", e)))?;
+        page.wait_for_navigation()
+            .await
+            .map_err(|e| ZeptoError::Tool(format!("Page navigation failed: {}", e)))?;
+        let html = page
+            .content()
+            .await
+            .map_err(|e| ZeptoError::Tool(format!("Failed to read page content: {}", e)))?;
+        let final_url = page
+            .url()
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or_else(|| url.to_string());
+        Ok(RenderedPage { final_url, html })
+    }
+    .await;
+
+    drop(browser);
+    handler_handle.abort();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::mock_http::{self, request_json, MockResponse};
+
+    fn service(endpoint: &str) -> PageRenderer {
+        PageRenderer::from_config(&WebFetchConfig {
+            render_endpoint: Some(endpoint.to_string()),
+            render_timeout_secs: 5,
+            ..Default::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn test_service_backend_posts_url_and_reads_final_url() {
+        let (base, requests) = mock_http::serve(vec![MockResponse::stream(
+            "text/html",
+            "<html><body><p>Rendered</p></body></html>",
+        )
+        .with_header("X-Final-Url", "https://example.com/app/home")])
+        .await;
+
+        let renderer = service(&format!("{}/content", base));
+        let url = Url::parse("https://example.com/app").unwrap();
+        let page = renderer.render(&url, 1024).await.unwrap();
+        assert_eq!(page.final_url, "https://example.com/app/home");
+        assert!(page.html.contains("Rendered"));
+
+        let requests = requests.await.unwrap();
+        assert!(requests[0].starts_with("POST /content"));
+        assert_eq!(
+            request_json(&requests[0]),
+            json!({"url": "https://example.com/app"})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_service_backend_http_error() {
+        let (base, _) = mock_http::serve(vec![MockResponse::json(502, "{}")]).await;
+        let renderer = service(&base);
+        let url = Url::parse("https://example.com").unwrap();
+        let err = renderer.render(&url, 1024).await.unwrap_err();
+        assert!(err.to_string().contains("HTTP 502"));
+    }
+
+    #[tokio::test]
+    async fn test_no_backend_is_an_error() {
+        // Without an endpoint, local Chromium still needs `render_chromium`.
+        let renderer = service("  ");
+        let url = Url::parse("https://example.com").unwrap();
+        let err = renderer.render(&url, 1024).await.unwrap_err();
+        assert!(err.to_string().contains("No rendering backend"));
+    }
+}
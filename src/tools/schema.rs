@@ -0,0 +1,244 @@
+//! Minimal JSON Schema checking for tool arguments.
+//!
+//! Covers the subset of JSON Schema that tool `parameters` declarations use
+//! in practice: `type` (single or list), `required`, `properties`,
+//! `additionalProperties: false`, `enum`, `items`, `minimum`/`maximum` and
+//! `minLength`/`maxLength`. Unknown keywords are ignored, so a schema this
+//! checker does not understand never rejects arguments.
+
+use serde_json::Value;
+
+/// Check `args` against `schema`.
+///
+/// Returns a human-readable description of the first violation found,
+/// e.g. `'count' must be of type integer, got string`.
+pub fn validate_args(schema: &Value, args: &Value) -> Result<(), String> {
+    check(schema, args, "")
+}
+
+fn check(schema: &Value, value: &Value, path: &str) -> Result<(), String> {
+    let Some(schema) = schema.as_object() else {
+        return Ok(());
+    };
+    let label = if path.is_empty() {
+        "arguments".to_string()
+    } else {
+        format!("'{}'", path)
+    };
+
+    if let Some(expected) = schema.get("type") {
+        let allowed: Vec<&str> = match expected {
+            Value::String(t) => vec![t.as_str()],
+            Value::Array(types) => types.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !allowed.is_empty() && !allowed.iter().any(|t| matches_type(t, value)) {
+            return Err(format!(
+                "{} must be of type {}, got {}",
+                label,
+                allowed.join(" or "),
+                type_name(value)
+            ));
+        }
+    }
+
+    if let Some(options) = schema.get("enum").and_then(Value::as_array) {
+        if !options.contains(value) {
+            let options: Vec<String> = options.iter().map(Value::to_string).collect();
+            return Err(format!("{} must be one of [{}]", label, options.join(", ")));
+        }
+    }
+
+    match value {
+        Value::Object(obj) => {
+            if let Some(required) = schema.get("required").and_then(Value::as_array) {
+                for key in required.iter().filter_map(Value::as_str) {
+                    if !obj.contains_key(key) {
+                        return Err(format!(
+                            "Missing required parameter '{}'",
+                            join_path(path, key)
+                        ));
+                    }
+                }
+            }
+
+            let properties = schema.get("properties").and_then(Value::as_object);
+            let closed = schema.get("additionalProperties") == Some(&Value::Bool(false));
+            for (key, item) in obj {
+                match properties.and_then(|props| props.get(key)) {
+                    Some(prop_schema) => check(prop_schema, item, &join_path(path, key))?,
+                    None if closed => {
+                        return Err(format!("Unexpected parameter '{}'", join_path(path, key)));
+                    }
+                    None => {}
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(item_schema) = schema.get("items") {
+                for (index, item) in items.iter().enumerate() {
+                    check(item_schema, item, &format!("{}[{}]", path, index))?;
+                }
+            }
+        }
+        Value::Number(number) => {
+            let n = number.as_f64().unwrap_or_default();
+            if let Some(min) = schema.get("minimum").and_then(Value::as_f64) {
+                if n < min {
+                    return Err(format!("{} must be >= {}", label, min));
+                }
+            }
+            if let Some(max) = schema.get("maximum").and_then(Value::as_f64) {
+                if n > max {
+                    return Err(format!("{} must be <= {}", label, max));
+                }
+            }
+        }
+        Value::String(s) => {
+            let len = s.chars().count() as u64;
+            if let Some(min) = schema.get("minLength").and_then(Value::as_u64) {
+                if len < min {
+                    return Err(format!("{} must be at least {} characters", label, min));
+                }
+            }
+            if let Some(max) = schema.get("maxLength").and_then(Value::as_u64) {
+                if len > max {
+                    return Err(format!("{} must be at most {} characters", label, max));
+                }
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+fn matches_type(expected: &str, value: &Value) -> bool {
+    match expected {
+        "string" => value.is_string(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "number" => value.is_number(),
+        "boolean" => value.is_boolean(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        "null" => value.is_null(),
+        // Unknown type names are not enforced.
+        _ => true,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_i64() || n.is_u64() => "integer",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn join_path(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "count": { "type": "integer", "minimum": 1, "maximum": 10 },
+                "mode": { "type": "string", "enum": ["fast", "slow"] },
+                "ratio": { "type": "number" },
+                "tags": { "type": "array", "items": { "type": "string" } },
+                "name": { "type": ["string", "null"], "maxLength": 5 },
+                "nested": {
+                    "type": "object",
+                    "properties": { "flag": { "type": "boolean" } },
+                    "required": ["flag"]
+                }
+            },
+            "required": ["count"]
+        })
+    }
+
+    #[test]
+    fn test_valid_args_pass() {
+        let args = json!({
+            "count": 3,
+            "mode": "fast",
+            "ratio": 1,
+            "tags": ["a", "b"],
+            "name": null,
+            "nested": { "flag": true },
+            "extra": "allowed"
+        });
+        assert_eq!(validate_args(&schema(), &args), Ok(()));
+    }
+
+    #[test]
+    fn test_missing_required() {
+        let err = validate_args(&schema(), &json!({})).unwrap_err();
+        assert_eq!(err, "Missing required parameter 'count'");
+
+        let err = validate_args(&schema(), &json!({"count": 1, "nested": {}})).unwrap_err();
+        assert_eq!(err, "Missing required parameter 'nested.flag'");
+    }
+
+    #[test]
+    fn test_wrong_types() {
+        let err = validate_args(&schema(), &json!({"count": "1; rm -rf /"})).unwrap_err();
+        assert_eq!(err, "'count' must be of type integer, got string");
+
+        let err = validate_args(&schema(), &json!({"count": 1.5})).unwrap_err();
+        assert_eq!(err, "'count' must be of type integer, got number");
+
+        let err = validate_args(&schema(), &json!({"count": 1, "tags": ["a", 2]})).unwrap_err();
+        assert_eq!(err, "'tags[1]' must be of type string, got integer");
+
+        let err = validate_args(&schema(), &json!({"count": 1, "name": 5})).unwrap_err();
+        assert_eq!(err, "'name' must be of type string or null, got integer");
+
+        let err = validate_args(&schema(), &json!("not an object")).unwrap_err();
+        assert_eq!(err, "arguments must be of type object, got string");
+    }
+
+    #[test]
+    fn test_enum_and_bounds() {
+        let err = validate_args(&schema(), &json!({"count": 1, "mode": "medium"})).unwrap_err();
+        assert_eq!(err, "'mode' must be one of [\"fast\", \"slow\"]");
+
+        let err = validate_args(&schema(), &json!({"count": 11})).unwrap_err();
+        assert_eq!(err, "'count' must be <= 10");
+
+        let err = validate_args(&schema(), &json!({"count": 1, "name": "toolong"})).unwrap_err();
+        assert_eq!(err, "'name' must be at most 5 characters");
+    }
+
+    #[test]
+    fn test_additional_properties_false() {
+        let schema = json!({
+            "type": "object",
+            "properties": { "a": { "type": "string" } },
+            "additionalProperties": false
+        });
+        let err = validate_args(&schema, &json!({"a": "x", "b": 1})).unwrap_err();
+        assert_eq!(err, "Unexpected parameter 'b'");
+    }
+
+    #[test]
+    fn test_empty_or_unknown_schema_accepts_anything() {
+        assert!(validate_args(&json!({}), &json!({"anything": [1, 2]})).is_ok());
+        assert!(validate_args(&Value::Null, &json!(42)).is_ok());
+        assert!(validate_args(&json!({"type": "custom"}), &json!(42)).is_ok());
+    }
+}
@@ -1,8 +1,12 @@
 //! Web screenshot tool (feature-gated behind `screenshot`).
 //!
 //! Captures screenshots of web pages using a headless Chromium browser
-//! via the Chrome DevTools Protocol. Includes full SSRF protection by
-//! reusing the validation from [`super::web`].
+//! via the Chrome DevTools Protocol. URLs go through the shared
+//! [`url_policy`](crate::utils::url_policy) and the DNS check from
+//! [`super::web`]. The tool is not registered by the CLI; callers that build
+//! it should pass `tools.url_policy` via
+//! [`with_url_policy`](WebScreenshotTool::with_url_policy), otherwise the
+//! default policy applies.
 
 use std::time::Duration;
 
@@ -12,13 +16,14 @@ use chromiumoxide::browser::{Browser, BrowserConfig};
 use chromiumoxide::handler::viewport::Viewport;
 use chromiumoxide::page::ScreenshotParams;
 use futures::StreamExt;
-use reqwest::Url;
 use serde_json::{json, Value};
 use tokio::time::timeout;
 
 use crate::error::{Result, ZeptoError};
 
-use super::web::{is_blocked_host, resolve_and_check_host};
+use crate::utils::url_policy::{validate_url, UrlPolicy};
+
+use super::web::resolve_and_check_host;
 use super::{Tool, ToolContext};
 
 /// Default page-load timeout in seconds.
@@ -44,12 +49,22 @@ const MAX_DIMENSION: u32 = 3840;
 /// Uses a headless Chromium browser via the Chrome DevTools Protocol.
 /// Applies the same SSRF protections as the web fetch tool to prevent
 /// screenshots of internal/private network resources.
-pub struct WebScreenshotTool;
+pub struct WebScreenshotTool {
+    url_policy: UrlPolicy,
+}
 
 impl WebScreenshotTool {
     /// Create a new web screenshot tool.
     pub fn new() -> Self {
-        Self
+        Self {
+            url_policy: UrlPolicy::default(),
+        }
+    }
+
+    /// Apply `tools.url_policy` to requested URLs.
+    pub fn with_url_policy(mut self, url_policy: UrlPolicy) -> Self {
+        self.url_policy = url_policy;
+        self
     }
 }
 
@@ -117,25 +132,8 @@ impl Tool for WebScreenshotTool {
             .filter(|s| !s.is_empty())
             .ok_or_else(|| ZeptoError::Tool("Missing or empty 'url' parameter".to_string()))?;
 
-        let parsed = Url::parse(url_str)
-            .map_err(|e| ZeptoError::Tool(format!("Invalid URL '{}': {}", url_str, e)))?;
-
-        match parsed.scheme() {
-            "http" | "https" => {}
-            other => {
-                return Err(ZeptoError::Tool(format!(
-                    "Only http/https URLs are allowed, got '{}'",
-                    other
-                )));
-            }
-        }
-
-        // ---- SSRF protection ----
-        if is_blocked_host(&parsed) {
-            return Err(ZeptoError::SecurityViolation(
-                "Blocked URL host (local or private network)".to_string(),
-            ));
-        }
+        // ---- URL policy and SSRF protection ----
+        let parsed = validate_url(url_str, &self.url_policy)?;
         resolve_and_check_host(&parsed).await?;
 
         // ---- Parse optional parameters ----
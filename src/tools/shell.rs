@@ -7,9 +7,12 @@ use async_trait::async_trait;
 use serde_json::{json, Value};
 use std::path::PathBuf;
 use std::sync::Arc;
+use tracing::debug;
 
 use crate::error::{Result, ZeptoError};
-use crate::runtime::{ContainerConfig, ContainerRuntime, NativeRuntime};
+use crate::runtime::{
+    CommandOutput, ContainerConfig, ContainerRuntime, NativeRuntime, OutputChunk,
+};
 use crate::security::ShellSecurityConfig;
 
 use super::{Tool, ToolContext};
@@ -17,7 +20,8 @@ use super::{Tool, ToolContext};
 /// Tool for executing shell commands.
 ///
 /// Executes a shell command and returns the combined stdout and stderr output.
-/// Commands are run using `sh -c` for shell interpretation.
+/// Commands are run using `sh -c` for shell interpretation. In dry-run mode
+/// the argv that would run is returned instead.
 ///
 /// # Parameters
 /// - `command`: The shell command to execute (required)
@@ -137,6 +141,10 @@ impl Tool for ShellTool {
         })
     }
 
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<String> {
         let command = args
             .get("command")
@@ -148,6 +156,17 @@ impl Tool for ShellTool {
 
         let timeout_secs = args.get("timeout").and_then(|v| v.as_u64()).unwrap_or(60);
 
+        if ctx.dry_run {
+            let argv = json!(["sh", "-c", command]);
+            return Ok(format!(
+                "[DRY RUN] Would run {} in the {} runtime (workdir: {}, timeout: {}s)",
+                argv,
+                self.runtime.name(),
+                ctx.workspace.as_deref().unwrap_or("current directory"),
+                timeout_secs
+            ));
+        }
+
         // Build container configuration
         let mut container_config = ContainerConfig::new().with_timeout(timeout_secs);
 
@@ -159,13 +178,47 @@ impl Tool for ShellTool {
                 .with_mount(workspace_path.clone(), workspace_path, false);
         }
 
-        // Execute command via runtime
-        let output = self
+        // Stream output via runtime so progress is visible as it arrives
+        // and a cancelled turn stops the command (dropping the receiver kills it)
+        let mut rx = self
             .runtime
-            .execute(command, &container_config)
+            .execute_streaming(command, &container_config)
             .await
             .map_err(|e| ZeptoError::Tool(e.to_string()))?;
 
+        let mut output = CommandOutput::new(String::new(), String::new(), None);
+        loop {
+            let chunk = tokio::select! {
+                chunk = rx.recv() => chunk,
+                _ = ctx.cancelled() => {
+                    return Err(ZeptoError::Tool("Shell command cancelled".into()));
+                }
+            };
+            match chunk {
+                Some(OutputChunk::Stdout(line)) => {
+                    debug!("stdout: {}", line);
+                    output.stdout.push_str(&line);
+                    output.stdout.push('\n');
+                }
+                Some(OutputChunk::Stderr(line)) => {
+                    debug!("stderr: {}", line);
+                    output.stderr.push_str(&line);
+                    output.stderr.push('\n');
+                }
+                Some(OutputChunk::Exit(result)) => {
+                    output
+                        .finish(result)
+                        .map_err(|e| ZeptoError::Tool(e.to_string()))?;
+                    break;
+                }
+                None => {
+                    return Err(ZeptoError::Tool(
+                        "Shell command ended without an exit status".into(),
+                    ))
+                }
+            }
+        }
+
         Ok(output.format())
     }
 }
@@ -426,6 +479,44 @@ mod tests {
         assert!(tool.security_config.enabled);
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_shell_cancel_kills_silent_command() {
+        let dir = tempdir().unwrap();
+        let pid_file = dir.path().join("pid");
+        let tool = ShellTool::new();
+        let token = tokio_util::sync::CancellationToken::new();
+        let ctx = ToolContext::new().with_cancellation(token.clone());
+
+        // The command prints nothing, so only cancellation can stop it.
+        let command = format!("echo $$ > {}; exec sleep 30", pid_file.display());
+        let run = tool.execute(json!({ "command": command }), &ctx);
+        let cancel = async {
+            while !pid_file.exists() {
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            token.cancel();
+        };
+        let (result, ()) = tokio::join!(run, cancel);
+        assert!(result.unwrap_err().to_string().contains("cancelled"));
+
+        let pid: libc::pid_t = std::fs::read_to_string(&pid_file)
+            .unwrap()
+            .trim()
+            .parse()
+            .unwrap();
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(2);
+        // SAFETY: signal 0 only checks whether the process exists.
+        while unsafe { libc::kill(pid, 0) } == 0 {
+            assert!(
+                std::time::Instant::now() < deadline,
+                "cancelled command is still running"
+            );
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+    }
+
     #[test]
     fn test_shell_tool_runtime_name() {
         let tool = ShellTool::new();
@@ -470,4 +561,31 @@ mod tests {
         let tool = ShellTool::permissive();
         assert_eq!(tool.runtime_name(), "native");
     }
+
+    #[tokio::test]
+    async fn test_shell_dry_run_reports_argv_without_running() {
+        let dir = tempdir().unwrap();
+        let marker = dir.path().join("ran");
+        let command = format!("touch {}", marker.display());
+        let tool = ShellTool::new();
+        let ctx = ToolContext::new()
+            .with_workspace(dir.path().to_str().unwrap())
+            .with_dry_run(true);
+
+        let result = tool
+            .execute(json!({"command": command}), &ctx)
+            .await
+            .unwrap();
+        assert!(result.starts_with("[DRY RUN]"), "{}", result);
+        assert!(
+            result.contains(&json!(["sh", "-c", command]).to_string()),
+            "{}",
+            result
+        );
+        assert!(!marker.exists());
+
+        // Blocked commands are still rejected in dry-run mode
+        let blocked = tool.execute(json!({"command": "rm -rf /"}), &ctx).await;
+        assert!(blocked.is_err());
+    }
 }
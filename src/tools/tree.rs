@@ -0,0 +1,512 @@
+//! Workspace directory tree tool.
+//!
+//! Renders a depth-limited tree of the workspace with file sizes, so the
+//! agent can get an overview of a project in one call instead of walking it
+//! with repeated `list_dir` calls.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use regex::Regex;
+use serde_json::{json, Value};
+
+use crate::error::{Result, ZeptoError};
+use crate::security::validate_path_in_workspace;
+
+use super::grep::{glob_to_regex, SKIP_DIRS};
+use super::{Tool, ToolContext};
+
+/// Levels shown when `max_depth` is not given.
+const DEFAULT_MAX_DEPTH: usize = 3;
+
+/// Upper bound for `max_depth`.
+const MAX_DEPTH_LIMIT: usize = 10;
+
+/// Entries shown when `max_entries` is not given.
+const DEFAULT_MAX_ENTRIES: usize = 200;
+
+/// Upper bound for `max_entries`.
+const MAX_ENTRIES_LIMIT: usize = 1000;
+
+/// Total output size, in bytes, before the tree is cut off.
+const MAX_OUTPUT_BYTES: usize = 32 * 1024;
+
+/// Tool for rendering the workspace as a directory tree.
+///
+/// # Parameters
+/// - `path`: Directory to start from, relative to the workspace (optional,
+///   default: the workspace root)
+/// - `max_depth`: Levels below `path` to show (optional, default: 3)
+/// - `max_entries`: Maximum files and directories to list (optional,
+///   default: 200)
+///
+/// Entries matched by `.gitignore` files and the `.git`, `node_modules` and
+/// `target` directories are left out. Symlinks are listed with a trailing
+/// `@` but not followed.
+///
+/// # Example
+/// ```rust
+/// use zeptoclaw::tools::{Tool, ToolContext};
+/// use zeptoclaw::tools::TreeTool;
+/// use serde_json::json;
+///
+/// # tokio_test::block_on(async {
+/// let dir = tempfile::tempdir().unwrap();
+/// std::fs::create_dir(dir.path().join("src")).unwrap();
+/// std::fs::write(dir.path().join("src/main.rs"), "fn main() {}\n").unwrap();
+/// let ctx = ToolContext::new().with_workspace(dir.path().to_str().unwrap());
+///
+/// let result = TreeTool.execute(json!({}), &ctx).await.unwrap();
+/// assert_eq!(result, "./\n  src/\n    main.rs (13 B)");
+/// # });
+/// ```
+pub struct TreeTool;
+
+#[async_trait]
+impl Tool for TreeTool {
+    fn name(&self) -> &str {
+        "tree"
+    }
+
+    fn description(&self) -> &str {
+        "Show a directory tree of the workspace with file sizes. \
+         Skips .gitignore'd files; use max_depth and path to zoom in."
+    }
+
+    fn compact_description(&self) -> &str {
+        "Directory tree"
+    }
+
+    fn is_parallel_safe(&self) -> bool {
+        true
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Directory to start from (e.g. 'src'). Defaults to the workspace root"
+                },
+                "max_depth": {
+                    "type": "integer",
+                    "description": "Levels below path to show (default: 3, max: 10)",
+                    "minimum": 1,
+                    "maximum": MAX_DEPTH_LIMIT
+                },
+                "max_entries": {
+                    "type": "integer",
+                    "description": "Maximum number of files and directories to list (default: 200, max: 1000)",
+                    "minimum": 1,
+                    "maximum": MAX_ENTRIES_LIMIT
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<String> {
+        let max_depth = args
+            .get("max_depth")
+            .and_then(|v| v.as_u64())
+            .map(|n| (n as usize).clamp(1, MAX_DEPTH_LIMIT))
+            .unwrap_or(DEFAULT_MAX_DEPTH);
+        let max_entries = args
+            .get("max_entries")
+            .and_then(|v| v.as_u64())
+            .map(|n| (n as usize).clamp(1, MAX_ENTRIES_LIMIT))
+            .unwrap_or(DEFAULT_MAX_ENTRIES);
+        let path = args
+            .get("path")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .unwrap_or(".");
+
+        let workspace = ctx.workspace.as_ref().ok_or_else(|| {
+            ZeptoError::SecurityViolation(
+                "Workspace not configured; filesystem tools require a workspace for safety"
+                    .to_string(),
+            )
+        })?;
+
+        let root = validate_path_in_workspace(path, workspace)?.into_path_buf();
+        if !root.is_dir() {
+            return Err(ZeptoError::Tool(format!(
+                "Path '{}' is not a directory",
+                path
+            )));
+        }
+        let workspace = Path::new(workspace)
+            .canonicalize()
+            .unwrap_or_else(|_| PathBuf::from(workspace));
+
+        let tree = Tree {
+            workspace,
+            root,
+            max_depth,
+            max_entries,
+        };
+        tokio::task::spawn_blocking(move || tree.render())
+            .await
+            .map_err(|e| ZeptoError::Tool(format!("Tree task failed: {}", e)))
+    }
+}
+
+/// One resolved tree request.
+struct Tree {
+    /// Canonical workspace root; ignore rules are relative to it
+    workspace: PathBuf,
+    /// Directory the tree starts at
+    root: PathBuf,
+    max_depth: usize,
+    max_entries: usize,
+}
+
+/// Why rendering stopped early.
+enum Stop {
+    Entries,
+    Bytes,
+}
+
+/// Output being built, with the running entry count.
+struct Render {
+    output: String,
+    entries: usize,
+    max_entries: usize,
+}
+
+impl Render {
+    /// Append one line, or report which cap was hit.
+    fn push(&mut self, depth: usize, text: &str) -> std::result::Result<(), Stop> {
+        if self.entries >= self.max_entries {
+            return Err(Stop::Entries);
+        }
+        if self.output.len() + 1 + depth * 2 + text.len() > MAX_OUTPUT_BYTES {
+            return Err(Stop::Bytes);
+        }
+        self.output.push('\n');
+        self.output.push_str(&"  ".repeat(depth));
+        self.output.push_str(text);
+        self.entries += 1;
+        Ok(())
+    }
+}
+
+impl Tree {
+    fn render(&self) -> String {
+        let relative = self.relative(&self.root);
+        let mut render = Render {
+            output: if relative.is_empty() {
+                "./".to_string()
+            } else {
+                format!("{}/", relative)
+            },
+            entries: 0,
+            max_entries: self.max_entries,
+        };
+
+        // Rules from .gitignore files between the workspace and the root
+        // apply too, not just those inside the tree.
+        let mut rules = Vec::new();
+        let mut dir = self.workspace.clone();
+        load_ignore_file(&dir, "", &mut rules);
+        for component in Path::new(&relative).components() {
+            dir.push(component);
+            load_ignore_file(&dir, &self.relative(&dir), &mut rules);
+        }
+
+        match self.walk(&self.root, 1, &mut rules, &mut render) {
+            Ok(()) => {}
+            Err(Stop::Entries) => render.output.push_str(&format!(
+                "\n[Stopped after {} entries; raise max_entries or narrow the path]",
+                render.entries
+            )),
+            Err(Stop::Bytes) => render.output.push_str(&format!(
+                "\n[Output truncated at {} bytes after {} entries; lower max_depth or narrow the path]",
+                MAX_OUTPUT_BYTES, render.entries
+            )),
+        }
+        render.output
+    }
+
+    /// List `dir` at `depth`, then descend into its subdirectories while
+    /// below `max_depth`.
+    fn walk(
+        &self,
+        dir: &Path,
+        depth: usize,
+        rules: &mut Vec<IgnoreRule>,
+        render: &mut Render,
+    ) -> std::result::Result<(), Stop> {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return Ok(());
+        };
+        let mut entries: Vec<_> = entries.filter_map(|entry| entry.ok()).collect();
+        entries.sort_by_key(|entry| entry.file_name());
+
+        for entry in entries {
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let path = entry.path();
+            let relative = self.relative(&path);
+            if is_ignored(rules, &relative, file_type.is_dir()) {
+                continue;
+            }
+
+            if file_type.is_symlink() {
+                render.push(depth, &format!("{}@", name))?;
+            } else if file_type.is_dir() {
+                if SKIP_DIRS.contains(&name.as_str()) {
+                    continue;
+                }
+                render.push(depth, &format!("{}/", name))?;
+                if depth < self.max_depth {
+                    let before = rules.len();
+                    load_ignore_file(&path, &relative, rules);
+                    let result = self.walk(&path, depth + 1, rules, render);
+                    rules.truncate(before);
+                    result?;
+                }
+            } else {
+                let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+                render.push(depth, &format!("{} ({})", name, format_size(size)))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Workspace-relative path with `/` separators; empty for the root.
+    fn relative(&self, path: &Path) -> String {
+        path.strip_prefix(&self.workspace)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/")
+    }
+}
+
+/// One line of a `.gitignore` file.
+struct IgnoreRule {
+    /// Workspace-relative directory holding the `.gitignore`; empty for the root
+    base: String,
+    regex: Regex,
+    /// Pattern contains `/`, so it matches the path below `base` rather than
+    /// the file name at any depth
+    anchored: bool,
+    dir_only: bool,
+    negate: bool,
+}
+
+impl IgnoreRule {
+    fn parse(line: &str, base: &str) -> Option<Self> {
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+        let (negate, pattern) = match line.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, line.strip_prefix('\\').unwrap_or(line)),
+        };
+        let (dir_only, pattern) = match pattern.strip_suffix('/') {
+            Some(rest) => (true, rest),
+            None => (false, pattern),
+        };
+        let anchored = pattern.contains('/');
+        let pattern = pattern.trim_start_matches('/');
+        if pattern.is_empty() {
+            return None;
+        }
+        Some(Self {
+            base: base.to_string(),
+            regex: glob_to_regex(pattern).ok()?,
+            anchored,
+            dir_only,
+            negate,
+        })
+    }
+
+    fn matches(&self, relative: &str, is_dir: bool) -> bool {
+        if self.dir_only && !is_dir {
+            return false;
+        }
+        let below = if self.base.is_empty() {
+            relative
+        } else {
+            match relative
+                .strip_prefix(self.base.as_str())
+                .and_then(|rest| rest.strip_prefix('/'))
+            {
+                Some(rest) => rest,
+                None => return false,
+            }
+        };
+        let target = if self.anchored {
+            below
+        } else {
+            below.rsplit('/').next().unwrap_or(below)
+        };
+        self.regex.is_match(target)
+    }
+}
+
+/// Append the rules of `dir/.gitignore`, if there is one.
+fn load_ignore_file(dir: &Path, base: &str, rules: &mut Vec<IgnoreRule>) {
+    if let Ok(text) = fs::read_to_string(dir.join(".gitignore")) {
+        rules.extend(
+            text.lines()
+                .filter_map(|line| IgnoreRule::parse(line, base)),
+        );
+    }
+}
+
+/// The last matching rule decides, as in git.
+fn is_ignored(rules: &[IgnoreRule], relative: &str, is_dir: bool) -> bool {
+    rules
+        .iter()
+        .rev()
+        .find(|rule| rule.matches(relative, is_dir))
+        .is_some_and(|rule| !rule.negate)
+}
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit + 1 < UNITS.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn workspace() -> (tempfile::TempDir, ToolContext) {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src/a/b")).unwrap();
+        fs::create_dir_all(root.join("build")).unwrap();
+        fs::create_dir_all(root.join(".git")).unwrap();
+        fs::write(root.join(".gitignore"), "build/\n*.log\n!keep.log\n").unwrap();
+        fs::write(root.join("README.md"), "# Demo\n").unwrap();
+        fs::write(root.join("debug.log"), "noise").unwrap();
+        fs::write(root.join("keep.log"), "kept").unwrap();
+        fs::write(root.join("build/out.bin"), "bin").unwrap();
+        fs::write(root.join("src/main.rs"), vec![b'x'; 2048]).unwrap();
+        fs::write(root.join("src/a/mod.rs"), "").unwrap();
+        fs::write(root.join("src/a/b/deep.rs"), "").unwrap();
+        fs::write(root.join("src/a/.gitignore"), "/b\n").unwrap();
+        let ctx = ToolContext::new().with_workspace(root.canonicalize().unwrap().to_str().unwrap());
+        (dir, ctx)
+    }
+
+    #[tokio::test]
+    async fn test_tree_depth_limit_and_ignores() {
+        let (dir, ctx) = workspace();
+
+        let result = TreeTool
+            .execute(json!({"max_depth": 1}), &ctx)
+            .await
+            .unwrap();
+        assert_eq!(
+            result,
+            "./\n  .gitignore (23 B)\n  README.md (7 B)\n  keep.log (4 B)\n  src/"
+        );
+
+        let result = TreeTool
+            .execute(json!({"path": "src"}), &ctx)
+            .await
+            .unwrap();
+        assert_eq!(
+            result,
+            "src/\n  a/\n    .gitignore (3 B)\n    mod.rs (0 B)\n  main.rs (2.0 KiB)"
+        );
+
+        // Without the nested .gitignore the third level shows up.
+        fs::remove_file(dir.path().join("src/a/.gitignore")).unwrap();
+        let result = TreeTool
+            .execute(json!({"path": "src", "max_depth": 2}), &ctx)
+            .await
+            .unwrap();
+        assert_eq!(
+            result,
+            "src/\n  a/\n    b/\n    mod.rs (0 B)\n  main.rs (2.0 KiB)"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tree_entry_cap() {
+        let dir = tempdir().unwrap();
+        for i in 0..50 {
+            fs::write(dir.path().join(format!("file{:02}.txt", i)), "").unwrap();
+        }
+        let ctx = ToolContext::new().with_workspace(dir.path().to_str().unwrap());
+
+        let result = TreeTool
+            .execute(json!({"max_entries": 3}), &ctx)
+            .await
+            .unwrap();
+        assert_eq!(
+            result,
+            "./\n  file00.txt (0 B)\n  file01.txt (0 B)\n  file02.txt (0 B)\n\
+             [Stopped after 3 entries; raise max_entries or narrow the path]"
+        );
+
+        let result = TreeTool.execute(json!({}), &ctx).await.unwrap();
+        assert_eq!(result.lines().count(), 51);
+    }
+
+    #[tokio::test]
+    async fn test_tree_output_cap() {
+        let dir = tempdir().unwrap();
+        let padding = "x".repeat(200);
+        for i in 0..300 {
+            fs::write(dir.path().join(format!("{}{:03}", padding, i)), "").unwrap();
+        }
+        let ctx = ToolContext::new().with_workspace(dir.path().to_str().unwrap());
+
+        let result = TreeTool
+            .execute(json!({"max_entries": 1000}), &ctx)
+            .await
+            .unwrap();
+        assert!(result.len() <= MAX_OUTPUT_BYTES + 200);
+        assert!(result.ends_with("lower max_depth or narrow the path]"));
+    }
+
+    #[tokio::test]
+    async fn test_tree_refuses_to_escape_workspace() {
+        let (_dir, ctx) = workspace();
+        for path in ["../", "/etc", "src/../../"] {
+            let result = TreeTool.execute(json!({"path": path}), &ctx).await;
+            assert!(
+                matches!(result, Err(ZeptoError::SecurityViolation(_))),
+                "{} was not rejected: {:?}",
+                path,
+                result
+            );
+        }
+
+        let result = TreeTool.execute(json!({}), &ToolContext::new()).await;
+        assert!(matches!(result, Err(ZeptoError::SecurityViolation(_))));
+
+        let result = TreeTool.execute(json!({"path": "README.md"}), &ctx).await;
+        assert!(matches!(result, Err(ZeptoError::Tool(_))));
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(0), "0 B");
+        assert_eq!(format_size(1023), "1023 B");
+        assert_eq!(format_size(1536), "1.5 KiB");
+        assert_eq!(format_size(5 * 1024 * 1024), "5.0 MiB");
+    }
+}
@@ -4,10 +4,15 @@
 //! that all tools must implement, and the `ToolContext` struct that provides
 //! execution context to tools.
 
+use std::time::Duration;
+
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use tokio_util::sync::CancellationToken;
 
-use crate::error::Result;
+use crate::error::{Result, ZeptoError};
+use crate::tools::schema;
 
 /// Trait that all tools must implement.
 ///
@@ -70,6 +75,22 @@ pub trait Tool: Send + Sync {
     /// A string result that will be sent back to the LLM.
     async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<String>;
 
+    /// Check arguments before [`execute`](Tool::execute) is called.
+    ///
+    /// The registry calls this for every call. The default checks `args`
+    /// against [`parameters`](Tool::parameters) (required fields, types,
+    /// enums and bounds; see [`schema`](crate::tools::schema)). Override to
+    /// add tool-specific rules, calling the default first if it still applies.
+    fn validate_args(&self, args: &Value) -> Result<()> {
+        schema::validate_args(&self.parameters(), args).map_err(|e| {
+            ZeptoError::Tool(format!(
+                "Invalid arguments for tool '{}': {}",
+                self.name(),
+                e
+            ))
+        })
+    }
+
     /// Get a compact (shorter) description for token-constrained environments.
     ///
     /// Defaults to the full description. Override in individual tools for
@@ -77,6 +98,72 @@ pub trait Tool: Send + Sync {
     fn compact_description(&self) -> &str {
         self.description()
     }
+
+    /// Whether calls may run concurrently with other parallel-safe calls in
+    /// the same turn.
+    ///
+    /// Defaults to `false`: the call runs alone, after the calls before it.
+    /// Override for read-only tools that touch no shared state.
+    fn is_parallel_safe(&self) -> bool {
+        false
+    }
+
+    /// Whether the tool honors [`ToolContext::dry_run`] by reporting what it
+    /// would do instead of doing it.
+    ///
+    /// Defaults to `false`. In dry-run mode the agent only executes tools
+    /// that return `true` here or are [parallel-safe](Tool::is_parallel_safe)
+    /// (read-only); every other call is described without being run.
+    fn supports_dry_run(&self) -> bool {
+        false
+    }
+
+    /// How long a result may be reused for an identical call when the agent's
+    /// tool result cache is enabled.
+    ///
+    /// Defaults to `None` (never cached). Override only for tools whose
+    /// output depends on nothing but their arguments for the given period;
+    /// mutating tools must stay uncached.
+    fn cache_ttl(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Where the tool comes from. Defaults to [`ToolSource::Builtin`].
+    fn source(&self) -> ToolSource {
+        ToolSource::Builtin
+    }
+}
+
+/// Origin of a registered tool, shown by `zeptoclaw tools list`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolSource {
+    /// Compiled into ZeptoClaw.
+    Builtin,
+    /// Provided by a plugin manifest (command or binary plugin).
+    Plugin,
+    /// Defined under `custom_tools` in the config.
+    Custom,
+    /// Proxied from an MCP server.
+    Mcp,
+}
+
+impl ToolSource {
+    /// Lowercase label for display.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Builtin => "builtin",
+            Self::Plugin => "plugin",
+            Self::Custom => "custom",
+            Self::Mcp => "mcp",
+        }
+    }
+}
+
+impl std::fmt::Display for ToolSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 /// Context provided to tools during execution.
@@ -91,6 +178,11 @@ pub struct ToolContext {
     pub chat_id: Option<String>,
     /// The workspace directory for file operations
     pub workspace: Option<String>,
+    /// Plan-only mode: mutating tools report what they would do instead
+    /// of doing it
+    pub dry_run: bool,
+    /// Cancellation token for the current turn; never cancelled by default
+    cancellation: CancellationToken,
 }
 
 impl ToolContext {
@@ -145,6 +237,53 @@ impl ToolContext {
         self.workspace = Some(workspace.to_string());
         self
     }
+
+    /// Enable or disable dry-run (plan-only) mode.
+    ///
+    /// # Example
+    /// ```
+    /// use zeptoclaw::tools::ToolContext;
+    ///
+    /// let ctx = ToolContext::new().with_dry_run(true);
+    /// assert!(ctx.dry_run);
+    /// ```
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Attach a cancellation token so the tool can stop early when the turn
+    /// is cancelled.
+    ///
+    /// # Example
+    /// ```
+    /// use tokio_util::sync::CancellationToken;
+    /// use zeptoclaw::tools::ToolContext;
+    ///
+    /// let token = CancellationToken::new();
+    /// let ctx = ToolContext::new().with_cancellation(token.clone());
+    /// assert!(!ctx.is_cancelled());
+    /// token.cancel();
+    /// assert!(ctx.is_cancelled());
+    /// ```
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = token;
+        self
+    }
+
+    /// Returns true if the turn this context belongs to has been cancelled.
+    ///
+    /// Long-running tools should poll this in their wait/poll loops and
+    /// return early once it flips.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation.is_cancelled()
+    }
+
+    /// Completes once the turn is cancelled. Useful in `tokio::select!`
+    /// against a long-running await.
+    pub async fn cancelled(&self) {
+        self.cancellation.cancelled().await
+    }
 }
 
 #[cfg(test)]
@@ -157,6 +296,7 @@ mod tests {
         assert!(ctx.channel.is_none());
         assert!(ctx.chat_id.is_none());
         assert!(ctx.workspace.is_none());
+        assert!(!ctx.dry_run);
     }
 
     #[test]
@@ -213,4 +353,66 @@ mod tests {
         assert_eq!(ctx1.chat_id, ctx2.chat_id);
         assert_eq!(ctx1.workspace, ctx2.workspace);
     }
+
+    #[test]
+    fn test_tool_context_new_is_never_cancelled() {
+        let ctx = ToolContext::new();
+        assert!(!ctx.is_cancelled());
+    }
+
+    /// Tool that polls until cancelled, giving up after a long deadline.
+    struct WaitForTool;
+
+    #[async_trait]
+    impl Tool for WaitForTool {
+        fn name(&self) -> &str {
+            "wait_for"
+        }
+
+        fn description(&self) -> &str {
+            "Poll until a condition holds"
+        }
+
+        fn parameters(&self) -> Value {
+            serde_json::json!({"type": "object", "properties": {}})
+        }
+
+        async fn execute(&self, _args: Value, ctx: &ToolContext) -> Result<String> {
+            for _ in 0..1000 {
+                if ctx.is_cancelled() {
+                    return Ok("cancelled".to_string());
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            }
+            Ok("timed out".to_string())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cooperating_tool_observes_cancellation() {
+        let token = CancellationToken::new();
+        let ctx = ToolContext::new().with_cancellation(token.clone());
+
+        let canceller = tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(30)).await;
+            token.cancel();
+        });
+
+        let start = std::time::Instant::now();
+        let result = WaitForTool.execute(serde_json::json!({}), &ctx).await;
+        canceller.await.unwrap();
+
+        assert_eq!(result.unwrap(), "cancelled");
+        assert!(start.elapsed() < std::time::Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_cancellation_shared_across_clones() {
+        let token = CancellationToken::new();
+        let ctx = ToolContext::new().with_cancellation(token.clone());
+        let cloned = ctx.clone();
+        token.cancel();
+        assert!(cloned.is_cancelled());
+        cloned.cancelled().await;
+    }
 }
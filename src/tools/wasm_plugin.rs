@@ -0,0 +1,384 @@
+//! WebAssembly plugin tool adapter for ZeptoClaw.
+//!
+//! Runs plugin tools compiled to WebAssembly with the wasmi interpreter.
+//! The module is compiled once at registration and instantiated afresh for
+//! every tool call, so no state leaks between calls. The host provides no
+//! imports at all: a module that imports WASI or any other host function
+//! fails to instantiate, so plugins have no filesystem, network, clock or
+//! environment access.
+//!
+//! # Module ABI
+//!
+//! The module must export:
+//! - `memory`: its linear memory
+//! - `alloc(len: i32) -> i32`: reserve `len` bytes and return the offset
+//! - `call(name_ptr: i32, name_len: i32, args_ptr: i32, args_len: i32) -> i64`:
+//!   run the tool named by the UTF-8 string at `name_ptr` with the JSON
+//!   arguments at `args_ptr`, and return `(result_ptr << 32) | result_len`
+//!
+//! The result is a UTF-8 JSON object, either `{"output": "..."}` or
+//! `{"error": "..."}`.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::Value;
+use tracing::warn;
+use wasmi::{Engine, Linker, Memory, Module, Store, StoreLimits, StoreLimitsBuilder, TypedFunc};
+
+use crate::error::{Result, ZeptoError};
+use crate::plugins::types::PluginToolDef;
+use crate::tools::types::{Tool, ToolContext, ToolSource};
+
+/// Linear memory a module may grow to.
+const MAX_MEMORY_BYTES: usize = 64 * 1024 * 1024;
+
+/// Largest result a module may return.
+const MAX_RESULT_BYTES: usize = 1024 * 1024;
+
+/// Fuel granted per second of timeout. The call is also cut off by a
+/// wall-clock timeout; fuel makes sure the interpreter thread itself stops.
+const FUEL_PER_SECOND: u64 = 500_000_000;
+
+#[derive(Deserialize)]
+struct WasmCallResult {
+    output: Option<String>,
+    error: Option<String>,
+}
+
+/// A tool adapter that executes a WebAssembly plugin module.
+pub struct WasmPluginTool {
+    def: PluginToolDef,
+    plugin_name: String,
+    module_path: PathBuf,
+    engine: Engine,
+    module: Arc<Module>,
+    timeout: Duration,
+}
+
+impl WasmPluginTool {
+    /// Compile the module for a plugin tool.
+    ///
+    /// # Arguments
+    /// * `def` - The tool definition from the plugin manifest
+    /// * `plugin_name` - Name of the parent plugin
+    /// * `module_path` - Absolute, validated path to the `.wasm` module
+    /// * `timeout_secs` - Execution timeout in seconds
+    ///
+    /// # Errors
+    /// Returns an error if the module cannot be read or is not valid
+    /// WebAssembly.
+    pub fn new(
+        def: PluginToolDef,
+        plugin_name: impl Into<String>,
+        module_path: PathBuf,
+        timeout_secs: u64,
+    ) -> Result<Self> {
+        let plugin_name = plugin_name.into();
+        let bytes = std::fs::read(&module_path).map_err(|e| {
+            ZeptoError::Config(format!(
+                "Failed to read WASM module {}: {}",
+                module_path.display(),
+                e
+            ))
+        })?;
+
+        let mut config = wasmi::Config::default();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config);
+        let module = Module::new(&engine, &bytes[..]).map_err(|e| {
+            ZeptoError::Config(format!(
+                "Plugin '{}' WASM module {} is invalid: {}",
+                plugin_name,
+                module_path.display(),
+                e
+            ))
+        })?;
+
+        Ok(Self {
+            def,
+            plugin_name,
+            module_path,
+            engine,
+            module: Arc::new(module),
+            timeout: Duration::from_secs(timeout_secs.max(1)),
+        })
+    }
+}
+
+impl std::fmt::Debug for WasmPluginTool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WasmPluginTool")
+            .field("name", &self.def.name)
+            .field("plugin", &self.plugin_name)
+            .field("module", &self.module_path)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl Tool for WasmPluginTool {
+    fn name(&self) -> &str {
+        &self.def.name
+    }
+
+    fn description(&self) -> &str {
+        &self.def.description
+    }
+
+    fn compact_description(&self) -> &str {
+        self.description()
+    }
+
+    fn source(&self) -> ToolSource {
+        ToolSource::Plugin
+    }
+
+    fn parameters(&self) -> Value {
+        self.def.parameters.clone()
+    }
+
+    async fn execute(&self, args: Value, _ctx: &ToolContext) -> Result<String> {
+        self.validate_args(&args)?;
+
+        let args_json = serde_json::to_string(&args)
+            .map_err(|e| ZeptoError::Tool(format!("Failed to serialize arguments: {}", e)))?;
+        let engine = self.engine.clone();
+        let module = Arc::clone(&self.module);
+        let tool_name = self.def.name.clone();
+        let fuel = FUEL_PER_SECOND.saturating_mul(self.timeout.as_secs());
+
+        let task = tokio::task::spawn_blocking(move || {
+            call_module(&engine, &module, &tool_name, &args_json, fuel)
+        });
+        let raw = match tokio::time::timeout(self.timeout, task).await {
+            Ok(Ok(result)) => result.map_err(|e| {
+                ZeptoError::Tool(format!("WASM plugin '{}' failed: {}", self.plugin_name, e))
+            })?,
+            Ok(Err(e)) => {
+                return Err(ZeptoError::Tool(format!(
+                    "WASM plugin '{}' task failed: {}",
+                    self.plugin_name, e
+                )));
+            }
+            Err(_) => {
+                return Err(ZeptoError::Tool(format!(
+                    "WASM plugin '{}' timed out after {}s",
+                    self.plugin_name,
+                    self.timeout.as_secs()
+                )));
+            }
+        };
+
+        let result: WasmCallResult = serde_json::from_slice(&raw).map_err(|e| {
+            ZeptoError::Tool(format!(
+                "WASM plugin '{}' returned invalid JSON: {} (raw: {})",
+                self.plugin_name,
+                e,
+                String::from_utf8_lossy(&raw[..raw.len().min(200)])
+            ))
+        })?;
+        if let Some(error) = result.error {
+            warn!(plugin = %self.plugin_name, "WASM plugin returned error");
+            return Err(ZeptoError::Tool(format!(
+                "WASM plugin '{}' error: {}",
+                self.plugin_name, error
+            )));
+        }
+        result.output.ok_or_else(|| {
+            ZeptoError::Tool(format!(
+                "WASM plugin '{}' returned neither output nor error",
+                self.plugin_name
+            ))
+        })
+    }
+}
+
+/// Instantiate `module` in a fresh store, run `call` and return the raw
+/// result bytes. Errors are plain strings; the caller adds plugin context.
+fn call_module(
+    engine: &Engine,
+    module: &Module,
+    tool_name: &str,
+    args_json: &str,
+    fuel: u64,
+) -> std::result::Result<Vec<u8>, String> {
+    let limits = StoreLimitsBuilder::new()
+        .memory_size(MAX_MEMORY_BYTES)
+        .instances(1)
+        .build();
+    let mut store = Store::new(engine, limits);
+    store.limiter(|limits: &mut StoreLimits| limits);
+    store.set_fuel(fuel).map_err(|e| e.to_string())?;
+
+    // An empty linker: any import (WASI or otherwise) fails here.
+    let instance = Linker::<StoreLimits>::new(engine)
+        .instantiate(&mut store, module)
+        .and_then(|pre| pre.start(&mut store))
+        .map_err(|e| format!("instantiation failed: {}", e))?;
+    let memory = instance
+        .get_memory(&store, "memory")
+        .ok_or("module does not export 'memory'")?;
+    let alloc: TypedFunc<i32, i32> = instance
+        .get_typed_func(&store, "alloc")
+        .map_err(|e| format!("missing export 'alloc(i32) -> i32': {}", e))?;
+    let call: TypedFunc<(i32, i32, i32, i32), i64> = instance
+        .get_typed_func(&store, "call")
+        .map_err(|e| format!("missing export 'call(i32, i32, i32, i32) -> i64': {}", e))?;
+
+    let (name_ptr, name_len) = write_input(&mut store, memory, &alloc, tool_name.as_bytes())?;
+    let (args_ptr, args_len) = write_input(&mut store, memory, &alloc, args_json.as_bytes())?;
+    let packed = call
+        .call(&mut store, (name_ptr, name_len, args_ptr, args_len))
+        .map_err(|e| match e.as_trap_code() {
+            Some(wasmi::core::TrapCode::OutOfFuel) => "execution budget exhausted".to_string(),
+            _ => format!("call trapped: {}", e),
+        })?;
+
+    let ptr = (packed as u64 >> 32) as usize;
+    let len = (packed as u64 & 0xffff_ffff) as usize;
+    if len > MAX_RESULT_BYTES {
+        return Err(format!(
+            "result of {} bytes exceeds the {} byte limit",
+            len, MAX_RESULT_BYTES
+        ));
+    }
+    let mut result = vec![0u8; len];
+    memory
+        .read(&store, ptr, &mut result)
+        .map_err(|e| format!("result out of bounds: {}", e))?;
+    Ok(result)
+}
+
+/// Copy `bytes` into guest memory obtained from the module's `alloc`.
+fn write_input(
+    store: &mut Store<StoreLimits>,
+    memory: Memory,
+    alloc: &TypedFunc<i32, i32>,
+    bytes: &[u8],
+) -> std::result::Result<(i32, i32), String> {
+    let len = i32::try_from(bytes.len()).map_err(|_| "input too large".to_string())?;
+    let ptr = alloc
+        .call(&mut *store, len)
+        .map_err(|e| format!("alloc trapped: {}", e))?;
+    memory
+        .write(&mut *store, ptr as u32 as usize, bytes)
+        .map_err(|e| format!("alloc returned an invalid offset: {}", e))?;
+    Ok((ptr, len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// Bump allocator plus a `call` that returns its arguments unchanged, so
+    /// the JSON arguments double as the result object.
+    const ECHO_WAT: &str = r#"
+        (module
+          (memory (export "memory") 1)
+          (global $next (mut i32) (i32.const 1024))
+          (func (export "alloc") (param $len i32) (result i32)
+            (local $ptr i32)
+            (local.set $ptr (global.get $next))
+            (global.set $next (i32.add (global.get $next) (local.get $len)))
+            (local.get $ptr))
+          (func (export "call") (param i32 i32 i32 i32) (result i64)
+            (i64.or
+              (i64.shl (i64.extend_i32_u (local.get 2)) (i64.const 32))
+              (i64.extend_i32_u (local.get 3)))))
+    "#;
+
+    fn tool_def() -> PluginToolDef {
+        serde_json::from_value(json!({
+            "name": "wasm_echo",
+            "description": "Echo",
+            "parameters": {"type": "object"}
+        }))
+        .unwrap()
+    }
+
+    fn tool(wat_src: &str, timeout_secs: u64) -> (tempfile::TempDir, WasmPluginTool) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("plugin.wasm");
+        std::fs::write(&path, wat::parse_str(wat_src).unwrap()).unwrap();
+        let tool = WasmPluginTool::new(tool_def(), "demo", path, timeout_secs).unwrap();
+        (dir, tool)
+    }
+
+    #[tokio::test]
+    async fn test_wasm_plugin_call_returns_output_and_errors() {
+        let (_dir, tool) = tool(ECHO_WAT, 5);
+        let ctx = ToolContext::new();
+
+        let output = tool
+            .execute(json!({"output": "hello from wasm"}), &ctx)
+            .await
+            .unwrap();
+        assert_eq!(output, "hello from wasm");
+
+        let err = tool
+            .execute(json!({"error": "boom"}), &ctx)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("WASM plugin 'demo' error: boom"));
+
+        let err = tool.execute(json!({}), &ctx).await.unwrap_err();
+        assert!(err.to_string().contains("neither output nor error"));
+    }
+
+    #[tokio::test]
+    async fn test_wasm_plugin_has_no_host_imports() {
+        let wat_src = r#"
+            (module
+              (import "wasi_snapshot_preview1" "fd_write"
+                (func (param i32 i32 i32 i32) (result i32)))
+              (memory (export "memory") 1)
+              (func (export "alloc") (param i32) (result i32) (i32.const 0))
+              (func (export "call") (param i32 i32 i32 i32) (result i64) (i64.const 0)))
+        "#;
+        let (_dir, tool) = tool(wat_src, 5);
+        let err = tool
+            .execute(json!({}), &ToolContext::new())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("instantiation failed"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_wasm_plugin_runaway_module_is_stopped() {
+        let wat_src = r#"
+            (module
+              (memory (export "memory") 1)
+              (func (export "alloc") (param i32) (result i32) (i32.const 0))
+              (func (export "call") (param i32 i32 i32 i32) (result i64)
+                (loop $spin (br $spin))
+                (i64.const 0)))
+        "#;
+        let (_dir, tool) = tool(wat_src, 1);
+        let started = std::time::Instant::now();
+        let err = tool
+            .execute(json!({}), &ToolContext::new())
+            .await
+            .unwrap_err();
+        let err = err.to_string();
+        assert!(
+            err.contains("timed out") || err.contains("execution budget exhausted"),
+            "{}",
+            err
+        );
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_invalid_module_is_rejected_at_registration() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("plugin.wasm");
+        std::fs::write(&path, b"\0asm\x01\0\0\0garbage").unwrap();
+        let err = WasmPluginTool::new(tool_def(), "demo", path, 5).unwrap_err();
+        assert!(err.to_string().contains("is invalid"));
+    }
+}
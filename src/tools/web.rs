@@ -2,24 +2,30 @@
 //!
 //! Provides:
 //! - `web_search`: search the web with Brave Search API.
-//! - `web_fetch`: fetch URL content and extract readable text.
+//! - `web_fetch`: fetch URL content and extract readable text, optionally
+//!   after rendering JavaScript (see [`render`](super::render)).
 
-use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::net::IpAddr;
 use std::time::Duration;
 
 use async_trait::async_trait;
 use regex::Regex;
-use reqwest::{Client, Url};
+use reqwest::header::LOCATION;
+use reqwest::{Client, Response, Url};
 use serde::Deserialize;
 use serde_json::{json, Value};
 use tokio::net::lookup_host;
+use tracing::warn;
 
+use crate::config::WebFetchConfig;
 use crate::error::{Result, ZeptoError};
+use crate::utils::url_policy::{is_private_host, is_private_or_local_ip, validate_url, UrlPolicy};
 
+use super::render::PageRenderer;
 use super::{Tool, ToolContext};
 
 const BRAVE_API_URL: &str = "https://api.search.brave.com/res/v1/web/search";
-const WEB_USER_AGENT: &str = "zeptoclaw/0.1 (+https://github.com/zeptoclaw/zeptoclaw)";
+pub(super) const WEB_USER_AGENT: &str = "zeptoclaw/0.1 (+https://github.com/zeptoclaw/zeptoclaw)";
 const MAX_WEB_SEARCH_COUNT: usize = 10;
 const DEFAULT_MAX_FETCH_CHARS: usize = 50_000;
 const MAX_FETCH_CHARS: usize = 200_000;
@@ -27,6 +33,10 @@ const MIN_FETCH_CHARS: usize = 256;
 /// Maximum bytes to read from a response body before truncating.
 /// Uses a 4x multiplier over MAX_FETCH_CHARS to account for multi-byte UTF-8.
 const MAX_FETCH_BYTES: usize = MAX_FETCH_CHARS * 4;
+/// How long identical search and fetch results may be reused from the tool cache.
+const WEB_CACHE_TTL: Duration = Duration::from_secs(300);
+/// Maximum redirects followed per request by `web_fetch` and `http_request`.
+pub(super) const MAX_REDIRECTS: usize = 5;
 
 /// Web search tool backed by Brave Search.
 pub struct WebSearchTool {
@@ -88,6 +98,14 @@ impl Tool for WebSearchTool {
         "Web search"
     }
 
+    fn is_parallel_safe(&self) -> bool {
+        true
+    }
+
+    fn cache_ttl(&self) -> Option<Duration> {
+        Some(WEB_CACHE_TTL)
+    }
+
     fn parameters(&self) -> Value {
         json!({
             "type": "object",
@@ -184,23 +202,23 @@ impl Tool for WebSearchTool {
 }
 
 /// Web fetch tool for URL content retrieval.
+///
+/// With `render: true`, the page is loaded through a rendering backend so
+/// JavaScript-built content is included; if no rendering backend is
+/// available the tool falls back to a plain fetch and reports why.
 pub struct WebFetchTool {
-    client: Client,
     max_chars: usize,
+    renderer: PageRenderer,
+    url_policy: UrlPolicy,
 }
 
 impl WebFetchTool {
     /// Create a new web fetch tool.
     pub fn new() -> Self {
-        let client = Client::builder()
-            .redirect(reqwest::redirect::Policy::limited(5))
-            .timeout(Duration::from_secs(30))
-            .build()
-            .unwrap_or_else(|_| Client::new());
-
         Self {
-            client,
             max_chars: DEFAULT_MAX_FETCH_CHARS,
+            renderer: PageRenderer::default(),
+            url_policy: UrlPolicy::default(),
         }
     }
 
@@ -211,6 +229,19 @@ impl WebFetchTool {
         tool
     }
 
+    /// Configure `render: true` fetches from `tools.web.fetch`: an external
+    /// rendering service, or local headless Chromium when opted in.
+    pub fn with_render(mut self, config: &WebFetchConfig) -> Self {
+        self.renderer = PageRenderer::from_config(config);
+        self
+    }
+
+    /// Apply `tools.url_policy` to requested URLs and redirect targets.
+    pub fn with_url_policy(mut self, url_policy: UrlPolicy) -> Self {
+        self.url_policy = url_policy;
+        self
+    }
+
     fn extract_title(&self, html: &str) -> Option<String> {
         let regex = Regex::new(r"(?is)<title[^>]*>(.*?)</title>").ok()?;
         let captures = regex.captures(html)?;
@@ -257,6 +288,14 @@ impl Tool for WebFetchTool {
         "Fetch URL"
     }
 
+    fn is_parallel_safe(&self) -> bool {
+        true
+    }
+
+    fn cache_ttl(&self) -> Option<Duration> {
+        Some(WEB_CACHE_TTL)
+    }
+
     fn parameters(&self) -> Value {
         json!({
             "type": "object",
@@ -270,13 +309,17 @@ impl Tool for WebFetchTool {
                     "description": "Maximum output characters",
                     "minimum": MIN_FETCH_CHARS,
                     "maximum": MAX_FETCH_CHARS
+                },
+                "render": {
+                    "type": "boolean",
+                    "description": "Run the page's JavaScript in a headless browser before extracting text. Slower; use for single-page apps that return an empty shell (default: false)"
                 }
             },
             "required": ["url"]
         })
     }
 
-    async fn execute(&self, args: Value, _ctx: &ToolContext) -> Result<String> {
+    async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<String> {
         let url = args
             .get("url")
             .and_then(|v| v.as_str())
@@ -284,23 +327,7 @@ impl Tool for WebFetchTool {
             .filter(|s| !s.is_empty())
             .ok_or_else(|| ZeptoError::Tool("Missing 'url' parameter".to_string()))?;
 
-        let parsed = Url::parse(url)
-            .map_err(|e| ZeptoError::Tool(format!("Invalid URL '{}': {}", url, e)))?;
-
-        match parsed.scheme() {
-            "http" | "https" => {}
-            _ => {
-                return Err(ZeptoError::Tool(
-                    "Only http/https URLs are allowed".to_string(),
-                ));
-            }
-        }
-
-        if is_blocked_host(&parsed) {
-            return Err(ZeptoError::SecurityViolation(
-                "Blocked URL host (local or private network)".to_string(),
-            ));
-        }
+        let parsed = validate_url(url, &self.url_policy)?;
 
         // DNS-based SSRF check: resolve the hostname before making the
         // request and verify none of the resolved IPs are private/local.
@@ -315,36 +342,52 @@ impl Tool for WebFetchTool {
             .unwrap_or(self.max_chars)
             .clamp(MIN_FETCH_CHARS, MAX_FETCH_CHARS);
 
-        // Build a client that pins the DNS resolution to the IP we already
-        // validated, so the HTTP library cannot re-resolve to a different
-        // (potentially private) address.
-        let client = if let Some((host, addr)) = pinned {
-            Client::builder()
-                .redirect(reqwest::redirect::Policy::limited(5))
-                .timeout(Duration::from_secs(30))
-                .resolve(&host, addr)
-                .build()
-                .unwrap_or_else(|_| self.client.clone())
-        } else {
-            self.client.clone()
-        };
-
-        let response = client
-            .get(parsed.clone())
-            .header("User-Agent", WEB_USER_AGENT)
-            .send()
-            .await
-            .map_err(|e| ZeptoError::Tool(format!("Web fetch failed: {}", e)))?;
-
-        // SSRF redirect check: after reqwest follows redirects, validate
-        // that the final destination URL is not a blocked host.
-        if is_blocked_host(response.url()) {
-            return Err(ZeptoError::SecurityViolation(format!(
-                "Redirect destination is blocked (local or private network): {}",
-                response.url()
-            )));
+        let render = args
+            .get("render")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let mut render_error = None;
+        if render {
+            let rendered = tokio::select! {
+                res = self.renderer.render(&parsed, MAX_FETCH_BYTES) => res,
+                _ = ctx.cancelled() => {
+                    return Err(ZeptoError::Tool("Web fetch cancelled".to_string()));
+                }
+            };
+            match rendered {
+                Ok(page) => {
+                    let final_url = Url::parse(&page.final_url).map_err(|e| {
+                        ZeptoError::Tool(format!(
+                            "Invalid rendered URL '{}': {}",
+                            page.final_url, e
+                        ))
+                    })?;
+                    self.url_policy.check(&final_url).map_err(|e| {
+                        ZeptoError::SecurityViolation(format!(
+                            "Rendered page navigated to a blocked URL: {}",
+                            e
+                        ))
+                    })?;
+                    let mut output = self.build_output(
+                        url,
+                        final_url.as_str(),
+                        None,
+                        "text/html",
+                        page.html,
+                        max_chars,
+                    );
+                    output["rendered"] = json!(true);
+                    return Ok(output.to_string());
+                }
+                Err(e) => {
+                    warn!(url = %parsed, error = %e, "Page rendering unavailable, using plain fetch");
+                    render_error = Some(e.to_string());
+                }
+            }
         }
 
+        let response = self.fetch(parsed, pinned, ctx).await?;
+
         let status = response.status();
         let final_url = response.url().to_string();
 
@@ -361,8 +404,95 @@ impl Tool for WebFetchTool {
 
         // Read body in chunks with a size limit to prevent unbounded memory
         // allocation from malicious or oversized responses.
-        let body = read_body_limited(response, MAX_FETCH_BYTES).await?;
+        let body = tokio::select! {
+            res = read_body_limited(response, MAX_FETCH_BYTES) => res?,
+            _ = ctx.cancelled() => {
+                return Err(ZeptoError::Tool("Web fetch cancelled".to_string()));
+            }
+        };
+
+        let mut output = self.build_output(
+            url,
+            &final_url,
+            Some(status.as_u16()),
+            &content_type,
+            body,
+            max_chars,
+        );
+        if render {
+            output["rendered"] = json!(false);
+            output["render_error"] = json!(render_error);
+        }
+        Ok(output.to_string())
+    }
+}
 
+impl WebFetchTool {
+    /// Client for a single hop: redirects are not followed (see
+    /// [`fetch`](Self::fetch)) and, when given, `host` is pinned to an
+    /// already-validated address so the HTTP library cannot re-resolve it to
+    /// a different (potentially private) address.
+    fn client(pinned: Option<(String, std::net::SocketAddr)>) -> Result<Client> {
+        let mut builder = Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .timeout(Duration::from_secs(30));
+        if let Some((host, addr)) = pinned {
+            builder = builder.resolve(&host, addr);
+        }
+        builder
+            .build()
+            .map_err(|e| ZeptoError::Tool(format!("Failed to build HTTP client: {}", e)))
+    }
+
+    /// GET `url`, following up to [`MAX_REDIRECTS`] redirects by hand.
+    ///
+    /// `url` must already have passed the URL policy and DNS check, with
+    /// `pinned` as the result of the latter. Every redirect target is
+    /// checked against the policy, resolved, and pinned before it is
+    /// requested, so a redirect cannot reach a blocked or private address.
+    async fn fetch(
+        &self,
+        mut url: Url,
+        mut pinned: Option<(String, std::net::SocketAddr)>,
+        ctx: &ToolContext,
+    ) -> Result<Response> {
+        for _ in 0..=MAX_REDIRECTS {
+            let request = Self::client(pinned)?
+                .get(url.clone())
+                .header("User-Agent", WEB_USER_AGENT)
+                .send();
+            let response = tokio::select! {
+                res = request => res.map_err(|e| ZeptoError::Tool(format!("Web fetch failed: {}", e)))?,
+                _ = ctx.cancelled() => {
+                    return Err(ZeptoError::Tool("Web fetch cancelled".to_string()));
+                }
+            };
+            let Some(next) = redirect_target(&url, &response)? else {
+                return Ok(response);
+            };
+            self.url_policy.check(&next).map_err(|e| {
+                ZeptoError::SecurityViolation(format!("Redirect destination is blocked: {}", e))
+            })?;
+            pinned = resolve_and_check_host(&next).await?;
+            url = next;
+        }
+        Err(ZeptoError::Tool(format!(
+            "Web fetch failed: more than {} redirects",
+            MAX_REDIRECTS
+        )))
+    }
+
+    /// Extract readable text from a fetched body and wrap it in the tool's
+    /// JSON result.
+    fn build_output(
+        &self,
+        url: &str,
+        final_url: &str,
+        status: Option<u16>,
+        content_type: &str,
+        body: String,
+        max_chars: usize,
+    ) -> Value {
         let (extractor, mut text) = if content_type.contains("application/json") {
             ("json", body)
         } else if content_type.contains("text/html") || body.trim_start().starts_with('<') {
@@ -387,16 +517,15 @@ impl Tool for WebFetchTool {
             text.truncate(end);
         }
 
-        Ok(json!({
+        json!({
             "url": url,
             "final_url": final_url,
-            "status": status.as_u16(),
+            "status": status,
             "extractor": extractor,
             "truncated": truncated,
             "length": text.len(),
             "text": text,
         })
-        .to_string())
     }
 }
 
@@ -431,7 +560,10 @@ fn decode_common_html_entities(input: &str) -> String {
 /// extremely large response (intentional or otherwise).  The bytes are
 /// accumulated in chunks and converted to a UTF-8 string (lossy) once
 /// the limit is reached or the stream ends.
-async fn read_body_limited(response: reqwest::Response, max_bytes: usize) -> Result<String> {
+pub(super) async fn read_body_limited(
+    response: reqwest::Response,
+    max_bytes: usize,
+) -> Result<String> {
     let mut buf: Vec<u8> = Vec::new();
     let mut stream = response;
 
@@ -461,30 +593,28 @@ async fn read_body_limited(response: reqwest::Response, max_bytes: usize) -> Res
     Ok(String::from_utf8_lossy(&buf).into_owned())
 }
 
-/// Check whether a URL's host is a blocked (local/private) address.
-/// Used by both `WebFetchTool` and the watch command to prevent SSRF.
-pub fn is_blocked_host(url: &Url) -> bool {
-    let Some(host_str) = url.host_str() else {
-        return true;
-    };
-
-    let host = host_str.to_ascii_lowercase();
-    if host == "localhost" || host.ends_with(".local") {
-        return true;
-    }
-
-    // Try parsing as IP directly first, then try stripping IPv6 brackets.
-    // `Url::host_str()` returns IPv6 addresses with surrounding brackets
-    // (e.g. "[::1]"), which `IpAddr::parse` does not accept.
-    let ip_str = host
-        .strip_prefix('[')
-        .and_then(|s| s.strip_suffix(']'))
-        .unwrap_or(&host);
-    if let Ok(ip) = ip_str.parse::<IpAddr>() {
-        return is_private_or_local_ip(ip);
+/// Where a redirect response points, resolved against `url`. `None` if the
+/// response is not a redirect to follow.
+pub(super) fn redirect_target(url: &Url, response: &Response) -> Result<Option<Url>> {
+    if !matches!(response.status().as_u16(), 301..=303 | 307 | 308) {
+        return Ok(None);
     }
+    let Some(location) = response.headers().get(LOCATION) else {
+        return Ok(None);
+    };
+    let location = location
+        .to_str()
+        .map_err(|_| ZeptoError::Tool("Redirect Location is not valid text".to_string()))?;
+    url.join(location)
+        .map(Some)
+        .map_err(|e| ZeptoError::Tool(format!("Invalid redirect Location '{}': {}", location, e)))
+}
 
-    false
+/// Check whether a URL's host is a blocked (local/private) address.
+/// Used by the watch command to prevent SSRF; tools apply the full
+/// [`UrlPolicy`], which includes this check.
+pub fn is_blocked_host(url: &Url) -> bool {
+    is_private_host(url)
 }
 
 /// Resolve a URL's hostname via DNS and check whether any of the resolved IPs
@@ -531,33 +661,6 @@ pub async fn resolve_and_check_host(url: &Url) -> Result<Option<(String, std::ne
         .map(|addr| (host.to_string(), addr)))
 }
 
-fn is_private_or_local_ip(ip: IpAddr) -> bool {
-    match ip {
-        IpAddr::V4(addr) => is_private_or_local_ipv4(addr),
-        IpAddr::V6(addr) => is_private_or_local_ipv6(addr),
-    }
-}
-
-fn is_private_or_local_ipv4(addr: Ipv4Addr) -> bool {
-    addr.is_private()
-        || addr.is_loopback()
-        || addr.is_link_local()
-        || addr.is_broadcast()
-        || addr.is_documentation()
-        || addr.is_unspecified()
-        || addr.octets()[0] == 0
-}
-
-fn is_private_or_local_ipv6(addr: Ipv6Addr) -> bool {
-    let first = addr.segments()[0];
-
-    addr.is_loopback()
-        || addr.is_unspecified()
-        || (first & 0xfe00) == 0xfc00
-        || (first & 0xffc0) == 0xfe80
-        || (first & 0xff00) == 0xff00
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -821,6 +924,69 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_web_fetch_render_keeps_ssrf_checks() {
+        let (base, requests) = crate::providers::mock_http::serve(vec![]).await;
+        let tool = WebFetchTool::new().with_render(&WebFetchConfig {
+            render_endpoint: Some(base),
+            render_timeout_secs: 5,
+            ..Default::default()
+        });
+        let result = tool
+            .execute(
+                json!({"url": "http://169.254.169.254/latest/meta-data", "render": true}),
+                &ToolContext::new(),
+            )
+            .await;
+        assert!(matches!(result, Err(ZeptoError::SecurityViolation(_))));
+        // The render service was never called.
+        assert!(requests.await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_web_fetch_redirect_to_private_address_is_blocked() {
+        use crate::providers::mock_http::{serve, MockResponse};
+
+        let (base, requests) = serve(vec![MockResponse::json(302, "")
+            .with_header("Location", "http://169.254.169.254/latest/meta-data")])
+        .await;
+        let err = WebFetchTool::new()
+            .fetch(
+                Url::parse(&format!("{}/start", base)).unwrap(),
+                None,
+                &ToolContext::new(),
+            )
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, ZeptoError::SecurityViolation(ref msg) if msg.contains("Redirect destination is blocked")),
+            "{:?}",
+            err
+        );
+        // Only the first hop was requested.
+        assert_eq!(requests.await.unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_build_output_for_rendered_html() {
+        let tool = WebFetchTool::new();
+        let html = "<html><head><title>App</title><script>var x = 1;</script>\
+                    <style>p { color: red }</style></head><body><div>Loaded by JS</div></body></html>";
+        let output = tool.build_output(
+            "https://example.com/app",
+            "https://example.com/app/home",
+            None,
+            "text/html",
+            html.to_string(),
+            1000,
+        );
+        assert_eq!(output["final_url"], "https://example.com/app/home");
+        assert!(output["status"].is_null());
+        assert_eq!(output["extractor"], "html");
+        // Scripts and styles are stripped.
+        assert_eq!(output["text"], "# App\n\nApp Loaded by JS");
+    }
+
     #[test]
     fn test_with_max_chars_clamping() {
         // Below minimum should clamp to MIN_FETCH_CHARS
@@ -1,11 +1,11 @@
 //! LLM API cost estimation and tracking.
 //!
 //! Provides model pricing data, per-call cost estimation, and a thread-safe
-//! `CostTracker` that accumulates spend across providers and models within
-//! a session. Uses interior mutability via `Mutex` so all recording methods
-//! take `&self`.
+//! `CostTracker` that accumulates spend and token usage across providers and
+//! models within a session. Uses interior mutability via `Mutex` so all
+//! recording methods take `&self`.
 
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::sync::Mutex;
 
 use serde::{Deserialize, Serialize};
@@ -86,6 +86,22 @@ pub fn default_pricing() -> HashMap<String, ModelPricing> {
         },
     );
 
+    // Google Gemini models
+    m.insert(
+        "gemini-2.5-flash".to_string(),
+        ModelPricing {
+            input_cost_per_million: 0.3,
+            output_cost_per_million: 2.5,
+        },
+    );
+    m.insert(
+        "gemini-2.5-pro".to_string(),
+        ModelPricing {
+            input_cost_per_million: 1.25,
+            output_cost_per_million: 10.0,
+        },
+    );
+
     m
 }
 
@@ -111,6 +127,27 @@ pub fn estimate_cost(
     })
 }
 
+/// Accumulated prompt and completion token counts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenTotals {
+    /// Total prompt (input) tokens.
+    pub prompt_tokens: u64,
+    /// Total completion (output) tokens.
+    pub completion_tokens: u64,
+}
+
+impl TokenTotals {
+    /// Prompt plus completion tokens.
+    pub fn total(&self) -> u64 {
+        self.prompt_tokens + self.completion_tokens
+    }
+
+    fn add(&mut self, prompt_tokens: u32, completion_tokens: u32) {
+        self.prompt_tokens += prompt_tokens as u64;
+        self.completion_tokens += completion_tokens as u64;
+    }
+}
+
 /// Internal mutable state guarded by the `CostTracker` mutex.
 #[derive(Debug, Default)]
 struct CostState {
@@ -118,6 +155,11 @@ struct CostState {
     per_provider: HashMap<String, f64>,
     per_model: HashMap<String, f64>,
     call_count: u64,
+    tokens: TokenTotals,
+    tokens_per_provider: HashMap<String, TokenTotals>,
+    tokens_per_model: HashMap<String, TokenTotals>,
+    /// Models seen without any pricing entry; their tokens count, their cost does not.
+    unpriced_models: BTreeSet<String>,
 }
 
 /// Thread-safe, session-level cost accumulator.
@@ -151,16 +193,23 @@ impl CostTracker {
 
     /// Record a single LLM call.
     ///
-    /// Estimates cost (if the model is known) and accumulates it under both
-    /// the provider name and the model name.
-    pub fn record(&self, provider: &str, model: &str, prompt_tokens: u32, completion_tokens: u32) {
-        let cost = estimate_cost(
+    /// Accumulates the token counts and, if the model is known, the estimated
+    /// cost under both the provider name and the model name. Unknown models
+    /// contribute tokens but no cost. Returns the call's estimated cost.
+    pub fn record(
+        &self,
+        provider: &str,
+        model: &str,
+        prompt_tokens: u32,
+        completion_tokens: u32,
+    ) -> Option<f64> {
+        let estimate = estimate_cost(
             model,
             prompt_tokens,
             completion_tokens,
             &self.custom_pricing,
-        )
-        .unwrap_or(0.0);
+        );
+        let cost = estimate.unwrap_or(0.0);
 
         let mut state = self.state.lock().unwrap();
         state.total_cost += cost;
@@ -170,6 +219,23 @@ impl CostTracker {
             .or_insert(0.0) += cost;
         *state.per_model.entry(model.to_string()).or_insert(0.0) += cost;
         state.call_count += 1;
+
+        state.tokens.add(prompt_tokens, completion_tokens);
+        state
+            .tokens_per_provider
+            .entry(provider.to_string())
+            .or_default()
+            .add(prompt_tokens, completion_tokens);
+        state
+            .tokens_per_model
+            .entry(model.to_string())
+            .or_default()
+            .add(prompt_tokens, completion_tokens);
+        if estimate.is_none() {
+            state.unpriced_models.insert(model.to_string());
+        }
+
+        estimate
     }
 
     /// Returns the total accumulated cost in USD.
@@ -192,18 +258,49 @@ impl CostTracker {
         self.state.lock().unwrap().call_count
     }
 
+    /// Returns the total tokens recorded across all calls.
+    pub fn total_tokens(&self) -> TokenTotals {
+        self.state.lock().unwrap().tokens
+    }
+
+    /// Returns a snapshot of accumulated tokens per provider.
+    pub fn tokens_by_provider(&self) -> HashMap<String, TokenTotals> {
+        self.state.lock().unwrap().tokens_per_provider.clone()
+    }
+
+    /// Returns a snapshot of accumulated tokens per model.
+    pub fn tokens_by_model(&self) -> HashMap<String, TokenTotals> {
+        self.state.lock().unwrap().tokens_per_model.clone()
+    }
+
+    /// Returns the models recorded without pricing, sorted by name.
+    pub fn unpriced_models(&self) -> Vec<String> {
+        self.state
+            .lock()
+            .unwrap()
+            .unpriced_models
+            .iter()
+            .cloned()
+            .collect()
+    }
+
     /// Produces a human-readable cost summary.
     ///
     /// Example output:
     /// ```text
-    /// Total: $0.0150 (3 calls) | anthropic: $0.0120, openai: $0.0030
+    /// Total: $0.0150 (3 calls) | tokens: 3000 in / 1500 out | anthropic: $0.0120, openai: $0.0030
     /// ```
+    ///
+    /// Models without pricing are listed at the end as `unpriced: <models>`.
     pub fn summary(&self) -> String {
         let state = self.state.lock().unwrap();
 
         let mut summary = format!(
-            "Total: ${:.4} ({} calls)",
-            state.total_cost, state.call_count,
+            "Total: ${:.4} ({} calls) | tokens: {} in / {} out",
+            state.total_cost,
+            state.call_count,
+            state.tokens.prompt_tokens,
+            state.tokens.completion_tokens,
         );
 
         if !state.per_provider.is_empty() {
@@ -219,6 +316,12 @@ impl CostTracker {
             summary.push_str(&parts.join(", "));
         }
 
+        if !state.unpriced_models.is_empty() {
+            let models: Vec<&str> = state.unpriced_models.iter().map(String::as_str).collect();
+            summary.push_str(" | unpriced: ");
+            summary.push_str(&models.join(", "));
+        }
+
         summary
     }
 }
@@ -269,6 +372,8 @@ mod tests {
             "gpt-5.1",
             "gpt-4o-mini",
             "gpt-4-turbo",
+            "gemini-2.5-flash",
+            "gemini-2.5-pro",
         ];
         for model in &expected {
             assert!(prices.contains_key(*model), "missing model: {}", model);
@@ -403,6 +508,44 @@ mod tests {
         assert!(summary.contains("(3 calls)"), "missing call count");
         assert!(summary.contains("anthropic: $"), "missing anthropic");
         assert!(summary.contains("openai: $"), "missing openai");
+        assert!(
+            summary.contains("tokens: 5000 in / 2500 out"),
+            "missing token totals"
+        );
+        assert!(!summary.contains("unpriced"));
+    }
+
+    #[test]
+    fn test_cost_tracker_record_returns_estimate() {
+        let tracker = CostTracker::new();
+        // gemini-2.5-flash: 1M/1M*0.3 + 1M/1M*2.5 = 2.8
+        let cost = tracker
+            .record("gemini", "gemini-2.5-flash", 1_000_000, 1_000_000)
+            .unwrap();
+        assert!((cost - 2.8).abs() < 1e-10);
+        assert!(tracker.record("ollama", "llama3.2", 10, 5).is_none());
+    }
+
+    #[test]
+    fn test_cost_tracker_accumulates_tokens() {
+        let tracker = CostTracker::new();
+        tracker.record("anthropic", "claude-sonnet-4-5-20250929", 1000, 500);
+        tracker.record("anthropic", "claude-3-haiku-20240307", 200, 100);
+        tracker.record("openai", "gpt-5.1", 300, 50);
+
+        let totals = tracker.total_tokens();
+        assert_eq!(totals.prompt_tokens, 1500);
+        assert_eq!(totals.completion_tokens, 650);
+        assert_eq!(totals.total(), 2150);
+
+        let by_provider = tracker.tokens_by_provider();
+        assert_eq!(by_provider["anthropic"].prompt_tokens, 1200);
+        assert_eq!(by_provider["anthropic"].completion_tokens, 600);
+        assert_eq!(by_provider["openai"].total(), 350);
+
+        let by_model = tracker.tokens_by_model();
+        assert_eq!(by_model.len(), 3);
+        assert_eq!(by_model["claude-3-haiku-20240307"].total(), 300);
     }
 
     #[test]
@@ -427,6 +570,11 @@ mod tests {
         assert!((tracker.total_cost() - 0.0).abs() < f64::EPSILON);
         assert_eq!(tracker.call_count(), 1);
         assert!(tracker.cost_by_provider().contains_key("custom"));
+
+        // ...and its tokens, flagged as unpriced
+        assert_eq!(tracker.total_tokens().total(), 15000);
+        assert_eq!(tracker.unpriced_models(), vec!["unknown-model".to_string()]);
+        assert!(tracker.summary().ends_with("| unpriced: unknown-model"));
     }
 
     #[test]
@@ -2,6 +2,8 @@
 
 pub mod cost;
 pub mod metrics;
+pub mod redact;
 pub mod sanitize;
 pub mod slo;
 pub mod telemetry;
+pub mod url_policy;
@@ -0,0 +1,347 @@
+//! Secret redaction for log and tracing output.
+//!
+//! Values stored under sensitive key names (`api_key`, `token`, `password`,
+//! ...) are replaced with `***` before they reach a log line. Wrap anything
+//! that may carry a secret in [`RedactedValue`] when logging it:
+//!
+//! ```
+//! use zeptoclaw::utils::redact::RedactedValue;
+//!
+//! let args = serde_json::json!({"url": "https://example.com", "api_key": "sk-123"});
+//! let logged = format!("{}", RedactedValue::new(&args));
+//! assert_eq!(logged, r#"{"api_key":"***","url":"https://example.com"}"#);
+//! ```
+
+use std::fmt;
+
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use serde_json::Value;
+
+/// Placeholder written in place of a secret.
+pub const REDACTED: &str = "***";
+
+/// Key names treated as sensitive by [`Redactor::default`].
+pub const DEFAULT_SENSITIVE_KEYS: &[&str] = &[
+    "api_key",
+    "apikey",
+    "token",
+    "secret",
+    "password",
+    "passwd",
+    "authorization",
+    "private_key",
+    "credential",
+    "credentials",
+    "cookie",
+];
+
+/// `key: value` / `key=value` / `"key": "value"` pairs, including Rust
+/// `Debug` output such as `api_key: Some("...")`.
+static PAIR_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#""?(?P<key>[A-Za-z_][A-Za-z0-9_\-]*)"?(?P<sep>\s*[:=]\s*)(?P<some>Some\(\s*)?(?P<value>"(?:[^"\\]|\\.)*"|(?:(?i:bearer|basic)\s+)?[^\s,;&"{}\[\]()]+)"#,
+    )
+    .unwrap()
+});
+
+/// `Bearer <token>` / `Basic <credentials>` in header values.
+static AUTH_SCHEME_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)\b(?P<scheme>bearer|basic)\s+[A-Za-z0-9._~+/=\-]+").unwrap());
+
+static DEFAULT_REDACTOR: Lazy<Redactor> = Lazy::new(Redactor::default);
+
+/// Replaces values under sensitive key names with [`REDACTED`].
+///
+/// A key matches a pattern when, after normalizing to `snake_case`, it equals
+/// the pattern or ends with `_<pattern>`: `openaiApiKey`, `X-Api-Key` and
+/// `bot_token` match, while `max_tokens` and `session_key` do not.
+#[derive(Debug, Clone)]
+pub struct Redactor {
+    keys: Vec<String>,
+}
+
+impl Default for Redactor {
+    fn default() -> Self {
+        Self::new(DEFAULT_SENSITIVE_KEYS.iter().copied())
+    }
+}
+
+impl Redactor {
+    /// Create a redactor matching the given key-name patterns.
+    pub fn new<I, S>(keys: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self {
+            keys: keys
+                .into_iter()
+                .map(|k| normalize_key(k.as_ref()))
+                .filter(|k| !k.is_empty())
+                .collect(),
+        }
+    }
+
+    /// Add a key-name pattern.
+    pub fn with_key(mut self, key: &str) -> Self {
+        let key = normalize_key(key);
+        if !key.is_empty() && !self.keys.contains(&key) {
+            self.keys.push(key);
+        }
+        self
+    }
+
+    /// Returns `true` if values stored under `key` should be hidden.
+    pub fn is_sensitive(&self, key: &str) -> bool {
+        let key = normalize_key(key);
+        self.keys.iter().any(|pattern| {
+            key == *pattern
+                || key
+                    .strip_suffix(pattern.as_str())
+                    .is_some_and(|prefix| prefix.ends_with('_'))
+        })
+    }
+
+    /// Redact secrets in free-form text such as `Debug` output, JSON or
+    /// query strings.
+    pub fn redact_str(&self, text: &str) -> String {
+        let text = AUTH_SCHEME_RE.replace_all(text, |caps: &Captures| {
+            format!("{} {}", &caps["scheme"], REDACTED)
+        });
+        PAIR_RE
+            .replace_all(&text, |caps: &Captures| {
+                let value = &caps["value"];
+                if !self.is_sensitive(&caps["key"]) || matches!(value, "None" | "null") {
+                    return caps[0].to_string();
+                }
+                let whole = caps.get(0).map_or(0, |m| m.start());
+                let value_start = caps.name("value").map_or(0, |m| m.start()) - whole;
+                let placeholder = if value.starts_with('"') {
+                    format!("\"{}\"", REDACTED)
+                } else {
+                    REDACTED.to_string()
+                };
+                format!("{}{}", &caps[0][..value_start], placeholder)
+            })
+            .into_owned()
+    }
+
+    /// Copy of `value` with every sensitive object member replaced by
+    /// [`REDACTED`], at any depth.
+    pub fn redact_json(&self, value: &Value) -> Value {
+        match value {
+            Value::Object(map) => Value::Object(
+                map.iter()
+                    .map(|(key, value)| {
+                        let value = if self.is_sensitive(key) && !value.is_null() {
+                            Value::String(REDACTED.to_string())
+                        } else {
+                            self.redact_json(value)
+                        };
+                        (key.clone(), value)
+                    })
+                    .collect(),
+            ),
+            Value::Array(items) => {
+                Value::Array(items.iter().map(|v| self.redact_json(v)).collect())
+            }
+            other => other.clone(),
+        }
+    }
+}
+
+/// Lowercase `snake_case` form of a key: `openaiApiKey` and `X-Api-Key`
+/// become `openai_api_key` and `x_api_key`.
+fn normalize_key(key: &str) -> String {
+    let mut out = String::with_capacity(key.len() + 4);
+    let mut prev_lower = false;
+    for c in key.trim().chars() {
+        if c == '-' || c == '.' || c == ' ' {
+            out.push('_');
+            prev_lower = false;
+        } else if c.is_ascii_uppercase() {
+            if prev_lower {
+                out.push('_');
+            }
+            out.push(c.to_ascii_lowercase());
+            prev_lower = false;
+        } else {
+            out.push(c);
+            prev_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        }
+    }
+    out
+}
+
+/// Redact `text` with the default key patterns.
+pub fn redact(text: &str) -> String {
+    DEFAULT_REDACTOR.redact_str(text)
+}
+
+/// Redact a JSON value with the default key patterns.
+pub fn redact_json(value: &Value) -> Value {
+    DEFAULT_REDACTOR.redact_json(value)
+}
+
+/// Formats the wrapped value with secrets replaced by [`REDACTED`].
+///
+/// Both `Display` and `Debug` (including `{:#?}`) are redacted, so the
+/// wrapper can be used directly as a tracing field: `args = %RedactedValue::new(&args)`.
+pub struct RedactedValue<'r, T> {
+    value: T,
+    redactor: &'r Redactor,
+}
+
+impl<T> RedactedValue<'static, T> {
+    /// Wrap `value`, redacting with the default key patterns.
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            redactor: &DEFAULT_REDACTOR,
+        }
+    }
+}
+
+impl<'r, T> RedactedValue<'r, T> {
+    /// Wrap `value`, redacting with a custom [`Redactor`].
+    pub fn with_redactor(value: T, redactor: &'r Redactor) -> Self {
+        Self { value, redactor }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for RedactedValue<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = if f.alternate() {
+            format!("{:#?}", self.value)
+        } else {
+            format!("{:?}", self.value)
+        };
+        f.write_str(&self.redactor.redact_str(&text))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for RedactedValue<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.redactor.redact_str(&self.value.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Provider {
+        name: String,
+        api_key: Option<String>,
+        max_tokens: u32,
+        fallback: Option<String>,
+    }
+
+    fn provider() -> Provider {
+        Provider {
+            name: "openai".to_string(),
+            api_key: Some("sk-live-abc123".to_string()),
+            max_tokens: 4096,
+            fallback: None,
+        }
+    }
+
+    #[test]
+    fn test_debug_struct_with_api_key() {
+        let out = format!("{:?}", RedactedValue::new(provider()));
+        assert_eq!(
+            out,
+            r#"Provider { name: "openai", api_key: Some("***"), max_tokens: 4096, fallback: None }"#
+        );
+
+        let pretty = format!("{:#?}", RedactedValue::new(provider()));
+        assert!(pretty.contains(r#""***""#));
+        assert!(!pretty.contains("sk-live"));
+    }
+
+    #[test]
+    fn test_provider_config_and_credential_debug() {
+        let config = crate::config::ProviderConfig {
+            api_key: Some("sk-ant-secret".to_string()),
+            ..Default::default()
+        };
+        let out = format!("{:?}", config);
+        assert!(out.contains(r#"api_key: Some("***")"#));
+        assert!(!out.contains("sk-ant-secret"));
+
+        let cred = crate::auth::ResolvedCredential::ApiKey("sk-ant-secret".to_string());
+        assert_eq!(format!("{:?}", cred), r#"ApiKey("***")"#);
+    }
+
+    #[test]
+    fn test_key_matching() {
+        let redactor = Redactor::default();
+        for key in [
+            "api_key",
+            "openaiApiKey",
+            "X-Api-Key",
+            "bot_token",
+            "accessToken",
+            "Authorization",
+            "db_password",
+            "client_secret",
+        ] {
+            assert!(redactor.is_sensitive(key), "{} should be sensitive", key);
+        }
+        for key in ["max_tokens", "token_budget", "session_key", "name", "url"] {
+            assert!(
+                !redactor.is_sensitive(key),
+                "{} should not be sensitive",
+                key
+            );
+        }
+    }
+
+    #[test]
+    fn test_redact_text_formats() {
+        assert_eq!(
+            redact("GET /v1?api_key=abc123&model=gpt-4"),
+            "GET /v1?api_key=***&model=gpt-4"
+        );
+        assert_eq!(
+            redact(r#"{"token": "xyz", "limit": 5}"#),
+            r#"{"token": "***", "limit": 5}"#
+        );
+        assert_eq!(
+            redact("Authorization: Bearer eyJhbGciOi.abc"),
+            "Authorization: ***"
+        );
+        assert_eq!(redact("nothing to hide"), "nothing to hide");
+    }
+
+    #[test]
+    fn test_redact_json_nested() {
+        let value = json!({
+            "url": "https://example.com",
+            "headers": {"Authorization": "Bearer abc", "Accept": "*/*"},
+            "accounts": [{"password": "hunter2", "user": "bob"}],
+            "token": null
+        });
+        let redacted = redact_json(&value);
+        assert_eq!(redacted["url"], "https://example.com");
+        assert_eq!(redacted["headers"]["Authorization"], "***");
+        assert_eq!(redacted["headers"]["Accept"], "*/*");
+        assert_eq!(redacted["accounts"][0]["password"], "***");
+        assert_eq!(redacted["accounts"][0]["user"], "bob");
+        assert!(redacted["token"].is_null());
+    }
+
+    #[test]
+    fn test_custom_patterns() {
+        let redactor = Redactor::new(["pin"]).with_key("otp");
+        let value = json!({"pin": "1234", "otp": "999", "api_key": "visible"});
+        let out = RedactedValue::with_redactor(&value, &redactor).to_string();
+        assert!(out.contains(r#""pin":"***""#));
+        assert!(out.contains(r#""otp":"***""#));
+        assert!(out.contains(r#""api_key":"visible""#));
+    }
+}
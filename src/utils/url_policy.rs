@@ -0,0 +1,367 @@
+//! URL policy shared by every URL-accepting tool.
+//!
+//! [`validate_url`] parses a URL and checks it against a [`UrlPolicy`] built
+//! from `tools.url_policy`: the scheme must be allowed, the host and port
+//! must pass the allow/deny lists, and the host must not be a private,
+//! loopback or link-local address. URLs without a host (`file://`, `data:`)
+//! are always rejected. `web_fetch` and `http_request` call it for the
+//! requested URL and again for every redirect target, so the rules cannot
+//! drift between tools. Both are registered with the configured policy;
+//! `http_request` narrows it with the `tools.http` host lists through
+//! [`UrlPolicy::with_host_rules`], so every host decision is still made here.
+//! `WebScreenshotTool` (`screenshot` feature) checks it too, but
+//! is not registered by the CLI: embedders that build it get
+//! `UrlPolicy::default()` unless they call `with_url_policy`.
+//!
+//! Hostnames are only checked by name here; tools still resolve them with
+//! [`resolve_and_check_host`](crate::tools::resolve_and_check_host) to catch
+//! DNS that points at a private address.
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use reqwest::Url;
+
+use crate::config::UrlPolicyConfig;
+use crate::error::{Result, ZeptoError};
+
+/// Allow/deny rules for URLs opened by tools.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UrlPolicy {
+    allowed_schemes: Vec<String>,
+    /// `None` allows any public host.
+    allowed_hosts: Option<Vec<String>>,
+    denied_hosts: Vec<String>,
+    allowed_ports: Vec<u16>,
+    denied_ports: Vec<u16>,
+}
+
+impl Default for UrlPolicy {
+    /// http/https to any public host and port.
+    fn default() -> Self {
+        Self::from_config(&UrlPolicyConfig::default())
+    }
+}
+
+impl UrlPolicy {
+    /// Build a policy from `tools.url_policy`.
+    pub fn from_config(config: &UrlPolicyConfig) -> Self {
+        let allowed_hosts = normalize(&config.allowed_hosts, "*.");
+        Self {
+            allowed_schemes: normalize(&config.allowed_schemes, ""),
+            allowed_hosts: (!allowed_hosts.is_empty()).then_some(allowed_hosts),
+            denied_hosts: normalize(&config.denied_hosts, "*."),
+            allowed_ports: config.allowed_ports.clone(),
+            denied_ports: config.denied_ports.clone(),
+        }
+    }
+
+    /// Narrow the policy with a tool's own host lists, such as
+    /// `tools.http.allowed_hosts` and `denied_hosts`.
+    ///
+    /// `denied` hosts are added to the denylist. A non-empty `allowed` list
+    /// is intersected with the allowlist, so a host must pass both.
+    pub fn with_host_rules(mut self, allowed: &[String], denied: &[String]) -> Self {
+        for host in normalize(denied, "*.") {
+            if !self.denied_hosts.contains(&host) {
+                self.denied_hosts.push(host);
+            }
+        }
+        let allowed = normalize(allowed, "*.");
+        if !allowed.is_empty() {
+            self.allowed_hosts = Some(match self.allowed_hosts.take() {
+                None => allowed,
+                Some(current) => intersect_hosts(&current, &allowed),
+            });
+        }
+        self
+    }
+
+    /// Check an already-parsed URL, e.g. a redirect target.
+    pub fn check(&self, url: &Url) -> Result<()> {
+        if !self.allowed_schemes.iter().any(|s| s == url.scheme()) {
+            return Err(ZeptoError::Tool(format!(
+                "Only {} URLs are allowed, got '{}'",
+                self.allowed_schemes.join("/"),
+                url.scheme()
+            )));
+        }
+        if is_private_host(url) {
+            return Err(ZeptoError::SecurityViolation(format!(
+                "Blocked URL host (local or private network): {}",
+                url
+            )));
+        }
+
+        let host = url
+            .host_str()
+            .unwrap_or_default()
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .to_ascii_lowercase();
+        if self.denied_hosts.iter().any(|d| host_matches(&host, d)) {
+            return Err(ZeptoError::SecurityViolation(format!(
+                "Host '{}' is in the URL policy's denied_hosts",
+                host
+            )));
+        }
+        if let Some(allowed) = &self.allowed_hosts {
+            if !allowed.iter().any(|a| host_matches(&host, a)) {
+                return Err(ZeptoError::SecurityViolation(format!(
+                    "Host '{}' is not in the URL policy's allowed_hosts",
+                    host
+                )));
+            }
+        }
+
+        if let Some(port) = url.port_or_known_default() {
+            if self.denied_ports.contains(&port)
+                || (!self.allowed_ports.is_empty() && !self.allowed_ports.contains(&port))
+            {
+                return Err(ZeptoError::SecurityViolation(format!(
+                    "Port {} is not allowed by tools.url_policy",
+                    port
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Trim and lowercase `values`, dropping a leading `strip` and empty entries.
+fn normalize(values: &[String], strip: &str) -> Vec<String> {
+    values
+        .iter()
+        .map(|v| v.trim().trim_start_matches(strip).to_ascii_lowercase())
+        .filter(|v| !v.is_empty())
+        .collect()
+}
+
+/// Host patterns matching exactly the hosts matched by both `a` and `b`.
+///
+/// Two patterns overlap only when one is the other or a subdomain of it,
+/// and then the more specific one covers the overlap.
+fn intersect_hosts(a: &[String], b: &[String]) -> Vec<String> {
+    let mut hosts = Vec::new();
+    for x in a {
+        for y in b {
+            if host_matches(x, y) {
+                hosts.push(x.clone());
+            } else if host_matches(y, x) {
+                hosts.push(y.clone());
+            }
+        }
+    }
+    hosts.sort();
+    hosts.dedup();
+    hosts
+}
+
+/// Parse `url` and check it against `policy`.
+pub fn validate_url(url: &str, policy: &UrlPolicy) -> Result<Url> {
+    let parsed =
+        Url::parse(url).map_err(|e| ZeptoError::Tool(format!("Invalid URL '{}': {}", url, e)))?;
+    policy.check(&parsed)?;
+    Ok(parsed)
+}
+
+/// `host` equals `pattern` or is a subdomain of it.
+pub(crate) fn host_matches(host: &str, pattern: &str) -> bool {
+    host == pattern
+        || host
+            .strip_suffix(pattern)
+            .is_some_and(|prefix| prefix.ends_with('.'))
+}
+
+/// Whether a URL has no host or names a local/private one (`localhost`,
+/// `*.local`, or a private, loopback or link-local IP literal).
+pub fn is_private_host(url: &Url) -> bool {
+    let Some(host_str) = url.host_str() else {
+        return true;
+    };
+
+    let host = host_str.to_ascii_lowercase();
+    if host == "localhost" || host.ends_with(".local") {
+        return true;
+    }
+
+    // Try parsing as IP directly first, then try stripping IPv6 brackets.
+    // `Url::host_str()` returns IPv6 addresses with surrounding brackets
+    // (e.g. "[::1]"), which `IpAddr::parse` does not accept.
+    let ip_str = host
+        .strip_prefix('[')
+        .and_then(|s| s.strip_suffix(']'))
+        .unwrap_or(&host);
+    if let Ok(ip) = ip_str.parse::<IpAddr>() {
+        return is_private_or_local_ip(ip);
+    }
+
+    false
+}
+
+/// Whether `ip` is private, loopback, link-local or otherwise not public.
+pub fn is_private_or_local_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(addr) => is_private_or_local_ipv4(addr),
+        IpAddr::V6(addr) => is_private_or_local_ipv6(addr),
+    }
+}
+
+fn is_private_or_local_ipv4(addr: Ipv4Addr) -> bool {
+    addr.is_private()
+        || addr.is_loopback()
+        || addr.is_link_local()
+        || addr.is_broadcast()
+        || addr.is_documentation()
+        || addr.is_unspecified()
+        || addr.octets()[0] == 0
+}
+
+fn is_private_or_local_ipv6(addr: Ipv6Addr) -> bool {
+    let first = addr.segments()[0];
+
+    addr.is_loopback()
+        || addr.is_unspecified()
+        || (first & 0xfe00) == 0xfc00
+        || (first & 0xffc0) == 0xfe80
+        || (first & 0xff00) == 0xff00
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(config: UrlPolicyConfig) -> UrlPolicy {
+        UrlPolicy::from_config(&config)
+    }
+
+    #[test]
+    fn test_rejects_disallowed_schemes() {
+        let default = UrlPolicy::default();
+        for url in [
+            "file:///etc/passwd",
+            "data:text/html,<script>alert(1)</script>",
+            "ftp://example.com/file",
+            "javascript:alert(1)",
+        ] {
+            let err = validate_url(url, &default).unwrap_err().to_string();
+            assert!(
+                err.contains("Only http/https URLs are allowed"),
+                "{url}: {err}"
+            );
+        }
+
+        // Even an allowed scheme needs a host
+        let with_file = policy(UrlPolicyConfig {
+            allowed_schemes: vec!["https".into(), "file".into()],
+            ..Default::default()
+        });
+        assert!(validate_url("file:///etc/passwd", &with_file).is_err());
+        assert!(validate_url("not a url", &default).is_err());
+    }
+
+    #[test]
+    fn test_rejects_private_and_link_local_hosts() {
+        let default = UrlPolicy::default();
+        for url in [
+            "http://localhost:8080/",
+            "http://127.0.0.1/",
+            "http://10.1.2.3/",
+            "http://192.168.0.1/",
+            "http://169.254.169.254/latest/meta-data/",
+            "http://[::1]/",
+            "http://[fe80::1]/",
+            "http://printer.local/",
+        ] {
+            let err = validate_url(url, &default).unwrap_err();
+            assert!(
+                matches!(err, ZeptoError::SecurityViolation(_)),
+                "{url}: {err}"
+            );
+        }
+
+        // The allowlist cannot open up private addresses
+        let allow_private = policy(UrlPolicyConfig {
+            allowed_hosts: vec!["127.0.0.1".into()],
+            ..Default::default()
+        });
+        assert!(validate_url("http://127.0.0.1/", &allow_private).is_err());
+    }
+
+    #[test]
+    fn test_allowlisted_host_passes() {
+        let policy = policy(UrlPolicyConfig {
+            allowed_hosts: vec!["*.example.com".into()],
+            denied_hosts: vec!["internal.example.com".into()],
+            ..Default::default()
+        });
+
+        let url = validate_url("https://api.example.com/v1?q=1", &policy).unwrap();
+        assert_eq!(url.host_str(), Some("api.example.com"));
+        assert!(validate_url("https://example.com/", &policy).is_ok());
+
+        let err = validate_url("https://example.org/", &policy).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("not in the URL policy's allowed_hosts"));
+        let err = validate_url("https://evilexample.com/", &policy).unwrap_err();
+        assert!(err.to_string().contains("allowed_hosts"));
+        let err = validate_url("https://internal.example.com/", &policy).unwrap_err();
+        assert!(err.to_string().contains("denied_hosts"));
+    }
+
+    #[test]
+    fn test_host_rules_narrow_the_policy() {
+        let tool_hosts = |policy: UrlPolicy| {
+            policy.with_host_rules(
+                &["*.example.com".into(), "github.com".into()],
+                &["metadata.google.internal".into()],
+            )
+        };
+
+        // Without a shared allowlist the tool's list applies as is.
+        let open = tool_hosts(UrlPolicy::default());
+        assert!(validate_url("https://api.example.com/", &open).is_ok());
+        assert!(validate_url("https://github.com/", &open).is_ok());
+        assert!(validate_url("https://other.org/", &open).is_err());
+        let err = validate_url("http://metadata.google.internal/", &open).unwrap_err();
+        assert!(err.to_string().contains("denied_hosts"));
+
+        // With one, a host must be on both lists.
+        let shared = tool_hosts(policy(UrlPolicyConfig {
+            allowed_hosts: vec!["api.example.com".into(), "gitlab.com".into()],
+            ..Default::default()
+        }));
+        assert!(validate_url("https://api.example.com/", &shared).is_ok());
+        assert!(validate_url("https://v2.api.example.com/", &shared).is_ok());
+        assert!(validate_url("https://www.example.com/", &shared).is_err());
+        assert!(validate_url("https://github.com/", &shared).is_err());
+        assert!(validate_url("https://gitlab.com/", &shared).is_err());
+
+        // Disjoint lists allow nothing rather than everything.
+        let disjoint = policy(UrlPolicyConfig {
+            allowed_hosts: vec!["example.org".into()],
+            ..Default::default()
+        })
+        .with_host_rules(&["example.com".into()], &[]);
+        assert!(validate_url("https://example.org/", &disjoint).is_err());
+        assert!(validate_url("https://example.com/", &disjoint).is_err());
+    }
+
+    #[test]
+    fn test_port_rules() {
+        let allowed = policy(UrlPolicyConfig {
+            allowed_ports: vec![443, 8443],
+            ..Default::default()
+        });
+        assert!(validate_url("https://example.com/", &allowed).is_ok());
+        assert!(validate_url("https://example.com:8443/", &allowed).is_ok());
+        assert!(validate_url("http://example.com/", &allowed).is_err());
+
+        let denied = policy(UrlPolicyConfig {
+            denied_ports: vec![25],
+            ..Default::default()
+        });
+        assert!(validate_url("http://example.com:25/", &denied).is_err());
+        assert!(validate_url("http://example.com:8080/", &denied).is_ok());
+    }
+}
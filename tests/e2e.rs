@@ -213,6 +213,7 @@ impl LLMProvider for MockTokenCountingProvider {
                 prompt_tokens: 500,
                 completion_tokens: 200,
                 total_tokens: 700,
+                model: None,
             }),
         })
     }